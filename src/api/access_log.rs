@@ -0,0 +1,147 @@
+//! Request IDs and per-request access logging.
+//!
+//! Every request gets an `x-request-id` (a client-supplied one is kept) that
+//! is echoed on the response and recorded on the request's tracing span, so
+//! anything logged while handling the request carries the same id.
+
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::http::{HeaderName, Request, Response};
+use axum::Router;
+use tower::ServiceBuilder;
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+use tower_http::trace::TraceLayer;
+use tracing::Span;
+
+/// Header carrying the request id on requests and responses.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Wrap `router` with request-id assignment, propagation and access logging.
+pub fn layer<S>(router: Router<S>) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let header = HeaderName::from_static(REQUEST_ID_HEADER);
+    router.layer(
+        ServiceBuilder::new()
+            .layer(SetRequestIdLayer::new(header.clone(), MakeRequestUuid))
+            .layer(
+                TraceLayer::new_for_http()
+                    .make_span_with(make_span)
+                    .on_request(())
+                    .on_response(log_response),
+            )
+            .layer(PropagateRequestIdLayer::new(header)),
+    )
+}
+
+/// Span for one request; the id was set by the outer layer.
+fn make_span(request: &Request<Body>) -> Span {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("-");
+    tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %request.method(),
+        path = %request.uri().path(),
+    )
+}
+
+/// One access log line per response, inside the request span.
+fn log_response<B>(response: &Response<B>, latency: Duration, _span: &Span) {
+    tracing::info!(
+        status = response.status().as_u16(),
+        latency_ms = latency.as_secs_f64() * 1000.0,
+        "request completed"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    /// Log sink shared with the test subscriber.
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Captured {
+        fn text(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    /// Serve a test route on an ephemeral port and GET it with `headers`.
+    async fn get_teapot(headers: &[(&str, &str)]) -> reqwest::Response {
+        let app = layer(Router::new().route(
+            "/teapot",
+            get(|| async {
+                tracing::info!("brewing");
+                StatusCode::IM_A_TEAPOT
+            }),
+        ));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let mut request = reqwest::Client::new().get(format!("http://{}/teapot", addr));
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        request.send().await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_request_id_assigned_and_logged() {
+        let logs = Captured::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let response = get_teapot(&[]).await;
+        assert_eq!(response.status(), reqwest::StatusCode::IM_A_TEAPOT);
+        let request_id = response.headers()[REQUEST_ID_HEADER].to_str().unwrap();
+        assert!(uuid::Uuid::parse_str(request_id).is_ok());
+
+        let logs = logs.text();
+        let access = logs
+            .lines()
+            .find(|l| l.contains("request completed"))
+            .expect("access log line");
+        assert!(access.contains("status=418"));
+        assert!(access.contains("method=GET"));
+        assert!(access.contains("path=/teapot"));
+        assert!(access.contains(request_id));
+
+        // Handler logs carry the same id through the span
+        let handler = logs.lines().find(|l| l.contains("brewing")).unwrap();
+        assert!(handler.contains(request_id));
+    }
+
+    #[tokio::test]
+    async fn test_client_request_id_is_kept() {
+        let response = get_teapot(&[(REQUEST_ID_HEADER, "client-abc-123")]).await;
+        assert_eq!(response.headers()[REQUEST_ID_HEADER], "client-abc-123");
+    }
+}
@@ -8,9 +8,16 @@
 //! 3. GET /api/auth/me - Get current user profile (requires auth)
 //! 4. PUT /api/auth/profile - Update profile settings (requires auth)
 //! 5. POST /api/auth/logout - Logout and invalidate session
+//!
+//! Also:
+//! - PUT /api/auth/profile/details - Update bio and avatar (requires auth)
+//! - GET /api/auth/users/:public_key - Public profile for any user
+//! - POST/DELETE /api/auth/follow/:public_key - Follow or unfollow a user (requires auth)
+//! - GET /api/auth/follow/following - Users you follow (requires auth)
+//! - GET /api/auth/follow/followers - Users following you (requires auth)
 
 use axum::{
-    extract::{FromRequestParts, State},
+    extract::{FromRequestParts, Path, State},
     http::request::Parts,
     routing::{get, post, put},
     Json, Router,
@@ -19,7 +26,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::services::AuthError;
 use crate::types::{
-    AuthChallenge, AuthRequest, AuthResponse, AuthenticatedUser, Profile, ProfileSettings,
+    AuthChallenge, AuthRequest, AuthResponse, AuthenticatedUser, Profile, ProfileDetailsRequest,
+    ProfileSettings, PublicProfile,
 };
 use crate::AppState;
 
@@ -30,7 +38,12 @@ pub fn router() -> Router<AppState> {
         .route("/verify", post(verify))
         .route("/me", get(get_me))
         .route("/profile", put(update_profile))
+        .route("/profile/details", put(update_profile_details))
         .route("/profile/leaderboard", post(update_leaderboard_visibility))
+        .route("/users/:public_key", get(get_public_profile))
+        .route("/follow/following", get(get_following))
+        .route("/follow/followers", get(get_followers))
+        .route("/follow/:public_key", post(follow).delete(unfollow))
         .route("/logout", post(logout))
 }
 
@@ -87,6 +100,133 @@ async fn update_profile(
     Ok(Json(ApiResponse { data: updated }))
 }
 
+/// PUT /api/auth/profile/details
+///
+/// Update the authenticated user's bio and avatar URL.
+async fn update_profile_details(
+    State(state): State<AppState>,
+    auth: Authenticated,
+    Json(request): Json<ProfileDetailsRequest>,
+) -> Result<Json<ApiResponse<Profile>>, AuthError> {
+    let updated = state
+        .auth_service
+        .update_profile_details(auth.user.profile, request.bio, request.avatar_url)
+        .await?;
+
+    Ok(Json(ApiResponse { data: updated }))
+}
+
+/// GET /api/auth/users/:public_key
+///
+/// Get the public view of a user's profile.
+async fn get_public_profile(
+    State(state): State<AppState>,
+    Path(public_key): Path<String>,
+) -> Result<Json<ApiResponse<PublicProfile>>, AuthError> {
+    let profile = state
+        .auth_service
+        .get_profile(&public_key)
+        .await
+        .ok_or(AuthError::ProfileNotFound)?;
+
+    Ok(Json(ApiResponse {
+        data: PublicProfile::from(&profile),
+    }))
+}
+
+/// POST /api/auth/follow/:public_key
+///
+/// Follow another user. Following someone you already follow is a no-op.
+async fn follow(
+    State(state): State<AppState>,
+    auth: Authenticated,
+    Path(public_key): Path<String>,
+) -> Result<Json<ApiResponse<FollowResponse>>, AuthError> {
+    if public_key == auth.user.public_key {
+        return Err(AuthError::SelfFollow);
+    }
+    state
+        .auth_service
+        .get_profile(&public_key)
+        .await
+        .ok_or(AuthError::ProfileNotFound)?;
+
+    state
+        .sqlite_store
+        .follow(&auth.user.public_key, &public_key)
+        .map_err(|e| {
+            tracing::warn!("Failed to save follow: {}", e);
+            AuthError::Storage(e.to_string())
+        })?;
+
+    Ok(Json(ApiResponse {
+        data: FollowResponse {
+            following: true,
+            follower_count: state.sqlite_store.follower_count(&public_key),
+        },
+    }))
+}
+
+/// DELETE /api/auth/follow/:public_key
+///
+/// Unfollow a user. Unfollowing someone you don't follow is a no-op.
+async fn unfollow(
+    State(state): State<AppState>,
+    auth: Authenticated,
+    Path(public_key): Path<String>,
+) -> Result<Json<ApiResponse<FollowResponse>>, AuthError> {
+    state
+        .sqlite_store
+        .unfollow(&auth.user.public_key, &public_key)
+        .map_err(|e| {
+            tracing::warn!("Failed to remove follow: {}", e);
+            AuthError::Storage(e.to_string())
+        })?;
+
+    Ok(Json(ApiResponse {
+        data: FollowResponse {
+            following: false,
+            follower_count: state.sqlite_store.follower_count(&public_key),
+        },
+    }))
+}
+
+/// GET /api/auth/follow/following
+///
+/// List the users the authenticated user follows, most recent first.
+async fn get_following(
+    State(state): State<AppState>,
+    auth: Authenticated,
+) -> Json<ApiResponse<Vec<PublicProfile>>> {
+    let keys = state.sqlite_store.get_following(&auth.user.public_key);
+    Json(ApiResponse {
+        data: public_profiles(&state, &keys).await,
+    })
+}
+
+/// GET /api/auth/follow/followers
+///
+/// List the users following the authenticated user, most recent first.
+async fn get_followers(
+    State(state): State<AppState>,
+    auth: Authenticated,
+) -> Json<ApiResponse<Vec<PublicProfile>>> {
+    let keys = state.sqlite_store.get_followers(&auth.user.public_key);
+    Json(ApiResponse {
+        data: public_profiles(&state, &keys).await,
+    })
+}
+
+async fn public_profiles(state: &AppState, public_keys: &[String]) -> Vec<PublicProfile> {
+    state
+        .auth_service
+        .get_profiles(public_keys)
+        .await
+        .iter()
+        .map(PublicProfile::from)
+        .collect()
+}
+
 /// POST /api/auth/profile/leaderboard
 ///
 /// Opt in or out of the public leaderboard.
@@ -166,6 +306,16 @@ pub struct LeaderboardConsentRequest {
     pub timestamp: i64,
 }
 
+/// Response for follow/unfollow.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FollowResponse {
+    /// Whether the caller now follows the user
+    pub following: bool,
+    /// The user's follower count after the change
+    pub follower_count: usize,
+}
+
 /// Authenticated user extractor.
 ///
 /// Use this in route handlers to require authentication:
@@ -1,5 +1,5 @@
 use crate::error::{AppError, Result};
-use crate::types::{AssetListing, ChartData, ChartRange, Quote};
+use crate::types::{AssetListing, ChartData, ChartRange, PriceSource, Quote};
 use crate::AppState;
 use axum::{
     extract::{Path, Query, State},
@@ -7,15 +7,16 @@ use axum::{
     Json, Router,
 };
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
 /// API response wrapper matching frontend expectations
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ApiResponse<T> {
     pub data: T,
     pub meta: ApiMeta,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ApiMeta {
     pub cached: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -61,7 +62,7 @@ impl ApiMeta {
 }
 
 /// Sort field options for listings.
-#[derive(Debug, Deserialize, Clone, Copy, Default)]
+#[derive(Debug, Deserialize, Clone, Copy, Default, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum SortField {
     #[default]
@@ -75,7 +76,7 @@ pub enum SortField {
 }
 
 /// Sort direction.
-#[derive(Debug, Deserialize, Clone, Copy, Default)]
+#[derive(Debug, Deserialize, Clone, Copy, Default, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum SortDirection {
     Asc,
@@ -84,7 +85,7 @@ pub enum SortDirection {
 }
 
 /// Filter options for listings.
-#[derive(Debug, Deserialize, Clone, Copy)]
+#[derive(Debug, Deserialize, Clone, Copy, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ListingFilter {
     All,
@@ -95,7 +96,7 @@ pub enum ListingFilter {
 }
 
 /// Asset type for filtering.
-#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum AssetType {
     #[default]
@@ -107,7 +108,8 @@ pub enum AssetType {
     Commodity,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListingsQuery {
     start: Option<i32>,
     limit: Option<i32>,
@@ -121,18 +123,38 @@ pub struct ListingsQuery {
     max_change: Option<f64>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct SearchQuery {
     q: String,
     limit: Option<i32>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ChartQuery {
     range: Option<String>,
+    /// Custom bucket size in seconds (resampled from stored resolutions).
+    interval: Option<i64>,
+    /// Candle style: "candles" (default) or "heikin_ashi".
+    style: Option<String>,
 }
 
 /// GET /api/crypto/listings
+#[utoipa::path(
+    get,
+    path = "/api/crypto/listings",
+    tag = "crypto",
+    params(ListingsQuery),
+    responses(
+        (
+            status = 200,
+            description = "Paginated asset listings",
+            body = ApiResponse<Vec<AssetListing>>
+        ),
+        (status = 503, description = "Stock or ETF listings requested without Finnhub")
+    )
+)]
 async fn get_listings(
     State(state): State<AppState>,
     Query(params): Query<ListingsQuery>,
@@ -142,6 +164,11 @@ async fn get_listings(
     let page = ((start - 1) / limit) + 1;
     let asset_type = params.asset_type.unwrap_or_default();
 
+    // Stock and ETF listings come only from Finnhub
+    if matches!(asset_type, AssetType::Stock | AssetType::Etf) {
+        state.source_capabilities.require(PriceSource::Finnhub)?;
+    }
+
     // Use asset service for unified listings
     let (mut data, total) = state
         .asset_service
@@ -265,6 +292,19 @@ async fn get_listings(
 }
 
 /// GET /api/crypto/search
+#[utoipa::path(
+    get,
+    path = "/api/crypto/search",
+    tag = "crypto",
+    params(SearchQuery),
+    responses(
+        (
+            status = 200,
+            description = "Assets matching the query",
+            body = ApiResponse<Vec<AssetListing>>
+        )
+    )
+)]
 async fn search(
     State(state): State<AppState>,
     Query(params): Query<SearchQuery>,
@@ -281,6 +321,16 @@ async fn search(
 }
 
 /// GET /api/crypto/:id
+#[utoipa::path(
+    get,
+    path = "/api/crypto/{id}",
+    tag = "crypto",
+    params(("id" = i64, Path, description = "Asset ID")),
+    responses(
+        (status = 200, description = "Asset details", body = ApiResponse<AssetListing>),
+        (status = 404, description = "Asset not found")
+    )
+)]
 async fn get_asset(
     State(state): State<AppState>,
     Path(id): Path<i64>,
@@ -352,6 +402,16 @@ async fn get_asset(
 }
 
 /// GET /api/crypto/:id/quotes
+#[utoipa::path(
+    get,
+    path = "/api/crypto/{id}/quotes",
+    tag = "crypto",
+    params(("id" = i64, Path, description = "Asset ID")),
+    responses(
+        (status = 200, description = "Latest quote", body = ApiResponse<Quote>),
+        (status = 404, description = "Asset or quote not found")
+    )
+)]
 async fn get_quotes(
     State(state): State<AppState>,
     Path(id): Path<i64>,
@@ -395,6 +455,17 @@ async fn get_quotes(
 }
 
 /// GET /api/crypto/:id/chart
+#[utoipa::path(
+    get,
+    path = "/api/crypto/{id}/chart",
+    tag = "crypto",
+    params(("id" = i64, Path, description = "Asset ID"), ChartQuery),
+    responses(
+        (status = 200, description = "OHLC chart data", body = ApiResponse<ChartData>),
+        (status = 400, description = "Invalid range, interval or style"),
+        (status = 404, description = "Asset not found")
+    )
+)]
 async fn get_chart(
     State(state): State<AppState>,
     Path(id): Path<i64>,
@@ -413,8 +484,29 @@ async fn get_chart(
         return Err(AppError::NotFound(format!("Asset {} not found", id)));
     };
 
-    // Get current chart data
-    let data = state.chart_store.get_chart(&symbol, range);
+    let heikin_ashi = match params.style.as_deref() {
+        None | Some("candles") => false,
+        Some("heikin_ashi") => true,
+        Some(other) => return Err(AppError::BadRequest(format!("Invalid style: {}", other))),
+    };
+
+    // Get current chart data, resampled if a custom interval was requested
+    let data = match params.interval {
+        Some(interval) => {
+            let end = chrono::Utc::now().timestamp();
+            let points = state
+                .chart_store
+                .get_chart_resampled(&symbol, end - range.duration_seconds(), end, interval)
+                .map_err(|e| AppError::BadRequest(e.to_string()))?;
+            if heikin_ashi {
+                crate::services::chart_store::heikin_ashi(&points)
+            } else {
+                points
+            }
+        }
+        None if heikin_ashi => state.chart_store.get_chart_heikin_ashi(&symbol, range),
+        None => state.chart_store.get_chart(&symbol, range),
+    };
 
     // Get current seeding status
     let status = state.historical_service.get_seed_status(&symbol);
@@ -515,19 +607,19 @@ async fn get_chart(
 }
 
 /// Request body for seeding a single symbol.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct SeedRequest {
     symbol: String,
 }
 
 /// Request body for batch seeding.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct BatchSeedRequest {
     symbols: Vec<String>,
 }
 
 /// Response for seed status.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct SeedResponse {
     symbol: String,
     status: String,
@@ -535,6 +627,19 @@ pub struct SeedResponse {
 }
 
 /// POST /api/crypto/seed - Trigger historical data seeding for a symbol
+#[utoipa::path(
+    post,
+    path = "/api/crypto/seed",
+    tag = "crypto",
+    request_body = SeedRequest,
+    responses(
+        (
+            status = 200,
+            description = "Seeding status for the symbol",
+            body = ApiResponse<SeedResponse>
+        )
+    )
+)]
 async fn seed_symbol(
     State(state): State<AppState>,
     Json(req): Json<SeedRequest>,
@@ -580,6 +685,19 @@ async fn seed_symbol(
 }
 
 /// POST /api/crypto/seed/batch - Trigger historical data seeding for multiple symbols
+#[utoipa::path(
+    post,
+    path = "/api/crypto/seed/batch",
+    tag = "crypto",
+    request_body = BatchSeedRequest,
+    responses(
+        (
+            status = 200,
+            description = "Seeding status per symbol",
+            body = ApiResponse<Vec<SeedResponse>>
+        )
+    )
+)]
 async fn seed_batch(
     State(state): State<AppState>,
     Json(req): Json<BatchSeedRequest>,
@@ -625,6 +743,18 @@ async fn seed_batch(
 }
 
 /// GET /api/crypto/seed/status - Get seeding status for all known symbols
+#[utoipa::path(
+    get,
+    path = "/api/crypto/seed/status",
+    tag = "crypto",
+    responses(
+        (
+            status = 200,
+            description = "Seeding status for common symbols",
+            body = ApiResponse<Vec<SeedResponse>>
+        )
+    )
+)]
 async fn seed_status(
     State(state): State<AppState>,
 ) -> Result<Json<ApiResponse<Vec<SeedResponse>>>> {
@@ -921,6 +1051,21 @@ mod tests {
         let json = r#"{}"#;
         let query: ChartQuery = serde_json::from_str(json).unwrap();
         assert!(query.range.is_none());
+        assert!(query.interval.is_none());
+    }
+
+    #[test]
+    fn test_chart_query_with_interval() {
+        let json = r#"{"range": "1d", "interval": 1800}"#;
+        let query: ChartQuery = serde_json::from_str(json).unwrap();
+        assert_eq!(query.interval, Some(1800));
+    }
+
+    #[test]
+    fn test_chart_query_with_style() {
+        let json = r#"{"range": "1w", "style": "heikin_ashi"}"#;
+        let query: ChartQuery = serde_json::from_str(json).unwrap();
+        assert_eq!(query.style.as_deref(), Some("heikin_ashi"));
     }
 
     // =========================================================================
@@ -0,0 +1,65 @@
+//! Developer API endpoints for inspecting server internals.
+
+use std::collections::HashMap;
+
+use axum::{extract::State, routing::get, Json, Router};
+use serde::Serialize;
+
+use crate::api::auth::Authenticated;
+use crate::services::source_capabilities::SourceCapability;
+use crate::AppState;
+
+/// Response for WebSocket room presence.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoomsResponse {
+    /// Subscribed clients per asset symbol.
+    pub rooms: HashMap<String, usize>,
+    /// Number of connected WebSocket clients.
+    pub total_connections: usize,
+    /// Number of asset rooms with at least one subscriber.
+    pub total_rooms: usize,
+    /// Sum of subscriptions across all rooms.
+    pub total_subscriptions: usize,
+    /// Timestamp of this response.
+    pub timestamp: i64,
+}
+
+/// Get per-room WebSocket subscriber counts. Requires a session, since the
+/// counts reveal which assets connected clients are watching.
+async fn get_rooms(State(state): State<AppState>, _auth: Authenticated) -> Json<RoomsResponse> {
+    let rooms = state.room_manager.subscriber_counts();
+
+    Json(RoomsResponse {
+        total_connections: state.room_manager.client_count(),
+        total_rooms: rooms.len(),
+        total_subscriptions: rooms.values().sum(),
+        rooms,
+        timestamp: chrono::Utc::now().timestamp_millis(),
+    })
+}
+
+/// Response for configured data sources.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourcesResponse {
+    /// Every known source with whether it is enabled and when it last updated.
+    pub sources: Vec<SourceCapability>,
+    /// Timestamp of this response.
+    pub timestamp: i64,
+}
+
+/// Get the capability report for each data source.
+async fn get_sources(State(state): State<AppState>) -> Json<SourcesResponse> {
+    Json(SourcesResponse {
+        sources: state.source_capabilities.report(&state.price_cache),
+        timestamp: chrono::Utc::now().timestamp_millis(),
+    })
+}
+
+/// Create the developer router.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/rooms", get(get_rooms))
+        .route("/sources", get(get_sources))
+}
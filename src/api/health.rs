@@ -1,5 +1,6 @@
+use crate::services::health::HealthReport;
 use crate::AppState;
-use axum::{routing::get, Json, Router};
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
 use serde::Serialize;
 
 #[derive(Serialize)]
@@ -15,8 +16,22 @@ async fn health() -> Json<HealthResponse> {
     })
 }
 
+/// Readiness probe: checks each dependency and returns 503 when a critical
+/// one is down. Liveness (`/api/health`) stays cheap and always answers ok.
+async fn ready(State(state): State<AppState>) -> (StatusCode, Json<HealthReport>) {
+    let report = state.health_service.report().await;
+    let status = if report.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report))
+}
+
 pub fn router() -> Router<AppState> {
-    Router::new().route("/api/health", get(health))
+    Router::new()
+        .route("/api/health", get(health))
+        .route("/api/health/ready", get(ready))
 }
 
 #[cfg(test)]
@@ -52,4 +67,29 @@ mod tests {
         assert_eq!(response.status, "ok");
         assert_eq!(response.version, env!("CARGO_PKG_VERSION"));
     }
+
+    #[tokio::test]
+    async fn test_liveness_ok_with_down_redis() {
+        use crate::services::health::HealthStatus;
+        use crate::services::{redis_store, HealthService, PriceCache, SqliteStore};
+        use crate::types::AggregationConfig;
+        use std::sync::Arc;
+
+        // Nothing listens on port 1, so Redis never connects
+        let redis = redis_store::connect_shared("redis://127.0.0.1:1").await;
+        let (price_cache, _rx) = PriceCache::new(AggregationConfig::default());
+        let sqlite = Arc::new(SqliteStore::new_in_memory().unwrap());
+        let service = HealthService::new(redis, sqlite, price_cache, None);
+
+        let report = service.report().await;
+        let redis = report
+            .components
+            .iter()
+            .find(|c| c.name == "redis")
+            .unwrap();
+        assert_eq!(redis.status, HealthStatus::Unhealthy);
+
+        let Json(response) = health().await;
+        assert_eq!(response.status, "ok");
+    }
 }
@@ -1,6 +1,11 @@
-use crate::error::Result;
+use crate::error::{AppError, Result};
+use crate::services::basket::{self, BasketComponent};
 use crate::services::price_cache::{ExchangeStats, SymbolConfidence, SymbolSourceStat};
-use crate::types::{FearGreedData, GlobalMetrics, MoverTimeframe, MoversResponse};
+use crate::services::ratio;
+use crate::types::{
+    ChartRange, FearGreedData, GlobalMetrics, MoverTimeframe, MoversResponse, OhlcPoint,
+    PriceSource,
+};
 use crate::AppState;
 use axum::{
     extract::{Path, Query, State},
@@ -8,9 +13,10 @@ use axum::{
     Json, Router,
 };
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
 /// Stats response for total updates tracked.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct StatsResponse {
     pub total_updates: u64,
@@ -29,13 +35,14 @@ pub struct StatsResponse {
 }
 
 /// API response wrapper matching frontend expectations
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ApiResponse<T> {
     pub data: T,
     pub meta: ApiMeta,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
+#[schema(as = MarketMeta)]
 pub struct ApiMeta {
     pub cached: bool,
 }
@@ -47,7 +54,17 @@ impl ApiMeta {
 }
 
 /// GET /api/market/global
+#[utoipa::path(
+    get,
+    path = "/api/market/global",
+    tag = "market",
+    responses(
+        (status = 200, description = "Global market metrics", body = ApiResponse<GlobalMetrics>),
+        (status = 503, description = "CoinMarketCap is disabled")
+    )
+)]
 async fn get_global(State(state): State<AppState>) -> Result<Json<ApiResponse<GlobalMetrics>>> {
+    state.source_capabilities.require(PriceSource::CoinMarketCap)?;
     let metrics = state.cmc_client.get_global_metrics().await?;
     Ok(Json(ApiResponse {
         data: metrics,
@@ -56,6 +73,14 @@ async fn get_global(State(state): State<AppState>) -> Result<Json<ApiResponse<Gl
 }
 
 /// GET /api/market/fear-greed
+#[utoipa::path(
+    get,
+    path = "/api/market/fear-greed",
+    tag = "market",
+    responses(
+        (status = 200, description = "Fear and greed index", body = ApiResponse<FearGreedData>)
+    )
+)]
 async fn get_fear_greed(State(state): State<AppState>) -> Result<Json<ApiResponse<FearGreedData>>> {
     let data = state.cmc_client.get_fear_greed().await?;
     Ok(Json(ApiResponse {
@@ -65,6 +90,18 @@ async fn get_fear_greed(State(state): State<AppState>) -> Result<Json<ApiRespons
 }
 
 /// GET /api/market/exchanges
+#[utoipa::path(
+    get,
+    path = "/api/market/exchanges",
+    tag = "market",
+    responses(
+        (
+            status = 200,
+            description = "Per-exchange update statistics",
+            body = ApiResponse<Vec<ExchangeStats>>
+        )
+    )
+)]
 async fn get_exchanges(
     State(state): State<AppState>,
 ) -> Result<Json<ApiResponse<Vec<ExchangeStats>>>> {
@@ -76,6 +113,18 @@ async fn get_exchanges(
 }
 
 /// GET /api/market/stats
+#[utoipa::path(
+    get,
+    path = "/api/market/stats",
+    tag = "market",
+    responses(
+        (
+            status = 200,
+            description = "Aggregate update statistics",
+            body = ApiResponse<StatsResponse>
+        )
+    )
+)]
 async fn get_stats(State(state): State<AppState>) -> Result<Json<ApiResponse<StatsResponse>>> {
     let total_updates = state.price_cache.get_total_updates();
     let tps = state.price_cache.get_tps();
@@ -100,7 +149,7 @@ async fn get_stats(State(state): State<AppState>) -> Result<Json<ApiResponse<Sta
 }
 
 /// Response for per-symbol source statistics.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SymbolSourceStatsResponse {
     pub symbol: String,
@@ -110,6 +159,19 @@ pub struct SymbolSourceStatsResponse {
 }
 
 /// GET /api/market/source-stats/:symbol
+#[utoipa::path(
+    get,
+    path = "/api/market/source-stats/{symbol}",
+    tag = "market",
+    params(("symbol" = String, Path, description = "Asset symbol, e.g. btc")),
+    responses(
+        (
+            status = 200,
+            description = "Per-source statistics for the symbol",
+            body = ApiResponse<SymbolSourceStatsResponse>
+        )
+    )
+)]
 async fn get_symbol_source_stats(
     State(state): State<AppState>,
     Path(symbol): Path<String>,
@@ -130,7 +192,7 @@ async fn get_symbol_source_stats(
 }
 
 /// Response for symbol confidence.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ConfidenceResponse {
     pub symbol: String,
@@ -141,6 +203,19 @@ pub struct ConfidenceResponse {
 }
 
 /// GET /api/market/confidence/:symbol
+#[utoipa::path(
+    get,
+    path = "/api/market/confidence/{symbol}",
+    tag = "market",
+    params(("symbol" = String, Path, description = "Asset symbol, e.g. btc")),
+    responses(
+        (
+            status = 200,
+            description = "Price confidence for the symbol",
+            body = ApiResponse<ConfidenceResponse>
+        )
+    )
+)]
 async fn get_symbol_confidence(
     State(state): State<AppState>,
     Path(symbol): Path<String>,
@@ -163,10 +238,13 @@ async fn get_symbol_confidence(
 }
 
 /// Query params for movers endpoint.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct MoversQuery {
+    /// Mover timeframe: "1m", "5m", "15m", "1h", "4h" or "24h"
     #[serde(default)]
     pub timeframe: Option<String>,
+    /// Maximum gainers and losers to return (1-50, default 10)
     #[serde(default = "default_limit")]
     pub limit: usize,
     /// Asset type filter: "all", "crypto", "stock"
@@ -179,6 +257,15 @@ fn default_limit() -> usize {
 }
 
 /// GET /api/market/movers
+#[utoipa::path(
+    get,
+    path = "/api/market/movers",
+    tag = "market",
+    params(MoversQuery),
+    responses(
+        (status = 200, description = "Top gainers and losers", body = ApiResponse<MoversResponse>)
+    )
+)]
 async fn get_movers(
     State(state): State<AppState>,
     Query(query): Query<MoversQuery>,
@@ -251,6 +338,141 @@ async fn get_movers(
     }))
 }
 
+/// Query params for the basket endpoint.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BasketQuery {
+    /// Comma-separated `symbol:weight` pairs, e.g. "btc:0.5,eth:0.5". Weights
+    /// default to 1 and are normalized to sum to 1.
+    pub symbols: String,
+    /// Chart range: "1h", "4h", "1d", "1w" or "1m" (default "1d")
+    #[serde(default)]
+    pub range: Option<String>,
+}
+
+/// Synthetic basket chart response.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BasketResponse {
+    pub components: Vec<BasketComponent>,
+    pub range: String,
+    /// Basket value at the first bucket of the range.
+    pub base_value: f64,
+    pub data: Vec<OhlcPoint>,
+}
+
+/// GET /api/market/basket
+#[utoipa::path(
+    get,
+    path = "/api/market/basket",
+    tag = "market",
+    params(BasketQuery),
+    responses(
+        (
+            status = 200,
+            description = "Weighted basket price normalized to the range start",
+            body = ApiResponse<BasketResponse>
+        ),
+        (status = 400, description = "Invalid basket or range")
+    )
+)]
+async fn get_basket(
+    State(state): State<AppState>,
+    Query(query): Query<BasketQuery>,
+) -> Result<Json<ApiResponse<BasketResponse>>> {
+    let range_str = query.range.as_deref().unwrap_or("1d");
+    let range = ChartRange::parse(range_str)
+        .ok_or_else(|| AppError::BadRequest(format!("Invalid range: {}", range_str)))?;
+    let components =
+        basket::parse_basket(&query.symbols).map_err(|e| AppError::BadRequest(e.to_string()))?;
+
+    let data = basket::basket_chart(&state.chart_store, &components, range);
+
+    Ok(Json(ApiResponse {
+        data: BasketResponse {
+            components,
+            range: range_str.to_string(),
+            base_value: basket::BASKET_BASE_VALUE,
+            data,
+        },
+        meta: ApiMeta::simple(),
+    }))
+}
+
+/// Query params for the ratio endpoint.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RatioQuery {
+    /// Numerator symbol, e.g. "eth"
+    pub num: String,
+    /// Denominator symbol, e.g. "btc"
+    pub den: String,
+    /// Chart range: "1h", "4h", "1d", "1w" or "1m" (default "1d")
+    #[serde(default)]
+    pub range: Option<String>,
+}
+
+/// Synthetic ratio chart response.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RatioResponse {
+    /// Ratio symbol, e.g. "eth/btc".
+    pub symbol: String,
+    pub numerator: String,
+    pub denominator: String,
+    pub range: String,
+    pub data: Vec<OhlcPoint>,
+}
+
+/// GET /api/market/ratio
+#[utoipa::path(
+    get,
+    path = "/api/market/ratio",
+    tag = "market",
+    params(RatioQuery),
+    responses(
+        (
+            status = 200,
+            description = "Numerator price divided by denominator price per bucket",
+            body = ApiResponse<RatioResponse>
+        ),
+        (status = 400, description = "Invalid symbols or range")
+    )
+)]
+async fn get_ratio(
+    State(state): State<AppState>,
+    Query(query): Query<RatioQuery>,
+) -> Result<Json<ApiResponse<RatioResponse>>> {
+    let range_str = query.range.as_deref().unwrap_or("1d");
+    let range = ChartRange::parse(range_str)
+        .ok_or_else(|| AppError::BadRequest(format!("Invalid range: {}", range_str)))?;
+    let numerator = query.num.trim().to_lowercase();
+    let denominator = query.den.trim().to_lowercase();
+    if numerator.is_empty() || denominator.is_empty() {
+        return Err(AppError::BadRequest(
+            "Both num and den symbols are required".to_string(),
+        ));
+    }
+    if numerator == denominator {
+        return Err(AppError::BadRequest(
+            "num and den must be different symbols".to_string(),
+        ));
+    }
+
+    let data = ratio::ratio_chart(&state.chart_store, &numerator, &denominator, range);
+
+    Ok(Json(ApiResponse {
+        data: RatioResponse {
+            symbol: ratio::ratio_symbol(&numerator, &denominator),
+            numerator,
+            denominator,
+            range: range_str.to_string(),
+            data,
+        },
+        meta: ApiMeta::simple(),
+    }))
+}
+
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/global", get(get_global))
@@ -258,6 +480,8 @@ pub fn router() -> Router<AppState> {
         .route("/exchanges", get(get_exchanges))
         .route("/stats", get(get_stats))
         .route("/movers", get(get_movers))
+        .route("/basket", get(get_basket))
+        .route("/ratio", get(get_ratio))
         .route("/source-stats/:symbol", get(get_symbol_source_stats))
         .route("/confidence/:symbol", get(get_symbol_confidence))
 }
@@ -1,8 +1,12 @@
+pub mod access_log;
 pub mod auth;
 pub mod bots;
 pub mod crypto;
+pub mod developer;
 pub mod health;
 pub mod market;
+pub mod notifications;
+pub mod openapi;
 pub mod orderbook;
 pub mod peers;
 pub mod signals;
@@ -15,13 +19,16 @@ use axum::Router;
 pub fn router() -> Router<AppState> {
     Router::new()
         .merge(health::router())
+        .merge(openapi::router())
         .nest("/api/crypto", crypto::router())
         .nest("/api/market", market::router())
         .nest("/api/signals", signals::router())
         .nest("/api/auth", auth::router())
+        .nest("/api/notifications", notifications::router())
         .nest("/api/orderbook", orderbook::router())
         .nest("/api/peers", peers::router())
         .nest("/api/mesh", peers::mesh_router())
         .nest("/api/trading", trading::router())
         .nest("/api/bots", bots::router())
+        .nest("/api/developer", developer::router())
 }
@@ -0,0 +1,155 @@
+//! Notifications API
+//!
+//! Endpoints for reading and managing the authenticated user's notifications.
+//!
+//! - GET /api/notifications - List notifications (newest first)
+//! - GET /api/notifications/unread-count - Number of unread notifications
+//! - POST /api/notifications/read-all - Mark all notifications as read
+
+use axum::{
+    extract::{Query, State},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use super::auth::{ApiResponse, Authenticated};
+use crate::types::{Notification, ServerMessage, UnreadCount, UnreadCountData};
+use crate::AppState;
+
+/// Default number of notifications returned.
+const DEFAULT_LIMIT: usize = 50;
+
+/// Maximum number of notifications returned.
+const MAX_LIMIT: usize = 200;
+
+/// Create notifications router.
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_notifications))
+        .route("/unread-count", get(get_unread_count))
+        .route("/read-all", post(mark_all_read))
+}
+
+/// Query parameters for listing notifications.
+#[derive(Debug, Deserialize)]
+pub struct NotificationsQuery {
+    /// Only return unread notifications
+    #[serde(default)]
+    pub unread_only: bool,
+    /// Limit number of results (default: 50, max: 200)
+    pub limit: Option<usize>,
+}
+
+/// Response for mark-all-read.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkAllReadResponse {
+    /// Number of notifications that were marked read
+    pub marked: usize,
+    /// Unread count after the update
+    pub unread_count: usize,
+}
+
+/// GET /api/notifications
+///
+/// List the authenticated user's notifications, newest first.
+async fn get_notifications(
+    State(state): State<AppState>,
+    auth: Authenticated,
+    Query(query): Query<NotificationsQuery>,
+) -> Json<ApiResponse<Vec<Notification>>> {
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+    let notifications =
+        state
+            .sqlite_store
+            .get_notifications(&auth.user.public_key, query.unread_only, limit);
+
+    Json(ApiResponse {
+        data: notifications,
+    })
+}
+
+/// GET /api/notifications/unread-count
+///
+/// Get the number of unread notifications.
+async fn get_unread_count(
+    State(state): State<AppState>,
+    auth: Authenticated,
+) -> Json<ApiResponse<UnreadCount>> {
+    let unread_count = state
+        .sqlite_store
+        .unread_notification_count(&auth.user.public_key);
+
+    Json(ApiResponse {
+        data: UnreadCount { unread_count },
+    })
+}
+
+/// POST /api/notifications/read-all
+///
+/// Mark every notification as read and push the new count over WebSocket.
+async fn mark_all_read(
+    State(state): State<AppState>,
+    auth: Authenticated,
+) -> Result<Json<ApiResponse<MarkAllReadResponse>>, (axum::http::StatusCode, String)> {
+    let user_id = &auth.user.public_key;
+    let marked = state
+        .sqlite_store
+        .mark_all_notifications_read(user_id)
+        .map_err(|e| (axum::http::StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let unread_count = push_unread_count(&state, user_id);
+
+    Ok(Json(ApiResponse {
+        data: MarkAllReadResponse {
+            marked,
+            unread_count,
+        },
+    }))
+}
+
+/// Build the unread count WebSocket message for a user.
+pub fn unread_count_message(user_id: &str, unread_count: usize) -> ServerMessage {
+    ServerMessage::UnreadCount {
+        data: UnreadCountData {
+            user_id: user_id.to_string(),
+            unread_count,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        },
+    }
+}
+
+/// Send a user's current unread count to their WebSocket subscribers.
+/// Returns the count that was sent.
+pub fn push_unread_count(state: &AppState, user_id: &str) -> usize {
+    let unread_count = state.sqlite_store.unread_notification_count(user_id);
+    let message = unread_count_message(user_id, unread_count);
+    state.room_manager.broadcast_notifications(user_id, &message);
+    unread_count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notifications_query_defaults() {
+        let query: NotificationsQuery = serde_json::from_str("{}").unwrap();
+        assert!(!query.unread_only);
+        assert!(query.limit.is_none());
+
+        let query: NotificationsQuery =
+            serde_json::from_str(r#"{"unread_only": true, "limit": 10}"#).unwrap();
+        assert!(query.unread_only);
+        assert_eq!(query.limit, Some(10));
+    }
+
+    #[test]
+    fn test_unread_count_message_serialization() {
+        let json = serde_json::to_string(&unread_count_message("abc", 3)).unwrap();
+        assert!(json.contains("\"type\":\"unread_count\""));
+        assert!(json.contains("\"userId\":\"abc\""));
+        assert!(json.contains("\"unreadCount\":3"));
+    }
+}
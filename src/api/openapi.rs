@@ -0,0 +1,165 @@
+//! OpenAPI description of the REST API.
+//!
+//! Serves the generated spec and an interactive explorer:
+//! - GET /api/openapi.json - OpenAPI 3.1 document
+//! - GET /api/docs - Swagger UI
+//!
+//! Covers the crypto, market and trading routers. Handlers are added to the
+//! spec through `#[utoipa::path]` and listed in [`ApiDoc`].
+
+use axum::Router;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::api::{crypto, market, trading};
+
+/// Path the spec is served from.
+pub const SPEC_PATH: &str = "/api/openapi.json";
+
+/// Path the Swagger UI is served from.
+pub const DOCS_PATH: &str = "/api/docs";
+
+/// Generated OpenAPI document.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Haunt API",
+        description = "Real-time market data aggregation and paper trading."
+    ),
+    paths(
+        crypto::get_listings,
+        crypto::search,
+        crypto::get_asset,
+        crypto::get_quotes,
+        crypto::get_chart,
+        crypto::seed_symbol,
+        crypto::seed_batch,
+        crypto::seed_status,
+        market::get_global,
+        market::get_fear_greed,
+        market::get_exchanges,
+        market::get_stats,
+        market::get_movers,
+        market::get_basket,
+        market::get_ratio,
+        market::get_symbol_source_stats,
+        market::get_symbol_confidence,
+        trading::get_leaderboard,
+        trading::list_portfolios,
+        trading::create_portfolio,
+        trading::get_portfolio,
+        trading::get_portfolio_summary,
+        trading::get_portfolio_history,
+        trading::update_portfolio,
+        trading::reset_portfolio,
+        trading::delete_portfolio,
+        trading::list_orders,
+        trading::place_order,
+        trading::get_order,
+        trading::cancel_order,
+        trading::list_executions,
+        trading::start_execution,
+        trading::get_execution,
+        trading::cancel_execution,
+        trading::list_strategy_templates,
+        trading::create_strategy_from_template,
+        trading::list_positions,
+        trading::get_position,
+        trading::get_position_funding,
+        trading::modify_position,
+        trading::close_position,
+        trading::list_trades,
+        trading::get_funding_history,
+        trading::get_option_chain,
+        trading::list_competitions,
+        trading::create_competition,
+        trading::get_competition,
+        trading::enroll_in_competition,
+        trading::get_competition_standings,
+    ),
+    modifiers(&BearerAuth),
+    tags(
+        (name = "crypto", description = "Asset listings, quotes and charts"),
+        (name = "market", description = "Market-wide metrics and source statistics"),
+        (name = "trading", description = "Paper trading portfolios, orders and positions")
+    )
+)]
+pub struct ApiDoc;
+
+/// Registers the bearer token scheme used by authenticated trading routes.
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+    }
+}
+
+/// Create the OpenAPI router.
+pub fn router<S>() -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    SwaggerUi::new(DOCS_PATH)
+        .url(SPEC_PATH, ApiDoc::openapi())
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::StatusCode;
+
+    /// Serve the router on an ephemeral port and fetch `uri` from it.
+    async fn get(uri: &str) -> (StatusCode, Vec<u8>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router::<()>()).await });
+
+        let response = reqwest::get(format!("http://{}{}", addr, uri))
+            .await
+            .unwrap();
+        let status = response.status();
+        (status, response.bytes().await.unwrap().to_vec())
+    }
+
+    #[tokio::test]
+    async fn test_openapi_spec_lists_routes() {
+        let (status, body) = get(SPEC_PATH).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let spec: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(spec["openapi"].as_str().unwrap().starts_with("3."));
+
+        let paths = &spec["paths"];
+        let listings = &paths["/api/crypto/listings"]["get"];
+        assert!(listings["responses"]["200"].is_object());
+        assert!(listings["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|p| p["name"] == "limit"));
+        assert!(paths["/api/market/movers"]["get"].is_object());
+        assert!(paths["/api/trading/orders"]["post"]["requestBody"].is_object());
+        assert!(paths["/api/trading/positions/{id}/funding"]["get"].is_object());
+
+        // Referenced schemas are emitted as components
+        let schemas = &spec["components"]["schemas"];
+        for name in ["AssetListing", "Order", "PlaceOrderRequest", "Position"] {
+            assert!(schemas[name].is_object(), "missing schema {}", name);
+        }
+        assert!(spec["components"]["securitySchemes"]["bearer_auth"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_swagger_ui_served() {
+        let (status, body) = get(&format!("{}/", DOCS_PATH)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(String::from_utf8_lossy(&body).contains("swagger"));
+    }
+}
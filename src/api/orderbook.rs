@@ -3,7 +3,7 @@
 //! Provides aggregated order book data from multiple exchanges.
 
 use crate::error::Result;
-use crate::types::AggregatedOrderBook;
+use crate::types::{AggregatedOrderBook, DepthChart};
 use crate::AppState;
 use axum::{
     extract::{Path, Query, State},
@@ -40,9 +40,26 @@ async fn get_orderbook(
     Ok(Json(ApiResponse { data: book }))
 }
 
+/// GET /api/orderbook/:symbol/depth-chart
+///
+/// Returns cumulative bid and ask curves for rendering a depth chart.
+async fn get_depth_chart(
+    State(state): State<AppState>,
+    Path(symbol): Path<String>,
+    Query(query): Query<OrderBookQuery>,
+) -> Result<Json<ApiResponse<DepthChart>>> {
+    let chart = state
+        .orderbook_service
+        .get_depth_chart(&symbol, query.depth)
+        .await;
+    Ok(Json(ApiResponse { data: chart }))
+}
+
 /// Create the order book router.
 pub fn router() -> Router<AppState> {
-    Router::new().route("/:symbol", get(get_orderbook))
+    Router::new()
+        .route("/:symbol", get(get_orderbook))
+        .route("/:symbol/depth-chart", get(get_depth_chart))
 }
 
 #[cfg(test)]
@@ -76,6 +76,7 @@ async fn get_peers(State(state): State<AppState>) -> Json<PeerMeshResponse> {
         .filter(|p| p.status == crate::services::PeerConnectionStatus::Connected)
         .count();
 
+    let config = state.config.load();
     let (server_id, server_region) = if let Some(ref mesh) = state.peer_mesh {
         (
             mesh.server_id().to_string(),
@@ -83,8 +84,8 @@ async fn get_peers(State(state): State<AppState>) -> Json<PeerMeshResponse> {
         )
     } else {
         (
-            state.config.server_id.clone(),
-            state.config.server_region.clone(),
+            config.server_id.clone(),
+            config.server_region.clone(),
         )
     };
 
@@ -116,6 +117,7 @@ async fn get_peer(
 /// This endpoint allows a frontend to discover all servers by connecting to just one.
 async fn get_mesh_servers(State(state): State<AppState>) -> Json<MeshDiscoveryResponse> {
     let timestamp = chrono::Utc::now().timestamp_millis();
+    let config = state.config.load();
 
     let (self_id, self_region, self_api_url, self_ws_url, mesh_key_hash) =
         if let Some(ref mesh) = state.peer_mesh {
@@ -127,7 +129,7 @@ async fn get_mesh_servers(State(state): State<AppState>) -> Json<MeshDiscoveryRe
                 // Create a partial hash for verification (first 8 chars of SHA256)
                 {
                     use sha2::{Digest, Sha256};
-                    let key = state.config.mesh_auth.shared_key.as_str();
+                    let key = config.mesh_auth.shared_key.as_str();
                     if key.is_empty() {
                         "none".to_string()
                     } else {
@@ -138,10 +140,10 @@ async fn get_mesh_servers(State(state): State<AppState>) -> Json<MeshDiscoveryRe
             )
         } else {
             (
-                state.config.server_id.clone(),
-                state.config.server_region.clone(),
-                format!("http://{}:{}", state.config.host, state.config.port),
-                format!("ws://{}:{}/ws", state.config.host, state.config.port),
+                config.server_id.clone(),
+                config.server_region.clone(),
+                format!("http://{}:{}", config.host, config.port),
+                format!("ws://{}:{}/ws", config.host, config.port),
                 "none".to_string(),
             )
         };
@@ -169,8 +171,7 @@ async fn get_mesh_servers(State(state): State<AppState>) -> Json<MeshDiscoveryRe
                 (info.api_url.clone(), info.ws_url.clone())
             } else {
                 // Fallback: try to get from peer servers config
-                let peer_config = state
-                    .config
+                let peer_config = config
                     .peer_servers
                     .iter()
                     .find(|p| p.id == peer_status.id);
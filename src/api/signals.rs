@@ -7,8 +7,11 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 
+use crate::services::signals::{parse_indicator_spec, StopSuggestionError};
 use crate::types::{
-    AccuracyResponse, PredictionsResponse, Recommendation, SignalAccuracy, SymbolSignals,
+    AccuracyResponse, ChartRange, IndicatorAccuracyBreakdown, IndicatorAccuracyResponse,
+    PivotPoints, PivotType, PositionSide, PredictionsResponse,
+    RawIndicators, Recommendation, SignalAccuracy, StopSuggestion, SymbolFibonacci, SymbolSignals,
     TradingTimeframe,
 };
 use crate::AppState;
@@ -39,6 +42,8 @@ impl<T> ApiResponse<T> {
 pub struct SignalsQuery {
     /// Trading timeframe: scalping, day_trading, swing_trading, position_trading
     pub timeframe: Option<String>,
+    /// Custom indicator spec, e.g. "rsi:7,ema:21,macd:5:13:3"
+    pub indicators: Option<String>,
 }
 
 /// Query parameters for predictions endpoint.
@@ -50,14 +55,54 @@ pub struct PredictionsQuery {
     pub limit: Option<usize>,
 }
 
+/// Query parameters for pivots endpoint.
+#[derive(Debug, Deserialize)]
+pub struct PivotsQuery {
+    /// Pivot method: classic (default) or fib
+    #[serde(rename = "type")]
+    pub pivot_type: Option<String>,
+}
+
+/// Query parameters for Fibonacci endpoint.
+#[derive(Debug, Deserialize)]
+pub struct FibonacciQuery {
+    /// Chart range to detect the swing over: 1h, 4h, 1d (default), 1w, 1m
+    pub range: Option<String>,
+}
+
+/// Query parameters for stop suggestion endpoint.
+#[derive(Debug, Deserialize)]
+pub struct StopSuggestionQuery {
+    /// Position side: long (default) or short
+    pub side: Option<PositionSide>,
+    /// ATR multiplier (default: 2)
+    pub mult: Option<f64>,
+    /// Entry price (default: latest close)
+    pub entry: Option<f64>,
+    /// Trading timeframe: scalping, day_trading, swing_trading, position_trading
+    pub timeframe: Option<String>,
+}
+
+/// Query parameters for the per-indicator accuracy endpoint.
+#[derive(Debug, Deserialize)]
+pub struct IndicatorAccuracyQuery {
+    /// Validation timeframe: 5m, 1h (default), 4h, 24h
+    pub timeframe: Option<String>,
+}
+
 /// Create the signals router.
 pub fn router() -> Router<AppState> {
     Router::new()
         .route("/:symbol", get(get_signals))
+        .route("/:symbol/raw", get(get_raw_indicators))
         .route("/:symbol/generate", post(generate_predictions))
         .route("/:symbol/recommendation", get(get_recommendation))
         .route("/:symbol/accuracy", get(get_symbol_accuracy))
         .route("/:symbol/predictions", get(get_symbol_predictions))
+        .route("/:symbol/pivots", get(get_pivots))
+        .route("/:symbol/fib", get(get_fibonacci))
+        .route("/:symbol/stop-suggestion", get(get_stop_suggestion))
+        .route("/accuracy/by-indicator", get(get_accuracy_by_indicator))
         .route("/accuracy/:indicator", get(get_indicator_accuracy))
 }
 
@@ -74,14 +119,21 @@ async fn get_signals(
         .and_then(TradingTimeframe::parse)
         .unwrap_or_default();
 
-    let signals = state
-        .signal_store
-        .get_signals(&symbol, timeframe)
-        .await
-        .ok_or((
-            axum::http::StatusCode::NOT_FOUND,
-            format!("No signals available for {}", symbol),
-        ))?;
+    let signals = match query.indicators.as_deref() {
+        Some(spec) => {
+            let indicators = parse_indicator_spec(spec)
+                .map_err(|e| (axum::http::StatusCode::BAD_REQUEST, e.to_string()))?;
+            state
+                .signal_store
+                .get_custom_signals(&symbol, timeframe, &indicators)
+                .await
+        }
+        None => state.signal_store.get_signals(&symbol, timeframe).await,
+    }
+    .ok_or((
+        axum::http::StatusCode::NOT_FOUND,
+        format!("No signals available for {}", symbol),
+    ))?;
 
     Ok(Json(ApiResponse::new(signals)))
 }
@@ -187,6 +239,100 @@ async fn get_symbol_predictions(
     }))
 }
 
+/// Get the raw value of every indicator for a symbol.
+async fn get_raw_indicators(
+    State(state): State<AppState>,
+    Path(symbol): Path<String>,
+    Query(query): Query<SignalsQuery>,
+) -> Json<ApiResponse<RawIndicators>> {
+    let timeframe = query
+        .timeframe
+        .as_deref()
+        .and_then(TradingTimeframe::parse)
+        .unwrap_or_default();
+
+    Json(ApiResponse::new(
+        state.signal_store.get_raw_indicators(&symbol, timeframe),
+    ))
+}
+
+/// Get daily pivot points for a symbol.
+async fn get_pivots(
+    State(state): State<AppState>,
+    Path(symbol): Path<String>,
+    Query(query): Query<PivotsQuery>,
+) -> Result<Json<ApiResponse<PivotPoints>>, (axum::http::StatusCode, String)> {
+    // Parse pivot type, default to classic
+    let pivot_type = match query.pivot_type.as_deref() {
+        Some(s) => PivotType::parse(s).ok_or((
+            axum::http::StatusCode::BAD_REQUEST,
+            format!("Unknown pivot type {}; expected classic or fib", s),
+        ))?,
+        None => PivotType::default(),
+    };
+
+    let pivots = state.signal_store.get_pivots(&symbol, pivot_type).ok_or((
+        axum::http::StatusCode::NOT_FOUND,
+        format!("No completed prior period for {}", symbol),
+    ))?;
+
+    Ok(Json(ApiResponse::new(pivots)))
+}
+
+/// Get Fibonacci retracement and extension levels for a symbol.
+async fn get_fibonacci(
+    State(state): State<AppState>,
+    Path(symbol): Path<String>,
+    Query(query): Query<FibonacciQuery>,
+) -> Result<Json<ApiResponse<SymbolFibonacci>>, (axum::http::StatusCode, String)> {
+    let range_str = query.range.as_deref().unwrap_or("1d");
+    let range = ChartRange::parse(range_str).ok_or((
+        axum::http::StatusCode::BAD_REQUEST,
+        format!("Invalid range: {}", range_str),
+    ))?;
+
+    let fibonacci = state.signal_store.get_fibonacci(&symbol, range).ok_or((
+        axum::http::StatusCode::NOT_FOUND,
+        format!("No chart data available for {}", symbol),
+    ))?;
+
+    Ok(Json(ApiResponse::new(fibonacci)))
+}
+
+/// Get an ATR-based stop-loss suggestion for a symbol.
+async fn get_stop_suggestion(
+    State(state): State<AppState>,
+    Path(symbol): Path<String>,
+    Query(query): Query<StopSuggestionQuery>,
+) -> Result<Json<ApiResponse<StopSuggestion>>, (axum::http::StatusCode, String)> {
+    let timeframe = query
+        .timeframe
+        .as_deref()
+        .and_then(TradingTimeframe::parse)
+        .unwrap_or_default();
+
+    let suggestion = state
+        .signal_store
+        .get_stop_suggestion(
+            &symbol,
+            timeframe,
+            query.side.unwrap_or(PositionSide::Long),
+            query.mult.unwrap_or(2.0),
+            query.entry,
+        )
+        .map_err(|e| {
+            let status = match e {
+                StopSuggestionError::InsufficientData { .. } => {
+                    axum::http::StatusCode::UNPROCESSABLE_ENTITY
+                }
+                _ => axum::http::StatusCode::BAD_REQUEST,
+            };
+            (status, format!("{}: {}", symbol.to_uppercase(), e))
+        })?;
+
+    Ok(Json(ApiResponse::new(suggestion)))
+}
+
 /// Get global accuracy for an indicator.
 async fn get_indicator_accuracy(
     State(state): State<AppState>,
@@ -200,6 +346,40 @@ async fn get_indicator_accuracy(
     Json(ApiResponse::new(accuracies))
 }
 
+/// Get accuracy across all symbols, broken down by indicator.
+async fn get_accuracy_by_indicator(
+    State(state): State<AppState>,
+    Query(query): Query<IndicatorAccuracyQuery>,
+) -> Result<Json<ApiResponse<IndicatorAccuracyResponse>>, (axum::http::StatusCode, String)> {
+    let timeframe = query.timeframe.unwrap_or_else(|| "1h".to_string());
+    if !matches!(timeframe.as_str(), "5m" | "1h" | "4h" | "24h") {
+        return Err((
+            axum::http::StatusCode::BAD_REQUEST,
+            format!("Unknown timeframe {}; expected 5m, 1h, 4h or 24h", timeframe),
+        ));
+    }
+
+    let indicators = state
+        .sqlite_store
+        .get_indicator_accuracy(&timeframe)
+        .into_iter()
+        .map(|(indicator, stats)| IndicatorAccuracyBreakdown {
+            indicator,
+            total_predictions: stats.total,
+            correct_predictions: stats.correct,
+            incorrect_predictions: stats.incorrect,
+            neutral_predictions: stats.neutral,
+            accuracy: stats.accuracy_pct() / 100.0,
+        })
+        .collect();
+
+    Ok(Json(ApiResponse::new(IndicatorAccuracyResponse {
+        timeframe,
+        indicators,
+        timestamp: chrono::Utc::now().timestamp_millis(),
+    })))
+}
+
 /// Get accuracy-weighted recommendation for a symbol.
 async fn get_recommendation(
     State(state): State<AppState>,
@@ -283,7 +463,10 @@ mod tests {
 
     #[test]
     fn test_signals_query_default() {
-        let query = SignalsQuery { timeframe: None };
+        let query = SignalsQuery {
+            timeframe: None,
+            indicators: None,
+        };
         assert!(query.timeframe.is_none());
     }
 
@@ -291,6 +474,7 @@ mod tests {
     fn test_signals_query_with_timeframe() {
         let query = SignalsQuery {
             timeframe: Some("day_trading".to_string()),
+            indicators: None,
         };
         assert_eq!(query.timeframe, Some("day_trading".to_string()));
     }
@@ -309,6 +493,20 @@ mod tests {
         assert!(query.timeframe.is_none());
     }
 
+    // =========================================================================
+    // PivotsQuery Tests
+    // =========================================================================
+
+    #[test]
+    fn test_pivots_query_deserialization() {
+        let json = r#"{"type": "fib"}"#;
+        let query: PivotsQuery = serde_json::from_str(json).unwrap();
+        assert_eq!(query.pivot_type, Some("fib".to_string()));
+
+        let query: PivotsQuery = serde_json::from_str("{}").unwrap();
+        assert!(query.pivot_type.is_none());
+    }
+
     // =========================================================================
     // PredictionsQuery Tests
     // =========================================================================
@@ -3,7 +3,7 @@
 //! Endpoints for paper trading functionality:
 //!
 //! Leaderboard:
-//! - GET /api/trading/leaderboard - Get portfolio leaderboard by return %
+//! - GET /api/trading/leaderboard - Get portfolio leaderboard by return % (?window=daily|weekly|all_time)
 //!
 //! Portfolios:
 //! - GET /api/trading/portfolios - List user's portfolios
@@ -21,14 +21,38 @@
 //! - GET /api/trading/orders/:id - Get order details
 //! - DELETE /api/trading/orders/:id - Cancel an order
 //!
+//! Executions (TWAP/VWAP):
+//! - GET /api/trading/executions - List execution schedules for a portfolio
+//! - POST /api/trading/executions - Work an order over time as child orders
+//! - GET /api/trading/executions/:id - Get an execution schedule
+//! - DELETE /api/trading/executions/:id - Cancel the unreleased remainder
+//!
+//! Strategies:
+//! - GET /api/trading/strategies/templates - List ready-made strategy templates
+//! - POST /api/trading/strategies/from-template - Create a strategy from a template
+//!
 //! Positions:
 //! - GET /api/trading/positions - List open positions
 //! - GET /api/trading/positions/:id - Get position details
 //! - PUT /api/trading/positions/:id - Modify position (SL/TP)
 //! - DELETE /api/trading/positions/:id - Close a position
+//! - GET /api/trading/positions/:id/funding - Get a position's funding payments and total
+//!
+//! Funding:
+//! - GET /api/trading/funding/:symbol - Get recent funding rate history
 //!
 //! Trades:
 //! - GET /api/trading/trades - List trade history
+//!
+//! Options:
+//! - GET /api/trading/options/chain - Generate a theoretical option chain (?symbol=btc)
+//!
+//! Competitions:
+//! - GET /api/trading/competitions - List competitions
+//! - POST /api/trading/competitions - Create a competition
+//! - GET /api/trading/competitions/:id - Get competition details
+//! - POST /api/trading/competitions/:id/enroll - Enroll a portfolio
+//! - GET /api/trading/competitions/:id/standings - Get ranked standings
 
 use axum::{
     extract::{Path, Query, State},
@@ -38,12 +62,18 @@ use axum::{
     Json, Router,
 };
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
 use crate::api::auth::Authenticated;
+use crate::services::options::{ChainConfig, OptionsService};
+use crate::services::strategy_engine::{self, StrategyTemplate};
 use crate::services::TradingError;
 use crate::types::{
-    EquityPoint, LeaderboardEntry, ModifyPositionRequest, Order, OrderType, PlaceOrderRequest,
-    Portfolio, Position, PortfolioSummary, RiskSettings, Trade,
+    Competition, CompetitionStandings, CreateCompetitionRequest, EquityPoint, ExecutionAlgoRequest,
+    ExecutionSchedule, FundingRateHistory,
+    LeaderboardEntry, LeaderboardWindow, ModifyPositionRequest, OptionStyle, OptionsChain, Order,
+    OrderType, PlaceOrderRequest, Portfolio, Position, PositionFunding, PortfolioSummary,
+    RiskSettings, StrategyFromTemplateRequest, Trade, TradingStrategy,
 };
 use crate::AppState;
 
@@ -66,25 +96,44 @@ pub fn router() -> Router<AppState> {
         .route("/orders", post(place_order))
         .route("/orders/:id", get(get_order))
         .route("/orders/:id", delete(cancel_order))
+        // Executions
+        .route("/executions", get(list_executions))
+        .route("/executions", post(start_execution))
+        .route("/executions/:id", get(get_execution))
+        .route("/executions/:id", delete(cancel_execution))
+        // Strategy templates
+        .route("/strategies/templates", get(list_strategy_templates))
+        .route("/strategies/from-template", post(create_strategy_from_template))
         // Position routes
         .route("/positions", get(list_positions))
         .route("/positions/:id", get(get_position))
         .route("/positions/:id", put(modify_position))
         .route("/positions/:id", delete(close_position))
+        .route("/positions/:id/funding", get(get_position_funding))
+        // Funding routes
+        .route("/funding/:symbol", get(get_funding_history))
         // Trade routes
         .route("/trades", get(list_trades))
+        // Options routes
+        .route("/options/chain", get(get_option_chain))
+        // Competition routes
+        .route("/competitions", get(list_competitions))
+        .route("/competitions", post(create_competition))
+        .route("/competitions/:id", get(get_competition))
+        .route("/competitions/:id/enroll", post(enroll_in_competition))
+        .route("/competitions/:id/standings", get(get_competition_standings))
 }
 
 // =============================================================================
 // Response Types
 // =============================================================================
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ApiResponse<T> {
     pub data: T,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct ErrorResponse {
     pub error: String,
     pub code: String,
@@ -106,6 +155,9 @@ impl IntoResponse for TradingError {
             TradingError::PositionLimitExceeded { .. } => {
                 (StatusCode::BAD_REQUEST, "POSITION_LIMIT_EXCEEDED")
             }
+            TradingError::ConcentrationLimitExceeded { .. } => {
+                (StatusCode::BAD_REQUEST, "CONCENTRATION_LIMIT_EXCEEDED")
+            }
             TradingError::InvalidOrder(_) => (StatusCode::BAD_REQUEST, "INVALID_ORDER"),
             TradingError::CannotCancelOrder(_) => (StatusCode::BAD_REQUEST, "CANNOT_CANCEL_ORDER"),
             TradingError::LeverageExceeded { .. } => {
@@ -117,6 +169,22 @@ impl IntoResponse for TradingError {
             }
             TradingError::NoPriceData(_) => (StatusCode::SERVICE_UNAVAILABLE, "NO_PRICE_DATA"),
             TradingError::Unauthorized(_) => (StatusCode::FORBIDDEN, "UNAUTHORIZED"),
+            TradingError::CompetitionNotFound(_) => {
+                (StatusCode::NOT_FOUND, "COMPETITION_NOT_FOUND")
+            }
+            TradingError::InvalidCompetition(_) => {
+                (StatusCode::BAD_REQUEST, "INVALID_COMPETITION")
+            }
+            TradingError::CompetitionClosed(_) => (StatusCode::FORBIDDEN, "COMPETITION_CLOSED"),
+            TradingError::InvalidOptionChain(_) => {
+                (StatusCode::BAD_REQUEST, "INVALID_OPTION_CHAIN")
+            }
+            TradingError::ExecutionNotFound(_) => (StatusCode::NOT_FOUND, "EXECUTION_NOT_FOUND"),
+            TradingError::StrategyTemplateNotFound(_) => {
+                (StatusCode::NOT_FOUND, "STRATEGY_TEMPLATE_NOT_FOUND")
+            }
+            TradingError::InvalidStrategy(_) => (StatusCode::BAD_REQUEST, "INVALID_STRATEGY"),
+            TradingError::Internal(_) => (StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR"),
         };
 
         let body = Json(ErrorResponse {
@@ -132,34 +200,71 @@ impl IntoResponse for TradingError {
 // Query Parameters
 // =============================================================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListOrdersQuery {
     pub portfolio_id: String,
     pub status: Option<String>,
     pub limit: Option<usize>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListExecutionsQuery {
+    pub portfolio_id: String,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListPositionsQuery {
     pub portfolio_id: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListTradesQuery {
     pub portfolio_id: String,
     pub limit: Option<usize>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct LeaderboardQuery {
     pub limit: Option<usize>,
+    /// Ranking window: daily, weekly, or all_time (default)
+    pub window: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ClosePositionQuery {
     pub price: f64,
 }
 
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FundingHistoryQuery {
+    /// Settlements to return (default 100, max 500)
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct OptionChainQuery {
+    /// Underlying symbol, e.g. btc
+    pub symbol: String,
+    /// Comma-separated days to expiration, at most 12 values of 1-1095 (default 7,30,90)
+    pub expirations: Option<String>,
+    /// Strikes on each side of the money (default 5)
+    pub strikes: Option<usize>,
+    /// Distance between strikes (default: a round step near 2.5% of spot)
+    pub spacing: Option<f64>,
+    /// Annualized volatility used for pricing (default 0.6)
+    pub volatility: Option<f64>,
+    /// Exercise style: american (default) or european
+    pub style: Option<OptionStyle>,
+}
+
 // =============================================================================
 // Portfolio Handlers
 // =============================================================================
@@ -168,11 +273,21 @@ pub struct ClosePositionQuery {
 ///
 /// List all portfolios for the authenticated user.
 /// For now, uses user_id from query param (will use auth later).
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListPortfoliosQuery {
     pub user_id: String,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/trading/portfolios",
+    tag = "trading",
+    params(ListPortfoliosQuery),
+    responses(
+        (status = 200, description = "The user's portfolios", body = ApiResponse<Vec<Portfolio>>)
+    )
+)]
 async fn list_portfolios(
     State(state): State<AppState>,
     Query(query): Query<ListPortfoliosQuery>,
@@ -184,6 +299,16 @@ async fn list_portfolios(
 /// POST /api/trading/portfolios
 ///
 /// Create a new portfolio.
+#[utoipa::path(
+    post,
+    path = "/api/trading/portfolios",
+    tag = "trading",
+    request_body = CreatePortfolioWithUser,
+    responses(
+        (status = 200, description = "The created portfolio", body = ApiResponse<Portfolio>),
+        (status = 400, description = "Invalid portfolio settings", body = ErrorResponse)
+    )
+)]
 async fn create_portfolio(
     State(state): State<AppState>,
     Json(request): Json<CreatePortfolioWithUser>,
@@ -198,7 +323,7 @@ async fn create_portfolio(
     Ok(Json(ApiResponse { data: portfolio }))
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreatePortfolioWithUser {
     pub user_id: String,
     pub name: String,
@@ -209,6 +334,16 @@ pub struct CreatePortfolioWithUser {
 /// GET /api/trading/portfolios/:id
 ///
 /// Get portfolio details.
+#[utoipa::path(
+    get,
+    path = "/api/trading/portfolios/{id}",
+    tag = "trading",
+    params(("id" = String, Path, description = "Portfolio ID")),
+    responses(
+        (status = 200, description = "Portfolio details", body = ApiResponse<Portfolio>),
+        (status = 404, description = "Portfolio not found", body = ErrorResponse)
+    )
+)]
 async fn get_portfolio(
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -224,6 +359,16 @@ async fn get_portfolio(
 /// GET /api/trading/portfolios/:id/summary
 ///
 /// Get portfolio summary with performance metrics.
+#[utoipa::path(
+    get,
+    path = "/api/trading/portfolios/{id}/summary",
+    tag = "trading",
+    params(("id" = String, Path, description = "Portfolio ID")),
+    responses(
+        (status = 200, description = "Portfolio summary", body = ApiResponse<PortfolioSummary>),
+        (status = 404, description = "Portfolio not found", body = ErrorResponse)
+    )
+)]
 async fn get_portfolio_summary(
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -233,7 +378,8 @@ async fn get_portfolio_summary(
 }
 
 /// Query parameters for portfolio history.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct PortfolioHistoryQuery {
     /// Filter snapshots since this timestamp (ms)
     pub since: Option<i64>,
@@ -244,6 +390,16 @@ pub struct PortfolioHistoryQuery {
 /// GET /api/trading/portfolios/:id/history
 ///
 /// Get portfolio equity history for charting (equity curve).
+#[utoipa::path(
+    get,
+    path = "/api/trading/portfolios/{id}/history",
+    tag = "trading",
+    params(("id" = String, Path, description = "Portfolio ID"), PortfolioHistoryQuery),
+    responses(
+        (status = 200, description = "Equity curve points", body = ApiResponse<Vec<EquityPoint>>),
+        (status = 404, description = "Portfolio not found", body = ErrorResponse)
+    )
+)]
 async fn get_portfolio_history(
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -258,6 +414,17 @@ async fn get_portfolio_history(
 /// PUT /api/trading/portfolios/:id
 ///
 /// Update portfolio settings.
+#[utoipa::path(
+    put,
+    path = "/api/trading/portfolios/{id}",
+    tag = "trading",
+    params(("id" = String, Path, description = "Portfolio ID")),
+    request_body = RiskSettings,
+    responses(
+        (status = 200, description = "The updated portfolio", body = ApiResponse<Portfolio>),
+        (status = 404, description = "Portfolio not found", body = ErrorResponse)
+    )
+)]
 async fn update_portfolio(
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -273,6 +440,16 @@ async fn update_portfolio(
 /// POST /api/trading/portfolios/:id/reset
 ///
 /// Reset portfolio to starting balance, closing all positions and orders.
+#[utoipa::path(
+    post,
+    path = "/api/trading/portfolios/{id}/reset",
+    tag = "trading",
+    params(("id" = String, Path, description = "Portfolio ID")),
+    responses(
+        (status = 200, description = "The reset portfolio", body = ApiResponse<Portfolio>),
+        (status = 404, description = "Portfolio not found", body = ErrorResponse)
+    )
+)]
 async fn reset_portfolio(
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -284,6 +461,16 @@ async fn reset_portfolio(
 /// DELETE /api/trading/portfolios/:id
 ///
 /// Delete a portfolio and all associated data.
+#[utoipa::path(
+    delete,
+    path = "/api/trading/portfolios/{id}",
+    tag = "trading",
+    params(("id" = String, Path, description = "Portfolio ID")),
+    responses(
+        (status = 200, description = "Deletion result", body = ApiResponse<DeleteResponse>),
+        (status = 404, description = "Portfolio not found", body = ErrorResponse)
+    )
+)]
 async fn delete_portfolio(
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -297,7 +484,7 @@ async fn delete_portfolio(
     }))
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct DeleteResponse {
     pub deleted: bool,
     pub id: String,
@@ -310,6 +497,18 @@ pub struct DeleteResponse {
 /// GET /api/trading/orders
 ///
 /// List orders for a portfolio. Requires authentication.
+#[utoipa::path(
+    get,
+    path = "/api/trading/orders",
+    tag = "trading",
+    params(ListOrdersQuery),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Orders for the portfolio", body = ApiResponse<Vec<Order>>),
+        (status = 403, description = "Caller does not own the portfolio", body = ErrorResponse),
+        (status = 404, description = "Portfolio not found", body = ErrorResponse)
+    )
+)]
 async fn list_orders(
     auth: Authenticated,
     State(state): State<AppState>,
@@ -344,6 +543,19 @@ async fn list_orders(
 ///
 /// Place a new order. Requires authentication.
 /// Market orders are executed immediately at current price.
+#[utoipa::path(
+    post,
+    path = "/api/trading/orders",
+    tag = "trading",
+    request_body = PlaceOrderRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "The placed order", body = ApiResponse<Order>),
+        (status = 400, description = "Invalid order or insufficient funds", body = ErrorResponse),
+        (status = 403, description = "Caller does not own the portfolio", body = ErrorResponse),
+        (status = 404, description = "Portfolio not found", body = ErrorResponse)
+    )
+)]
 async fn place_order(
     auth: Authenticated,
     State(state): State<AppState>,
@@ -395,6 +607,16 @@ async fn place_order(
 /// GET /api/trading/orders/:id
 ///
 /// Get order details.
+#[utoipa::path(
+    get,
+    path = "/api/trading/orders/{id}",
+    tag = "trading",
+    params(("id" = String, Path, description = "Order ID")),
+    responses(
+        (status = 200, description = "Order details", body = ApiResponse<Order>),
+        (status = 404, description = "Order not found", body = ErrorResponse)
+    )
+)]
 async fn get_order(
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -410,6 +632,19 @@ async fn get_order(
 /// DELETE /api/trading/orders/:id
 ///
 /// Cancel an order. Requires authentication.
+#[utoipa::path(
+    delete,
+    path = "/api/trading/orders/{id}",
+    tag = "trading",
+    params(("id" = String, Path, description = "Order ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "The cancelled order", body = ApiResponse<Order>),
+        (status = 400, description = "Order cannot be cancelled", body = ErrorResponse),
+        (status = 403, description = "Caller does not own the portfolio", body = ErrorResponse),
+        (status = 404, description = "Order not found", body = ErrorResponse)
+    )
+)]
 async fn cancel_order(
     auth: Authenticated,
     State(state): State<AppState>,
@@ -437,6 +672,227 @@ async fn cancel_order(
     Ok(Json(ApiResponse { data: order }))
 }
 
+// =============================================================================
+// Execution Handlers
+// =============================================================================
+
+/// GET /api/trading/executions
+///
+/// List TWAP/VWAP execution schedules for a portfolio. Requires authentication.
+#[utoipa::path(
+    get,
+    path = "/api/trading/executions",
+    tag = "trading",
+    params(ListExecutionsQuery),
+    security(("bearer_auth" = [])),
+    responses(
+        (
+            status = 200,
+            description = "Execution schedules for the portfolio",
+            body = ApiResponse<Vec<ExecutionSchedule>>
+        ),
+        (status = 403, description = "Caller does not own the portfolio", body = ErrorResponse),
+        (status = 404, description = "Portfolio not found", body = ErrorResponse)
+    )
+)]
+async fn list_executions(
+    auth: Authenticated,
+    State(state): State<AppState>,
+    Query(query): Query<ListExecutionsQuery>,
+) -> Result<Json<ApiResponse<Vec<ExecutionSchedule>>>, TradingError> {
+    // Verify user owns the portfolio
+    let portfolio = state
+        .trading_service
+        .get_portfolio(&query.portfolio_id)
+        .ok_or_else(|| TradingError::PortfolioNotFound(query.portfolio_id.clone()))?;
+
+    if portfolio.user_id != auth.user.public_key {
+        return Err(TradingError::Unauthorized(
+            "You do not own this portfolio".to_string(),
+        ));
+    }
+
+    let executions = state
+        .trading_service
+        .get_portfolio_executions(&query.portfolio_id);
+    Ok(Json(ApiResponse { data: executions }))
+}
+
+/// POST /api/trading/executions
+///
+/// Work a large order over time with TWAP or VWAP. Requires authentication.
+/// The parent order is split into market child orders released on a
+/// schedule by the market simulation engine.
+#[utoipa::path(
+    post,
+    path = "/api/trading/executions",
+    tag = "trading",
+    request_body = ExecutionAlgoRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (
+            status = 200,
+            description = "The execution schedule",
+            body = ApiResponse<ExecutionSchedule>
+        ),
+        (status = 400, description = "Invalid quantity, slices or duration", body = ErrorResponse),
+        (status = 403, description = "Caller does not own the portfolio", body = ErrorResponse),
+        (status = 404, description = "Portfolio not found", body = ErrorResponse)
+    )
+)]
+async fn start_execution(
+    auth: Authenticated,
+    State(state): State<AppState>,
+    Json(request): Json<ExecutionAlgoRequest>,
+) -> Result<Json<ApiResponse<ExecutionSchedule>>, TradingError> {
+    // Verify user owns the portfolio
+    let portfolio = state
+        .trading_service
+        .get_portfolio(&request.portfolio_id)
+        .ok_or_else(|| TradingError::PortfolioNotFound(request.portfolio_id.clone()))?;
+
+    if portfolio.user_id != auth.user.public_key {
+        return Err(TradingError::Unauthorized(
+            "You do not own this portfolio".to_string(),
+        ));
+    }
+
+    let schedule = state.trading_service.start_execution(request)?;
+    Ok(Json(ApiResponse { data: schedule }))
+}
+
+/// GET /api/trading/executions/:id
+///
+/// Get an execution schedule with its child order IDs.
+#[utoipa::path(
+    get,
+    path = "/api/trading/executions/{id}",
+    tag = "trading",
+    params(("id" = String, Path, description = "Execution schedule ID")),
+    responses(
+        (status = 200, description = "Execution schedule", body = ApiResponse<ExecutionSchedule>),
+        (status = 404, description = "Execution schedule not found", body = ErrorResponse)
+    )
+)]
+async fn get_execution(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<ExecutionSchedule>>, TradingError> {
+    let schedule = state
+        .trading_service
+        .get_execution(&id)
+        .ok_or(TradingError::ExecutionNotFound(id))?;
+
+    Ok(Json(ApiResponse { data: schedule }))
+}
+
+/// DELETE /api/trading/executions/:id
+///
+/// Cancel the unreleased remainder of an execution schedule. Requires authentication.
+#[utoipa::path(
+    delete,
+    path = "/api/trading/executions/{id}",
+    tag = "trading",
+    params(("id" = String, Path, description = "Execution schedule ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (
+            status = 200,
+            description = "The cancelled schedule",
+            body = ApiResponse<ExecutionSchedule>
+        ),
+        (status = 400, description = "Schedule is no longer active", body = ErrorResponse),
+        (status = 403, description = "Caller does not own the portfolio", body = ErrorResponse),
+        (status = 404, description = "Execution schedule not found", body = ErrorResponse)
+    )
+)]
+async fn cancel_execution(
+    auth: Authenticated,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<ExecutionSchedule>>, TradingError> {
+    let schedule = state
+        .trading_service
+        .get_execution(&id)
+        .ok_or_else(|| TradingError::ExecutionNotFound(id.clone()))?;
+
+    // Verify user owns the portfolio
+    let portfolio = state
+        .trading_service
+        .get_portfolio(&schedule.portfolio_id)
+        .ok_or_else(|| TradingError::PortfolioNotFound(schedule.portfolio_id.clone()))?;
+
+    if portfolio.user_id != auth.user.public_key {
+        return Err(TradingError::Unauthorized(
+            "You do not own this execution".to_string(),
+        ));
+    }
+
+    let schedule = state.trading_service.cancel_execution(&id)?;
+    Ok(Json(ApiResponse { data: schedule }))
+}
+
+// =============================================================================
+// Strategy Template Handlers
+// =============================================================================
+
+/// GET /api/trading/strategies/templates
+///
+/// List the ready-made strategy templates.
+#[utoipa::path(
+    get,
+    path = "/api/trading/strategies/templates",
+    tag = "trading",
+    responses(
+        (status = 200, description = "Strategy templates with their IDs and descriptions")
+    )
+)]
+async fn list_strategy_templates() -> Json<ApiResponse<Vec<StrategyTemplate>>> {
+    Json(ApiResponse {
+        data: strategy_engine::templates(),
+    })
+}
+
+/// POST /api/trading/strategies/from-template
+///
+/// Create a strategy for a portfolio from a template. Requires authentication.
+/// The strategy is created paused unless `activate` is set.
+#[utoipa::path(
+    post,
+    path = "/api/trading/strategies/from-template",
+    tag = "trading",
+    request_body = StrategyFromTemplateRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "The created strategy"),
+        (status = 400, description = "No symbols given", body = ErrorResponse),
+        (status = 403, description = "Caller does not own the portfolio", body = ErrorResponse),
+        (status = 404, description = "Portfolio or template not found", body = ErrorResponse)
+    )
+)]
+async fn create_strategy_from_template(
+    auth: Authenticated,
+    State(state): State<AppState>,
+    Json(request): Json<StrategyFromTemplateRequest>,
+) -> Result<Json<ApiResponse<TradingStrategy>>, TradingError> {
+    // Verify user owns the portfolio
+    let portfolio = state
+        .trading_service
+        .get_portfolio(&request.portfolio_id)
+        .ok_or_else(|| TradingError::PortfolioNotFound(request.portfolio_id.clone()))?;
+
+    if portfolio.user_id != auth.user.public_key {
+        return Err(TradingError::Unauthorized(
+            "You do not own this portfolio".to_string(),
+        ));
+    }
+
+    let strategy = state
+        .trading_service
+        .create_strategy_from_template(request)?;
+    Ok(Json(ApiResponse { data: strategy }))
+}
+
 // =============================================================================
 // Position Handlers
 // =============================================================================
@@ -444,6 +900,22 @@ async fn cancel_order(
 /// GET /api/trading/positions
 ///
 /// List open positions for a portfolio. Requires authentication.
+#[utoipa::path(
+    get,
+    path = "/api/trading/positions",
+    tag = "trading",
+    params(ListPositionsQuery),
+    security(("bearer_auth" = [])),
+    responses(
+        (
+            status = 200,
+            description = "Open positions for the portfolio",
+            body = ApiResponse<Vec<Position>>
+        ),
+        (status = 403, description = "Caller does not own the portfolio", body = ErrorResponse),
+        (status = 404, description = "Portfolio not found", body = ErrorResponse)
+    )
+)]
 async fn list_positions(
     auth: Authenticated,
     State(state): State<AppState>,
@@ -468,6 +940,16 @@ async fn list_positions(
 /// GET /api/trading/positions/:id
 ///
 /// Get position details.
+#[utoipa::path(
+    get,
+    path = "/api/trading/positions/{id}",
+    tag = "trading",
+    params(("id" = String, Path, description = "Position ID")),
+    responses(
+        (status = 200, description = "Position details", body = ApiResponse<Position>),
+        (status = 404, description = "Position not found", body = ErrorResponse)
+    )
+)]
 async fn get_position(
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -480,9 +962,47 @@ async fn get_position(
     Ok(Json(ApiResponse { data: position }))
 }
 
+/// GET /api/trading/positions/:id/funding
+///
+/// Get the funding payments charged to a position and their total.
+#[utoipa::path(
+    get,
+    path = "/api/trading/positions/{id}/funding",
+    tag = "trading",
+    params(("id" = String, Path, description = "Position ID")),
+    responses(
+        (
+            status = 200,
+            description = "Funding payments and their total",
+            body = ApiResponse<PositionFunding>
+        ),
+        (status = 404, description = "Position not found", body = ErrorResponse)
+    )
+)]
+async fn get_position_funding(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<PositionFunding>>, TradingError> {
+    let funding = state.trading_service.get_position_funding(&id)?;
+    Ok(Json(ApiResponse { data: funding }))
+}
+
 /// PUT /api/trading/positions/:id
 ///
 /// Modify position stop loss and take profit. Requires authentication.
+#[utoipa::path(
+    put,
+    path = "/api/trading/positions/{id}",
+    tag = "trading",
+    params(("id" = String, Path, description = "Position ID")),
+    request_body = ModifyPositionRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "The modified position", body = ApiResponse<Position>),
+        (status = 403, description = "Caller does not own the portfolio", body = ErrorResponse),
+        (status = 404, description = "Position not found", body = ErrorResponse)
+    )
+)]
 async fn modify_position(
     auth: Authenticated,
     State(state): State<AppState>,
@@ -517,6 +1037,18 @@ async fn modify_position(
 /// DELETE /api/trading/positions/:id
 ///
 /// Close a position at market price. Requires authentication.
+#[utoipa::path(
+    delete,
+    path = "/api/trading/positions/{id}",
+    tag = "trading",
+    params(("id" = String, Path, description = "Position ID"), ClosePositionQuery),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "The closing trade", body = ApiResponse<Trade>),
+        (status = 403, description = "Caller does not own the portfolio", body = ErrorResponse),
+        (status = 404, description = "Position not found", body = ErrorResponse)
+    )
+)]
 async fn close_position(
     auth: Authenticated,
     State(state): State<AppState>,
@@ -552,6 +1084,22 @@ async fn close_position(
 /// GET /api/trading/trades
 ///
 /// List trade history for a portfolio. Requires authentication.
+#[utoipa::path(
+    get,
+    path = "/api/trading/trades",
+    tag = "trading",
+    params(ListTradesQuery),
+    security(("bearer_auth" = [])),
+    responses(
+        (
+            status = 200,
+            description = "Trade history for the portfolio",
+            body = ApiResponse<Vec<Trade>>
+        ),
+        (status = 403, description = "Caller does not own the portfolio", body = ErrorResponse),
+        (status = 404, description = "Portfolio not found", body = ErrorResponse)
+    )
+)]
 async fn list_trades(
     auth: Authenticated,
     State(state): State<AppState>,
@@ -574,20 +1122,327 @@ async fn list_trades(
     Ok(Json(ApiResponse { data: trades }))
 }
 
+// =============================================================================
+// Funding Handlers
+// =============================================================================
+
+/// GET /api/trading/funding/:symbol
+///
+/// Get the funding rates recently applied to a symbol, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/trading/funding/{symbol}",
+    tag = "trading",
+    params(
+        ("symbol" = String, Path, description = "Perpetual symbol, e.g. BTC-PERP"),
+        FundingHistoryQuery
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Funding rates, newest first",
+            body = ApiResponse<FundingRateHistory>
+        )
+    )
+)]
+async fn get_funding_history(
+    State(state): State<AppState>,
+    Path(symbol): Path<String>,
+    Query(query): Query<FundingHistoryQuery>,
+) -> Json<ApiResponse<FundingRateHistory>> {
+    let limit = query.limit.unwrap_or(100);
+    let history = state.trading_service.get_funding_rate_history(&symbol, limit);
+    Json(ApiResponse { data: history })
+}
+
+// =============================================================================
+// Options Handlers
+// =============================================================================
+
+/// Default days to expiration for a generated chain.
+const DEFAULT_CHAIN_EXPIRATION_DAYS: [i64; 3] = [7, 30, 90];
+
+/// Most strikes per side a chain request may ask for.
+const MAX_CHAIN_STRIKES_PER_SIDE: usize = 50;
+
+/// Most distinct expirations a chain request may ask for.
+const MAX_CHAIN_EXPIRATIONS: usize = 12;
+
+/// Furthest expiration a chain request may ask for, in days (about three years).
+const MAX_CHAIN_EXPIRATION_DAYS: i64 = 1095;
+
+/// Milliseconds in one day.
+const MS_PER_DAY: i64 = 86_400_000;
+
+/// GET /api/trading/options/chain
+///
+/// Generate theoretical calls and puts around the current price of an
+/// underlying, one chain per expiration.
+#[utoipa::path(
+    get,
+    path = "/api/trading/options/chain",
+    tag = "trading",
+    params(OptionChainQuery),
+    responses(
+        (
+            status = 200,
+            description = "One chain per expiration, soonest first",
+            body = ApiResponse<Vec<OptionsChain>>
+        ),
+        (status = 400, description = "Invalid chain parameters", body = ErrorResponse),
+        (status = 503, description = "No price for the underlying", body = ErrorResponse)
+    )
+)]
+async fn get_option_chain(
+    State(state): State<AppState>,
+    Query(query): Query<OptionChainQuery>,
+) -> Result<Json<ApiResponse<Vec<OptionsChain>>>, TradingError> {
+    let spot = state
+        .price_cache
+        .get_price(&query.symbol)
+        .ok_or_else(|| TradingError::NoPriceData(query.symbol.clone()))?;
+
+    let mut days = match &query.expirations {
+        Some(list) => list
+            .split(',')
+            .map(|d| d.trim().parse::<i64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| {
+                TradingError::InvalidOptionChain(format!("invalid expirations: {}", list))
+            })?,
+        None => DEFAULT_CHAIN_EXPIRATION_DAYS.to_vec(),
+    };
+    days.sort_unstable();
+    days.dedup();
+    if days.len() > MAX_CHAIN_EXPIRATIONS {
+        return Err(TradingError::InvalidOptionChain(format!(
+            "at most {} expirations per request",
+            MAX_CHAIN_EXPIRATIONS
+        )));
+    }
+    if let Some(d) = days.iter().find(|d| !(1..=MAX_CHAIN_EXPIRATION_DAYS).contains(*d)) {
+        return Err(TradingError::InvalidOptionChain(format!(
+            "expiration of {} days is outside 1-{}",
+            d, MAX_CHAIN_EXPIRATION_DAYS
+        )));
+    }
+
+    let defaults = ChainConfig::default();
+    let config = ChainConfig {
+        strike_step: query.spacing,
+        strikes_per_side: query
+            .strikes
+            .unwrap_or(defaults.strikes_per_side)
+            .min(MAX_CHAIN_STRIKES_PER_SIDE),
+        volatility: query.volatility.unwrap_or(defaults.volatility),
+        style: query.style.unwrap_or(defaults.style),
+    };
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let expirations = days
+        .iter()
+        .map(|d| d.checked_mul(MS_PER_DAY).and_then(|ms| now.checked_add(ms)))
+        .collect::<Option<Vec<i64>>>()
+        .ok_or_else(|| {
+            TradingError::InvalidOptionChain("expiration is out of range".to_string())
+        })?;
+
+    // Pricing every strike is CPU-bound, so keep it off the async workers.
+    let symbol = query.symbol.clone();
+    let chains = tokio::task::spawn_blocking(move || {
+        OptionsService::default().generate_chain(&symbol, spot, &expirations, &config, now)
+    })
+    .await
+    .map_err(|e| TradingError::Internal(format!("option chain generation failed: {}", e)))??;
+    Ok(Json(ApiResponse { data: chains }))
+}
+
+// =============================================================================
+// Competition Handlers
+// =============================================================================
+
+/// GET /api/trading/competitions
+///
+/// List all competitions.
+#[utoipa::path(
+    get,
+    path = "/api/trading/competitions",
+    tag = "trading",
+    responses(
+        (status = 200, description = "All competitions", body = ApiResponse<Vec<Competition>>)
+    )
+)]
+async fn list_competitions(State(state): State<AppState>) -> Json<ApiResponse<Vec<Competition>>> {
+    let competitions = state.trading_service.get_competitions();
+    Json(ApiResponse { data: competitions })
+}
+
+/// POST /api/trading/competitions
+///
+/// Create a competition. Requires authentication.
+#[utoipa::path(
+    post,
+    path = "/api/trading/competitions",
+    tag = "trading",
+    request_body = CreateCompetitionRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "The created competition", body = ApiResponse<Competition>),
+        (status = 400, description = "Invalid competition", body = ErrorResponse)
+    )
+)]
+async fn create_competition(
+    _auth: Authenticated,
+    State(state): State<AppState>,
+    Json(request): Json<CreateCompetitionRequest>,
+) -> Result<Json<ApiResponse<Competition>>, TradingError> {
+    let competition = state.trading_service.create_competition(request)?;
+    Ok(Json(ApiResponse { data: competition }))
+}
+
+/// GET /api/trading/competitions/:id
+///
+/// Get competition details.
+#[utoipa::path(
+    get,
+    path = "/api/trading/competitions/{id}",
+    tag = "trading",
+    params(("id" = String, Path, description = "Competition ID")),
+    responses(
+        (status = 200, description = "Competition details", body = ApiResponse<Competition>),
+        (status = 404, description = "Competition not found", body = ErrorResponse)
+    )
+)]
+async fn get_competition(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<Competition>>, TradingError> {
+    let competition = state
+        .trading_service
+        .get_competition(&id)
+        .ok_or(TradingError::CompetitionNotFound(id))?;
+
+    Ok(Json(ApiResponse { data: competition }))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct EnrollRequest {
+    pub portfolio_id: String,
+}
+
+/// POST /api/trading/competitions/:id/enroll
+///
+/// Enroll one of the caller's portfolios, resetting it to the competition's
+/// starting balance. Requires authentication.
+#[utoipa::path(
+    post,
+    path = "/api/trading/competitions/{id}/enroll",
+    tag = "trading",
+    params(("id" = String, Path, description = "Competition ID")),
+    request_body = EnrollRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "The enrolled portfolio", body = ApiResponse<Portfolio>),
+        (status = 400, description = "Portfolio already enrolled", body = ErrorResponse),
+        (
+            status = 403,
+            description = "Caller does not own the portfolio, or the competition has ended",
+            body = ErrorResponse
+        ),
+        (status = 404, description = "Competition or portfolio not found", body = ErrorResponse)
+    )
+)]
+async fn enroll_in_competition(
+    auth: Authenticated,
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<EnrollRequest>,
+) -> Result<Json<ApiResponse<Portfolio>>, TradingError> {
+    // Verify user owns the portfolio
+    let portfolio = state
+        .trading_service
+        .get_portfolio(&request.portfolio_id)
+        .ok_or_else(|| TradingError::PortfolioNotFound(request.portfolio_id.clone()))?;
+
+    if portfolio.user_id != auth.user.public_key {
+        return Err(TradingError::Unauthorized(
+            "You do not own this portfolio".to_string(),
+        ));
+    }
+
+    let portfolio = state
+        .trading_service
+        .enroll_portfolio(&id, &request.portfolio_id)?;
+    Ok(Json(ApiResponse { data: portfolio }))
+}
+
+/// GET /api/trading/competitions/:id/standings
+///
+/// Get enrolled portfolios ranked by realized return within the competition window.
+#[utoipa::path(
+    get,
+    path = "/api/trading/competitions/{id}/standings",
+    tag = "trading",
+    params(("id" = String, Path, description = "Competition ID")),
+    responses(
+        (
+            status = 200,
+            description = "Competition standings",
+            body = ApiResponse<CompetitionStandings>
+        ),
+        (status = 404, description = "Competition not found", body = ErrorResponse)
+    )
+)]
+async fn get_competition_standings(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ApiResponse<CompetitionStandings>>, TradingError> {
+    let standings = state.trading_service.get_competition_standings(&id)?;
+    Ok(Json(ApiResponse { data: standings }))
+}
+
 // =============================================================================
 // Leaderboard Handlers
 // =============================================================================
 
 /// GET /api/trading/leaderboard
 ///
-/// Get leaderboard of portfolios ranked by total return percentage.
+/// Get leaderboard of portfolios ranked by total return percentage,
+/// optionally over a daily or weekly window.
+#[utoipa::path(
+    get,
+    path = "/api/trading/leaderboard",
+    tag = "trading",
+    params(LeaderboardQuery),
+    responses(
+        (
+            status = 200,
+            description = "Ranked portfolios",
+            body = ApiResponse<Vec<LeaderboardEntry>>
+        ),
+        (status = 400, description = "Invalid window", body = ErrorResponse)
+    )
+)]
 async fn get_leaderboard(
     State(state): State<AppState>,
     Query(query): Query<LeaderboardQuery>,
-) -> Json<ApiResponse<Vec<LeaderboardEntry>>> {
+) -> Result<Json<ApiResponse<Vec<LeaderboardEntry>>>, (StatusCode, Json<ErrorResponse>)> {
+    let window = match query.window.as_deref() {
+        None => LeaderboardWindow::AllTime,
+        Some(w) => LeaderboardWindow::parse(w).ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: format!("Invalid window '{}': expected daily, weekly or all_time", w),
+                    code: "INVALID_WINDOW".to_string(),
+                }),
+            )
+        })?,
+    };
+
     let limit = query.limit.unwrap_or(100);
-    let leaderboard = state.trading_service.get_leaderboard(limit);
-    Json(ApiResponse { data: leaderboard })
+    let leaderboard = state.trading_service.get_windowed_leaderboard(window, limit);
+    Ok(Json(ApiResponse { data: leaderboard }))
 }
 
 // =============================================================================
@@ -1,7 +1,67 @@
+use crate::types::ChartResolution;
+use arc_swap::ArcSwap;
+use serde::Deserialize;
 use std::env;
+use std::path::Path;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::{info, warn};
+
+/// Live configuration handle shared with handlers; swapped on SIGHUP.
+pub type SharedConfig = Arc<ArcSwap<Config>>;
+
+/// A single problem found by [`Config::validate`].
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ConfigError {
+    #[error("{var} is not a valid number: {value:?}")]
+    InvalidNumber { var: &'static str, value: String },
+    #[error("{field} out of range: {reason}")]
+    OutOfRange { field: &'static str, reason: String },
+    #[error("{field} is required when {when}")]
+    MissingRequired { field: &'static str, when: &'static str },
+    #[error("{field} is not a valid URL ({expected}): {value:?}")]
+    InvalidUrl {
+        field: String,
+        value: String,
+        expected: String,
+    },
+    #[error("{field} is not a host:port address: {value:?}")]
+    InvalidAddress { field: String, value: String },
+    #[error("failed to read config file {path}: {reason}")]
+    FileRead { path: String, reason: String },
+    #[error("failed to parse config file {path}: {reason}")]
+    FileParse { path: String, reason: String },
+}
+
+/// Symbols streamed from Kraken's trade feed when none are configured.
+const DEFAULT_KRAKEN_WS_SYMBOLS: &[&str] = &["btc", "eth", "sol", "xrp", "doge"];
+
+/// Symbols with a live Coinbase level2 order book when none are configured.
+const DEFAULT_COINBASE_L2_SYMBOLS: &[&str] = &["btc", "eth", "sol"];
+
+/// Returns whether an env var value parses as the expected numeric type.
+type NumericCheck = fn(&str) -> bool;
+
+/// Numeric env vars that silently fall back to a default when unparseable.
+const NUMERIC_ENV_VARS: &[(&str, NumericCheck)] = &[
+    ("PORT", |v| v.parse::<u16>().is_ok()),
+    ("PRICE_CHANGE_THRESHOLD", |v| v.parse::<f64>().is_ok()),
+    ("THROTTLE_MS", |v| v.parse::<u64>().is_ok()),
+    ("STALE_THRESHOLD_MS", |v| v.parse::<u64>().is_ok()),
+    ("CIRCUIT_BREAKER_PCT", |v| v.parse::<f64>().is_ok()),
+    ("CIRCUIT_BREAKER_WINDOW_MS", |v| v.parse::<u64>().is_ok()),
+    ("SQLITE_BACKUP_INTERVAL_SECS", |v| v.parse::<u64>().is_ok()),
+    ("SQLITE_BACKUP_RETAIN", |v| v.parse::<usize>().is_ok()),
+    ("SMTP_PORT", |v| v.parse::<u16>().is_ok()),
+    ("NOTIFICATION_MAX_ATTEMPTS", |v| v.parse::<u32>().is_ok()),
+    ("NOTIFICATION_RETRY_DELAY_MS", |v| v.parse::<u64>().is_ok()),
+    ("CHART_RETENTION_1M_SECS", |v| v.parse::<i64>().is_ok()),
+    ("CHART_RETENTION_5M_SECS", |v| v.parse::<i64>().is_ok()),
+    ("CHART_RETENTION_1H_SECS", |v| v.parse::<i64>().is_ok()),
+];
 
 /// Peer server configuration for mesh networking.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct PeerServerConfig {
     /// Unique server ID.
     pub id: String,
@@ -14,7 +74,7 @@ pub struct PeerServerConfig {
 }
 
 /// Bootstrap server configuration for initial mesh discovery.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct BootstrapServerConfig {
     /// Server ID.
     pub id: String,
@@ -31,6 +91,180 @@ pub struct MeshAuthConfig {
     pub require_auth: bool,
 }
 
+/// SQLite storage configuration.
+#[derive(Debug, Clone)]
+pub struct StorageConfig {
+    /// Path to the SQLite database file.
+    pub sqlite_path: String,
+    /// SQLCipher key; the database is encrypted at rest when set (requires
+    /// the `sqlcipher` build feature).
+    pub sqlite_key: Option<String>,
+    /// Directory for scheduled SQLite backups (backups disabled if None).
+    pub backup_dir: Option<String>,
+    /// Interval between scheduled backups (seconds).
+    pub backup_interval_secs: u64,
+    /// Number of most recent backups to keep.
+    pub backup_retain: usize,
+    /// Interval between scheduled portfolio equity snapshots (seconds).
+    pub snapshot_interval_secs: u64,
+    /// Days of portfolio equity snapshots to keep.
+    pub snapshot_retain_days: i64,
+}
+
+/// External notification delivery configuration.
+#[derive(Debug, Clone)]
+pub struct NotificationConfig {
+    /// SMTP relay host (email delivery disabled if None).
+    pub smtp_host: Option<String>,
+    /// SMTP port (STARTTLS).
+    pub smtp_port: u16,
+    /// SMTP username.
+    pub smtp_username: Option<String>,
+    /// SMTP password.
+    pub smtp_password: Option<String>,
+    /// Sender address for notification emails.
+    pub smtp_from: Option<String>,
+    /// URL notifications are POSTed to (webhook delivery disabled if None).
+    pub webhook_url: Option<String>,
+    /// Attempts per delivery before giving up.
+    pub max_attempts: u32,
+    /// Delay before the first retry in milliseconds; doubled after each failure.
+    pub retry_delay_ms: u64,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            smtp_host: None,
+            smtp_port: 587,
+            smtp_username: None,
+            smtp_password: None,
+            smtp_from: None,
+            webhook_url: None,
+            max_attempts: 3,
+            retry_delay_ms: 2000,
+        }
+    }
+}
+
+/// How long `ChartStore` keeps candles at each resolution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChartRetentionConfig {
+    /// Retention for 1-minute candles (seconds).
+    pub one_minute_secs: i64,
+    /// Retention for 5-minute candles (seconds).
+    pub five_minute_secs: i64,
+    /// Retention for 1-hour candles (seconds).
+    pub one_hour_secs: i64,
+}
+
+impl ChartRetentionConfig {
+    /// Retention for `resolution` in seconds.
+    pub fn seconds(&self, resolution: ChartResolution) -> i64 {
+        match resolution {
+            ChartResolution::OneMinute => self.one_minute_secs,
+            ChartResolution::FiveMinute => self.five_minute_secs,
+            ChartResolution::OneHour => self.one_hour_secs,
+        }
+    }
+}
+
+impl Default for ChartRetentionConfig {
+    fn default() -> Self {
+        Self {
+            one_minute_secs: ChartResolution::OneMinute.retention_seconds(),
+            five_minute_secs: ChartResolution::FiveMinute.retention_seconds(),
+            one_hour_secs: ChartResolution::OneHour.retention_seconds(),
+        }
+    }
+}
+
+/// On-disk TOML configuration; every field is optional and env vars win.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileConfig {
+    host: Option<String>,
+    port: Option<u16>,
+    redis_url: Option<String>,
+    price_fanout_enabled: Option<bool>,
+    chart_redis_compression: Option<bool>,
+    cors_origins: Option<Vec<String>>,
+    cmc_api_key: Option<String>,
+    coingecko_api_key: Option<String>,
+    cryptocompare_api_key: Option<String>,
+    binance_api_key: Option<String>,
+    kraken_api_key: Option<String>,
+    kraken_ws_symbols: Option<Vec<String>>,
+    coinbase_l2_symbols: Option<Vec<String>>,
+    kucoin_api_key: Option<String>,
+    okx_api_key: Option<String>,
+    huobi_api_key: Option<String>,
+    finnhub_api_key: Option<String>,
+    alpha_vantage_api_key: Option<String>,
+    alpaca_api_key: Option<String>,
+    alpaca_api_secret: Option<String>,
+    tiingo_api_key: Option<String>,
+    price_change_threshold: Option<f64>,
+    throttle_ms: Option<u64>,
+    stale_threshold_ms: Option<u64>,
+    circuit_breaker_pct: Option<f64>,
+    circuit_breaker_window_ms: Option<u64>,
+    server_id: Option<String>,
+    server_region: Option<String>,
+    peer_servers: Option<Vec<PeerServerConfig>>,
+    bootstrap_servers: Option<Vec<BootstrapServerConfig>>,
+    public_ws_url: Option<String>,
+    public_api_url: Option<String>,
+    mesh_auth: FileMeshAuthConfig,
+    storage: FileStorageConfig,
+    notifications: FileNotificationConfig,
+    chart_retention: FileChartRetentionConfig,
+}
+
+/// `[mesh_auth]` section of the TOML config.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileMeshAuthConfig {
+    shared_key: Option<String>,
+    require_auth: Option<bool>,
+}
+
+/// `[storage]` section of the TOML config.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileStorageConfig {
+    sqlite_path: Option<String>,
+    sqlite_key: Option<String>,
+    backup_dir: Option<String>,
+    backup_interval_secs: Option<u64>,
+    backup_retain: Option<usize>,
+    snapshot_interval_secs: Option<u64>,
+    snapshot_retain_days: Option<i64>,
+}
+
+/// `[notifications]` section of the TOML config.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileNotificationConfig {
+    smtp_host: Option<String>,
+    smtp_port: Option<u16>,
+    smtp_username: Option<String>,
+    smtp_password: Option<String>,
+    smtp_from: Option<String>,
+    webhook_url: Option<String>,
+    max_attempts: Option<u32>,
+    retry_delay_ms: Option<u64>,
+}
+
+/// `[chart_retention]` section of the TOML config.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileChartRetentionConfig {
+    one_minute_secs: Option<i64>,
+    five_minute_secs: Option<i64>,
+    one_hour_secs: Option<i64>,
+}
+
 /// Application configuration.
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -40,6 +274,12 @@ pub struct Config {
     pub port: u16,
     /// Redis URL for persistent caching.
     pub redis_url: Option<String>,
+    /// Share price updates with other servers over Redis pub/sub.
+    pub price_fanout_enabled: bool,
+    /// Compress sparklines persisted to Redis.
+    pub chart_redis_compression: bool,
+    /// Origins allowed to make cross-origin requests (empty allows any).
+    pub cors_origins: Vec<String>,
     /// CoinMarketCap API key.
     pub cmc_api_key: Option<String>,
     /// CoinGecko API key (optional, for pro tier).
@@ -50,6 +290,10 @@ pub struct Config {
     pub binance_api_key: Option<String>,
     /// Kraken API key (optional).
     pub kraken_api_key: Option<String>,
+    /// Symbols streamed from Kraken's WebSocket trade feed (empty disables it).
+    pub kraken_ws_symbols: Vec<String>,
+    /// Symbols with a live Coinbase level2 order book (empty disables the feed).
+    pub coinbase_l2_symbols: Vec<String>,
     /// KuCoin API key (optional, public endpoints work without).
     pub kucoin_api_key: Option<String>,
     /// OKX API key (optional).
@@ -72,6 +316,12 @@ pub struct Config {
     pub throttle_ms: u64,
     /// Stale threshold for price sources (ms).
     pub stale_threshold_ms: u64,
+    /// Deviation from the other sources (percent) that trips a symbol's price
+    /// circuit breaker; 0 disables it.
+    pub circuit_breaker_pct: f64,
+    /// How recent other sources' prices must be to count as consensus, and
+    /// how long a quarantined price waits for corroboration (ms).
+    pub circuit_breaker_window_ms: u64,
     /// This server's unique ID for peer mesh.
     pub server_id: String,
     /// This server's region/location.
@@ -86,11 +336,45 @@ pub struct Config {
     pub public_api_url: String,
     /// Mesh authentication configuration.
     pub mesh_auth: MeshAuthConfig,
+    /// SQLite storage configuration.
+    pub storage: StorageConfig,
+    /// External notification delivery configuration.
+    pub notifications: NotificationConfig,
+    /// Chart candle retention per resolution.
+    pub chart_retention: ChartRetentionConfig,
 }
 
 impl Config {
     /// Load configuration from environment variables.
     pub fn from_env() -> Self {
+        Self::layered(FileConfig::default())
+    }
+
+    /// Load configuration from a TOML file, with environment variables taking
+    /// precedence over values set in the file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| ConfigError::FileRead {
+            path: path.display().to_string(),
+            reason: e.to_string(),
+        })?;
+        let file: FileConfig = toml::from_str(&contents).map_err(|e| ConfigError::FileParse {
+            path: path.display().to_string(),
+            reason: e.to_string(),
+        })?;
+        Ok(Self::layered(file))
+    }
+
+    /// Load from the file named by `HAUNT_CONFIG` if set, otherwise from env only.
+    pub fn load() -> Result<Self, ConfigError> {
+        match env::var("HAUNT_CONFIG") {
+            Ok(path) if !path.is_empty() => Self::from_file(path),
+            _ => Ok(Self::from_env()),
+        }
+    }
+
+    /// Build the config from env vars, falling back to `file` and then defaults.
+    fn layered(file: FileConfig) -> Self {
         // Parse peer servers from PEER_SERVERS env var
         // Format: "id|region|ws_url|api_url,id2|region2|ws_url2|api_url2"
         let peer_servers = env::var("PEER_SERVERS")
@@ -112,6 +396,7 @@ impl Config {
                     })
                     .collect()
             })
+            .or(file.peer_servers)
             .unwrap_or_default();
 
         // Parse bootstrap servers from MESH_BOOTSTRAP_SERVERS env var
@@ -133,71 +418,435 @@ impl Config {
                     })
                     .collect()
             })
+            .or(file.bootstrap_servers)
             .unwrap_or_default();
 
-        let host = env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string());
-        let port: u16 = env::var("PORT")
-            .ok()
-            .and_then(|p| p.parse().ok())
-            .unwrap_or(3001);
+        // Symbols for the Kraken trade feed and the Coinbase level2 book,
+        // e.g. "btc,eth,sol". An empty value disables the feed.
+        let kraken_ws_symbols = env_symbols(
+            "KRAKEN_WS_SYMBOLS",
+            file.kraken_ws_symbols,
+            DEFAULT_KRAKEN_WS_SYMBOLS,
+        );
+        let coinbase_l2_symbols = env_symbols(
+            "COINBASE_L2_SYMBOLS",
+            file.coinbase_l2_symbols,
+            DEFAULT_COINBASE_L2_SYMBOLS,
+        );
+
+        let host = env_string("HOST", file.host).unwrap_or_else(|| "0.0.0.0".to_string());
+        let port: u16 = env_parse("PORT", file.port).unwrap_or(3001);
 
         // Public URLs for mesh announcements (defaults to local address)
-        let public_ws_url =
-            env::var("PUBLIC_WS_URL").unwrap_or_else(|_| format!("ws://{}:{}/ws", host, port));
-        let public_api_url =
-            env::var("PUBLIC_API_URL").unwrap_or_else(|_| format!("http://{}:{}", host, port));
+        let public_ws_url = env_string("PUBLIC_WS_URL", file.public_ws_url)
+            .unwrap_or_else(|| format!("ws://{}:{}/ws", host, port));
+        let public_api_url = env_string("PUBLIC_API_URL", file.public_api_url)
+            .unwrap_or_else(|| format!("http://{}:{}", host, port));
 
         Self {
             host,
             port,
-            redis_url: env::var("REDIS_URL")
-                .ok()
+            redis_url: env_string("REDIS_URL", file.redis_url)
                 .or_else(|| Some("redis://127.0.0.1:6379".to_string())),
-            cmc_api_key: env::var("CMC_API_KEY").ok(),
-            coingecko_api_key: env::var("COINGECKO_API_KEY").ok(),
-            cryptocompare_api_key: env::var("CRYPTOCOMPARE_API_KEY").ok(),
-            binance_api_key: env::var("BINANCE_API_KEY").ok(),
-            kraken_api_key: env::var("KRAKEN_API_KEY").ok(),
-            kucoin_api_key: env::var("KUCOIN_API_KEY").ok(),
-            okx_api_key: env::var("OKX_API_KEY").ok(),
-            huobi_api_key: env::var("HUOBI_API_KEY").ok(),
-            finnhub_api_key: env::var("FINNHUB_API_KEY").ok(),
-            alpha_vantage_api_key: env::var("ALPHA_VANTAGE_API_KEY").ok(),
-            alpaca_api_key: env::var("ALPACA_API_KEY").ok(),
-            alpaca_api_secret: env::var("ALPACA_API_SECRET").ok(),
-            tiingo_api_key: env::var("TIINGO_API_KEY").ok(),
-            price_change_threshold: env::var("PRICE_CHANGE_THRESHOLD")
-                .ok()
-                .and_then(|v| v.parse().ok())
+            price_fanout_enabled: env_bool("PRICE_FANOUT_ENABLED", file.price_fanout_enabled)
+                .unwrap_or(false),
+            chart_redis_compression: env_bool(
+                "CHART_REDIS_COMPRESSION",
+                file.chart_redis_compression,
+            )
+            .unwrap_or(true),
+            cors_origins: env_symbols("CORS_ORIGINS", file.cors_origins, &[]),
+            cmc_api_key: env_string("CMC_API_KEY", file.cmc_api_key),
+            coingecko_api_key: env_string("COINGECKO_API_KEY", file.coingecko_api_key),
+            cryptocompare_api_key: env_string("CRYPTOCOMPARE_API_KEY", file.cryptocompare_api_key),
+            binance_api_key: env_string("BINANCE_API_KEY", file.binance_api_key),
+            kraken_api_key: env_string("KRAKEN_API_KEY", file.kraken_api_key),
+            kraken_ws_symbols,
+            coinbase_l2_symbols,
+            kucoin_api_key: env_string("KUCOIN_API_KEY", file.kucoin_api_key),
+            okx_api_key: env_string("OKX_API_KEY", file.okx_api_key),
+            huobi_api_key: env_string("HUOBI_API_KEY", file.huobi_api_key),
+            finnhub_api_key: env_string("FINNHUB_API_KEY", file.finnhub_api_key),
+            alpha_vantage_api_key: env_string("ALPHA_VANTAGE_API_KEY", file.alpha_vantage_api_key),
+            alpaca_api_key: env_string("ALPACA_API_KEY", file.alpaca_api_key),
+            alpaca_api_secret: env_string("ALPACA_API_SECRET", file.alpaca_api_secret),
+            tiingo_api_key: env_string("TIINGO_API_KEY", file.tiingo_api_key),
+            price_change_threshold: env_parse("PRICE_CHANGE_THRESHOLD", file.price_change_threshold)
                 .unwrap_or(0.01),
-            throttle_ms: env::var("THROTTLE_MS")
-                .ok()
-                .and_then(|v| v.parse().ok())
-                .unwrap_or(100),
-            stale_threshold_ms: env::var("STALE_THRESHOLD_MS")
-                .ok()
-                .and_then(|v| v.parse().ok())
+            throttle_ms: env_parse("THROTTLE_MS", file.throttle_ms).unwrap_or(100),
+            stale_threshold_ms: env_parse("STALE_THRESHOLD_MS", file.stale_threshold_ms)
                 .unwrap_or(120_000),
-            server_id: env::var("SERVER_ID").unwrap_or_else(|_| {
+            circuit_breaker_pct: env_parse("CIRCUIT_BREAKER_PCT", file.circuit_breaker_pct)
+                .unwrap_or(20.0),
+            circuit_breaker_window_ms: env_parse(
+                "CIRCUIT_BREAKER_WINDOW_MS",
+                file.circuit_breaker_window_ms,
+            )
+            .unwrap_or(10_000),
+            server_id: env_string("SERVER_ID", file.server_id).unwrap_or_else(|| {
                 // Generate a random ID if not specified
                 uuid::Uuid::new_v4().to_string()
             }),
-            server_region: env::var("SERVER_REGION").unwrap_or_else(|_| "unknown".to_string()),
+            server_region: env_string("SERVER_REGION", file.server_region)
+                .unwrap_or_else(|| "unknown".to_string()),
             peer_servers,
             bootstrap_servers,
             public_ws_url,
             public_api_url,
             mesh_auth: MeshAuthConfig {
-                shared_key: env::var("MESH_SHARED_KEY").unwrap_or_default(),
-                require_auth: env::var("MESH_REQUIRE_AUTH")
-                    .ok()
-                    .map(|v| v == "true" || v == "1")
+                shared_key: env_string("MESH_SHARED_KEY", file.mesh_auth.shared_key)
+                    .unwrap_or_default(),
+                require_auth: env_bool("MESH_REQUIRE_AUTH", file.mesh_auth.require_auth)
                     .unwrap_or(false),
             },
+            storage: StorageConfig {
+                sqlite_path: env_string("SQLITE_PATH", file.storage.sqlite_path)
+                    .unwrap_or_else(|| "haunt.db".to_string()),
+                sqlite_key: env_string("SQLITE_KEY", file.storage.sqlite_key)
+                    .filter(|key| !key.is_empty()),
+                backup_dir: env_string("SQLITE_BACKUP_DIR", file.storage.backup_dir),
+                backup_interval_secs: env_parse(
+                    "SQLITE_BACKUP_INTERVAL_SECS",
+                    file.storage.backup_interval_secs,
+                )
+                .unwrap_or(3600),
+                backup_retain: env_parse("SQLITE_BACKUP_RETAIN", file.storage.backup_retain)
+                    .unwrap_or(7),
+                snapshot_interval_secs: env_parse(
+                    "SNAPSHOT_INTERVAL_SECS",
+                    file.storage.snapshot_interval_secs,
+                )
+                .unwrap_or(300),
+                snapshot_retain_days: env_parse(
+                    "SNAPSHOT_RETAIN_DAYS",
+                    file.storage.snapshot_retain_days,
+                )
+                .unwrap_or(90),
+            },
+            notifications: NotificationConfig {
+                smtp_host: env_string("SMTP_HOST", file.notifications.smtp_host),
+                smtp_port: env_parse("SMTP_PORT", file.notifications.smtp_port).unwrap_or(587),
+                smtp_username: env_string("SMTP_USERNAME", file.notifications.smtp_username),
+                smtp_password: env_string("SMTP_PASSWORD", file.notifications.smtp_password),
+                smtp_from: env_string("SMTP_FROM", file.notifications.smtp_from),
+                webhook_url: env_string(
+                    "NOTIFICATION_WEBHOOK_URL",
+                    file.notifications.webhook_url,
+                ),
+                max_attempts: env_parse(
+                    "NOTIFICATION_MAX_ATTEMPTS",
+                    file.notifications.max_attempts,
+                )
+                .unwrap_or(3),
+                retry_delay_ms: env_parse(
+                    "NOTIFICATION_RETRY_DELAY_MS",
+                    file.notifications.retry_delay_ms,
+                )
+                .unwrap_or(2000),
+            },
+            chart_retention: {
+                let defaults = ChartRetentionConfig::default();
+                let file = file.chart_retention;
+                ChartRetentionConfig {
+                    one_minute_secs: env_parse("CHART_RETENTION_1M_SECS", file.one_minute_secs)
+                        .unwrap_or(defaults.one_minute_secs),
+                    five_minute_secs: env_parse("CHART_RETENTION_5M_SECS", file.five_minute_secs)
+                        .unwrap_or(defaults.five_minute_secs),
+                    one_hour_secs: env_parse("CHART_RETENTION_1H_SECS", file.one_hour_secs)
+                        .unwrap_or(defaults.one_hour_secs),
+                }
+            },
         }
     }
 }
 
+/// Read a string env var, falling back to the file value.
+fn env_string(var: &str, file: Option<String>) -> Option<String> {
+    env::var(var).ok().or(file)
+}
+
+/// Read and parse an env var, falling back to the file value.
+fn env_parse<T: std::str::FromStr>(var: &str, file: Option<T>) -> Option<T> {
+    env::var(var).ok().and_then(|v| v.parse().ok()).or(file)
+}
+
+/// Read a boolean env var ("true" or "1"), falling back to the file value.
+fn env_bool(var: &str, file: Option<bool>) -> Option<bool> {
+    env::var(var).ok().map(|v| v == "true" || v == "1").or(file)
+}
+
+/// Read a comma-separated symbol list env var, falling back to the file
+/// value and then `default`. Symbols are trimmed and lowercased.
+fn env_symbols(var: &str, file: Option<Vec<String>>, default: &[&str]) -> Vec<String> {
+    env::var(var)
+        .ok()
+        .map(|s| {
+            s.split(',')
+                .map(|symbol| symbol.trim().to_lowercase())
+                .filter(|symbol| !symbol.is_empty())
+                .collect()
+        })
+        .or(file)
+        .unwrap_or_else(|| default.iter().map(|symbol| symbol.to_string()).collect())
+}
+
+impl Config {
+    /// Check the configuration for bad values, returning every problem found.
+    pub fn validate(&self) -> Result<(), Vec<ConfigError>> {
+        let mut errors = Vec::new();
+
+        // Numeric env vars that failed to parse were replaced by defaults
+        for (var, parses) in NUMERIC_ENV_VARS {
+            if let Ok(value) = env::var(var) {
+                if !parses(value.trim()) {
+                    errors.push(ConfigError::InvalidNumber { var, value });
+                }
+            }
+        }
+
+        if self.port == 0 {
+            errors.push(ConfigError::OutOfRange {
+                field: "port",
+                reason: "must be between 1 and 65535".to_string(),
+            });
+        }
+        if !(0.0..=100.0).contains(&self.price_change_threshold) {
+            errors.push(ConfigError::OutOfRange {
+                field: "price_change_threshold",
+                reason: format!("{} is not a percentage in 0..=100", self.price_change_threshold),
+            });
+        }
+        if self.stale_threshold_ms == 0 {
+            errors.push(ConfigError::OutOfRange {
+                field: "stale_threshold_ms",
+                reason: "must be greater than 0".to_string(),
+            });
+        }
+        if !(self.circuit_breaker_pct >= 0.0 && self.circuit_breaker_pct.is_finite()) {
+            errors.push(ConfigError::OutOfRange {
+                field: "circuit_breaker_pct",
+                reason: format!("{} is not a non-negative percentage", self.circuit_breaker_pct),
+            });
+        }
+        if self.circuit_breaker_pct > 0.0 && self.circuit_breaker_window_ms == 0 {
+            errors.push(ConfigError::OutOfRange {
+                field: "circuit_breaker_window_ms",
+                reason: "must be greater than 0 while the circuit breaker is enabled".to_string(),
+            });
+        }
+        if self.storage.sqlite_path.trim().is_empty() {
+            errors.push(ConfigError::MissingRequired {
+                field: "SQLITE_PATH",
+                when: "storage is enabled",
+            });
+        }
+        if self.storage.backup_dir.is_some() {
+            if self.storage.backup_interval_secs < 60 {
+                errors.push(ConfigError::OutOfRange {
+                    field: "storage.backup_interval_secs",
+                    reason: format!("{} is below the 60s minimum", self.storage.backup_interval_secs),
+                });
+            }
+            if self.storage.backup_retain == 0 {
+                errors.push(ConfigError::OutOfRange {
+                    field: "storage.backup_retain",
+                    reason: "must keep at least one backup".to_string(),
+                });
+            }
+        }
+
+        if self.storage.snapshot_interval_secs < 10 {
+            errors.push(ConfigError::OutOfRange {
+                field: "storage.snapshot_interval_secs",
+                reason: format!("{} is below the 10s minimum", self.storage.snapshot_interval_secs),
+            });
+        }
+        if self.storage.snapshot_retain_days < 1 {
+            errors.push(ConfigError::OutOfRange {
+                field: "storage.snapshot_retain_days",
+                reason: "must keep at least one day of snapshots".to_string(),
+            });
+        }
+
+        // Every resolution must keep at least one candle
+        for (field, resolution) in [
+            ("chart_retention.one_minute_secs", ChartResolution::OneMinute),
+            ("chart_retention.five_minute_secs", ChartResolution::FiveMinute),
+            ("chart_retention.one_hour_secs", ChartResolution::OneHour),
+        ] {
+            let retention = self.chart_retention.seconds(resolution);
+            if retention < resolution.seconds() {
+                errors.push(ConfigError::OutOfRange {
+                    field,
+                    reason: format!(
+                        "{} is shorter than one {}s candle",
+                        retention,
+                        resolution.seconds()
+                    ),
+                });
+            }
+        }
+
+        // Mutually required settings
+        let mesh_configured = !self.peer_servers.is_empty() || !self.bootstrap_servers.is_empty();
+        if mesh_configured && self.server_id.trim().is_empty() {
+            errors.push(ConfigError::MissingRequired {
+                field: "SERVER_ID",
+                when: "peer mesh servers are configured",
+            });
+        }
+        if self.mesh_auth.require_auth && self.mesh_auth.shared_key.is_empty() {
+            errors.push(ConfigError::MissingRequired {
+                field: "MESH_SHARED_KEY",
+                when: "MESH_REQUIRE_AUTH is enabled",
+            });
+        }
+        if self.alpaca_api_key.is_some() != self.alpaca_api_secret.is_some() {
+            errors.push(ConfigError::MissingRequired {
+                field: "ALPACA_API_KEY/ALPACA_API_SECRET",
+                when: "either one is set",
+            });
+        }
+
+        if self.notifications.smtp_host.is_some() && self.notifications.smtp_from.is_none() {
+            errors.push(ConfigError::MissingRequired {
+                field: "SMTP_FROM",
+                when: "SMTP_HOST is set",
+            });
+        }
+
+        // URL formats
+        if let Some(ref url) = self.redis_url {
+            check_url(&mut errors, "REDIS_URL", url, &["redis", "rediss"]);
+        }
+        check_url(&mut errors, "PUBLIC_WS_URL", &self.public_ws_url, &["ws", "wss"]);
+        check_url(&mut errors, "PUBLIC_API_URL", &self.public_api_url, &["http", "https"]);
+        for origin in &self.cors_origins {
+            check_url(&mut errors, "CORS_ORIGINS", origin, &["http", "https"]);
+        }
+        if let Some(ref url) = self.notifications.webhook_url {
+            check_url(&mut errors, "NOTIFICATION_WEBHOOK_URL", url, &["http", "https"]);
+        }
+        for peer in &self.peer_servers {
+            let ws_field = format!("PEER_SERVERS[{}].ws_url", peer.id);
+            check_url(&mut errors, &ws_field, &peer.ws_url, &["ws", "wss"]);
+            let api_field = format!("PEER_SERVERS[{}].api_url", peer.id);
+            check_url(&mut errors, &api_field, &peer.api_url, &["http", "https"]);
+        }
+        for server in &self.bootstrap_servers {
+            let valid = server
+                .address
+                .rsplit_once(':')
+                .map(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok())
+                .unwrap_or(false);
+            if !valid {
+                errors.push(ConfigError::InvalidAddress {
+                    field: format!("MESH_BOOTSTRAP_SERVERS[{}]", server.id),
+                    value: server.address.clone(),
+                });
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Whether a browser request from `origin` passes CORS.
+    pub fn allows_origin(&self, origin: &str) -> bool {
+        self.cors_origins.is_empty()
+            || self
+                .cors_origins
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(origin))
+    }
+
+    /// Merge a freshly loaded config into this one.
+    ///
+    /// Only the reloadable subset (CORS origins, mesh auth, backup and
+    /// snapshot schedules) is taken from `fresh`; fields that are bound at
+    /// startup keep their current value and log a warning if they changed.
+    pub fn reloaded(&self, fresh: Config) -> Config {
+        let mut ignored = Vec::new();
+        if fresh.host != self.host || fresh.port != self.port {
+            ignored.push("HOST/PORT");
+        }
+        if fresh.redis_url != self.redis_url {
+            ignored.push("REDIS_URL");
+        }
+        if fresh.storage.sqlite_path != self.storage.sqlite_path {
+            ignored.push("SQLITE_PATH");
+        }
+        if fresh.storage.sqlite_key != self.storage.sqlite_key {
+            ignored.push("SQLITE_KEY");
+        }
+        if fresh.storage.backup_dir != self.storage.backup_dir {
+            ignored.push("SQLITE_BACKUP_DIR");
+        }
+        if fresh.chart_retention != self.chart_retention {
+            ignored.push("CHART_RETENTION_*");
+        }
+        for name in ignored {
+            warn!("Config reload: {} changed but requires a restart; ignoring", name);
+        }
+
+        let mut next = self.clone();
+        next.cors_origins = fresh.cors_origins;
+        next.mesh_auth = fresh.mesh_auth;
+        next.storage.backup_interval_secs = fresh.storage.backup_interval_secs;
+        next.storage.backup_retain = fresh.storage.backup_retain;
+        next.storage.snapshot_interval_secs = fresh.storage.snapshot_interval_secs;
+        next.storage.snapshot_retain_days = fresh.storage.snapshot_retain_days;
+        next
+    }
+}
+
+/// Push an error unless `value` is a URL with one of `schemes` and a host.
+fn check_url(errors: &mut Vec<ConfigError>, field: &str, value: &str, schemes: &[&str]) {
+    let valid = value
+        .split_once("://")
+        .map(|(scheme, rest)| {
+            schemes.contains(&scheme) && !rest.is_empty() && !rest.starts_with('/')
+        })
+        .unwrap_or(false);
+    if !valid {
+        errors.push(ConfigError::InvalidUrl {
+            field: field.to_string(),
+            value: value.to_string(),
+            expected: schemes
+                .iter()
+                .map(|s| format!("{}://", s))
+                .collect::<Vec<_>>()
+                .join(" or "),
+        });
+    }
+}
+
+/// Re-read the config file and environment and swap the reloadable fields into `shared`.
+///
+/// The reload is skipped if the resulting config fails validation.
+pub fn reload(shared: &ArcSwap<Config>) {
+    let fresh = match Config::load() {
+        Ok(fresh) => fresh,
+        Err(e) => {
+            warn!("Config reload rejected: {}", e);
+            return;
+        }
+    };
+    let current = shared.load_full();
+    let next = current.reloaded(fresh);
+    if let Err(errors) = next.validate() {
+        for e in &errors {
+            warn!("Config reload rejected: {}", e);
+        }
+        return;
+    }
+    shared.store(Arc::new(next));
+    info!("Configuration reloaded");
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self::from_env()
@@ -207,6 +856,62 @@ impl Default for Config {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Mutex;
+
+    /// Serializes tests that mutate process environment variables.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    fn valid_config() -> Config {
+        Config {
+            host: "0.0.0.0".to_string(),
+            port: 3001,
+            redis_url: Some("redis://127.0.0.1:6379".to_string()),
+            price_fanout_enabled: false,
+            cors_origins: vec![],
+            chart_redis_compression: true,
+            cmc_api_key: None,
+            coingecko_api_key: None,
+            cryptocompare_api_key: None,
+            binance_api_key: None,
+            kraken_api_key: None,
+            kraken_ws_symbols: vec![],
+            coinbase_l2_symbols: vec![],
+            kucoin_api_key: None,
+            okx_api_key: None,
+            huobi_api_key: None,
+            finnhub_api_key: None,
+            alpha_vantage_api_key: None,
+            alpaca_api_key: None,
+            alpaca_api_secret: None,
+            tiingo_api_key: None,
+            price_change_threshold: 0.01,
+            throttle_ms: 100,
+            stale_threshold_ms: 120_000,
+            circuit_breaker_pct: 20.0,
+            circuit_breaker_window_ms: 10_000,
+            server_id: "us-east".to_string(),
+            server_region: "US East".to_string(),
+            peer_servers: vec![],
+            bootstrap_servers: vec![],
+            public_ws_url: "ws://0.0.0.0:3001/ws".to_string(),
+            public_api_url: "http://0.0.0.0:3001".to_string(),
+            mesh_auth: MeshAuthConfig {
+                shared_key: String::new(),
+                require_auth: false,
+            },
+            storage: StorageConfig {
+                sqlite_path: "haunt.db".to_string(),
+                sqlite_key: None,
+                backup_dir: None,
+                backup_interval_secs: 3600,
+                backup_retain: 7,
+                snapshot_interval_secs: 300,
+                snapshot_retain_days: 90,
+            },
+            notifications: NotificationConfig::default(),
+            chart_retention: ChartRetentionConfig::default(),
+        }
+    }
 
     // =========================================================================
     // PeerServerConfig Tests
@@ -293,11 +998,16 @@ mod tests {
             host: "0.0.0.0".to_string(),
             port: 3001,
             redis_url: Some("redis://127.0.0.1:6379".to_string()),
+            price_fanout_enabled: false,
+            cors_origins: vec![],
+            chart_redis_compression: true,
             cmc_api_key: None,
             coingecko_api_key: None,
             cryptocompare_api_key: None,
             binance_api_key: None,
             kraken_api_key: None,
+            kraken_ws_symbols: vec![],
+            coinbase_l2_symbols: vec![],
             kucoin_api_key: None,
             okx_api_key: None,
             huobi_api_key: None,
@@ -309,6 +1019,8 @@ mod tests {
             price_change_threshold: 0.01,
             throttle_ms: 100,
             stale_threshold_ms: 120_000,
+            circuit_breaker_pct: 20.0,
+            circuit_breaker_window_ms: 10_000,
             server_id: "test-server".to_string(),
             server_region: "unknown".to_string(),
             peer_servers: vec![],
@@ -319,6 +1031,17 @@ mod tests {
                 shared_key: String::new(),
                 require_auth: false,
             },
+            storage: StorageConfig {
+                sqlite_path: "haunt.db".to_string(),
+                sqlite_key: None,
+                backup_dir: None,
+                backup_interval_secs: 3600,
+                backup_retain: 7,
+                snapshot_interval_secs: 300,
+                snapshot_retain_days: 90,
+            },
+            notifications: NotificationConfig::default(),
+            chart_retention: ChartRetentionConfig::default(),
         };
 
         assert_eq!(config.host, "0.0.0.0");
@@ -334,11 +1057,16 @@ mod tests {
             host: "localhost".to_string(),
             port: 8080,
             redis_url: None,
+            price_fanout_enabled: false,
+            cors_origins: vec![],
+            chart_redis_compression: true,
             cmc_api_key: Some("cmc-key".to_string()),
             coingecko_api_key: Some("gecko-key".to_string()),
             cryptocompare_api_key: Some("cc-key".to_string()),
             binance_api_key: Some("binance-key".to_string()),
             kraken_api_key: Some("kraken-key".to_string()),
+            kraken_ws_symbols: vec!["btc".to_string()],
+            coinbase_l2_symbols: vec!["eth".to_string()],
             kucoin_api_key: None,
             okx_api_key: None,
             huobi_api_key: None,
@@ -350,6 +1078,8 @@ mod tests {
             price_change_threshold: 0.05,
             throttle_ms: 200,
             stale_threshold_ms: 60_000,
+            circuit_breaker_pct: 20.0,
+            circuit_breaker_window_ms: 10_000,
             server_id: "prod-server".to_string(),
             server_region: "US East".to_string(),
             peer_servers: vec![],
@@ -360,6 +1090,17 @@ mod tests {
                 shared_key: "production-secret".to_string(),
                 require_auth: true,
             },
+            storage: StorageConfig {
+                sqlite_path: "haunt.db".to_string(),
+                sqlite_key: None,
+                backup_dir: None,
+                backup_interval_secs: 3600,
+                backup_retain: 7,
+                snapshot_interval_secs: 300,
+                snapshot_retain_days: 90,
+            },
+            notifications: NotificationConfig::default(),
+            chart_retention: ChartRetentionConfig::default(),
         };
 
         assert_eq!(config.cmc_api_key, Some("cmc-key".to_string()));
@@ -373,11 +1114,16 @@ mod tests {
             host: "0.0.0.0".to_string(),
             port: 3001,
             redis_url: None,
+            price_fanout_enabled: false,
+            cors_origins: vec![],
+            chart_redis_compression: true,
             cmc_api_key: None,
             coingecko_api_key: None,
             cryptocompare_api_key: None,
             binance_api_key: None,
             kraken_api_key: None,
+            kraken_ws_symbols: vec![],
+            coinbase_l2_symbols: vec![],
             kucoin_api_key: None,
             okx_api_key: None,
             huobi_api_key: None,
@@ -389,6 +1135,8 @@ mod tests {
             price_change_threshold: 0.01,
             throttle_ms: 100,
             stale_threshold_ms: 120_000,
+            circuit_breaker_pct: 20.0,
+            circuit_breaker_window_ms: 10_000,
             server_id: "us-east".to_string(),
             server_region: "US East".to_string(),
             peer_servers: vec![
@@ -412,6 +1160,17 @@ mod tests {
                 shared_key: String::new(),
                 require_auth: false,
             },
+            storage: StorageConfig {
+                sqlite_path: "haunt.db".to_string(),
+                sqlite_key: None,
+                backup_dir: None,
+                backup_interval_secs: 3600,
+                backup_retain: 7,
+                snapshot_interval_secs: 300,
+                snapshot_retain_days: 90,
+            },
+            notifications: NotificationConfig::default(),
+            chart_retention: ChartRetentionConfig::default(),
         };
 
         assert_eq!(config.peer_servers.len(), 2);
@@ -425,11 +1184,16 @@ mod tests {
             host: "test".to_string(),
             port: 1234,
             redis_url: Some("redis://test".to_string()),
+            price_fanout_enabled: false,
+            cors_origins: vec![],
+            chart_redis_compression: true,
             cmc_api_key: None,
             coingecko_api_key: None,
             cryptocompare_api_key: None,
             binance_api_key: None,
             kraken_api_key: None,
+            kraken_ws_symbols: vec![],
+            coinbase_l2_symbols: vec![],
             kucoin_api_key: None,
             okx_api_key: None,
             huobi_api_key: None,
@@ -441,6 +1205,8 @@ mod tests {
             price_change_threshold: 0.01,
             throttle_ms: 100,
             stale_threshold_ms: 120_000,
+            circuit_breaker_pct: 20.0,
+            circuit_breaker_window_ms: 10_000,
             server_id: "test".to_string(),
             server_region: "test".to_string(),
             peer_servers: vec![],
@@ -451,6 +1217,17 @@ mod tests {
                 shared_key: String::new(),
                 require_auth: false,
             },
+            storage: StorageConfig {
+                sqlite_path: "haunt.db".to_string(),
+                sqlite_key: None,
+                backup_dir: None,
+                backup_interval_secs: 3600,
+                backup_retain: 7,
+                snapshot_interval_secs: 300,
+                snapshot_retain_days: 90,
+            },
+            notifications: NotificationConfig::default(),
+            chart_retention: ChartRetentionConfig::default(),
         };
 
         let cloned = config.clone();
@@ -458,4 +1235,250 @@ mod tests {
         assert_eq!(cloned.port, config.port);
         assert_eq!(cloned.server_id, config.server_id);
     }
+
+    // =========================================================================
+    // Reload Tests
+    // =========================================================================
+
+    #[test]
+    fn test_reload_swaps_reloadable_fields_only() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        env::set_var("PORT", "4100");
+        env::set_var("MESH_SHARED_KEY", "before");
+        env::set_var("SQLITE_BACKUP_RETAIN", "3");
+        env::set_var("CORS_ORIGINS", "https://old.example");
+        let shared: SharedConfig = Arc::new(ArcSwap::from_pointee(Config::from_env()));
+        let handler_view = shared.clone();
+
+        env::set_var("PORT", "4200");
+        env::set_var("MESH_SHARED_KEY", "after");
+        env::set_var("SQLITE_BACKUP_RETAIN", "5");
+        env::set_var("CORS_ORIGINS", "https://new.example");
+        reload(&shared);
+
+        let config = handler_view.load();
+        assert_eq!(config.mesh_auth.shared_key, "after");
+        assert_eq!(config.storage.backup_retain, 5);
+        assert!(config.allows_origin("https://new.example"));
+        assert!(!config.allows_origin("https://old.example"));
+        // Bind address is not reloadable
+        assert_eq!(config.port, 4100);
+
+        env::remove_var("PORT");
+        env::remove_var("MESH_SHARED_KEY");
+        env::remove_var("SQLITE_BACKUP_RETAIN");
+        env::remove_var("CORS_ORIGINS");
+    }
+
+    // =========================================================================
+    // Validation Tests
+    // =========================================================================
+
+    #[test]
+    fn test_validate_accepts_valid_config() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        assert_eq!(valid_config().validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_reports_all_problems() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        let mut config = valid_config();
+        config.port = 0;
+        config.redis_url = Some("127.0.0.1:6379".to_string());
+        config.public_ws_url = "http://example.com/ws".to_string();
+
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(errors[0], ConfigError::OutOfRange { field: "port", .. }));
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, ConfigError::InvalidUrl { field, .. } if field == "REDIS_URL")));
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, ConfigError::InvalidUrl { field, .. } if field == "PUBLIC_WS_URL")));
+    }
+
+    #[test]
+    fn test_validate_mesh_requires_server_id_and_key() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        let mut config = valid_config();
+        config.server_id = String::new();
+        config.bootstrap_servers = vec![BootstrapServerConfig {
+            id: "seed".to_string(),
+            address: "seed.example.com".to_string(),
+        }];
+        config.mesh_auth.require_auth = true;
+
+        let errors = config.validate().unwrap_err();
+        assert!(errors.contains(&ConfigError::MissingRequired {
+            field: "SERVER_ID",
+            when: "peer mesh servers are configured",
+        }));
+        assert!(errors.contains(&ConfigError::MissingRequired {
+            field: "MESH_SHARED_KEY",
+            when: "MESH_REQUIRE_AUTH is enabled",
+        }));
+        assert!(errors.contains(&ConfigError::InvalidAddress {
+            field: "MESH_BOOTSTRAP_SERVERS[seed]".to_string(),
+            value: "seed.example.com".to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_validate_backup_settings() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        let mut config = valid_config();
+        config.storage.backup_dir = Some("/tmp/backups".to_string());
+        config.storage.backup_interval_secs = 10;
+        config.storage.backup_retain = 0;
+
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 2);
+    }
+
+    #[test]
+    fn test_validate_notification_settings() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        let mut config = valid_config();
+        config.notifications.smtp_host = Some("smtp.example.com".to_string());
+        config.notifications.webhook_url = Some("hooks.example.com/haunt".to_string());
+
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.contains(&ConfigError::MissingRequired {
+            field: "SMTP_FROM",
+            when: "SMTP_HOST is set",
+        }));
+    }
+
+    #[test]
+    fn test_validate_chart_retention() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        let mut config = valid_config();
+        config.chart_retention.one_minute_secs = 86_400;
+        assert!(config.validate().is_ok());
+
+        config.chart_retention.one_hour_secs = 600;
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0],
+            ConfigError::OutOfRange {
+                field: "chart_retention.one_hour_secs",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_validate_flags_unparseable_env_number() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        env::set_var("THROTTLE_MS", "10O");
+        let result = valid_config().validate();
+        env::remove_var("THROTTLE_MS");
+
+        assert_eq!(
+            result,
+            Err(vec![ConfigError::InvalidNumber {
+                var: "THROTTLE_MS",
+                value: "10O".to_string(),
+            }])
+        );
+    }
+
+    // =========================================================================
+    // TOML File Tests
+    // =========================================================================
+
+    const SAMPLE_TOML: &str = r#"
+host = "127.0.0.1"
+port = 4500
+redis_url = "redis://cache:6379"
+server_id = "eu-west"
+server_region = "EU West"
+throttle_ms = 250
+circuit_breaker_pct = 15.0
+
+[[peer_servers]]
+id = "us-east"
+region = "US East"
+ws_url = "wss://us.example.com/ws"
+api_url = "https://us.example.com"
+
+[[bootstrap_servers]]
+id = "seed"
+address = "seed.example.com:3001"
+
+[mesh_auth]
+shared_key = "file-secret"
+require_auth = true
+
+[storage]
+sqlite_path = "/var/lib/haunt/haunt.db"
+backup_retain = 3
+
+[chart_retention]
+one_minute_secs = 86400
+"#;
+
+    fn write_sample(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("haunt-{}-{}.toml", name, std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_from_file_populates_fields() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        let path = write_sample("populate", SAMPLE_TOML);
+        let config = Config::from_file(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(config.host, "127.0.0.1");
+        assert_eq!(config.port, 4500);
+        assert_eq!(config.redis_url.as_deref(), Some("redis://cache:6379"));
+        assert_eq!(config.server_id, "eu-west");
+        assert_eq!(config.throttle_ms, 250);
+        assert_eq!(config.circuit_breaker_pct, 15.0);
+        assert_eq!(config.peer_servers.len(), 1);
+        assert_eq!(config.peer_servers[0].api_url, "https://us.example.com");
+        assert_eq!(config.bootstrap_servers[0].address, "seed.example.com:3001");
+        assert_eq!(config.mesh_auth.shared_key, "file-secret");
+        assert!(config.mesh_auth.require_auth);
+        assert_eq!(config.storage.sqlite_path, "/var/lib/haunt/haunt.db");
+        assert_eq!(config.storage.backup_retain, 3);
+        assert_eq!(config.chart_retention.one_minute_secs, 86400);
+        // Unset values keep their defaults
+        assert_eq!(config.storage.backup_interval_secs, 3600);
+        assert_eq!(config.circuit_breaker_window_ms, 10_000);
+        assert_eq!(config.chart_retention.one_hour_secs, 7_776_000);
+        assert_eq!(config.public_api_url, "http://127.0.0.1:4500");
+    }
+
+    #[test]
+    fn test_env_overrides_file() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        let path = write_sample("override", SAMPLE_TOML);
+        env::set_var("PORT", "4600");
+        env::set_var("MESH_SHARED_KEY", "env-secret");
+        let config = Config::from_file(&path);
+        env::remove_var("PORT");
+        env::remove_var("MESH_SHARED_KEY");
+        std::fs::remove_file(&path).ok();
+
+        let config = config.unwrap();
+        assert_eq!(config.port, 4600);
+        assert_eq!(config.mesh_auth.shared_key, "env-secret");
+        assert_eq!(config.host, "127.0.0.1");
+    }
+
+    #[test]
+    fn test_from_file_rejects_unknown_keys() {
+        let path = write_sample("unknown", "prot = 80\n");
+        let result = Config::from_file(&path);
+        std::fs::remove_file(&path).ok();
+
+        assert!(matches!(result, Err(ConfigError::FileParse { .. })));
+    }
 }
@@ -21,6 +21,10 @@ pub enum AppError {
     #[error("External API error: {0}")]
     ExternalApi(String),
 
+    /// The data source backing this request is disabled (e.g. missing API key).
+    #[error("Data unavailable: {0}")]
+    SourceUnavailable(String),
+
     #[error("WebSocket error: {0}")]
     #[allow(dead_code)]
     WebSocket(String),
@@ -42,6 +46,7 @@ impl IntoResponse for AppError {
             AppError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
             AppError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg.clone()),
             AppError::ExternalApi(msg) => (StatusCode::BAD_GATEWAY, msg.clone()),
+            AppError::SourceUnavailable(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg.clone()),
             AppError::WebSocket(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg.clone()),
             AppError::Reqwest(e) => (StatusCode::BAD_GATEWAY, e.to_string()),
             AppError::SerdeJson(e) => (StatusCode::BAD_REQUEST, e.to_string()),
@@ -91,6 +96,12 @@ mod tests {
         assert_eq!(error.to_string(), "External API error: API rate limited");
     }
 
+    #[test]
+    fn test_source_unavailable_display() {
+        let error = AppError::SourceUnavailable("CoinMarketCap is disabled".to_string());
+        assert_eq!(error.to_string(), "Data unavailable: CoinMarketCap is disabled");
+    }
+
     #[test]
     fn test_websocket_display() {
         let error = AppError::WebSocket("Connection closed".to_string());
@@ -129,6 +140,13 @@ mod tests {
         assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
     }
 
+    #[test]
+    fn test_source_unavailable_status_code() {
+        let error = AppError::SourceUnavailable("Finnhub is disabled".to_string());
+        let response = error.into_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
     #[test]
     fn test_websocket_status_code() {
         let error = AppError::WebSocket("WS error".to_string());
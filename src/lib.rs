@@ -10,11 +10,19 @@ pub mod services {
 
     mod cache_impl {
         use dashmap::DashMap;
+        use serde::de::DeserializeOwned;
+        use serde::{Deserialize, Serialize};
+        use std::borrow::Borrow;
+        use std::hash::Hash;
+        use std::io;
+        use std::path::Path;
         use std::time::{Duration, Instant};
 
         /// A thread-safe cache with TTL support.
-        pub struct Cache<V> {
-            data: DashMap<String, CacheEntry<V>>,
+        ///
+        /// Keys are strings unless another key type is given, e.g. `Cache<f64, (String, u32)>`.
+        pub struct Cache<V, K = String> {
+            data: DashMap<K, CacheEntry<V>>,
             default_ttl: Duration,
         }
 
@@ -23,7 +31,16 @@ pub mod services {
             expires_at: Instant,
         }
 
-        impl<V: Clone> Cache<V> {
+        /// A live entry as written to a snapshot file.
+        #[derive(Serialize, Deserialize)]
+        struct SnapshotEntry<K, V> {
+            key: K,
+            value: V,
+            /// Wall-clock expiry (unix ms), so time spent offline counts against the TTL.
+            expires_at_ms: i64,
+        }
+
+        impl<V: Clone, K: Eq + Hash + Clone> Cache<V, K> {
             /// Create a new cache with the given default TTL.
             pub fn new(default_ttl: Duration) -> Self {
                 Self {
@@ -33,7 +50,11 @@ pub mod services {
             }
 
             /// Get a value from the cache.
-            pub fn get(&self, key: &str) -> Option<V> {
+            pub fn get<Q>(&self, key: &Q) -> Option<V>
+            where
+                K: Borrow<Q>,
+                Q: Eq + Hash + ?Sized,
+            {
                 let entry = self.data.get(key)?;
                 if entry.expires_at > Instant::now() {
                     Some(entry.value.clone())
@@ -45,12 +66,12 @@ pub mod services {
             }
 
             /// Set a value in the cache with the default TTL.
-            pub fn set(&self, key: String, value: V) {
+            pub fn set(&self, key: K, value: V) {
                 self.set_with_ttl(key, value, self.default_ttl);
             }
 
             /// Set a value in the cache with a custom TTL.
-            pub fn set_with_ttl(&self, key: String, value: V, ttl: Duration) {
+            pub fn set_with_ttl(&self, key: K, value: V, ttl: Duration) {
                 self.data.insert(
                     key,
                     CacheEntry {
@@ -61,12 +82,20 @@ pub mod services {
             }
 
             /// Check if a key exists and is not expired.
-            pub fn contains(&self, key: &str) -> bool {
+            pub fn contains<Q>(&self, key: &Q) -> bool
+            where
+                K: Borrow<Q>,
+                Q: Eq + Hash + ?Sized,
+            {
                 self.get(key).is_some()
             }
 
             /// Remove a value from the cache.
-            pub fn remove(&self, key: &str) -> Option<V> {
+            pub fn remove<Q>(&self, key: &Q) -> Option<V>
+            where
+                K: Borrow<Q>,
+                Q: Eq + Hash + ?Sized,
+            {
                 self.data.remove(key).map(|(_, entry)| entry.value)
             }
 
@@ -91,6 +120,58 @@ pub mod services {
                 self.data.is_empty()
             }
         }
+
+        impl<V, K> Cache<V, K>
+        where
+            K: Eq + Hash + Clone + Serialize + DeserializeOwned,
+            V: Clone + Serialize + DeserializeOwned,
+        {
+            /// Write all live entries and their expiry times to `path`.
+            ///
+            /// Expired entries are skipped. Returns the number of entries written.
+            pub fn save_snapshot(&self, path: impl AsRef<Path>) -> io::Result<usize> {
+                let now = Instant::now();
+                let now_ms = chrono::Utc::now().timestamp_millis();
+                let entries: Vec<SnapshotEntry<K, V>> = self
+                    .data
+                    .iter()
+                    .filter(|entry| entry.expires_at > now)
+                    .map(|entry| SnapshotEntry {
+                        key: entry.key().clone(),
+                        value: entry.value.clone(),
+                        expires_at_ms: now_ms + (entry.expires_at - now).as_millis() as i64,
+                    })
+                    .collect();
+
+                // Write beside the target and rename so a crash never leaves a torn file.
+                let path = path.as_ref();
+                let mut tmp = path.as_os_str().to_owned();
+                tmp.push(".tmp");
+                std::fs::write(&tmp, serde_json::to_vec(&entries)?)?;
+                std::fs::rename(&tmp, path)?;
+                Ok(entries.len())
+            }
+
+            /// Load entries from a snapshot written by [`Cache::save_snapshot`].
+            ///
+            /// Each entry keeps its original expiry time; entries that expired while
+            /// the snapshot sat on disk are skipped. Returns the number of entries loaded.
+            pub fn load_snapshot(&self, path: impl AsRef<Path>) -> io::Result<usize> {
+                let bytes = std::fs::read(path)?;
+                let entries: Vec<SnapshotEntry<K, V>> = serde_json::from_slice(&bytes)?;
+                let now_ms = chrono::Utc::now().timestamp_millis();
+                let mut count = 0;
+                for entry in entries {
+                    if entry.expires_at_ms <= now_ms {
+                        continue;
+                    }
+                    let ttl = Duration::from_millis((entry.expires_at_ms - now_ms) as u64);
+                    self.set_with_ttl(entry.key, entry.value, ttl);
+                    count += 1;
+                }
+                Ok(count)
+            }
+        }
     }
 
     mod chart_store_impl {
@@ -7,19 +7,19 @@ mod types;
 mod websocket;
 
 use axum::{routing::get, Router};
-use config::Config;
+use config::{Config, SharedConfig};
 use services::{
     AccuracyStore, AssetService, AuthService, BotRunner, ChartStore, CryptoBroBot, GrandmaBot,
     HistoricalDataService, MultiSourceCoordinator, OrderBookService, PeerConfig, PeerMesh,
     PredictionStore, QuantBot, ScalperBot, SignalStore, SqliteStore,
 };
-use sources::{AlpacaWs, CoinCapClient, CoinMarketCapClient, FinnhubClient};
+use services::signals::DEFAULT_VALIDATION_CONCURRENCY;
+use sources::{AlpacaWs, CoinCapClient, CoinMarketCapClient, CoinbaseL2Ws, FinnhubClient};
 // FinnhubWs requires paid tier for US stocks - use Tiingo or Alpaca instead
 #[allow(unused_imports)]
 use sources::{FinnhubWs, TiingoWs};
 use std::sync::Arc;
-use tower_http::cors::{Any, CorsLayer};
-use tower_http::trace::TraceLayer;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 use tracing::{debug, info};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use types::TradingTimeframe;
@@ -28,7 +28,7 @@ use websocket::RoomManager;
 /// Application state shared across handlers.
 #[derive(Clone)]
 pub struct AppState {
-    pub config: Arc<Config>,
+    pub config: SharedConfig,
     pub coordinator: Arc<MultiSourceCoordinator>,
     pub room_manager: Arc<RoomManager>,
     pub chart_store: Arc<ChartStore>,
@@ -44,6 +44,10 @@ pub struct AppState {
     pub peer_mesh: Option<Arc<PeerMesh>>,
     pub trading_service: Arc<services::TradingService>,
     pub bot_runner: Option<Arc<BotRunner>>,
+    pub notification_service: Arc<services::NotificationService>,
+    pub health_service: Arc<services::HealthService>,
+    pub source_capabilities: Arc<services::SourceCapabilities>,
+    pub shutdown: Arc<services::Shutdown>,
 }
 
 #[tokio::main]
@@ -61,8 +65,22 @@ async fn main() -> anyhow::Result<()> {
         .init();
 
     // Load configuration
-    let config = Arc::new(Config::from_env());
+    let config = Arc::new(Config::load()?);
+    if let Err(errors) = config.validate() {
+        for e in &errors {
+            tracing::error!("Invalid configuration: {}", e);
+        }
+        anyhow::bail!("refusing to start with {} configuration error(s)", errors.len());
+    }
     info!("Starting Haunt server on {}:{}", config.host, config.port);
+    let shared_config: SharedConfig = Arc::new(arc_swap::ArcSwap::new(config.clone()));
+
+    // Report which sources will run, so a missing API key is visible up front
+    let source_capabilities = services::SourceCapabilities::from_config(&config);
+    source_capabilities.log();
+
+    // Background tasks are spawned through this so they stop on Ctrl-C/SIGTERM
+    let shutdown = services::Shutdown::new();
 
     // Create the multi-source coordinator
     let (coordinator, _price_rx) = MultiSourceCoordinator::new(&config);
@@ -71,19 +89,40 @@ async fn main() -> anyhow::Result<()> {
     let chart_store = coordinator.chart_store();
     let price_cache = coordinator.price_cache();
 
+    // Open one shared Redis connection for all services
+    let redis_conn = match config.redis_url {
+        Some(ref redis_url) => services::redis_store::connect_shared(redis_url).await,
+        None => None,
+    };
+
     // Connect to Redis for persistence
-    if let Some(ref redis_url) = config.redis_url {
+    if let (Some(redis_url), Some(conn)) = (&config.redis_url, &redis_conn) {
         // Connect chart store
-        chart_store.connect_redis(redis_url).await;
+        chart_store.set_redis_compression(config.chart_redis_compression);
+        chart_store.connect_redis(conn.clone()).await;
 
         // Connect price cache
-        price_cache.connect_redis(redis_url).await;
+        price_cache.connect_redis(conn.clone()).await;
+
+        // Share price updates with other servers if enabled
+        if config.price_fanout_enabled {
+            match services::redis_store::PriceFanout::connect_redis(
+                conn.clone(),
+                redis_url,
+                services::redis_store::PRICE_FANOUT_CHANNEL,
+            )
+            .await
+            {
+                Ok(bus) => price_cache.start_fanout(bus, config.server_id.clone()),
+                Err(e) => tracing::warn!("Failed to start price fan-out: {}", e),
+            }
+        }
 
         // Load ALL available sparkline data from Redis (scans for all persisted symbols)
         chart_store.load_all_from_redis().await;
 
         // Also load from the known symbol list for price cache
-        let symbols: Vec<&str> = sources::coingecko::SYMBOL_TO_ID
+        let symbols: Vec<&str> = sources::symbols::COINGECKO_IDS
             .iter()
             .map(|(s, _)| *s)
             .collect();
@@ -147,7 +186,7 @@ async fn main() -> anyhow::Result<()> {
             price_cache.clone(),
             chart_store.clone(),
         );
-        tokio::spawn(async move {
+        shutdown.spawn(async move {
             if let Err(e) = alpaca_ws.connect().await {
                 tracing::error!("Alpaca WebSocket error: {}", e);
             }
@@ -195,8 +234,8 @@ async fn main() -> anyhow::Result<()> {
     );
 
     // Connect historical service to Redis and load historical data
-    if let Some(ref redis_url) = config.redis_url {
-        historical_service.connect_redis(redis_url).await;
+    if let Some(ref conn) = redis_conn {
+        historical_service.connect_redis(conn.clone()).await;
         // Load historical data for common symbols on startup
         historical_service.load_common_symbols().await;
         // Load historical data for stocks/ETFs
@@ -208,9 +247,9 @@ async fn main() -> anyhow::Result<()> {
     let accuracy_store = AccuracyStore::new();
 
     // Connect signal stores to Redis
-    if let Some(ref redis_url) = config.redis_url {
-        prediction_store.connect_redis(redis_url).await;
-        accuracy_store.connect_redis(redis_url).await;
+    if let Some(ref conn) = redis_conn {
+        prediction_store.connect_redis(conn.clone()).await;
+        accuracy_store.connect_redis(conn.clone()).await;
         accuracy_store.load_all_from_redis().await;
     }
 
@@ -221,9 +260,14 @@ async fn main() -> anyhow::Result<()> {
     );
 
     // Create SQLite store for persistent profile and prediction storage
-    let sqlite_store =
-        Arc::new(SqliteStore::new("haunt.db").expect("Failed to initialize SQLite database"));
-    info!("SQLite database initialized at haunt.db");
+    let sqlite_store = Arc::new(
+        SqliteStore::new_with_key(
+            &config.storage.sqlite_path,
+            config.storage.sqlite_key.as_deref(),
+        )
+        .expect("Failed to initialize SQLite database"),
+    );
+    info!("SQLite database initialized at {}", config.storage.sqlite_path);
 
     // Connect prediction store to SQLite for permanent history
     prediction_store.connect_sqlite(sqlite_store.clone()).await;
@@ -234,6 +278,7 @@ async fn main() -> anyhow::Result<()> {
 
     // Create order book service for aggregated depth data
     let orderbook_service = Arc::new(OrderBookService::new());
+    let orderbook_for_l2 = orderbook_service.clone();
     info!("Order book service initialized");
 
     // Create peer mesh for multi-server connectivity
@@ -254,12 +299,7 @@ async fn main() -> anyhow::Result<()> {
             config.server_region.clone(),
             config.public_ws_url.clone(),
             config.public_api_url.clone(),
-            if config.mesh_auth.shared_key.is_empty() {
-                None
-            } else {
-                Some(config.mesh_auth.shared_key.clone())
-            },
-            config.mesh_auth.require_auth,
+            shared_config.clone(),
         );
 
         // Add configured peer servers
@@ -300,37 +340,32 @@ async fn main() -> anyhow::Result<()> {
     };
 
     // Create auth service with Redis (for sessions) and SQLite (for profiles)
-    let redis_conn = if let Some(ref redis_url) = config.redis_url {
-        match redis::Client::open(redis_url.as_str()) {
-            Ok(client) => match redis::aio::ConnectionManager::new(client).await {
-                Ok(conn) => {
-                    info!("Auth service connected to Redis");
-                    Some(conn)
-                }
-                Err(e) => {
-                    tracing::warn!("Failed to connect auth service to Redis: {}", e);
-                    None
-                }
-            },
-            Err(e) => {
-                tracing::warn!("Invalid Redis URL for auth service: {}", e);
-                None
-            }
-        }
-    } else {
-        None
-    };
+    let health_service = services::HealthService::new(
+        redis_conn.clone(),
+        sqlite_store.clone(),
+        price_cache.clone(),
+        peer_mesh.clone(),
+    );
     let auth_service = Arc::new(AuthService::new(redis_conn, Some(sqlite_store.clone())));
 
     // Create room manager for WebSocket subscriptions
     let room_manager = RoomManager::new();
 
-    // Create trading service for paper trading (with room_manager for real-time updates)
-    let trading_service = Arc::new(services::TradingService::with_room_manager(
+    // Create notification service (stores notifications, delivers email/webhook)
+    let notification_service = Arc::new(services::NotificationService::from_config(
         sqlite_store.clone(),
-        room_manager.clone(),
+        &config.notifications,
     ));
 
+    // Create trading service for paper trading (with room_manager for real-time updates)
+    let trading_service = {
+        let mut service =
+            services::TradingService::with_room_manager(sqlite_store.clone(), room_manager.clone());
+        service.set_notification_service(notification_service.clone());
+        service.set_chart_store(chart_store.clone());
+        Arc::new(service)
+    };
+
     // Create bot runner for AI trading bots
     let bot_runner = {
         let runner = BotRunner::new(
@@ -362,7 +397,7 @@ async fn main() -> anyhow::Result<()> {
 
     // Create application state
     let state = AppState {
-        config: config.clone(),
+        config: shared_config.clone(),
         coordinator: coordinator.clone(),
         room_manager: room_manager.clone(),
         chart_store: chart_store.clone(),
@@ -378,6 +413,10 @@ async fn main() -> anyhow::Result<()> {
         peer_mesh: peer_mesh.clone(),
         trading_service: trading_service.clone(),
         bot_runner: bot_runner.clone(),
+        notification_service,
+        health_service,
+        source_capabilities,
+        shutdown: shutdown.clone(),
     };
 
     // Keep a reference for the market simulation engine
@@ -386,6 +425,20 @@ async fn main() -> anyhow::Result<()> {
     // Start the price sources
     coordinator.start().await;
 
+    // Fan price updates out to subscribed WebSocket clients
+    shutdown.spawn(websocket::handler::broadcast_prices(state.clone()));
+    shutdown.spawn(websocket::handler::broadcast_breaker_trips(state.clone()));
+
+    // Stream Coinbase level2 books into the order book service
+    if !config.coinbase_l2_symbols.is_empty() {
+        let feed = CoinbaseL2Ws::new(&config.coinbase_l2_symbols, orderbook_for_l2);
+        tokio::spawn(async move {
+            if let Err(e) = feed.connect().await {
+                tracing::error!("Coinbase level2 WebSocket error: {}", e);
+            }
+        });
+    }
+
     // Start the peer mesh if configured
     if let Some(ref mesh) = peer_mesh {
         info!("Starting peer mesh connections...");
@@ -396,7 +449,7 @@ async fn main() -> anyhow::Result<()> {
         let room_manager_for_peers = room_manager.clone();
         let config_for_peers = config.clone();
 
-        tokio::spawn(async move {
+        shutdown.spawn(async move {
             let mut peer_rx = mesh_for_broadcast.subscribe();
 
             while let Ok(statuses) = peer_rx.recv().await {
@@ -410,12 +463,26 @@ async fn main() -> anyhow::Result<()> {
 
                 let msg = types::ServerMessage::PeerUpdate { data: update_data };
 
-                if let Ok(json) = serde_json::to_string(&msg) {
-                    // Send to all clients subscribed to peer updates
-                    let subscribers = room_manager_for_peers.get_peer_subscribers();
-                    for tx in subscribers {
-                        let _ = tx.send(json.clone());
-                    }
+                room_manager_for_peers.broadcast_peers(&msg);
+            }
+        });
+    }
+
+    // Periodically drop WebSocket clients whose connection died without a
+    // clean disconnect so room counts stay accurate, and replay buffers for
+    // rooms that have gone quiet
+    {
+        let room_manager = room_manager.clone();
+        shutdown.spawn(async move {
+            loop {
+                tokio::time::sleep(tokio::time::Duration::from_secs(30)).await;
+                let reaped = room_manager.reap_dead_connections();
+                if reaped > 0 {
+                    debug!("Reaped {} dead WebSocket connections", reaped);
+                }
+                let pruned = room_manager.prune_replay_buffers();
+                if pruned > 0 {
+                    debug!("Dropped {} idle WebSocket replay buffers", pruned);
                 }
             }
         });
@@ -424,7 +491,7 @@ async fn main() -> anyhow::Result<()> {
     // Start periodic Redis save tasks
     {
         let chart_store = chart_store.clone();
-        tokio::spawn(async move {
+        shutdown.spawn(async move {
             loop {
                 tokio::time::sleep(tokio::time::Duration::from_secs(60)).await;
                 chart_store.save_all_to_redis().await;
@@ -433,7 +500,7 @@ async fn main() -> anyhow::Result<()> {
     }
     {
         let price_cache = price_cache.clone();
-        tokio::spawn(async move {
+        shutdown.spawn(async move {
             loop {
                 tokio::time::sleep(tokio::time::Duration::from_secs(30)).await;
                 price_cache.save_update_counts().await;
@@ -441,57 +508,109 @@ async fn main() -> anyhow::Result<()> {
         });
     }
 
+    // Start scheduled SQLite backups if a backup directory is configured
+    if let Some(ref backup_dir) = config.storage.backup_dir {
+        let sqlite_store = state.sqlite_store.clone();
+        let backup_dir = backup_dir.clone();
+        let live_config = shared_config.clone();
+        info!(
+            "SQLite backups enabled: dir={}, every {}s, keeping {}",
+            backup_dir, config.storage.backup_interval_secs, config.storage.backup_retain
+        );
+        shutdown.spawn(async move {
+            loop {
+                // Schedule and retention are reloadable, so read them each pass
+                let (interval_secs, retain) = {
+                    let current = live_config.load();
+                    (
+                        current.storage.backup_interval_secs.max(60),
+                        current.storage.backup_retain,
+                    )
+                };
+                tokio::time::sleep(tokio::time::Duration::from_secs(interval_secs)).await;
+                let store = sqlite_store.clone();
+                let dir = backup_dir.clone();
+                match tokio::task::spawn_blocking(move || store.backup_rotating(&dir, retain))
+                    .await
+                {
+                    Ok(Ok(_)) => {}
+                    Ok(Err(e)) => tracing::warn!("SQLite backup failed: {}", e),
+                    Err(e) => tracing::warn!("SQLite backup task panicked: {}", e),
+                }
+            }
+        });
+    }
+
+    // Snapshot portfolio equity on a schedule so equity curves fill in
+    // between trades
+    {
+        let trading_service = trading_service_for_sim.clone();
+        let live_config = shared_config.clone();
+        shutdown.spawn(async move {
+            loop {
+                let (interval_secs, retain_days) = {
+                    let current = live_config.load();
+                    (
+                        current.storage.snapshot_interval_secs.max(10),
+                        current.storage.snapshot_retain_days.max(1),
+                    )
+                };
+                tokio::time::sleep(tokio::time::Duration::from_secs(interval_secs)).await;
+                let service = trading_service.clone();
+                match tokio::task::spawn_blocking(move || {
+                    service.snapshot_all_portfolios(retain_days)
+                })
+                .await
+                {
+                    Ok(summary) => debug!(
+                        "Portfolio snapshots: {} taken, {} idle, {} pruned",
+                        summary.snapshotted, summary.skipped, summary.pruned
+                    ),
+                    Err(e) => tracing::warn!("Portfolio snapshot task panicked: {}", e),
+                }
+            }
+        });
+    }
+
+    // Reload the reloadable subset of the config on SIGHUP
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        let shared_config = shared_config.clone();
+        match signal(SignalKind::hangup()) {
+            Ok(mut hangup) => {
+                shutdown.spawn(async move {
+                    while hangup.recv().await.is_some() {
+                        info!("SIGHUP received, reloading configuration");
+                        dotenvy::dotenv_override().ok();
+                        config::reload(&shared_config);
+                    }
+                });
+            }
+            Err(e) => tracing::warn!("Failed to install SIGHUP handler: {}", e),
+        }
+    }
+
     // Start prediction validation task (every 30 seconds for faster scalping feedback)
     {
         let signal_store = signal_store.clone();
-        let chart_store_clone = chart_store.clone();
-        tokio::spawn(async move {
+        shutdown.spawn(async move {
             loop {
                 tokio::time::sleep(tokio::time::Duration::from_secs(30)).await;
 
-                // Get all symbols with pending predictions using the new efficient method
-                let symbols = signal_store.prediction_store().get_pending_symbols();
-
-                if symbols.is_empty() {
-                    continue;
-                }
-
-                let symbol_count = symbols.len();
-                let mut total_validations = 0usize;
-
-                for symbol in &symbols {
-                    if let Some(current_price) = chart_store_clone.get_current_price(symbol) {
-                        // Validate for each timeframe (including 5m for quick feedback)
-                        for timeframe in &["5m", "1h", "4h", "24h"] {
-                            let outcomes = signal_store
-                                .prediction_store()
-                                .validate_pending(symbol, current_price, timeframe)
-                                .await;
-
-                            // Record outcomes in accuracy store
-                            for (indicator, outcome) in &outcomes {
-                                signal_store
-                                    .accuracy_store()
-                                    .record_outcome(symbol, indicator, timeframe, *outcome)
-                                    .await;
-                            }
-
-                            total_validations += outcomes.len();
-                        }
-                    } else {
-                        debug!("No price data for {} - skipping validation", symbol);
-                    }
-                }
+                let summary = signal_store
+                    .validate_pending_predictions(DEFAULT_VALIDATION_CONCURRENCY)
+                    .await;
 
-                if total_validations > 0 {
+                if summary.validations > 0 {
                     info!(
                         "Validated {} predictions across {} symbols",
-                        total_validations, symbol_count
+                        summary.validations, summary.symbols
                     );
-                } else {
+                } else if summary.symbols > 0 {
                     debug!(
                         "Checked {} symbols - no predictions ready for validation",
-                        symbol_count
+                        summary.symbols
                     );
                 }
             }
@@ -503,7 +622,7 @@ async fn main() -> anyhow::Result<()> {
         let trading_service = trading_service_for_sim.clone();
         let chart_store = chart_store.clone();
 
-        tokio::spawn(async move {
+        shutdown.spawn(async move {
             // Initial delay to let system stabilize
             tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
             info!("Market simulation engine started");
@@ -522,6 +641,15 @@ async fn main() -> anyhow::Result<()> {
                     info!("Auto-filled {} pending market orders", filled);
                 }
 
+                // Release due TWAP/VWAP slices as market orders
+                let released = trading_service.process_execution_schedules(
+                    chrono::Utc::now().timestamp_millis(),
+                    |symbol| chart_store.get_current_price(symbol),
+                );
+                if released > 0 {
+                    info!("Released {} execution child orders", released);
+                }
+
                 // Then process all active symbols for limit/stop orders and position updates
                 let (positions_updated, orders_triggered, positions_closed) =
                     trading_service.process_all_market_ticks(|symbol| {
@@ -543,7 +671,7 @@ async fn main() -> anyhow::Result<()> {
     if let Some(ref runner) = bot_runner {
         info!("Starting bot runner with {} registered bots", runner.bot_count());
         let runner = runner.clone();
-        tokio::spawn(async move {
+        shutdown.spawn(async move {
             runner.start().await;
         });
     }
@@ -554,7 +682,7 @@ async fn main() -> anyhow::Result<()> {
         let signal_store = signal_store.clone();
         let chart_store = chart_store.clone();
 
-        tokio::spawn(async move {
+        shutdown.spawn(async move {
             // Initial delay to let system stabilize
             tokio::time::sleep(tokio::time::Duration::from_secs(60)).await;
 
@@ -604,9 +732,15 @@ async fn main() -> anyhow::Result<()> {
         });
     }
 
-    // Build CORS layer
+    // Build CORS layer; allowed origins are read from the live config so a
+    // SIGHUP reload applies to the next request
+    let cors_config = shared_config.clone();
     let cors = CorsLayer::new()
-        .allow_origin(Any)
+        .allow_origin(AllowOrigin::predicate(move |origin, _| {
+            origin
+                .to_str()
+                .is_ok_and(|origin| cors_config.load().allows_origin(origin))
+        }))
         .allow_methods(Any)
         .allow_headers(Any);
 
@@ -614,16 +748,66 @@ async fn main() -> anyhow::Result<()> {
     let app = Router::new()
         .merge(api::router())
         .route("/ws", get(websocket::ws_handler))
-        .layer(cors)
-        .layer(TraceLayer::new_for_http())
-        .with_state(state);
+        .layer(cors);
+    let app = api::access_log::layer(app).with_state(state);
 
     // Start the server
     let addr = format!("{}:{}", config.host, config.port);
     let listener = tokio::net::TcpListener::bind(&addr).await?;
     info!("Haunt server listening on {}", addr);
 
-    axum::serve(listener, app).await?;
+    // Stop on Ctrl-C or SIGTERM
+    {
+        let shutdown = shutdown.clone();
+        tokio::spawn(async move {
+            services::shutdown::wait_for_signal().await;
+            info!("Shutdown signal received, draining connections");
+            shutdown.trigger();
+        });
+    }
+
+    // Close peer connections as soon as shutdown starts
+    if let Some(ref mesh) = peer_mesh {
+        let mesh = mesh.clone();
+        let shutdown = shutdown.clone();
+        tokio::spawn(async move {
+            shutdown.wait().await;
+            mesh.stop();
+        });
+    }
+
+    // Flush state that is otherwise only saved periodically
+    {
+        let chart_store = chart_store.clone();
+        shutdown.on_shutdown("chart store", move || async move {
+            chart_store.save_all_to_redis().await;
+        });
+    }
+    {
+        let price_cache = price_cache.clone();
+        shutdown.on_shutdown("price update counts", move || async move {
+            price_cache.save_update_counts().await;
+        });
+    }
+    {
+        // WebSocket clients are sent a close frame when shutdown starts; wait
+        // for them to go before the runtime drops their tasks
+        let room_manager = room_manager.clone();
+        shutdown.on_shutdown("websocket clients", move || async move {
+            while room_manager.client_count() > 0 {
+                tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+            }
+        });
+    }
+
+    let server_shutdown = shutdown.clone();
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async move { server_shutdown.wait().await })
+        .await?;
+
+    info!("Server drained, running shutdown hooks");
+    shutdown.run_hooks().await;
+    info!("Haunt server stopped");
 
     Ok(())
 }
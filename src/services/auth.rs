@@ -8,6 +8,7 @@
 //! - Redis: Sessions (24-hour TTL, ephemeral)
 //! - DashMap: In-memory cache for both
 
+use crate::services::content_filter::{self, ContentError};
 use crate::services::SqliteStore;
 use crate::types::{AuthChallenge, AuthRequest, Profile, Session};
 use dashmap::DashMap;
@@ -338,6 +339,29 @@ impl AuthService {
         Ok(profile)
     }
 
+    /// Validate and save a new bio and avatar URL for a profile.
+    pub async fn update_profile_details(
+        &self,
+        mut profile: Profile,
+        bio: Option<String>,
+        avatar_url: Option<String>,
+    ) -> Result<Profile, AuthError> {
+        profile.bio = content_filter::validate_bio(bio)?;
+        profile.avatar_url = content_filter::validate_avatar_url(avatar_url)?;
+        self.update_profile(profile).await
+    }
+
+    /// Load the profiles for a list of public keys, skipping unknown keys.
+    pub async fn get_profiles(&self, public_keys: &[String]) -> Vec<Profile> {
+        let mut profiles = Vec::with_capacity(public_keys.len());
+        for public_key in public_keys {
+            if let Some(profile) = self.get_profile(public_key).await {
+                profiles.push(profile);
+            }
+        }
+        profiles
+    }
+
     /// Logout - invalidate session.
     pub async fn logout(&self, token: &str) {
         self.sessions.remove(token);
@@ -454,37 +478,37 @@ pub enum AuthError {
 
     #[error("Unauthorized")]
     Unauthorized,
+
+    #[error("Cannot follow yourself")]
+    SelfFollow,
+
+    #[error("Invalid profile: {0}")]
+    InvalidProfile(#[from] ContentError),
+
+    #[error("Storage error: {0}")]
+    Storage(String),
 }
 
 impl axum::response::IntoResponse for AuthError {
     fn into_response(self) -> axum::response::Response {
-        let (status, message) = match self {
-            AuthError::InvalidChallenge => {
-                (axum::http::StatusCode::BAD_REQUEST, "Invalid challenge")
-            }
-            AuthError::ExpiredChallenge => {
-                (axum::http::StatusCode::BAD_REQUEST, "Challenge expired")
-            }
-            AuthError::InvalidSignature => {
-                (axum::http::StatusCode::UNAUTHORIZED, "Invalid signature")
-            }
-            AuthError::InvalidSignatureFormat => (
-                axum::http::StatusCode::BAD_REQUEST,
-                "Invalid signature format",
-            ),
-            AuthError::InvalidPublicKeyFormat => (
-                axum::http::StatusCode::BAD_REQUEST,
-                "Invalid public key format",
-            ),
-            AuthError::SessionNotFound => {
-                (axum::http::StatusCode::UNAUTHORIZED, "Session not found")
-            }
-            AuthError::ProfileNotFound => (axum::http::StatusCode::NOT_FOUND, "Profile not found"),
-            AuthError::Unauthorized => (axum::http::StatusCode::UNAUTHORIZED, "Unauthorized"),
+        use axum::http::StatusCode;
+
+        let status = match self {
+            AuthError::InvalidChallenge
+            | AuthError::ExpiredChallenge
+            | AuthError::InvalidSignatureFormat
+            | AuthError::InvalidPublicKeyFormat
+            | AuthError::SelfFollow
+            | AuthError::InvalidProfile(_) => StatusCode::BAD_REQUEST,
+            AuthError::InvalidSignature
+            | AuthError::SessionNotFound
+            | AuthError::Unauthorized => StatusCode::UNAUTHORIZED,
+            AuthError::ProfileNotFound => StatusCode::NOT_FOUND,
+            AuthError::Storage(_) => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
         let body = serde_json::json!({
-            "error": message,
+            "error": self.to_string(),
         });
 
         (status, axum::Json(body)).into_response()
@@ -546,4 +570,50 @@ mod tests {
         let result = service.validate_session("nonexistent").await;
         assert!(result.is_none());
     }
+
+    #[tokio::test]
+    async fn test_update_profile_details_validates() {
+        let service = AuthService::new(None, None);
+        let profile = service.get_or_create_profile(&"c".repeat(64)).await;
+
+        let updated = service
+            .update_profile_details(
+                profile.clone(),
+                Some("Momentum trader".to_string()),
+                Some("https://example.com/avatar.png".to_string()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(updated.bio.as_deref(), Some("Momentum trader"));
+        let cached = service.get_profile(&profile.public_key).await.unwrap();
+        assert_eq!(cached.avatar_url, updated.avatar_url);
+
+        let result = service
+            .update_profile_details(profile.clone(), Some("x".repeat(281)), None)
+            .await;
+        assert!(matches!(
+            result,
+            Err(AuthError::InvalidProfile(ContentError::BioTooLong))
+        ));
+
+        let result = service
+            .update_profile_details(profile, None, Some("http://example.com/a.png".to_string()))
+            .await;
+        assert!(matches!(
+            result,
+            Err(AuthError::InvalidProfile(ContentError::InvalidAvatarUrl))
+        ));
+    }
+
+    #[test]
+    fn test_storage_errors_are_not_reported_as_missing_profiles() {
+        use axum::http::StatusCode;
+        use axum::response::IntoResponse;
+
+        let missing = AuthError::ProfileNotFound.into_response();
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+
+        let storage = AuthError::Storage("database is locked".to_string()).into_response();
+        assert_eq!(storage.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
 }
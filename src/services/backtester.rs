@@ -12,15 +12,20 @@
 use crate::services::{SqliteStore, StrategyEngine, IndicatorSnapshot};
 use crate::types::{
     AssetClass, BacktestConfig, BacktestMetrics, BacktestResult, BacktestStatus, BacktestTrade,
-    BuyAndHoldComparison, EquityPoint, MonteCarloResults, OrderSide, TradingStrategy,
+    BuyAndHoldComparison, EquityPoint, MonteCarloMethod, MonteCarloResults, OrderSide,
+    TradingStrategy,
 };
 use dashmap::DashMap;
 use rand::prelude::*;
+use rand::rngs::StdRng;
 use std::collections::HashMap;
 use std::sync::Arc;
 use thiserror::Error;
 use tracing::{debug, info, warn};
 
+/// Drawdown (%) at which a Monte Carlo run counts as ruined.
+const RUIN_DRAWDOWN_PCT: f64 = 50.0;
+
 /// Backtesting errors.
 #[derive(Debug, Error)]
 pub enum BacktestError {
@@ -512,7 +517,13 @@ impl BacktestRunner {
 
         // Monte Carlo simulation (if enabled)
         if let Some(runs) = config.monte_carlo_runs {
-            result.monte_carlo = Some(self.run_monte_carlo(&result.trades, config.initial_balance, runs));
+            result.monte_carlo = Some(self.run_monte_carlo(
+                &result.trades,
+                config.initial_balance,
+                runs,
+                config.monte_carlo_method,
+                config.monte_carlo_seed,
+            ));
         }
 
         result.complete(final_equity);
@@ -1031,87 +1042,120 @@ impl BacktestRunner {
     }
 
     /// Run Monte Carlo simulation.
+    ///
+    /// Each realized trade is turned into a return on the equity it was taken
+    /// with, and every run compounds `trades.len()` of those returns, either
+    /// resampled with replacement or shuffled. Shuffling keeps the final equity
+    /// fixed and only varies the path; bootstrapping varies both.
     fn run_monte_carlo(
         &self,
         trades: &[BacktestTrade],
         initial_balance: f64,
         num_runs: u32,
+        method: MonteCarloMethod,
+        seed: Option<u64>,
     ) -> MonteCarloResults {
-        if trades.is_empty() {
+        if trades.is_empty() || num_runs == 0 || initial_balance <= 0.0 {
             return MonteCarloResults {
                 num_runs,
+                method,
+                final_equity_mean: initial_balance,
+                final_equity_p5: initial_balance,
+                final_equity_p50: initial_balance,
+                final_equity_p95: initial_balance,
                 return_p5: 0.0,
                 return_p25: 0.0,
                 return_p50: 0.0,
                 return_p75: 0.0,
                 return_p95: 0.0,
                 max_dd_p5: 0.0,
+                max_dd_p25: 0.0,
                 max_dd_p50: 0.0,
+                max_dd_p75: 0.0,
                 max_dd_p95: 0.0,
                 probability_of_profit: 0.0,
                 probability_of_ruin: 0.0,
             };
         }
 
-        let mut rng = rand::thread_rng();
-        let mut returns: Vec<f64> = Vec::with_capacity(num_runs as usize);
+        let mut rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let mut final_equities: Vec<f64> = Vec::with_capacity(num_runs as usize);
         let mut max_drawdowns: Vec<f64> = Vec::with_capacity(num_runs as usize);
         let mut profitable_runs = 0u32;
         let mut ruin_runs = 0u32;
 
-        // Collect trade P&Ls
-        let pnls: Vec<f64> = trades.iter().map(|t| t.pnl).collect();
+        // Per-trade returns on the equity each trade was taken with
+        let mut equity = initial_balance;
+        let mut trade_returns: Vec<f64> = Vec::with_capacity(trades.len());
+        for trade in trades {
+            let ret = if equity > 0.0 { trade.pnl / equity } else { 0.0 };
+            trade_returns.push(ret.max(-1.0));
+            equity += trade.pnl;
+        }
 
+        let mut sequence = trade_returns.clone();
         for _ in 0..num_runs {
-            // Shuffle trades and simulate
-            let mut shuffled = pnls.clone();
-            shuffled.shuffle(&mut rng);
+            match method {
+                MonteCarloMethod::Bootstrap => {
+                    for ret in sequence.iter_mut() {
+                        *ret = trade_returns[rng.gen_range(0..trade_returns.len())];
+                    }
+                }
+                MonteCarloMethod::Shuffle => sequence.shuffle(&mut rng),
+            }
 
             let mut balance = initial_balance;
             let mut peak = initial_balance;
-            let mut max_dd = 0.0;
+            let mut max_dd: f64 = 0.0;
 
-            for pnl in &shuffled {
-                balance += pnl;
-                if balance > peak {
-                    peak = balance;
-                }
-                let dd = (peak - balance) / peak * 100.0;
-                if dd > max_dd {
-                    max_dd = dd;
-                }
+            for ret in &sequence {
+                balance *= 1.0 + ret;
+                peak = peak.max(balance);
+                max_dd = max_dd.max((peak - balance) / peak * 100.0);
             }
 
-            let ret = (balance / initial_balance - 1.0) * 100.0;
-            returns.push(ret);
+            final_equities.push(balance);
             max_drawdowns.push(max_dd);
 
-            if ret > 0.0 {
+            if balance > initial_balance {
                 profitable_runs += 1;
             }
-            if max_dd > 50.0 {
+            if max_dd > RUIN_DRAWDOWN_PCT {
                 ruin_runs += 1;
             }
         }
 
+        let final_equity_mean = final_equities.iter().sum::<f64>() / num_runs as f64;
+
         // Sort for percentiles
-        returns.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        final_equities.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
         max_drawdowns.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
 
         let percentile = |arr: &[f64], p: f64| -> f64 {
             let idx = ((arr.len() as f64) * p).floor() as usize;
             arr.get(idx.min(arr.len() - 1)).copied().unwrap_or(0.0)
         };
+        let return_pct = |p: f64| (percentile(&final_equities, p) / initial_balance - 1.0) * 100.0;
 
         MonteCarloResults {
             num_runs,
-            return_p5: percentile(&returns, 0.05),
-            return_p25: percentile(&returns, 0.25),
-            return_p50: percentile(&returns, 0.50),
-            return_p75: percentile(&returns, 0.75),
-            return_p95: percentile(&returns, 0.95),
+            method,
+            final_equity_mean,
+            final_equity_p5: percentile(&final_equities, 0.05),
+            final_equity_p50: percentile(&final_equities, 0.50),
+            final_equity_p95: percentile(&final_equities, 0.95),
+            return_p5: return_pct(0.05),
+            return_p25: return_pct(0.25),
+            return_p50: return_pct(0.50),
+            return_p75: return_pct(0.75),
+            return_p95: return_pct(0.95),
             max_dd_p5: percentile(&max_drawdowns, 0.05),
+            max_dd_p25: percentile(&max_drawdowns, 0.25),
             max_dd_p50: percentile(&max_drawdowns, 0.50),
+            max_dd_p75: percentile(&max_drawdowns, 0.75),
             max_dd_p95: percentile(&max_drawdowns, 0.95),
             probability_of_profit: profitable_runs as f64 / num_runs as f64 * 100.0,
             probability_of_ruin: ruin_runs as f64 / num_runs as f64 * 100.0,
@@ -1308,12 +1352,69 @@ mod tests {
             },
         ];
 
-        let mc = runner.run_monte_carlo(&trades, 10000.0, 100);
+        let mc = runner.run_monte_carlo(&trades, 10000.0, 100, MonteCarloMethod::Bootstrap, None);
 
         assert_eq!(mc.num_runs, 100);
         assert!(mc.probability_of_profit > 0.0);
     }
 
+    #[test]
+    fn test_monte_carlo_mean_final_equity_matches_backtest() {
+        let store = create_test_store();
+        let strategy_engine = Arc::new(StrategyEngine::new(store.clone()));
+        let runner = BacktestRunner::new(store, strategy_engine);
+
+        // Alternating +100 / -50 trades on a 10,000 account
+        let trades: Vec<BacktestTrade> = (0..10)
+            .map(|i| {
+                let pnl = if i % 2 == 0 { 100.0 } else { -50.0 };
+                BacktestTrade {
+                    id: i.to_string(),
+                    symbol: "BTC".to_string(),
+                    side: OrderSide::Buy,
+                    entry_price: 100.0,
+                    exit_price: Some(100.0 + pnl / 10.0),
+                    quantity: 10.0,
+                    entry_time: i * 2,
+                    exit_time: Some(i * 2 + 1),
+                    pnl,
+                    pnl_pct: pnl / 10.0,
+                    commission: 0.0,
+                    entry_rule_id: None,
+                    exit_rule_id: None,
+                    is_winner: pnl > 0.0,
+                    max_favorable_excursion: pnl.max(0.0),
+                    max_adverse_excursion: pnl.min(0.0),
+                }
+            })
+            .collect();
+        let deterministic: f64 = 10000.0 + trades.iter().map(|t| t.pnl).sum::<f64>();
+
+        // Shuffling only reorders the returns, so every run ends at the same equity
+        let shuffled =
+            runner.run_monte_carlo(&trades, 10000.0, 500, MonteCarloMethod::Shuffle, Some(7));
+        assert_eq!(shuffled.method, MonteCarloMethod::Shuffle);
+        assert!((shuffled.final_equity_mean - deterministic).abs() < 1e-6);
+        assert!((shuffled.final_equity_p5 - shuffled.final_equity_p95).abs() < 1e-6);
+        assert!(shuffled.max_dd_p5 <= shuffled.max_dd_p50);
+        assert!(shuffled.max_dd_p50 <= shuffled.max_dd_p95);
+        assert_eq!(shuffled.probability_of_profit, 100.0);
+        assert_eq!(shuffled.probability_of_ruin, 0.0);
+
+        // Resampling varies the outcome but centers on the realized result
+        let bootstrap =
+            runner.run_monte_carlo(&trades, 10000.0, 5000, MonteCarloMethod::Bootstrap, Some(7));
+        assert!((bootstrap.final_equity_mean - deterministic).abs() / deterministic < 0.005);
+        assert!(bootstrap.final_equity_p5 < bootstrap.final_equity_p95);
+        assert!(bootstrap.return_p5 <= bootstrap.return_p50);
+        assert!(bootstrap.return_p50 <= bootstrap.return_p95);
+
+        // The same seed reproduces the same distribution
+        let again =
+            runner.run_monte_carlo(&trades, 10000.0, 5000, MonteCarloMethod::Bootstrap, Some(7));
+        assert_eq!(again.final_equity_mean, bootstrap.final_equity_mean);
+    }
+
     #[test]
     fn test_backtest_result_lifecycle() {
         let config = BacktestConfig::new(
@@ -0,0 +1,271 @@
+//! Synthetic basket (index) prices built from several `ChartStore` series.
+//!
+//! Each component is rebased to its own price at the start of the range, so a
+//! basket of BTC and a penny coin moves by the weighted percentage change of
+//! each rather than being dominated by the larger price.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use thiserror::Error;
+use utoipa::ToSchema;
+
+use super::ChartStore;
+use crate::types::{ChartRange, OhlcPoint};
+
+/// Value of the basket at the first aligned bucket of the range.
+pub const BASKET_BASE_VALUE: f64 = 100.0;
+
+/// Maximum number of symbols in one basket.
+pub const MAX_BASKET_COMPONENTS: usize = 20;
+
+/// Errors returned when parsing a basket definition.
+#[derive(Debug, Error, PartialEq)]
+pub enum BasketError {
+    #[error("Basket must contain at least one symbol")]
+    Empty,
+
+    #[error("Basket may contain at most {} symbols", MAX_BASKET_COMPONENTS)]
+    TooManyComponents,
+
+    #[error("Invalid basket component: {0}")]
+    InvalidComponent(String),
+
+    #[error("Invalid weight for {0}: weights must be positive numbers")]
+    InvalidWeight(String),
+
+    #[error("Duplicate basket symbol: {0}")]
+    DuplicateSymbol(String),
+}
+
+/// One symbol and its weight in a basket.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct BasketComponent {
+    pub symbol: String,
+    /// Share of the basket, normalized so all weights sum to 1.
+    pub weight: f64,
+}
+
+/// Parse a basket definition such as `btc:0.5,eth:0.5`.
+///
+/// A component without a weight (`btc,eth`) gets weight 1. Weights are
+/// normalized to sum to 1, so `btc:2,eth:1` is a 2/3 - 1/3 basket.
+pub fn parse_basket(spec: &str) -> Result<Vec<BasketComponent>, BasketError> {
+    let mut components: Vec<BasketComponent> = Vec::new();
+    for part in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (symbol, weight) = match part.split_once(':') {
+            Some((symbol, weight)) => (symbol.trim(), Some(weight.trim())),
+            None => (part, None),
+        };
+        if symbol.is_empty() {
+            return Err(BasketError::InvalidComponent(part.to_string()));
+        }
+        let symbol = symbol.to_lowercase();
+        let weight = match weight {
+            Some(w) => w
+                .parse::<f64>()
+                .ok()
+                .filter(|w| w.is_finite() && *w > 0.0)
+                .ok_or_else(|| BasketError::InvalidWeight(symbol.clone()))?,
+            None => 1.0,
+        };
+        if components.iter().any(|c| c.symbol == symbol) {
+            return Err(BasketError::DuplicateSymbol(symbol));
+        }
+        components.push(BasketComponent { symbol, weight });
+    }
+
+    if components.is_empty() {
+        return Err(BasketError::Empty);
+    }
+    if components.len() > MAX_BASKET_COMPONENTS {
+        return Err(BasketError::TooManyComponents);
+    }
+
+    let total: f64 = components.iter().map(|c| c.weight).sum();
+    for component in &mut components {
+        component.weight /= total;
+    }
+    Ok(components)
+}
+
+/// Compute the basket chart for a range from the stored component series.
+///
+/// Returns an empty series if any component has no data in the range.
+pub fn basket_chart(
+    chart_store: &ChartStore,
+    components: &[BasketComponent],
+    range: ChartRange,
+) -> Vec<OhlcPoint> {
+    let series: Vec<(f64, Vec<OhlcPoint>)> = components
+        .iter()
+        .map(|c| (c.weight, chart_store.get_chart(&c.symbol, range)))
+        .collect();
+    combine_series(&series, BASKET_BASE_VALUE)
+}
+
+/// Combine weighted OHLC series into one basket series.
+///
+/// Only buckets present in every series are kept. Each series is rebased to
+/// the open of its first aligned bucket and the basket value is
+/// `base * sum(weight * price / start_price)`. Highs and lows are combined the
+/// same way, which bounds the true basket range since the components need not
+/// peak in the same instant.
+pub fn combine_series(series: &[(f64, Vec<OhlcPoint>)], base: f64) -> Vec<OhlcPoint> {
+    if series.is_empty() || series.iter().any(|(_, points)| points.is_empty()) {
+        return Vec::new();
+    }
+
+    // Buckets keyed by time, keeping only times every series has
+    let mut aligned: BTreeMap<i64, Vec<&OhlcPoint>> = BTreeMap::new();
+    for (_, points) in series {
+        for point in points {
+            aligned.entry(point.time).or_default().push(point);
+        }
+    }
+    aligned.retain(|_, points| points.len() == series.len());
+
+    let Some(first) = aligned.values().next() else {
+        return Vec::new();
+    };
+    let start_prices: Vec<f64> = first.iter().map(|p| p.open).collect();
+    if start_prices.iter().any(|p| *p <= 0.0) {
+        return Vec::new();
+    }
+
+    let weights: Vec<f64> = series.iter().map(|(w, _)| *w).collect();
+    let combine = |points: &[&OhlcPoint], field: fn(&OhlcPoint) -> f64| -> f64 {
+        base * points
+            .iter()
+            .zip(&weights)
+            .zip(&start_prices)
+            .map(|((p, w), start)| w * field(p) / start)
+            .sum::<f64>()
+    };
+
+    aligned
+        .into_iter()
+        .map(|(time, points)| OhlcPoint {
+            time,
+            open: combine(&points, |p| p.open),
+            high: combine(&points, |p| p.high),
+            low: combine(&points, |p| p.low),
+            close: combine(&points, |p| p.close),
+            volume: None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(time: i64, open: f64, high: f64, low: f64, close: f64) -> OhlcPoint {
+        OhlcPoint {
+            time,
+            open,
+            high,
+            low,
+            close,
+            volume: Some(1.0),
+        }
+    }
+
+    #[test]
+    fn test_parse_basket_normalizes_weights() {
+        let basket = parse_basket("BTC:2, eth:1").unwrap();
+        assert_eq!(basket[0].symbol, "btc");
+        assert!((basket[0].weight - 2.0 / 3.0).abs() < 1e-12);
+        assert!((basket[1].weight - 1.0 / 3.0).abs() < 1e-12);
+
+        let equal = parse_basket("btc,eth,sol,xrp").unwrap();
+        assert!(equal.iter().all(|c| (c.weight - 0.25).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_parse_basket_rejects_bad_input() {
+        assert_eq!(parse_basket(""), Err(BasketError::Empty));
+        assert_eq!(
+            parse_basket("btc:-1"),
+            Err(BasketError::InvalidWeight("btc".to_string()))
+        );
+        assert_eq!(
+            parse_basket("btc:abc"),
+            Err(BasketError::InvalidWeight("btc".to_string()))
+        );
+        assert_eq!(
+            parse_basket(":0.5"),
+            Err(BasketError::InvalidComponent(":0.5".to_string()))
+        );
+        assert_eq!(
+            parse_basket("btc,BTC"),
+            Err(BasketError::DuplicateSymbol("btc".to_string()))
+        );
+        let many: Vec<String> = (0..=MAX_BASKET_COMPONENTS)
+            .map(|i| format!("s{}", i))
+            .collect();
+        assert_eq!(
+            parse_basket(&many.join(",")),
+            Err(BasketError::TooManyComponents)
+        );
+    }
+
+    #[test]
+    fn test_combine_weighted_series() {
+        // A doubles while B halves over the range; B is missing bucket 120
+        let a = vec![
+            candle(0, 100.0, 110.0, 90.0, 100.0),
+            candle(60, 100.0, 160.0, 100.0, 150.0),
+            candle(120, 150.0, 150.0, 150.0, 150.0),
+            candle(180, 150.0, 200.0, 150.0, 200.0),
+        ];
+        let b = vec![
+            candle(0, 10.0, 10.0, 10.0, 10.0),
+            candle(60, 10.0, 10.0, 8.0, 8.0),
+            candle(180, 8.0, 8.0, 5.0, 5.0),
+        ];
+
+        let basket = combine_series(&[(0.75, a), (0.25, b)], 100.0);
+        let times: Vec<i64> = basket.iter().map(|p| p.time).collect();
+        assert_eq!(times, vec![0, 60, 180]);
+
+        let close = |i: usize| basket[i].close;
+        assert!((basket[0].open - 100.0).abs() < 1e-9);
+        // 0.75 * 100/100 + 0.25 * 10/10
+        assert!((close(0) - 100.0).abs() < 1e-9);
+        // 0.75 * 150/100 + 0.25 * 8/10 = 1.325
+        assert!((close(1) - 132.5).abs() < 1e-9);
+        // 0.75 * 200/100 + 0.25 * 5/10 = 1.625
+        assert!((close(2) - 162.5).abs() < 1e-9);
+        // 0.75 * 160/100 + 0.25 * 10/10 = 1.45
+        assert!((basket[1].high - 145.0).abs() < 1e-9);
+        assert!(basket.iter().all(|p| p.low <= p.close && p.close <= p.high));
+        assert!(basket.iter().all(|p| p.volume.is_none()));
+    }
+
+    #[test]
+    fn test_combine_without_overlap_is_empty() {
+        let a = vec![candle(0, 1.0, 1.0, 1.0, 1.0)];
+        let b = vec![candle(60, 1.0, 1.0, 1.0, 1.0)];
+        assert!(combine_series(&[(0.5, a.clone()), (0.5, b)], 100.0).is_empty());
+        assert!(combine_series(&[(0.5, a), (0.5, Vec::new())], 100.0).is_empty());
+    }
+
+    #[test]
+    fn test_basket_chart_from_store() {
+        let store = ChartStore::new();
+        let bucket = chrono::Utc::now().timestamp() / 60 * 60 - 600;
+        for (i, (btc, eth)) in [(100.0, 10.0), (120.0, 9.0)].iter().enumerate() {
+            let t_ms = (bucket + i as i64 * 60) * 1000;
+            store.add_price("btc", *btc, None, t_ms);
+            store.add_price("eth", *eth, None, t_ms);
+        }
+
+        let basket = parse_basket("btc:0.5,eth:0.5").unwrap();
+        let chart = basket_chart(&store, &basket, ChartRange::OneHour);
+        assert_eq!(chart.len(), 2);
+        assert!((chart[0].close - 100.0).abs() < 1e-9);
+        // 0.5 * 1.2 + 0.5 * 0.9 = 1.05
+        assert!((chart[1].close - 105.0).abs() < 1e-9);
+    }
+}
@@ -1,11 +1,19 @@
 #![allow(dead_code)]
 
 use dashmap::DashMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::io;
+use std::path::Path;
 use std::time::{Duration, Instant};
 
 /// A thread-safe cache with TTL support.
-pub struct Cache<V> {
-    data: DashMap<String, CacheEntry<V>>,
+///
+/// Keys are strings unless another key type is given, e.g. `Cache<f64, (String, u32)>`.
+pub struct Cache<V, K = String> {
+    data: DashMap<K, CacheEntry<V>>,
     default_ttl: Duration,
 }
 
@@ -14,7 +22,16 @@ struct CacheEntry<V> {
     expires_at: Instant,
 }
 
-impl<V: Clone> Cache<V> {
+/// A live entry as written to a snapshot file.
+#[derive(Serialize, Deserialize)]
+struct SnapshotEntry<K, V> {
+    key: K,
+    value: V,
+    /// Wall-clock expiry (unix ms), so time spent offline counts against the TTL.
+    expires_at_ms: i64,
+}
+
+impl<V: Clone, K: Eq + Hash + Clone> Cache<V, K> {
     /// Create a new cache with the given default TTL.
     pub fn new(default_ttl: Duration) -> Self {
         Self {
@@ -24,7 +41,11 @@ impl<V: Clone> Cache<V> {
     }
 
     /// Get a value from the cache.
-    pub fn get(&self, key: &str) -> Option<V> {
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
         let entry = self.data.get(key)?;
         if entry.expires_at > Instant::now() {
             Some(entry.value.clone())
@@ -36,12 +57,12 @@ impl<V: Clone> Cache<V> {
     }
 
     /// Set a value in the cache with the default TTL.
-    pub fn set(&self, key: String, value: V) {
+    pub fn set(&self, key: K, value: V) {
         self.set_with_ttl(key, value, self.default_ttl);
     }
 
     /// Set a value in the cache with a custom TTL.
-    pub fn set_with_ttl(&self, key: String, value: V, ttl: Duration) {
+    pub fn set_with_ttl(&self, key: K, value: V, ttl: Duration) {
         self.data.insert(
             key,
             CacheEntry {
@@ -52,12 +73,20 @@ impl<V: Clone> Cache<V> {
     }
 
     /// Check if a key exists and is not expired.
-    pub fn contains(&self, key: &str) -> bool {
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
         self.get(key).is_some()
     }
 
     /// Remove a value from the cache.
-    pub fn remove(&self, key: &str) -> Option<V> {
+    pub fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
         self.data.remove(key).map(|(_, entry)| entry.value)
     }
 
@@ -83,6 +112,58 @@ impl<V: Clone> Cache<V> {
     }
 }
 
+impl<V, K> Cache<V, K>
+where
+    K: Eq + Hash + Clone + Serialize + DeserializeOwned,
+    V: Clone + Serialize + DeserializeOwned,
+{
+    /// Write all live entries and their expiry times to `path`.
+    ///
+    /// Expired entries are skipped. Returns the number of entries written.
+    pub fn save_snapshot(&self, path: impl AsRef<Path>) -> io::Result<usize> {
+        let now = Instant::now();
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let entries: Vec<SnapshotEntry<K, V>> = self
+            .data
+            .iter()
+            .filter(|entry| entry.expires_at > now)
+            .map(|entry| SnapshotEntry {
+                key: entry.key().clone(),
+                value: entry.value.clone(),
+                expires_at_ms: now_ms + (entry.expires_at - now).as_millis() as i64,
+            })
+            .collect();
+
+        // Write beside the target and rename so a crash never leaves a torn file.
+        let path = path.as_ref();
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        std::fs::write(&tmp, serde_json::to_vec(&entries)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(entries.len())
+    }
+
+    /// Load entries from a snapshot written by [`Cache::save_snapshot`].
+    ///
+    /// Each entry keeps its original expiry time; entries that expired while
+    /// the snapshot sat on disk are skipped. Returns the number of entries loaded.
+    pub fn load_snapshot(&self, path: impl AsRef<Path>) -> io::Result<usize> {
+        let bytes = std::fs::read(path)?;
+        let entries: Vec<SnapshotEntry<K, V>> = serde_json::from_slice(&bytes)?;
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let mut count = 0;
+        for entry in entries {
+            if entry.expires_at_ms <= now_ms {
+                continue;
+            }
+            let ttl = Duration::from_millis((entry.expires_at_ms - now_ms) as u64);
+            self.set_with_ttl(entry.key, entry.value, ttl);
+            count += 1;
+        }
+        Ok(count)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -205,4 +286,89 @@ mod tests {
 
         assert_eq!(cache.get("count"), Some(42));
     }
+
+    #[test]
+    fn test_cache_tuple_key() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        enum Timeframe {
+            Hourly,
+            Daily,
+        }
+
+        let cache: Cache<f64, (&'static str, Timeframe)> = Cache::new(Duration::from_secs(60));
+        cache.set(("btc", Timeframe::Hourly), 1.0);
+        cache.set(("btc", Timeframe::Daily), 2.0);
+
+        assert_eq!(cache.get(&("btc", Timeframe::Hourly)), Some(1.0));
+        assert_eq!(cache.get(&("btc", Timeframe::Daily)), Some(2.0));
+        assert!(!cache.contains(&("eth", Timeframe::Hourly)));
+
+        assert_eq!(cache.remove(&("btc", Timeframe::Hourly)), Some(1.0));
+        assert_eq!(cache.get(&("btc", Timeframe::Hourly)), None);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_cache_snapshot_round_trip() {
+        let path = std::env::temp_dir().join(format!("haunt-cache-{}.json", uuid::Uuid::new_v4()));
+
+        let cache: Cache<f64> = Cache::new(Duration::from_secs(60));
+        cache.set("btc".to_string(), 50000.0);
+        cache.set_with_ttl("eth".to_string(), 3000.0, Duration::from_secs(600));
+        cache.set_with_ttl("sol".to_string(), 150.0, Duration::from_millis(10));
+        std::thread::sleep(Duration::from_millis(20));
+
+        assert_eq!(cache.save_snapshot(&path).unwrap(), 2);
+
+        let restored: Cache<f64> = Cache::new(Duration::from_secs(1));
+        assert_eq!(restored.load_snapshot(&path).unwrap(), 2);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(restored.get("btc"), Some(50000.0));
+        assert_eq!(restored.get("eth"), Some(3000.0));
+        assert_eq!(restored.get("sol"), None);
+
+        // Remaining TTLs carry over instead of the new cache's default.
+        let ttl = |key: &str| restored.data.get(key).unwrap().expires_at - Instant::now();
+        assert!(ttl("btc") > Duration::from_secs(50) && ttl("btc") <= Duration::from_secs(60));
+        assert!(ttl("eth") > Duration::from_secs(590) && ttl("eth") <= Duration::from_secs(600));
+    }
+
+    #[test]
+    fn test_cache_snapshot_skips_entries_expired_while_offline() {
+        let path = std::env::temp_dir().join(format!("haunt-cache-{}.json", uuid::Uuid::new_v4()));
+
+        // A snapshot saved two minutes ago
+        let saved_at = chrono::Utc::now().timestamp_millis() - 120_000;
+        let snapshot = serde_json::json!([
+            { "key": "btc", "value": 50000.0, "expires_at_ms": saved_at + 60_000 },
+            { "key": "eth", "value": 3000.0, "expires_at_ms": saved_at + 600_000 },
+        ]);
+        std::fs::write(&path, snapshot.to_string()).unwrap();
+
+        let restored: Cache<f64> = Cache::new(Duration::from_secs(60));
+        assert_eq!(restored.load_snapshot(&path).unwrap(), 1);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(restored.get("btc"), None);
+        assert_eq!(restored.get("eth"), Some(3000.0));
+        // Downtime is subtracted from the remaining TTL
+        let ttl = restored.data.get("eth").unwrap().expires_at - Instant::now();
+        assert!(ttl > Duration::from_secs(470) && ttl <= Duration::from_secs(480));
+    }
+
+    #[test]
+    fn test_cache_snapshot_temp_file_keeps_extension() {
+        let dir = std::env::temp_dir().join(format!("haunt-cache-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir(&dir).unwrap();
+        let cache: Cache<f64> = Cache::new(Duration::from_secs(60));
+        cache.set("btc".to_string(), 50000.0);
+
+        // Saving `a.json` must not clobber a sibling `a.tmp`
+        std::fs::write(dir.join("a.tmp"), "keep").unwrap();
+        cache.save_snapshot(dir.join("a.json")).unwrap();
+        assert_eq!(std::fs::read_to_string(dir.join("a.tmp")).unwrap(), "keep");
+        assert!(!dir.join("a.json.tmp").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -1,11 +1,31 @@
+use crate::config::ChartRetentionConfig;
 use crate::types::{ChartRange, ChartResolution, Mover, MoverTimeframe, OhlcPoint};
 use dashmap::DashMap;
 use redis::aio::ConnectionManager;
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use thiserror::Error;
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
+/// Stored resolutions, finest first.
+const RESOLUTIONS: [ChartResolution; 3] = [
+    ChartResolution::OneMinute,
+    ChartResolution::FiveMinute,
+    ChartResolution::OneHour,
+];
+
+/// Errors returned when resampling chart data to a custom interval.
+#[derive(Debug, Error, PartialEq)]
+pub enum ResampleError {
+    #[error("Interval {target}s is finer than the finest stored resolution ({finest}s)")]
+    TooFine { target: i64, finest: i64 },
+
+    #[error("Interval {0}s is not a multiple of any stored resolution")]
+    Unaligned(i64),
+}
+
 /// OHLC bucket for a time period.
 #[derive(Debug, Clone)]
 struct OhlcBucket {
@@ -63,8 +83,7 @@ struct TimeSeries {
 }
 
 impl TimeSeries {
-    fn new(resolution: ChartResolution) -> Self {
-        let retention_seconds = resolution.retention_seconds();
+    fn new(resolution: ChartResolution, retention_seconds: i64) -> Self {
         let bucket_seconds = resolution.seconds();
         let max_buckets = (retention_seconds / bucket_seconds) as usize;
 
@@ -136,6 +155,14 @@ impl TimeSeries {
             .map(|b| b.to_ohlc_point())
             .collect()
     }
+
+    /// Get buckets with start time in `[start_time, end_time]`.
+    fn get_range(&self, start_time: i64, end_time: i64) -> Vec<&OhlcBucket> {
+        self.buckets
+            .iter()
+            .filter(|b| b.time >= start_time && b.time <= end_time)
+            .collect()
+    }
 }
 
 /// Symbol-specific chart data.
@@ -152,17 +179,39 @@ struct SymbolChartData {
     volume_24h: Option<f64>,
 }
 
-impl Default for SymbolChartData {
-    fn default() -> Self {
+impl SymbolChartData {
+    /// Create empty series sized for `retention`.
+    fn new(retention: &ChartRetentionConfig) -> Self {
+        let series = |resolution| TimeSeries::new(resolution, retention.seconds(resolution));
         Self {
-            one_minute: TimeSeries::new(ChartResolution::OneMinute),
-            five_minute: TimeSeries::new(ChartResolution::FiveMinute),
-            one_hour: TimeSeries::new(ChartResolution::OneHour),
+            one_minute: series(ChartResolution::OneMinute),
+            five_minute: series(ChartResolution::FiveMinute),
+            one_hour: series(ChartResolution::OneHour),
             current_price: None,
             last_update: 0,
             volume_24h: None,
         }
     }
+
+    /// Add a price point to every resolution.
+    fn add_price(&mut self, price: f64, volume: Option<f64>, timestamp: i64) {
+        self.one_minute.add_price(price, volume, timestamp);
+        self.five_minute.add_price(price, volume, timestamp);
+        self.one_hour.add_price(price, volume, timestamp);
+
+        // Track current price and update time
+        self.current_price = Some(price);
+        self.last_update = timestamp / 1000; // Convert ms to seconds
+    }
+
+    /// Get the time series for a resolution.
+    fn series(&self, resolution: ChartResolution) -> &TimeSeries {
+        match resolution {
+            ChartResolution::OneMinute => &self.one_minute,
+            ChartResolution::FiveMinute => &self.five_minute,
+            ChartResolution::OneHour => &self.one_hour,
+        }
+    }
 }
 
 const REDIS_SPARKLINE_PREFIX: &str = "haunt:sparkline:";
@@ -173,40 +222,116 @@ const MAX_REDIS_SPARKLINE_POINTS: usize = 500;
 /// TTL for sparkline data in Redis (24 hours)
 const SPARKLINE_TTL_SECS: i64 = 86400;
 
+/// Leading byte of a zstd-compressed sparkline payload (format version 1).
+/// Plain payloads start with an ASCII digit or `-`, so the two never collide.
+const SPARKLINE_FORMAT_ZSTD: u8 = 0x01;
+
+/// zstd level for sparklines; the payloads are small, so favour speed.
+const SPARKLINE_ZSTD_LEVEL: i32 = 3;
+
+/// Encode `(unix ms, price)` points as a compressed Redis string value.
+fn encode_sparkline(points: &[(i64, f64)]) -> Vec<u8> {
+    let text = points
+        .iter()
+        .map(|(timestamp, price)| format!("{}:{}", timestamp, price))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut encoded = vec![SPARKLINE_FORMAT_ZSTD];
+    // Compressing an in-memory buffer cannot fail
+    encoded.extend(zstd::encode_all(text.as_bytes(), SPARKLINE_ZSTD_LEVEL).unwrap_or_default());
+    encoded
+}
+
+/// Decode a Redis string value written by [`encode_sparkline`], or an
+/// uncompressed payload in the same `timestamp:price` line format.
+fn decode_sparkline(bytes: &[u8]) -> Option<Vec<(i64, f64)>> {
+    let text = match bytes.split_first() {
+        Some((&SPARKLINE_FORMAT_ZSTD, compressed)) => {
+            String::from_utf8(zstd::decode_all(compressed).ok()?).ok()?
+        }
+        _ => String::from_utf8(bytes.to_vec()).ok()?,
+    };
+    Some(text.lines().filter_map(parse_sparkline_point).collect())
+}
+
+/// Parse one `timestamp:price` entry.
+fn parse_sparkline_point(value: &str) -> Option<(i64, f64)> {
+    let (timestamp, price) = value.split_once(':')?;
+    Some((timestamp.parse().ok()?, price.parse().ok()?))
+}
+
+/// Read the sparkline prices stored at `key`, whether saved as a compressed
+/// string or as a legacy list of `timestamp:price` entries.
+async fn read_sparkline(conn: &mut ConnectionManager, key: &str) -> Option<Vec<f64>> {
+    let key_type: String = redis::cmd("TYPE").arg(key).query_async(conn).await.ok()?;
+    let points = match key_type.as_str() {
+        "string" => {
+            let bytes: Vec<u8> = redis::cmd("GET").arg(key).query_async(conn).await.ok()?;
+            decode_sparkline(&bytes)?
+        }
+        "list" => {
+            let values: Vec<String> = redis::cmd("LRANGE")
+                .arg(key)
+                .arg(0)
+                .arg(-1)
+                .query_async(conn)
+                .await
+                .ok()?;
+            values
+                .iter()
+                .filter_map(|v| parse_sparkline_point(v))
+                .collect()
+        }
+        _ => return None,
+    };
+
+    let prices: Vec<f64> = points.into_iter().map(|(_, price)| price).collect();
+    (!prices.is_empty()).then_some(prices)
+}
+
 /// Chart data store with multiple resolutions and optional Redis persistence.
 pub struct ChartStore {
     data: DashMap<String, SymbolChartData>,
     redis: RwLock<Option<ConnectionManager>>,
+    retention: ChartRetentionConfig,
+    /// Whether sparklines are compressed when saved to Redis.
+    redis_compression: AtomicBool,
 }
 
 impl ChartStore {
-    /// Create a new chart store.
+    /// Create a new chart store with the default retention.
     pub fn new() -> Arc<Self> {
+        Self::with_retention(ChartRetentionConfig::default())
+    }
+
+    /// Create a new chart store keeping candles for the given retention.
+    pub fn with_retention(retention: ChartRetentionConfig) -> Arc<Self> {
         Arc::new(Self {
             data: DashMap::new(),
             redis: RwLock::new(None),
+            retention,
+            redis_compression: AtomicBool::new(true),
         })
     }
 
-    /// Connect to Redis for persistence.
-    pub async fn connect_redis(&self, redis_url: &str) {
-        match redis::Client::open(redis_url) {
-            Ok(client) => match ConnectionManager::new(client).await {
-                Ok(conn) => {
-                    info!("ChartStore connected to Redis at {}", redis_url);
-                    *self.redis.write().await = Some(conn);
-                }
-                Err(e) => {
-                    warn!(
-                        "Failed to connect ChartStore to Redis: {}. Data will not persist.",
-                        e
-                    );
-                }
-            },
-            Err(e) => {
-                warn!("Invalid Redis URL: {}. Data will not persist.", e);
-            }
-        }
+    /// Get or create the chart data for a lowercased symbol.
+    fn entry(&self, symbol: String) -> dashmap::mapref::one::RefMut<'_, String, SymbolChartData> {
+        self.data
+            .entry(symbol)
+            .or_insert_with(|| SymbolChartData::new(&self.retention))
+    }
+
+    /// Choose whether sparklines are compressed when saved to Redis. Both
+    /// formats are always readable, so this can be flipped between restarts.
+    pub fn set_redis_compression(&self, enabled: bool) {
+        self.redis_compression.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Use a shared Redis connection for persistence.
+    pub async fn connect_redis(&self, conn: ConnectionManager) {
+        info!("ChartStore connected to Redis");
+        *self.redis.write().await = Some(conn);
     }
 
     /// Load sparkline data from Redis for known symbols.
@@ -222,30 +347,14 @@ impl ChartStore {
         for symbol in symbols {
             let key = format!("{}{}", REDIS_SPARKLINE_PREFIX, symbol.to_lowercase());
 
-            let values: Result<Vec<String>, _> = redis::cmd("LRANGE")
-                .arg(&key)
-                .arg(0)
-                .arg(-1)
-                .query_async(&mut conn)
-                .await;
-
-            if let Ok(vals) = values {
-                if !vals.is_empty() {
-                    let prices: Vec<f64> = vals
-                        .iter()
-                        .filter_map(|v| v.split(':').nth(1).and_then(|p| p.parse().ok()))
-                        .collect();
-
-                    if !prices.is_empty() {
-                        self.seed_sparkline(symbol, &prices);
-                        loaded_count += 1;
-                        debug!(
-                            "Loaded {} sparkline points for {} from Redis",
-                            prices.len(),
-                            symbol
-                        );
-                    }
-                }
+            if let Some(prices) = read_sparkline(&mut conn, &key).await {
+                self.seed_sparkline(symbol, &prices);
+                loaded_count += 1;
+                debug!(
+                    "Loaded {} sparkline points for {} from Redis",
+                    prices.len(),
+                    symbol
+                );
             }
         }
 
@@ -272,9 +381,26 @@ impl ChartStore {
         let key = format!("{}{}", REDIS_SPARKLINE_PREFIX, symbol.to_lowercase());
         let now = chrono::Utc::now().timestamp_millis();
         let interval_ms = 60_000i64; // 1 minute
+        let points: Vec<(i64, f64)> = sparkline
+            .iter()
+            .enumerate()
+            .map(|(i, price)| (now - (sparkline.len() - 1 - i) as i64 * interval_ms, *price))
+            .collect();
 
         let mut conn = conn.clone();
 
+        // One compressed string replaces the list (SET overwrites either type)
+        if self.redis_compression.load(Ordering::Relaxed) {
+            let _ = redis::cmd("SET")
+                .arg(&key)
+                .arg(encode_sparkline(&points))
+                .arg("EX")
+                .arg(SPARKLINE_TTL_SECS)
+                .query_async::<_, ()>(&mut conn)
+                .await;
+            return;
+        }
+
         // Clear existing data
         let _ = redis::cmd("DEL")
             .arg(&key)
@@ -282,8 +408,7 @@ impl ChartStore {
             .await;
 
         // Add all points
-        for (i, price) in sparkline.iter().enumerate() {
-            let timestamp = now - (sparkline.len() - 1 - i) as i64 * interval_ms;
+        for (timestamp, price) in &points {
             let value = format!("{}:{}", timestamp, price);
             let _ = redis::cmd("RPUSH")
                 .arg(&key)
@@ -338,32 +463,14 @@ impl ChartStore {
                     for key in keys {
                         // Extract symbol from key (e.g., "haunt:sparkline:btc" -> "btc")
                         if let Some(symbol) = key.strip_prefix(REDIS_SPARKLINE_PREFIX) {
-                            let values: Result<Vec<String>, _> = redis::cmd("LRANGE")
-                                .arg(&key)
-                                .arg(0)
-                                .arg(-1)
-                                .query_async(&mut conn)
-                                .await;
-
-                            if let Ok(vals) = values {
-                                if !vals.is_empty() {
-                                    let prices: Vec<f64> = vals
-                                        .iter()
-                                        .filter_map(|v| {
-                                            v.split(':').nth(1).and_then(|p| p.parse().ok())
-                                        })
-                                        .collect();
-
-                                    if !prices.is_empty() {
-                                        self.seed_sparkline(symbol, &prices);
-                                        loaded_count += 1;
-                                        debug!(
-                                            "Loaded {} sparkline points for {} from Redis",
-                                            prices.len(),
-                                            symbol
-                                        );
-                                    }
-                                }
+                            if let Some(prices) = read_sparkline(&mut conn, &key).await {
+                                self.seed_sparkline(symbol, &prices);
+                                loaded_count += 1;
+                                debug!(
+                                    "Loaded {} sparkline points for {} from Redis",
+                                    prices.len(),
+                                    symbol
+                                );
                             }
                         }
                     }
@@ -390,17 +497,29 @@ impl ChartStore {
 
     /// Add a price point for a symbol.
     pub fn add_price(&self, symbol: &str, price: f64, volume: Option<f64>, timestamp: i64) {
-        let symbol_lower = symbol.to_lowercase();
-        let mut entry = self.data.entry(symbol_lower).or_default();
-        let chart_data = entry.value_mut();
+        self.entry(symbol.to_lowercase())
+            .add_price(price, volume, timestamp);
+    }
 
-        chart_data.one_minute.add_price(price, volume, timestamp);
-        chart_data.five_minute.add_price(price, volume, timestamp);
-        chart_data.one_hour.add_price(price, volume, timestamp);
+    /// Add many `(symbol, price, volume, timestamp)` points at once.
+    ///
+    /// Points are grouped by symbol so each symbol's entry is locked once;
+    /// points for the same symbol are applied in batch order.
+    pub fn add_prices(&self, batch: &[(&str, f64, Option<f64>, i64)]) {
+        let mut groups: HashMap<String, Vec<(f64, Option<f64>, i64)>> = HashMap::new();
+        for &(symbol, price, volume, timestamp) in batch {
+            groups
+                .entry(symbol.to_lowercase())
+                .or_default()
+                .push((price, volume, timestamp));
+        }
 
-        // Track current price and update time
-        chart_data.current_price = Some(price);
-        chart_data.last_update = timestamp / 1000; // Convert ms to seconds
+        for (symbol, points) in groups {
+            let mut entry = self.entry(symbol);
+            for (price, volume, timestamp) in points {
+                entry.add_price(price, volume, timestamp);
+            }
+        }
     }
 
     /// Update 24h volume for a symbol (from authoritative sources).
@@ -578,6 +697,74 @@ impl ChartStore {
         }
     }
 
+    /// Get chart data for a range as Heikin-Ashi candles.
+    ///
+    /// The stored candles are left untouched; see [`heikin_ashi`] for the transform.
+    pub fn get_chart_heikin_ashi(&self, symbol: &str, range: ChartRange) -> Vec<OhlcPoint> {
+        heikin_ashi(&self.get_chart(symbol, range))
+    }
+
+    /// Get chart data between `start` and `end` (unix seconds) at an arbitrary interval.
+    ///
+    /// Uses the finest stored resolution whose buckets divide `target_seconds` and whose
+    /// retention still covers `start`, then folds those buckets into `target_seconds`
+    /// buckets (open=first, high=max, low=min, close=last, volume=sum).
+    pub fn get_chart_resampled(
+        &self,
+        symbol: &str,
+        start: i64,
+        end: i64,
+        target_seconds: i64,
+    ) -> Result<Vec<OhlcPoint>, ResampleError> {
+        let finest = RESOLUTIONS[0].seconds();
+        if target_seconds < finest {
+            return Err(ResampleError::TooFine {
+                target: target_seconds,
+                finest,
+            });
+        }
+
+        let candidates: Vec<ChartResolution> = RESOLUTIONS
+            .iter()
+            .copied()
+            .filter(|r| target_seconds % r.seconds() == 0)
+            .collect();
+        let Some(&coarsest) = candidates.last() else {
+            return Err(ResampleError::Unaligned(target_seconds));
+        };
+
+        let now = chrono::Utc::now().timestamp();
+        let resolution = candidates
+            .iter()
+            .copied()
+            .find(|r| now - self.retention.seconds(*r) <= start)
+            .unwrap_or(coarsest);
+
+        let entry = match self.data.get(&symbol.to_lowercase()) {
+            Some(e) => e,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut result: Vec<OhlcBucket> = Vec::new();
+        for bucket in entry.series(resolution).get_range(start, end) {
+            let bucket_time = bucket.time.div_euclid(target_seconds) * target_seconds;
+            match result.last_mut() {
+                Some(last) if last.time == bucket_time => {
+                    last.high = last.high.max(bucket.high);
+                    last.low = last.low.min(bucket.low);
+                    last.close = bucket.close;
+                    last.volume += bucket.volume;
+                }
+                _ => result.push(OhlcBucket {
+                    time: bucket_time,
+                    ..bucket.clone()
+                }),
+            }
+        }
+
+        Ok(result.iter().map(|b| b.to_ohlc_point()).collect())
+    }
+
     /// Get sparkline data for a symbol (last N close prices).
     /// Returns prices from available data, preferring 1-hour resolution for longer sparklines.
     pub fn get_sparkline(&self, symbol: &str, points: usize) -> Vec<f64> {
@@ -724,6 +911,342 @@ impl Default for ChartStore {
         Self {
             data: DashMap::new(),
             redis: RwLock::new(None),
+            retention: ChartRetentionConfig::default(),
+            redis_compression: AtomicBool::new(true),
+        }
+    }
+}
+
+/// Transform standard OHLC candles into Heikin-Ashi candles.
+///
+/// - HA close = (open + high + low + close) / 4
+/// - HA open = (previous HA open + previous HA close) / 2, seeded with
+///   (open + close) / 2 of the first candle
+/// - HA high/low = extremes of the candle's high/low and the HA open/close
+///
+/// Time and volume are carried over unchanged.
+pub fn heikin_ashi(candles: &[OhlcPoint]) -> Vec<OhlcPoint> {
+    let mut result: Vec<OhlcPoint> = Vec::with_capacity(candles.len());
+    for candle in candles {
+        let close = (candle.open + candle.high + candle.low + candle.close) / 4.0;
+        let open = match result.last() {
+            Some(prev) => (prev.open + prev.close) / 2.0,
+            None => (candle.open + candle.close) / 2.0,
+        };
+        result.push(OhlcPoint {
+            time: candle.time,
+            open,
+            high: candle.high.max(open).max(close),
+            low: candle.low.min(open).min(close),
+            close,
+            volume: candle.volume,
+        });
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feed one price per minute for `minutes` minutes starting at `start` (unix seconds).
+    fn seed_minutes(store: &ChartStore, symbol: &str, start: i64, minutes: i64) {
+        for i in 0..minutes {
+            let t = start + i * 60;
+            let base = 100.0 + i as f64;
+            // Open, spike high, dip low, then close inside each minute
+            store.add_price(symbol, base, None, t * 1000);
+            store.add_price(symbol, base + 5.0, Some(1.0), (t + 10) * 1000);
+            store.add_price(symbol, base - 5.0, Some(1.0), (t + 20) * 1000);
+            store.add_price(symbol, base + 1.0, Some(1.0), (t + 30) * 1000);
         }
     }
+
+    fn aligned_start(minutes_ago: i64) -> i64 {
+        let now = chrono::Utc::now().timestamp();
+        (now - minutes_ago * 60).div_euclid(1800) * 1800
+    }
+
+    #[test]
+    fn test_resample_one_minute_into_thirty_minutes() {
+        let store = ChartStore::default();
+        let start = aligned_start(120);
+        seed_minutes(&store, "BTC", start, 60);
+
+        let points = store
+            .get_chart_resampled("btc", start, start + 3600, 1800)
+            .unwrap();
+        assert_eq!(points.len(), 2);
+
+        let first = &points[0];
+        assert_eq!(first.time, start);
+        assert_eq!(first.open, 100.0);
+        assert_eq!(first.high, 129.0 + 5.0);
+        assert_eq!(first.low, 100.0 - 5.0);
+        assert_eq!(first.close, 129.0 + 1.0);
+        assert_eq!(first.volume, Some(30.0 * 3.0));
+
+        let second = &points[1];
+        assert_eq!(second.time, start + 1800);
+        assert_eq!(second.open, 130.0);
+        assert_eq!(second.high, 159.0 + 5.0);
+        assert_eq!(second.low, 130.0 - 5.0);
+        assert_eq!(second.close, 159.0 + 1.0);
+    }
+
+    #[test]
+    fn test_sparkline_codec_round_trips_chart_data() {
+        let store = ChartStore::default();
+        seed_minutes(&store, "btc", aligned_start(300), 300);
+        let sparkline = store.get_sparkline("btc", MAX_REDIS_SPARKLINE_POINTS);
+        let now = chrono::Utc::now().timestamp_millis();
+        let points: Vec<(i64, f64)> = sparkline
+            .iter()
+            .enumerate()
+            .map(|(i, price)| (now - (sparkline.len() - i) as i64 * 60_000, *price))
+            .collect();
+
+        let encoded = encode_sparkline(&points);
+        assert_eq!(encoded[0], SPARKLINE_FORMAT_ZSTD);
+        let plain: String = points
+            .iter()
+            .map(|(t, p)| format!("{}:{}\n", t, p))
+            .collect();
+        assert!(encoded.len() < plain.len() / 2);
+        assert_eq!(decode_sparkline(&encoded).unwrap(), points);
+
+        // The loaded prices are exactly the persisted sparkline
+        let prices: Vec<f64> = decode_sparkline(&encoded)
+            .unwrap()
+            .into_iter()
+            .map(|(_, p)| p)
+            .collect();
+        assert_eq!(prices, sparkline);
+    }
+
+    #[test]
+    fn test_decode_uncompressed_sparkline() {
+        assert_eq!(
+            decode_sparkline(b"1700000000000:101.5\n1700000060000:102\nbad"),
+            Some(vec![(1_700_000_000_000, 101.5), (1_700_000_060_000, 102.0)])
+        );
+        assert_eq!(decode_sparkline(&[SPARKLINE_FORMAT_ZSTD, 0xff, 0x00]), None);
+    }
+
+    /// Requires a running Redis server (REDIS_URL, default redis://127.0.0.1:6379).
+    #[tokio::test]
+    #[ignore]
+    async fn test_redis_loads_compressed_and_legacy_sparklines() {
+        let url =
+            std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+        let mut conn = crate::services::redis_store::connect_shared(&url)
+            .await
+            .expect("Redis not available");
+
+        let store = ChartStore::new();
+        store.connect_redis(conn.clone()).await;
+        seed_minutes(&store, "zstest", aligned_start(60), 30);
+        let sparkline = store.get_sparkline("zstest", MAX_REDIS_SPARKLINE_POINTS);
+        store.save_to_redis("zstest").await;
+
+        // A list written by an older server alongside the compressed key
+        let legacy_key = format!("{}zslegacy", REDIS_SPARKLINE_PREFIX);
+        for value in ["1700000000000:5", "1700000060000:6"] {
+            let _ = redis::cmd("RPUSH")
+                .arg(&legacy_key)
+                .arg(value)
+                .query_async::<_, i64>(&mut conn)
+                .await;
+        }
+
+        let loaded = ChartStore::new();
+        loaded.connect_redis(conn.clone()).await;
+        loaded.load_from_redis(&["zstest", "zslegacy"]).await;
+        let key = format!("{}zstest", REDIS_SPARKLINE_PREFIX);
+        assert_eq!(read_sparkline(&mut conn, &key).await, Some(sparkline));
+        assert_eq!(
+            read_sparkline(&mut conn, &legacy_key).await,
+            Some(vec![5.0, 6.0])
+        );
+        assert_eq!(loaded.get_current_price("zstest"), store.get_current_price("zstest"));
+        assert_eq!(loaded.get_current_price("zslegacy"), Some(6.0));
+
+        for symbol in ["zstest", "zslegacy"] {
+            let _ = redis::cmd("DEL")
+                .arg(format!("{}{}", REDIS_SPARKLINE_PREFIX, symbol))
+                .query_async::<_, ()>(&mut conn)
+                .await;
+        }
+    }
+
+    #[test]
+    fn test_longer_retention_keeps_more_minute_buckets() {
+        // Six hours of minutes overflows the default four-hour 1m retention
+        let start = aligned_start(360);
+        let default_store = ChartStore::default();
+        seed_minutes(&default_store, "btc", start, 360);
+
+        let retention = ChartRetentionConfig {
+            one_minute_secs: 8 * 3600,
+            ..ChartRetentionConfig::default()
+        };
+        let long_store = ChartStore::with_retention(retention);
+        seed_minutes(&long_store, "btc", start, 360);
+
+        let minute_buckets =
+            |store: &ChartStore| store.data.get("btc").unwrap().one_minute.buckets.len();
+        assert_eq!(minute_buckets(&default_store), 240);
+        assert_eq!(minute_buckets(&long_store), 360);
+
+        // The oldest minutes were evicted from the default store only
+        let oldest = |store: &ChartStore| store.data.get("btc").unwrap().one_minute.buckets[0].time;
+        assert_eq!(oldest(&default_store), start + 120 * 60);
+        assert_eq!(oldest(&long_store), start);
+    }
+
+    #[test]
+    fn test_add_prices_matches_individual_inserts() {
+        let start = aligned_start(90);
+        let mut batch = Vec::new();
+        for i in 0..120 {
+            let t = (start + i * 20) * 1000;
+            let wave = (i % 7) as f64;
+            // Interleave symbols, mixing case to check grouping
+            batch.push(("BTC", 100.0 + wave, Some(1.0 + wave), t));
+            batch.push(("eth", 50.0 - wave, None, t));
+            batch.push(("btc", 101.0 + wave, Some(2.0), t + 5_000));
+        }
+
+        let individual = ChartStore::default();
+        for &(symbol, price, volume, timestamp) in &batch {
+            individual.add_price(symbol, price, volume, timestamp);
+        }
+        let batched = ChartStore::default();
+        batched.add_prices(&batch);
+
+        let as_tuples = |points: Vec<OhlcPoint>| -> Vec<(i64, f64, f64, f64, f64, Option<f64>)> {
+            points
+                .into_iter()
+                .map(|p| (p.time, p.open, p.high, p.low, p.close, p.volume))
+                .collect()
+        };
+        for symbol in ["btc", "eth"] {
+            for range in [ChartRange::FourHours, ChartRange::OneDay, ChartRange::OneWeek] {
+                let expected = as_tuples(individual.get_chart(symbol, range));
+                assert!(!expected.is_empty());
+                assert_eq!(as_tuples(batched.get_chart(symbol, range)), expected);
+            }
+            assert_eq!(
+                batched.get_current_price(symbol),
+                individual.get_current_price(symbol)
+            );
+        }
+        assert_eq!(batched.get_current_price("btc"), Some(101.0 + (119 % 7) as f64));
+    }
+
+    #[test]
+    fn test_resample_respects_range_bounds() {
+        let store = ChartStore::default();
+        let start = aligned_start(120);
+        seed_minutes(&store, "ETH", start, 60);
+
+        let points = store
+            .get_chart_resampled("eth", start + 1800, start + 3600, 1800)
+            .unwrap();
+        assert_eq!(points.len(), 1);
+        assert_eq!(points[0].open, 130.0);
+    }
+
+    #[test]
+    fn test_resample_rejects_finer_than_stored() {
+        let store = ChartStore::default();
+        assert_eq!(
+            store.get_chart_resampled("btc", 0, 100, 30).unwrap_err(),
+            ResampleError::TooFine {
+                target: 30,
+                finest: 60
+            }
+        );
+        assert_eq!(
+            store.get_chart_resampled("btc", 0, 100, 90).unwrap_err(),
+            ResampleError::Unaligned(90)
+        );
+    }
+
+    #[test]
+    fn test_resample_unknown_symbol_is_empty() {
+        let store = ChartStore::default();
+        let points = store.get_chart_resampled("nope", 0, 100, 1800).unwrap();
+        assert!(points.is_empty());
+    }
+
+    fn candle(time: i64, open: f64, high: f64, low: f64, close: f64) -> OhlcPoint {
+        OhlcPoint {
+            time,
+            open,
+            high,
+            low,
+            close,
+            volume: Some(1.0),
+        }
+    }
+
+    #[test]
+    fn test_heikin_ashi_known_series() {
+        let candles = vec![
+            candle(0, 10.0, 12.0, 9.0, 11.0),
+            candle(60, 11.0, 14.0, 10.0, 13.0),
+            candle(120, 13.0, 13.5, 8.0, 9.0),
+        ];
+        let ha = heikin_ashi(&candles);
+
+        // Bar 1: seeded open (10 + 11) / 2
+        assert_eq!(ha[0].open, 10.5);
+        assert_eq!(ha[0].close, 10.5);
+        assert_eq!(ha[0].high, 12.0);
+        assert_eq!(ha[0].low, 9.0);
+
+        // Bar 2: open = (10.5 + 10.5) / 2, close = (11 + 14 + 10 + 13) / 4
+        assert_eq!(ha[1].open, 10.5);
+        assert_eq!(ha[1].close, 12.0);
+        assert_eq!(ha[1].high, 14.0);
+        assert_eq!(ha[1].low, 10.0);
+
+        // Bar 3: open = (10.5 + 12) / 2, close = (13 + 13.5 + 8 + 9) / 4
+        assert_eq!(ha[2].open, 11.25);
+        assert_eq!(ha[2].close, 10.875);
+        assert_eq!(ha[2].high, 13.5);
+        assert_eq!(ha[2].low, 8.0);
+
+        assert_eq!(ha[2].time, 120);
+        assert_eq!(ha[2].volume, Some(1.0));
+        assert!(heikin_ashi(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_heikin_ashi_high_low_include_body() {
+        // A gap down leaves the HA open above the raw candle's high
+        let candles = vec![
+            candle(0, 100.0, 101.0, 99.0, 100.0),
+            candle(60, 90.0, 91.0, 89.0, 90.0),
+        ];
+        let ha = heikin_ashi(&candles);
+        assert_eq!(ha[1].open, 100.0);
+        assert_eq!(ha[1].high, 100.0);
+        assert_eq!(ha[1].low, 89.0);
+    }
+
+    #[test]
+    fn test_get_chart_heikin_ashi_leaves_candles_unchanged() {
+        let store = ChartStore::default();
+        let start = aligned_start(30);
+        seed_minutes(&store, "ETH", start, 10);
+
+        let raw = store.get_chart("eth", ChartRange::OneHour);
+        let ha = store.get_chart_heikin_ashi("eth", ChartRange::OneHour);
+        assert_eq!(raw.len(), ha.len());
+        assert!(!raw.is_empty());
+        assert_eq!(store.get_chart("eth", ChartRange::OneHour)[0].open, raw[0].open);
+        assert_eq!(ha[0].close, (raw[0].open + raw[0].high + raw[0].low + raw[0].close) / 4.0);
+    }
 }
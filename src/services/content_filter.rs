@@ -0,0 +1,202 @@
+//! User-generated content checks
+//!
+//! Validates free text users can show to others (profile bios) and profile
+//! avatar URLs. Text is normalized (case, common digit/symbol substitutions,
+//! punctuation inside words) before matching so "Sh1t" or "s.h.i.t" are
+//! caught without flagging innocent words that merely contain a blocked term.
+
+/// Maximum bio length in characters.
+pub const MAX_BIO_LENGTH: usize = 280;
+
+/// Maximum avatar URL length.
+pub const MAX_AVATAR_URL_LENGTH: usize = 2048;
+
+/// Words rejected anywhere in the text (matched against whole words).
+const BLOCKED_WORDS: &[&str] = &[
+    "fuck", "fucker", "fucking", "shit", "bitch", "cunt", "asshole", "bastard", "dick", "cock",
+    "pussy", "whore", "slut", "retard", "nazi",
+];
+
+/// Phrases commonly used by scams impersonating traders.
+const BLOCKED_PHRASES: &[&str] = &[
+    "send btc",
+    "send eth",
+    "send crypto",
+    "double your",
+    "guaranteed profit",
+    "guaranteed returns",
+    "dm for signals",
+    "seed phrase",
+    "private key",
+];
+
+/// Why a piece of content was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum ContentError {
+    #[error("Bio must be at most 280 characters")]
+    BioTooLong,
+    #[error("Bio contains control characters")]
+    ControlCharacters,
+    #[error("Content contains blocked language")]
+    BlockedContent,
+    #[error("Avatar URL must be an https:// URL")]
+    InvalidAvatarUrl,
+}
+
+/// Normalize a word for matching: lowercase, undo leetspeak, drop punctuation.
+fn normalize_word(word: &str) -> String {
+    // Trailing sentence punctuation isn't a letter substitution
+    word.trim_end_matches(['!', '?', '.', ',', ';', ':', ')', '"', '\''])
+        .chars()
+        .filter_map(|c| {
+            let c = match c.to_ascii_lowercase() {
+                '0' => 'o',
+                '1' | '!' | '|' => 'i',
+                '3' => 'e',
+                '4' | '@' => 'a',
+                '5' | '$' => 's',
+                '7' => 't',
+                c => c,
+            };
+            c.is_alphanumeric().then_some(c)
+        })
+        .collect()
+}
+
+/// Returns true if `text` contains a blocked word or phrase.
+pub fn contains_blocked_content(text: &str) -> bool {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(normalize_word)
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    if words.iter().any(|w| BLOCKED_WORDS.contains(&w.as_str())) {
+        return true;
+    }
+
+    let joined = format!(" {} ", words.join(" "));
+    BLOCKED_PHRASES
+        .iter()
+        .any(|phrase| joined.contains(&format!(" {} ", phrase)))
+}
+
+/// Validate and normalize a bio. Blank bios become None.
+pub fn validate_bio(bio: Option<String>) -> Result<Option<String>, ContentError> {
+    let Some(bio) = bio.map(|b| b.trim().to_string()).filter(|b| !b.is_empty()) else {
+        return Ok(None);
+    };
+
+    if bio.chars().count() > MAX_BIO_LENGTH {
+        return Err(ContentError::BioTooLong);
+    }
+    if bio.chars().any(|c| c.is_control() && c != '\n') {
+        return Err(ContentError::ControlCharacters);
+    }
+    if contains_blocked_content(&bio) {
+        return Err(ContentError::BlockedContent);
+    }
+
+    Ok(Some(bio))
+}
+
+/// Validate an avatar URL. Only https URLs with a host are accepted; blank
+/// values become None.
+pub fn validate_avatar_url(url: Option<String>) -> Result<Option<String>, ContentError> {
+    let Some(url) = url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty()) else {
+        return Ok(None);
+    };
+
+    if url.len() > MAX_AVATAR_URL_LENGTH {
+        return Err(ContentError::InvalidAvatarUrl);
+    }
+    let parsed = reqwest::Url::parse(&url).map_err(|_| ContentError::InvalidAvatarUrl)?;
+    if parsed.scheme() != "https" || parsed.host_str().is_none() {
+        return Err(ContentError::InvalidAvatarUrl);
+    }
+
+    Ok(Some(url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bio_length_limit() {
+        let max = "a".repeat(MAX_BIO_LENGTH);
+        assert_eq!(validate_bio(Some(max.clone())), Ok(Some(max)));
+
+        let too_long = "a".repeat(MAX_BIO_LENGTH + 1);
+        assert_eq!(validate_bio(Some(too_long)), Err(ContentError::BioTooLong));
+
+        // Counted in characters, not bytes
+        let emoji = "🚀".repeat(MAX_BIO_LENGTH);
+        assert!(validate_bio(Some(emoji)).is_ok());
+    }
+
+    #[test]
+    fn test_bio_blank_clears() {
+        assert_eq!(validate_bio(None), Ok(None));
+        assert_eq!(validate_bio(Some("   ".to_string())), Ok(None));
+        assert_eq!(
+            validate_bio(Some("  hodl  ".to_string())),
+            Ok(Some("hodl".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_bio_content_checks() {
+        for bad in [
+            "what the FUCK",
+            "total sh1t coin",
+            "b.i.t.c.h",
+            "Send BTC to double your money",
+            "DM for signals!!",
+        ] {
+            assert_eq!(
+                validate_bio(Some(bad.to_string())),
+                Err(ContentError::BlockedContent),
+                "{}",
+                bad
+            );
+        }
+
+        // Blocked words inside innocent words are fine
+        for good in [
+            "Scunthorpe trader",
+            "Dickens fan, shiitake farmer",
+            "Cocktail hour",
+        ] {
+            assert!(validate_bio(Some(good.to_string())).is_ok(), "{}", good);
+        }
+
+        assert_eq!(
+            validate_bio(Some("tab\there".to_string())),
+            Err(ContentError::ControlCharacters)
+        );
+    }
+
+    #[test]
+    fn test_avatar_url_scheme() {
+        assert_eq!(
+            validate_avatar_url(Some("https://cdn.example.com/a.png".to_string())),
+            Ok(Some("https://cdn.example.com/a.png".to_string()))
+        );
+        assert_eq!(validate_avatar_url(Some(String::new())), Ok(None));
+
+        for bad in [
+            "http://example.com/a.png",
+            "javascript:alert(1)",
+            "data:image/png;base64,AAAA",
+            "not a url",
+        ] {
+            assert_eq!(
+                validate_avatar_url(Some(bad.to_string())),
+                Err(ContentError::InvalidAvatarUrl),
+                "{}",
+                bad
+            );
+        }
+    }
+}
@@ -7,7 +7,8 @@
 
 use serde::{de::DeserializeOwned, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tracing::{debug, warn};
 
@@ -19,11 +20,23 @@ const CACHE_DIR: &str = ".haunt_cache";
 struct CacheEntry<T> {
     data: T,
     timestamp: u64,
+    /// Per-entry expiry (unix seconds), if set with a TTL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_at: Option<u64>,
+}
+
+/// Entry header used when only the expiry matters.
+#[derive(Debug, serde::Deserialize)]
+struct CacheEntryMeta {
+    #[serde(default)]
+    expires_at: Option<u64>,
 }
 
 /// File-based cache service.
 pub struct FileCache {
     cache_dir: PathBuf,
+    /// Total size cap for the cache directory (bytes).
+    max_size_bytes: Option<u64>,
 }
 
 impl FileCache {
@@ -35,7 +48,28 @@ impl FileCache {
                 warn!("Failed to create cache directory: {}", e);
             }
         }
-        Self { cache_dir }
+        Self {
+            cache_dir,
+            max_size_bytes: None,
+        }
+    }
+
+    /// Create a file cache that evicts least-recently-used entries beyond `max_size_bytes`.
+    pub fn with_max_size(max_size_bytes: u64) -> Self {
+        Self {
+            max_size_bytes: Some(max_size_bytes),
+            ..Self::new()
+        }
+    }
+
+    /// Run `cleanup_expired()` every `interval` in the background.
+    pub fn start_janitor(self: Arc<Self>, interval: Duration) {
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                self.cleanup_expired();
+            }
+        });
     }
 
     /// Get the cache file path for a key.
@@ -63,16 +97,19 @@ impl FileCache {
         };
 
         // Check if expired
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
+        let now = now_secs();
 
         if now - entry.timestamp > max_age.as_secs() {
             debug!("Cache entry {} expired", key);
             return None;
         }
 
+        if entry.expires_at.is_some_and(|expires_at| now >= expires_at) {
+            debug!("Cache entry {} past its TTL", key);
+            return None;
+        }
+
+        touch(&path);
         Some(entry.data)
     }
 
@@ -99,14 +136,25 @@ impl FileCache {
 
     /// Set data in cache.
     pub fn set<T: Serialize>(&self, key: &str, data: &T) {
+        self.write_entry(key, data, None);
+    }
+
+    /// Set data in cache, expiring after `ttl`.
+    pub fn set_with_ttl<T: Serialize>(&self, key: &str, data: &T, ttl: Duration) {
+        self.write_entry(key, data, Some(ttl));
+    }
+
+    fn write_entry<T: Serialize>(&self, key: &str, data: &T, ttl: Option<Duration>) {
         let path = self.get_path(key);
 
-        let timestamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
+        let timestamp = now_secs();
+        let expires_at = ttl.map(|ttl| timestamp + ttl.as_secs());
 
-        let entry = CacheEntry { data, timestamp };
+        let entry = CacheEntry {
+            data,
+            timestamp,
+            expires_at,
+        };
 
         match serde_json::to_string(&entry) {
             Ok(content) => {
@@ -114,6 +162,7 @@ impl FileCache {
                     warn!("Failed to write cache {}: {}", key, e);
                 } else {
                     debug!("Cached {} to disk", key);
+                    self.enforce_size_limit(Some(&path));
                 }
             }
             Err(e) => {
@@ -128,8 +177,80 @@ impl FileCache {
         let _ = fs::remove_file(path);
     }
 
-    /// Clean up old cache files.
+    /// Remove entries past their TTL and enforce the size cap.
+    pub fn cleanup_expired(&self) {
+        let now = now_secs();
+        for (path, _, _) in self.cache_files() {
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let expired = serde_json::from_str::<CacheEntryMeta>(&content)
+                .ok()
+                .and_then(|meta| meta.expires_at)
+                .is_some_and(|expires_at| now >= expires_at);
+            if expired {
+                let _ = fs::remove_file(&path);
+                debug!("Removed expired cache file: {:?}", path);
+            }
+        }
+
+        self.enforce_size_limit(None);
+    }
+
+    /// Evict least-recently-used files until the cache fits the size cap.
+    /// `keep` is never evicted (the entry just written).
+    fn enforce_size_limit(&self, keep: Option<&Path>) {
+        let Some(max_size) = self.max_size_bytes else {
+            return;
+        };
+
+        let mut files = self.cache_files();
+        let mut total: u64 = files.iter().map(|(_, size, _)| size).sum();
+        if total <= max_size {
+            return;
+        }
+
+        // Oldest access first
+        files.sort_by_key(|(_, _, accessed)| *accessed);
+        for (path, size, _) in files {
+            if total <= max_size {
+                break;
+            }
+            if keep == Some(path.as_path()) {
+                continue;
+            }
+            if fs::remove_file(&path).is_ok() {
+                total = total.saturating_sub(size);
+                debug!("Evicted cache file to fit size cap: {:?}", path);
+            }
+        }
+    }
+
+    /// List cache files with their size and last access (modification) time.
+    fn cache_files(&self) -> Vec<(PathBuf, u64, SystemTime)> {
+        let Ok(entries) = fs::read_dir(&self.cache_dir) else {
+            return Vec::new();
+        };
+
+        entries
+            .flatten()
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                let accessed = metadata.modified().unwrap_or(UNIX_EPOCH);
+                Some((entry.path(), metadata.len(), accessed))
+            })
+            .collect()
+    }
+
+    /// Clean up cache files not written or read within `max_age`.
+    #[deprecated(note = "renamed to `cleanup_older_than`")]
     pub fn cleanup(&self, max_age: Duration) {
+        self.cleanup_older_than(max_age);
+    }
+
+    /// Clean up cache files not written or read within `max_age`.
+    pub fn cleanup_older_than(&self, max_age: Duration) {
         let Ok(entries) = fs::read_dir(&self.cache_dir) else {
             return;
         };
@@ -151,6 +272,21 @@ impl FileCache {
     }
 }
 
+/// Current unix time in seconds.
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Bump a file's modification time so LRU eviction sees it as recently used.
+fn touch(path: &Path) {
+    if let Ok(file) = fs::File::options().write(true).open(path) {
+        let _ = file.set_modified(SystemTime::now());
+    }
+}
+
 impl Default for FileCache {
     fn default() -> Self {
         Self::new()
@@ -168,7 +304,10 @@ mod tests {
             let _ = fs::remove_dir_all(&cache_dir);
         }
         let _ = fs::create_dir_all(&cache_dir);
-        FileCache { cache_dir }
+        FileCache {
+            cache_dir,
+            max_size_bytes: None,
+        }
     }
 
     fn cleanup_test_cache(cache: &FileCache) {
@@ -291,6 +430,67 @@ mod tests {
         cleanup_test_cache(&cache);
     }
 
+    #[test]
+    fn test_file_cache_ttl_expired_entry_misses() {
+        let cache = create_test_cache("ttl");
+
+        cache.set_with_ttl("short", &"value".to_string(), Duration::from_secs(1));
+        cache.set_with_ttl("long", &"value".to_string(), Duration::from_secs(60));
+        let hit: Option<String> = cache.get("short", Duration::from_secs(60));
+        assert_eq!(hit, Some("value".to_string()));
+
+        thread::sleep(Duration::from_secs(2));
+
+        // TTL applies even though max_age would still allow the entry
+        let miss: Option<String> = cache.get("short", Duration::from_secs(60));
+        assert!(miss.is_none());
+
+        // cleanup_expired() removes only the expired file
+        cache.cleanup_expired();
+        assert!(cache.get_stale::<String>("short").is_none());
+        assert!(cache.get_stale::<String>("long").is_some());
+        cleanup_test_cache(&cache);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_file_cache_deprecated_cleanup_removes_old_files() {
+        let cache = create_test_cache("deprecated_cleanup");
+
+        cache.set("key", &"value".to_string());
+        cache.cleanup(Duration::from_secs(60));
+        assert!(cache.get_stale::<String>("key").is_some());
+
+        thread::sleep(Duration::from_millis(20));
+        cache.cleanup(Duration::from_millis(1));
+        assert!(cache.get_stale::<String>("key").is_none());
+        cleanup_test_cache(&cache);
+    }
+
+    #[test]
+    fn test_file_cache_size_cap_evicts_lru() {
+        let mut cache = create_test_cache("size_cap");
+        let value = "x".repeat(1000);
+
+        cache.set("a", &value);
+        let entry_size = fs::metadata(cache.get_path("a")).unwrap().len();
+        cache.max_size_bytes = Some(entry_size * 2);
+
+        thread::sleep(Duration::from_millis(20));
+        cache.set("b", &value);
+        thread::sleep(Duration::from_millis(20));
+
+        // Reading "a" makes "b" the least recently used
+        let _: Option<String> = cache.get("a", Duration::from_secs(60));
+        thread::sleep(Duration::from_millis(20));
+        cache.set("c", &value);
+
+        assert!(cache.get_stale::<String>("a").is_some());
+        assert!(cache.get_stale::<String>("b").is_none());
+        assert!(cache.get_stale::<String>("c").is_some());
+        cleanup_test_cache(&cache);
+    }
+
     #[test]
     fn test_file_cache_vec_data() {
         let cache = create_test_cache("vec");
@@ -0,0 +1,320 @@
+//! Dependency health checks for readiness probes.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use redis::aio::ConnectionManager;
+use serde::Serialize;
+
+use crate::services::{PeerMesh, PriceCache, SqliteStore};
+use crate::types::{PeerConnectionStatus, PriceSource};
+
+/// A source with no successful update for this long is considered stale.
+pub const SOURCE_STALE_MS: u64 = 60_000;
+
+/// How long to wait for Redis to answer a ping.
+const REDIS_PING_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Health of a single component, ordered from best to worst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Healthy,
+    Degraded,
+    Unhealthy,
+}
+
+/// Health of one dependency.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentHealth {
+    pub name: String,
+    pub status: HealthStatus,
+    /// Critical components take the whole server out of readiness when unhealthy.
+    pub critical: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl ComponentHealth {
+    fn new(name: &str, status: HealthStatus, critical: bool, detail: Option<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            critical,
+            detail,
+        }
+    }
+}
+
+/// Freshness of one price source.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceHealth {
+    pub source: PriceSource,
+    pub status: HealthStatus,
+    pub online: bool,
+    /// Milliseconds since the last successful update.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_update_age_ms: Option<u64>,
+}
+
+/// Detailed health report.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthReport {
+    /// Unhealthy if any critical component is unhealthy, degraded if anything
+    /// else is not healthy.
+    pub status: HealthStatus,
+    pub components: Vec<ComponentHealth>,
+    pub sources: Vec<SourceHealth>,
+    pub timestamp: i64,
+}
+
+impl HealthReport {
+    /// Whether the server should receive traffic.
+    pub fn is_ready(&self) -> bool {
+        self.status != HealthStatus::Unhealthy
+    }
+}
+
+/// Checks the server's dependencies.
+pub struct HealthService {
+    redis: Option<ConnectionManager>,
+    sqlite: Arc<SqliteStore>,
+    price_cache: Arc<PriceCache>,
+    peer_mesh: Option<Arc<PeerMesh>>,
+}
+
+impl HealthService {
+    /// Create a health service. `redis` is `None` when Redis could not be reached
+    /// at startup.
+    pub fn new(
+        redis: Option<ConnectionManager>,
+        sqlite: Arc<SqliteStore>,
+        price_cache: Arc<PriceCache>,
+        peer_mesh: Option<Arc<PeerMesh>>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            redis,
+            sqlite,
+            price_cache,
+            peer_mesh,
+        })
+    }
+
+    /// Check every dependency and summarize the result.
+    pub async fn report(&self) -> HealthReport {
+        let now = chrono::Utc::now().timestamp_millis();
+        let sources = self.check_sources(now as u64);
+
+        let components = vec![
+            self.check_redis().await,
+            self.check_sqlite(),
+            Self::summarize_sources(&sources),
+            self.check_peer_mesh(),
+        ];
+
+        HealthReport {
+            status: overall_status(&components),
+            components,
+            sources,
+            timestamp: now,
+        }
+    }
+
+    /// Redis is only used for persistence, so losing it degrades the server
+    /// without taking it out of service.
+    async fn check_redis(&self) -> ComponentHealth {
+        let Some(conn) = &self.redis else {
+            return ComponentHealth::new(
+                "redis",
+                HealthStatus::Unhealthy,
+                false,
+                Some("not connected".to_string()),
+            );
+        };
+
+        let mut conn = conn.clone();
+        let cmd = redis::cmd("PING");
+        let ping = cmd.query_async::<_, String>(&mut conn);
+        let (status, detail) = match tokio::time::timeout(REDIS_PING_TIMEOUT, ping).await {
+            Ok(Ok(_)) => (HealthStatus::Healthy, None),
+            Ok(Err(e)) => (HealthStatus::Unhealthy, Some(e.to_string())),
+            Err(_) => (HealthStatus::Unhealthy, Some("ping timed out".to_string())),
+        };
+        ComponentHealth::new("redis", status, false, detail)
+    }
+
+    fn check_sqlite(&self) -> ComponentHealth {
+        match self.sqlite.check_writable() {
+            Ok(()) => ComponentHealth::new("sqlite", HealthStatus::Healthy, true, None),
+            Err(e) => {
+                ComponentHealth::new("sqlite", HealthStatus::Unhealthy, true, Some(e.to_string()))
+            }
+        }
+    }
+
+    fn check_sources(&self, now_ms: u64) -> Vec<SourceHealth> {
+        self.price_cache
+            .get_exchange_stats()
+            .into_iter()
+            .map(|stats| {
+                let age = stats.last_update.map(|ts| now_ms.saturating_sub(ts));
+                let fresh = age.is_some_and(|age| age < SOURCE_STALE_MS);
+                SourceHealth {
+                    source: stats.source,
+                    status: if stats.online && fresh {
+                        HealthStatus::Healthy
+                    } else {
+                        HealthStatus::Unhealthy
+                    },
+                    online: stats.online,
+                    last_update_age_ms: age,
+                }
+            })
+            .collect()
+    }
+
+    /// Price feeds are critical: with no fresh source, every price is stale.
+    fn summarize_sources(sources: &[SourceHealth]) -> ComponentHealth {
+        let fresh = sources
+            .iter()
+            .filter(|s| s.status == HealthStatus::Healthy)
+            .count();
+        let status = if fresh == 0 {
+            HealthStatus::Unhealthy
+        } else if fresh < sources.len() {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Healthy
+        };
+        let detail = format!("{} of {} sources fresh", fresh, sources.len());
+        ComponentHealth::new("price_feeds", status, true, Some(detail))
+    }
+
+    fn check_peer_mesh(&self) -> ComponentHealth {
+        let Some(mesh) = &self.peer_mesh else {
+            return ComponentHealth::new(
+                "peer_mesh",
+                HealthStatus::Healthy,
+                false,
+                Some("disabled".to_string()),
+            );
+        };
+
+        let peers = mesh.get_all_statuses();
+        let connected = peers
+            .iter()
+            .filter(|p| p.status == PeerConnectionStatus::Connected)
+            .count();
+        let status = if peers.is_empty() || connected == peers.len() {
+            HealthStatus::Healthy
+        } else {
+            HealthStatus::Degraded
+        };
+        let detail = format!("{} of {} peers connected", connected, peers.len());
+        ComponentHealth::new("peer_mesh", status, false, Some(detail))
+    }
+}
+
+/// Combine component statuses: a critical failure makes the server unhealthy,
+/// anything else short of healthy only degrades it.
+fn overall_status(components: &[ComponentHealth]) -> HealthStatus {
+    components
+        .iter()
+        .map(|c| match c.status {
+            HealthStatus::Unhealthy if !c.critical => HealthStatus::Degraded,
+            status => status,
+        })
+        .max()
+        .unwrap_or(HealthStatus::Healthy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::AggregationConfig;
+
+    /// A service whose Redis never connected, as when it is down at startup.
+    fn create_service() -> (Arc<HealthService>, Arc<PriceCache>) {
+        let sqlite = Arc::new(SqliteStore::new_in_memory().unwrap());
+        let (price_cache, _rx) = PriceCache::new(AggregationConfig {
+            throttle_ms: 0,
+            ..AggregationConfig::default()
+        });
+        let service = HealthService::new(None, sqlite, price_cache.clone(), None);
+        (service, price_cache)
+    }
+
+    fn component<'a>(report: &'a HealthReport, name: &str) -> &'a ComponentHealth {
+        report.components.iter().find(|c| c.name == name).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_down_redis_degrades_report() {
+        let (service, price_cache) = create_service();
+        price_cache.update_price("btc", PriceSource::Coinbase, 50000.0, None);
+
+        let report = service.report().await;
+        let redis = component(&report, "redis");
+        assert_eq!(redis.status, HealthStatus::Unhealthy);
+        assert!(!redis.critical);
+        assert_eq!(component(&report, "sqlite").status, HealthStatus::Healthy);
+        assert_eq!(
+            component(&report, "price_feeds").status,
+            HealthStatus::Healthy
+        );
+
+        // Redis is not critical, so the server stays ready
+        assert_eq!(report.status, HealthStatus::Degraded);
+        assert!(report.is_ready());
+    }
+
+    #[tokio::test]
+    async fn test_no_fresh_sources_is_unhealthy() {
+        let (service, price_cache) = create_service();
+
+        let report = service.report().await;
+        assert_eq!(
+            component(&report, "price_feeds").status,
+            HealthStatus::Unhealthy
+        );
+        assert_eq!(report.status, HealthStatus::Unhealthy);
+        assert!(!report.is_ready());
+
+        price_cache.update_price("btc", PriceSource::Coinbase, 50000.0, None);
+        price_cache.report_source_error(PriceSource::Coinbase, "socket closed");
+        price_cache.update_price("eth", PriceSource::Kraken, 3000.0, None);
+
+        let report = service.report().await;
+        let coinbase = report
+            .sources
+            .iter()
+            .find(|s| s.source == PriceSource::Coinbase)
+            .unwrap();
+        assert_eq!(coinbase.status, HealthStatus::Unhealthy);
+        assert!(coinbase.last_update_age_ms.is_some());
+        assert_eq!(
+            component(&report, "price_feeds").status,
+            HealthStatus::Degraded
+        );
+        assert!(report.is_ready());
+    }
+
+    #[test]
+    fn test_overall_status() {
+        let healthy = || ComponentHealth::new("a", HealthStatus::Healthy, true, None);
+        let down = |critical| ComponentHealth::new("b", HealthStatus::Unhealthy, critical, None);
+
+        assert_eq!(overall_status(&[healthy()]), HealthStatus::Healthy);
+        assert_eq!(
+            overall_status(&[healthy(), down(false)]),
+            HealthStatus::Degraded
+        );
+        assert_eq!(
+            overall_status(&[healthy(), down(false), down(true)]),
+            HealthStatus::Unhealthy
+        );
+    }
+}
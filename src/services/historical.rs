@@ -8,9 +8,11 @@ use crate::services::ChartStore;
 use crate::sources::finnhub::{ETF_SYMBOLS, STOCK_SYMBOLS};
 use crate::sources::{AlphaVantageClient, YahooFinanceClient};
 use dashmap::DashMap;
+use futures_util::stream::{self, StreamExt};
 use redis::aio::ConnectionManager;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
@@ -27,6 +29,9 @@ const MIN_POINTS_1D: usize = 48;
 const MIN_POINTS_1W: usize = 84;
 const MIN_POINTS_1M: usize = 120;
 
+/// Maximum number of symbols loaded from Redis at once on startup
+const LOAD_CONCURRENCY: usize = 8;
+
 /// OHLC data point for Redis storage
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OhlcDataPoint {
@@ -187,6 +192,91 @@ pub struct SeedProgress {
     pub message: Option<String>,
 }
 
+/// Backoff settings for retrying upstream fetches.
+#[derive(Debug, Clone, Copy)]
+struct RetryPolicy {
+    /// Retries after the first attempt.
+    max_retries: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Exponential delay for the given retry (0-based) with up to 50% jitter.
+    fn delay(&self, retry: u32) -> Duration {
+        let exp = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_delay);
+        let jitter = exp.mul_f64(rand::random::<f64>() * 0.5);
+        exp - jitter
+    }
+}
+
+/// Retry policy for CoinGecko/CryptoCompare requests
+const FETCH_RETRY: RetryPolicy = RetryPolicy {
+    max_retries: 3,
+    base_delay: Duration::from_millis(500),
+    max_delay: Duration::from_secs(8),
+};
+
+/// A failed fetch attempt, classified by whether it is worth retrying.
+#[derive(Debug)]
+enum FetchFailure {
+    /// Rate limited, server error or timeout.
+    Retryable(AppError),
+    /// Client errors such as 404 that will not succeed on retry.
+    Fatal(AppError),
+}
+
+/// Run `op` until it succeeds, fails fatally, or exhausts the policy's retries.
+async fn retry_with_backoff<T, F, Fut>(policy: RetryPolicy, label: &str, mut op: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = std::result::Result<T, FetchFailure>>,
+{
+    let mut retry = 0;
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(FetchFailure::Fatal(e)) => return Err(e),
+            Err(FetchFailure::Retryable(e)) if retry >= policy.max_retries => {
+                warn!("[{}] Giving up after {} retries: {}", label, retry, e);
+                return Err(e);
+            }
+            Err(FetchFailure::Retryable(e)) => {
+                let delay = policy.delay(retry);
+                debug!("[{}] Retrying in {:?}: {}", label, delay, e);
+                tokio::time::sleep(delay).await;
+                retry += 1;
+            }
+        }
+    }
+}
+
+/// Run `loader` for every symbol with at most `limit` in flight.
+///
+/// Results are returned in completion order, so a slow symbol never holds up
+/// the ones behind it, and a failed symbol does not stop the rest.
+async fn load_concurrently<F, Fut>(
+    symbols: Vec<String>,
+    limit: usize,
+    loader: F,
+) -> Vec<(String, SeedStatus)>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = SeedStatus>,
+{
+    stream::iter(symbols)
+        .map(|symbol| {
+            let load = loader(symbol.clone());
+            async move { (symbol, load.await) }
+        })
+        .buffer_unordered(limit.max(1))
+        .collect()
+        .await
+}
+
 /// Check if a symbol is a stock or ETF.
 fn is_stock_or_etf(symbol: &str) -> bool {
     let upper = symbol.to_uppercase();
@@ -233,22 +323,10 @@ impl HistoricalDataService {
         })
     }
 
-    /// Connect to Redis.
-    pub async fn connect_redis(&self, redis_url: &str) {
-        match redis::Client::open(redis_url) {
-            Ok(client) => match ConnectionManager::new(client).await {
-                Ok(conn) => {
-                    info!("HistoricalDataService connected to Redis");
-                    *self.redis.write().await = Some(conn);
-                }
-                Err(e) => {
-                    warn!("Failed to connect HistoricalDataService to Redis: {}", e);
-                }
-            },
-            Err(e) => {
-                warn!("Invalid Redis URL for HistoricalDataService: {}", e);
-            }
-        }
+    /// Use a shared Redis connection for persistence.
+    pub async fn connect_redis(&self, conn: ConnectionManager) {
+        info!("HistoricalDataService connected to Redis");
+        *self.redis.write().await = Some(conn);
     }
 
     /// Load historical data from Redis for common symbols on startup.
@@ -260,14 +338,8 @@ impl HistoricalDataService {
             "aave", "mkr", "crv", "ldo", "snx", "comp",
         ];
 
-        let mut loaded_count = 0;
-        for symbol in common_symbols {
-            if self.load_from_redis(symbol).await {
-                self.seed_status
-                    .insert(symbol.to_string(), SeedStatus::Seeded);
-                loaded_count += 1;
-            }
-        }
+        let symbols = common_symbols.into_iter().map(String::from).collect();
+        let loaded_count = self.load_symbols_from_redis(symbols).await;
 
         if loaded_count > 0 {
             info!(
@@ -277,6 +349,36 @@ impl HistoricalDataService {
         }
     }
 
+    /// Load several symbols from Redis concurrently, recording each one that
+    /// was found as seeded. Returns the number of symbols loaded.
+    async fn load_symbols_from_redis(&self, symbols: Vec<String>) -> usize {
+        let results = load_concurrently(symbols, LOAD_CONCURRENCY, |symbol| async move {
+            self.load_status_from_redis(&symbol).await
+        })
+        .await;
+        self.record_load_results(results)
+    }
+
+    /// Record the outcome of each startup load. Symbols that failed to load are
+    /// marked failed so they are re-seeded on request; symbols with no stored
+    /// data are left untouched. Returns the number of symbols loaded.
+    fn record_load_results(&self, results: Vec<(String, SeedStatus)>) -> usize {
+        let mut loaded_count = 0;
+        for (symbol, status) in results {
+            match status {
+                SeedStatus::Seeded => {
+                    self.seed_status.insert(symbol, status);
+                    loaded_count += 1;
+                }
+                SeedStatus::Failed => {
+                    self.seed_status.insert(symbol, status);
+                }
+                SeedStatus::NotSeeded | SeedStatus::Seeding => {}
+            }
+        }
+        loaded_count
+    }
+
     /// Check if a symbol has adequate chart data for a given range.
     pub fn has_adequate_data(&self, symbol: &str, range: &str) -> bool {
         let sparkline = self.chart_store.get_sparkline(symbol, 500);
@@ -351,9 +453,15 @@ impl HistoricalDataService {
 
     /// Load historical OHLC data from Redis into the chart store.
     pub async fn load_from_redis(&self, symbol: &str) -> bool {
+        self.load_status_from_redis(symbol).await == SeedStatus::Seeded
+    }
+
+    /// Load stored data for a symbol, reporting `Seeded` when data was found,
+    /// `NotSeeded` when there is none, and `Failed` when Redis returned an error.
+    async fn load_status_from_redis(&self, symbol: &str) -> SeedStatus {
         let conn_guard = self.redis.read().await;
         let Some(ref conn) = *conn_guard else {
-            return false;
+            return SeedStatus::NotSeeded;
         };
 
         let mut conn = conn.clone();
@@ -389,9 +497,13 @@ impl HistoricalDataService {
                         );
                     }
                 }
-                true
+                SeedStatus::Seeded
+            }
+            Ok(_) => SeedStatus::NotSeeded,
+            Err(e) => {
+                warn!("Failed to load historical data for {} from Redis: {}", symbol, e);
+                SeedStatus::Failed
             }
-            _ => false,
         }
     }
 
@@ -638,6 +750,38 @@ impl HistoricalDataService {
         info!("Completed historical data seed for {}", symbol);
     }
 
+    /// Send a request, retrying rate limits, server errors and timeouts with
+    /// exponential backoff. Other failures (e.g. 404) are returned immediately.
+    async fn send_with_retry<F>(&self, source: &str, build: F) -> Result<reqwest::Response>
+    where
+        F: Fn() -> reqwest::RequestBuilder,
+    {
+        let build = &build;
+        retry_with_backoff(FETCH_RETRY, source, move || async move {
+            let response = build().send().await.map_err(|e| {
+                let err = AppError::ExternalApi(format!("{} request failed: {}", source, e));
+                if e.is_timeout() || e.is_connect() {
+                    FetchFailure::Retryable(err)
+                } else {
+                    FetchFailure::Fatal(err)
+                }
+            })?;
+
+            let status = response.status();
+            if status.is_success() {
+                return Ok(response);
+            }
+            let body = response.text().await.unwrap_or_default();
+            let err = AppError::ExternalApi(format!("{} API error {}: {}", source, status, body));
+            if status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+                Err(FetchFailure::Retryable(err))
+            } else {
+                Err(FetchFailure::Fatal(err))
+            }
+        })
+        .await
+    }
+
     /// Fetch market chart data from CoinGecko and convert to OHLC points.
     async fn fetch_coingecko_market_chart(
         &self,
@@ -655,26 +799,16 @@ impl HistoricalDataService {
             days, coin_id
         );
 
-        let mut request = self.http_client.get(&url);
-
-        // Add API key if available (for higher rate limits)
-        if let Some(ref key) = self.coingecko_api_key {
-            request = request.header("x-cg-demo-api-key", key);
-        }
-
-        let response = request
-            .send()
-            .await
-            .map_err(|e| AppError::ExternalApi(format!("CoinGecko request failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(AppError::ExternalApi(format!(
-                "CoinGecko API error {}: {}",
-                status, body
-            )));
-        }
+        let response = self
+            .send_with_retry("CoinGecko", || {
+                let request = self.http_client.get(&url);
+                // Add API key if available (for higher rate limits)
+                match self.coingecko_api_key {
+                    Some(ref key) => request.header("x-cg-demo-api-key", key),
+                    None => request,
+                }
+            })
+            .await?;
 
         let chart: CoinGeckoMarketChart = response.json().await.map_err(|e| {
             AppError::ExternalApi(format!("Failed to parse CoinGecko response: {}", e))
@@ -740,24 +874,15 @@ impl HistoricalDataService {
             valid_days, coin_id
         );
 
-        let mut request = self.http_client.get(&url);
-
-        if let Some(ref key) = self.coingecko_api_key {
-            request = request.header("x-cg-demo-api-key", key);
-        }
-
-        let response = request
-            .send()
-            .await
-            .map_err(|e| AppError::ExternalApi(format!("CoinGecko request failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            return Err(AppError::ExternalApi(format!(
-                "CoinGecko API error: {}",
-                status
-            )));
-        }
+        let response = self
+            .send_with_retry("CoinGecko", || {
+                let request = self.http_client.get(&url);
+                match self.coingecko_api_key {
+                    Some(ref key) => request.header("x-cg-demo-api-key", key),
+                    None => request,
+                }
+            })
+            .await?;
 
         let ohlc: CoinGeckoOhlc = response
             .json()
@@ -799,26 +924,16 @@ impl HistoricalDataService {
             limit, symbol
         );
 
-        let mut request = self.http_client.get(&url);
-
-        // Add API key if available (for higher rate limits)
-        if let Some(ref key) = self.cryptocompare_api_key {
-            request = request.header("authorization", format!("Apikey {}", key));
-        }
-
-        let response = request
-            .send()
-            .await
-            .map_err(|e| AppError::ExternalApi(format!("CryptoCompare request failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(AppError::ExternalApi(format!(
-                "CryptoCompare API error {}: {}",
-                status, body
-            )));
-        }
+        let response = self
+            .send_with_retry("CryptoCompare", || {
+                let request = self.http_client.get(&url);
+                // Add API key if available (for higher rate limits)
+                match self.cryptocompare_api_key {
+                    Some(ref key) => request.header("authorization", format!("Apikey {}", key)),
+                    None => request,
+                }
+            })
+            .await?;
 
         let resp: CryptoCompareResponse = response.json().await.map_err(|e| {
             AppError::ExternalApi(format!("Failed to parse CryptoCompare response: {}", e))
@@ -870,24 +985,15 @@ impl HistoricalDataService {
             limit, symbol
         );
 
-        let mut request = self.http_client.get(&url);
-
-        if let Some(ref key) = self.cryptocompare_api_key {
-            request = request.header("authorization", format!("Apikey {}", key));
-        }
-
-        let response = request
-            .send()
-            .await
-            .map_err(|e| AppError::ExternalApi(format!("CryptoCompare request failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            return Err(AppError::ExternalApi(format!(
-                "CryptoCompare API error: {}",
-                status
-            )));
-        }
+        let response = self
+            .send_with_retry("CryptoCompare", || {
+                let request = self.http_client.get(&url);
+                match self.cryptocompare_api_key {
+                    Some(ref key) => request.header("authorization", format!("Apikey {}", key)),
+                    None => request,
+                }
+            })
+            .await?;
 
         let resp: CryptoCompareResponse = response.json().await.map_err(|e| {
             AppError::ExternalApi(format!("Failed to parse CryptoCompare response: {}", e))
@@ -1091,15 +1197,12 @@ impl HistoricalDataService {
 
     /// Load common stock symbols from Redis on startup.
     pub async fn load_stock_symbols(&self) {
-        let mut loaded_count = 0;
-
-        for symbol in STOCK_SYMBOLS.iter().chain(ETF_SYMBOLS.iter()) {
-            let symbol_lower = symbol.to_lowercase();
-            if self.load_from_redis(&symbol_lower).await {
-                self.seed_status.insert(symbol_lower, SeedStatus::Seeded);
-                loaded_count += 1;
-            }
-        }
+        let symbols = STOCK_SYMBOLS
+            .iter()
+            .chain(ETF_SYMBOLS.iter())
+            .map(|symbol| symbol.to_lowercase())
+            .collect();
+        let loaded_count = self.load_symbols_from_redis(symbols).await;
 
         if loaded_count > 0 {
             info!(
@@ -1109,3 +1212,130 @@ impl HistoricalDataService {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+    use tokio::sync::Semaphore;
+
+    const TEST_RETRY: RetryPolicy = RetryPolicy {
+        max_retries: 3,
+        base_delay: Duration::from_millis(1),
+        max_delay: Duration::from_millis(10),
+    };
+
+    #[tokio::test]
+    async fn test_retry_succeeds_after_transient_failures() {
+        let attempts = AtomicU32::new(0);
+        let result = retry_with_backoff(TEST_RETRY, "mock", || async {
+            if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err(FetchFailure::Retryable(AppError::ExternalApi("429".to_string())))
+            } else {
+                Ok(vec![OhlcDataPoint {
+                    time: 0,
+                    open: 1.0,
+                    high: 1.0,
+                    low: 1.0,
+                    close: 1.0,
+                    volume: 0.0,
+                }])
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap().len(), 1);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_stops_on_fatal_and_caps_retries() {
+        let attempts = AtomicU32::new(0);
+        let result: Result<()> = retry_with_backoff(TEST_RETRY, "mock", || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(FetchFailure::Fatal(AppError::ExternalApi("404".to_string())))
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+
+        let attempts = AtomicU32::new(0);
+        let result: Result<()> = retry_with_backoff(TEST_RETRY, "mock", || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(FetchFailure::Retryable(AppError::ExternalApi("503".to_string())))
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1 + TEST_RETRY.max_retries);
+    }
+
+    #[test]
+    fn test_retry_delay_grows_and_is_capped() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(1000),
+        };
+        for retry in 0..10 {
+            let exp = (100u64 << retry).min(1000);
+            let delay = policy.delay(retry).as_millis() as u64;
+            assert!(delay <= exp && delay >= exp / 2, "retry {} delay {}", retry, delay);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_load_concurrently_does_not_serialize_slow_symbols() {
+        let symbols: Vec<String> = (0..20).map(|i| format!("sym{}", i)).collect();
+        // Every fifth symbol is slow: it cannot finish until all 16 fast symbols
+        // have, which only happens if the fast ones are not queued behind it
+        let slow_gate = &Semaphore::new(0);
+        let fast_done = &AtomicUsize::new(0);
+
+        let results = load_concurrently(symbols, 8, |symbol| async move {
+            let n: usize = symbol[3..].parse().unwrap();
+            if n.is_multiple_of(5) {
+                slow_gate.acquire().await.unwrap().forget();
+            } else if fast_done.fetch_add(1, Ordering::SeqCst) + 1 == 16 {
+                slow_gate.add_permits(4);
+            }
+            // sym7 fails outright
+            if n == 7 {
+                SeedStatus::Failed
+            } else {
+                SeedStatus::Seeded
+            }
+        })
+        .await;
+
+        // All symbols complete, including those after the failure
+        assert_eq!(results.len(), 20);
+        assert_eq!(
+            results.iter().filter(|(_, s)| *s == SeedStatus::Seeded).count(),
+            19
+        );
+        assert!(results.contains(&("sym7".to_string(), SeedStatus::Failed)));
+
+        // The fast symbols finish before the slow ones rather than queueing behind them
+        let first_slow = results
+            .iter()
+            .position(|(s, _)| s[3..].parse::<usize>().unwrap().is_multiple_of(5))
+            .unwrap();
+        assert_eq!(first_slow, 16);
+    }
+
+    #[test]
+    fn test_failed_startup_loads_are_recorded() {
+        let service = HistoricalDataService::new(ChartStore::new(), None, None, None);
+
+        let loaded = service.record_load_results(vec![
+            ("btc".to_string(), SeedStatus::Seeded),
+            ("eth".to_string(), SeedStatus::NotSeeded),
+            ("sol".to_string(), SeedStatus::Failed),
+        ]);
+
+        assert_eq!(loaded, 1);
+        assert_eq!(service.get_seed_status("btc"), SeedStatus::Seeded);
+        assert_eq!(service.get_seed_status("eth"), SeedStatus::NotSeeded);
+        assert_eq!(service.get_seed_status("sol"), SeedStatus::Failed);
+    }
+}
@@ -143,9 +143,7 @@ impl LiquidationEngine {
             };
 
             let message = ServerMessage::MarginWarning { data: warning_data };
-            if let Ok(json) = serde_json::to_string(&message) {
-                self.room_manager.broadcast_trading(&position.portfolio_id, &json);
-            }
+            self.room_manager.broadcast_trading(&position.portfolio_id, &message);
 
             info!(
                 "Margin warning {:?} for position {} (margin level: {:.2}%)",
@@ -241,9 +239,7 @@ impl LiquidationEngine {
         };
 
         let message = ServerMessage::LiquidationAlert { data: alert_data };
-        if let Ok(json) = serde_json::to_string(&message) {
-            self.room_manager.broadcast_trading(&position.portfolio_id, &json);
-        }
+        self.room_manager.broadcast_trading(&position.portfolio_id, &message);
 
         // Create a closed position for the update
         let mut closed_position = position.clone();
@@ -261,9 +257,7 @@ impl LiquidationEngine {
         };
 
         let pos_message = ServerMessage::PositionUpdate { data: position_update };
-        if let Ok(json) = serde_json::to_string(&pos_message) {
-            self.room_manager.broadcast_trading(&position.portfolio_id, &json);
-        }
+        self.room_manager.broadcast_trading(&position.portfolio_id, &pos_message);
 
         // Clear warning state
         self.warning_state.remove(&position.id);
@@ -6,9 +6,15 @@
 //! - Partial fill simulation based on available liquidity
 //! - Market impact modeling
 //! - Volume-based limit order fill probability
+//! - Per-symbol liquidity profiles, so microcaps fill worse than blue chips
+
+use std::collections::HashMap;
 
 use crate::types::{AggregatedOrderBook, AggregatedLevel, OrderSide};
 
+/// Upper bound on market impact, so a large sell never pushes the VWAP to zero.
+const MAX_IMPACT_PCT: f64 = 99.0;
+
 /// Result of simulating a market order execution.
 #[derive(Debug, Clone)]
 pub struct MarketOrderSimulation {
@@ -64,13 +70,54 @@ pub struct LimitOrderSimulation {
     pub estimated_queue_depth: f64,
 }
 
+/// How liquid a symbol's market is simulated to be.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LiquidityProfile {
+    /// Multiplier for order book depth
+    /// 1.0 = use real depth, 0.1 = simulate a tenth of the liquidity
+    pub depth_multiplier: f64,
+    /// Extra spread in basis points of mid, half applied to each side of the book
+    pub spread_bps: f64,
+    /// Market impact factor (% of visible book consumed -> % price impact)
+    pub impact_factor: f64,
+}
+
+impl LiquidityProfile {
+    /// Check that the profile describes a usable market.
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.depth_multiplier.is_finite() || self.depth_multiplier <= 0.0 {
+            return Err(format!(
+                "depth_multiplier must be positive, got {}",
+                self.depth_multiplier
+            ));
+        }
+        // Half the spread is taken off each bid, so 20,000 bps would price sells at zero
+        if !self.spread_bps.is_finite() || !(0.0..20_000.0).contains(&self.spread_bps) {
+            return Err(format!(
+                "spread_bps must be in [0, 20000), got {}",
+                self.spread_bps
+            ));
+        }
+        if !self.impact_factor.is_finite() || self.impact_factor < 0.0 {
+            return Err(format!(
+                "impact_factor must be non-negative, got {}",
+                self.impact_factor
+            ));
+        }
+        Ok(())
+    }
+}
+
 /// Configuration for liquidity simulation.
 #[derive(Debug, Clone)]
 pub struct LiquiditySimConfig {
     /// Multiplier for order book depth (simulates larger market)
     /// 1.0 = use real depth, 10.0 = simulate 10x the liquidity
     pub depth_multiplier: f64,
+    /// Extra spread in basis points of mid for symbols without a profile
+    pub spread_bps: f64,
     /// Base market impact factor (% of book consumed -> % price impact)
+    /// 0.0 = price comes from walking the book alone
     pub impact_factor: f64,
     /// Minimum fill rate per hour (% of order size) for limit orders
     pub min_fill_rate_per_hour: f64,
@@ -78,16 +125,47 @@ pub struct LiquiditySimConfig {
     pub allow_partial_fills: bool,
     /// Maximum slippage allowed (order fails if exceeded)
     pub max_slippage_pct: Option<f64>,
+    /// Profile overrides keyed by lowercase symbol; other symbols use the
+    /// depth, spread and impact settings above
+    pub symbol_profiles: HashMap<String, LiquidityProfile>,
+}
+
+impl LiquiditySimConfig {
+    /// Simulate `symbol` with its own liquidity profile.
+    ///
+    /// Fails if the profile does not pass [`LiquidityProfile::validate`].
+    pub fn with_symbol_profile(
+        mut self,
+        symbol: &str,
+        profile: LiquidityProfile,
+    ) -> Result<Self, String> {
+        profile
+            .validate()
+            .map_err(|e| format!("invalid liquidity profile for {}: {}", symbol, e))?;
+        self.symbol_profiles.insert(symbol.to_lowercase(), profile);
+        Ok(self)
+    }
+
+    /// Profile used for symbols without an override.
+    pub fn default_profile(&self) -> LiquidityProfile {
+        LiquidityProfile {
+            depth_multiplier: self.depth_multiplier,
+            spread_bps: self.spread_bps,
+            impact_factor: self.impact_factor,
+        }
+    }
 }
 
 impl Default for LiquiditySimConfig {
     fn default() -> Self {
         Self {
             depth_multiplier: 1.0,
-            impact_factor: 0.1,
+            spread_bps: 0.0,
+            impact_factor: 0.0,
             min_fill_rate_per_hour: 0.05, // 5% of order per hour minimum
             allow_partial_fills: true,
             max_slippage_pct: None,
+            symbol_profiles: HashMap::new(),
         }
     }
 }
@@ -103,10 +181,22 @@ impl LiquiditySimulator {
         Self { config }
     }
 
+    /// Liquidity profile for a symbol, falling back to the default profile.
+    pub fn profile(&self, symbol: &str) -> LiquidityProfile {
+        self.config
+            .symbol_profiles
+            .get(&symbol.to_lowercase())
+            .copied()
+            .unwrap_or_else(|| self.config.default_profile())
+    }
+
     /// Simulate a market order execution by walking the order book.
     ///
     /// For a buy order, walks the asks (ascending price).
     /// For a sell order, walks the bids (descending price).
+    /// The book's symbol selects the liquidity profile: each level is scaled
+    /// by its depth multiplier and shifted by half its extra spread, and the
+    /// resulting VWAP is moved further by its impact factor.
     pub fn simulate_market_order(
         &self,
         order_book: &AggregatedOrderBook,
@@ -118,7 +208,12 @@ impl LiquiditySimulator {
             OrderSide::Sell => &order_book.bids,
         };
 
+        let profile = self.profile(&order_book.symbol);
         let mid_price = order_book.mid_price;
+        let half_spread = match side {
+            OrderSide::Buy => mid_price * profile.spread_bps / 20_000.0,
+            OrderSide::Sell => -mid_price * profile.spread_bps / 20_000.0,
+        };
         let mut remaining = quantity;
         let mut total_cost = 0.0;
         let mut filled = 0.0;
@@ -131,27 +226,41 @@ impl LiquiditySimulator {
             }
 
             // Apply depth multiplier to simulate more/less liquidity
-            let available = level.total_quantity * self.config.depth_multiplier;
+            let available = level.total_quantity * profile.depth_multiplier;
             let fill_qty = remaining.min(available);
+            let price = level.price + half_spread;
 
             filled += fill_qty;
-            total_cost += fill_qty * level.price;
+            total_cost += fill_qty * price;
             remaining -= fill_qty;
             levels_consumed += 1;
 
             fills.push(LevelFill {
-                price: level.price,
+                price,
                 quantity: fill_qty,
                 cumulative_quantity: filled,
             });
         }
 
-        let vwap = if filled > 0.0 {
+        let mut vwap = if filled > 0.0 {
             total_cost / filled
         } else {
             mid_price
         };
 
+        // Market impact grows with the share of the visible book consumed,
+        // capped so a sell VWAP stays positive
+        let visible: f64 =
+            levels.iter().map(|l| l.total_quantity).sum::<f64>() * profile.depth_multiplier;
+        if visible > 0.0 && profile.impact_factor > 0.0 {
+            let impact_pct =
+                (profile.impact_factor * (filled / visible) * 100.0).min(MAX_IMPACT_PCT);
+            vwap *= match side {
+                OrderSide::Buy => 1.0 + impact_pct / 100.0,
+                OrderSide::Sell => 1.0 - impact_pct / 100.0,
+            };
+        }
+
         let slippage = match side {
             OrderSide::Buy => vwap - mid_price,
             OrderSide::Sell => mid_price - vwap,
@@ -200,6 +309,8 @@ impl LiquiditySimulator {
             }
         };
 
+        let depth_multiplier = self.profile(&order_book.symbol).depth_multiplier;
+
         // Calculate available quantity at limit price or better
         let available_quantity: f64 = levels
             .iter()
@@ -207,7 +318,7 @@ impl LiquiditySimulator {
                 OrderSide::Buy => l.price <= limit_price,
                 OrderSide::Sell => l.price >= limit_price,
             })
-            .map(|l| l.total_quantity * self.config.depth_multiplier)
+            .map(|l| l.total_quantity * depth_multiplier)
             .sum();
 
         let can_fill_immediately = is_executable && available_quantity >= quantity;
@@ -224,7 +335,7 @@ impl LiquiditySimulator {
         };
 
         // Estimate queue depth (volume ahead of us at our price level)
-        let queue_depth = self.estimate_queue_depth(levels, side, limit_price);
+        let queue_depth = self.estimate_queue_depth(levels, side, limit_price, depth_multiplier);
 
         // Calculate fill probability based on position relative to spread
         let fill_probability = self.calculate_fill_probability(
@@ -303,6 +414,7 @@ impl LiquiditySimulator {
             OrderSide::Buy => &order_book.asks,
             OrderSide::Sell => &order_book.bids,
         };
+        let depth_multiplier = self.profile(&order_book.symbol).depth_multiplier;
 
         levels
             .iter()
@@ -316,7 +428,7 @@ impl LiquiditySimulator {
                     true
                 }
             })
-            .map(|l| l.total_quantity * self.config.depth_multiplier)
+            .map(|l| l.total_quantity * depth_multiplier)
             .sum()
     }
 
@@ -384,6 +496,7 @@ impl LiquiditySimulator {
         levels: &[AggregatedLevel],
         side: OrderSide,
         limit_price: f64,
+        depth_multiplier: f64,
     ) -> f64 {
         // Find levels at or better than our limit price
         levels
@@ -392,7 +505,7 @@ impl LiquiditySimulator {
                 OrderSide::Buy => l.price < limit_price,
                 OrderSide::Sell => l.price > limit_price,
             })
-            .map(|l| l.total_quantity * self.config.depth_multiplier)
+            .map(|l| l.total_quantity * depth_multiplier)
             .sum()
     }
 
@@ -625,4 +738,101 @@ mod tests {
         assert_eq!(price, 50010.0);
         assert!(slippage > 0.0); // Should have some slippage from mid
     }
+
+    #[test]
+    fn test_thin_profile_slips_more_than_deep() {
+        let deep = LiquidityProfile {
+            depth_multiplier: 10.0,
+            spread_bps: 0.0,
+            impact_factor: 0.0,
+        };
+        let thin = LiquidityProfile {
+            depth_multiplier: 0.2,
+            spread_bps: 20.0,
+            impact_factor: 0.05,
+        };
+        let config = LiquiditySimConfig::default()
+            .with_symbol_profile("BTC", deep)
+            .unwrap()
+            .with_symbol_profile("pepe", thin)
+            .unwrap();
+        let sim = LiquiditySimulator::new(config);
+
+        let btc = create_test_order_book();
+        let pepe = AggregatedOrderBook {
+            symbol: "PEPE".to_string(),
+            ..create_test_order_book()
+        };
+
+        for side in [OrderSide::Buy, OrderSide::Sell] {
+            let deep_fill = sim.simulate_market_order(&btc, side, 5.0);
+            let thin_fill = sim.simulate_market_order(&pepe, side, 5.0);
+
+            assert!(deep_fill.fully_filled && thin_fill.fully_filled);
+            assert_eq!(deep_fill.levels_consumed, 1);
+            assert_eq!(thin_fill.levels_consumed, 2); // 2 + 4 available at the first two levels
+            assert!(thin_fill.slippage > deep_fill.slippage);
+            assert!(thin_fill.impact_cost > deep_fill.impact_cost);
+        }
+
+        // Thin profile also shrinks the immediately available quantity
+        assert_eq!(sim.available_fill_quantity(&btc, OrderSide::Buy, None), 600.0);
+        assert!((sim.available_fill_quantity(&pepe, OrderSide::Buy, None) - 12.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_unknown_symbol_uses_default_profile() {
+        let thin = LiquidityProfile {
+            depth_multiplier: 0.1,
+            spread_bps: 50.0,
+            impact_factor: 0.5,
+        };
+        let sim = LiquiditySimulator::new(
+            LiquiditySimConfig::default().with_symbol_profile("pepe", thin).unwrap(),
+        );
+
+        assert_eq!(sim.profile("PEPE"), thin);
+        assert_eq!(sim.profile("btc"), LiquiditySimConfig::default().default_profile());
+
+        // No override for BTC, so the fill matches the plain book walk
+        let result = sim.simulate_market_order(&create_test_order_book(), OrderSide::Buy, 5.0);
+        assert_eq!(result.vwap, 50010.0);
+    }
+
+    #[test]
+    fn test_large_impact_keeps_sell_vwap_positive() {
+        let config = LiquiditySimConfig {
+            impact_factor: 5.0,
+            ..LiquiditySimConfig::default()
+        };
+        let sim = LiquiditySimulator::new(config);
+
+        // Selling the whole book would be a 500% impact without the cap
+        let result = sim.simulate_market_order(&create_test_order_book(), OrderSide::Sell, 1_000.0);
+        assert!(result.filled_quantity > 0.0);
+        assert!(result.vwap > 0.0);
+        assert!(result.slippage_pct < 100.0);
+    }
+
+    #[test]
+    fn test_with_symbol_profile_rejects_invalid_profile() {
+        let valid = LiquidityProfile {
+            depth_multiplier: 0.5,
+            spread_bps: 10.0,
+            impact_factor: 2.0,
+        };
+        assert!(LiquiditySimConfig::default().with_symbol_profile("pepe", valid).is_ok());
+
+        for invalid in [
+            LiquidityProfile { depth_multiplier: 0.0, ..valid },
+            LiquidityProfile { spread_bps: 20_000.0, ..valid },
+            LiquidityProfile { impact_factor: -0.1, ..valid },
+            LiquidityProfile { impact_factor: f64::NAN, ..valid },
+        ] {
+            let err = LiquiditySimConfig::default()
+                .with_symbol_profile("pepe", invalid)
+                .unwrap_err();
+            assert!(err.contains("pepe"));
+        }
+    }
 }
@@ -1,21 +1,29 @@
 pub mod asset_service;
 pub mod auth;
 pub mod backtester;
+pub mod basket;
 pub mod cache;
 pub mod chart_store;
+pub mod content_filter;
 pub mod file_cache;
+pub mod health;
 pub mod historical;
 pub mod liquidation;
 pub mod liquidity_sim;
 pub mod multi_source;
 pub mod names;
+pub mod notification_delivery;
 pub mod options;
 pub mod orderbook;
 pub mod paperbot;
 pub mod peer_mesh;
 pub mod price_cache;
+pub mod ratio;
 pub mod redis_store;
+pub mod shutdown;
 pub mod signals;
+pub mod source_capabilities;
+pub mod source_reliability;
 pub mod sqlite_store;
 pub mod strategy_engine;
 pub mod trading;
@@ -25,8 +33,10 @@ pub use auth::{AuthError, AuthService};
 pub use cache::Cache;
 pub use chart_store::ChartStore;
 pub use file_cache::FileCache;
+pub use health::HealthService;
 pub use historical::{HistoricalDataService, SeedStatus};
 pub use multi_source::MultiSourceCoordinator;
+pub use notification_delivery::NotificationService;
 pub use orderbook::OrderBookService;
 pub use peer_mesh::PeerMesh;
 // Re-export peer types from types module
@@ -35,6 +45,8 @@ pub use price_cache::PriceCache;
 #[allow(unused_imports)]
 pub use redis_store::RedisStore;
 pub use liquidation::{LiquidationEngine, LiquidationError};
+pub use shutdown::Shutdown;
+pub use source_capabilities::SourceCapabilities;
 pub use signals::{AccuracyStore, PredictionStore, SignalStore};
 pub use sqlite_store::SqliteStore;
 pub use strategy_engine::{IndicatorSnapshot, StrategyEngine, StrategyError};
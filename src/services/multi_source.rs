@@ -2,9 +2,9 @@ use crate::config::Config;
 use crate::services::{ChartStore, PriceCache};
 use crate::sources::{
     BinanceClient, CoinGeckoClient, CoinMarketCapClient, CoinbaseWs, CryptoCompareClient,
-    HuobiClient, KrakenClient, KuCoinClient, OkxClient,
+    HuobiClient, KrakenClient, KrakenWs, KuCoinClient, OkxClient,
 };
-use crate::types::{AggregatedPrice, AggregationConfig};
+use crate::types::{AggregatedPrice, AggregationConfig, PriceSource};
 use std::sync::Arc;
 use tokio::sync::broadcast;
 use tracing::{error, info};
@@ -19,6 +19,7 @@ pub struct MultiSourceCoordinator {
     coinmarketcap: Option<CoinMarketCapClient>,
     binance: Option<BinanceClient>,
     kraken: Option<KrakenClient>,
+    kraken_ws: Option<KrakenWs>,
     kucoin: Option<KuCoinClient>,
     okx: Option<OkxClient>,
     huobi: Option<HuobiClient>,
@@ -31,10 +32,12 @@ impl MultiSourceCoordinator {
             change_threshold: config.price_change_threshold,
             throttle_ms: config.throttle_ms,
             stale_threshold_ms: config.stale_threshold_ms,
+            circuit_breaker_pct: config.circuit_breaker_pct,
+            circuit_breaker_window_ms: config.circuit_breaker_window_ms,
         };
 
         let (price_cache, rx) = PriceCache::new(agg_config);
-        let chart_store = ChartStore::new();
+        let chart_store = ChartStore::with_retention(config.chart_retention);
 
         let coinbase_ws = Some(CoinbaseWs::new(price_cache.clone(), chart_store.clone()));
 
@@ -65,6 +68,15 @@ impl MultiSourceCoordinator {
             chart_store.clone(),
         ));
 
+        // Kraken trade feed for the configured symbols, alongside REST polling
+        let kraken_ws = (!config.kraken_ws_symbols.is_empty()).then(|| {
+            KrakenWs::new(
+                &config.kraken_ws_symbols,
+                price_cache.clone(),
+                chart_store.clone(),
+            )
+        });
+
         let kucoin = Some(KuCoinClient::new(
             config.kucoin_api_key.clone(),
             price_cache.clone(),
@@ -92,6 +104,7 @@ impl MultiSourceCoordinator {
             coinmarketcap,
             binance,
             kraken,
+            kraken_ws,
             kucoin,
             okx,
             huobi,
@@ -110,6 +123,12 @@ impl MultiSourceCoordinator {
         self.chart_store.clone()
     }
 
+    /// Reliability score (0-1] for each source based on its drift from the
+    /// other sources, least reliable first.
+    pub fn source_reliability(&self) -> Vec<(PriceSource, f64)> {
+        self.price_cache.source_reliability()
+    }
+
     /// Start all price sources.
     pub async fn start(&self) {
         info!("Starting multi-source coordinator with 9 data sources");
@@ -164,6 +183,16 @@ impl MultiSourceCoordinator {
             });
         }
 
+        // Start Kraken WebSocket
+        if let Some(ref ws) = self.kraken_ws {
+            let ws = ws.clone();
+            tokio::spawn(async move {
+                if let Err(e) = ws.connect().await {
+                    error!("Kraken WebSocket error: {}", e);
+                }
+            });
+        }
+
         // Start KuCoin polling
         if let Some(ref client) = self.kucoin {
             let client = client.clone();
@@ -194,17 +223,23 @@ impl MultiSourceCoordinator {
         self.price_cache.subscribe()
     }
 
-    /// Subscribe to assets via Coinbase WebSocket.
+    /// Subscribe to assets via the Coinbase and Kraken WebSockets.
     pub async fn subscribe_assets(&self, symbols: &[String]) {
         if let Some(ref ws) = self.coinbase_ws {
             ws.subscribe(symbols).await;
         }
+        if let Some(ref ws) = self.kraken_ws {
+            ws.subscribe(symbols).await;
+        }
     }
 
-    /// Unsubscribe from assets via Coinbase WebSocket.
+    /// Unsubscribe from assets via the Coinbase and Kraken WebSockets.
     pub async fn unsubscribe_assets(&self, symbols: &[String]) {
         if let Some(ref ws) = self.coinbase_ws {
             ws.unsubscribe(symbols).await;
         }
+        if let Some(ref ws) = self.kraken_ws {
+            ws.unsubscribe(symbols).await;
+        }
     }
 }
@@ -0,0 +1,403 @@
+//! External notification delivery.
+//!
+//! Notifications are always stored and counted in-app. Users who opt into
+//! external delivery (`ProfileSettings::external_notifications`) additionally
+//! get each notification pushed through every configured deliverer (email,
+//! webhook). Deliveries run on spawned tasks with retry so a slow or failing
+//! endpoint never holds up the caller.
+
+use crate::config::NotificationConfig;
+use crate::services::SqliteStore;
+use crate::types::{Notification, Profile};
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Default number of attempts per deliverer before giving up.
+const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry; doubled after each failed attempt.
+const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// Timeout for a single webhook request.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A channel that can push a notification outside the app.
+#[axum::async_trait]
+pub trait NotificationDeliverer: Send + Sync {
+    /// Short name used in logs.
+    fn name(&self) -> &str;
+
+    /// Deliver a notification to the user owning `profile`.
+    async fn deliver(&self, profile: &Profile, notification: &Notification) -> Result<(), String>;
+}
+
+/// Sends notifications as plain-text email over SMTP.
+///
+/// Users without a `notification_email` in their settings are skipped.
+pub struct EmailDeliverer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl EmailDeliverer {
+    /// Create an SMTP deliverer using STARTTLS against `host`.
+    pub fn new(
+        host: &str,
+        port: u16,
+        username: Option<String>,
+        password: Option<String>,
+        from: &str,
+    ) -> Result<Self, String> {
+        let from: Mailbox = from
+            .parse()
+            .map_err(|e| format!("invalid from address: {}", e))?;
+        let mut builder = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+            .map_err(|e| format!("invalid SMTP host: {}", e))?
+            .port(port);
+        if let (Some(username), Some(password)) = (username, password) {
+            builder = builder.credentials(Credentials::new(username, password));
+        }
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+#[axum::async_trait]
+impl NotificationDeliverer for EmailDeliverer {
+    fn name(&self) -> &str {
+        "email"
+    }
+
+    async fn deliver(&self, profile: &Profile, notification: &Notification) -> Result<(), String> {
+        let Some(ref address) = profile.settings.notification_email else {
+            return Ok(());
+        };
+        let to: Mailbox = address
+            .parse()
+            .map_err(|e| format!("invalid recipient address: {}", e))?;
+        let email = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(&notification.title)
+            .body(notification.message.clone())
+            .map_err(|e| e.to_string())?;
+        self.transport
+            .send(email)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Webhook request body.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookPayload<'a> {
+    username: &'a str,
+    notification: &'a Notification,
+}
+
+/// Posts notifications as JSON to an operator-configured URL.
+pub struct WebhookDeliverer {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookDeliverer {
+    pub fn new(url: String) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self { client, url }
+    }
+}
+
+#[axum::async_trait]
+impl NotificationDeliverer for WebhookDeliverer {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn deliver(&self, profile: &Profile, notification: &Notification) -> Result<(), String> {
+        let payload = WebhookPayload {
+            username: &profile.username,
+            notification,
+        };
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("webhook returned {}", response.status()))
+        }
+    }
+}
+
+/// Stores notifications and fans them out to external deliverers.
+pub struct NotificationService {
+    sqlite: Arc<SqliteStore>,
+    deliverers: Vec<Arc<dyn NotificationDeliverer>>,
+    max_attempts: u32,
+    retry_delay: Duration,
+}
+
+impl NotificationService {
+    /// Create a service with no external deliverers.
+    pub fn new(sqlite: Arc<SqliteStore>) -> Self {
+        Self {
+            sqlite,
+            deliverers: Vec::new(),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            retry_delay: DEFAULT_RETRY_DELAY,
+        }
+    }
+
+    /// Create a service with the deliverers enabled in `config`.
+    pub fn from_config(sqlite: Arc<SqliteStore>, config: &NotificationConfig) -> Self {
+        let mut service = Self::new(sqlite);
+        service.max_attempts = config.max_attempts.max(1);
+        service.retry_delay = Duration::from_millis(config.retry_delay_ms);
+
+        if let (Some(host), Some(from)) = (&config.smtp_host, &config.smtp_from) {
+            match EmailDeliverer::new(
+                host,
+                config.smtp_port,
+                config.smtp_username.clone(),
+                config.smtp_password.clone(),
+                from,
+            ) {
+                Ok(email) => service.add_deliverer(Arc::new(email)),
+                Err(e) => warn!("Email notification delivery disabled: {}", e),
+            }
+        }
+        if let Some(ref url) = config.webhook_url {
+            service.add_deliverer(Arc::new(WebhookDeliverer::new(url.clone())));
+        }
+
+        service
+    }
+
+    /// Register an external deliverer.
+    pub fn add_deliverer(&mut self, deliverer: Arc<dyn NotificationDeliverer>) {
+        self.deliverers.push(deliverer);
+    }
+
+    /// Override the retry policy.
+    pub fn with_retry(mut self, max_attempts: u32, retry_delay: Duration) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Store a notification and dispatch it externally if the user opted in.
+    ///
+    /// Returns once the notification is stored; external deliveries continue
+    /// in the background. Returns the spawned delivery tasks.
+    pub fn notify(
+        &self,
+        notification: &Notification,
+    ) -> Result<Vec<tokio::task::JoinHandle<()>>, rusqlite::Error> {
+        self.sqlite.create_notification(notification)?;
+
+        if self.deliverers.is_empty() {
+            return Ok(Vec::new());
+        }
+        let Some(profile) = self.sqlite.get_profile(&notification.user_id) else {
+            return Ok(Vec::new());
+        };
+        if !profile.settings.external_notifications {
+            return Ok(Vec::new());
+        }
+
+        let profile = Arc::new(profile);
+        let notification = Arc::new(notification.clone());
+        let handles = self
+            .deliverers
+            .iter()
+            .map(|deliverer| {
+                tokio::spawn(deliver_with_retry(
+                    deliverer.clone(),
+                    profile.clone(),
+                    notification.clone(),
+                    self.max_attempts,
+                    self.retry_delay,
+                ))
+            })
+            .collect();
+        Ok(handles)
+    }
+}
+
+/// Attempt a delivery up to `max_attempts` times with exponential backoff.
+async fn deliver_with_retry(
+    deliverer: Arc<dyn NotificationDeliverer>,
+    profile: Arc<Profile>,
+    notification: Arc<Notification>,
+    max_attempts: u32,
+    retry_delay: Duration,
+) {
+    let mut delay = retry_delay;
+    for attempt in 1..=max_attempts {
+        match deliverer.deliver(&profile, &notification).await {
+            Ok(()) => {
+                debug!(
+                    "Delivered notification {} via {}",
+                    notification.id,
+                    deliverer.name()
+                );
+                return;
+            }
+            Err(e) if attempt < max_attempts => {
+                debug!(
+                    "{} delivery of {} failed (attempt {}/{}): {}",
+                    deliverer.name(),
+                    notification.id,
+                    attempt,
+                    max_attempts,
+                    e
+                );
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+            Err(e) => {
+                warn!(
+                    "Giving up on {} delivery of notification {} after {} attempts: {}",
+                    deliverer.name(),
+                    notification.id,
+                    max_attempts,
+                    e
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::NotificationType;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Records calls and fails the first `failures` attempts.
+    struct MockDeliverer {
+        calls: AtomicU32,
+        failures: u32,
+    }
+
+    impl MockDeliverer {
+        fn new(failures: u32) -> Arc<Self> {
+            Arc::new(Self {
+                calls: AtomicU32::new(0),
+                failures,
+            })
+        }
+    }
+
+    #[axum::async_trait]
+    impl NotificationDeliverer for MockDeliverer {
+        fn name(&self) -> &str {
+            "mock"
+        }
+
+        async fn deliver(&self, _: &Profile, _: &Notification) -> Result<(), String> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if call < self.failures {
+                Err("unavailable".to_string())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn service_with(mock: Arc<MockDeliverer>, opted_in: bool) -> NotificationService {
+        let sqlite = Arc::new(SqliteStore::new_in_memory().unwrap());
+        let mut profile = Profile::new("user1".to_string(), "alice".to_string());
+        profile.settings.external_notifications = opted_in;
+        sqlite.save_profile(&profile).unwrap();
+
+        let mut service = NotificationService::new(sqlite).with_retry(3, Duration::from_millis(1));
+        service.add_deliverer(mock);
+        service
+    }
+
+    fn notification() -> Notification {
+        Notification::new(
+            "user1".to_string(),
+            NotificationType::Trade,
+            "Order filled".to_string(),
+            "Bought 1 BTC".to_string(),
+        )
+    }
+
+    async fn wait(handles: Vec<tokio::task::JoinHandle<()>>) {
+        for handle in handles {
+            handle.await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delivers_to_opted_in_user() {
+        let mock = MockDeliverer::new(0);
+        let service = service_with(mock.clone(), true);
+
+        let handles = service.notify(&notification()).unwrap();
+        assert_eq!(handles.len(), 1);
+        wait(handles).await;
+
+        assert_eq!(mock.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(service.sqlite.unread_notification_count("user1"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_skips_user_not_opted_in() {
+        let mock = MockDeliverer::new(0);
+        let service = service_with(mock.clone(), false);
+
+        let handles = service.notify(&notification()).unwrap();
+        assert!(handles.is_empty());
+
+        assert_eq!(mock.calls.load(Ordering::SeqCst), 0);
+        // Still stored in-app
+        assert_eq!(service.sqlite.unread_notification_count("user1"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retries_failed_delivery() {
+        let mock = MockDeliverer::new(2);
+        let service = service_with(mock.clone(), true);
+        wait(service.notify(&notification()).unwrap()).await;
+        assert_eq!(mock.calls.load(Ordering::SeqCst), 3);
+
+        // Gives up after max attempts
+        let mock = MockDeliverer::new(10);
+        let service = service_with(mock.clone(), true);
+        wait(service.notify(&notification()).unwrap()).await;
+        assert_eq!(mock.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_from_config_uses_retry_settings() {
+        let sqlite = Arc::new(SqliteStore::new_in_memory().unwrap());
+        let config = NotificationConfig {
+            max_attempts: 5,
+            retry_delay_ms: 250,
+            ..NotificationConfig::default()
+        };
+        let service = NotificationService::from_config(sqlite, &config);
+        assert_eq!(service.max_attempts, 5);
+        assert_eq!(service.retry_delay, Duration::from_millis(250));
+    }
+}
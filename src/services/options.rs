@@ -5,8 +5,11 @@
 //! - Binomial pricing for American options
 //! - Greeks calculation (Delta, Gamma, Theta, Vega, Rho)
 //! - Implied volatility calculation
+//! - Theoretical option chain generation
 
-use crate::types::{Greeks, OptionContract, OptionPosition, OptionStyle, OptionType};
+use crate::types::{
+    Greeks, OptionContract, OptionPosition, OptionStyle, OptionType, OptionsChain,
+};
 use std::f64::consts::{E, PI};
 use thiserror::Error;
 use tracing::debug;
@@ -22,6 +25,37 @@ pub enum OptionsError {
     IVConvergenceFailed(u32),
 }
 
+/// Binomial tree steps used to mark American-style options.
+pub const AMERICAN_TREE_STEPS: u32 = 100;
+
+/// Milliseconds in a day, for converting expirations to year fractions.
+const MS_PER_DAY: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+/// Strike layout and pricing inputs for a generated option chain.
+#[derive(Debug, Clone, Copy)]
+pub struct ChainConfig {
+    /// Distance between adjacent strikes; `None` picks a round step near
+    /// 2.5% of the underlying price
+    pub strike_step: Option<f64>,
+    /// Strikes listed on each side of the at-the-money strike
+    pub strikes_per_side: usize,
+    /// Annualized volatility used to price every contract
+    pub volatility: f64,
+    /// Exercise style of the generated contracts
+    pub style: OptionStyle,
+}
+
+impl Default for ChainConfig {
+    fn default() -> Self {
+        Self {
+            strike_step: None,
+            strikes_per_side: 5,
+            volatility: 0.6,
+            style: OptionStyle::American,
+        }
+    }
+}
+
 /// Options pricing and Greeks calculator.
 pub struct OptionsService {
     /// Risk-free interest rate (annual, as decimal)
@@ -256,55 +290,16 @@ impl OptionsService {
         style: OptionStyle,
         steps: u32,
     ) -> Result<f64, OptionsError> {
-        if spot <= 0.0 || strike <= 0.0 || time_years <= 0.0 || volatility <= 0.0 || steps == 0 {
-            return Err(OptionsError::InvalidInput(
-                "All inputs must be positive".to_string(),
-            ));
-        }
-
-        let dt = time_years / steps as f64;
-        let r = self.risk_free_rate;
-
-        // Up and down factors
-        let u = E.powf(volatility * dt.sqrt());
-        let d = 1.0 / u;
-
-        // Risk-neutral probability
-        let p = (E.powf(r * dt) - d) / (u - d);
-        let discount = E.powf(-r * dt);
-
-        // Build price tree at expiration
-        let n = steps as usize;
-        let mut prices = vec![0.0; n + 1];
-
-        for i in 0..=n {
-            let spot_at_node = spot * u.powi(i as i32) * d.powi((n - i) as i32);
-            prices[i] = match option_type {
-                OptionType::Call => (spot_at_node - strike).max(0.0),
-                OptionType::Put => (strike - spot_at_node).max(0.0),
-            };
-        }
-
-        // Work backwards through tree
-        for step in (0..n).rev() {
-            for i in 0..=step {
-                let spot_at_node = spot * u.powi(i as i32) * d.powi((step - i) as i32);
-                let hold_value = discount * (p * prices[i + 1] + (1.0 - p) * prices[i]);
-
-                prices[i] = match style {
-                    OptionStyle::European => hold_value,
-                    OptionStyle::American => {
-                        let exercise_value = match option_type {
-                            OptionType::Call => (spot_at_node - strike).max(0.0),
-                            OptionType::Put => (strike - spot_at_node).max(0.0),
-                        };
-                        hold_value.max(exercise_value)
-                    }
-                };
-            }
-        }
-
-        Ok(prices[0])
+        crr_price(
+            spot,
+            strike,
+            time_years,
+            self.risk_free_rate,
+            volatility,
+            option_type,
+            style,
+            steps,
+        )
     }
 
     // ========== Option Pricing Wrapper ==========
@@ -323,10 +318,15 @@ impl OptionsService {
             OptionStyle::European => {
                 self.black_scholes_price(spot, strike, time_years, volatility, option_type)
             }
-            OptionStyle::American => {
-                // Use binomial with 100 steps for American options
-                self.binomial_price(spot, strike, time_years, volatility, option_type, style, 100)
-            }
+            OptionStyle::American => american_price(
+                spot,
+                strike,
+                time_years,
+                self.risk_free_rate,
+                volatility,
+                option_type,
+                AMERICAN_TREE_STEPS,
+            ),
         }
     }
 
@@ -397,6 +397,220 @@ impl OptionsService {
 
         Ok(())
     }
+
+    // ========== Chain Generation ==========
+
+    /// Generate a theoretical option chain, one [`OptionsChain`] per expiration.
+    ///
+    /// Strikes are laid out symmetrically around the strike nearest `spot`;
+    /// strikes that would fall to zero or below are dropped. Each contract is
+    /// priced at `config.volatility`, with bid, ask and last all set to the
+    /// theoretical premium. Expirations at or before `now_ms` are rejected.
+    pub fn generate_chain(
+        &self,
+        underlying: &str,
+        spot: f64,
+        expirations: &[i64],
+        config: &ChainConfig,
+        now_ms: i64,
+    ) -> Result<Vec<OptionsChain>, OptionsError> {
+        if spot <= 0.0 || config.volatility <= 0.0 {
+            return Err(OptionsError::InvalidInput(
+                "Spot and volatility must be positive".to_string(),
+            ));
+        }
+        let step = config.strike_step.unwrap_or_else(|| default_strike_step(spot));
+        if step <= 0.0 || !step.is_finite() {
+            return Err(OptionsError::InvalidInput(
+                "Strike step must be positive".to_string(),
+            ));
+        }
+
+        let atm = (spot / step).round() * step;
+        let side = config.strikes_per_side as i64;
+        let strikes: Vec<f64> = (-side..=side)
+            .map(|k| atm + k as f64 * step)
+            .filter(|strike| *strike > 0.0)
+            .collect();
+
+        let underlying = underlying.to_uppercase();
+        let mut chains = Vec::with_capacity(expirations.len());
+        for &expiration in expirations {
+            let time_years = (expiration - now_ms) as f64 / MS_PER_DAY / 365.0;
+            if time_years <= 0.0 {
+                return Err(OptionsError::InvalidInput(format!(
+                    "Expiration {} is not in the future",
+                    expiration
+                )));
+            }
+
+            let contracts = |option_type: OptionType| {
+                strikes
+                    .iter()
+                    .map(|&strike| {
+                        self.chain_contract(
+                            &underlying,
+                            spot,
+                            strike,
+                            expiration,
+                            time_years,
+                            option_type,
+                            config,
+                        )
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            };
+
+            chains.push(OptionsChain {
+                underlying_symbol: underlying.clone(),
+                underlying_price: spot,
+                expiration,
+                calls: contracts(OptionType::Call)?,
+                puts: contracts(OptionType::Put)?,
+                timestamp: now_ms,
+            });
+        }
+
+        debug!(
+            "Generated {} option chain(s) for {} with {} strikes",
+            chains.len(),
+            underlying,
+            strikes.len()
+        );
+        Ok(chains)
+    }
+
+    /// Price a single contract for [`OptionsService::generate_chain`].
+    #[allow(clippy::too_many_arguments)]
+    fn chain_contract(
+        &self,
+        underlying: &str,
+        spot: f64,
+        strike: f64,
+        expiration: i64,
+        time_years: f64,
+        option_type: OptionType,
+        config: &ChainConfig,
+    ) -> Result<OptionContract, OptionsError> {
+        let (premium, greeks) = self.analyze_option(
+            spot,
+            strike,
+            time_years,
+            config.volatility,
+            option_type,
+            config.style,
+        )?;
+
+        let mut contract = OptionContract::new(
+            underlying.to_string(),
+            option_type,
+            strike,
+            expiration,
+            config.style,
+        );
+        contract.bid = premium;
+        contract.ask = premium;
+        contract.last = premium;
+        contract.implied_volatility = config.volatility;
+        contract.greeks = greeks;
+        Ok(contract)
+    }
+}
+
+/// Round strike spacing for an underlying: the 1/2/5 x 10^n step closest to
+/// 2.5% of `spot`.
+fn default_strike_step(spot: f64) -> f64 {
+    let target = spot * 0.025;
+    let magnitude = 10f64.powf(target.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|m| m * magnitude)
+        .min_by(|a, b| (a - target).abs().total_cmp(&(b - target).abs()))
+        .unwrap_or(magnitude)
+}
+
+/// Price an American option on a Cox-Ross-Rubinstein binomial tree.
+///
+/// `rate` is the annual risk-free rate as a decimal. At every node the option
+/// is worth the larger of holding and exercising immediately, so the result
+/// includes the early-exercise premium a European price misses (mainly for
+/// puts; without dividends an American call matches its European value).
+pub fn american_price(
+    spot: f64,
+    strike: f64,
+    time_years: f64,
+    rate: f64,
+    volatility: f64,
+    option_type: OptionType,
+    steps: u32,
+) -> Result<f64, OptionsError> {
+    crr_price(
+        spot,
+        strike,
+        time_years,
+        rate,
+        volatility,
+        option_type,
+        OptionStyle::American,
+        steps,
+    )
+}
+
+/// Cox-Ross-Rubinstein tree pricing for either exercise style.
+#[allow(clippy::too_many_arguments)]
+fn crr_price(
+    spot: f64,
+    strike: f64,
+    time_years: f64,
+    rate: f64,
+    volatility: f64,
+    option_type: OptionType,
+    style: OptionStyle,
+    steps: u32,
+) -> Result<f64, OptionsError> {
+    if spot <= 0.0 || strike <= 0.0 || time_years <= 0.0 || volatility <= 0.0 || steps == 0 {
+        return Err(OptionsError::InvalidInput(
+            "All inputs must be positive".to_string(),
+        ));
+    }
+
+    let dt = time_years / steps as f64;
+
+    // Up and down factors
+    let u = E.powf(volatility * dt.sqrt());
+    let d = 1.0 / u;
+
+    // Risk-neutral probability
+    let p = (E.powf(rate * dt) - d) / (u - d);
+    let discount = E.powf(-rate * dt);
+
+    let payoff = |spot_at_node: f64| match option_type {
+        OptionType::Call => (spot_at_node - strike).max(0.0),
+        OptionType::Put => (strike - spot_at_node).max(0.0),
+    };
+
+    // Build price tree at expiration
+    let n = steps as usize;
+    let mut prices: Vec<f64> = (0..=n)
+        .map(|i| payoff(spot * u.powi(i as i32) * d.powi((n - i) as i32)))
+        .collect();
+
+    // Work backwards through tree
+    for step in (0..n).rev() {
+        for i in 0..=step {
+            let hold_value = discount * (p * prices[i + 1] + (1.0 - p) * prices[i]);
+
+            prices[i] = match style {
+                OptionStyle::European => hold_value,
+                OptionStyle::American => {
+                    let spot_at_node = spot * u.powi(i as i32) * d.powi((step - i) as i32);
+                    hold_value.max(payoff(spot_at_node))
+                }
+            };
+        }
+    }
+
+    Ok(prices[0])
 }
 
 /// Error function approximation for normal CDF.
@@ -567,6 +781,61 @@ mod tests {
         assert!(american_price >= european_price);
     }
 
+    #[test]
+    fn test_american_put_at_or_above_black_scholes() {
+        let service = setup_service();
+
+        for strike in [80.0, 100.0, 120.0] {
+            let european = service
+                .black_scholes_price(100.0, strike, 1.0, 0.25, OptionType::Put)
+                .unwrap();
+            let american =
+                american_price(100.0, strike, 1.0, 0.05, 0.25, OptionType::Put, 200).unwrap();
+            assert!(
+                american >= european - 1e-9,
+                "strike {}: american {} < european {}",
+                strike,
+                american,
+                european
+            );
+        }
+
+        // Deep in the money the early-exercise premium is clearly visible
+        let european = service
+            .black_scholes_price(100.0, 140.0, 1.0, 0.25, OptionType::Put)
+            .unwrap();
+        let american = american_price(100.0, 140.0, 1.0, 0.05, 0.25, OptionType::Put, 200).unwrap();
+        assert!(american > european + 0.5);
+        assert!(american >= 40.0);
+    }
+
+    #[test]
+    fn test_american_call_matches_european_without_dividends() {
+        let service = setup_service();
+        let european = service
+            .black_scholes_price(100.0, 100.0, 1.0, 0.20, OptionType::Call)
+            .unwrap();
+        let american =
+            american_price(100.0, 100.0, 1.0, 0.05, 0.20, OptionType::Call, 200).unwrap();
+        assert!((american - european).abs() / european < 0.01);
+
+        // American-style positions are marked with the tree price
+        let marked = service
+            .price_option(100.0, 100.0, 1.0, 0.20, OptionType::Put, OptionStyle::American)
+            .unwrap();
+        let tree = american_price(
+            100.0,
+            100.0,
+            1.0,
+            0.05,
+            0.20,
+            OptionType::Put,
+            AMERICAN_TREE_STEPS,
+        )
+        .unwrap();
+        assert_eq!(marked, tree);
+    }
+
     #[test]
     fn test_deep_itm_call() {
         let service = setup_service();
@@ -612,4 +881,100 @@ mod tests {
             .black_scholes_price(100.0, 100.0, 1.0, -0.20, OptionType::Call)
             .is_err());
     }
+
+    #[test]
+    fn test_generate_chain_contains_atm_strikes() {
+        let service = setup_service();
+        let now = 1_700_000_000_000;
+        let expirations = [now + 7 * 86_400_000, now + 30 * 86_400_000];
+        let config = ChainConfig {
+            strike_step: Some(1000.0),
+            strikes_per_side: 3,
+            ..ChainConfig::default()
+        };
+
+        let chains = service
+            .generate_chain("btc", 50_200.0, &expirations, &config, now)
+            .unwrap();
+
+        assert_eq!(chains.len(), 2);
+        for chain in &chains {
+            assert_eq!(chain.underlying_symbol, "BTC");
+            assert_eq!(chain.calls.len(), 7);
+            assert_eq!(chain.puts.len(), 7);
+            assert_eq!(chain.atm_strike(), Some(50_000.0));
+            assert!(chain.get_call(50_000.0).is_some());
+            assert!(chain.get_put(50_000.0).is_some());
+        }
+    }
+
+    #[test]
+    fn test_generate_chain_premiums_decrease_away_from_money() {
+        let service = setup_service();
+        let now = 1_700_000_000_000;
+        let config = ChainConfig {
+            strike_step: Some(5.0),
+            strikes_per_side: 5,
+            volatility: 0.4,
+            style: OptionStyle::European,
+        };
+
+        let chain = service
+            .generate_chain("eth", 100.0, &[now + 30 * 86_400_000], &config, now)
+            .unwrap()
+            .remove(0);
+
+        // Calls lose value as the strike rises above the money
+        let otm_calls: Vec<f64> = chain
+            .calls
+            .iter()
+            .filter(|c| c.strike >= 100.0)
+            .map(|c| c.last)
+            .collect();
+        assert_eq!(otm_calls.len(), 6);
+        assert!(otm_calls.windows(2).all(|w| w[1] < w[0]));
+
+        // Puts lose value as the strike falls below the money
+        let otm_puts: Vec<f64> = chain
+            .puts
+            .iter()
+            .rev()
+            .filter(|p| p.strike <= 100.0)
+            .map(|p| p.last)
+            .collect();
+        assert_eq!(otm_puts.len(), 6);
+        assert!(otm_puts.windows(2).all(|w| w[1] < w[0]));
+
+        assert!(chain.calls.iter().all(|c| c.greeks.delta > 0.0));
+        assert!(chain.puts.iter().all(|p| p.greeks.delta < 0.0));
+    }
+
+    #[test]
+    fn test_generate_chain_default_step_and_validation() {
+        let service = setup_service();
+        let now = 1_700_000_000_000;
+        let expiry = [now + 86_400_000];
+
+        // 2.5% of 50,000 is 1,250, so the nearest round step is 1,000
+        let chain = service
+            .generate_chain("btc", 50_000.0, &expiry, &ChainConfig::default(), now)
+            .unwrap()
+            .remove(0);
+        assert_eq!(chain.calls[1].strike - chain.calls[0].strike, 1000.0);
+
+        // Strikes at or below zero are dropped
+        let wide = ChainConfig {
+            strike_step: Some(40.0),
+            ..ChainConfig::default()
+        };
+        let chain = service
+            .generate_chain("sol", 100.0, &expiry, &wide, now)
+            .unwrap()
+            .remove(0);
+        assert!(chain.calls.iter().all(|c| c.strike > 0.0));
+
+        assert!(service
+            .generate_chain("btc", 50_000.0, &[now], &ChainConfig::default(), now)
+            .is_err());
+    }
 }
@@ -2,7 +2,9 @@
 //!
 //! Fetches and aggregates order book data from multiple exchanges.
 
-use crate::types::{AggregatedOrderBook, ExchangeOrderBook, OrderBookLevel, PriceSource};
+use crate::types::{
+    AggregatedOrderBook, DepthChart, ExchangeOrderBook, OrderBookLevel, PriceSource,
+};
 use dashmap::DashMap;
 use reqwest::Client;
 use serde::Deserialize;
@@ -19,6 +21,10 @@ const DEFAULT_DEPTH: usize = 50;
 /// Maximum depth levels to return
 const MAX_DEPTH: usize = 100;
 
+/// Live books pushed by streaming feeds are used instead of a REST fetch
+/// while younger than this
+const LIVE_BOOK_MAX_AGE_MS: i64 = 5000;
+
 // ============================================================================
 // Exchange symbol mappings
 // ============================================================================
@@ -271,6 +277,8 @@ struct CacheEntry {
 pub struct OrderBookService {
     client: Client,
     cache: DashMap<String, CacheEntry>,
+    /// Latest book per (symbol, exchange) from streaming feeds
+    live_books: DashMap<(String, PriceSource), ExchangeOrderBook>,
 }
 
 impl OrderBookService {
@@ -285,7 +293,38 @@ impl OrderBookService {
         Self {
             client,
             cache: DashMap::new(),
+            live_books: DashMap::new(),
+        }
+    }
+
+    /// Store a book maintained by a streaming feed.
+    pub fn update_live_book(&self, book: ExchangeOrderBook) {
+        let key = (book.symbol.to_lowercase(), book.exchange);
+        self.live_books.insert(key, book);
+    }
+
+    /// Latest streamed book for a symbol from one exchange, if any.
+    pub fn live_book(&self, symbol: &str, exchange: PriceSource) -> Option<ExchangeOrderBook> {
+        self.live_books
+            .get(&(symbol.to_lowercase(), exchange))
+            .map(|book| book.clone())
+    }
+
+    /// A streamed book recent enough to stand in for a REST fetch, trimmed
+    /// to `depth` levels per side.
+    fn fresh_live_book(
+        &self,
+        symbol: &str,
+        exchange: PriceSource,
+        depth: usize,
+    ) -> Option<ExchangeOrderBook> {
+        let mut book = self.live_book(symbol, exchange)?;
+        if chrono::Utc::now().timestamp_millis() - book.timestamp > LIVE_BOOK_MAX_AGE_MS {
+            return None;
         }
+        book.bids.truncate(depth);
+        book.asks.truncate(depth);
+        Some(book)
     }
 
     /// Get aggregated order book for a symbol.
@@ -318,10 +357,21 @@ impl OrderBookService {
         aggregated
     }
 
+    /// Get cumulative depth curves for a symbol from the aggregated book.
+    pub async fn get_depth_chart(&self, symbol: &str, depth: Option<usize>) -> DepthChart {
+        self.get_aggregated(symbol, depth).await.depth_chart()
+    }
+
     /// Fetch order books from all exchanges in parallel.
     async fn fetch_all_exchanges(&self, symbol: &str, depth: usize) -> Vec<ExchangeOrderBook> {
+        let live_coinbase = self.fresh_live_book(symbol, PriceSource::Coinbase, depth);
         let (coinbase, kraken, kucoin, okx, huobi, hyperliquid) = tokio::join!(
-            self.fetch_coinbase(symbol, depth),
+            async {
+                match live_coinbase {
+                    Some(book) => Some(book),
+                    None => self.fetch_coinbase(symbol, depth).await,
+                }
+            },
             self.fetch_kraken(symbol, depth),
             self.fetch_kucoin(symbol, depth),
             self.fetch_okx(symbol, depth),
@@ -600,6 +600,7 @@ impl BotRunner {
                     take_profit: *take_profit,
                     client_order_id: None,
                     bypass_drawdown: false,
+                    display_quantity: None,
                 };
 
                 let order = self
@@ -655,6 +656,7 @@ impl BotRunner {
                     take_profit: None,
                     client_order_id: None,
                     bypass_drawdown: false,
+                    display_quantity: None,
                 };
 
                 let order = self
@@ -3,6 +3,7 @@
 //! This module manages WebSocket connections between Haunt API servers,
 //! providing real-time latency tracking and server health monitoring.
 
+use crate::config::SharedConfig;
 use dashmap::DashMap;
 use futures_util::{SinkExt, StreamExt};
 use hmac::{Hmac, Mac};
@@ -10,7 +11,7 @@ use sha2::Sha256;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::net::TcpStream;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::{broadcast, watch, RwLock};
 use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
 use tracing::{debug, error, info, warn};
 
@@ -132,10 +133,11 @@ pub struct PeerMesh {
     status_tx: broadcast::Sender<Vec<PeerStatus>>,
     /// Pending ping timestamps for latency calculation.
     pending_pings: DashMap<String, Instant>,
-    /// Shared key for mesh authentication.
-    shared_key: Option<String>,
-    /// Whether authentication is required.
-    require_auth: bool,
+    /// Live config; mesh auth is read from it on every handshake so a
+    /// SIGHUP reload takes effect without reconnecting.
+    config: SharedConfig,
+    /// Flipped to true when the mesh is stopping.
+    stop_tx: watch::Sender<bool>,
 }
 
 impl PeerMesh {
@@ -145,10 +147,10 @@ impl PeerMesh {
         server_region: String,
         ws_url: String,
         api_url: String,
-        shared_key: Option<String>,
-        require_auth: bool,
+        config: SharedConfig,
     ) -> Arc<Self> {
         let (status_tx, _) = broadcast::channel(256);
+        let (stop_tx, _) = watch::channel(false);
 
         Arc::new(Self {
             server_id,
@@ -159,11 +161,28 @@ impl PeerMesh {
             known_peers: DashMap::new(),
             status_tx,
             pending_pings: DashMap::new(),
-            shared_key,
-            require_auth,
+            config,
+            stop_tx,
         })
     }
 
+    /// Stop the mesh: peer connections send a close frame and are not
+    /// reconnected, and the background tasks exit.
+    pub fn stop(&self) {
+        self.stop_tx.send_replace(true);
+    }
+
+    /// Whether [`PeerMesh::stop`] has been called.
+    pub fn is_stopped(&self) -> bool {
+        *self.stop_tx.borrow()
+    }
+
+    /// Resolve once the mesh has been stopped.
+    async fn stopped(&self) {
+        let mut rx = self.stop_tx.subscribe();
+        let _ = rx.wait_for(|stopped| *stopped).await;
+    }
+
     /// Get this server's WebSocket URL.
     pub fn ws_url(&self) -> &str {
         &self.ws_url
@@ -174,9 +193,19 @@ impl PeerMesh {
         &self.api_url
     }
 
+    /// Current shared key (None when unset) and whether auth is required.
+    fn mesh_auth(&self) -> (Option<String>, bool) {
+        let config = self.config.load();
+        let key = &config.mesh_auth.shared_key;
+        (
+            (!key.is_empty()).then(|| key.clone()),
+            config.mesh_auth.require_auth,
+        )
+    }
+
     /// Generate an HMAC signature for authentication.
     fn generate_signature(&self, message: &str) -> Option<String> {
-        self.shared_key.as_ref().map(|key| {
+        self.mesh_auth().0.map(|key| {
             let mut mac =
                 HmacSha256::new_from_slice(key.as_bytes()).expect("HMAC can take key of any size");
             mac.update(message.as_bytes());
@@ -187,7 +216,8 @@ impl PeerMesh {
 
     /// Verify an HMAC signature.
     fn verify_signature(&self, message: &str, signature: &str) -> bool {
-        match &self.shared_key {
+        let (shared_key, require_auth) = self.mesh_auth();
+        match shared_key {
             Some(key) => {
                 let mut mac = HmacSha256::new_from_slice(key.as_bytes())
                     .expect("HMAC can take key of any size");
@@ -199,7 +229,7 @@ impl PeerMesh {
                     false
                 }
             }
-            None => !self.require_auth, // If no key and auth not required, pass
+            None => !require_auth, // If no key and auth not required, pass
         }
     }
 
@@ -275,7 +305,7 @@ impl PeerMesh {
 
     /// Generate announcement signature.
     fn generate_announce_signature(&self, timestamp: i64) -> Option<String> {
-        self.shared_key.as_ref().map(|key| {
+        self.mesh_auth().0.map(|key| {
             let message = format!(
                 "announce:{}:{}:{}",
                 self.server_id, self.server_region, timestamp
@@ -295,7 +325,8 @@ impl PeerMesh {
         timestamp: i64,
         signature: &str,
     ) -> bool {
-        match &self.shared_key {
+        let (shared_key, require_auth) = self.mesh_auth();
+        match shared_key {
             Some(key) => {
                 let message = format!("announce:{}:{}:{}", id, region, timestamp);
                 let mut mac = HmacSha256::new_from_slice(key.as_bytes())
@@ -307,7 +338,7 @@ impl PeerMesh {
                     false
                 }
             }
-            None => !self.require_auth,
+            None => !require_auth,
         }
     }
 
@@ -515,6 +546,9 @@ impl PeerMesh {
             let mut interval = tokio::time::interval(Duration::from_millis(100));
             loop {
                 interval.tick().await;
+                if mesh_broadcast.is_stopped() {
+                    break;
+                }
                 mesh_broadcast.broadcast_statuses();
             }
         });
@@ -525,6 +559,9 @@ impl PeerMesh {
             let mut interval = tokio::time::interval(Duration::from_secs(30));
             loop {
                 interval.tick().await;
+                if mesh_gossip.is_stopped() {
+                    break;
+                }
                 // Check for new discovered peers and connect
                 mesh_gossip.connect_to_discovered_peers();
             }
@@ -536,6 +573,9 @@ impl PeerMesh {
             let mut interval = tokio::time::interval(Duration::from_secs(300));
             loop {
                 interval.tick().await;
+                if mesh_prune.is_stopped() {
+                    break;
+                }
                 // Prune peers not seen in 10 minutes
                 mesh_prune.prune_stale_peers(600_000);
             }
@@ -545,6 +585,10 @@ impl PeerMesh {
     /// Manage a single peer connection with auto-reconnect.
     async fn manage_peer_connection(self: Arc<Self>, peer_id: String) {
         loop {
+            if self.is_stopped() {
+                break;
+            }
+
             // Update status to connecting
             if let Some(entry) = self.peers.get(&peer_id) {
                 let mut peer = entry.write().await;
@@ -598,7 +642,10 @@ impl PeerMesh {
             }
 
             // Wait before reconnect attempt
-            tokio::time::sleep(Duration::from_secs(5)).await;
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(5)) => {}
+                _ = self.stopped() => break,
+            }
         }
     }
 
@@ -726,23 +773,37 @@ impl PeerMesh {
             }
         });
 
-        // Spawn write task
-        let write_task = tokio::spawn(async move {
+        // Spawn write task. Once the ping task is gone it says goodbye with a
+        // close frame.
+        let mut write_task = tokio::spawn(async move {
             while let Some(msg) = ping_rx.recv().await {
                 if write
                     .send(tokio_tungstenite::tungstenite::Message::Text(msg))
                     .await
                     .is_err()
                 {
-                    break;
+                    return;
                 }
             }
+            let _ = write
+                .send(tokio_tungstenite::tungstenite::Message::Close(None))
+                .await;
         });
 
         info!("Starting ping loop for peer {}", peer_id);
 
-        // Handle incoming messages
-        while let Some(result) = read.next().await {
+        // Handle incoming messages until the peer goes away or the mesh stops
+        loop {
+            let result = tokio::select! {
+                next = read.next() => match next {
+                    Some(result) => result,
+                    None => break,
+                },
+                _ = self.stopped() => {
+                    info!("Closing connection to peer {} for shutdown", peer_id);
+                    break;
+                }
+            };
             match result {
                 Ok(tokio_tungstenite::tungstenite::Message::Text(text)) => {
                     debug!(
@@ -773,6 +834,10 @@ impl PeerMesh {
         info!("Exiting message loop for peer {}", peer_id);
 
         ping_task.abort();
+        if self.is_stopped() {
+            // Dropping the ping sender ends the write loop, which then sends the close frame
+            let _ = tokio::time::timeout(Duration::from_secs(2), &mut write_task).await;
+        }
         write_task.abort();
     }
 
@@ -867,3 +932,40 @@ impl PeerMesh {
 // NOTE: PeerMesh should always be used via Arc<PeerMesh>.
 // Do NOT implement Clone for PeerMesh as it would create a separate instance
 // with empty peer/pending_pings maps, breaking shared state.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use arc_swap::ArcSwap;
+
+    #[test]
+    fn test_mesh_auth_follows_config_reload() {
+        let mut config = Config::from_env();
+        config.mesh_auth.shared_key = "before".to_string();
+        config.mesh_auth.require_auth = true;
+        let shared: SharedConfig = Arc::new(ArcSwap::from_pointee(config.clone()));
+        let mesh = PeerMesh::new(
+            "server-a".to_string(),
+            "us-east".to_string(),
+            "ws://localhost:3001/ws".to_string(),
+            "http://localhost:3001".to_string(),
+            shared.clone(),
+        );
+
+        let old_signature = mesh.generate_signature("ping").unwrap();
+        assert!(mesh.verify_signature("ping", &old_signature));
+
+        config.mesh_auth.shared_key = "after".to_string();
+        shared.store(Arc::new(config.clone()));
+        assert!(!mesh.verify_signature("ping", &old_signature));
+        let new_signature = mesh.generate_signature("ping").unwrap();
+        assert!(mesh.verify_signature("ping", &new_signature));
+
+        // Clearing the key with auth required rejects unsigned peers
+        config.mesh_auth.shared_key = String::new();
+        shared.store(Arc::new(config));
+        assert!(mesh.generate_signature("ping").is_none());
+        assert!(!mesh.verify_signature("ping", &new_signature));
+    }
+}
@@ -1,12 +1,18 @@
-use crate::types::{AggregatedPrice, AggregationConfig, PriceSource, SourcePrice, TradeDirection};
+use super::redis_store::{FanoutPrice, PriceFanout};
+use super::source_reliability::SourceReliability;
+use crate::types::{
+    AggregatedPrice, AggregationConfig, CircuitBreakerTrip, PriceSource, SourcePrice,
+    TradeDirection,
+};
 use dashmap::DashMap;
 use redis::aio::ConnectionManager;
 use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Instant;
 use tokio::sync::{broadcast, RwLock};
 use tracing::{debug, info, warn};
+use utoipa::ToSchema;
 
 const REDIS_PRICE_PREFIX: &str = "haunt:price:";
 const REDIS_UPDATE_COUNT_KEY: &str = "haunt:stats:update_count";
@@ -35,6 +41,13 @@ struct SymbolPrice {
     cached_volume: Option<CachedVolume>,
     /// Last trade direction (up/down based on price movement).
     trade_direction: Option<TradeDirection>,
+    /// Recent aggregated prices as (unix ms, price), one per history bucket.
+    history: VecDeque<(i64, f64)>,
+    /// Aggregated price after every change, unthrottled, for smoothing.
+    recent: VecDeque<f64>,
+    /// Prices held back by the circuit breaker as (price, source timestamp in
+    /// unix ms), awaiting corroboration from another source.
+    quarantined: HashMap<PriceSource, (f64, i64)>,
 }
 
 impl Default for SymbolPrice {
@@ -46,6 +59,33 @@ impl Default for SymbolPrice {
             last_update_time: Instant::now(),
             cached_volume: None,
             trade_direction: None,
+            history: VecDeque::new(),
+            recent: VecDeque::new(),
+            quarantined: HashMap::new(),
+        }
+    }
+}
+
+impl SymbolPrice {
+    /// Record an aggregated price, keeping at most one sample per bucket.
+    fn record_history(&mut self, timestamp: i64, price: f64) {
+        let bucket = timestamp / HISTORY_BUCKET_MS;
+        match self.history.back_mut() {
+            Some(last) if last.0 / HISTORY_BUCKET_MS == bucket => *last = (timestamp, price),
+            _ => {
+                self.history.push_back((timestamp, price));
+                if self.history.len() > HISTORY_MAX_POINTS {
+                    self.history.pop_front();
+                }
+            }
+        }
+    }
+
+    /// Record an aggregated price for smoothing, dropping the oldest past the cap.
+    fn record_recent(&mut self, price: f64) {
+        self.recent.push_back(price);
+        if self.recent.len() > SMOOTHING_MAX_SAMPLES {
+            self.recent.pop_front();
         }
     }
 }
@@ -59,9 +99,28 @@ struct SourceStatus {
     last_success: AtomicU64,
 }
 
+/// Width of a price history bucket (ms); later prices in a bucket replace earlier ones.
+const HISTORY_BUCKET_MS: i64 = 10_000;
+
+/// Maximum history samples kept per symbol (4 hours of 10s buckets).
+const HISTORY_MAX_POINTS: usize = 1_440;
+
+/// Maximum aggregated prices kept per symbol for [`PriceCache::smoothed_price`].
+pub const SMOOTHING_MAX_SAMPLES: usize = 64;
+
+/// A source price update: (source, price, 24h volume, source timestamp in unix ms).
+type SourceUpdate = (PriceSource, f64, Option<f64>, i64);
+
 /// TPS window for calculating transactions per second (last 60 seconds).
 const TPS_WINDOW_SECS: u64 = 60;
 
+/// Link between a price cache and a cross-server fan-out bus.
+struct FanoutLink {
+    bus: Arc<PriceFanout>,
+    /// This server's ID, used to drop our own messages.
+    node_id: String,
+}
+
 /// Multi-source price aggregation cache.
 pub struct PriceCache {
     /// Price data keyed by symbol.
@@ -82,16 +141,25 @@ pub struct PriceCache {
     source_status: DashMap<PriceSource, SourceStatus>,
     /// Last error per source (separate DashMap for simpler borrowing).
     source_errors: DashMap<PriceSource, String>,
+    /// Connection state of WebSocket feeds for sources that also poll REST.
+    stream_status: DashMap<PriceSource, bool>,
     /// Start time for uptime calculation.
     start_time: Instant,
     /// Recent update timestamps for TPS calculation (last 60 seconds).
     recent_updates: Mutex<VecDeque<Instant>>,
+    /// Optional cross-server price fan-out.
+    fanout: OnceLock<FanoutLink>,
+    /// Per-source drift from consensus, used to weight aggregation.
+    reliability: SourceReliability,
+    /// Circuit breaker trips, for alerting.
+    breaker_tx: broadcast::Sender<CircuitBreakerTrip>,
 }
 
 impl PriceCache {
     /// Create a new price cache.
     pub fn new(config: AggregationConfig) -> (Arc<Self>, broadcast::Receiver<AggregatedPrice>) {
         let (tx, rx) = broadcast::channel(4096);
+        let (breaker_tx, _) = broadcast::channel(256);
         let cache = Arc::new(Self {
             prices: DashMap::new(),
             config,
@@ -102,28 +170,75 @@ impl PriceCache {
             symbol_source_updates: DashMap::new(),
             source_status: DashMap::new(),
             source_errors: DashMap::new(),
+            stream_status: DashMap::new(),
             start_time: Instant::now(),
             recent_updates: Mutex::new(VecDeque::with_capacity(10000)),
+            fanout: OnceLock::new(),
+            reliability: SourceReliability::new(),
+            breaker_tx,
         });
         (cache, rx)
     }
 
-    /// Connect to Redis for persistence.
-    pub async fn connect_redis(&self, redis_url: &str) {
-        match redis::Client::open(redis_url) {
-            Ok(client) => match ConnectionManager::new(client).await {
-                Ok(conn) => {
-                    info!("PriceCache connected to Redis at {}", redis_url);
-                    *self.redis.write().await = Some(conn);
-                }
-                Err(e) => {
-                    warn!("Failed to connect PriceCache to Redis: {}", e);
+    /// Share local source prices over `bus` and apply prices published by other servers.
+    pub fn start_fanout(self: &Arc<Self>, bus: Arc<PriceFanout>, node_id: String) {
+        let mut rx = bus.subscribe();
+        if self.fanout.set(FanoutLink { bus, node_id }).is_err() {
+            warn!("PriceCache fan-out already started");
+            return;
+        }
+
+        let cache = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match rx.recv().await {
+                    Ok(remote) => cache.apply_remote_price(remote),
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        warn!("Price fan-out lagged, skipped {} messages", n);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
                 }
-            },
-            Err(e) => {
-                warn!("Invalid Redis URL for PriceCache: {}", e);
             }
+        });
+    }
+
+    /// Apply a price published by another server.
+    /// Ignores our own messages and anything not newer than what we hold for that source.
+    fn apply_remote_price(&self, remote: FanoutPrice) {
+        let Some(link) = self.fanout.get() else { return };
+        if remote.origin == link.node_id {
+            return;
         }
+
+        let symbol_lower = remote.symbol.to_lowercase();
+        let is_newer = self
+            .prices
+            .get(&symbol_lower)
+            .and_then(|entry| {
+                entry
+                    .sources
+                    .iter()
+                    .find(|s| s.source == remote.source)
+                    .map(|s| remote.timestamp > s.timestamp)
+            })
+            .unwrap_or(true);
+        if !is_newer {
+            return;
+        }
+
+        self.apply_price(
+            &symbol_lower,
+            remote.source,
+            remote.price,
+            remote.volume_24h,
+            remote.timestamp,
+        );
+    }
+
+    /// Use a shared Redis connection for persistence.
+    pub async fn connect_redis(&self, conn: ConnectionManager) {
+        info!("PriceCache connected to Redis");
+        *self.redis.write().await = Some(conn);
     }
 
     /// Load prices from Redis.
@@ -161,6 +276,11 @@ impl PriceCache {
         self.tx.subscribe()
     }
 
+    /// Subscribe to circuit breaker trips (rejected price spikes).
+    pub fn subscribe_breaker_trips(&self) -> broadcast::Receiver<CircuitBreakerTrip> {
+        self.breaker_tx.subscribe()
+    }
+
     /// Update a price from a source.
     pub fn update_price(
         &self,
@@ -169,56 +289,94 @@ impl PriceCache {
         price: f64,
         volume_24h: Option<f64>,
     ) {
-        let now = Instant::now();
         let timestamp = chrono::Utc::now().timestamp_millis();
-        let symbol_lower = symbol.to_lowercase();
 
-        let mut entry = self.prices.entry(symbol_lower.clone()).or_default();
-        let symbol_price = entry.value_mut();
+        if let Some(link) = self.fanout.get() {
+            link.bus.publish(FanoutPrice {
+                origin: link.node_id.clone(),
+                symbol: symbol.to_lowercase(),
+                source,
+                price,
+                volume_24h,
+                timestamp,
+            });
+        }
 
-        // Check if this source's price actually changed
-        let last_source_price = symbol_price.last_source_prices.get(&source).copied();
-        let source_price_changed = match last_source_price {
-            Some(last) => (price - last).abs() > 0.0001, // Any meaningful change
-            None => true,                                // New source
-        };
+        self.apply_price(symbol, source, price, volume_24h, timestamp);
+    }
 
-        // Update source price tracking
-        symbol_price.last_source_prices.insert(source, price);
+    /// Update prices for many symbols at once.
+    ///
+    /// Updates are grouped per symbol so each symbol is locked once and at most
+    /// one aggregated price is broadcast per symbol, reflecting the whole batch.
+    /// Updates for the same symbol are applied in batch order.
+    pub fn update_prices(&self, batch: &[(&str, PriceSource, f64, Option<f64>)]) {
+        let timestamp = chrono::Utc::now().timestamp_millis();
 
-        // Only accept volume from authoritative sources (CoinMarketCap, CoinGecko)
-        // Individual exchanges only report their own volume, not market-wide 24h volume
-        if let Some(vol) = volume_24h {
-            if source.is_volume_authoritative() {
-                symbol_price.cached_volume = Some(CachedVolume { value: vol, source });
+        let mut groups: HashMap<String, Vec<SourceUpdate>> = HashMap::new();
+        for &(symbol, source, price, volume_24h) in batch {
+            let symbol_lower = symbol.to_lowercase();
+            if let Some(link) = self.fanout.get() {
+                link.bus.publish(FanoutPrice {
+                    origin: link.node_id.clone(),
+                    symbol: symbol_lower.clone(),
+                    source,
+                    price,
+                    volume_24h,
+                    timestamp,
+                });
             }
+            groups
+                .entry(symbol_lower)
+                .or_default()
+                .push((source, price, volume_24h, timestamp));
         }
 
-        // Update or add source price
-        let source_price = SourcePrice {
-            source,
-            price,
-            timestamp,
-            volume_24h,
-        };
-
-        if let Some(existing) = symbol_price.sources.iter_mut().find(|s| s.source == source) {
-            *existing = source_price;
-        } else {
-            symbol_price.sources.push(source_price);
+        for (symbol, updates) in groups {
+            self.apply_prices(&symbol, &updates);
         }
+    }
 
-        // Remove stale sources
-        let stale_threshold = timestamp - self.config.stale_threshold_ms as i64;
-        symbol_price
-            .sources
-            .retain(|s| s.timestamp > stale_threshold);
+    /// Record a source price observed at `source_timestamp` (unix ms) and broadcast
+    /// the re-aggregated price.
+    fn apply_price(
+        &self,
+        symbol: &str,
+        source: PriceSource,
+        price: f64,
+        volume_24h: Option<f64>,
+        source_timestamp: i64,
+    ) {
+        self.apply_prices(symbol, &[(source, price, volume_24h, source_timestamp)]);
+    }
 
-        // If this source's price didn't change, skip broadcasting
-        if !source_price_changed {
-            return;
+    /// Record updates for one symbol, in order, then broadcast a single
+    /// re-aggregated price if any source's price changed.
+    fn apply_prices(&self, symbol: &str, updates: &[SourceUpdate]) {
+        let now = Instant::now();
+        let timestamp = chrono::Utc::now().timestamp_millis();
+        let symbol_lower = symbol.to_lowercase();
+
+        let mut entry = self.prices.entry(symbol_lower.clone()).or_default();
+        let symbol_price = entry.value_mut();
+
+        let mut changed = Vec::new();
+        for &update in updates {
+            if self.record_source_price(&symbol_lower, symbol_price, update, timestamp) {
+                changed.push((update.0, update.3));
+            }
         }
 
+        // If no source's price changed, skip broadcasting
+        let Some(&(source, _)) = changed.last() else {
+            return;
+        };
+
+        // Calculate weighted average price; smoothing sees every change,
+        // including ones the throttle keeps from being broadcast
+        let aggregated = self.aggregate(&symbol_price.sources);
+        symbol_price.record_recent(aggregated);
+
         // Check throttle (per-symbol, not per-source)
         let elapsed_ms = now
             .duration_since(symbol_price.last_update_time)
@@ -227,9 +385,6 @@ impl PriceCache {
             return;
         }
 
-        // Calculate weighted average price
-        let aggregated = self.aggregate(&symbol_price.sources);
-
         // Get primary source (highest weight)
         let primary_source = symbol_price
             .sources
@@ -267,29 +422,39 @@ impl PriceCache {
 
         symbol_price.last_aggregated = Some(aggregated);
         symbol_price.last_update_time = now;
+        symbol_price.record_history(timestamp, aggregated);
 
         // Clone data for async Redis save
         let source_prices = symbol_price.last_source_prices.clone();
         drop(entry);
 
         // Increment update counters
-        self.total_updates.fetch_add(1, Ordering::Relaxed);
-        self.source_updates
-            .entry(source)
-            .or_insert_with(|| AtomicU64::new(0))
-            .fetch_add(1, Ordering::Relaxed);
-
-        // Increment per-symbol source counter
-        self.symbol_source_updates
+        self.total_updates
+            .fetch_add(changed.len() as u64, Ordering::Relaxed);
+        let symbol_counts = self
+            .symbol_source_updates
             .entry(symbol_lower.clone())
-            .or_default()
-            .entry(source)
-            .or_insert_with(|| AtomicU64::new(0))
-            .fetch_add(1, Ordering::Relaxed);
+            .or_default();
+        for &(source, source_timestamp) in &changed {
+            self.source_updates
+                .entry(source)
+                .or_insert_with(|| AtomicU64::new(0))
+                .fetch_add(1, Ordering::Relaxed);
+
+            // Increment per-symbol source counter
+            symbol_counts
+                .entry(source)
+                .or_insert_with(|| AtomicU64::new(0))
+                .fetch_add(1, Ordering::Relaxed);
+
+            // Mark source as online (successful update)
+            self.mark_source_online(source, source_timestamp as u64);
+        }
+        drop(symbol_counts);
 
-        // Track timestamp for TPS calculation
+        // Track timestamps for TPS calculation
         if let Ok(mut recent) = self.recent_updates.lock() {
-            recent.push_back(now);
+            recent.extend(std::iter::repeat_n(now, changed.len()));
 
             // Remove old entries outside the TPS window
             let cutoff = now - std::time::Duration::from_secs(TPS_WINDOW_SECS);
@@ -302,9 +467,6 @@ impl PriceCache {
             }
         }
 
-        // Mark source as online (successful update)
-        self.mark_source_online(source, timestamp as u64);
-
         // Broadcast update
         debug!(
             "Broadcasting {} update from {:?}: ${:.2}",
@@ -322,6 +484,166 @@ impl PriceCache {
         });
     }
 
+    /// Record one source update in a symbol's entry.
+    /// Returns true if the source's price changed (or the source is new).
+    fn record_source_price(
+        &self,
+        symbol_lower: &str,
+        symbol_price: &mut SymbolPrice,
+        update: SourceUpdate,
+        timestamp: i64,
+    ) -> bool {
+        let (source, price, volume_24h, source_timestamp) = update;
+
+        let accepted =
+            self.check_circuit_breaker(symbol_lower, symbol_price, source, price, source_timestamp);
+        if !accepted {
+            return false;
+        }
+
+        // Check if this source's price actually changed
+        let last_source_price = symbol_price.last_source_prices.get(&source).copied();
+        let source_price_changed = match last_source_price {
+            Some(last) => (price - last).abs() > 0.0001, // Any meaningful change
+            None => true,                                // New source
+        };
+
+        // Update source price tracking
+        symbol_price.last_source_prices.insert(source, price);
+
+        // Only accept volume from authoritative sources (CoinMarketCap, CoinGecko)
+        // Individual exchanges only report their own volume, not market-wide 24h volume
+        if let Some(vol) = volume_24h {
+            if source.is_volume_authoritative() {
+                symbol_price.cached_volume = Some(CachedVolume { value: vol, source });
+            }
+        }
+
+        // Update or add source price
+        let source_price = SourcePrice {
+            source,
+            price,
+            timestamp: source_timestamp,
+            volume_24h,
+        };
+
+        if let Some(existing) = symbol_price.sources.iter_mut().find(|s| s.source == source) {
+            *existing = source_price;
+        } else {
+            symbol_price.sources.push(source_price);
+        }
+
+        // Remove stale sources
+        let stale_threshold = timestamp - self.config.stale_threshold_ms as i64;
+        symbol_price
+            .sources
+            .retain(|s| s.timestamp > stale_threshold);
+
+        // Track drift from the other sources, even if this tick won't be broadcast
+        let current_prices: Vec<f64> = symbol_price.sources.iter().map(|s| s.price).collect();
+        self.reliability
+            .record(symbol_lower, source, price, &current_prices);
+
+        source_price_changed
+    }
+
+    /// Check a source update against the symbol's circuit breaker.
+    ///
+    /// A price more than `circuit_breaker_pct` away from the other sources'
+    /// recent prices is quarantined rather than applied, so one bad tick cannot
+    /// move the aggregate. If another source has a quarantined price within the
+    /// same band, the move is corroborated: the breaker clears, the other
+    /// source's held price is applied and this update goes through. Returns
+    /// false if the update was quarantined.
+    fn check_circuit_breaker(
+        &self,
+        symbol_lower: &str,
+        symbol_price: &mut SymbolPrice,
+        source: PriceSource,
+        price: f64,
+        source_timestamp: i64,
+    ) -> bool {
+        let max_pct = self.config.circuit_breaker_pct;
+        if max_pct <= 0.0 {
+            return true;
+        }
+        let window_start = source_timestamp - self.config.circuit_breaker_window_ms as i64;
+        symbol_price
+            .quarantined
+            .retain(|_, (_, at)| *at >= window_start);
+
+        // Consensus is what the other sources reported recently; with none there
+        // is nothing to compare against
+        let others: Vec<SourcePrice> = symbol_price
+            .sources
+            .iter()
+            .filter(|s| s.source != source && s.timestamp >= window_start)
+            .cloned()
+            .collect();
+        let consensus = self.aggregate(&others);
+        let deviation_pct = if consensus > 0.0 {
+            (price - consensus) / consensus * 100.0
+        } else {
+            0.0
+        };
+        if deviation_pct.abs() <= max_pct {
+            symbol_price.quarantined.remove(&source);
+            return true;
+        }
+
+        let corroborating: Vec<(PriceSource, f64, i64)> = symbol_price
+            .quarantined
+            .iter()
+            .filter(|(other, (held, _))| {
+                **other != source && ((held - price) / price * 100.0).abs() <= max_pct
+            })
+            .map(|(other, (held, at))| (*other, *held, *at))
+            .collect();
+
+        if corroborating.is_empty() {
+            symbol_price
+                .quarantined
+                .insert(source, (price, source_timestamp));
+            warn!(
+                "Circuit breaker tripped for {}: {:?} reported ${} ({:+.1}% from consensus ${:.2})",
+                symbol_lower, source, price, deviation_pct, consensus
+            );
+            let _ = self.breaker_tx.send(CircuitBreakerTrip {
+                symbol: symbol_lower.to_string(),
+                source,
+                price,
+                consensus_price: consensus,
+                deviation_pct,
+                timestamp: source_timestamp,
+            });
+            return false;
+        }
+
+        info!(
+            "Circuit breaker cleared for {}: {:?} at ${} corroborated by {:?}",
+            symbol_lower,
+            source,
+            price,
+            corroborating.iter().map(|c| c.0).collect::<Vec<_>>()
+        );
+        symbol_price.quarantined.remove(&source);
+        for (other, held, at) in corroborating {
+            symbol_price.quarantined.remove(&other);
+            symbol_price.last_source_prices.insert(other, held);
+            let held_price = SourcePrice {
+                source: other,
+                price: held,
+                timestamp: at,
+                volume_24h: None,
+            };
+            match symbol_price.sources.iter_mut().find(|s| s.source == other) {
+                Some(existing) => *existing = held_price,
+                None => symbol_price.sources.push(held_price),
+            }
+        }
+        true
+    }
+
     fn clone_for_redis(&self) -> PriceCacheRedisRef {
         PriceCacheRedisRef {
             redis: self.redis.clone(),
@@ -329,33 +651,72 @@ impl PriceCache {
     }
 
     /// Calculate weighted average price from sources.
+    /// Each source's trust weight is scaled by its reliability.
     fn aggregate(&self, sources: &[SourcePrice]) -> f64 {
         if sources.is_empty() {
             return 0.0;
         }
 
-        let mut total_weight = 0u32;
+        let mut total_weight = 0.0;
         let mut weighted_sum = 0.0;
 
         for source in sources {
-            let weight = source.source.weight();
+            let weight = source.source.weight() as f64 * self.reliability.score(source.source);
             total_weight += weight;
-            weighted_sum += source.price * weight as f64;
+            weighted_sum += source.price * weight;
         }
 
-        if total_weight == 0 {
+        if total_weight == 0.0 {
             sources[0].price
         } else {
-            weighted_sum / total_weight as f64
+            weighted_sum / total_weight
         }
     }
 
+    /// Reliability score (0-1] for each source that has been compared against
+    /// consensus, least reliable first.
+    pub fn source_reliability(&self) -> Vec<(PriceSource, f64)> {
+        self.reliability.scores()
+    }
+
     /// Get the current aggregated price for a symbol.
     pub fn get_price(&self, symbol: &str) -> Option<f64> {
         let entry = self.prices.get(&symbol.to_lowercase())?;
         entry.last_aggregated
     }
 
+    /// Average of the last `window` aggregated prices for a symbol.
+    ///
+    /// A steadier "fair" price than [`PriceCache::get_price`] for consumers that
+    /// do not want every tick. `window` is capped at [`SMOOTHING_MAX_SAMPLES`];
+    /// fewer samples are averaged while a symbol is warming up.
+    pub fn smoothed_price(&self, symbol: &str, window: usize) -> Option<f64> {
+        let entry = self.prices.get(&symbol.to_lowercase())?;
+        let n = window.min(entry.recent.len());
+        if n == 0 {
+            return None;
+        }
+        let sum: f64 = entry.recent.iter().rev().take(n).sum();
+        Some(sum / n as f64)
+    }
+
+    /// Percent change from the oldest price within `window` to the latest.
+    ///
+    /// Returns `None` if fewer than two history samples fall inside the window.
+    /// History covers at most the last four hours.
+    pub fn change_pct(&self, symbol: &str, window: std::time::Duration) -> Option<f64> {
+        let entry = self.prices.get(&symbol.to_lowercase())?;
+        let cutoff = chrono::Utc::now().timestamp_millis() - window.as_millis() as i64;
+
+        let mut in_window = entry.history.iter().filter(|(ts, _)| *ts >= cutoff);
+        let (_, oldest) = *in_window.next()?;
+        let (_, latest) = *in_window.next_back()?;
+        if oldest == 0.0 {
+            return None;
+        }
+        Some((latest - oldest) / oldest * 100.0)
+    }
+
     /// Get all current prices.
     pub fn get_all_prices(&self) -> Vec<(String, f64)> {
         self.prices
@@ -409,6 +770,25 @@ impl PriceCache {
         self.source_errors.insert(source, error.to_string());
     }
 
+    /// Record whether a source's WebSocket feed is connected.
+    ///
+    /// Kept apart from the online status reported by the source's REST poller,
+    /// so a dropped stream does not mark a working poller offline.
+    pub fn set_stream_connected(&self, source: PriceSource, connected: bool) {
+        let was_connected = self.stream_status.insert(source, connected);
+        if was_connected == Some(true) && !connected {
+            warn!("Source {:?} WebSocket feed is now DISCONNECTED", source);
+        } else if was_connected != Some(true) && connected {
+            info!("Source {:?} WebSocket feed is now CONNECTED", source);
+        }
+    }
+
+    /// Whether a source's WebSocket feed is connected, or `None` if the
+    /// source has no feed tracked separately.
+    pub fn is_stream_connected(&self, source: PriceSource) -> Option<bool> {
+        self.stream_status.get(&source).map(|s| *s)
+    }
+
     /// Check if a source is online.
     pub fn is_source_online(&self, source: PriceSource) -> bool {
         self.source_status
@@ -742,7 +1122,7 @@ impl PriceCache {
 }
 
 /// Exchange statistics for API response.
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ExchangeStats {
     pub source: PriceSource,
@@ -757,7 +1137,7 @@ pub struct ExchangeStats {
 }
 
 /// Per-symbol source statistics for API response.
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SymbolSourceStat {
     pub source: PriceSource,
@@ -767,7 +1147,7 @@ pub struct SymbolSourceStat {
 }
 
 /// Confidence metrics for a symbol.
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SymbolConfidence {
     /// Overall confidence score (0-100).
@@ -789,7 +1169,7 @@ pub struct SymbolConfidence {
 }
 
 /// Breakdown of confidence calculation factors.
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ConfidenceFactors {
     /// Score from source diversity (0-30).
@@ -838,3 +1218,290 @@ impl PriceCacheRedisRef {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn unthrottled_cache() -> Arc<PriceCache> {
+        let (cache, _rx) = PriceCache::new(AggregationConfig {
+            throttle_ms: 0,
+            ..AggregationConfig::default()
+        });
+        cache
+    }
+
+    async fn settle() {
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+
+    /// Seed a symbol's history with (age in ms, price) samples, oldest first.
+    fn seed_history(cache: &PriceCache, symbol: &str, samples: &[(i64, f64)]) {
+        let now = chrono::Utc::now().timestamp_millis();
+        let mut entry = cache.prices.entry(symbol.to_string()).or_default();
+        for (age_ms, price) in samples {
+            entry.record_history(now - age_ms, *price);
+            entry.last_aggregated = Some(*price);
+        }
+    }
+
+    #[test]
+    fn test_change_pct_up_move() {
+        let cache = unthrottled_cache();
+        seed_history(
+            &cache,
+            "btc",
+            &[(2 * 3_600_000, 80_000.0), (3_000_000, 100_000.0), (600_000, 104_000.0), (0, 110_000.0)],
+        );
+
+        // The sample from two hours ago is outside the one hour window
+        let change = cache.change_pct("BTC", Duration::from_secs(3600)).unwrap();
+        assert!((change - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_change_pct_down_move() {
+        let cache = unthrottled_cache();
+        seed_history(&cache, "eth", &[(1_800_000, 4_000.0), (0, 3_000.0)]);
+
+        let change = cache.change_pct("eth", Duration::from_secs(3600)).unwrap();
+        assert!((change + 25.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_smoothed_price_reduces_variance() {
+        let cache = unthrottled_cache();
+        assert_eq!(cache.smoothed_price("btc", 10), None);
+
+        let variance = |xs: &[f64]| {
+            let mean = xs.iter().sum::<f64>() / xs.len() as f64;
+            xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / xs.len() as f64
+        };
+
+        // A flat price with alternating +/- noise of varying size
+        let mut raw = Vec::new();
+        let mut smoothed = Vec::new();
+        for i in 0..200 {
+            let noise = ((i * 37) % 11) as f64 * if i % 2 == 0 { 1.0 } else { -1.0 };
+            let price = 100.0 + noise;
+            cache.update_price("BTC", PriceSource::Binance, price, None);
+            raw.push(cache.get_price("btc").unwrap());
+            smoothed.push(cache.smoothed_price("btc", 10).unwrap());
+        }
+
+        assert!(variance(&smoothed[20..]) < variance(&raw[20..]) / 4.0);
+        assert!((cache.smoothed_price("btc", 1).unwrap() - raw[199]).abs() < 1e-9);
+
+        // The buffer stays bounded and oversized windows average what is kept
+        assert_eq!(cache.prices.get("btc").unwrap().recent.len(), SMOOTHING_MAX_SAMPLES);
+        let tail = &raw[200 - SMOOTHING_MAX_SAMPLES..];
+        let expected = tail.iter().sum::<f64>() / tail.len() as f64;
+        assert!((cache.smoothed_price("btc", 1_000).unwrap() - expected).abs() < 1e-9);
+        assert_eq!(cache.smoothed_price("btc", 0), None);
+    }
+
+    #[tokio::test]
+    async fn test_change_pct_requires_history() {
+        let cache = unthrottled_cache();
+        assert_eq!(cache.change_pct("sol", Duration::from_secs(3600)), None);
+
+        // Two quick updates land in the same bucket, leaving a single sample
+        cache.update_price("SOL", PriceSource::Binance, 150.0, None);
+        cache.update_price("SOL", PriceSource::Binance, 151.0, None);
+        assert_eq!(cache.change_pct("sol", Duration::from_secs(3600)), None);
+
+        // Samples older than the window don't count
+        seed_history(&cache, "ada", &[(7_200_000, 0.5), (0, 0.6)]);
+        assert_eq!(cache.change_pct("ada", Duration::from_secs(60)), None);
+    }
+
+    #[tokio::test]
+    async fn test_update_prices_coalesces_per_symbol() {
+        let (cache, mut rx) = PriceCache::new(AggregationConfig {
+            throttle_ms: 0,
+            ..AggregationConfig::default()
+        });
+        let batch = [
+            ("BTC", PriceSource::Binance, 100.0, None),
+            ("ETH", PriceSource::Binance, 10.0, None),
+            ("btc", PriceSource::Coinbase, 101.0, None),
+            ("BTC", PriceSource::Binance, 102.0, None),
+        ];
+        cache.update_prices(&batch);
+
+        // One broadcast per symbol, reflecting the last price from each source
+        let mut updates = Vec::new();
+        while let Ok(update) = rx.try_recv() {
+            updates.push(update);
+        }
+        assert_eq!(updates.len(), 2);
+        let btc = updates.iter().find(|u| u.symbol == "btc").unwrap();
+        assert_eq!(btc.sources, vec![PriceSource::Binance, PriceSource::Coinbase]);
+        assert_eq!(cache.get_total_updates(), 4);
+
+        // Same final state as applying the updates one at a time
+        let individual = unthrottled_cache();
+        for &(symbol, source, price, volume) in &batch {
+            individual.update_price(symbol, source, price, volume);
+        }
+        assert_eq!(cache.get_price("btc"), individual.get_price("btc"));
+        assert_eq!(cache.get_price("eth"), Some(10.0));
+        assert_eq!(btc.price, individual.get_price("btc").unwrap());
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut symbol = SymbolPrice::default();
+        for i in 0..(HISTORY_MAX_POINTS as i64 + 100) {
+            symbol.record_history(i * HISTORY_BUCKET_MS, i as f64);
+        }
+        assert_eq!(symbol.history.len(), HISTORY_MAX_POINTS);
+        assert_eq!(symbol.history.front().unwrap().1, 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_fanout_shares_price_between_caches() {
+        let bus = PriceFanout::in_memory();
+        let node_a = unthrottled_cache();
+        let node_b = unthrottled_cache();
+        node_a.start_fanout(bus.clone(), "node-a".to_string());
+        node_b.start_fanout(bus.clone(), "node-b".to_string());
+
+        // Only node A covers this symbol
+        node_a.update_price("SOL", PriceSource::Binance, 150.0, None);
+        settle().await;
+
+        assert_eq!(node_a.get_price("sol"), Some(150.0));
+        assert_eq!(node_b.get_price("sol"), Some(150.0));
+        assert_eq!(node_b.get_sources("sol"), vec![PriceSource::Binance]);
+    }
+
+    #[tokio::test]
+    async fn test_fanout_ignores_own_and_duplicate_messages() {
+        let bus = PriceFanout::in_memory();
+        let node = unthrottled_cache();
+        node.start_fanout(bus.clone(), "node-a".to_string());
+
+        // Our own message comes back over the bus and must not be re-applied
+        node.update_price("ETH", PriceSource::Coinbase, 3000.0, None);
+        settle().await;
+        assert_eq!(node.get_total_updates(), 1);
+
+        let remote = FanoutPrice {
+            origin: "node-b".to_string(),
+            symbol: "eth".to_string(),
+            source: PriceSource::Kraken,
+            price: 3010.0,
+            volume_24h: None,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        };
+        bus.publish(remote.clone());
+        bus.publish(remote.clone());
+        settle().await;
+        assert_eq!(node.get_total_updates(), 2);
+
+        // Older prices for a source we already hold are dropped
+        bus.publish(FanoutPrice {
+            price: 2900.0,
+            timestamp: remote.timestamp - 1000,
+            ..remote
+        });
+        settle().await;
+        assert_eq!(node.get_total_updates(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_offset_source_loses_reliability_and_weight() {
+        let cache = unthrottled_cache();
+        let mut previous = 1.0;
+
+        for i in 0..40 {
+            let base = 50_000.0 + i as f64;
+            cache.update_price("BTC", PriceSource::Coinbase, base, None);
+            cache.update_price("BTC", PriceSource::Binance, base, None);
+            // Stale mapping: consistently 5% above everyone else
+            cache.update_price("BTC", PriceSource::Huobi, base * 1.05, None);
+
+            let reliability = cache.source_reliability();
+            let huobi = reliability
+                .iter()
+                .find(|(s, _)| *s == PriceSource::Huobi)
+                .map(|(_, score)| *score)
+                .unwrap();
+            assert!(huobi < previous);
+            previous = huobi;
+        }
+
+        let reliability = cache.source_reliability();
+        assert_eq!(reliability[0].0, PriceSource::Huobi);
+        assert!(previous < 0.1);
+        assert!(reliability[1..].iter().all(|(_, score)| *score == 1.0));
+
+        // Weighted by reliability, the aggregate stays close to consensus
+        let consensus = 50_039.0;
+        let price = cache.get_price("btc").unwrap();
+        assert!(
+            (price - consensus) / consensus < 0.005,
+            "aggregate {}",
+            price
+        );
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_rejects_single_source_spike() {
+        let cache = unthrottled_cache();
+        let mut trips = cache.subscribe_breaker_trips();
+        cache.update_prices(&[
+            ("btc", PriceSource::Binance, 50_000.0, None),
+            ("btc", PriceSource::Coinbase, 50_010.0, None),
+            ("btc", PriceSource::Kraken, 49_990.0, None),
+        ]);
+        let before = cache.get_price("btc").unwrap();
+
+        cache.update_price("btc", PriceSource::Kraken, 1.0, None);
+        assert_eq!(cache.get_price("btc"), Some(before));
+
+        let trip = trips.try_recv().unwrap();
+        assert_eq!(trip.symbol, "btc");
+        assert_eq!(trip.source, PriceSource::Kraken);
+        assert_eq!(trip.price, 1.0);
+        assert!(trip.deviation_pct < -99.0);
+
+        // The source is back in line once it reports a sane price
+        cache.update_price("btc", PriceSource::Kraken, 50_020.0, None);
+        assert!(cache.get_price("btc").unwrap() > 50_000.0);
+        assert!(cache.prices.get("btc").unwrap().quarantined.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_accepts_corroborated_move() {
+        let cache = unthrottled_cache();
+        let mut trips = cache.subscribe_breaker_trips();
+        cache.update_prices(&[
+            ("eth", PriceSource::Binance, 3_000.0, None),
+            ("eth", PriceSource::Coinbase, 3_000.0, None),
+            ("eth", PriceSource::Kraken, 3_000.0, None),
+        ]);
+
+        // The first source to report a crash is held back...
+        cache.update_price("eth", PriceSource::Binance, 2_000.0, None);
+        assert_eq!(cache.get_price("eth"), Some(3_000.0));
+        assert!(trips.try_recv().is_ok());
+
+        // ...until a second source confirms it, which applies both
+        cache.update_price("eth", PriceSource::Coinbase, 2_010.0, None);
+        let price = cache.get_price("eth").unwrap();
+        assert!(price < 2_500.0, "aggregate {}", price);
+        assert!(trips.try_recv().is_err());
+
+        let entry = cache.prices.get("eth").unwrap();
+        assert!(entry.quarantined.is_empty());
+        let binance = entry
+            .sources
+            .iter()
+            .find(|s| s.source == PriceSource::Binance)
+            .unwrap();
+        assert_eq!(binance.price, 2_000.0);
+    }
+}
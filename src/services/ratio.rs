@@ -0,0 +1,133 @@
+//! Synthetic ratio instruments such as ETH/BTC built from two `ChartStore` series.
+//!
+//! A ratio lets pairs traders chart one asset priced in another without a
+//! venue listing the cross directly.
+
+use std::collections::BTreeMap;
+
+use super::ChartStore;
+use crate::types::{ChartRange, OhlcPoint};
+
+/// Symbol used for the ratio of `numerator` to `denominator`, e.g. `eth/btc`.
+pub fn ratio_symbol(numerator: &str, denominator: &str) -> String {
+    format!(
+        "{}/{}",
+        numerator.to_lowercase(),
+        denominator.to_lowercase()
+    )
+}
+
+/// Compute the ratio chart for a range from the stored series.
+pub fn ratio_chart(
+    chart_store: &ChartStore,
+    numerator: &str,
+    denominator: &str,
+    range: ChartRange,
+) -> Vec<OhlcPoint> {
+    divide_series(
+        &chart_store.get_chart(numerator, range),
+        &chart_store.get_chart(denominator, range),
+    )
+}
+
+/// Divide one OHLC series by another, bucket by bucket.
+///
+/// Buckets missing from the denominator, or with a zero or negative
+/// denominator price, are skipped rather than producing an infinite ratio.
+/// Open and close divide directly. The high divides by the denominator low
+/// and the low by the denominator high, which bounds the true ratio range
+/// since the two assets need not peak in the same instant.
+pub fn divide_series(numerator: &[OhlcPoint], denominator: &[OhlcPoint]) -> Vec<OhlcPoint> {
+    let denominators: BTreeMap<i64, &OhlcPoint> = denominator.iter().map(|p| (p.time, p)).collect();
+
+    numerator
+        .iter()
+        .filter_map(|num| {
+            let den = denominators.get(&num.time)?;
+            if [den.open, den.high, den.low, den.close]
+                .iter()
+                .any(|p| *p <= 0.0)
+            {
+                return None;
+            }
+            Some(OhlcPoint {
+                time: num.time,
+                open: num.open / den.open,
+                high: num.high / den.low,
+                low: num.low / den.high,
+                close: num.close / den.close,
+                volume: None,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(time: i64, open: f64, high: f64, low: f64, close: f64) -> OhlcPoint {
+        OhlcPoint {
+            time,
+            open,
+            high,
+            low,
+            close,
+            volume: Some(1.0),
+        }
+    }
+
+    #[test]
+    fn test_divide_series_per_bucket() {
+        // The denominator is missing bucket 120 and has a zero price at 180
+        let eth = vec![
+            candle(0, 2000.0, 2200.0, 1900.0, 2100.0),
+            candle(60, 2100.0, 2100.0, 2000.0, 2000.0),
+            candle(120, 2000.0, 2000.0, 2000.0, 2000.0),
+            candle(180, 2000.0, 2000.0, 2000.0, 2000.0),
+        ];
+        let btc = vec![
+            candle(0, 40000.0, 44000.0, 40000.0, 42000.0),
+            candle(60, 42000.0, 50000.0, 40000.0, 50000.0),
+            candle(180, 0.0, 0.0, 0.0, 0.0),
+        ];
+
+        let ratio = divide_series(&eth, &btc);
+        let times: Vec<i64> = ratio.iter().map(|p| p.time).collect();
+        assert_eq!(times, vec![0, 60]);
+
+        assert!((ratio[0].open - 0.05).abs() < 1e-12);
+        assert!((ratio[0].close - 0.05).abs() < 1e-12);
+        // 2200 / 40000 and 1900 / 44000
+        assert!((ratio[0].high - 0.055).abs() < 1e-12);
+        assert!((ratio[0].low - 1900.0 / 44000.0).abs() < 1e-12);
+        assert!((ratio[1].close - 0.04).abs() < 1e-12);
+        assert!(ratio.iter().all(|p| p.low <= p.open && p.open <= p.high));
+        assert!(ratio.iter().all(|p| p.low <= p.close && p.close <= p.high));
+        assert!(ratio.iter().all(|p| p.volume.is_none()));
+    }
+
+    #[test]
+    fn test_divide_by_empty_series_is_empty() {
+        let eth = vec![candle(0, 1.0, 1.0, 1.0, 1.0)];
+        assert!(divide_series(&eth, &[]).is_empty());
+        assert!(divide_series(&[], &eth).is_empty());
+    }
+
+    #[test]
+    fn test_ratio_chart_from_store() {
+        let store = ChartStore::new();
+        let bucket = chrono::Utc::now().timestamp() / 60 * 60 - 600;
+        for (i, (eth, btc)) in [(2000.0, 40000.0), (2100.0, 35000.0)].iter().enumerate() {
+            let t_ms = (bucket + i as i64 * 60) * 1000;
+            store.add_price("eth", *eth, None, t_ms);
+            store.add_price("btc", *btc, None, t_ms);
+        }
+
+        let chart = ratio_chart(&store, "ETH", "btc", ChartRange::OneHour);
+        assert_eq!(chart.len(), 2);
+        assert!((chart[0].close - 0.05).abs() < 1e-12);
+        assert!((chart[1].close - 0.06).abs() < 1e-12);
+        assert_eq!(ratio_symbol("ETH", "btc"), "eth/btc");
+    }
+}
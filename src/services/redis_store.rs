@@ -2,18 +2,59 @@
 // Keeping this module for potential future use.
 #![allow(dead_code)]
 
-use redis::{aio::ConnectionManager, AsyncCommands, RedisResult};
+use crate::sources::backoff::Backoff;
+use crate::types::PriceSource;
+use futures_util::StreamExt;
+use redis::{
+    aio::{ConnectionManager, PubSub},
+    AsyncCommands, RedisResult,
+};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::{broadcast, RwLock};
 use tracing::{debug, error, info, warn};
 
 /// Redis key prefixes
 const SPARKLINE_PREFIX: &str = "haunt:sparkline:";
 const PRICE_PREFIX: &str = "haunt:price:";
 
+/// Redis pub/sub channel for cross-server price fan-out.
+pub const PRICE_FANOUT_CHANNEL: &str = "haunt:prices";
+
+/// Delay bounds for re-subscribing after the fan-out subscription drops.
+const FANOUT_RECONNECT_BASE: Duration = Duration::from_secs(1);
+const FANOUT_RECONNECT_MAX: Duration = Duration::from_secs(60);
+
 /// Maximum sparkline points to store per symbol
 const MAX_SPARKLINE_POINTS: usize = 3600; // 1 hour at 1-second granularity
 
+/// Open the Redis connection shared by all services.
+///
+/// `ConnectionManager` multiplexes commands over one connection and reconnects on
+/// failure, so clones can be handed to every service instead of each opening its own.
+pub async fn connect_shared(redis_url: &str) -> Option<ConnectionManager> {
+    match redis::Client::open(redis_url) {
+        Ok(client) => match ConnectionManager::new(client).await {
+            Ok(conn) => {
+                info!("Connected to Redis at {}", redis_url);
+                Some(conn)
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to connect to Redis: {}. Data will not persist.",
+                    e
+                );
+                None
+            }
+        },
+        Err(e) => {
+            warn!("Invalid Redis URL: {}. Data will not persist.", e);
+            None
+        }
+    }
+}
+
 /// RedisStore for persistent caching of price and chart data.
 #[derive(Clone)]
 pub struct RedisStore {
@@ -217,3 +258,343 @@ impl RedisStore {
         debug!("Seeded {} sparkline points for {}", prices.len(), symbol);
     }
 }
+
+/// A single source price published to other servers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FanoutPrice {
+    /// Server that observed the price.
+    pub origin: String,
+    pub symbol: String,
+    pub source: PriceSource,
+    pub price: f64,
+    pub volume_24h: Option<f64>,
+    /// Source timestamp (unix ms).
+    pub timestamp: i64,
+}
+
+/// Price fan-out bus shared between servers.
+///
+/// Published prices are delivered to every subscriber, including the publisher, so
+/// consumers are expected to drop messages carrying their own origin.
+pub struct PriceFanout {
+    tx: broadcast::Sender<FanoutPrice>,
+    redis: Option<(ConnectionManager, String)>,
+}
+
+impl PriceFanout {
+    /// Create an in-process bus (single server or tests).
+    pub fn in_memory() -> Arc<Self> {
+        let (tx, _) = broadcast::channel(4096);
+        Arc::new(Self { tx, redis: None })
+    }
+
+    /// Create a bus backed by Redis pub/sub on `channel`.
+    ///
+    /// Prices are published over the shared `conn`. A subscribed connection cannot
+    /// issue other commands, so the subscription opens one dedicated connection.
+    pub async fn connect_redis(
+        conn: ConnectionManager,
+        redis_url: &str,
+        channel: &str,
+    ) -> RedisResult<Arc<Self>> {
+        let client = redis::Client::open(redis_url)?;
+        let pubsub = Self::subscribe_redis(&client, channel).await?;
+
+        let (tx, _) = broadcast::channel(4096);
+        let fanout = Arc::new(Self {
+            tx: tx.clone(),
+            redis: Some((conn, channel.to_string())),
+        });
+
+        info!("Price fan-out subscribed to Redis channel {}", channel);
+
+        // The pub/sub connection is dedicated, so a Redis restart ends the
+        // subscription; reopen it rather than silently stop fanning out
+        let channel = channel.to_string();
+        tokio::spawn(async move {
+            let mut backoff = Backoff::new(FANOUT_RECONNECT_BASE, FANOUT_RECONNECT_MAX);
+            let mut pubsub = Some(pubsub);
+            loop {
+                if let Some(pubsub) = pubsub.take() {
+                    Self::forward_messages(pubsub, &tx).await;
+                    warn!("Price fan-out subscription closed, reconnecting...");
+                }
+                tokio::time::sleep(backoff.next_delay()).await;
+                match Self::subscribe_redis(&client, &channel).await {
+                    Ok(resubscribed) => {
+                        info!("Price fan-out resubscribed to Redis channel {}", channel);
+                        backoff.reset();
+                        pubsub = Some(resubscribed);
+                    }
+                    Err(e) => warn!("Price fan-out resubscribe failed: {}", e),
+                }
+            }
+        });
+
+        Ok(fanout)
+    }
+
+    /// Open a dedicated pub/sub connection subscribed to `channel`.
+    async fn subscribe_redis(client: &redis::Client, channel: &str) -> RedisResult<PubSub> {
+        let mut pubsub = client.get_async_connection().await?.into_pubsub();
+        pubsub.subscribe(channel).await?;
+        Ok(pubsub)
+    }
+
+    /// Forward fan-out messages to local subscribers until the subscription closes.
+    async fn forward_messages(mut pubsub: PubSub, tx: &broadcast::Sender<FanoutPrice>) {
+        let mut messages = pubsub.on_message();
+        while let Some(msg) = messages.next().await {
+            let Ok(payload) = msg.get_payload::<String>() else {
+                continue;
+            };
+            match serde_json::from_str::<FanoutPrice>(&payload) {
+                Ok(price) => {
+                    let _ = tx.send(price);
+                }
+                Err(e) => debug!("Ignoring malformed fan-out message: {}", e),
+            }
+        }
+    }
+
+    /// Publish a price to all subscribers.
+    pub fn publish(&self, price: FanoutPrice) {
+        match &self.redis {
+            Some((conn, channel)) => {
+                let Ok(payload) = serde_json::to_string(&price) else {
+                    return;
+                };
+                let mut conn = conn.clone();
+                let channel = channel.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = conn.publish::<_, _, ()>(&channel, payload).await {
+                        debug!("Failed to publish price fan-out: {}", e);
+                    }
+                });
+            }
+            None => {
+                let _ = self.tx.send(price);
+            }
+        }
+    }
+
+    /// Subscribe to prices published by any server.
+    pub fn subscribe(&self) -> broadcast::Receiver<FanoutPrice> {
+        self.tx.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::{AccuracyStore, ChartStore, PredictionStore, PriceCache};
+    use crate::types::AggregationConfig;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::tcp::OwnedReadHalf;
+    use tokio::net::TcpListener;
+
+    /// Read a RESP length line such as `*3` or `$5`.
+    async fn read_length(reader: &mut BufReader<OwnedReadHalf>, prefix: char) -> Option<usize> {
+        let mut line = String::new();
+        reader.read_line(&mut line).await.ok()?;
+        line.trim_end().strip_prefix(prefix)?.parse().ok()
+    }
+
+    /// Read one command, returning its uppercased name.
+    ///
+    /// Commands arrive as `*<n>` followed by n `$<len>` bulk strings.
+    async fn read_command(reader: &mut BufReader<OwnedReadHalf>) -> Option<String> {
+        let n = read_length(reader, '*').await?;
+        let mut name = None;
+        for _ in 0..n {
+            let len = read_length(reader, '$').await?;
+            let mut arg = vec![0; len + 2];
+            reader.read_exact(&mut arg).await.ok()?;
+            arg.truncate(len);
+            name.get_or_insert(String::from_utf8_lossy(&arg).to_uppercase());
+        }
+        name
+    }
+
+    /// Minimal RESP server that answers `+OK` to every command, counting
+    /// accepted connections and recording command names.
+    async fn fake_redis() -> (String, Arc<AtomicUsize>, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("redis://{}", listener.local_addr().unwrap());
+        let connections = Arc::new(AtomicUsize::new(0));
+        let commands = Arc::new(Mutex::new(Vec::new()));
+
+        let (accepted, seen) = (connections.clone(), commands.clone());
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                accepted.fetch_add(1, Ordering::SeqCst);
+                let seen = seen.clone();
+                tokio::spawn(async move {
+                    let (read, mut write) = socket.into_split();
+                    let mut reader = BufReader::new(read);
+                    while let Some(name) = read_command(&mut reader).await {
+                        seen.lock().unwrap().push(name);
+                        if write.write_all(b"+OK\r\n").await.is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+
+        (url, connections, commands)
+    }
+
+    #[tokio::test]
+    async fn test_services_and_fanout_reuse_shared_connection() {
+        let (url, connections, commands) = fake_redis().await;
+        let shared = connect_shared(&url).await.expect("fake Redis not available");
+
+        let chart_store = ChartStore::new();
+        let (price_cache, _rx) = PriceCache::new(AggregationConfig::default());
+        let prediction_store = PredictionStore::new();
+        let accuracy_store = AccuracyStore::new();
+        chart_store.connect_redis(shared.clone()).await;
+        price_cache.connect_redis(shared.clone()).await;
+        prediction_store.connect_redis(shared.clone()).await;
+        accuracy_store.connect_redis(shared.clone()).await;
+        let fanout = PriceFanout::connect_redis(shared.clone(), &url, PRICE_FANOUT_CHANNEL)
+            .await
+            .unwrap();
+
+        chart_store.add_price("sharedtest", 1.0, None, chrono::Utc::now().timestamp_millis());
+        chart_store.save_to_redis("sharedtest").await;
+        fanout.publish(FanoutPrice {
+            origin: "test".to_string(),
+            symbol: "btc".to_string(),
+            source: PriceSource::Coinbase,
+            price: 1.0,
+            volume_24h: None,
+            timestamp: 0,
+        });
+
+        // Publishing is spawned, so wait for it to reach the server
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while !commands.lock().unwrap().iter().any(|c| c == "PUBLISH") {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("price was never published");
+
+        // The shared connection plus the dedicated pub/sub subscription
+        assert_eq!(connections.load(Ordering::SeqCst), 2);
+        assert!(commands.lock().unwrap().iter().any(|c| c == "SUBSCRIBE"));
+    }
+
+    #[tokio::test]
+    async fn test_fanout_resubscribes_after_disconnect() {
+        let price = FanoutPrice {
+            origin: "peer".to_string(),
+            symbol: "btc".to_string(),
+            source: PriceSource::Coinbase,
+            price: 1.0,
+            volume_24h: None,
+            timestamp: 0,
+        };
+        let payload = serde_json::to_string(&price).unwrap();
+        let message = format!(
+            "*3\r\n$7\r\nmessage\r\n${}\r\n{}\r\n${}\r\n{}\r\n",
+            PRICE_FANOUT_CHANNEL.len(),
+            PRICE_FANOUT_CHANNEL,
+            payload.len(),
+            payload
+        );
+
+        // Closes the first subscription once acknowledged, then keeps
+        // publishing the price to the next one
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("redis://{}", listener.local_addr().unwrap());
+        let subscriptions = Arc::new(AtomicUsize::new(0));
+        let counted = subscriptions.clone();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let (counted, message) = (counted.clone(), message.clone());
+                tokio::spawn(async move {
+                    let (read, mut write) = socket.into_split();
+                    let mut reader = BufReader::new(read);
+                    while let Some(name) = read_command(&mut reader).await {
+                        if write.write_all(b"+OK\r\n").await.is_err() {
+                            return;
+                        }
+                        if name != "SUBSCRIBE" {
+                            continue;
+                        }
+                        if counted.fetch_add(1, Ordering::SeqCst) == 0 {
+                            return;
+                        }
+                        loop {
+                            tokio::time::sleep(Duration::from_millis(50)).await;
+                            if write.write_all(message.as_bytes()).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                });
+            }
+        });
+
+        let shared = connect_shared(&url).await.expect("fake Redis not available");
+        let fanout = PriceFanout::connect_redis(shared, &url, PRICE_FANOUT_CHANNEL)
+            .await
+            .unwrap();
+        let mut rx = fanout.subscribe();
+        let received = tokio::time::timeout(Duration::from_secs(10), rx.recv())
+            .await
+            .expect("fan-out never delivered a price")
+            .unwrap();
+        assert_eq!(received.origin, "peer");
+        assert_eq!(subscriptions.load(Ordering::SeqCst), 2);
+    }
+
+    async fn connected_clients(conn: &mut ConnectionManager) -> usize {
+        let info: String = redis::cmd("INFO")
+            .arg("clients")
+            .query_async(conn)
+            .await
+            .unwrap();
+        info.lines()
+            .find_map(|l| l.strip_prefix("connected_clients:"))
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap()
+    }
+
+    /// Requires a running Redis server (REDIS_URL, default redis://127.0.0.1:6379).
+    #[tokio::test]
+    #[ignore]
+    async fn test_services_share_one_connection() {
+        let url =
+            std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+        let mut shared = connect_shared(&url).await.expect("Redis not available");
+        let before = connected_clients(&mut shared).await;
+
+        let chart_store = ChartStore::new();
+        let (price_cache, _rx) = PriceCache::new(AggregationConfig::default());
+        let prediction_store = PredictionStore::new();
+        let accuracy_store = AccuracyStore::new();
+        chart_store.connect_redis(shared.clone()).await;
+        price_cache.connect_redis(shared.clone()).await;
+        prediction_store.connect_redis(shared.clone()).await;
+        accuracy_store.connect_redis(shared.clone()).await;
+
+        chart_store.add_price("sharedtest", 1.0, None, chrono::Utc::now().timestamp_millis());
+        chart_store.save_to_redis("sharedtest").await;
+        price_cache.save_update_counts().await;
+        accuracy_store.load_all_from_redis().await;
+
+        assert_eq!(connected_clients(&mut shared).await, before);
+
+        let _ = redis::cmd("DEL")
+            .arg(format!("{}sharedtest", SPARKLINE_PREFIX))
+            .query_async::<_, ()>(&mut shared)
+            .await;
+    }
+}
@@ -0,0 +1,197 @@
+//! Graceful shutdown coordination.
+//!
+//! Background tasks are spawned through [`Shutdown::spawn`] so they stop at
+//! their next await point once shutdown is triggered. Final-save hooks are
+//! registered up front and run once the HTTP server has drained.
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures_util::future::BoxFuture;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// Upper bound on how long a single shutdown hook may run.
+const HOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+type Hook = Box<dyn FnOnce() -> BoxFuture<'static, ()> + Send>;
+
+/// Shared shutdown signal plus the hooks to run once it fires.
+pub struct Shutdown {
+    tx: watch::Sender<bool>,
+    hooks: Mutex<Vec<(String, Hook)>>,
+}
+
+impl Shutdown {
+    /// Create a coordinator that has not been triggered yet.
+    pub fn new() -> Arc<Self> {
+        let (tx, _) = watch::channel(false);
+        Arc::new(Self {
+            tx,
+            hooks: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Signal shutdown. Safe to call more than once.
+    pub fn trigger(&self) {
+        self.tx.send_replace(true);
+    }
+
+    /// Whether shutdown has been signalled.
+    pub fn is_triggered(&self) -> bool {
+        *self.tx.borrow()
+    }
+
+    /// Resolve once shutdown has been signalled.
+    pub async fn wait(&self) {
+        let mut rx = self.tx.subscribe();
+        // The sender lives in `self`, so this can only end by the value flipping
+        let _ = rx.wait_for(|triggered| *triggered).await;
+    }
+
+    /// Spawn a background task that is cancelled when shutdown is signalled.
+    pub fn spawn<F>(&self, task: F) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let mut rx = self.tx.subscribe();
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = task => {}
+                _ = rx.wait_for(|triggered| *triggered) => {}
+            }
+        })
+    }
+
+    /// Register a hook to run after the server has drained. Hooks run in
+    /// registration order.
+    pub fn on_shutdown<F, Fut>(&self, name: &str, hook: F)
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let hook: Hook = Box::new(move || Box::pin(hook()));
+        self.hooks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push((name.to_string(), hook));
+    }
+
+    /// Run every registered hook once, each bounded by a timeout so one stuck
+    /// save cannot hold up the rest. Returns the number that completed.
+    pub async fn run_hooks(&self) -> usize {
+        let hooks = std::mem::take(&mut *self.hooks.lock().unwrap_or_else(|e| e.into_inner()));
+        let mut completed = 0;
+        for (name, hook) in hooks {
+            match tokio::time::timeout(HOOK_TIMEOUT, hook()).await {
+                Ok(()) => {
+                    info!("Shutdown hook '{}' finished", name);
+                    completed += 1;
+                }
+                Err(_) => warn!(
+                    "Shutdown hook '{}' timed out after {:?}",
+                    name, HOOK_TIMEOUT
+                ),
+            }
+        }
+        completed
+    }
+}
+
+/// Resolve when the process receives Ctrl-C or SIGTERM.
+pub async fn wait_for_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!("Failed to listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut term) => {
+                term.recv().await;
+            }
+            Err(e) => {
+                warn!("Failed to install SIGTERM handler: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_hooks_run_in_order_on_shutdown() {
+        let shutdown = Shutdown::new();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        for name in ["charts", "prices"] {
+            let calls = calls.clone();
+            shutdown.on_shutdown(name, move || async move {
+                calls.lock().unwrap().push(name);
+            });
+        }
+
+        let waiter = {
+            let shutdown = shutdown.clone();
+            tokio::spawn(async move {
+                shutdown.wait().await;
+                shutdown.run_hooks().await
+            })
+        };
+
+        tokio::task::yield_now().await;
+        assert!(calls.lock().unwrap().is_empty());
+
+        shutdown.trigger();
+        assert_eq!(waiter.await.unwrap(), 2);
+        assert_eq!(*calls.lock().unwrap(), vec!["charts", "prices"]);
+
+        // Hooks are consumed by the first run
+        assert_eq!(shutdown.run_hooks().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_spawned_tasks_cancelled_on_trigger() {
+        let shutdown = Shutdown::new();
+        let ticks = Arc::new(AtomicUsize::new(0));
+
+        let handle = {
+            let ticks = ticks.clone();
+            shutdown.spawn(async move {
+                loop {
+                    ticks.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                }
+            })
+        };
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!shutdown.is_triggered());
+        shutdown.trigger();
+        assert!(shutdown.is_triggered());
+
+        tokio::time::timeout(Duration::from_secs(1), handle)
+            .await
+            .expect("task should stop once shutdown is triggered")
+            .unwrap();
+        assert!(ticks.load(Ordering::SeqCst) > 0);
+    }
+}
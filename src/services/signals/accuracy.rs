@@ -5,7 +5,7 @@ use dashmap::DashMap;
 use redis::{aio::ConnectionManager, AsyncCommands};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::{debug, info, warn};
+use tracing::{debug, info};
 
 /// Redis key prefix for accuracy stats.
 const REDIS_ACCURACY_PREFIX: &str = "haunt:accuracy:";
@@ -30,22 +30,10 @@ impl AccuracyStore {
         })
     }
 
-    /// Connect to Redis for persistence.
-    pub async fn connect_redis(&self, redis_url: &str) {
-        match redis::Client::open(redis_url) {
-            Ok(client) => match ConnectionManager::new(client).await {
-                Ok(conn) => {
-                    info!("AccuracyStore connected to Redis");
-                    *self.redis.write().await = Some(conn);
-                }
-                Err(e) => {
-                    warn!("Failed to connect AccuracyStore to Redis: {}", e);
-                }
-            },
-            Err(e) => {
-                warn!("Invalid Redis URL for AccuracyStore: {}", e);
-            }
-        }
+    /// Use a shared Redis connection for persistence.
+    pub async fn connect_redis(&self, conn: ConnectionManager) {
+        info!("AccuracyStore connected to Redis");
+        *self.redis.write().await = Some(conn);
     }
 
     /// Record a prediction outcome.
@@ -0,0 +1,191 @@
+//! Fibonacci retracement and extension levels.
+
+use crate::services::ChartStore;
+use crate::types::{ChartRange, FibLevel, FibonacciLevels, OhlcPoint, SymbolFibonacci};
+
+/// Standard retracement ratios.
+pub const RETRACEMENT_RATIOS: [f64; 5] = [0.236, 0.382, 0.5, 0.618, 0.786];
+
+/// Standard extension ratios.
+pub const EXTENSION_RATIOS: [f64; 4] = [1.272, 1.618, 2.0, 2.618];
+
+/// Calculate Fibonacci levels for a swing between `high` and `low`.
+///
+/// In an uptrend (low then high) retracements are measured down from the high
+/// and extensions project above it: high - r * range and low + e * range.
+/// A downtrend mirrors this from the low. A flat swing (high == low) yields
+/// every level at that price.
+pub fn calculate_levels(high: f64, low: f64, uptrend: bool) -> FibonacciLevels {
+    let range = high - low;
+    let level = |ratio: f64, price: f64| FibLevel { ratio, price };
+
+    let (retracements, extensions) = if uptrend {
+        (
+            RETRACEMENT_RATIOS
+                .iter()
+                .map(|&r| level(r, high - r * range))
+                .collect(),
+            EXTENSION_RATIOS
+                .iter()
+                .map(|&e| level(e, low + e * range))
+                .collect(),
+        )
+    } else {
+        (
+            RETRACEMENT_RATIOS
+                .iter()
+                .map(|&r| level(r, low + r * range))
+                .collect(),
+            EXTENSION_RATIOS
+                .iter()
+                .map(|&e| level(e, high - e * range))
+                .collect(),
+        )
+    };
+
+    FibonacciLevels {
+        swing_high: high,
+        swing_low: low,
+        uptrend,
+        retracements,
+        extensions,
+    }
+}
+
+/// Find the swing high and low candles in `candles`.
+///
+/// Returns the indices of the highest high and lowest low; ties keep the
+/// earliest candle. Returns None for an empty slice.
+pub fn detect_swing(candles: &[OhlcPoint]) -> Option<(usize, usize)> {
+    let first = candles.first()?;
+    let (mut high_idx, mut low_idx) = (0, 0);
+    let (mut high, mut low) = (first.high, first.low);
+
+    for (i, candle) in candles.iter().enumerate().skip(1) {
+        if candle.high > high {
+            high = candle.high;
+            high_idx = i;
+        }
+        if candle.low < low {
+            low = candle.low;
+            low_idx = i;
+        }
+    }
+
+    Some((high_idx, low_idx))
+}
+
+/// Compute Fibonacci levels for a symbol from the swing over `range`.
+///
+/// Returns None if there is no chart data for the range.
+pub fn symbol_fibonacci(
+    chart_store: &ChartStore,
+    symbol: &str,
+    range: ChartRange,
+) -> Option<SymbolFibonacci> {
+    let candles = chart_store.get_chart(&symbol.to_lowercase(), range);
+    let (high_idx, low_idx) = detect_swing(&candles)?;
+    let (high, low) = (&candles[high_idx], &candles[low_idx]);
+
+    Some(SymbolFibonacci {
+        symbol: symbol.to_uppercase(),
+        range,
+        high_time: high.time,
+        low_time: low.time,
+        levels: calculate_levels(high.high, low.low, low_idx <= high_idx),
+        timestamp: chrono::Utc::now().timestamp_millis(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    fn candle(time: i64, high: f64, low: f64) -> OhlcPoint {
+        OhlcPoint {
+            time,
+            open: (high + low) / 2.0,
+            high,
+            low,
+            close: (high + low) / 2.0,
+            volume: None,
+        }
+    }
+
+    #[test]
+    fn test_levels_uptrend() {
+        let levels = calculate_levels(200.0, 100.0, true);
+        let prices: Vec<f64> = levels.retracements.iter().map(|l| l.price).collect();
+        for (actual, expected) in prices.iter().zip([176.4, 161.8, 150.0, 138.2, 121.4]) {
+            assert_close(*actual, expected);
+        }
+        let prices: Vec<f64> = levels.extensions.iter().map(|l| l.price).collect();
+        for (actual, expected) in prices.iter().zip([227.2, 261.8, 300.0, 361.8]) {
+            assert_close(*actual, expected);
+        }
+        assert_eq!(levels.retracements[3].ratio, 0.618);
+    }
+
+    #[test]
+    fn test_levels_downtrend() {
+        let levels = calculate_levels(200.0, 100.0, false);
+        assert_close(levels.retracements[0].price, 123.6);
+        assert_close(levels.retracements[2].price, 150.0);
+        assert_close(levels.retracements[4].price, 178.6);
+        assert_close(levels.extensions[1].price, 38.2);
+        assert!(!levels.uptrend);
+    }
+
+    #[test]
+    fn test_levels_flat_range() {
+        let levels = calculate_levels(100.0, 100.0, true);
+        assert!(levels
+            .retracements
+            .iter()
+            .chain(&levels.extensions)
+            .all(|l| l.price == 100.0));
+    }
+
+    #[test]
+    fn test_detect_swing() {
+        let candles = vec![
+            candle(0, 105.0, 95.0),
+            candle(60, 102.0, 90.0),
+            candle(120, 120.0, 100.0),
+            candle(180, 115.0, 108.0),
+            candle(240, 120.0, 110.0),
+        ];
+        // Lowest low at 1, highest high first reached at 2
+        assert_eq!(detect_swing(&candles), Some((2, 1)));
+        assert_eq!(detect_swing(&[]), None);
+    }
+
+    #[test]
+    fn test_symbol_fibonacci_detects_swing_from_chart() {
+        let store = ChartStore::default();
+        let start = (chrono::Utc::now().timestamp() - 1800) / 60 * 60;
+        for (i, price) in [150.0, 120.0, 100.0, 140.0, 180.0, 200.0, 190.0]
+            .into_iter()
+            .enumerate()
+        {
+            store.add_price("ETH", price, None, (start + i as i64 * 60) * 1000);
+        }
+
+        let fib = symbol_fibonacci(&store, "eth", ChartRange::OneHour).unwrap();
+        assert_eq!(fib.symbol, "ETH");
+        assert_eq!(fib.low_time, start + 120);
+        assert_eq!(fib.high_time, start + 300);
+        assert_eq!(fib.levels, calculate_levels(200.0, 100.0, true));
+
+        assert!(symbol_fibonacci(&store, "nope", ChartRange::OneHour).is_none());
+    }
+}
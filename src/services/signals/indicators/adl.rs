@@ -0,0 +1,207 @@
+//! Accumulation/Distribution Line (ADL) indicator.
+
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// A/D Line (Accumulation/Distribution Line) indicator.
+///
+/// Cumulative money flow volume based on where each close falls in its range:
+/// - CLV = ((Close - Low) - (High - Close)) / (High - Low)
+/// - ADL += CLV * Volume
+///
+/// Signals based on the recent ADL slope, with extra weight when the
+/// ADL diverges from price (e.g. price rising while ADL falls is bearish).
+/// Requires volume on every candle.
+pub struct Adl {
+    lookback: usize,
+}
+
+impl Default for Adl {
+    fn default() -> Self {
+        Self { lookback: 14 }
+    }
+}
+
+impl Adl {
+    /// Close location value in [-1, 1]; 0 for a zero-range candle.
+    fn close_location_value(candle: &OhlcPoint) -> f64 {
+        let range = candle.high - candle.low;
+        if range == 0.0 {
+            return 0.0;
+        }
+        ((candle.close - candle.low) - (candle.high - candle.close)) / range
+    }
+
+    /// Calculate the ADL value after each candle, or None if any volume is missing.
+    pub fn adl_series(candles: &[OhlcPoint]) -> Option<Vec<f64>> {
+        let mut adl = 0.0;
+        candles
+            .iter()
+            .map(|candle| {
+                adl += Self::close_location_value(candle) * candle.volume?;
+                Some(adl)
+            })
+            .collect()
+    }
+}
+
+impl Signal for Adl {
+    fn id(&self) -> &str {
+        "adl"
+    }
+
+    fn name(&self) -> &str {
+        "A/D Line"
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Volume
+    }
+
+    fn min_periods(&self) -> usize {
+        self.lookback + 1
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if candles.len() < self.min_periods() {
+            return None;
+        }
+
+        let adl = Self::adl_series(candles)?;
+        let start = candles.len() - 1 - self.lookback;
+        let current = *adl.last()?;
+
+        // Normalize the ADL change by volume traded over the lookback, giving [-1, 1]
+        let volume: f64 = candles[start + 1..].iter().filter_map(|c| c.volume).sum();
+        if volume <= 0.0 {
+            return None;
+        }
+        let slope = (current - adl[start]) / volume;
+
+        // Without a positive starting price there is no meaningful price change
+        let start_price = candles[start].close;
+        let price_change = if start_price > 0.0 {
+            (candles.last()?.close - start_price) / start_price
+        } else {
+            0.0
+        };
+
+        // Base score from ADL slope
+        let mut score = slope * 100.0;
+
+        // Divergence: price moving meaningfully against the ADL
+        let diverging = price_change.abs() > 0.01
+            && slope != 0.0
+            && price_change.signum() != slope.signum();
+        if diverging {
+            score += slope.signum() * 30.0;
+        }
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            current,
+            clamp_score(score),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rising closes; `close_near_high` controls where each close sits in its range.
+    fn create_rising_candles(count: usize, close_near_high: bool) -> Vec<OhlcPoint> {
+        (0..count)
+            .map(|i| {
+                let close = 100.0 + i as f64;
+                let (high, low) = if close_near_high {
+                    (close + 0.2, close - 2.0)
+                } else {
+                    (close + 2.0, close - 0.2)
+                };
+                OhlcPoint {
+                    time: 1000000 + i as i64 * 60000,
+                    open: close - 0.5,
+                    high,
+                    low,
+                    close,
+                    volume: Some(1000.0),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_adl_id_and_name() {
+        let adl = Adl::default();
+        assert_eq!(adl.id(), "adl");
+        assert_eq!(adl.name(), "A/D Line");
+        assert_eq!(adl.category(), SignalCategory::Volume);
+        assert_eq!(adl.min_periods(), 15);
+    }
+
+    #[test]
+    fn test_adl_close_location_value() {
+        let candle = OhlcPoint {
+            time: 0,
+            open: 10.0,
+            high: 12.0,
+            low: 8.0,
+            close: 11.0,
+            volume: Some(100.0),
+        };
+        assert_eq!(Adl::close_location_value(&candle), 0.5);
+
+        let flat = OhlcPoint {
+            high: 10.0,
+            low: 10.0,
+            close: 10.0,
+            ..candle
+        };
+        assert_eq!(Adl::close_location_value(&flat), 0.0);
+    }
+
+    #[test]
+    fn test_adl_confirmed_uptrend_is_bullish() {
+        let candles = create_rising_candles(30, true);
+        let output = Adl::default().calculate(&candles).unwrap();
+        assert!(output.value > 0.0);
+        assert!(output.score > 0);
+    }
+
+    #[test]
+    fn test_adl_bearish_divergence_reads_negative() {
+        // Price rises, but every close sits near the low so the ADL falls
+        let candles = create_rising_candles(30, false);
+        let output = Adl::default().calculate(&candles).unwrap();
+        assert!(output.value < 0.0);
+        assert!(
+            output.score <= -80,
+            "bearish divergence should score strongly negative, got {}",
+            output.score
+        );
+    }
+
+    #[test]
+    fn test_adl_requires_volume() {
+        let mut candles = create_rising_candles(30, true);
+        candles[3].volume = None;
+        assert!(Adl::default().calculate(&candles).is_none());
+    }
+
+    #[test]
+    fn test_adl_zero_start_price_skips_divergence() {
+        // Bearish ADL under rising prices would normally add a divergence penalty
+        let mut candles = create_rising_candles(30, false);
+        let start = candles.len() - Adl::default().min_periods();
+
+        candles[start].close = 0.001;
+        let tiny_start = Adl::default().calculate(&candles).unwrap();
+        candles[start].close = 0.0;
+        let zero_start = Adl::default().calculate(&candles).unwrap();
+
+        assert!(tiny_start.score < 0);
+        assert!(zero_start.score > tiny_start.score);
+    }
+}
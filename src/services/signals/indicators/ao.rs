@@ -0,0 +1,228 @@
+//! Awesome Oscillator (AO) indicator.
+
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// Number of recent AO bars searched for a twin peaks pattern.
+const TWIN_PEAKS_WINDOW: usize = 20;
+
+/// Awesome Oscillator (Bill Williams).
+///
+/// Momentum as the difference between a fast and slow SMA of median price:
+/// AO = SMA5((High + Low) / 2) - SMA34((High + Low) / 2)
+///
+/// Signals:
+/// - Zero-line crossover: momentum shift in the direction of the cross
+/// - Twin peaks below zero with a higher second trough: bullish
+/// - Twin peaks above zero with a lower second peak: bearish
+///
+/// The output value is the latest AO histogram bar.
+pub struct Ao {
+    fast: usize,
+    slow: usize,
+}
+
+impl Default for Ao {
+    fn default() -> Self {
+        Self { fast: 5, slow: 34 }
+    }
+}
+
+impl Ao {
+    /// Calculate the AO histogram for every bar with a full slow window.
+    fn ao_series(candles: &[OhlcPoint], fast: usize, slow: usize) -> Vec<f64> {
+        if fast == 0 || slow < fast || candles.len() < slow {
+            return Vec::new();
+        }
+
+        let median: Vec<f64> = candles.iter().map(|c| (c.high + c.low) / 2.0).collect();
+        let sma = |end: usize, period: usize| -> f64 {
+            median[end + 1 - period..=end].iter().sum::<f64>() / period as f64
+        };
+
+        (slow - 1..median.len())
+            .map(|end| sma(end, fast) - sma(end, slow))
+            .collect()
+    }
+
+    /// Detect a twin peaks pattern in the recent AO histogram.
+    ///
+    /// Returns 1.0 for bullish (two troughs below zero, the second higher, and
+    /// the latest bar rising), -1.0 for the bearish mirror, and 0.0 otherwise.
+    fn twin_peaks(ao: &[f64]) -> f64 {
+        if ao.len() < 5 {
+            return 0.0;
+        }
+        let recent = &ao[ao.len().saturating_sub(TWIN_PEAKS_WINDOW)..];
+        let last = recent.len() - 1;
+        let rising = recent[last] > recent[last - 1];
+
+        // Both patterns require the histogram to stay on one side of zero
+        for side in [-1.0, 1.0] {
+            let same_side_from = recent
+                .iter()
+                .rposition(|v| v * side <= 0.0)
+                .map_or(0, |i| i + 1);
+            let segment = &recent[same_side_from..];
+            if segment.len() < 5 {
+                continue;
+            }
+
+            // Extremes (troughs below zero, peaks above) within the segment
+            let extremes: Vec<f64> = segment
+                .windows(3)
+                .filter(|w| w[1] * side > w[0] * side && w[1] * side >= w[2] * side)
+                .map(|w| w[1])
+                .collect();
+            if extremes.len() < 2 {
+                continue;
+            }
+
+            let (first, second) = (extremes[extremes.len() - 2], extremes[extremes.len() - 1]);
+            // Bullish: below zero, second trough shallower, turning up
+            if side < 0.0 && second > first && rising {
+                return 1.0;
+            }
+            // Bearish: above zero, second peak lower, turning down
+            if side > 0.0 && second < first && !rising {
+                return -1.0;
+            }
+        }
+
+        0.0
+    }
+}
+
+impl Signal for Ao {
+    fn id(&self) -> &str {
+        "ao"
+    }
+
+    fn name(&self) -> &str {
+        "Awesome Oscillator"
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Momentum
+    }
+
+    fn min_periods(&self) -> usize {
+        self.slow
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if candles.len() < self.min_periods() {
+            return None;
+        }
+
+        let ao = Self::ao_series(candles, self.fast, self.slow);
+        let current = *ao.last()?;
+        let price = candles.last()?.close;
+        if price <= 0.0 {
+            return None;
+        }
+
+        // Base score from AO as a percentage of price (4% = full signal)
+        let mut score = current / price * 100.0 * 25.0;
+
+        // Zero-line crossover on the latest bar
+        if ao.len() >= 2 {
+            let previous = ao[ao.len() - 2];
+            if previous <= 0.0 && current > 0.0 {
+                score += 60.0;
+            } else if previous >= 0.0 && current < 0.0 {
+                score -= 60.0;
+            }
+        }
+
+        score += Self::twin_peaks(&ao) * 40.0;
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            current,
+            clamp_score(score),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candles_from_closes(closes: &[f64]) -> Vec<OhlcPoint> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| OhlcPoint {
+                time: 1000000 + i as i64 * 60000,
+                open: close,
+                high: close + 1.0,
+                low: close - 1.0,
+                close,
+                volume: Some(1000.0),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ao_id_and_name() {
+        let ao = Ao::default();
+        assert_eq!(ao.id(), "ao");
+        assert_eq!(ao.name(), "Awesome Oscillator");
+        assert_eq!(ao.category(), SignalCategory::Momentum);
+        assert_eq!(ao.min_periods(), 34);
+    }
+
+    #[test]
+    fn test_ao_insufficient_data() {
+        let candles = candles_from_closes(&[100.0; 33]);
+        assert!(Ao::default().calculate(&candles).is_none());
+        assert!(Ao::default().calculate(&candles_from_closes(&[100.0; 34])).is_some());
+    }
+
+    #[test]
+    fn test_ao_positive_on_accelerating_uptrend() {
+        let closes: Vec<f64> = (0..60).map(|i| 100.0 + 0.05 * (i * i) as f64).collect();
+        let output = Ao::default().calculate(&candles_from_closes(&closes)).unwrap();
+        assert!(output.value > 0.0, "AO should be positive, got {}", output.value);
+        assert!(output.score > 0);
+    }
+
+    #[test]
+    fn test_ao_zero_line_cross_is_strong() {
+        // Long flat base, then a decline and a sharp recovery through zero
+        let mut closes = vec![100.0; 40];
+        closes.extend((1..=10).map(|i| 100.0 - i as f64));
+        closes.extend((1..=20).map(|i| 90.0 + i as f64 * 2.0));
+
+        let ao = Ao::ao_series(&candles_from_closes(&closes), 5, 34);
+        let cross = ao.windows(2).position(|w| w[0] <= 0.0 && w[1] > 0.0).unwrap() + 1;
+
+        // ao[i] ends at candle i + 33
+        let at_cross = Ao::default()
+            .calculate(&candles_from_closes(&closes[..=cross + 33]))
+            .unwrap();
+        assert!(at_cross.value > 0.0);
+        assert!(at_cross.score >= 60, "crossover score {}", at_cross.score);
+    }
+
+    #[test]
+    fn test_ao_twin_peaks() {
+        // Two troughs below zero, the second shallower, then turning up
+        let bullish = [-1.0, -3.0, -5.0, -3.0, -2.0, -4.0, -3.0, -2.5];
+        assert_eq!(Ao::twin_peaks(&bullish), 1.0);
+
+        // Second trough deeper is not a twin peaks buy
+        let deeper = [-1.0, -3.0, -2.0, -6.0, -4.0, -3.5];
+        assert_eq!(Ao::twin_peaks(&deeper), 0.0);
+
+        // Mirror above zero: second peak lower, then turning down
+        let bearish = [1.0, 3.0, 5.0, 3.0, 2.0, 4.0, 3.0, 2.5];
+        assert_eq!(Ao::twin_peaks(&bearish), -1.0);
+
+        // Crossing zero between the peaks breaks the pattern
+        let crossed = [-1.0, -3.0, -2.0, 1.0, -2.0, -1.5, -1.0];
+        assert_eq!(Ao::twin_peaks(&crossed), 0.0);
+    }
+}
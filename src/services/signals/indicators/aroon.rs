@@ -0,0 +1,188 @@
+//! Aroon indicator.
+
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// Aroon indicator.
+///
+/// Measures how recently the window's extremes were set:
+/// - Aroon Up = (period - bars since highest high) / period * 100
+/// - Aroon Down = (period - bars since lowest low) / period * 100
+/// - Aroon Oscillator = Aroon Up - Aroon Down
+///
+/// Signals:
+/// - Up near 100 with Down near 0: Strong uptrend (bullish)
+/// - Down near 100 with Up near 0: Strong downtrend (bearish)
+/// - Both below 50: Consolidation, signal is damped
+///
+/// The output value is the oscillator.
+pub struct Aroon {
+    period: usize,
+    name: String,
+}
+
+impl Default for Aroon {
+    fn default() -> Self {
+        Self::new(25)
+    }
+}
+
+impl Aroon {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            name: format!("Aroon ({})", period),
+        }
+    }
+
+    /// Aroon Up and Aroon Down for the latest bar.
+    fn up_down(&self, candles: &[OhlcPoint]) -> (f64, f64) {
+        let window = &candles[candles.len() - self.period - 1..];
+
+        // Ties go to the most recent bar
+        let mut high_index = 0;
+        let mut low_index = 0;
+        for (i, candle) in window.iter().enumerate() {
+            if candle.high >= window[high_index].high {
+                high_index = i;
+            }
+            if candle.low <= window[low_index].low {
+                low_index = i;
+            }
+        }
+
+        let period = self.period as f64;
+        let bars_since_high = (self.period - high_index) as f64;
+        let bars_since_low = (self.period - low_index) as f64;
+        (
+            (period - bars_since_high) / period * 100.0,
+            (period - bars_since_low) / period * 100.0,
+        )
+    }
+}
+
+impl Signal for Aroon {
+    fn id(&self) -> &str {
+        "aroon"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Trend
+    }
+
+    fn min_periods(&self) -> usize {
+        // The window spans the current bar plus `period` bars back
+        self.period + 1
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if self.period == 0 || candles.len() < self.min_periods() {
+            return None;
+        }
+
+        let (up, down) = self.up_down(candles);
+        let oscillator = up - down;
+
+        let score = if up < 50.0 && down < 50.0 {
+            // Neither extreme is recent: no clear trend
+            oscillator / 2.0
+        } else {
+            oscillator
+        };
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            oscillator,
+            clamp_score(score),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(i: usize, close: f64) -> OhlcPoint {
+        OhlcPoint {
+            time: 1000000 + i as i64 * 60000,
+            open: close,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+            volume: Some(1000.0),
+        }
+    }
+
+    fn create_trend_candles(count: usize, step: f64) -> Vec<OhlcPoint> {
+        (0..count)
+            .map(|i| candle(i, 100.0 + i as f64 * step))
+            .collect()
+    }
+
+    #[test]
+    fn test_aroon_id_and_name() {
+        let aroon = Aroon::default();
+        assert_eq!(aroon.id(), "aroon");
+        assert_eq!(aroon.name(), "Aroon (25)");
+        assert_eq!(aroon.category(), SignalCategory::Trend);
+        assert_eq!(aroon.min_periods(), 26);
+    }
+
+    #[test]
+    fn test_aroon_insufficient_data() {
+        let aroon = Aroon::default();
+        assert!(aroon.calculate(&create_trend_candles(25, 1.0)).is_none());
+        assert!(aroon.calculate(&create_trend_candles(26, 1.0)).is_some());
+    }
+
+    #[test]
+    fn test_aroon_fresh_high() {
+        let aroon = Aroon::default();
+
+        // Choppy range, then a breakout to a new high on the latest bar
+        let mut candles: Vec<OhlcPoint> = (0..40)
+            .map(|i| candle(i, if i % 2 == 0 { 100.0 } else { 103.0 }))
+            .collect();
+        candles.push(candle(40, 110.0));
+
+        let (up, down) = aroon.up_down(&candles);
+        assert_eq!(up, 100.0);
+        assert!(down < 100.0);
+
+        let output = aroon.calculate(&create_trend_candles(40, 1.0)).unwrap();
+        assert_eq!(output.value, 100.0);
+        assert_eq!(output.score, 100);
+    }
+
+    #[test]
+    fn test_aroon_downtrend_is_bearish() {
+        let output = Aroon::default()
+            .calculate(&create_trend_candles(40, -1.0))
+            .unwrap();
+        assert_eq!(output.value, -100.0);
+        assert_eq!(output.score, -100);
+    }
+
+    #[test]
+    fn test_aroon_stale_extremes_are_damped() {
+        let aroon = Aroon::new(10);
+        // High 8 bars ago, low 7 bars ago, flat since
+        let mut candles = vec![candle(0, 100.0); 3];
+        candles.push(candle(3, 110.0));
+        candles.push(candle(4, 90.0));
+        candles.extend((5..12).map(|i| candle(i, 100.0)));
+
+        let (up, down) = aroon.up_down(&candles);
+        assert!((up - 20.0).abs() < 1e-9);
+        assert!((down - 30.0).abs() < 1e-9);
+
+        let output = aroon.calculate(&candles).unwrap();
+        assert!((output.value + 10.0).abs() < 1e-9);
+        assert_eq!(output.score, -5);
+    }
+}
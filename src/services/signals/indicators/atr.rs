@@ -14,15 +14,52 @@ use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
 /// For scoring, we compare current ATR to recent average.
 pub struct Atr {
     period: usize,
+    name: String,
 }
 
 impl Default for Atr {
     fn default() -> Self {
-        Self { period: 14 }
+        Self::new(14)
     }
 }
 
 impl Atr {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            name: format!("ATR ({})", period),
+        }
+    }
+
+    /// Calculate the ATR in price units using Wilder's smoothing.
+    /// Returns None if there are fewer than `period + 1` candles.
+    pub fn atr(&self, candles: &[OhlcPoint]) -> Option<f64> {
+        if self.period == 0 || candles.len() < self.min_periods() {
+            return None;
+        }
+        Some(self.smooth(&Self::true_ranges(candles)))
+    }
+
+    /// True range of every candle after the first.
+    fn true_ranges(candles: &[OhlcPoint]) -> Vec<f64> {
+        candles
+            .windows(2)
+            .map(|w| Self::true_range(&w[1], &w[0]))
+            .collect()
+    }
+
+    /// Wilder-smoothed average of `true_ranges`, seeded with the first `period` values.
+    fn smooth(&self, true_ranges: &[f64]) -> f64 {
+        let initial_atr: f64 =
+            true_ranges.iter().take(self.period).sum::<f64>() / self.period as f64;
+
+        let mut atr = initial_atr;
+        for tr in true_ranges.iter().skip(self.period) {
+            atr = (atr * (self.period - 1) as f64 + tr) / self.period as f64;
+        }
+        atr
+    }
+
     /// Calculate True Range.
     fn true_range(current: &OhlcPoint, previous: &OhlcPoint) -> f64 {
         let hl = current.high - current.low;
@@ -38,7 +75,7 @@ impl Signal for Atr {
     }
 
     fn name(&self) -> &str {
-        "ATR (14)"
+        &self.name
     }
 
     fn category(&self) -> SignalCategory {
@@ -50,24 +87,13 @@ impl Signal for Atr {
     }
 
     fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
-        if candles.len() < self.min_periods() {
+        if self.period == 0 || candles.len() < self.min_periods() {
             return None;
         }
 
-        // Calculate true ranges
-        let mut true_ranges = Vec::new();
-        for i in 1..candles.len() {
-            true_ranges.push(Self::true_range(&candles[i], &candles[i - 1]));
-        }
-
         // Calculate ATR using Wilder's smoothing
-        let initial_atr: f64 =
-            true_ranges.iter().take(self.period).sum::<f64>() / self.period as f64;
-
-        let mut atr = initial_atr;
-        for tr in true_ranges.iter().skip(self.period) {
-            atr = (atr * (self.period - 1) as f64 + tr) / self.period as f64;
-        }
+        let true_ranges = Self::true_ranges(candles);
+        let atr = self.smooth(&true_ranges);
 
         // Calculate ATR as percentage of current price
         let current_price = candles.last()?.close;
@@ -166,6 +192,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_atr_value_in_price_units() {
+        // Every bar spans 3.0 with no gaps, so every true range is 3.0
+        let atr = Atr::new(5);
+        let candles = create_uptrend_candles(30);
+        assert!((atr.atr(&candles).unwrap() - 3.0).abs() < 1e-9);
+        assert!(atr.atr(&candles[..5]).is_none());
+    }
+
     #[test]
     fn test_atr_score_range() {
         let atr = Atr::default();
@@ -0,0 +1,197 @@
+//! Chaikin Oscillator indicator.
+
+use super::{Adl, Ema};
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// Chaikin Oscillator.
+///
+/// MACD of the Accumulation/Distribution Line:
+/// Chaikin = EMA(ADL, fast) - EMA(ADL, slow)
+///
+/// Signals:
+/// - Above zero: Money flow accelerating into the asset (bullish)
+/// - Below zero: Money flow accelerating out (bearish)
+/// - A zero-line crossing on the latest bar marks a momentum shift
+///
+/// Requires volume on every candle.
+pub struct ChaikinOsc {
+    fast_period: usize,
+    slow_period: usize,
+    name: String,
+}
+
+impl Default for ChaikinOsc {
+    fn default() -> Self {
+        Self::new(3, 10)
+    }
+}
+
+impl ChaikinOsc {
+    pub fn new(fast_period: usize, slow_period: usize) -> Self {
+        Self {
+            fast_period,
+            slow_period,
+            name: format!("Chaikin Oscillator ({}, {})", fast_period, slow_period),
+        }
+    }
+
+    /// Oscillator value for every bar where the slow EMA is available.
+    fn oscillator_series(&self, adl: &[f64]) -> Vec<f64> {
+        let fast = Ema::series(adl, self.fast_period);
+        let slow = Ema::series(adl, self.slow_period);
+        // Both series end on the last bar; the fast one starts earlier
+        let offset = fast.len().saturating_sub(slow.len());
+        slow.iter()
+            .zip(&fast[offset..])
+            .map(|(slow, fast)| fast - slow)
+            .collect()
+    }
+}
+
+impl Signal for ChaikinOsc {
+    fn id(&self) -> &str {
+        "chaikin_osc"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Volume
+    }
+
+    fn min_periods(&self) -> usize {
+        // One extra bar so the previous reading is available for crossovers
+        self.slow_period + 1
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if candles.len() < self.min_periods() {
+            return None;
+        }
+
+        let adl = Adl::adl_series(candles)?;
+        let oscillator = self.oscillator_series(&adl);
+        if oscillator.len() < 2 {
+            return None;
+        }
+        let current = oscillator[oscillator.len() - 1];
+        let previous = oscillator[oscillator.len() - 2];
+
+        // The oscillator is in volume units; compare it to the average volume
+        // over the slow window so the score is scale-free
+        let recent = &candles[candles.len() - self.slow_period..];
+        let average_volume =
+            recent.iter().filter_map(|c| c.volume).sum::<f64>() / self.slow_period as f64;
+        let mut score = if average_volume > 0.0 {
+            current / average_volume * 100.0
+        } else {
+            0.0
+        };
+
+        // Fresh zero-line crossing on the latest bar
+        if current != 0.0 && previous != 0.0 && current.signum() != previous.signum() {
+            score = current.signum() * (score.abs() + 60.0);
+        }
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            current,
+            clamp_score(score),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A candle closing `clv` of the way from the middle of its range to the
+    /// high (negative toward the low).
+    fn candle(i: usize, close: f64, clv: f64, volume: Option<f64>) -> OhlcPoint {
+        let half_range = 1.0;
+        let mid = close - clv * half_range;
+        OhlcPoint {
+            time: 1000000 + i as i64 * 60000,
+            open: mid,
+            high: mid + half_range,
+            low: mid - half_range,
+            close,
+            volume,
+        }
+    }
+
+    #[test]
+    fn test_chaikin_osc_id_and_name() {
+        let chaikin = ChaikinOsc::default();
+        assert_eq!(chaikin.id(), "chaikin_osc");
+        assert_eq!(chaikin.name(), "Chaikin Oscillator (3, 10)");
+        assert_eq!(chaikin.category(), SignalCategory::Volume);
+        assert_eq!(chaikin.min_periods(), 11);
+    }
+
+    #[test]
+    fn test_chaikin_osc_insufficient_data() {
+        let candles: Vec<OhlcPoint> = (0..10)
+            .map(|i| candle(i, 100.0, 0.5, Some(1000.0)))
+            .collect();
+        assert!(ChaikinOsc::default().calculate(&candles).is_none());
+    }
+
+    #[test]
+    fn test_chaikin_osc_accumulation_with_accelerating_volume_is_positive() {
+        // Closes near the high push the ADL up, and growing volume makes it
+        // rise faster each bar, so the fast EMA pulls ahead of the slow one
+        let candles: Vec<OhlcPoint> = (0..30)
+            .map(|i| {
+                candle(
+                    i,
+                    100.0 + i as f64,
+                    0.8,
+                    Some(1000.0 * 1.1f64.powi(i as i32)),
+                )
+            })
+            .collect();
+        let output = ChaikinOsc::default().calculate(&candles).unwrap();
+        assert!(output.value > 0.0, "oscillator {}", output.value);
+        assert!(output.score > 0, "score {}", output.score);
+    }
+
+    #[test]
+    fn test_chaikin_osc_distribution_is_negative() {
+        let candles: Vec<OhlcPoint> = (0..30)
+            .map(|i| candle(i, 100.0, -0.8, Some(1000.0 * 1.1f64.powi(i as i32))))
+            .collect();
+        let output = ChaikinOsc::default().calculate(&candles).unwrap();
+        assert!(output.value < 0.0);
+        assert!(output.score < 0);
+    }
+
+    #[test]
+    fn test_chaikin_osc_zero_cross_scores_strongly() {
+        let chaikin = ChaikinOsc::default();
+        // Steady distribution, then one heavy accumulation bar flips the sign
+        let mut candles: Vec<OhlcPoint> = (0..20)
+            .map(|i| candle(i, 100.0, -0.5, Some(1000.0)))
+            .collect();
+        let before = chaikin.calculate(&candles).unwrap();
+        assert!(before.value < 0.0);
+
+        candles.push(candle(20, 101.0, 1.0, Some(20_000.0)));
+        let output = chaikin.calculate(&candles).unwrap();
+        assert!(output.value > 0.0);
+        assert!(output.score >= 60, "score {}", output.score);
+    }
+
+    #[test]
+    fn test_chaikin_osc_requires_volume() {
+        let mut candles: Vec<OhlcPoint> = (0..30)
+            .map(|i| candle(i, 100.0, 0.5, Some(1000.0)))
+            .collect();
+        candles[12].volume = None;
+        assert!(ChaikinOsc::default().calculate(&candles).is_none());
+    }
+}
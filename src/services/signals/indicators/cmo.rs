@@ -0,0 +1,191 @@
+//! Chande Momentum Oscillator (CMO) indicator.
+
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// CMO (Chande Momentum Oscillator) indicator.
+///
+/// Compares the sum of up moves to the sum of down moves over a period:
+/// CMO = 100 * (Sum Up - Sum Down) / (Sum Up + Sum Down)
+///
+/// Values range from -100 to +100:
+/// - Above +50: Overbought (bearish)
+/// - Below -50: Oversold (bullish)
+pub struct Cmo {
+    period: usize,
+    name: String,
+}
+
+impl Default for Cmo {
+    fn default() -> Self {
+        Self::new(14)
+    }
+}
+
+impl Cmo {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            name: format!("CMO ({})", period),
+        }
+    }
+
+    /// Calculate the CMO value over the last `period` close-to-close changes.
+    fn calculate_cmo(candles: &[OhlcPoint], period: usize) -> Option<f64> {
+        if period == 0 || candles.len() < period + 1 {
+            return None;
+        }
+
+        let window = &candles[candles.len() - period - 1..];
+        let mut sum_up = 0.0;
+        let mut sum_down = 0.0;
+
+        for pair in window.windows(2) {
+            let change = pair[1].close - pair[0].close;
+            if change > 0.0 {
+                sum_up += change;
+            } else {
+                sum_down -= change;
+            }
+        }
+
+        let total = sum_up + sum_down;
+        if total == 0.0 {
+            return Some(0.0);
+        }
+
+        Some(100.0 * (sum_up - sum_down) / total)
+    }
+}
+
+impl Signal for Cmo {
+    fn id(&self) -> &str {
+        "cmo"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Momentum
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period + 1
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        let cmo = Self::calculate_cmo(candles, self.period)?;
+
+        // Contrarian score:
+        // |CMO| <= 50 = mild lean against the move (up to 25)
+        // |CMO| > 50 = overbought/oversold, scaling from 25 to 100 at the extremes
+        let magnitude = cmo.abs();
+        let strength = if magnitude <= 50.0 {
+            magnitude * 0.5
+        } else {
+            25.0 + (magnitude - 50.0) * 1.5
+        };
+        let score = -cmo.signum() * strength;
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            cmo,
+            clamp_score(score),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candles_from_closes(closes: &[f64]) -> Vec<OhlcPoint> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| OhlcPoint {
+                time: 1000000 + i as i64 * 60000,
+                open: close,
+                high: close + 1.0,
+                low: close - 1.0,
+                close,
+                volume: Some(1000.0),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cmo_id_and_name() {
+        let cmo = Cmo::default();
+        assert_eq!(cmo.id(), "cmo");
+        assert_eq!(cmo.name(), "CMO (14)");
+        assert_eq!(cmo.category(), SignalCategory::Momentum);
+        assert_eq!(cmo.min_periods(), 15);
+    }
+
+    #[test]
+    fn test_cmo_insufficient_data() {
+        let cmo = Cmo::default();
+        let candles = candles_from_closes(&[100.0; 10]);
+        assert!(cmo.calculate(&candles).is_none());
+    }
+
+    #[test]
+    fn test_cmo_known_value() {
+        // Changes: +2, -1, +3, -2 => up 5, down 3 => 100 * 2 / 8 = 25
+        let cmo = Cmo::new(4);
+        let candles = candles_from_closes(&[100.0, 102.0, 101.0, 104.0, 102.0]);
+        let output = cmo.calculate(&candles).unwrap();
+        assert!((output.value - 25.0).abs() < 1e-9);
+        assert_eq!(output.score, -12);
+    }
+
+    #[test]
+    fn test_cmo_sign_tracks_dominant_direction() {
+        let cmo = Cmo::new(10);
+
+        // Mostly up moves with small pullbacks
+        let mut closes = vec![100.0];
+        for i in 0..10 {
+            let last = *closes.last().unwrap();
+            closes.push(if i % 3 == 2 { last - 0.5 } else { last + 2.0 });
+        }
+        let up = cmo.calculate(&candles_from_closes(&closes)).unwrap();
+        assert!(up.value > 0.0, "CMO should be positive, got {}", up.value);
+        assert!(up.score < 0, "Overbought CMO should score bearish");
+
+        // Mostly down moves with small bounces
+        let mut closes = vec![100.0];
+        for i in 0..10 {
+            let last = *closes.last().unwrap();
+            closes.push(if i % 3 == 2 { last + 0.5 } else { last - 2.0 });
+        }
+        let down = cmo.calculate(&candles_from_closes(&closes)).unwrap();
+        assert!(down.value < 0.0, "CMO should be negative, got {}", down.value);
+        assert!(down.score > 0, "Oversold CMO should score bullish");
+    }
+
+    #[test]
+    fn test_cmo_only_uses_recent_window() {
+        // A long decline followed by a steady rise over the last period
+        let mut closes: Vec<f64> = (0..20).map(|i| 200.0 - i as f64 * 3.0).collect();
+        let last = *closes.last().unwrap();
+        closes.extend((1..=5).map(|i| last + i as f64));
+
+        let output = Cmo::new(5).calculate(&candles_from_closes(&closes)).unwrap();
+        assert_eq!(output.value, 100.0);
+        assert_eq!(output.score, -100);
+    }
+
+    #[test]
+    fn test_cmo_flat_prices() {
+        let output = Cmo::default()
+            .calculate(&candles_from_closes(&[100.0; 20]))
+            .unwrap();
+        assert_eq!(output.value, 0.0);
+        assert_eq!(output.score, 0);
+    }
+}
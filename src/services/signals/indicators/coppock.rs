@@ -0,0 +1,198 @@
+//! Coppock Curve indicator.
+
+use super::Hma;
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// Coppock Curve indicator.
+///
+/// A long-term momentum curve originally designed to spot market bottoms:
+/// Coppock = WMA(ROC(14) + ROC(11), 10)
+///
+/// Signals:
+/// - Turning up while below zero: Bottom forming (strongly bullish)
+/// - Rising below zero: Recovery under way (bullish)
+/// - Otherwise the slope gives a mild trend reading
+///
+/// Works best on coarse candles (hourly or slower).
+pub struct Coppock {
+    long_roc: usize,
+    short_roc: usize,
+    wma_period: usize,
+}
+
+impl Default for Coppock {
+    fn default() -> Self {
+        Self::new(14, 11, 10)
+    }
+}
+
+impl Coppock {
+    pub fn new(long_roc: usize, short_roc: usize, wma_period: usize) -> Self {
+        Self {
+            long_roc,
+            short_roc,
+            wma_period,
+        }
+    }
+
+    /// Rate of change (%) over `period` bars for every bar after the first `period`.
+    fn roc_series(closes: &[f64], period: usize) -> Vec<f64> {
+        closes
+            .windows(period + 1)
+            .map(|w| {
+                if w[0] == 0.0 {
+                    0.0
+                } else {
+                    (w[period] - w[0]) / w[0] * 100.0
+                }
+            })
+            .collect()
+    }
+
+    /// Calculate the Coppock Curve for every bar with a full WMA window.
+    fn coppock_series(&self, closes: &[f64]) -> Vec<f64> {
+        let long = Self::roc_series(closes, self.long_roc);
+        let short = Self::roc_series(closes, self.short_roc);
+
+        // Align the short ROC with the long one (it starts earlier)
+        let offset = short.len() - long.len().min(short.len());
+        let summed: Vec<f64> = short
+            .iter()
+            .skip(offset)
+            .zip(long.iter())
+            .map(|(s, l)| s + l)
+            .collect();
+
+        Hma::wma_series(&summed, self.wma_period)
+    }
+}
+
+impl Signal for Coppock {
+    fn id(&self) -> &str {
+        "coppock"
+    }
+
+    fn name(&self) -> &str {
+        "Coppock Curve"
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Momentum
+    }
+
+    fn min_periods(&self) -> usize {
+        // Long ROC plus the WMA window, and two extra bars so the previous
+        // slope is available to detect a turn
+        self.long_roc.max(self.short_roc) + self.wma_period + 2
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if candles.len() < self.min_periods() {
+            return None;
+        }
+
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let curve = self.coppock_series(&closes);
+        let current = *curve.last()?;
+        let previous = curve[curve.len() - 2];
+        let before = curve[curve.len() - 3];
+
+        let slope = current - previous;
+        let previous_slope = previous - before;
+
+        let score = if current < 0.0 && slope > 0.0 {
+            // Recovering from below zero; deeper troughs are stronger bottoms
+            let mut score = 40.0 + (current.abs() * 2.0).min(40.0);
+            if previous_slope <= 0.0 {
+                // Fresh upturn on the latest bar
+                score += 20.0;
+            }
+            score
+        } else {
+            // Not a bottom signal: mild reading from the slope only
+            (slope * 10.0).clamp(-30.0, 30.0)
+        };
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            current,
+            clamp_score(score),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Falling by `down_rate` per bar for `down` bars, then rising by 2% per bar.
+    fn create_reversal_candles(down: usize, up: usize, down_rate: f64) -> Vec<OhlcPoint> {
+        let mut price = 100.0;
+        (0..down + up)
+            .map(|i| {
+                price *= if i < down { 1.0 - down_rate } else { 1.02 };
+                OhlcPoint {
+                    time: 1000000 + i as i64 * 60000,
+                    open: price,
+                    high: price * 1.01,
+                    low: price * 0.99,
+                    close: price,
+                    volume: Some(1000.0),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_coppock_id_and_name() {
+        let coppock = Coppock::default();
+        assert_eq!(coppock.id(), "coppock");
+        assert_eq!(coppock.name(), "Coppock Curve");
+        assert_eq!(coppock.category(), SignalCategory::Momentum);
+        assert_eq!(coppock.min_periods(), 26);
+    }
+
+    #[test]
+    fn test_coppock_insufficient_data() {
+        let coppock = Coppock::default();
+        assert!(coppock
+            .calculate(&create_reversal_candles(25, 0, 0.01))
+            .is_none());
+        assert!(coppock
+            .calculate(&create_reversal_candles(26, 0, 0.01))
+            .is_some());
+    }
+
+    #[test]
+    fn test_coppock_steady_decline_is_not_bullish() {
+        let output = Coppock::default()
+            .calculate(&create_reversal_candles(50, 0, 0.01))
+            .unwrap();
+        assert!(output.value < 0.0);
+        assert!(output.score <= 0);
+    }
+
+    #[test]
+    fn test_coppock_upturn_from_below_zero_is_bullish() {
+        let coppock = Coppock::default();
+
+        // Find the bar where the curve first turns up after the decline
+        let candles = create_reversal_candles(50, 20, 0.01);
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let curve = coppock.coppock_series(&closes);
+        let reversal = 50 - (coppock.min_periods() - 3);
+        let turn = (reversal..curve.len())
+            .find(|&i| curve[i] > curve[i - 1])
+            .unwrap();
+        assert!(curve[turn] < 0.0);
+
+        // curve[i] covers candles up to index i + min_periods - 3
+        let output = coppock
+            .calculate(&candles[..turn + coppock.min_periods() - 2])
+            .unwrap();
+        assert!(output.value < 0.0);
+        assert!(output.score >= 60, "upturn score {}", output.score);
+    }
+}
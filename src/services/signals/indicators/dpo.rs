@@ -0,0 +1,162 @@
+//! Detrended Price Oscillator (DPO) indicator.
+
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// DPO (Detrended Price Oscillator) indicator.
+///
+/// Removes the trend from price to expose short-term cycles by comparing a
+/// past close to the moving average around it:
+/// DPO = Close (period / 2 + 1 bars ago) - SMA(period)
+///
+/// Signals:
+/// - Positive: Price above its detrended average, cycle peaking (bullish)
+/// - Negative: Price below its detrended average, cycle troughing (bearish)
+///
+/// The output value is the DPO in price units.
+pub struct Dpo {
+    period: usize,
+    name: String,
+}
+
+impl Default for Dpo {
+    fn default() -> Self {
+        Self::new(20)
+    }
+}
+
+impl Dpo {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            name: format!("DPO ({})", period),
+        }
+    }
+
+    /// Bars between the displaced close and the latest bar.
+    fn displacement(&self) -> usize {
+        self.period / 2 + 1
+    }
+}
+
+impl Signal for Dpo {
+    fn id(&self) -> &str {
+        "dpo"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Momentum
+    }
+
+    fn min_periods(&self) -> usize {
+        // The displaced close must exist, which is always inside the SMA window
+        self.period.max(self.displacement() + 1)
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if self.period == 0 || candles.len() < self.min_periods() {
+            return None;
+        }
+
+        let sma = candles[candles.len() - self.period..]
+            .iter()
+            .map(|c| c.close)
+            .sum::<f64>()
+            / self.period as f64;
+        if sma == 0.0 {
+            return None;
+        }
+
+        let displaced = candles[candles.len() - 1 - self.displacement()].close;
+        let dpo = displaced - sma;
+
+        // Scale so that 5% from the detrended average = full signal
+        let score = dpo / sma * 100.0 * 20.0;
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            dpo,
+            clamp_score(score),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_candles(closes: impl Iterator<Item = f64>) -> Vec<OhlcPoint> {
+        closes
+            .enumerate()
+            .map(|(i, close)| OhlcPoint {
+                time: 1000000 + i as i64 * 60000,
+                open: close,
+                high: close + 1.0,
+                low: close - 1.0,
+                close,
+                volume: Some(1000.0),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_dpo_id_and_name() {
+        let dpo = Dpo::default();
+        assert_eq!(dpo.id(), "dpo");
+        assert_eq!(dpo.name(), "DPO (20)");
+        assert_eq!(dpo.category(), SignalCategory::Momentum);
+        assert_eq!(dpo.displacement(), 11);
+        assert_eq!(dpo.min_periods(), 20);
+    }
+
+    #[test]
+    fn test_dpo_insufficient_data() {
+        let dpo = Dpo::default();
+        assert!(dpo
+            .calculate(&create_candles((0..19).map(|_| 100.0)))
+            .is_none());
+        assert!(dpo
+            .calculate(&create_candles((0..20).map(|_| 100.0)))
+            .is_some());
+    }
+
+    #[test]
+    fn test_dpo_removes_linear_trend() {
+        let dpo = Dpo::default();
+
+        // A ramp sits 9.5 steps above its SMA, but detrended it is only the
+        // 1.5 step gap between the displaced close and the SMA midpoint
+        let step = 0.25;
+        let ramp = create_candles((0..60).map(|i| 100.0 + i as f64 * step));
+        let output = dpo.calculate(&ramp).unwrap();
+        assert!((output.value + 1.5 * step).abs() < 1e-9);
+        assert!(output.score.abs() <= 10, "ramp score {}", output.score);
+
+        // The reading does not grow as the trend continues
+        let longer = create_candles((0..200).map(|i| 100.0 + i as f64 * step));
+        assert!((dpo.calculate(&longer).unwrap().value - output.value).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_dpo_tracks_cycle() {
+        let dpo = Dpo::default();
+        let cycle = |i: usize| 100.0 + 5.0 * (i as f64 * std::f64::consts::PI / 10.0).sin();
+
+        // Displaced close at a cycle peak reads positive
+        let peak = create_candles((0..57).map(cycle));
+        let output = dpo.calculate(&peak).unwrap();
+        assert!(output.value > 3.0, "peak dpo {}", output.value);
+        assert!(output.score > 50);
+
+        // And at a trough reads negative
+        let trough = create_candles((0..47).map(cycle));
+        let output = dpo.calculate(&trough).unwrap();
+        assert!(output.value < -3.0, "trough dpo {}", output.value);
+        assert!(output.score < -50);
+    }
+}
@@ -0,0 +1,188 @@
+//! Elder Ray (Bull/Bear Power) indicator.
+
+use std::collections::BTreeMap;
+
+use super::Ema;
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// Elder Ray indicator.
+///
+/// Measures how far buyers and sellers push price away from consensus value:
+/// - Bull Power = High - EMA(Close, period)
+/// - Bear Power = Low - EMA(Close, period)
+///
+/// Signals:
+/// - Bull Power rising above zero while Bear Power recovers from below = bullish
+/// - Bear Power falling below zero while Bull Power fades from above = bearish
+///
+/// The output value is net power (Bull + Bear); both powers are included as
+/// `bullPower` and `bearPower` components.
+pub struct ElderRay {
+    period: usize,
+    name: String,
+}
+
+impl Default for ElderRay {
+    fn default() -> Self {
+        Self::new(13)
+    }
+}
+
+impl ElderRay {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            name: format!("Elder Ray ({})", period),
+        }
+    }
+
+    /// Bull and Bear Power for every bar with an EMA value.
+    fn powers(&self, candles: &[OhlcPoint]) -> Vec<(f64, f64)> {
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let ema = Ema::series(&closes, self.period);
+        let offset = candles.len() - ema.len();
+
+        candles[offset..]
+            .iter()
+            .zip(ema.iter())
+            .map(|(c, e)| (c.high - e, c.low - e))
+            .collect()
+    }
+}
+
+impl Signal for ElderRay {
+    fn id(&self) -> &str {
+        "elder_ray"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Momentum
+    }
+
+    fn min_periods(&self) -> usize {
+        // One extra bar so the previous powers are available for direction
+        self.period + 1
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if self.period == 0 || candles.len() < self.min_periods() {
+            return None;
+        }
+
+        let powers = self.powers(candles);
+        let (bull, bear) = *powers.last()?;
+        let (previous_bull, previous_bear) = powers[powers.len() - 2];
+        let price = candles.last()?.close;
+        if price == 0.0 {
+            return None;
+        }
+
+        // Net power as a percentage of price: 5% = full signal
+        let net = bull + bear;
+        let mut score = net / price * 100.0 * 20.0;
+
+        let bull_rising = bull > previous_bull;
+        let bear_rising = bear > previous_bear;
+        if bull > 0.0 && bull_rising && bear < 0.0 && bear_rising {
+            // Buyers strengthening while sellers lose their grip
+            score += 30.0;
+        } else if bear < 0.0 && !bear_rising && bull > 0.0 && !bull_rising {
+            // Sellers strengthening while buyers fade
+            score -= 30.0;
+        }
+
+        let mut output = make_signal_output(self.name(), self.category(), net, clamp_score(score));
+        output.components = Some(BTreeMap::from([
+            ("bullPower".to_string(), bull),
+            ("bearPower".to_string(), bear),
+        ]));
+        Some(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Candles drifting by `step` per bar, with highs `up` above and lows
+    /// `down` below the close.
+    fn create_candles(count: usize, step: f64, up: f64, down: f64) -> Vec<OhlcPoint> {
+        (0..count)
+            .map(|i| {
+                let close = 100.0 + i as f64 * step;
+                OhlcPoint {
+                    time: 1000000 + i as i64 * 60000,
+                    open: close,
+                    high: close + up,
+                    low: close - down,
+                    close,
+                    volume: Some(1000.0),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_elder_ray_id_and_name() {
+        let elder = ElderRay::default();
+        assert_eq!(elder.id(), "elder_ray");
+        assert_eq!(elder.name(), "Elder Ray (13)");
+        assert_eq!(elder.category(), SignalCategory::Momentum);
+        assert_eq!(elder.min_periods(), 14);
+    }
+
+    #[test]
+    fn test_elder_ray_insufficient_data() {
+        let elder = ElderRay::default();
+        assert!(elder
+            .calculate(&create_candles(13, 1.0, 1.0, 1.0))
+            .is_none());
+    }
+
+    #[test]
+    fn test_elder_ray_reports_both_powers() {
+        // Flat closes at the EMA: powers equal the distance to high and low
+        let output = ElderRay::default()
+            .calculate(&create_candles(30, 0.0, 2.0, 1.5))
+            .unwrap();
+        let components = output.components.unwrap();
+        assert!((components["bullPower"] - 2.0).abs() < 1e-9);
+        assert!((components["bearPower"] + 1.5).abs() < 1e-9);
+        assert!((output.value - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_elder_ray_bull_dominant_bars_read_bullish() {
+        let elder = ElderRay::default();
+
+        // Rising closes with bars reaching far above and barely below
+        let mut candles = create_candles(30, 0.1, 1.0, 0.5);
+        let output = elder.calculate(&candles).unwrap();
+        assert!(output.value > 0.0);
+        assert!(output.score > 0, "bull score {}", output.score);
+
+        // A bar pushing higher while the low recovers toward the EMA
+        let last = candles.last().unwrap().close;
+        candles.push(OhlcPoint {
+            time: candles.last().unwrap().time + 60000,
+            open: last,
+            high: last + 5.0,
+            low: last - 0.1,
+            close: last + 1.0,
+            volume: Some(1000.0),
+        });
+        let stronger = elder.calculate(&candles).unwrap();
+        assert!(stronger.score > output.score);
+
+        // Bear-dominant mirror image reads bearish
+        let bearish = elder
+            .calculate(&create_candles(30, -0.1, 0.5, 1.0))
+            .unwrap();
+        assert!(bearish.score < 0, "bear score {}", bearish.score);
+    }
+}
@@ -11,31 +11,45 @@ use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
 /// - Price below EMA = bearish
 pub struct Ema {
     period: usize,
+    name: String,
 }
 
 impl Ema {
     pub fn new(period: usize) -> Self {
-        Self { period }
+        Self {
+            period,
+            name: format!("EMA ({})", period),
+        }
     }
 
-    /// Calculate EMA value.
-    fn calculate_ema(candles: &[OhlcPoint], period: usize) -> Option<f64> {
-        if candles.len() < period {
-            return None;
+    /// Calculate the EMA of every bar once `period` values are available.
+    ///
+    /// The first value is the SMA of the first `period` values, so the
+    /// result has `values.len() - period + 1` entries (empty if too short).
+    pub fn series(values: &[f64], period: usize) -> Vec<f64> {
+        if period == 0 || values.len() < period {
+            return Vec::new();
         }
 
         let multiplier = 2.0 / (period as f64 + 1.0);
+        let mut ema = Vec::with_capacity(values.len() - period + 1);
 
         // First EMA is SMA
-        let sma: f64 = candles.iter().take(period).map(|c| c.close).sum::<f64>() / period as f64;
+        let mut current = values.iter().take(period).sum::<f64>() / period as f64;
+        ema.push(current);
 
-        // Calculate EMA for remaining data
-        let mut ema = sma;
-        for candle in candles.iter().skip(period) {
-            ema = (candle.close - ema) * multiplier + ema;
+        for value in values.iter().skip(period) {
+            current = (value - current) * multiplier + current;
+            ema.push(current);
         }
 
-        Some(ema)
+        ema
+    }
+
+    /// Calculate EMA value.
+    fn calculate_ema(candles: &[OhlcPoint], period: usize) -> Option<f64> {
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        Self::series(&closes, period).last().copied()
     }
 }
 
@@ -49,11 +63,7 @@ impl Signal for Ema {
     }
 
     fn name(&self) -> &str {
-        match self.period {
-            12 => "EMA (12)",
-            26 => "EMA (26)",
-            _ => "EMA",
-        }
+        &self.name
     }
 
     fn category(&self) -> SignalCategory {
@@ -86,7 +96,7 @@ impl Signal for Ema {
 
 impl Default for Ema {
     fn default() -> Self {
-        Self { period: 12 }
+        Self::new(12)
     }
 }
 
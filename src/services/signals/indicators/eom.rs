@@ -0,0 +1,171 @@
+//! Ease of Movement (EOM) indicator.
+
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// Volume divisor so box ratios stay in a readable range.
+const VOLUME_SCALE: f64 = 10_000.0;
+
+/// Ease of Movement indicator.
+///
+/// Relates price change to the volume needed to produce it:
+/// Distance Moved = (High + Low) / 2 - (Prev High + Prev Low) / 2
+/// Box Ratio = (Volume / 10,000) / (High - Low)
+/// EOM = SMA(Distance Moved / Box Ratio, period)
+///
+/// Signals:
+/// - Positive: Price rising easily on light volume (bullish)
+/// - Negative: Price falling easily on light volume (bearish)
+///
+/// Requires volume on every candle in the window.
+pub struct Eom {
+    period: usize,
+    name: String,
+}
+
+impl Default for Eom {
+    fn default() -> Self {
+        Self::new(14)
+    }
+}
+
+impl Eom {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            name: format!("EOM ({})", period),
+        }
+    }
+
+    /// One-bar ease of movement, or None if volume is missing.
+    fn raw_eom(prev: &OhlcPoint, current: &OhlcPoint) -> Option<f64> {
+        let volume = current.volume?;
+        let range = current.high - current.low;
+        if volume <= 0.0 || range <= 0.0 {
+            return Some(0.0);
+        }
+
+        let distance = (current.high + current.low) / 2.0 - (prev.high + prev.low) / 2.0;
+        let box_ratio = (volume / VOLUME_SCALE) / range;
+        Some(distance / box_ratio)
+    }
+}
+
+impl Signal for Eom {
+    fn id(&self) -> &str {
+        "eom"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Volume
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period + 1
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if self.period == 0 || candles.len() < self.min_periods() {
+            return None;
+        }
+
+        let window = &candles[candles.len() - self.period - 1..];
+        let raw: Vec<f64> = window
+            .windows(2)
+            .map(|pair| Self::raw_eom(&pair[0], &pair[1]))
+            .collect::<Option<_>>()?;
+
+        let eom = raw.iter().sum::<f64>() / self.period as f64;
+
+        // Net ease relative to total ease: 100 when every bar moved up
+        let magnitude = raw.iter().map(|v| v.abs()).sum::<f64>() / self.period as f64;
+        let score = if magnitude > 0.0 {
+            eom / magnitude * 100.0
+        } else {
+            0.0
+        };
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            eom,
+            clamp_score(score),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Candles drifting by `step` per bar with constant volume.
+    fn create_trend_candles(count: usize, step: f64, volume: Option<f64>) -> Vec<OhlcPoint> {
+        (0..count)
+            .map(|i| {
+                let mid = 100.0 + i as f64 * step;
+                OhlcPoint {
+                    time: 1000000 + i as i64 * 60000,
+                    open: mid,
+                    high: mid + 1.0,
+                    low: mid - 1.0,
+                    close: mid,
+                    volume,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_eom_id_and_name() {
+        let eom = Eom::default();
+        assert_eq!(eom.id(), "eom");
+        assert_eq!(eom.name(), "EOM (14)");
+        assert_eq!(eom.category(), SignalCategory::Volume);
+        assert_eq!(eom.min_periods(), 15);
+    }
+
+    #[test]
+    fn test_eom_insufficient_data() {
+        let candles = create_trend_candles(10, 1.0, Some(100.0));
+        assert!(Eom::default().calculate(&candles).is_none());
+    }
+
+    #[test]
+    fn test_eom_rising_on_low_volume_is_bullish() {
+        let eom = Eom::default();
+        let light = eom
+            .calculate(&create_trend_candles(30, 1.0, Some(100.0)))
+            .unwrap();
+        assert!(light.value > 0.0);
+        assert!(light.score > 0);
+
+        // Same move on heavy volume is harder, so the reading is smaller
+        let heavy = eom
+            .calculate(&create_trend_candles(30, 1.0, Some(100_000.0)))
+            .unwrap();
+        assert!(heavy.value > 0.0);
+        assert!(heavy.value < light.value);
+
+        let falling = eom
+            .calculate(&create_trend_candles(30, -1.0, Some(100.0)))
+            .unwrap();
+        assert!(falling.value < 0.0);
+        assert!(falling.score < 0);
+    }
+
+    #[test]
+    fn test_eom_requires_volume() {
+        let eom = Eom::default();
+        assert!(eom
+            .calculate(&create_trend_candles(30, 1.0, None))
+            .is_none());
+
+        let mut candles = create_trend_candles(30, 1.0, Some(100.0));
+        candles[25].volume = None;
+        assert!(eom.calculate(&candles).is_none());
+    }
+}
@@ -0,0 +1,205 @@
+//! Fisher Transform indicator.
+
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// Bound applied to the normalized price so the log never sees ±1.
+const MAX_NORMALIZED: f64 = 0.999;
+
+/// Fisher value beyond which a turn counts as a reversal from an extreme.
+const EXTREME_LEVEL: f64 = 1.5;
+
+/// Fisher Transform (Ehlers).
+///
+/// Normalizes the median price to [-1, 1] over a lookback window, smooths it,
+/// and applies the Fisher transform to produce a near-Gaussian oscillator:
+/// Value = 0.66 * ((Median - Lowest) / (Highest - Lowest) - 0.5) + 0.67 * Value[prev]
+/// Fisher = 0.5 * ln((1 + Value) / (1 - Value)) + 0.5 * Fisher[prev]
+///
+/// The trigger line is the previous Fisher value. Signals:
+/// - Fisher crossing above the trigger: bullish reversal (stronger from below -1.5)
+/// - Fisher crossing below the trigger: bearish reversal (stronger from above +1.5)
+pub struct Fisher {
+    period: usize,
+    name: String,
+}
+
+impl Default for Fisher {
+    fn default() -> Self {
+        Self::new(10)
+    }
+}
+
+impl Fisher {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            name: format!("Fisher ({})", period),
+        }
+    }
+
+    /// Calculate the Fisher series for every bar with a full lookback window.
+    fn fisher_series(candles: &[OhlcPoint], period: usize) -> Vec<f64> {
+        if period == 0 || candles.len() < period {
+            return Vec::new();
+        }
+
+        let median: Vec<f64> = candles.iter().map(|c| (c.high + c.low) / 2.0).collect();
+        let mut value = 0.0;
+        let mut fisher = 0.0;
+        let mut series = Vec::with_capacity(median.len() + 1 - period);
+
+        for end in period - 1..median.len() {
+            let window = &median[end + 1 - period..=end];
+            let highest = window.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+            let lowest = window.iter().cloned().fold(f64::INFINITY, f64::min);
+
+            let position = if highest > lowest {
+                (median[end] - lowest) / (highest - lowest) - 0.5
+            } else {
+                0.0
+            };
+            value = (0.66 * position + 0.67 * value).clamp(-MAX_NORMALIZED, MAX_NORMALIZED);
+            fisher = 0.5 * ((1.0 + value) / (1.0 - value)).ln() + 0.5 * fisher;
+            series.push(fisher);
+        }
+
+        series
+    }
+}
+
+impl Signal for Fisher {
+    fn id(&self) -> &str {
+        "fisher"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Momentum
+    }
+
+    fn min_periods(&self) -> usize {
+        // Two extra bars to detect a trigger line crossover
+        self.period + 2
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if candles.len() < self.min_periods() {
+            return None;
+        }
+
+        let series = Self::fisher_series(candles, self.period);
+        let n = series.len();
+        let (fisher, trigger) = (series[n - 1], series[n - 2]);
+        let prev_trigger = series[n - 3];
+
+        let crossed_up = trigger <= prev_trigger && fisher > trigger;
+        let crossed_down = trigger >= prev_trigger && fisher < trigger;
+
+        let score = if crossed_up {
+            // Turning up; sharper from a deeply negative extreme
+            if trigger <= -EXTREME_LEVEL {
+                90.0
+            } else if trigger < 0.0 {
+                60.0
+            } else {
+                30.0
+            }
+        } else if crossed_down {
+            if trigger >= EXTREME_LEVEL {
+                -90.0
+            } else if trigger > 0.0 {
+                -60.0
+            } else {
+                -30.0
+            }
+        } else {
+            // No turn: follow the slope of the transform
+            ((fisher - trigger) * 20.0).clamp(-30.0, 30.0)
+        };
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            fisher,
+            clamp_score(score),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candles_from_closes(closes: &[f64]) -> Vec<OhlcPoint> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| OhlcPoint {
+                time: 1000000 + i as i64 * 60000,
+                open: close,
+                high: close + 1.0,
+                low: close - 1.0,
+                close,
+                volume: Some(1000.0),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fisher_id_and_name() {
+        let fisher = Fisher::default();
+        assert_eq!(fisher.id(), "fisher");
+        assert_eq!(fisher.name(), "Fisher (10)");
+        assert_eq!(fisher.category(), SignalCategory::Momentum);
+        assert_eq!(fisher.min_periods(), 12);
+    }
+
+    #[test]
+    fn test_fisher_insufficient_data() {
+        let candles = candles_from_closes(&[100.0; 11]);
+        assert!(Fisher::default().calculate(&candles).is_none());
+    }
+
+    #[test]
+    fn test_fisher_finite_at_extremes() {
+        // Every bar makes a new high, pinning the normalized price at the top
+        let rising: Vec<f64> = (0..500).map(|i| 100.0 * 1.05f64.powi(i)).collect();
+        let output = Fisher::default()
+            .calculate(&candles_from_closes(&rising))
+            .unwrap();
+        assert!(output.value.is_finite());
+        assert!(output.value > 0.0);
+
+        let falling: Vec<f64> = (0..500).map(|i| 1e6 - i as f64 * 1000.0).collect();
+        let output = Fisher::default()
+            .calculate(&candles_from_closes(&falling))
+            .unwrap();
+        assert!(output.value.is_finite());
+        assert!(output.value < 0.0);
+
+        // Flat prices have a zero range
+        let output = Fisher::default()
+            .calculate(&candles_from_closes(&[100.0; 30]))
+            .unwrap();
+        assert_eq!(output.value, 0.0);
+    }
+
+    #[test]
+    fn test_fisher_reversal_from_extreme_is_strong() {
+        // Long decline pushes Fisher deeply negative, then price turns up
+        let mut closes: Vec<f64> = (0..40).map(|i| 200.0 - i as f64 * 2.0).collect();
+        closes.push(125.0);
+
+        let series = Fisher::fisher_series(&candles_from_closes(&closes), 10);
+        assert!(series[series.len() - 2] <= -EXTREME_LEVEL);
+
+        let output = Fisher::default()
+            .calculate(&candles_from_closes(&closes))
+            .unwrap();
+        assert!(output.score >= 60, "reversal score {}", output.score);
+    }
+}
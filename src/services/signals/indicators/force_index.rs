@@ -0,0 +1,173 @@
+//! Force Index indicator.
+
+use super::Ema;
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// Elder's Force Index.
+///
+/// Combines the size of a price move with the volume behind it:
+/// Raw Force = (Close - Prev Close) * Volume
+/// Force Index = EMA(Raw Force, period)
+///
+/// Signals:
+/// - Above zero: Buyers in control (bullish)
+/// - Below zero: Sellers in control (bearish)
+/// - A zero-line crossing on the latest bar is a strong signal
+///
+/// Requires volume on every candle.
+pub struct ForceIndex {
+    period: usize,
+    name: String,
+}
+
+impl Default for ForceIndex {
+    fn default() -> Self {
+        Self::new(13)
+    }
+}
+
+impl ForceIndex {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            name: format!("Force Index ({})", period),
+        }
+    }
+
+    /// Raw force for every bar after the first, or None if any volume is missing.
+    fn raw_force(candles: &[OhlcPoint]) -> Option<Vec<f64>> {
+        candles
+            .windows(2)
+            .map(|pair| Some((pair[1].close - pair[0].close) * pair[1].volume?))
+            .collect()
+    }
+}
+
+impl Signal for ForceIndex {
+    fn id(&self) -> &str {
+        "force_index"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Volume
+    }
+
+    fn min_periods(&self) -> usize {
+        // One extra bar so the previous reading is available for crossovers
+        self.period + 2
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if candles.len() < self.min_periods() {
+            return None;
+        }
+
+        let raw = Self::raw_force(candles)?;
+        let smoothed = Ema::series(&raw, self.period);
+        let force = *smoothed.last()?;
+        let previous = smoothed[smoothed.len() - 2];
+
+        // Force is in price * volume units; compare it to the typical raw
+        // force over the smoothing window so the score is scale-free
+        let recent = &raw[raw.len() - self.period..];
+        let typical = recent.iter().map(|f| f.abs()).sum::<f64>() / self.period as f64;
+        let mut score = if typical > 0.0 {
+            force / typical * 100.0
+        } else {
+            0.0
+        };
+
+        // Fresh zero-line crossing on the latest bar
+        if force != 0.0 && previous != 0.0 && force.signum() != previous.signum() {
+            score = force.signum() * (score.abs() + 60.0);
+        }
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            force,
+            clamp_score(score),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(i: usize, close: f64, volume: Option<f64>) -> OhlcPoint {
+        OhlcPoint {
+            time: 1000000 + i as i64 * 60000,
+            open: close,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+            volume,
+        }
+    }
+
+    /// Closes alternating around 100 on steady volume.
+    fn create_choppy_candles(count: usize) -> Vec<OhlcPoint> {
+        (0..count)
+            .map(|i| {
+                let close = if i % 2 == 0 { 100.0 } else { 100.5 };
+                candle(i, close, Some(1000.0))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_force_index_id_and_name() {
+        let force = ForceIndex::default();
+        assert_eq!(force.id(), "force_index");
+        assert_eq!(force.name(), "Force Index (13)");
+        assert_eq!(force.category(), SignalCategory::Volume);
+        assert_eq!(force.min_periods(), 15);
+    }
+
+    #[test]
+    fn test_force_index_insufficient_data() {
+        let candles = create_choppy_candles(10);
+        assert!(ForceIndex::default().calculate(&candles).is_none());
+    }
+
+    #[test]
+    fn test_force_index_strong_up_close_on_high_volume() {
+        let force = ForceIndex::default();
+        let mut candles = create_choppy_candles(30);
+        let baseline = force.calculate(&candles).unwrap();
+
+        // +10 close on 20x the usual volume
+        candles.push(candle(30, 110.0, Some(20_000.0)));
+        let output = force.calculate(&candles).unwrap();
+
+        // One bar contributes 2 / (13 + 1) of 200,000
+        assert!(output.value > 25_000.0, "force {}", output.value);
+        assert!(output.value > baseline.value.abs() * 10.0);
+        assert!(output.score >= 60, "score {}", output.score);
+    }
+
+    #[test]
+    fn test_force_index_sign_follows_trend() {
+        let force = ForceIndex::new(5);
+        let falling: Vec<OhlcPoint> = (0..20)
+            .map(|i| candle(i, 200.0 - i as f64, Some(1000.0)))
+            .collect();
+        let output = force.calculate(&falling).unwrap();
+        assert!(output.value < 0.0);
+        assert!(output.score < 0);
+    }
+
+    #[test]
+    fn test_force_index_requires_volume() {
+        let force = ForceIndex::default();
+        let mut candles = create_choppy_candles(30);
+        candles[20].volume = None;
+        assert!(force.calculate(&candles).is_none());
+    }
+}
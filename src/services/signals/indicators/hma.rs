@@ -0,0 +1,218 @@
+//! Hull Moving Average (HMA) indicator.
+
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// HMA (Hull Moving Average) indicator.
+///
+/// A low-lag moving average built from weighted moving averages:
+/// HMA = WMA(2 * WMA(n/2) - WMA(n), sqrt(n))
+///
+/// Signal based on price position relative to HMA:
+/// - Price above HMA = bullish
+/// - Price below HMA = bearish
+/// - Price crossing the HMA on the latest bar is a strong signal
+pub struct Hma {
+    period: usize,
+    name: String,
+}
+
+impl Default for Hma {
+    fn default() -> Self {
+        Self::new(16)
+    }
+}
+
+impl Hma {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            name: format!("HMA ({})", period),
+        }
+    }
+
+    fn half_period(&self) -> usize {
+        (self.period / 2).max(1)
+    }
+
+    fn sqrt_period(&self) -> usize {
+        ((self.period as f64).sqrt().round() as usize).max(1)
+    }
+
+    /// Linearly weighted moving average of every full window (newest weighted most).
+    pub fn wma_series(values: &[f64], period: usize) -> Vec<f64> {
+        if period == 0 || values.len() < period {
+            return Vec::new();
+        }
+
+        let weight_sum = (period * (period + 1)) as f64 / 2.0;
+        values
+            .windows(period)
+            .map(|window| {
+                window
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| v * (i + 1) as f64)
+                    .sum::<f64>()
+                    / weight_sum
+            })
+            .collect()
+    }
+
+    /// Calculate the HMA for every bar with full nested windows.
+    fn hma_series(&self, closes: &[f64]) -> Vec<f64> {
+        let half = Self::wma_series(closes, self.half_period());
+        let full = Self::wma_series(closes, self.period);
+
+        // Align the half-period WMA with the full one (it starts earlier)
+        let offset = half.len() - full.len().min(half.len());
+        let diff: Vec<f64> = half
+            .iter()
+            .skip(offset)
+            .zip(full.iter())
+            .map(|(h, f)| 2.0 * h - f)
+            .collect();
+
+        Self::wma_series(&diff, self.sqrt_period())
+    }
+}
+
+impl Signal for Hma {
+    fn id(&self) -> &str {
+        "hma"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Trend
+    }
+
+    fn min_periods(&self) -> usize {
+        // WMA(n) needs n bars, the outer WMA needs sqrt(n) of those, plus one
+        // extra bar so the previous reading is available for crossovers
+        self.period + self.sqrt_period()
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if candles.len() < self.min_periods() {
+            return None;
+        }
+
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let hma = self.hma_series(&closes);
+        let current_hma = *hma.last()?;
+        let previous_hma = hma[hma.len() - 2];
+        if current_hma == 0.0 {
+            return None;
+        }
+
+        let price = closes[closes.len() - 1];
+        let previous_price = closes[closes.len() - 2];
+
+        // Scale so that 5% deviation = full signal
+        let pct_diff = (price - current_hma) / current_hma * 100.0;
+        let mut score = pct_diff * 20.0;
+
+        // Fresh crossover on the latest bar
+        let spread = price - current_hma;
+        let previous_spread = previous_price - previous_hma;
+        if spread != 0.0 && previous_spread != 0.0 && spread.signum() != previous_spread.signum() {
+            score = spread.signum() * (score.abs() + 60.0);
+        }
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            current_hma,
+            clamp_score(score),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Flat at 100 for `flat` bars, then flat at 120 for `after` bars.
+    fn create_step_candles(flat: usize, after: usize) -> Vec<OhlcPoint> {
+        (0..flat + after)
+            .map(|i| {
+                let close = if i < flat { 100.0 } else { 120.0 };
+                OhlcPoint {
+                    time: 1000000 + i as i64 * 60000,
+                    open: close,
+                    high: close + 1.0,
+                    low: close - 1.0,
+                    close,
+                    volume: Some(1000.0),
+                }
+            })
+            .collect()
+    }
+
+    fn sma(candles: &[OhlcPoint], period: usize) -> f64 {
+        candles[candles.len() - period..]
+            .iter()
+            .map(|c| c.close)
+            .sum::<f64>()
+            / period as f64
+    }
+
+    #[test]
+    fn test_hma_id_and_name() {
+        let hma = Hma::default();
+        assert_eq!(hma.id(), "hma");
+        assert_eq!(hma.name(), "HMA (16)");
+        assert_eq!(hma.category(), SignalCategory::Trend);
+        assert_eq!(hma.min_periods(), 20);
+    }
+
+    #[test]
+    fn test_hma_insufficient_data() {
+        let hma = Hma::default();
+        assert!(hma.calculate(&create_step_candles(19, 0)).is_none());
+        assert!(hma.calculate(&create_step_candles(20, 0)).is_some());
+    }
+
+    #[test]
+    fn test_hma_flat_series_matches_price() {
+        let output = Hma::default()
+            .calculate(&create_step_candles(40, 0))
+            .unwrap();
+        assert!((output.value - 100.0).abs() < 1e-9);
+        assert_eq!(output.score, 0);
+    }
+
+    #[test]
+    fn test_hma_reacts_faster_than_sma() {
+        let hma = Hma::default();
+        for after in 1..=8 {
+            let candles = create_step_candles(40, after);
+            let hma_value = hma.calculate(&candles).unwrap().value;
+            let sma_value = sma(&candles, 16);
+            assert!(
+                hma_value > sma_value,
+                "{} bars after step: HMA {} vs SMA {}",
+                after,
+                hma_value,
+                sma_value
+            );
+        }
+
+        // Several bars in, HMA has nearly caught up while SMA lags well behind
+        let candles = create_step_candles(40, 6);
+        assert!(hma.calculate(&candles).unwrap().value > 115.0);
+        assert!(sma(&candles, 16) < 110.0);
+    }
+
+    #[test]
+    fn test_hma_price_crossover_is_strong() {
+        let output = Hma::default()
+            .calculate(&create_step_candles(40, 1))
+            .unwrap();
+        assert!(output.score >= 60, "crossover score {}", output.score);
+    }
+}
@@ -0,0 +1,217 @@
+//! Know Sure Thing (KST) indicator.
+
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// Pring's default (ROC period, SMA smoothing, weight) for each component.
+const DEFAULT_COMPONENTS: [(usize, usize, f64); 4] =
+    [(10, 10, 1.0), (15, 10, 2.0), (20, 10, 3.0), (30, 15, 4.0)];
+
+/// KST (Know Sure Thing) indicator.
+///
+/// A weighted sum of four smoothed rates of change, so short and long
+/// cycles both contribute:
+/// - KST = 1*SMA(ROC(10), 10) + 2*SMA(ROC(15), 10) + 3*SMA(ROC(20), 10) + 4*SMA(ROC(30), 15)
+/// - Signal Line = SMA(9) of KST
+///
+/// Signals:
+/// - KST above zero: Bullish; below zero: Bearish
+/// - KST crossing its signal line on the latest bar is a strong signal
+///
+/// The output value is the KST line.
+pub struct Kst {
+    components: [(usize, usize, f64); 4],
+    signal_period: usize,
+}
+
+impl Default for Kst {
+    fn default() -> Self {
+        Self::new(DEFAULT_COMPONENTS, 9)
+    }
+}
+
+impl Kst {
+    pub fn new(components: [(usize, usize, f64); 4], signal_period: usize) -> Self {
+        Self {
+            components,
+            signal_period,
+        }
+    }
+
+    /// Bars needed before the first KST value is available.
+    fn warmup(&self) -> usize {
+        self.components
+            .iter()
+            .map(|(roc, smooth, _)| roc + smooth)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Smoothed rate of change (%) for every bar with a full window, aligned to the end.
+    fn smoothed_roc(closes: &[f64], roc_period: usize, smooth_period: usize) -> Vec<f64> {
+        let roc: Vec<f64> = closes
+            .windows(roc_period + 1)
+            .map(|w| {
+                let base = w[0];
+                if base == 0.0 {
+                    0.0
+                } else {
+                    (w[roc_period] - base) / base * 100.0
+                }
+            })
+            .collect();
+
+        roc.windows(smooth_period)
+            .map(|w| w.iter().sum::<f64>() / smooth_period as f64)
+            .collect()
+    }
+
+    /// Calculate the KST line for every bar where all components are available.
+    fn kst_series(&self, closes: &[f64]) -> Vec<f64> {
+        let len = closes.len() + 1 - self.warmup().min(closes.len() + 1);
+        let mut kst = vec![0.0; len];
+
+        for &(roc_period, smooth_period, weight) in &self.components {
+            let smoothed = Self::smoothed_roc(closes, roc_period, smooth_period);
+            // Components with shorter windows start earlier; keep the tail
+            for (total, value) in kst.iter_mut().zip(&smoothed[smoothed.len() - len..]) {
+                *total += weight * value;
+            }
+        }
+
+        kst
+    }
+
+    /// Simple moving average of every full window.
+    fn signal_series(&self, kst: &[f64]) -> Vec<f64> {
+        kst.windows(self.signal_period)
+            .map(|w| w.iter().sum::<f64>() / self.signal_period as f64)
+            .collect()
+    }
+}
+
+impl Signal for Kst {
+    fn id(&self) -> &str {
+        "kst"
+    }
+
+    fn name(&self) -> &str {
+        "KST"
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Momentum
+    }
+
+    fn min_periods(&self) -> usize {
+        // Longest ROC + smoothing, a full signal window, and one extra bar so
+        // the previous reading is available for crossovers
+        self.warmup() + self.signal_period
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if self.signal_period == 0 || candles.len() < self.min_periods() {
+            return None;
+        }
+
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let kst = self.kst_series(&closes);
+        let signal = self.signal_series(&kst);
+
+        let current = *kst.last()?;
+        let current_signal = *signal.last()?;
+        let previous_diff = kst[kst.len() - 2] - signal[signal.len() - 2];
+        let diff = current - current_signal;
+
+        // Zero-line position contributes up to 30 points
+        let position = (current * 2.0).clamp(-30.0, 30.0);
+        // A 5 point gap to the signal line contributes 70 points
+        let mut score = position + diff * 14.0;
+
+        // Fresh signal line crossover on the latest bar
+        if diff != 0.0 && previous_diff != 0.0 && diff.signum() != previous_diff.signum() {
+            score = diff.signum() * (score.abs() + 60.0);
+        }
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            current,
+            clamp_score(score),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Flat at 100 for `flat` bars, then compounding by `rate` per bar.
+    fn create_trend_candles(flat: usize, trend: usize, rate: f64) -> Vec<OhlcPoint> {
+        let mut price = 100.0;
+        (0..flat + trend)
+            .map(|i| {
+                if i >= flat {
+                    price *= 1.0 + rate;
+                }
+                OhlcPoint {
+                    time: 1000000 + i as i64 * 60000,
+                    open: price,
+                    high: price * 1.01,
+                    low: price * 0.99,
+                    close: price,
+                    volume: Some(1000.0),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_kst_id_and_name() {
+        let kst = Kst::default();
+        assert_eq!(kst.id(), "kst");
+        assert_eq!(kst.name(), "KST");
+        assert_eq!(kst.category(), SignalCategory::Momentum);
+        assert_eq!(kst.min_periods(), 54);
+    }
+
+    #[test]
+    fn test_kst_insufficient_data() {
+        let kst = Kst::default();
+        assert!(kst.calculate(&create_trend_candles(53, 0, 0.0)).is_none());
+        assert!(kst.calculate(&create_trend_candles(54, 0, 0.0)).is_some());
+    }
+
+    #[test]
+    fn test_kst_flat_series_is_neutral() {
+        let output = Kst::default()
+            .calculate(&create_trend_candles(80, 0, 0.0))
+            .unwrap();
+        assert_eq!(output.value, 0.0);
+        assert_eq!(output.score, 0);
+    }
+
+    #[test]
+    fn test_kst_uptrend_rises_above_signal_line() {
+        let kst = Kst::default();
+        let candles = create_trend_candles(60, 30, 0.01);
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+
+        let line = kst.kst_series(&closes);
+        let signal = kst.signal_series(&line);
+        assert!(line.last().unwrap() > signal.last().unwrap());
+
+        let output = kst.calculate(&candles).unwrap();
+        assert!(output.value > 0.0);
+        assert!(output.score > 0, "score {}", output.score);
+    }
+
+    #[test]
+    fn test_kst_downtrend_is_bearish() {
+        let output = Kst::default()
+            .calculate(&create_trend_candles(60, 30, -0.01))
+            .unwrap();
+        assert!(output.value < 0.0);
+        assert!(output.score < 0);
+    }
+}
@@ -16,19 +16,32 @@ pub struct Macd {
     fast_period: usize,
     slow_period: usize,
     signal_period: usize,
+    id: String,
+    name: String,
 }
 
 impl Default for Macd {
     fn default() -> Self {
-        Self {
-            fast_period: 12,
-            slow_period: 26,
-            signal_period: 9,
-        }
+        Self::new(12, 26, 9)
     }
 }
 
 impl Macd {
+    /// Create a MACD with custom periods. `fast_period` must be below `slow_period`.
+    ///
+    /// The default 12/26/9 periods keep the plain `macd` id; any other periods
+    /// are spelled out in the id and name so custom variants stay distinct.
+    pub fn new(fast_period: usize, slow_period: usize, signal_period: usize) -> Self {
+        let (id, name) = periods_label("macd", "MACD", fast_period, slow_period, signal_period);
+        Self {
+            fast_period,
+            slow_period,
+            signal_period,
+            id,
+            name,
+        }
+    }
+
     /// Calculate EMA for a series of values.
     fn calculate_ema(values: &[f64], period: usize) -> Vec<f64> {
         if values.len() < period {
@@ -50,37 +63,15 @@ impl Macd {
 
         ema
     }
-}
-
-impl Signal for Macd {
-    fn id(&self) -> &str {
-        "macd"
-    }
 
-    fn name(&self) -> &str {
-        "MACD"
-    }
-
-    fn category(&self) -> SignalCategory {
-        SignalCategory::Trend
-    }
-
-    fn min_periods(&self) -> usize {
-        self.slow_period + self.signal_period
-    }
-
-    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
-        if candles.len() < self.min_periods() {
-            return None;
-        }
-
-        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
-
-        let fast_ema = Self::calculate_ema(&closes, self.fast_period);
-        let slow_ema = Self::calculate_ema(&closes, self.slow_period);
+    /// Calculate the histogram (MACD line - signal line) for every bar with a
+    /// signal line value. Empty if there is not enough data.
+    pub fn histogram_series(&self, closes: &[f64]) -> Vec<f64> {
+        let fast_ema = Self::calculate_ema(closes, self.fast_period);
+        let slow_ema = Self::calculate_ema(closes, self.slow_period);
 
         if fast_ema.is_empty() || slow_ema.is_empty() {
-            return None;
+            return Vec::new();
         }
 
         // Calculate MACD line (fast EMA - slow EMA)
@@ -94,25 +85,68 @@ impl Signal for Macd {
             .collect();
 
         if macd_line.len() < self.signal_period {
-            return None;
+            return Vec::new();
         }
 
-        // Calculate signal line (EMA of MACD)
+        // Calculate signal line (EMA of MACD), aligned to the end of the MACD line
         let signal_line = Self::calculate_ema(&macd_line, self.signal_period);
+        let offset = macd_line.len() - signal_line.len();
+        macd_line[offset..]
+            .iter()
+            .zip(signal_line.iter())
+            .map(|(m, s)| m - s)
+            .collect()
+    }
+}
+
+/// Id and display name for a fast/slow/signal indicator, with the periods
+/// appended unless they are the standard 12/26/9.
+pub(crate) fn periods_label(
+    id: &str,
+    name: &str,
+    fast_period: usize,
+    slow_period: usize,
+    signal_period: usize,
+) -> (String, String) {
+    if (fast_period, slow_period, signal_period) == (12, 26, 9) {
+        (id.to_string(), name.to_string())
+    } else {
+        (
+            format!("{}_{}_{}_{}", id, fast_period, slow_period, signal_period),
+            format!("{} ({}, {}, {})", name, fast_period, slow_period, signal_period),
+        )
+    }
+}
+
+impl Signal for Macd {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Trend
+    }
 
-        if signal_line.is_empty() {
+    fn min_periods(&self) -> usize {
+        self.slow_period + self.signal_period
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if candles.len() < self.min_periods() {
             return None;
         }
 
-        // Get current values
-        let macd = *macd_line.last().unwrap();
-        let signal = *signal_line.last().unwrap();
-        let histogram = macd - signal;
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let histograms = self.histogram_series(&closes);
 
-        // Get previous histogram for momentum
-        let prev_histogram = if macd_line.len() > 1 && signal_line.len() > 1 {
-            macd_line[macd_line.len() - 2]
-                - signal_line.get(signal_line.len() - 2).unwrap_or(&signal)
+        // Get current and previous histogram for momentum
+        let histogram = *histograms.last()?;
+        let prev_histogram = if histograms.len() > 1 {
+            histograms[histograms.len() - 2]
         } else {
             histogram
         };
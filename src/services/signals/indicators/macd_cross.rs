@@ -0,0 +1,186 @@
+//! MACD histogram zero-cross indicator.
+
+use super::macd::periods_label;
+use super::Macd;
+use crate::services::signals::{make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// Score emitted on the bar the histogram crosses zero.
+const CROSS_SCORE: i8 = 100;
+
+/// MACD histogram crossover indicator.
+///
+/// Fires on the event rather than the level: the score is only non-zero on
+/// the bar where the MACD histogram (MACD Line - Signal Line) crosses zero.
+/// - Histogram crosses above zero = strong bullish
+/// - Histogram crosses below zero = strong bearish
+/// - Any other bar = neutral
+///
+/// The output value is the current histogram.
+pub struct MacdCross {
+    macd: Macd,
+    id: String,
+    name: String,
+}
+
+impl Default for MacdCross {
+    fn default() -> Self {
+        Self::new(12, 26, 9)
+    }
+}
+
+impl MacdCross {
+    pub fn new(fast_period: usize, slow_period: usize, signal_period: usize) -> Self {
+        let (id, name) =
+            periods_label("macd_cross", "MACD Cross", fast_period, slow_period, signal_period);
+        Self {
+            macd: Macd::new(fast_period, slow_period, signal_period),
+            id,
+            name,
+        }
+    }
+}
+
+impl Signal for MacdCross {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Trend
+    }
+
+    fn min_periods(&self) -> usize {
+        // Same as MACD, which leaves two histogram values to compare
+        self.macd.min_periods()
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if candles.len() < self.min_periods() {
+            return None;
+        }
+
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let histograms = self.macd.histogram_series(&closes);
+        if histograms.len() < 2 {
+            return None;
+        }
+
+        let histogram = histograms[histograms.len() - 1];
+        let previous = histograms[histograms.len() - 2];
+
+        let score = if previous <= 0.0 && histogram > 0.0 {
+            CROSS_SCORE
+        } else if previous >= 0.0 && histogram < 0.0 {
+            -CROSS_SCORE
+        } else {
+            0
+        };
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            histogram,
+            score,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_candles(closes: impl Iterator<Item = f64>) -> Vec<OhlcPoint> {
+        closes
+            .enumerate()
+            .map(|(i, close)| OhlcPoint {
+                time: 1000000 + i as i64 * 60000,
+                open: close,
+                high: close + 1.0,
+                low: close - 1.0,
+                close,
+                volume: Some(1000.0),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_macd_cross_id_and_name() {
+        let cross = MacdCross::default();
+        assert_eq!(cross.id(), "macd_cross");
+        assert_eq!(cross.name(), "MACD Cross");
+        assert_eq!(cross.category(), SignalCategory::Trend);
+        assert_eq!(cross.min_periods(), 35);
+    }
+
+    #[test]
+    fn test_macd_cross_insufficient_data() {
+        let cross = MacdCross::default();
+        assert!(cross
+            .calculate(&create_candles((0..34).map(|i| 100.0 + i as f64)))
+            .is_none());
+    }
+
+    #[test]
+    fn test_macd_cross_fires_only_on_crossing_bar() {
+        let cross = MacdCross::default();
+
+        // Accelerating decline then recovery: the histogram turns positive exactly once
+        let turn = 45;
+        let bottom = 300.0 - 0.05 * (turn * turn) as f64;
+        let closes = (0..75).map(|i| {
+            if i < turn {
+                300.0 - 0.05 * (i * i) as f64
+            } else {
+                bottom + (i - turn) as f64 * 3.0
+            }
+        });
+        let candles = create_candles(closes);
+
+        let fired: Vec<(usize, i8)> = (cross.min_periods()..=candles.len())
+            .filter_map(|end| {
+                let output = cross.calculate(&candles[..end]).unwrap();
+                (output.score != 0).then_some((end - 1, output.score))
+            })
+            .collect();
+        assert_eq!(fired.len(), 1, "fired on {:?}", fired);
+
+        let (bar, score) = fired[0];
+        assert_eq!(score, 100);
+        assert!(bar >= turn, "crossed at bar {}", bar);
+
+        // The histogram flips sign on that bar and no other
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let histograms = Macd::default().histogram_series(&closes);
+        let offset = candles.len() - histograms.len();
+        assert!(histograms[bar - offset - 1] < 0.0);
+        assert!(histograms[bar - offset] > 0.0);
+
+        // The bars either side of the event are neutral
+        assert_eq!(cross.calculate(&candles[..bar]).unwrap().score, 0);
+        assert_eq!(cross.calculate(&candles[..bar + 2]).unwrap().score, 0);
+    }
+
+    #[test]
+    fn test_macd_cross_bearish_event() {
+        let cross = MacdCross::default();
+        let turn = 45;
+        let candles = create_candles((0..60).map(|i| {
+            if i < turn {
+                100.0 + 0.05 * (i * i) as f64
+            } else {
+                100.0 + 0.05 * (turn * turn) as f64 - (i - turn) as f64 * 3.0
+            }
+        }));
+
+        let scores: Vec<i8> = (cross.min_periods()..=candles.len())
+            .map(|end| cross.calculate(&candles[..end]).unwrap().score)
+            .collect();
+        assert_eq!(scores.iter().filter(|s| **s != 0).count(), 1);
+        assert!(scores.contains(&-100));
+    }
+}
@@ -0,0 +1,236 @@
+//! Mass Index indicator.
+
+use super::Ema;
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// Mass Index level that sets up a reversal bulge.
+const BULGE_SETUP: f64 = 27.0;
+/// Mass Index level that completes a reversal bulge after a setup.
+const BULGE_TRIGGER: f64 = 26.5;
+/// Bars after a completed bulge during which the reversal is still signalled.
+const REVERSAL_BARS: usize = 3;
+
+/// Reversal bulge state on the latest bar.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Bulge {
+    /// No bulge in progress.
+    None,
+    /// Mass Index rose above 27 and has not yet fallen back below 26.5.
+    Setup,
+    /// Bulge completed this many bars ago.
+    Reversal(usize),
+}
+
+/// Mass Index indicator.
+///
+/// Detects trend reversals from range expansion, without regard to direction:
+/// - Ratio = EMA(High - Low, 9) / EMA(EMA(High - Low, 9), 9)
+/// - Mass Index = Sum(Ratio, 25)
+///
+/// A "reversal bulge" is the Mass Index rising above 27 and then dropping
+/// below 26.5. The direction comes from the prevailing trend, which the
+/// bulge is expected to reverse:
+/// - Bulge completed in an uptrend = bearish
+/// - Bulge completed in a downtrend = bullish
+/// - Bulge set up but not yet completed = weak early warning
+///
+/// The output value is the Mass Index.
+pub struct MassIndex {
+    ema_period: usize,
+    sum_period: usize,
+}
+
+impl Default for MassIndex {
+    fn default() -> Self {
+        Self::new(9, 25)
+    }
+}
+
+impl MassIndex {
+    pub fn new(ema_period: usize, sum_period: usize) -> Self {
+        Self {
+            ema_period,
+            sum_period,
+        }
+    }
+
+    /// Calculate the Mass Index for every bar with a full sum window.
+    fn mass_series(&self, candles: &[OhlcPoint]) -> Vec<f64> {
+        let ranges: Vec<f64> = candles.iter().map(|c| c.high - c.low).collect();
+        let single = Ema::series(&ranges, self.ema_period);
+        let double = Ema::series(&single, self.ema_period);
+
+        // Align the single EMA with the double one (it starts earlier)
+        let offset = single.len() - double.len().min(single.len());
+        let ratios: Vec<f64> = single
+            .iter()
+            .skip(offset)
+            .zip(double.iter())
+            .map(|(s, d)| if *d == 0.0 { 1.0 } else { s / d })
+            .collect();
+
+        ratios
+            .windows(self.sum_period)
+            .map(|w| w.iter().sum())
+            .collect()
+    }
+
+    /// Walk the Mass Index history to find the bulge state on the latest bar.
+    fn bulge(series: &[f64]) -> Bulge {
+        let mut state = Bulge::None;
+        for &mass in series {
+            state = match state {
+                _ if mass > BULGE_SETUP => Bulge::Setup,
+                Bulge::Setup if mass < BULGE_TRIGGER => Bulge::Reversal(0),
+                Bulge::Reversal(bars) => Bulge::Reversal(bars + 1),
+                other => other,
+            };
+        }
+        state
+    }
+}
+
+impl Signal for MassIndex {
+    fn id(&self) -> &str {
+        "mass_index"
+    }
+
+    fn name(&self) -> &str {
+        "Mass Index"
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Volatility
+    }
+
+    fn min_periods(&self) -> usize {
+        // Two EMA passes followed by the sum window
+        2 * self.ema_period + self.sum_period - 2
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if self.ema_period == 0 || self.sum_period == 0 || candles.len() < self.min_periods() {
+            return None;
+        }
+
+        let series = self.mass_series(candles);
+        let mass = *series.last()?;
+
+        // Prevailing trend over the sum window, which a bulge reverses
+        let latest = candles.last()?.close;
+        let earlier = candles[candles.len() - self.sum_period].close;
+        let trend = if latest > earlier {
+            1.0
+        } else if latest < earlier {
+            -1.0
+        } else {
+            0.0
+        };
+
+        let score = match Self::bulge(&series) {
+            Bulge::Reversal(bars) if bars < REVERSAL_BARS => -trend * (80.0 - 15.0 * bars as f64),
+            Bulge::Setup => -trend * 30.0,
+            _ => 0.0,
+        };
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            mass,
+            clamp_score(score),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rising closes with a 2-point range, widened to `wide` for bars in `expansion`.
+    fn create_candles(
+        count: usize,
+        expansion: std::ops::Range<usize>,
+        wide: f64,
+    ) -> Vec<OhlcPoint> {
+        (0..count)
+            .map(|i| {
+                let close = 100.0 + i as f64 * 0.5;
+                let half_range = if expansion.contains(&i) {
+                    wide / 2.0
+                } else {
+                    1.0
+                };
+                OhlcPoint {
+                    time: 1000000 + i as i64 * 60000,
+                    open: close,
+                    high: close + half_range,
+                    low: close - half_range,
+                    close,
+                    volume: Some(1000.0),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_mass_index_id_and_name() {
+        let mass = MassIndex::default();
+        assert_eq!(mass.id(), "mass_index");
+        assert_eq!(mass.name(), "Mass Index");
+        assert_eq!(mass.category(), SignalCategory::Volatility);
+        assert_eq!(mass.min_periods(), 41);
+    }
+
+    #[test]
+    fn test_mass_index_insufficient_data() {
+        let mass = MassIndex::default();
+        assert!(mass.calculate(&create_candles(40, 0..0, 0.0)).is_none());
+        assert!(mass.calculate(&create_candles(41, 0..0, 0.0)).is_some());
+    }
+
+    #[test]
+    fn test_mass_index_constant_range_is_quiet() {
+        let output = MassIndex::default()
+            .calculate(&create_candles(80, 0..0, 0.0))
+            .unwrap();
+        assert!((output.value - 25.0).abs() < 1e-9);
+        assert_eq!(output.score, 0);
+    }
+
+    #[test]
+    fn test_mass_index_range_expansion_produces_bulge() {
+        let mass = MassIndex::default();
+        let candles = create_candles(120, 60..66, 8.0);
+        let series = mass.mass_series(&candles);
+        let offset = candles.len() - series.len();
+
+        // Expansion pushes the index through the setup level
+        let setup = series.iter().position(|m| *m > BULGE_SETUP).unwrap();
+        let during = mass.calculate(&candles[..setup + offset + 1]).unwrap();
+        assert!(during.value > BULGE_SETUP);
+        assert_eq!(during.score, -30, "setup in uptrend warns bearish");
+
+        // Then falls back through the trigger, completing the bulge
+        let trigger = setup
+            + series[setup..]
+                .iter()
+                .position(|m| *m < BULGE_TRIGGER)
+                .unwrap();
+        let window = &candles[..trigger + offset + 1];
+        assert_eq!(
+            MassIndex::bulge(&mass.mass_series(window)),
+            Bulge::Reversal(0)
+        );
+
+        let output = mass.calculate(window).unwrap();
+        assert!(output.value < BULGE_TRIGGER);
+        assert_eq!(output.score, -80, "bulge in uptrend is bearish");
+
+        // The reversal signal fades after a few bars
+        let later = mass
+            .calculate(&candles[..trigger + offset + 1 + REVERSAL_BARS])
+            .unwrap();
+        assert_eq!(later.score, 0);
+    }
+}
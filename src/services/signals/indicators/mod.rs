@@ -1,30 +1,78 @@
 //! Technical indicator implementations.
 
+pub mod adl;
 pub mod adx;
+pub mod ao;
+pub mod aroon;
 pub mod atr;
 pub mod bollinger;
 pub mod cci;
+pub mod chaikin_osc;
+pub mod cmo;
+pub mod coppock;
+pub mod dema;
+pub mod dpo;
+pub mod elder_ray;
 pub mod ema;
+pub mod eom;
+pub mod fisher;
+pub mod force_index;
+pub mod hma;
+pub mod kst;
 pub mod macd;
+pub mod macd_cross;
+pub mod mass_index;
 pub mod mfi;
+pub mod momentum;
 pub mod obv;
+pub mod ppo;
 pub mod rsi;
 pub mod sma;
+pub mod stddev;
 pub mod stochastic;
+pub mod tema;
+pub mod trix;
+pub mod ultimate;
+pub mod vortex;
 pub mod vwap;
+pub mod vwma;
 
+pub use adl::Adl;
 pub use adx::Adx;
+pub use ao::Ao;
+pub use aroon::Aroon;
 pub use atr::Atr;
 pub use bollinger::BollingerBands;
 pub use cci::Cci;
+pub use chaikin_osc::ChaikinOsc;
+pub use cmo::Cmo;
+pub use coppock::Coppock;
+pub use dema::Dema;
+pub use dpo::Dpo;
+pub use elder_ray::ElderRay;
 pub use ema::Ema;
+pub use eom::Eom;
+pub use fisher::Fisher;
+pub use force_index::ForceIndex;
+pub use hma::Hma;
+pub use kst::Kst;
 pub use macd::Macd;
+pub use macd_cross::MacdCross;
+pub use mass_index::MassIndex;
 pub use mfi::Mfi;
+pub use momentum::Momentum;
 pub use obv::Obv;
+pub use ppo::Ppo;
 pub use rsi::Rsi;
 pub use sma::Sma;
+pub use stddev::StdDev;
 pub use stochastic::Stochastic;
+pub use tema::Tema;
+pub use trix::Trix;
+pub use ultimate::UltimateOscillator;
+pub use vortex::Vortex;
 pub use vwap::Vwap;
+pub use vwma::Vwma;
 
 use super::Signal;
 
@@ -37,18 +85,44 @@ pub fn all_indicators() -> Vec<Box<dyn Signal>> {
         Box::new(Sma::new(200)), // For golden/death cross detection
         Box::new(Ema::new(12)),
         Box::new(Ema::new(26)),
+        Box::new(Vwma::new(20)),
+        Box::new(Vwma::new(50)),
+        Box::new(Hma::default()),
+        Box::new(Dema::new(20)),
+        Box::new(Tema::new(20)),
         Box::new(Macd::default()),
+        Box::new(MacdCross::default()),
         Box::new(Adx::default()),
+        Box::new(Vortex::default()),
+        Box::new(Aroon::default()),
         // Momentum indicators
         Box::new(Rsi::default()),
         Box::new(Stochastic::default()),
         Box::new(Cci::default()),
         Box::new(Mfi::default()),
+        Box::new(Cmo::default()),
+        Box::new(Ao::default()),
+        Box::new(Fisher::default()),
+        Box::new(Ppo::default()),
+        Box::new(Kst::default()),
+        Box::new(Coppock::default()),
+        Box::new(UltimateOscillator::default()),
+        Box::new(Trix::default()),
+        Box::new(Dpo::default()),
+        Box::new(ElderRay::default()),
+        Box::new(Momentum::new(10)),
+        Box::new(Momentum::new(20)),
         // Volatility indicators
         Box::new(BollingerBands::default()),
         Box::new(Atr::default()),
+        Box::new(StdDev::default()),
+        Box::new(MassIndex::default()),
         // Volume indicators
         Box::new(Obv::default()),
+        Box::new(Adl::default()),
+        Box::new(ChaikinOsc::default()),
         Box::new(Vwap::default()),
+        Box::new(Eom::default()),
+        Box::new(ForceIndex::default()),
     ]
 }
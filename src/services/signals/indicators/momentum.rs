@@ -0,0 +1,156 @@
+//! Momentum (MOM) indicator.
+
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// Momentum indicator.
+///
+/// The absolute price change over a lookback:
+/// MOM = Close - Close (period bars ago)
+///
+/// Unlike ROC it stays in price units. For scoring, the change is compared
+/// to the total distance travelled bar to bar over the same window, so a
+/// straight move scores fully and a choppy one scores little:
+/// - Positive: Price higher than `period` bars ago (bullish)
+/// - Negative: Price lower than `period` bars ago (bearish)
+pub struct Momentum {
+    period: usize,
+    name: String,
+}
+
+impl Momentum {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            name: format!("Momentum ({})", period),
+        }
+    }
+}
+
+impl Default for Momentum {
+    fn default() -> Self {
+        Self::new(10)
+    }
+}
+
+impl Signal for Momentum {
+    fn id(&self) -> &str {
+        match self.period {
+            10 => "mom10",
+            20 => "mom20",
+            _ => "mom",
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Momentum
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period + 1
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if self.period == 0 || candles.len() < self.min_periods() {
+            return None;
+        }
+
+        let window = &candles[candles.len() - self.period - 1..];
+        let momentum = window[self.period].close - window[0].close;
+
+        let path: f64 = window
+            .windows(2)
+            .map(|pair| (pair[1].close - pair[0].close).abs())
+            .sum();
+        let score = if path > 0.0 {
+            momentum / path * 100.0
+        } else {
+            0.0
+        };
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            momentum,
+            clamp_score(score),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_candles(closes: impl Iterator<Item = f64>) -> Vec<OhlcPoint> {
+        closes
+            .enumerate()
+            .map(|(i, close)| OhlcPoint {
+                time: 1000000 + i as i64 * 60000,
+                open: close,
+                high: close + 1.0,
+                low: close - 1.0,
+                close,
+                volume: Some(1000.0),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_momentum_id_and_name() {
+        let mom = Momentum::new(10);
+        assert_eq!(mom.id(), "mom10");
+        assert_eq!(mom.name(), "Momentum (10)");
+        assert_eq!(mom.category(), SignalCategory::Momentum);
+        assert_eq!(mom.min_periods(), 11);
+
+        let mom = Momentum::new(20);
+        assert_eq!(mom.id(), "mom20");
+        assert_eq!(mom.name(), "Momentum (20)");
+    }
+
+    #[test]
+    fn test_momentum_insufficient_data() {
+        let mom = Momentum::default();
+        assert!(mom
+            .calculate(&create_candles((0..10).map(|i| i as f64)))
+            .is_none());
+    }
+
+    #[test]
+    fn test_momentum_sign_matches_direction() {
+        for period in [10, 20] {
+            let mom = Momentum::new(period);
+
+            let rising = mom
+                .calculate(&create_candles((0..40).map(|i| 100.0 + i as f64 * 2.0)))
+                .unwrap();
+            assert_eq!(rising.value, period as f64 * 2.0);
+            assert_eq!(rising.score, 100);
+
+            let falling = mom
+                .calculate(&create_candles((0..40).map(|i| 200.0 - i as f64)))
+                .unwrap();
+            assert_eq!(falling.value, -(period as f64));
+            assert_eq!(falling.score, -100);
+        }
+    }
+
+    #[test]
+    fn test_momentum_choppy_series_scores_weakly() {
+        // Net +1 over the window, but the path zig-zags
+        let closes = (0..11).map(|i| 100.0 + if i % 2 == 0 { 0.0 } else { 5.0 } + i as f64 * 0.1);
+        let output = Momentum::default()
+            .calculate(&create_candles(closes))
+            .unwrap();
+        assert!((output.value - 1.0).abs() < 1e-9);
+        assert!(
+            output.score > 0 && output.score < 5,
+            "score {}",
+            output.score
+        );
+    }
+}
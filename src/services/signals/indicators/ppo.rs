@@ -0,0 +1,197 @@
+//! Percentage Price Oscillator (PPO) indicator.
+
+use super::macd::periods_label;
+use super::Ema;
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// PPO (Percentage Price Oscillator) indicator.
+///
+/// MACD expressed as a percentage of the slow EMA, so readings can be
+/// compared across symbols with different price scales:
+/// - PPO Line = (EMA(12) - EMA(26)) / EMA(26) * 100
+/// - Signal Line = EMA(9) of PPO Line
+/// - Histogram = PPO Line - Signal Line
+///
+/// Buy signal: PPO crosses above signal line
+/// Sell signal: PPO crosses below signal line
+///
+/// The output value is the histogram.
+pub struct Ppo {
+    fast_period: usize,
+    slow_period: usize,
+    signal_period: usize,
+    id: String,
+    name: String,
+}
+
+impl Default for Ppo {
+    fn default() -> Self {
+        Self::new(12, 26, 9)
+    }
+}
+
+impl Ppo {
+    pub fn new(fast_period: usize, slow_period: usize, signal_period: usize) -> Self {
+        let (id, name) = periods_label("ppo", "PPO", fast_period, slow_period, signal_period);
+        Self {
+            fast_period,
+            slow_period,
+            signal_period,
+            id,
+            name,
+        }
+    }
+
+    /// Calculate the histogram for every bar with a full signal line.
+    fn histogram(&self, candles: &[OhlcPoint]) -> Vec<f64> {
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+
+        let fast_ema = Ema::series(&closes, self.fast_period);
+        let slow_ema = Ema::series(&closes, self.slow_period);
+
+        // Align the EMAs (fast starts earlier)
+        let offset = self.slow_period.saturating_sub(self.fast_period);
+        let ppo_line: Vec<f64> = fast_ema
+            .iter()
+            .skip(offset)
+            .zip(slow_ema.iter())
+            .map(|(f, s)| if *s == 0.0 { 0.0 } else { (f - s) / s * 100.0 })
+            .collect();
+
+        let signal_line = Ema::series(&ppo_line, self.signal_period);
+        let signal_offset = ppo_line.len() - signal_line.len().min(ppo_line.len());
+        ppo_line
+            .iter()
+            .skip(signal_offset)
+            .zip(signal_line.iter())
+            .map(|(p, s)| p - s)
+            .collect()
+    }
+}
+
+impl Signal for Ppo {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Momentum
+    }
+
+    fn min_periods(&self) -> usize {
+        // One extra bar so the previous histogram is available for crossovers
+        self.slow_period + self.signal_period
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if candles.len() < self.min_periods() {
+            return None;
+        }
+
+        let histogram = self.histogram(candles);
+        let current = *histogram.last()?;
+        let previous = histogram
+            .len()
+            .checked_sub(2)
+            .map(|i| histogram[i])
+            .unwrap_or(current);
+
+        // A 0.5 point gap between PPO and its signal line is a full signal
+        let mut score = current * 200.0;
+
+        // Fresh crossover on the latest bar
+        if current != 0.0 && previous != 0.0 && current.signum() != previous.signum() {
+            score = current.signum() * (score.abs() + 60.0);
+        }
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            current,
+            clamp_score(score),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Downtrend for `down` bars, then an uptrend for `up` bars.
+    fn create_reversal_candles(down: usize, up: usize, scale: f64) -> Vec<OhlcPoint> {
+        let mut price = 200.0;
+        (0..down + up)
+            .map(|i| {
+                price += if i < down { -2.0 } else { 2.0 };
+                OhlcPoint {
+                    time: 1000000 + i as i64 * 60000,
+                    open: price * scale,
+                    high: (price + 1.0) * scale,
+                    low: (price - 1.0) * scale,
+                    close: price * scale,
+                    volume: Some(1000.0),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ppo_id_and_name() {
+        let ppo = Ppo::default();
+        assert_eq!(ppo.id(), "ppo");
+        assert_eq!(ppo.name(), "PPO");
+        assert_eq!(ppo.category(), SignalCategory::Momentum);
+        assert_eq!(ppo.min_periods(), 35);
+    }
+
+    #[test]
+    fn test_ppo_insufficient_data() {
+        let candles = create_reversal_candles(30, 0, 1.0);
+        assert!(Ppo::default().calculate(&candles).is_none());
+    }
+
+    #[test]
+    fn test_ppo_is_scale_invariant() {
+        let ppo = Ppo::default();
+        for (down, up) in [(50, 0), (40, 15), (30, 30)] {
+            let base = ppo
+                .calculate(&create_reversal_candles(down, up, 1.0))
+                .unwrap();
+            let scaled = ppo
+                .calculate(&create_reversal_candles(down, up, 10.0))
+                .unwrap();
+            assert!(
+                (base.value - scaled.value).abs() < 1e-9,
+                "{} vs {}",
+                base.value,
+                scaled.value
+            );
+            assert_eq!(base.score, scaled.score);
+        }
+    }
+
+    #[test]
+    fn test_ppo_crossover_direction() {
+        let ppo = Ppo::default();
+
+        let falling = ppo.calculate(&create_reversal_candles(50, 0, 1.0)).unwrap();
+        assert!(falling.score <= 0);
+
+        let candles = create_reversal_candles(50, 30, 1.0);
+        let histogram = ppo.histogram(&candles);
+        let cross = histogram.iter().rposition(|h| *h <= 0.0).unwrap() + 1;
+        assert!(cross < histogram.len());
+
+        // histogram[i] covers candles up to index i + min_periods - 2
+        let at_cross = ppo
+            .calculate(&candles[..cross + ppo.min_periods() - 1])
+            .unwrap();
+        assert!(at_cross.value > 0.0);
+        assert!(at_cross.score >= 60, "crossover score {}", at_cross.score);
+    }
+}
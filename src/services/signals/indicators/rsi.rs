@@ -11,18 +11,21 @@ use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
 /// - Above 70: Overbought (potential sell signal)
 pub struct Rsi {
     period: usize,
+    name: String,
 }
 
 impl Default for Rsi {
     fn default() -> Self {
-        Self { period: 14 }
+        Self::new(14)
     }
 }
 
 impl Rsi {
-    #[allow(dead_code)]
     pub fn new(period: usize) -> Self {
-        Self { period }
+        Self {
+            period,
+            name: format!("RSI ({})", period),
+        }
     }
 
     /// Calculate RSI value from price changes.
@@ -73,7 +76,7 @@ impl Signal for Rsi {
     }
 
     fn name(&self) -> &str {
-        "RSI (14)"
+        &self.name
     }
 
     fn category(&self) -> SignalCategory {
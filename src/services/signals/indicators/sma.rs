@@ -11,11 +11,15 @@ use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
 /// - Price below SMA = bearish
 pub struct Sma {
     period: usize,
+    name: String,
 }
 
 impl Sma {
     pub fn new(period: usize) -> Self {
-        Self { period }
+        Self {
+            period,
+            name: format!("SMA ({})", period),
+        }
     }
 }
 
@@ -30,12 +34,7 @@ impl Signal for Sma {
     }
 
     fn name(&self) -> &str {
-        match self.period {
-            20 => "SMA (20)",
-            50 => "SMA (50)",
-            200 => "SMA (200)",
-            _ => "SMA",
-        }
+        &self.name
     }
 
     fn category(&self) -> SignalCategory {
@@ -80,7 +79,7 @@ impl Signal for Sma {
 
 impl Default for Sma {
     fn default() -> Self {
-        Self { period: 20 }
+        Self::new(20)
     }
 }
 
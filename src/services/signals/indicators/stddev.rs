@@ -0,0 +1,198 @@
+//! Standard deviation (historical volatility) indicator.
+
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// Milliseconds in a year; crypto trades around the clock.
+const YEAR_MS: f64 = 365.0 * 24.0 * 60.0 * 60.0 * 1000.0;
+
+/// Historical volatility indicator.
+///
+/// Rolling standard deviation of one-bar log returns, annualized using the
+/// candle spacing:
+/// HV = StdDev(ln(Close / Prev Close), period) * sqrt(bars per year) * 100
+///
+/// The score describes the volatility regime rather than direction, by
+/// comparing current volatility to the longer lookback:
+/// - Volatility expanding (above its average) = negative
+/// - Volatility contracting (below its average) = positive
+///
+/// The output value is annualized volatility in percent.
+pub struct StdDev {
+    period: usize,
+    name: String,
+}
+
+impl Default for StdDev {
+    fn default() -> Self {
+        Self::new(20)
+    }
+}
+
+impl StdDev {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            name: format!("Volatility ({})", period),
+        }
+    }
+
+    /// Sample standard deviation.
+    fn std_dev(values: &[f64]) -> f64 {
+        if values.len() < 2 {
+            return 0.0;
+        }
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let variance =
+            values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+        variance.sqrt()
+    }
+
+    /// Bars per year from the median candle spacing.
+    fn bars_per_year(candles: &[OhlcPoint]) -> Option<f64> {
+        let mut spacing: Vec<i64> = candles
+            .windows(2)
+            .map(|w| w[1].time - w[0].time)
+            .filter(|d| *d > 0)
+            .collect();
+        if spacing.is_empty() {
+            return None;
+        }
+        spacing.sort_unstable();
+        Some(YEAR_MS / spacing[spacing.len() / 2] as f64)
+    }
+}
+
+impl Signal for StdDev {
+    fn id(&self) -> &str {
+        "stddev"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Volatility
+    }
+
+    fn min_periods(&self) -> usize {
+        // One extra bar for the first return
+        self.period + 1
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if self.period < 2 || candles.len() < self.min_periods() {
+            return None;
+        }
+
+        let returns: Vec<f64> = candles
+            .windows(2)
+            .filter(|w| w[0].close > 0.0 && w[1].close > 0.0)
+            .map(|w| (w[1].close / w[0].close).ln())
+            .collect();
+        if returns.len() < self.period {
+            return None;
+        }
+
+        let current = Self::std_dev(&returns[returns.len() - self.period..]);
+        let annualized = current * Self::bars_per_year(candles)?.sqrt() * 100.0;
+
+        // Compare against volatility over a longer lookback
+        let lookback = (self.period * 3).min(returns.len());
+        let average = Self::std_dev(&returns[returns.len() - lookback..]);
+        let relative_vol = if average > 0.0 {
+            (current / average - 1.0) * 100.0
+        } else {
+            0.0
+        };
+        let score = -relative_vol.clamp(-100.0, 100.0);
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            annualized,
+            clamp_score(score),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hourly candles whose closes follow `returns` (fractional per bar).
+    fn create_candles(returns: impl Iterator<Item = f64>) -> Vec<OhlcPoint> {
+        let mut price = 100.0;
+        std::iter::once(0.0)
+            .chain(returns)
+            .enumerate()
+            .map(|(i, r)| {
+                price *= 1.0 + r;
+                OhlcPoint {
+                    time: 1000000 + i as i64 * 3_600_000,
+                    open: price,
+                    high: price * 1.001,
+                    low: price * 0.999,
+                    close: price,
+                    volume: Some(1000.0),
+                }
+            })
+            .collect()
+    }
+
+    fn alternating(count: usize, size: f64) -> impl Iterator<Item = f64> {
+        (0..count).map(move |i| if i % 2 == 0 { size } else { -size })
+    }
+
+    #[test]
+    fn test_stddev_id_and_name() {
+        let stddev = StdDev::default();
+        assert_eq!(stddev.id(), "stddev");
+        assert_eq!(stddev.name(), "Volatility (20)");
+        assert_eq!(stddev.category(), SignalCategory::Volatility);
+        assert_eq!(stddev.min_periods(), 21);
+    }
+
+    #[test]
+    fn test_stddev_insufficient_data() {
+        let stddev = StdDev::default();
+        assert!(stddev
+            .calculate(&create_candles(alternating(19, 0.01)))
+            .is_none());
+        assert!(stddev
+            .calculate(&create_candles(alternating(20, 0.01)))
+            .is_some());
+    }
+
+    #[test]
+    fn test_stddev_noisy_series_has_higher_vol() {
+        let stddev = StdDev::default();
+        let smooth = stddev
+            .calculate(&create_candles(std::iter::repeat_n(0.002, 60)))
+            .unwrap();
+        let noisy = stddev
+            .calculate(&create_candles(alternating(60, 0.02)))
+            .unwrap();
+
+        assert!(smooth.value < 1.0, "smooth vol {}", smooth.value);
+        assert!(noisy.value > smooth.value * 100.0);
+
+        // About 2% per hourly bar, annualized over 8760 bars
+        let expected = 0.02 * (8760.0_f64).sqrt() * 100.0;
+        assert!((noisy.value - expected).abs() / expected < 0.05);
+    }
+
+    #[test]
+    fn test_stddev_score_reflects_regime() {
+        let stddev = StdDev::default();
+
+        // Calm history, then a burst of volatility
+        let expanding = create_candles(alternating(40, 0.002).chain(alternating(20, 0.03)));
+        assert!(stddev.calculate(&expanding).unwrap().score < -50);
+
+        // Volatile history, then calm
+        let contracting = create_candles(alternating(40, 0.03).chain(alternating(20, 0.002)));
+        assert!(stddev.calculate(&contracting).unwrap().score > 50);
+    }
+}
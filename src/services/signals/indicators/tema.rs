@@ -0,0 +1,184 @@
+//! Triple Exponential Moving Average (TEMA) indicator.
+
+use super::Ema;
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// TEMA (Triple Exponential Moving Average) indicator.
+///
+/// Cancels even more lag than DEMA using a third EMA pass:
+/// TEMA = 3 * EMA(n) - 3 * EMA(EMA(n)) + EMA(EMA(EMA(n)))
+///
+/// Signal based on price position relative to TEMA:
+/// - Price above TEMA = bullish
+/// - Price below TEMA = bearish
+/// - Price crossing the TEMA on the latest bar is a strong signal
+pub struct Tema {
+    period: usize,
+    name: String,
+}
+
+impl Default for Tema {
+    fn default() -> Self {
+        Self::new(20)
+    }
+}
+
+impl Tema {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            name: format!("TEMA ({})", period),
+        }
+    }
+
+    /// Calculate the TEMA for every bar with all EMA passes available.
+    fn series(&self, closes: &[f64]) -> Vec<f64> {
+        let single = Ema::series(closes, self.period);
+        let double = Ema::series(&single, self.period);
+        let triple = Ema::series(&double, self.period);
+
+        // Align the earlier-starting passes with the triple EMA
+        let single_offset = single.len() - triple.len().min(single.len());
+        let double_offset = double.len() - triple.len().min(double.len());
+        single
+            .iter()
+            .skip(single_offset)
+            .zip(double.iter().skip(double_offset))
+            .zip(triple.iter())
+            .map(|((s, d), t)| 3.0 * s - 3.0 * d + t)
+            .collect()
+    }
+}
+
+impl Signal for Tema {
+    fn id(&self) -> &str {
+        "tema"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Trend
+    }
+
+    fn min_periods(&self) -> usize {
+        // Three EMA passes, plus one extra bar so the previous reading is
+        // available for crossovers
+        3 * self.period - 1
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if self.period == 0 || candles.len() < self.min_periods() {
+            return None;
+        }
+
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let ma = self.series(&closes);
+        let current_ma = *ma.last()?;
+        let previous_ma = ma[ma.len() - 2];
+        if current_ma == 0.0 {
+            return None;
+        }
+
+        let price = closes[closes.len() - 1];
+        let previous_price = closes[closes.len() - 2];
+
+        // Scale so that 5% deviation = full signal
+        let pct_diff = (price - current_ma) / current_ma * 100.0;
+        let mut score = pct_diff * 20.0;
+
+        // Fresh crossover on the latest bar
+        let spread = price - current_ma;
+        let previous_spread = previous_price - previous_ma;
+        if spread != 0.0 && previous_spread != 0.0 && spread.signum() != previous_spread.signum() {
+            score = spread.signum() * (score.abs() + 60.0);
+        }
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            current_ma,
+            clamp_score(score),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Flat at 100 for `flat` bars, then flat at 120 for `after` bars.
+    fn create_step_candles(flat: usize, after: usize) -> Vec<OhlcPoint> {
+        (0..flat + after)
+            .map(|i| {
+                let close = if i < flat { 100.0 } else { 120.0 };
+                OhlcPoint {
+                    time: 1000000 + i as i64 * 60000,
+                    open: close,
+                    high: close + 1.0,
+                    low: close - 1.0,
+                    close,
+                    volume: Some(1000.0),
+                }
+            })
+            .collect()
+    }
+
+    fn ema(candles: &[OhlcPoint], period: usize) -> f64 {
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        *Ema::series(&closes, period).last().unwrap()
+    }
+
+    #[test]
+    fn test_tema_id_and_name() {
+        let tema = Tema::default();
+        assert_eq!(tema.id(), "tema");
+        assert_eq!(tema.name(), "TEMA (20)");
+        assert_eq!(tema.category(), SignalCategory::Trend);
+        assert_eq!(tema.min_periods(), 59);
+    }
+
+    #[test]
+    fn test_tema_insufficient_data() {
+        let tema = Tema::default();
+        assert!(tema.calculate(&create_step_candles(58, 0)).is_none());
+        assert!(tema.calculate(&create_step_candles(59, 0)).is_some());
+    }
+
+    #[test]
+    fn test_tema_flat_series_matches_price() {
+        let output = Tema::default()
+            .calculate(&create_step_candles(100, 0))
+            .unwrap();
+        assert!((output.value - 100.0).abs() < 1e-9);
+        assert_eq!(output.score, 0);
+    }
+
+    #[test]
+    fn test_tema_tracks_step_with_less_lag_than_ema() {
+        let tema = Tema::default();
+        for after in 1..=10 {
+            let candles = create_step_candles(100, after);
+            let value = tema.calculate(&candles).unwrap().value;
+            let ema_value = ema(&candles, 20);
+            assert!(
+                (120.0 - value).abs() < 120.0 - ema_value,
+                "{} bars after step: TEMA {} vs EMA {}",
+                after,
+                value,
+                ema_value
+            );
+        }
+    }
+
+    #[test]
+    fn test_tema_price_crossover_is_strong() {
+        let output = Tema::default()
+            .calculate(&create_step_candles(100, 1))
+            .unwrap();
+        assert!(output.score >= 60, "crossover score {}", output.score);
+    }
+}
@@ -0,0 +1,182 @@
+//! TRIX indicator.
+
+use super::Ema;
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// TRIX (Triple Exponential Average) indicator.
+///
+/// The one-bar rate of change of a triple-smoothed EMA, which filters out
+/// moves too small to matter:
+/// - Triple EMA = EMA(EMA(EMA(Close, period), period), period)
+/// - TRIX = (Triple EMA - Prev Triple EMA) / Prev Triple EMA * 100
+/// - Signal Line = EMA(9) of TRIX
+///
+/// Signals:
+/// - Above zero: Bullish momentum; below zero: Bearish momentum
+/// - Crossing zero or the signal line on the latest bar is a strong signal
+///
+/// The output value is the TRIX line.
+pub struct Trix {
+    period: usize,
+    signal_period: usize,
+    name: String,
+}
+
+impl Default for Trix {
+    fn default() -> Self {
+        Self::new(15, 9)
+    }
+}
+
+impl Trix {
+    pub fn new(period: usize, signal_period: usize) -> Self {
+        Self {
+            period,
+            signal_period,
+            name: format!("TRIX ({})", period),
+        }
+    }
+
+    /// Calculate TRIX for every bar with a full triple-smoothed EMA.
+    fn trix_series(&self, closes: &[f64]) -> Vec<f64> {
+        let single = Ema::series(closes, self.period);
+        let double = Ema::series(&single, self.period);
+        let triple = Ema::series(&double, self.period);
+
+        triple
+            .windows(2)
+            .map(|w| {
+                if w[0] == 0.0 {
+                    0.0
+                } else {
+                    (w[1] - w[0]) / w[0] * 100.0
+                }
+            })
+            .collect()
+    }
+}
+
+impl Signal for Trix {
+    fn id(&self) -> &str {
+        "trix"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Momentum
+    }
+
+    fn min_periods(&self) -> usize {
+        // Three EMA passes, one bar for the rate of change, a full signal
+        // window, and one extra bar so the previous reading is available for
+        // crossovers
+        3 * self.period + self.signal_period - 1
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if self.period == 0 || candles.len() < self.min_periods() {
+            return None;
+        }
+
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let trix = self.trix_series(&closes);
+        let signal = Ema::series(&trix, self.signal_period);
+        if signal.len() < 2 {
+            return None;
+        }
+
+        let current = trix[trix.len() - 1];
+        let previous = trix[trix.len() - 2];
+        let diff = current - signal[signal.len() - 1];
+        let previous_diff = previous - signal[signal.len() - 2];
+
+        // 0.4% per bar is full zero-line strength; a 0.2 point gap to the
+        // signal line adds 60
+        let mut score = (current * 100.0).clamp(-40.0, 40.0) + diff * 300.0;
+
+        // Fresh crossover on the latest bar
+        if current != 0.0 && previous != 0.0 && current.signum() != previous.signum() {
+            score = current.signum() * (score.abs() + 60.0);
+        } else if diff != 0.0 && previous_diff != 0.0 && diff.signum() != previous_diff.signum() {
+            score = diff.signum() * (score.abs() + 60.0);
+        }
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            current,
+            clamp_score(score),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Falling by 1% per bar for `down` bars, then rising by 1% per bar.
+    fn create_reversal_candles(down: usize, up: usize) -> Vec<OhlcPoint> {
+        let mut price = 100.0;
+        (0..down + up)
+            .map(|i| {
+                price *= if i < down { 0.99 } else { 1.01 };
+                OhlcPoint {
+                    time: 1000000 + i as i64 * 60000,
+                    open: price,
+                    high: price * 1.005,
+                    low: price * 0.995,
+                    close: price,
+                    volume: Some(1000.0),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_trix_id_and_name() {
+        let trix = Trix::default();
+        assert_eq!(trix.id(), "trix");
+        assert_eq!(trix.name(), "TRIX (15)");
+        assert_eq!(trix.category(), SignalCategory::Momentum);
+        assert_eq!(trix.min_periods(), 53);
+    }
+
+    #[test]
+    fn test_trix_insufficient_data() {
+        let trix = Trix::default();
+        assert!(trix.calculate(&create_reversal_candles(52, 0)).is_none());
+        assert!(trix.calculate(&create_reversal_candles(53, 0)).is_some());
+    }
+
+    #[test]
+    fn test_trix_turns_positive_after_reversal() {
+        let trix = Trix::default();
+
+        let falling = trix.calculate(&create_reversal_candles(80, 0)).unwrap();
+        assert!(falling.value < 0.0);
+        assert!(falling.score < 0);
+
+        // TRIX lags, so it stays negative right after the turn
+        let early = trix.calculate(&create_reversal_candles(80, 3)).unwrap();
+        assert!(early.value < 0.0);
+
+        let candles = create_reversal_candles(80, 60);
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let series = trix.trix_series(&closes);
+        let cross = series.iter().rposition(|t| *t <= 0.0).unwrap() + 1;
+        assert!(cross < series.len());
+
+        // trix[i] covers candles up to index i + 3 * period - 2
+        let at_cross = trix.calculate(&candles[..cross + 3 * 15 - 1]).unwrap();
+        assert!(at_cross.value > 0.0);
+        assert!(at_cross.score >= 60, "crossover score {}", at_cross.score);
+
+        let rising = trix.calculate(&candles).unwrap();
+        assert!(rising.value > 0.0);
+        assert!(rising.score > 0);
+    }
+}
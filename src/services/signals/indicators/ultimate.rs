@@ -0,0 +1,187 @@
+//! Ultimate Oscillator indicator.
+
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// Ultimate Oscillator.
+///
+/// Williams' oscillator averaging buying pressure over three windows so a
+/// single timeframe cannot dominate:
+/// - Buying Pressure = Close - min(Low, Prev Close)
+/// - True Range = max(High, Prev Close) - min(Low, Prev Close)
+/// - Average(n) = sum(BP, n) / sum(TR, n)
+/// - UO = 100 * (4 * Average(7) + 2 * Average(14) + Average(28)) / 7
+///
+/// Signals:
+/// - Below 30: Oversold (bullish)
+/// - Above 70: Overbought (bearish)
+pub struct UltimateOscillator {
+    short_period: usize,
+    medium_period: usize,
+    long_period: usize,
+}
+
+impl Default for UltimateOscillator {
+    fn default() -> Self {
+        Self::new(7, 14, 28)
+    }
+}
+
+impl UltimateOscillator {
+    pub fn new(short_period: usize, medium_period: usize, long_period: usize) -> Self {
+        Self {
+            short_period,
+            medium_period,
+            long_period,
+        }
+    }
+
+    /// Buying pressure and true range for each candle. The first candle has
+    /// no previous close, so its own range is used.
+    fn pressure_and_range(candles: &[OhlcPoint]) -> Vec<(f64, f64)> {
+        candles
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let prev_close = if i > 0 { candles[i - 1].close } else { c.low };
+                let true_low = c.low.min(prev_close);
+                let true_high = c.high.max(prev_close);
+                (c.close - true_low, true_high - true_low)
+            })
+            .collect()
+    }
+
+    /// Ratio of buying pressure to true range over the last `period` bars.
+    fn average(values: &[(f64, f64)], period: usize) -> Option<f64> {
+        let window = &values[values.len() - period..];
+        let pressure: f64 = window.iter().map(|(bp, _)| bp).sum();
+        let range: f64 = window.iter().map(|(_, tr)| tr).sum();
+        if range > 0.0 {
+            Some(pressure / range)
+        } else {
+            None
+        }
+    }
+}
+
+impl Signal for UltimateOscillator {
+    fn id(&self) -> &str {
+        "ultimate"
+    }
+
+    fn name(&self) -> &str {
+        "Ultimate Oscillator"
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Momentum
+    }
+
+    fn min_periods(&self) -> usize {
+        self.short_period
+            .max(self.medium_period)
+            .max(self.long_period)
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if self.short_period == 0 || candles.len() < self.min_periods() {
+            return None;
+        }
+
+        let values = Self::pressure_and_range(candles);
+        let short = Self::average(&values, self.short_period)?;
+        let medium = Self::average(&values, self.medium_period)?;
+        let long = Self::average(&values, self.long_period)?;
+        let uo = 100.0 * (4.0 * short + 2.0 * medium + long) / 7.0;
+
+        // Convert to score:
+        // 30 or below = oversold (bullish), 70 or above = overbought (bearish)
+        let score = if uo <= 30.0 {
+            // Oversold zone - bullish
+            50.0 + (30.0 - uo) / 30.0 * 50.0
+        } else if uo >= 70.0 {
+            // Overbought zone - bearish
+            -(50.0 + (uo - 70.0) / 30.0 * 50.0)
+        } else {
+            // Neutral zone - linear interpolation
+            (50.0 - uo) / 20.0 * 50.0
+        };
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            uo,
+            clamp_score(score),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Identical candles spanning 100-104 that close at `close`.
+    fn create_flat_candles(count: usize, close: f64) -> Vec<OhlcPoint> {
+        (0..count)
+            .map(|i| OhlcPoint {
+                time: 1000000 + i as i64 * 60000,
+                open: 102.0,
+                high: 104.0,
+                low: 100.0,
+                close,
+                volume: Some(1000.0),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ultimate_id_and_name() {
+        let uo = UltimateOscillator::default();
+        assert_eq!(uo.id(), "ultimate");
+        assert_eq!(uo.name(), "Ultimate Oscillator");
+        assert_eq!(uo.category(), SignalCategory::Momentum);
+        assert_eq!(uo.min_periods(), 28);
+    }
+
+    #[test]
+    fn test_ultimate_insufficient_data() {
+        let uo = UltimateOscillator::default();
+        assert!(uo.calculate(&create_flat_candles(27, 103.0)).is_none());
+        assert!(uo.calculate(&create_flat_candles(28, 103.0)).is_some());
+    }
+
+    #[test]
+    fn test_ultimate_known_reading() {
+        // Every bar closes 3/4 of the way up its range: BP = 3, TR = 4
+        let output = UltimateOscillator::default()
+            .calculate(&create_flat_candles(40, 103.0))
+            .unwrap();
+        assert!((output.value - 75.0).abs() < 1e-9);
+        assert!(output.score <= -50, "overbought score {}", output.score);
+
+        // Closing at the bottom quarter is oversold
+        let output = UltimateOscillator::default()
+            .calculate(&create_flat_candles(40, 101.0))
+            .unwrap();
+        assert!((output.value - 25.0).abs() < 1e-9);
+        assert!(output.score >= 50, "oversold score {}", output.score);
+    }
+
+    #[test]
+    fn test_ultimate_weights_recent_pressure() {
+        // Long window at 50%, short window pushed to the top of the range
+        let mut candles = create_flat_candles(28, 102.0);
+        for candle in candles.iter_mut().skip(21) {
+            candle.close = 104.0;
+        }
+
+        // Prev close 102 is inside the range for the first strong bar, so
+        // every bar still has TR = 4
+        let output = UltimateOscillator::default().calculate(&candles).unwrap();
+        let short = 1.0;
+        let medium = (7.0 * 2.0 + 7.0 * 4.0) / (14.0 * 4.0);
+        let long = (21.0 * 2.0 + 7.0 * 4.0) / (28.0 * 4.0);
+        let expected = 100.0 * (4.0 * short + 2.0 * medium + long) / 7.0;
+        assert!((output.value - expected).abs() < 1e-9);
+    }
+}
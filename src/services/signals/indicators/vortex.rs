@@ -0,0 +1,190 @@
+//! Vortex Indicator (VI).
+
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// Vortex Indicator.
+///
+/// Compares upward and downward trend movement relative to true range:
+/// VM+ = |High - Prev Low|, VM- = |Low - Prev High|
+/// VI+ = Sum(VM+) / Sum(TR), VI- = Sum(VM-) / Sum(TR)
+///
+/// Signals:
+/// - VI+ above VI-: Uptrend (bullish)
+/// - VI- above VI+: Downtrend (bearish)
+/// - A crossover on the latest bar is a strong signal
+///
+/// The output value is the spread VI+ - VI-.
+pub struct Vortex {
+    period: usize,
+    name: String,
+}
+
+impl Default for Vortex {
+    fn default() -> Self {
+        Self::new(14)
+    }
+}
+
+impl Vortex {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            name: format!("Vortex ({})", period),
+        }
+    }
+
+    /// Calculate (VI+, VI-) for every bar with a full window.
+    fn vortex_lines(candles: &[OhlcPoint], period: usize) -> Vec<(f64, f64)> {
+        if period == 0 || candles.len() < period + 1 {
+            return Vec::new();
+        }
+
+        // Per-bar movements, starting from the second candle
+        let moves: Vec<(f64, f64, f64)> = candles
+            .windows(2)
+            .map(|pair| {
+                let (prev, current) = (&pair[0], &pair[1]);
+                let vm_plus = (current.high - prev.low).abs();
+                let vm_minus = (current.low - prev.high).abs();
+                let tr = (current.high - current.low)
+                    .max((current.high - prev.close).abs())
+                    .max((current.low - prev.close).abs());
+                (vm_plus, vm_minus, tr)
+            })
+            .collect();
+
+        moves
+            .windows(period)
+            .map(|window| {
+                let (plus, minus, tr) = window.iter().fold((0.0, 0.0, 0.0), |acc, m| {
+                    (acc.0 + m.0, acc.1 + m.1, acc.2 + m.2)
+                });
+                if tr == 0.0 {
+                    (1.0, 1.0)
+                } else {
+                    (plus / tr, minus / tr)
+                }
+            })
+            .collect()
+    }
+}
+
+impl Signal for Vortex {
+    fn id(&self) -> &str {
+        "vortex"
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Trend
+    }
+
+    fn min_periods(&self) -> usize {
+        // One extra bar so the previous reading is available for crossovers
+        self.period + 2
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        if candles.len() < self.min_periods() {
+            return None;
+        }
+
+        let lines = Self::vortex_lines(candles, self.period);
+        let (vi_plus, vi_minus) = *lines.last()?;
+        let (prev_plus, prev_minus) = lines[lines.len() - 2];
+
+        let spread = vi_plus - vi_minus;
+        let prev_spread = prev_plus - prev_minus;
+
+        // A spread of 0.4 is a strong trend
+        let mut score = spread * 250.0;
+
+        // Fresh crossover on the latest bar
+        if spread != 0.0 && prev_spread != 0.0 && spread.signum() != prev_spread.signum() {
+            score = spread.signum() * (score.abs() + 60.0);
+        }
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            spread,
+            clamp_score(score),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Downtrend for `down` bars, then an uptrend for `up` bars.
+    fn create_reversal_candles(down: usize, up: usize) -> Vec<OhlcPoint> {
+        let mut candles = Vec::new();
+        let mut price = 200.0;
+        for i in 0..down + up {
+            price += if i < down { -2.0 } else { 2.0 };
+            candles.push(OhlcPoint {
+                time: 1000000 + i as i64 * 60000,
+                open: price,
+                high: price + 1.0,
+                low: price - 1.0,
+                close: price,
+                volume: Some(1000.0),
+            });
+        }
+        candles
+    }
+
+    #[test]
+    fn test_vortex_id_and_name() {
+        let vortex = Vortex::default();
+        assert_eq!(vortex.id(), "vortex");
+        assert_eq!(vortex.name(), "Vortex (14)");
+        assert_eq!(vortex.category(), SignalCategory::Trend);
+        assert_eq!(vortex.min_periods(), 16);
+    }
+
+    #[test]
+    fn test_vortex_insufficient_data() {
+        let candles = create_reversal_candles(10, 0);
+        assert!(Vortex::default().calculate(&candles).is_none());
+    }
+
+    #[test]
+    fn test_vortex_trend_direction() {
+        let vortex = Vortex::new(5);
+
+        let downtrend = create_reversal_candles(20, 0);
+        let output = vortex.calculate(&downtrend).unwrap();
+        assert!(output.value < 0.0);
+        assert!(output.score < 0);
+
+        let reversed = create_reversal_candles(20, 20);
+        let output = vortex.calculate(&reversed).unwrap();
+        assert!(output.value > 0.0);
+        assert!(output.score > 0);
+    }
+
+    #[test]
+    fn test_vortex_crossover_is_strong() {
+        let vortex = Vortex::new(5);
+        let candles = create_reversal_candles(20, 20);
+        let lines = Vortex::vortex_lines(&candles, 5);
+
+        // VI- leads during the decline, then VI+ crosses above it
+        assert!(lines[5].1 > lines[5].0);
+        let cross = lines.iter().position(|(plus, minus)| plus > minus).unwrap();
+        assert!(cross > 5);
+
+        // lines[i] covers candles up to index i + period
+        let before_cross = vortex.calculate(&candles[..=cross + 4]).unwrap();
+        let at_cross = vortex.calculate(&candles[..=cross + 5]).unwrap();
+        assert!(before_cross.value < 0.0);
+        assert!(at_cross.value > 0.0);
+        assert!(at_cross.score >= 60, "crossover score {}", at_cross.score);
+    }
+}
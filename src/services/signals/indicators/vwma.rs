@@ -0,0 +1,161 @@
+//! Volume-Weighted Moving Average (VWMA) indicator.
+
+use crate::services::signals::{clamp_score, make_signal_output, Signal};
+use crate::types::{OhlcPoint, SignalCategory, SignalOutput};
+
+/// VWMA (Volume-Weighted Moving Average) indicator.
+///
+/// Average closing price over a period, weighted by volume:
+/// VWMA = Sum(Close * Volume) / Sum(Volume)
+///
+/// Signal based on price position relative to VWMA:
+/// - Price above VWMA = bullish
+/// - Price below VWMA = bearish
+///
+/// Requires volume on every candle in the window.
+pub struct Vwma {
+    period: usize,
+    name: String,
+}
+
+impl Vwma {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            name: format!("VWMA ({})", period),
+        }
+    }
+
+    /// Calculate VWMA over the last `period` candles, or None if any volume is missing.
+    fn calculate_vwma(candles: &[OhlcPoint], period: usize) -> Option<f64> {
+        if period == 0 || candles.len() < period {
+            return None;
+        }
+
+        let mut weighted_sum = 0.0;
+        let mut volume_sum = 0.0;
+        for candle in &candles[candles.len() - period..] {
+            let volume = candle.volume?;
+            weighted_sum += candle.close * volume;
+            volume_sum += volume;
+        }
+
+        if volume_sum <= 0.0 {
+            return None;
+        }
+
+        Some(weighted_sum / volume_sum)
+    }
+}
+
+impl Default for Vwma {
+    fn default() -> Self {
+        Self::new(20)
+    }
+}
+
+impl Signal for Vwma {
+    fn id(&self) -> &str {
+        match self.period {
+            20 => "vwma20",
+            50 => "vwma50",
+            _ => "vwma",
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> SignalCategory {
+        SignalCategory::Trend
+    }
+
+    fn min_periods(&self) -> usize {
+        self.period
+    }
+
+    fn calculate(&self, candles: &[OhlcPoint]) -> Option<SignalOutput> {
+        let vwma = Self::calculate_vwma(candles, self.period)?;
+        let current_price = candles.last()?.close;
+
+        // Calculate percentage difference from VWMA
+        let pct_diff = ((current_price - vwma) / vwma) * 100.0;
+
+        // Score: price above VWMA is bullish, below is bearish
+        // Scale so that 5% deviation = full signal
+        let score = (pct_diff * 20.0).clamp(-100.0, 100.0);
+
+        Some(make_signal_output(
+            self.name(),
+            self.category(),
+            vwma,
+            clamp_score(score),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(i: usize, close: f64, volume: Option<f64>) -> OhlcPoint {
+        OhlcPoint {
+            time: 1000000 + i as i64 * 60000,
+            open: close,
+            high: close + 1.0,
+            low: close - 1.0,
+            close,
+            volume,
+        }
+    }
+
+    #[test]
+    fn test_vwma_id_and_name() {
+        assert_eq!(Vwma::new(20).id(), "vwma20");
+        assert_eq!(Vwma::new(50).name(), "VWMA (50)");
+        assert_eq!(Vwma::default().category(), SignalCategory::Trend);
+        assert_eq!(Vwma::new(50).min_periods(), 50);
+    }
+
+    #[test]
+    fn test_vwma_weights_by_volume() {
+        // (100 * 1 + 110 * 3) / 4 = 107.5, vs a plain average of 105
+        let candles = vec![candle(0, 100.0, Some(1.0)), candle(1, 110.0, Some(3.0))];
+        let output = Vwma::new(2).calculate(&candles).unwrap();
+        assert!((output.value - 107.5).abs() < 1e-9);
+
+        // Price 110 is ~2.3% above the VWMA
+        assert!(output.score > 0);
+    }
+
+    #[test]
+    fn test_vwma_only_uses_last_period() {
+        let candles = vec![
+            candle(0, 500.0, Some(100.0)),
+            candle(1, 100.0, Some(1.0)),
+            candle(2, 90.0, Some(1.0)),
+        ];
+        let output = Vwma::new(2).calculate(&candles).unwrap();
+        assert!((output.value - 95.0).abs() < 1e-9);
+        assert!(output.score < 0);
+    }
+
+    #[test]
+    fn test_vwma_missing_volume_returns_none() {
+        let mut candles: Vec<OhlcPoint> = (0..20).map(|i| candle(i, 100.0, Some(10.0))).collect();
+        assert!(Vwma::new(20).calculate(&candles).is_some());
+
+        candles[10].volume = None;
+        assert!(Vwma::new(20).calculate(&candles).is_none());
+
+        let zero_volume: Vec<OhlcPoint> = (0..20).map(|i| candle(i, 100.0, Some(0.0))).collect();
+        assert!(Vwma::new(20).calculate(&zero_volume).is_none());
+    }
+
+    #[test]
+    fn test_vwma_insufficient_data() {
+        let candles: Vec<OhlcPoint> = (0..10).map(|i| candle(i, 100.0, Some(1.0))).collect();
+        assert!(Vwma::new(20).calculate(&candles).is_none());
+    }
+}
@@ -4,13 +4,19 @@
 //! and historical accuracy tracking for trading signals.
 
 pub mod accuracy;
+pub mod fibonacci;
 pub mod indicators;
+pub mod pivots;
 pub mod predictions;
+pub mod spec;
+pub mod stops;
 pub mod store;
 
 pub use accuracy::AccuracyStore;
 pub use predictions::PredictionStore;
-pub use store::SignalStore;
+pub use spec::parse_indicator_spec;
+pub use stops::StopSuggestionError;
+pub use store::{SignalStore, DEFAULT_VALIDATION_CONCURRENCY};
 
 use crate::types::{OhlcPoint, SignalCategory, SignalDirection, SignalOutput};
 
@@ -44,6 +50,7 @@ pub fn make_signal_output(
         name: name.to_string(),
         category,
         value,
+        components: None,
         score,
         direction: SignalDirection::from_score(score),
         accuracy: None,
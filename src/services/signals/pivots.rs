@@ -0,0 +1,169 @@
+//! Daily pivot point calculation.
+
+use crate::services::ChartStore;
+use crate::types::{PivotLevels, PivotPoints, PivotType};
+
+/// Length of the pivot period in seconds (one UTC day).
+const PERIOD_SECONDS: i64 = 86_400;
+
+/// Fibonacci ratios applied to the prior range for levels 1-3.
+const FIB_RATIOS: [f64; 3] = [0.382, 0.618, 1.0];
+
+/// Calculate pivot levels from a period's high, low and close.
+///
+/// Both methods share the pivot P = (H + L + C) / 3.
+///
+/// Classic:
+/// - R1 = 2P - L, S1 = 2P - H
+/// - R2 = P + (H - L), S2 = P - (H - L)
+/// - R3 = H + 2(P - L), S3 = L - 2(H - P)
+///
+/// Fibonacci: Rn = P + ratio * (H - L), Sn = P - ratio * (H - L)
+/// for ratios 0.382, 0.618 and 1.0.
+pub fn calculate_pivots(high: f64, low: f64, close: f64, pivot_type: PivotType) -> PivotLevels {
+    let pivot = (high + low + close) / 3.0;
+    let range = high - low;
+
+    match pivot_type {
+        PivotType::Classic => PivotLevels {
+            pivot,
+            r1: 2.0 * pivot - low,
+            r2: pivot + range,
+            r3: high + 2.0 * (pivot - low),
+            s1: 2.0 * pivot - high,
+            s2: pivot - range,
+            s3: low - 2.0 * (high - pivot),
+        },
+        PivotType::Fibonacci => {
+            let [f1, f2, f3] = FIB_RATIOS;
+            PivotLevels {
+                pivot,
+                r1: pivot + f1 * range,
+                r2: pivot + f2 * range,
+                r3: pivot + f3 * range,
+                s1: pivot - f1 * range,
+                s2: pivot - f2 * range,
+                s3: pivot - f3 * range,
+            }
+        }
+    }
+}
+
+/// Compute daily pivot points for a symbol from the prior UTC day's candle.
+///
+/// `now` is unix seconds. Returns None if the chart store has no data for the
+/// prior day.
+pub fn daily_pivots(
+    chart_store: &ChartStore,
+    symbol: &str,
+    pivot_type: PivotType,
+    now: i64,
+) -> Option<PivotPoints> {
+    let today_start = now.div_euclid(PERIOD_SECONDS) * PERIOD_SECONDS;
+    let period_start = today_start - PERIOD_SECONDS;
+
+    let candles = chart_store
+        .get_chart_resampled(symbol, period_start, today_start - 1, PERIOD_SECONDS)
+        .ok()?;
+    let prior = candles.iter().find(|c| c.time == period_start)?;
+
+    Some(PivotPoints {
+        symbol: symbol.to_uppercase(),
+        pivot_type,
+        period_start,
+        high: prior.high,
+        low: prior.low,
+        close: prior.close,
+        levels: calculate_pivots(prior.high, prior.low, prior.close, pivot_type),
+        timestamp: now * 1000,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_classic_pivots_known_values() {
+        // H = 110, L = 90, C = 100 -> P = 100, range = 20
+        let levels = calculate_pivots(110.0, 90.0, 100.0, PivotType::Classic);
+        assert_close(levels.pivot, 100.0);
+        assert_close(levels.r1, 110.0);
+        assert_close(levels.r2, 120.0);
+        assert_close(levels.r3, 130.0);
+        assert_close(levels.s1, 90.0);
+        assert_close(levels.s2, 80.0);
+        assert_close(levels.s3, 70.0);
+    }
+
+    #[test]
+    fn test_classic_pivots_uneven_close() {
+        // H = 120, L = 100, C = 115 -> P = 111.666..
+        let levels = calculate_pivots(120.0, 100.0, 115.0, PivotType::Classic);
+        let p = 335.0 / 3.0;
+        assert_close(levels.pivot, p);
+        assert_close(levels.r1, 2.0 * p - 100.0);
+        assert_close(levels.s1, 2.0 * p - 120.0);
+        assert_close(levels.r3, 120.0 + 2.0 * (p - 100.0));
+        assert_close(levels.s3, 100.0 - 2.0 * (120.0 - p));
+    }
+
+    #[test]
+    fn test_fibonacci_pivots_known_values() {
+        let levels = calculate_pivots(110.0, 90.0, 100.0, PivotType::Fibonacci);
+        assert_close(levels.pivot, 100.0);
+        assert_close(levels.r1, 107.64);
+        assert_close(levels.r2, 112.36);
+        assert_close(levels.r3, 120.0);
+        assert_close(levels.s1, 92.36);
+        assert_close(levels.s2, 87.64);
+        assert_close(levels.s3, 80.0);
+    }
+
+    #[test]
+    fn test_daily_pivots_uses_prior_day() {
+        let store = ChartStore::default();
+        let now = chrono::Utc::now().timestamp();
+        let today_start = now.div_euclid(PERIOD_SECONDS) * PERIOD_SECONDS;
+        let yesterday = today_start - PERIOD_SECONDS;
+
+        for (offset, price) in [(0, 100.0), (3600, 110.0), (7200, 90.0), (10800, 105.0)] {
+            store.add_price("BTC", price, None, (yesterday + offset) * 1000);
+        }
+        // Today's prices must not leak into the prior period
+        store.add_price("BTC", 200.0, None, today_start * 1000);
+
+        let pivots = daily_pivots(&store, "btc", PivotType::Classic, now).unwrap();
+        assert_eq!(pivots.symbol, "BTC");
+        assert_eq!(pivots.period_start, yesterday);
+        assert_eq!(pivots.high, 110.0);
+        assert_eq!(pivots.low, 90.0);
+        assert_eq!(pivots.close, 105.0);
+        assert_eq!(
+            pivots.levels,
+            calculate_pivots(110.0, 90.0, 105.0, PivotType::Classic)
+        );
+    }
+
+    #[test]
+    fn test_daily_pivots_none_without_prior_period() {
+        let store = ChartStore::default();
+        let now = chrono::Utc::now().timestamp();
+        let today_start = now.div_euclid(PERIOD_SECONDS) * PERIOD_SECONDS;
+
+        assert!(daily_pivots(&store, "btc", PivotType::Classic, now).is_none());
+
+        // Only the current, incomplete day has data
+        store.add_price("BTC", 100.0, None, today_start * 1000);
+        assert!(daily_pivots(&store, "btc", PivotType::Fibonacci, now).is_none());
+    }
+}
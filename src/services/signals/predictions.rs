@@ -50,22 +50,10 @@ impl PredictionStore {
         *self.sqlite.write().await = Some(sqlite_store);
     }
 
-    /// Connect to Redis for persistence.
-    pub async fn connect_redis(&self, redis_url: &str) {
-        match redis::Client::open(redis_url) {
-            Ok(client) => match ConnectionManager::new(client).await {
-                Ok(conn) => {
-                    info!("PredictionStore connected to Redis");
-                    *self.redis.write().await = Some(conn);
-                }
-                Err(e) => {
-                    warn!("Failed to connect PredictionStore to Redis: {}", e);
-                }
-            },
-            Err(e) => {
-                warn!("Invalid Redis URL for PredictionStore: {}", e);
-            }
-        }
+    /// Use a shared Redis connection for persistence.
+    pub async fn connect_redis(&self, conn: ConnectionManager) {
+        info!("PredictionStore connected to Redis");
+        *self.redis.write().await = Some(conn);
     }
 
     /// Check if we should create a new prediction for this indicator.
@@ -226,6 +214,10 @@ impl PredictionStore {
 
         let mut outcomes = Vec::new();
 
+        // Clone the SQLite handle up front so no queue guard is held across an
+        // await while other symbols are validated concurrently
+        let sqlite_opt = self.sqlite.read().await.clone();
+
         if let Some(mut pending) = queue.get_mut(&symbol_lower) {
             let mut remaining = Vec::new();
 
@@ -248,8 +240,7 @@ impl PredictionStore {
                         }
                     }
 
-                    // Update in SQLite (clone sqlite ref to avoid holding lock)
-                    let sqlite_opt = self.sqlite.read().await.clone();
+                    // Update in SQLite
                     if let Some(ref sqlite) = sqlite_opt {
                         if let Err(e) = sqlite.archive_prediction(&prediction) {
                             warn!("Failed to update prediction in SQLite: {}", e);
@@ -0,0 +1,282 @@
+//! Per-request indicator specifications.
+//!
+//! Parses specs like `rsi:7,ema:21,macd:5:13:3` into indicator instances,
+//! so a single request can use custom parameters without touching the
+//! registered defaults.
+
+use thiserror::Error;
+
+use crate::services::signals::indicators::{
+    Aroon, ChaikinOsc, Cmo, Coppock, Dema, Dpo, ElderRay, Ema, Eom, Fisher, ForceIndex, Hma, Macd,
+    MacdCross, MassIndex, Momentum, Ppo, Rsi, Sma, StdDev, Tema, Trix, UltimateOscillator, Vortex,
+    Vwma,
+};
+use crate::services::signals::Signal;
+
+/// Largest period accepted for any indicator parameter.
+pub const MAX_PERIOD: usize = 500;
+
+/// Most indicators accepted in a single spec.
+pub const MAX_INDICATORS: usize = 20;
+
+/// Errors from parsing an indicator spec.
+#[derive(Debug, Error, PartialEq)]
+pub enum IndicatorSpecError {
+    #[error("Indicator spec is empty")]
+    Empty,
+    #[error("Too many indicators: {0} (max {MAX_INDICATORS})")]
+    TooMany(usize),
+    #[error("Unknown indicator: {0}")]
+    UnknownIndicator(String),
+    #[error("Invalid parameter '{value}' for {indicator}: expected a period between {min} and {MAX_PERIOD}")]
+    InvalidParameter {
+        indicator: String,
+        value: String,
+        min: usize,
+    },
+    #[error("{indicator} takes {expected} parameter(s), got {actual}")]
+    WrongParameterCount {
+        indicator: String,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("Invalid parameters for {indicator}: {reason}")]
+    InvalidParameters { indicator: String, reason: String },
+}
+
+/// Parse a comma-separated indicator spec.
+///
+/// Each entry is an indicator name followed by colon-separated periods, e.g.
+/// `macd:5:13:3`. A bare name uses the indicator's default parameters.
+pub fn parse_indicator_spec(spec: &str) -> Result<Vec<Box<dyn Signal>>, IndicatorSpecError> {
+    let entries: Vec<&str> = spec
+        .split(',')
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+        .collect();
+    if entries.is_empty() {
+        return Err(IndicatorSpecError::Empty);
+    }
+    if entries.len() > MAX_INDICATORS {
+        return Err(IndicatorSpecError::TooMany(entries.len()));
+    }
+
+    entries.into_iter().map(parse_entry).collect()
+}
+
+/// Parse a single `name[:param...]` entry.
+fn parse_entry(entry: &str) -> Result<Box<dyn Signal>, IndicatorSpecError> {
+    let mut parts = entry.split(':').map(str::trim);
+    let name = parts.next().unwrap_or_default().to_lowercase();
+    let raw: Vec<&str> = parts.collect();
+
+    let (defaults, min): (&[usize], usize) = match name.as_str() {
+        "rsi" | "cmo" | "vortex" | "eom" => (&[14], 1),
+        "ema" => (&[12], 1),
+        "sma" | "dpo" | "dema" | "tema" | "vwma" => (&[20], 1),
+        "fisher" | "mom" => (&[10], 1),
+        "hma" => (&[16], 2),
+        "stddev" => (&[20], 2),
+        "aroon" => (&[25], 1),
+        "elder_ray" | "force_index" => (&[13], 1),
+        "macd" | "macd_cross" | "ppo" => (&[12, 26, 9], 1),
+        "trix" => (&[15, 9], 1),
+        "chaikin_osc" => (&[3, 10], 1),
+        "ultimate" => (&[7, 14, 28], 1),
+        "mass_index" => (&[9, 25], 1),
+        "coppock" => (&[14, 11, 10], 1),
+        _ => return Err(IndicatorSpecError::UnknownIndicator(name)),
+    };
+
+    let params = if raw.is_empty() {
+        defaults.to_vec()
+    } else if raw.len() != defaults.len() {
+        return Err(IndicatorSpecError::WrongParameterCount {
+            indicator: name,
+            expected: defaults.len(),
+            actual: raw.len(),
+        });
+    } else {
+        raw.iter()
+            .map(|value| match value.parse::<usize>() {
+                Ok(period) if (min..=MAX_PERIOD).contains(&period) => Ok(period),
+                _ => Err(IndicatorSpecError::InvalidParameter {
+                    indicator: name.clone(),
+                    value: value.to_string(),
+                    min,
+                }),
+            })
+            .collect::<Result<Vec<_>, _>>()?
+    };
+
+    let invalid = |reason: &str| IndicatorSpecError::InvalidParameters {
+        indicator: name.clone(),
+        reason: reason.to_string(),
+    };
+
+    let indicator: Box<dyn Signal> = match name.as_str() {
+        "rsi" => Box::new(Rsi::new(params[0])),
+        "ema" => Box::new(Ema::new(params[0])),
+        "sma" => Box::new(Sma::new(params[0])),
+        "cmo" => Box::new(Cmo::new(params[0])),
+        "fisher" => Box::new(Fisher::new(params[0])),
+        "vortex" => Box::new(Vortex::new(params[0])),
+        "hma" => Box::new(Hma::new(params[0])),
+        "dema" => Box::new(Dema::new(params[0])),
+        "tema" => Box::new(Tema::new(params[0])),
+        "aroon" => Box::new(Aroon::new(params[0])),
+        "dpo" => Box::new(Dpo::new(params[0])),
+        "elder_ray" => Box::new(ElderRay::new(params[0])),
+        "mom" => Box::new(Momentum::new(params[0])),
+        "stddev" => Box::new(StdDev::new(params[0])),
+        "vwma" => Box::new(Vwma::new(params[0])),
+        "eom" => Box::new(Eom::new(params[0])),
+        "force_index" => Box::new(ForceIndex::new(params[0])),
+        "macd" | "macd_cross" | "ppo" => {
+            if params[0] >= params[1] {
+                return Err(invalid("fast period must be shorter than slow period"));
+            }
+            match name.as_str() {
+                "macd" => Box::new(Macd::new(params[0], params[1], params[2])),
+                "macd_cross" => Box::new(MacdCross::new(params[0], params[1], params[2])),
+                _ => Box::new(Ppo::new(params[0], params[1], params[2])),
+            }
+        }
+        "trix" => Box::new(Trix::new(params[0], params[1])),
+        "chaikin_osc" => {
+            if params[0] >= params[1] {
+                return Err(invalid("fast period must be shorter than slow period"));
+            }
+            Box::new(ChaikinOsc::new(params[0], params[1]))
+        }
+        "ultimate" => {
+            if !(params[0] < params[1] && params[1] < params[2]) {
+                return Err(invalid("periods must be strictly increasing"));
+            }
+            Box::new(UltimateOscillator::new(params[0], params[1], params[2]))
+        }
+        "mass_index" => Box::new(MassIndex::new(params[0], params[1])),
+        "coppock" => {
+            if params[0] <= params[1] {
+                return Err(invalid(
+                    "long ROC period must be longer than short ROC period",
+                ));
+            }
+            Box::new(Coppock::new(params[0], params[1], params[2]))
+        }
+        _ => unreachable!("indicator names are validated above"),
+    };
+
+    Ok(indicator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::OhlcPoint;
+
+    fn create_candles(count: usize) -> Vec<OhlcPoint> {
+        (0..count)
+            .map(|i| {
+                let close = 100.0 + (i as f64 * 0.7).sin() * 5.0 + i as f64 * 0.1;
+                OhlcPoint {
+                    time: 1000000 + i as i64 * 60000,
+                    open: close,
+                    high: close + 1.0,
+                    low: close - 1.0,
+                    close,
+                    volume: Some(1000.0),
+                }
+            })
+            .collect()
+    }
+
+    fn names(spec: &str) -> Vec<String> {
+        parse_indicator_spec(spec)
+            .unwrap()
+            .iter()
+            .map(|i| i.name().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_valid_spec() {
+        assert_eq!(
+            names("rsi:7,ema:21,macd:5:13:3"),
+            vec!["RSI (7)", "EMA (21)", "MACD (5, 13, 3)"]
+        );
+        assert_eq!(names("macd,ppo:8:21:5"), vec!["MACD", "PPO (8, 21, 5)"]);
+        let ids: Vec<String> = parse_indicator_spec("macd,macd:5:13:3,macd_cross:5:13:3")
+            .unwrap()
+            .iter()
+            .map(|i| i.id().to_string())
+            .collect();
+        assert_eq!(ids, vec!["macd", "macd_5_13_3", "macd_cross_5_13_3"]);
+        assert_eq!(names(" RSI , sma:50 "), vec!["RSI (14)", "SMA (50)"]);
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_specs() {
+        assert_eq!(
+            parse_indicator_spec(" , ").err(),
+            Some(IndicatorSpecError::Empty)
+        );
+        assert_eq!(
+            parse_indicator_spec("rsi,foo:3").err(),
+            Some(IndicatorSpecError::UnknownIndicator("foo".to_string()))
+        );
+        assert!(matches!(
+            parse_indicator_spec("rsi:abc").err(),
+            Some(IndicatorSpecError::InvalidParameter { .. })
+        ));
+        assert!(matches!(
+            parse_indicator_spec("rsi:0").err(),
+            Some(IndicatorSpecError::InvalidParameter { .. })
+        ));
+        assert!(matches!(
+            parse_indicator_spec("ema:501").err(),
+            Some(IndicatorSpecError::InvalidParameter { .. })
+        ));
+        assert!(matches!(
+            parse_indicator_spec("stddev:1").err(),
+            Some(IndicatorSpecError::InvalidParameter { .. })
+        ));
+        assert_eq!(
+            parse_indicator_spec("macd:5:13").err(),
+            Some(IndicatorSpecError::WrongParameterCount {
+                indicator: "macd".to_string(),
+                expected: 3,
+                actual: 2,
+            })
+        );
+        assert!(matches!(
+            parse_indicator_spec("macd:13:5:3").err(),
+            Some(IndicatorSpecError::InvalidParameters { .. })
+        ));
+        assert!(matches!(
+            parse_indicator_spec("chaikin_osc:10:3").err(),
+            Some(IndicatorSpecError::InvalidParameters { .. })
+        ));
+        assert!(matches!(
+            parse_indicator_spec("ultimate:7:7:28").err(),
+            Some(IndicatorSpecError::InvalidParameters { .. })
+        ));
+        assert_eq!(
+            parse_indicator_spec(&vec!["rsi"; MAX_INDICATORS + 1].join(",")).err(),
+            Some(IndicatorSpecError::TooMany(MAX_INDICATORS + 1))
+        );
+    }
+
+    #[test]
+    fn test_custom_rsi_differs_from_default() {
+        let candles = create_candles(100);
+        let custom = parse_indicator_spec("rsi:7").unwrap();
+        let default = parse_indicator_spec("rsi").unwrap();
+
+        let fast = custom[0].calculate(&candles).unwrap();
+        let standard = default[0].calculate(&candles).unwrap();
+        assert_eq!(fast.name, "RSI (7)");
+        assert_eq!(standard.name, "RSI (14)");
+        assert!((fast.value - standard.value).abs() > 1e-6);
+    }
+}
@@ -0,0 +1,170 @@
+//! ATR-based stop-loss suggestions.
+
+use thiserror::Error;
+
+use crate::services::signals::indicators::Atr;
+use crate::types::{OhlcPoint, PositionSide};
+
+/// Default ATR period for stop suggestions.
+pub const DEFAULT_ATR_PERIOD: usize = 14;
+
+/// Largest ATR multiplier accepted.
+pub const MAX_MULTIPLIER: f64 = 20.0;
+
+/// Errors from computing a stop suggestion.
+#[derive(Debug, Error, PartialEq)]
+pub enum StopSuggestionError {
+    #[error("Not enough candles to compute ATR({period}): need {required}, have {available}")]
+    InsufficientData {
+        period: usize,
+        required: usize,
+        available: usize,
+    },
+    #[error("Multiplier must be greater than 0 and at most {MAX_MULTIPLIER}, got {0}")]
+    InvalidMultiplier(f64),
+    #[error("Entry price must be positive, got {0}")]
+    InvalidEntry(f64),
+}
+
+/// A stop level computed from ATR.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AtrStop {
+    /// Average True Range in price units.
+    pub atr: f64,
+    /// Suggested stop price.
+    pub stop_price: f64,
+    /// Distance from entry to stop.
+    pub risk_per_unit: f64,
+}
+
+/// Suggest a stop `multiplier` ATRs away from `entry`, on the losing side of `side`.
+///
+/// Longs stop below entry (entry - mult * ATR) and shorts above it
+/// (entry + mult * ATR). A long stop never goes below zero, in which case the
+/// risk is the full entry price.
+pub fn atr_stop(
+    candles: &[OhlcPoint],
+    side: PositionSide,
+    entry: f64,
+    multiplier: f64,
+    period: usize,
+) -> Result<AtrStop, StopSuggestionError> {
+    if !(multiplier > 0.0 && multiplier <= MAX_MULTIPLIER) {
+        return Err(StopSuggestionError::InvalidMultiplier(multiplier));
+    }
+    if !(entry > 0.0 && entry.is_finite()) {
+        return Err(StopSuggestionError::InvalidEntry(entry));
+    }
+
+    let atr = Atr::new(period)
+        .atr(candles)
+        .ok_or(StopSuggestionError::InsufficientData {
+            period,
+            required: period + 1,
+            available: candles.len(),
+        })?;
+
+    let distance = multiplier * atr;
+    let stop_price = match side {
+        PositionSide::Long => (entry - distance).max(0.0),
+        PositionSide::Short => entry + distance,
+    };
+
+    Ok(AtrStop {
+        atr,
+        stop_price,
+        risk_per_unit: (entry - stop_price).abs(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Candles with a fixed `range` around a close drifting by `step`.
+    fn create_candles(count: usize, step: f64, range: f64) -> Vec<OhlcPoint> {
+        (0..count)
+            .map(|i| {
+                let close = 100.0 + i as f64 * step;
+                OhlcPoint {
+                    time: 1000000 + i as i64 * 60000,
+                    open: close,
+                    high: close + range / 2.0,
+                    low: close - range / 2.0,
+                    close,
+                    volume: Some(1000.0),
+                }
+            })
+            .collect()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_stop_distance_is_multiple_of_atr() {
+        // Flat closes with a 4.0 range: every true range, and so ATR, is 4.0
+        let candles = create_candles(30, 0.0, 4.0);
+
+        let long = atr_stop(&candles, PositionSide::Long, 100.0, 2.0, 14).unwrap();
+        assert_close(long.atr, 4.0);
+        assert_close(long.stop_price, 92.0);
+        assert_close(long.risk_per_unit, 8.0);
+
+        let short = atr_stop(&candles, PositionSide::Short, 100.0, 1.5, 14).unwrap();
+        assert_close(short.stop_price, 106.0);
+        assert_close(short.risk_per_unit, 6.0);
+    }
+
+    #[test]
+    fn test_stop_matches_atr_on_trending_series() {
+        // Gaps between bars make the true range exceed the bar range
+        let mut candles = create_candles(40, 1.0, 2.0);
+        candles[35].high += 5.0;
+
+        let atr = Atr::new(14).atr(&candles).unwrap();
+        let stop = atr_stop(&candles, PositionSide::Long, 150.0, 3.0, 14).unwrap();
+        assert_close(stop.atr, atr);
+        assert_close(150.0 - stop.stop_price, 3.0 * atr);
+        assert_close(stop.risk_per_unit, 3.0 * atr);
+    }
+
+    #[test]
+    fn test_long_stop_floors_at_zero() {
+        let candles = create_candles(30, 0.0, 4.0);
+        let stop = atr_stop(&candles, PositionSide::Long, 5.0, 2.0, 14).unwrap();
+        assert_eq!(stop.stop_price, 0.0);
+        assert_close(stop.risk_per_unit, 5.0);
+    }
+
+    #[test]
+    fn test_stop_rejects_bad_input() {
+        let candles = create_candles(10, 0.0, 4.0);
+        assert_eq!(
+            atr_stop(&candles, PositionSide::Long, 100.0, 2.0, 14),
+            Err(StopSuggestionError::InsufficientData {
+                period: 14,
+                required: 15,
+                available: 10,
+            })
+        );
+
+        let candles = create_candles(30, 0.0, 4.0);
+        for mult in [0.0, -1.0, f64::NAN, 21.0] {
+            assert!(matches!(
+                atr_stop(&candles, PositionSide::Long, 100.0, mult, 14),
+                Err(StopSuggestionError::InvalidMultiplier(_))
+            ));
+        }
+        assert_eq!(
+            atr_stop(&candles, PositionSide::Short, 0.0, 2.0, 14),
+            Err(StopSuggestionError::InvalidEntry(0.0))
+        );
+    }
+}
@@ -1,16 +1,36 @@
 //! Signal store for computing and caching trading signals.
 
+use crate::services::signals::fibonacci::symbol_fibonacci;
 use crate::services::signals::indicators::all_indicators;
+use crate::services::signals::pivots::daily_pivots;
+use crate::services::signals::stops::{atr_stop, StopSuggestionError, DEFAULT_ATR_PERIOD};
 use crate::services::signals::{AccuracyStore, PredictionStore, Signal};
 use crate::services::ChartStore;
 use crate::types::{
-    Recommendation, SignalCategory, SignalDirection, SignalOutput, SignalPrediction, SymbolSignals,
-    TradingTimeframe,
+    ChartRange, OhlcPoint, PivotPoints, PivotType, PositionSide, RawIndicator, RawIndicatorValue,
+    RawIndicators, Recommendation, SignalCategory, SignalDirection, SignalOutput, SignalPrediction,
+    StopSuggestion, SymbolFibonacci, SymbolSignals, TradingTimeframe,
 };
 use dashmap::DashMap;
+use futures_util::stream::{self, StreamExt};
 use std::sync::Arc;
 use tracing::debug;
 
+/// Timeframes checked for due predictions, shortest first.
+const VALIDATION_TIMEFRAMES: [&str; 4] = ["5m", "1h", "4h", "24h"];
+
+/// Default number of symbols validated at once.
+pub const DEFAULT_VALIDATION_CONCURRENCY: usize = 8;
+
+/// Result of a prediction validation run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ValidationSummary {
+    /// Symbols that had pending predictions.
+    pub symbols: usize,
+    /// Predictions validated across all symbols and timeframes.
+    pub validations: usize,
+}
+
 /// Cache entry for computed signals.
 struct CachedSignals {
     signals: SymbolSignals,
@@ -64,7 +84,12 @@ impl SignalStore {
         }
 
         // Compute signals
-        let signals = self.compute_signals(&symbol_lower, timeframe).await?;
+        let (signals, current_price) = self
+            .evaluate(&symbol_lower, timeframe, &self.indicators, true)
+            .await?;
+
+        // Record predictions for accuracy tracking
+        self.record_predictions(&signals, current_price).await;
 
         // Cache result
         self.cache.insert(
@@ -78,12 +103,32 @@ impl SignalStore {
         Some(signals)
     }
 
-    /// Compute signals for a symbol with specified trading timeframe.
-    async fn compute_signals(
+    /// Get signals for a symbol using a custom set of indicators.
+    ///
+    /// Used for per-request indicator parameters, so unlike
+    /// [`get_signals`](Self::get_signals) this is not cached, records no
+    /// predictions, and attaches no accuracy data.
+    pub async fn get_custom_signals(
         &self,
         symbol: &str,
         timeframe: TradingTimeframe,
+        indicators: &[Box<dyn Signal>],
     ) -> Option<SymbolSignals> {
+        self.evaluate(&symbol.to_lowercase(), timeframe, indicators, false)
+            .await
+            .map(|(signals, _)| signals)
+    }
+
+    /// Compute signals for a symbol with specified trading timeframe.
+    ///
+    /// Returns the signals along with the current price.
+    async fn evaluate(
+        &self,
+        symbol: &str,
+        timeframe: TradingTimeframe,
+        indicators: &[Box<dyn Signal>],
+        with_accuracy: bool,
+    ) -> Option<(SymbolSignals, f64)> {
         // Get OHLC data using timeframe-appropriate range
         let chart_range = timeframe.chart_range();
         let candles = self.chart_store.get_chart(symbol, chart_range);
@@ -107,9 +152,14 @@ impl SignalStore {
         let current_price = candles.last()?.close;
 
         // Calculate each indicator
-        for indicator in &self.indicators {
+        for indicator in indicators {
             if candles.len() >= indicator.min_periods() {
                 if let Some(mut signal) = indicator.calculate(&candles) {
+                    if !with_accuracy {
+                        signals.push(signal);
+                        continue;
+                    }
+
                     // Add accuracy data if available
                     // Use timeframe-specific accuracy validation period
                     let accuracy_timeframe = match timeframe {
@@ -168,11 +218,106 @@ impl SignalStore {
             timestamp,
         };
 
-        // Record predictions for accuracy tracking
-        self.record_predictions(&symbol_signals, current_price)
-            .await;
+        Some((symbol_signals, current_price))
+    }
 
-        Some(symbol_signals)
+    /// Get the raw output of every registered indicator for a symbol.
+    ///
+    /// Unlike [`get_signals`](Self::get_signals) this is not cached, records no
+    /// predictions, and reports indicators that could not be computed instead of
+    /// dropping them.
+    pub fn get_raw_indicators(&self, symbol: &str, timeframe: TradingTimeframe) -> RawIndicators {
+        let candles = self
+            .chart_store
+            .get_chart(&symbol.to_lowercase(), timeframe.chart_range());
+
+        RawIndicators {
+            symbol: symbol.to_uppercase(),
+            timeframe,
+            candle_count: candles.len(),
+            indicators: Self::compute_raw(&self.indicators, &candles),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    /// Get daily pivot points for a symbol from the prior UTC day.
+    pub fn get_pivots(&self, symbol: &str, pivot_type: PivotType) -> Option<PivotPoints> {
+        daily_pivots(
+            &self.chart_store,
+            symbol,
+            pivot_type,
+            chrono::Utc::now().timestamp(),
+        )
+    }
+
+    /// Get Fibonacci levels for a symbol from the swing over a chart range.
+    pub fn get_fibonacci(&self, symbol: &str, range: ChartRange) -> Option<SymbolFibonacci> {
+        symbol_fibonacci(&self.chart_store, symbol, range)
+    }
+
+    /// Suggest an ATR-based stop for a position in a symbol.
+    ///
+    /// `entry` defaults to the latest close.
+    pub fn get_stop_suggestion(
+        &self,
+        symbol: &str,
+        timeframe: TradingTimeframe,
+        side: PositionSide,
+        multiplier: f64,
+        entry: Option<f64>,
+    ) -> Result<StopSuggestion, StopSuggestionError> {
+        let candles = self
+            .chart_store
+            .get_chart(&symbol.to_lowercase(), timeframe.chart_range());
+        let entry = entry.or_else(|| candles.last().map(|c| c.close)).ok_or(
+            StopSuggestionError::InsufficientData {
+                period: DEFAULT_ATR_PERIOD,
+                required: DEFAULT_ATR_PERIOD + 1,
+                available: 0,
+            },
+        )?;
+
+        let stop = atr_stop(&candles, side, entry, multiplier, DEFAULT_ATR_PERIOD)?;
+
+        Ok(StopSuggestion {
+            symbol: symbol.to_uppercase(),
+            side,
+            timeframe,
+            entry,
+            atr_period: DEFAULT_ATR_PERIOD,
+            atr: stop.atr,
+            multiplier,
+            stop_price: stop.stop_price,
+            risk_per_unit: stop.risk_per_unit,
+            risk_pct: stop.risk_per_unit / entry * 100.0,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        })
+    }
+
+    /// Compute each indicator over `candles`, marking the ones that have no value.
+    fn compute_raw(indicators: &[Box<dyn Signal>], candles: &[OhlcPoint]) -> Vec<RawIndicator> {
+        indicators
+            .iter()
+            .map(|indicator| {
+                let result = if candles.len() < indicator.min_periods() {
+                    RawIndicatorValue::InsufficientData {
+                        required: indicator.min_periods(),
+                        available: candles.len(),
+                    }
+                } else {
+                    match indicator.calculate(candles) {
+                        Some(output) => RawIndicatorValue::Ok { output },
+                        None => RawIndicatorValue::Unavailable,
+                    }
+                };
+                RawIndicator {
+                    id: indicator.id().to_string(),
+                    name: indicator.name().to_string(),
+                    category: indicator.category(),
+                    result,
+                }
+            })
+            .collect()
     }
 
     /// Calculate composite score for a category.
@@ -263,6 +408,54 @@ impl SignalStore {
         self.cache.clear();
     }
 
+    /// Validate every pending prediction that has reached its timeframe.
+    ///
+    /// Symbols are validated concurrently, at most `concurrency` at a time, and
+    /// each outcome is recorded in the accuracy store. Symbols without a
+    /// current price are skipped until the next run. SQLite archiving goes
+    /// through the store's shared connection, which serializes the writes.
+    pub async fn validate_pending_predictions(&self, concurrency: usize) -> ValidationSummary {
+        let symbols = self.prediction_store.get_pending_symbols();
+        let symbol_count = symbols.len();
+
+        let counts: Vec<usize> = stream::iter(symbols)
+            .map(|symbol| async move { self.validate_symbol(&symbol).await })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await;
+
+        ValidationSummary {
+            symbols: symbol_count,
+            validations: counts.into_iter().sum(),
+        }
+    }
+
+    /// Validate one symbol across all timeframes, returning the outcome count.
+    async fn validate_symbol(&self, symbol: &str) -> usize {
+        let Some(current_price) = self.chart_store.get_current_price(symbol) else {
+            debug!("No price data for {} - skipping validation", symbol);
+            return 0;
+        };
+
+        let mut validations = 0;
+        for timeframe in VALIDATION_TIMEFRAMES {
+            let outcomes = self
+                .prediction_store
+                .validate_pending(symbol, current_price, timeframe)
+                .await;
+
+            for (indicator, outcome) in &outcomes {
+                self.accuracy_store
+                    .record_outcome(symbol, indicator, timeframe, *outcome)
+                    .await;
+            }
+
+            validations += outcomes.len();
+        }
+
+        validations
+    }
+
     /// Get prediction store reference.
     pub fn prediction_store(&self) -> &Arc<PredictionStore> {
         &self.prediction_store
@@ -369,6 +562,7 @@ mod tests {
             category,
             score,
             value: 50.0,
+            components: None,
             direction: SignalDirection::from_score(score),
             accuracy,
             sample_size: if accuracy.is_some() { Some(100) } else { None },
@@ -481,4 +675,148 @@ mod tests {
             assert_eq!(score, 75);
         }
     }
+
+    // =========================================================================
+    // compute_raw Tests
+    // =========================================================================
+
+    fn create_test_candles(count: usize) -> Vec<OhlcPoint> {
+        (0..count)
+            .map(|i| {
+                let close = 100.0 + (i as f64 * 0.3).sin() * 5.0 + i as f64 * 0.1;
+                OhlcPoint {
+                    time: i as i64 * 60_000,
+                    open: close - 0.5,
+                    high: close + 1.0,
+                    low: close - 1.0,
+                    close,
+                    volume: Some(1000.0 + i as f64),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_compute_raw_reports_every_indicator() {
+        let indicators = all_indicators();
+        // Enough for the short indicators but not SMA 200
+        let candles = create_test_candles(60);
+        let raw = SignalStore::compute_raw(&indicators, &candles);
+
+        assert_eq!(raw.len(), indicators.len());
+        for (entry, indicator) in raw.iter().zip(&indicators) {
+            assert_eq!(entry.id, indicator.id());
+            match &entry.result {
+                RawIndicatorValue::Ok { output } => assert!(output.value.is_finite()),
+                RawIndicatorValue::InsufficientData {
+                    required,
+                    available,
+                } => {
+                    assert_eq!(*required, indicator.min_periods());
+                    assert_eq!(*available, 60);
+                }
+                RawIndicatorValue::Unavailable => {}
+            }
+        }
+
+        let sma_200 = raw.iter().find(|r| r.id == "sma200").unwrap();
+        assert!(matches!(
+            sma_200.result,
+            RawIndicatorValue::InsufficientData { required: 200, .. }
+        ));
+        let rsi = raw.iter().find(|r| r.id.starts_with("rsi")).unwrap();
+        assert!(matches!(rsi.result, RawIndicatorValue::Ok { .. }));
+    }
+
+    #[test]
+    fn test_compute_raw_with_no_candles() {
+        let indicators = all_indicators();
+        let raw = SignalStore::compute_raw(&indicators, &[]);
+
+        assert_eq!(raw.len(), indicators.len());
+        assert!(raw
+            .iter()
+            .all(|r| matches!(r.result, RawIndicatorValue::InsufficientData { available: 0, .. })));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_validate_pending_predictions_concurrently() {
+        use crate::services::SqliteStore;
+        use crate::types::PredictionOutcome;
+
+        let chart_store = ChartStore::new();
+        let prediction_store = PredictionStore::new();
+        let accuracy_store = AccuracyStore::new();
+        let sqlite = Arc::new(SqliteStore::new_in_memory().unwrap());
+        prediction_store.connect_sqlite(sqlite.clone()).await;
+
+        // Buy predictions made over a day ago, with price now up 5%
+        let symbols: Vec<String> = (0..40).map(|i| format!("SYM{}", i)).collect();
+        let day_ago = chrono::Utc::now().timestamp_millis() - 25 * 3_600_000;
+        for symbol in &symbols {
+            chart_store.add_price(symbol, 105.0, None, chrono::Utc::now().timestamp());
+            for indicator in ["rsi", "macd"] {
+                let mut prediction = SignalPrediction::new(
+                    symbol.clone(),
+                    indicator.to_string(),
+                    SignalDirection::Buy,
+                    60,
+                    100.0,
+                );
+                prediction.timestamp = day_ago;
+                prediction_store.add_prediction(prediction).await;
+            }
+        }
+
+        let store = SignalStore::new(chart_store, prediction_store, accuracy_store);
+        let summary = store.validate_pending_predictions(4).await;
+
+        // 40 symbols x 2 indicators x 4 timeframes
+        assert_eq!(summary.symbols, 40);
+        assert_eq!(summary.validations, 320);
+
+        for symbol in &symbols {
+            let accuracies = store.accuracy_store().get_symbol_accuracies(symbol);
+            assert_eq!(accuracies.len(), 8, "missing outcomes for {}", symbol);
+            assert!(accuracies
+                .iter()
+                .all(|a| a.total_predictions == 1 && a.correct_predictions == 1));
+            for timeframe in VALIDATION_TIMEFRAMES {
+                assert!(store
+                    .prediction_store()
+                    .get_pending(symbol, timeframe)
+                    .is_empty());
+            }
+        }
+
+        // Every timeframe's outcome reached SQLite through the shared connection
+        let archived = sqlite.get_all_predictions(1000);
+        assert_eq!(archived.len(), 80);
+        assert!(archived.iter().all(|p| {
+            [p.outcome_5m, p.outcome_1h, p.outcome_4h, p.outcome_24h]
+                .iter()
+                .all(|o| *o == Some(PredictionOutcome::Correct))
+        }));
+
+        // Nothing is left to validate on the next run
+        let summary = store.validate_pending_predictions(4).await;
+        assert_eq!(summary.validations, 0);
+    }
+
+    #[test]
+    fn test_raw_indicator_serialization() {
+        let raw = RawIndicator {
+            id: "sma200".to_string(),
+            name: "SMA (200)".to_string(),
+            category: SignalCategory::Trend,
+            result: RawIndicatorValue::InsufficientData {
+                required: 200,
+                available: 60,
+            },
+        };
+        let json = serde_json::to_value(&raw).unwrap();
+        assert_eq!(json["status"], "insufficientData");
+        assert_eq!(json["required"], 200);
+        assert_eq!(json["id"], "sma200");
+    }
 }
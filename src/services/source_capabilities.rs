@@ -0,0 +1,195 @@
+//! Which data sources this server was configured to run.
+//!
+//! Several sources need an API key and are silently skipped without one. The
+//! capability report makes that visible at startup and lets handlers that
+//! depend on a disabled source fail with a clear error instead of empty data.
+
+use std::sync::Arc;
+
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::config::Config;
+use crate::error::AppError;
+use crate::services::PriceCache;
+use crate::types::PriceSource;
+
+/// Every source the server knows about, in report order.
+const ALL_SOURCES: [PriceSource; 14] = [
+    PriceSource::Coinbase,
+    PriceSource::CoinGecko,
+    PriceSource::CryptoCompare,
+    PriceSource::CoinMarketCap,
+    PriceSource::Binance,
+    PriceSource::Kraken,
+    PriceSource::KuCoin,
+    PriceSource::Okx,
+    PriceSource::Huobi,
+    PriceSource::Hyperliquid,
+    PriceSource::Finnhub,
+    PriceSource::AlphaVantage,
+    PriceSource::Alpaca,
+    PriceSource::Tiingo,
+];
+
+/// Capability of one source, combining configuration with live state.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourceCapability {
+    pub source: PriceSource,
+    pub enabled: bool,
+    /// Why the source is disabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub online: bool,
+    /// Timestamp (ms) of the last successful update.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_update: Option<u64>,
+    /// Whether the WebSocket feed is connected, for sources that stream
+    /// alongside REST polling.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream_connected: Option<bool>,
+}
+
+/// Sources enabled by the startup configuration.
+pub struct SourceCapabilities {
+    /// `(source, reason)` for each source, with `None` meaning enabled.
+    sources: Vec<(PriceSource, Option<String>)>,
+}
+
+impl SourceCapabilities {
+    /// Determine which sources the given configuration starts.
+    pub fn from_config(config: &Config) -> Arc<Self> {
+        let sources = ALL_SOURCES
+            .iter()
+            .map(|&source| (source, disabled_reason(config, source)))
+            .collect();
+        Arc::new(Self { sources })
+    }
+
+    /// Fail with a "data unavailable" error when the source is disabled.
+    pub fn require(&self, source: PriceSource) -> Result<(), AppError> {
+        match self.sources.iter().find(|(s, _)| *s == source) {
+            Some((_, Some(reason))) => Err(AppError::SourceUnavailable(format!(
+                "{} is disabled ({})",
+                source, reason
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Current capability of every source.
+    pub fn report(&self, price_cache: &PriceCache) -> Vec<SourceCapability> {
+        self.sources
+            .iter()
+            .map(|(source, reason)| SourceCapability {
+                source: *source,
+                enabled: reason.is_none(),
+                reason: reason.clone(),
+                online: price_cache.is_source_online(*source),
+                last_update: price_cache.get_source_last_update(*source),
+                stream_connected: price_cache.is_stream_connected(*source),
+            })
+            .collect()
+    }
+
+    /// Log the enabled and disabled sources.
+    pub fn log(&self) {
+        let enabled: Vec<String> = self
+            .sources
+            .iter()
+            .filter(|(_, reason)| reason.is_none())
+            .map(|(source, _)| source.to_string())
+            .collect();
+        info!("Enabled sources: {}", enabled.join(", "));
+
+        for (source, reason) in &self.sources {
+            if let Some(reason) = reason {
+                warn!("Source {} disabled: {}", source, reason);
+            }
+        }
+    }
+}
+
+/// Why `config` leaves `source` disabled, or `None` if it is started.
+fn disabled_reason(config: &Config, source: PriceSource) -> Option<String> {
+    let missing = |var: &str| Some(format!("missing {}", var));
+    match source {
+        PriceSource::CryptoCompare if config.cryptocompare_api_key.is_none() => {
+            missing("CRYPTOCOMPARE_API_KEY")
+        }
+        PriceSource::CoinMarketCap if config.cmc_api_key.is_none() => missing("CMC_API_KEY"),
+        PriceSource::Finnhub if config.finnhub_api_key.is_none() => missing("FINNHUB_API_KEY"),
+        PriceSource::AlphaVantage if config.alpha_vantage_api_key.is_none() => {
+            missing("ALPHA_VANTAGE_API_KEY")
+        }
+        PriceSource::Alpaca if config.alpaca_api_key.is_none() => missing("ALPACA_API_KEY"),
+        PriceSource::Alpaca if config.alpaca_api_secret.is_none() => missing("ALPACA_API_SECRET"),
+        // The free IEX feed no longer supports the WebSocket, so it is never started.
+        PriceSource::Tiingo => Some("not supported on the free tier".to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::AggregationConfig;
+
+    fn capability(report: &[SourceCapability], source: PriceSource) -> &SourceCapability {
+        report.iter().find(|c| c.source == source).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_report_reflects_missing_api_key() {
+        let config = Config {
+            cmc_api_key: Some("key".to_string()),
+            cryptocompare_api_key: None,
+            ..Config::default()
+        };
+        let capabilities = SourceCapabilities::from_config(&config);
+        let (price_cache, _rx) = PriceCache::new(AggregationConfig {
+            throttle_ms: 0,
+            ..AggregationConfig::default()
+        });
+        price_cache.update_price("btc", PriceSource::CoinMarketCap, 50000.0, None);
+
+        let report = capabilities.report(&price_cache);
+        let cmc = capability(&report, PriceSource::CoinMarketCap);
+        assert!(cmc.enabled);
+        assert!(cmc.reason.is_none());
+        assert!(cmc.last_update.is_some());
+
+        let cryptocompare = capability(&report, PriceSource::CryptoCompare);
+        assert!(!cryptocompare.enabled);
+        assert_eq!(
+            cryptocompare.reason.as_deref(),
+            Some("missing CRYPTOCOMPARE_API_KEY")
+        );
+        assert!(cryptocompare.last_update.is_none());
+
+        assert!(capabilities.require(PriceSource::CoinMarketCap).is_ok());
+        assert!(matches!(
+            capabilities.require(PriceSource::CryptoCompare),
+            Err(AppError::SourceUnavailable(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_stream_disconnect_keeps_rest_source_online() {
+        let capabilities = SourceCapabilities::from_config(&Config::default());
+        let (price_cache, _rx) = PriceCache::new(AggregationConfig {
+            throttle_ms: 0,
+            ..AggregationConfig::default()
+        });
+        price_cache.update_price("btc", PriceSource::Kraken, 50000.0, None);
+        price_cache.set_stream_connected(PriceSource::Kraken, true);
+        price_cache.set_stream_connected(PriceSource::Kraken, false);
+
+        let report = capabilities.report(&price_cache);
+        let kraken = capability(&report, PriceSource::Kraken);
+        assert!(kraken.online);
+        assert_eq!(kraken.stream_connected, Some(false));
+        assert!(capability(&report, PriceSource::Coinbase).stream_connected.is_none());
+    }
+}
@@ -0,0 +1,164 @@
+//! Per-source reliability tracking based on drift from consensus.
+//!
+//! A single bad tick is handled by staleness and aggregation, but a source
+//! that is consistently off (e.g. a stale symbol mapping) keeps pulling the
+//! aggregate away. This tracks each source's smoothed relative deviation from
+//! the other sources and turns it into a reliability score used to weight
+//! aggregation.
+
+use crate::types::PriceSource;
+use dashmap::DashMap;
+use tracing::{info, warn};
+
+/// Smoothing factor for the rolling deviation (higher = reacts faster).
+const DEVIATION_ALPHA: f64 = 0.1;
+
+/// Relative deviation at which reliability falls to 0.5 (0.5%).
+const DEVIATION_SCALE: f64 = 0.005;
+
+/// Reliability below which a source is flagged as drifting.
+pub const RELIABILITY_THRESHOLD: f64 = 0.5;
+
+/// Minimum number of sources (including the one being scored) to form a consensus.
+const MIN_SOURCES: usize = 3;
+
+/// Rolling deviation state for one source.
+#[derive(Debug, Default)]
+struct DeviationState {
+    /// Exponentially weighted relative deviation from consensus, starting at 0.
+    deviation: f64,
+    /// Whether the source is currently flagged as unreliable.
+    flagged: bool,
+}
+
+/// Tracks how closely each source agrees with the others.
+#[derive(Debug, Default)]
+pub struct SourceReliability {
+    states: DashMap<PriceSource, DeviationState>,
+}
+
+impl SourceReliability {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a price from `source` against the current prices of all sources.
+    ///
+    /// The consensus is the median of `prices` (which includes `price`); samples
+    /// with fewer than three sources are ignored since there is no majority to
+    /// compare against.
+    pub fn record(&self, symbol: &str, source: PriceSource, price: f64, prices: &[f64]) {
+        if prices.len() < MIN_SOURCES {
+            return;
+        }
+        let Some(consensus) = median(prices) else {
+            return;
+        };
+        if consensus <= 0.0 {
+            return;
+        }
+
+        let deviation = (price - consensus).abs() / consensus;
+        let mut state = self.states.entry(source).or_default();
+        state.deviation = DEVIATION_ALPHA * deviation + (1.0 - DEVIATION_ALPHA) * state.deviation;
+
+        let score = Self::score_for(state.deviation);
+        if !state.flagged && score < RELIABILITY_THRESHOLD {
+            state.flagged = true;
+            warn!(
+                "Source {:?} drifting from consensus on {}: reliability {:.2} ({:.2}% avg deviation)",
+                source,
+                symbol,
+                score,
+                state.deviation * 100.0
+            );
+        } else if state.flagged && score >= RELIABILITY_THRESHOLD {
+            state.flagged = false;
+            info!(
+                "Source {:?} back in line with consensus: reliability {:.2}",
+                source, score
+            );
+        }
+    }
+
+    /// Reliability score for a source in (0, 1]; 1.0 if nothing has been recorded.
+    pub fn score(&self, source: PriceSource) -> f64 {
+        self.states
+            .get(&source)
+            .map(|s| Self::score_for(s.deviation))
+            .unwrap_or(1.0)
+    }
+
+    /// Reliability score for every tracked source, least reliable first.
+    pub fn scores(&self) -> Vec<(PriceSource, f64)> {
+        let mut scores: Vec<(PriceSource, f64)> = self
+            .states
+            .iter()
+            .map(|entry| (*entry.key(), Self::score_for(entry.deviation)))
+            .collect();
+        scores.sort_by(|a, b| a.1.total_cmp(&b.1));
+        scores
+    }
+
+    fn score_for(deviation: f64) -> f64 {
+        1.0 / (1.0 + deviation / DEVIATION_SCALE)
+    }
+}
+
+fn median(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let mid = sorted.len() / 2;
+    Some(if sorted.len().is_multiple_of(2) {
+        (sorted[mid - 1] + sorted[mid]) / 2.0
+    } else {
+        sorted[mid]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_untracked_source_is_fully_reliable() {
+        let reliability = SourceReliability::new();
+        assert_eq!(reliability.score(PriceSource::Binance), 1.0);
+        assert!(reliability.scores().is_empty());
+    }
+
+    #[test]
+    fn test_constant_offset_degrades_reliability() {
+        let reliability = SourceReliability::new();
+        let mut previous = 1.0;
+        for i in 0..30 {
+            let base = 100.0 + i as f64 * 0.1;
+            let prices = [base, base, base * 1.03];
+            reliability.record("btc", PriceSource::Binance, base, &prices);
+            reliability.record("btc", PriceSource::Kraken, base, &prices);
+            reliability.record("btc", PriceSource::Huobi, base * 1.03, &prices);
+
+            let score = reliability.score(PriceSource::Huobi);
+            assert!(score < previous, "sample {}: {} >= {}", i, score, previous);
+            if i == 0 {
+                // A single drifting sample is not enough to flag a source
+                assert!(score > RELIABILITY_THRESHOLD);
+            }
+            previous = score;
+        }
+
+        assert!(previous < RELIABILITY_THRESHOLD);
+        assert_eq!(reliability.score(PriceSource::Binance), 1.0);
+        assert_eq!(reliability.scores()[0].0, PriceSource::Huobi);
+    }
+
+    #[test]
+    fn test_needs_three_sources() {
+        let reliability = SourceReliability::new();
+        reliability.record("btc", PriceSource::Huobi, 200.0, &[100.0, 200.0]);
+        assert_eq!(reliability.score(PriceSource::Huobi), 1.0);
+    }
+}
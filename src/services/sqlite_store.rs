@@ -9,32 +9,186 @@
 //! - Recent predictions (7-day TTL, quick access)
 
 use crate::types::{
-    AssetClass, BracketRole, CostBasisMethod, EquityPoint, Fill, FundingPayment, Greeks,
-    InsuranceFund, Liquidation, MarginChangeType, MarginHistory, MarginMode, OptionPosition,
+    AssetClass, BracketRole, Competition, CostBasisMethod, EquityPoint, ExecutionAlgo,
+    ExecutionSchedule, ExecutionSlice, ExecutionStatus, Fill, FundingPayment, Greeks,
+    FUNDING_INTERVAL_MS, MAX_FUNDING_HISTORY_POINTS,
+    InsuranceFund, Liquidation, MarginChangeType, MarginHistory, MarginMode, Notification,
+    NotificationType, OptionPosition,
     OptionStyle, OptionType, Order, OrderSide, OrderStatus, OrderType, Position, PositionSide,
     Portfolio, PredictionOutcome, Profile, ProfileSettings, RiskSettings, SignalPrediction,
-    StrategyStatus, TimeInForce, Trade, TradingRule, TradingStrategy,
+    StrategyStatus, TimeInForce, Trade, TradeWindowStats, TradingRule, TradingStrategy,
 };
-use rusqlite::{params, Connection};
-use std::path::Path;
-use std::sync::Mutex;
-use tracing::{debug, error, info};
+use r2d2::{ManageConnection, Pool, PooledConnection};
+use rusqlite::backup::Backup;
+use rusqlite::{params, Connection, OpenFlags, OptionalExtension};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+/// Pages copied per backup step.
+const BACKUP_PAGES_PER_STEP: i32 = 256;
+
+/// Pause between backup steps so writers can make progress.
+const BACKUP_STEP_PAUSE: Duration = Duration::from_millis(5);
+
+/// File name prefix for scheduled backups.
+const BACKUP_FILE_PREFIX: &str = "haunt-backup-";
+
+/// Read-only connections kept open for file-backed stores.
+const READ_POOL_SIZE: u32 = 4;
+
+/// How long a statement waits on a locked database before failing.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long a read waits for a pooled connection before using the writer.
+const READ_POOL_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Unlock an SQLCipher database, or set the key on a new one. Must run before
+/// any other statement on the connection.
+fn apply_key(conn: &Connection, key: &str) -> Result<(), rusqlite::Error> {
+    conn.pragma_update(None, "key", key)?;
+
+    // Plain SQLite silently ignores PRAGMA key; only SQLCipher reports a version
+    let cipher_version: Option<String> = conn
+        .query_row("PRAGMA cipher_version", [], |row| row.get(0))
+        .optional()?;
+    if cipher_version.is_none() {
+        return Err(rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_MISUSE),
+            Some("an encryption key was given but SQLite was built without SQLCipher".into()),
+        ));
+    }
+
+    // A wrong key only surfaces on the first read
+    conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(()))
+}
+
+/// Opens read-only connections to a store's database file.
+struct ReadConnectionManager {
+    path: PathBuf,
+    key: Option<String>,
+}
+
+impl ManageConnection for ReadConnectionManager {
+    type Connection = Connection;
+    type Error = rusqlite::Error;
+
+    fn connect(&self) -> Result<Connection, rusqlite::Error> {
+        let conn = Connection::open_with_flags(
+            &self.path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )?;
+        if let Some(key) = &self.key {
+            apply_key(&conn, key)?;
+        }
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        Ok(conn)
+    }
+
+    fn is_valid(&self, conn: &mut Connection) -> Result<(), rusqlite::Error> {
+        conn.execute_batch("")
+    }
+
+    fn has_broken(&self, _conn: &mut Connection) -> bool {
+        false
+    }
+}
+
+/// A connection for running queries: pooled when available, otherwise the writer.
+enum ReadConnection<'a> {
+    Pooled(PooledConnection<ReadConnectionManager>),
+    Writer(MutexGuard<'a, Connection>),
+}
+
+impl Deref for ReadConnection<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        match self {
+            ReadConnection::Pooled(conn) => conn,
+            ReadConnection::Writer(conn) => conn,
+        }
+    }
+}
+
 /// SQLite store for persistent profile and prediction data.
+///
+/// Writes go through a single connection. File-backed stores run in WAL mode
+/// and serve reads from a pool of read-only connections, so a slow query does
+/// not hold up writers; in-memory stores read through the writer.
 pub struct SqliteStore {
     conn: Mutex<Connection>,
+    /// Read-only connections (None for in-memory stores).
+    readers: Option<Pool<ReadConnectionManager>>,
+    /// Database file path (None for in-memory stores).
+    path: Option<PathBuf>,
+    /// SQLCipher key for encrypted databases.
+    key: Option<String>,
 }
 
 impl SqliteStore {
     /// Create a new SQLite store at the given path.
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, rusqlite::Error> {
+        Self::new_with_key(path, None)
+    }
+
+    /// Create a SQLite store at the given path, encrypted at rest with
+    /// SQLCipher when `key` is set. Opening an existing database with the
+    /// wrong key fails, as does passing a key to a build without SQLCipher.
+    pub fn new_with_key<P: AsRef<Path>>(
+        path: P,
+        key: Option<&str>,
+    ) -> Result<Self, rusqlite::Error> {
+        let path = path.as_ref();
+        // Each ":memory:" connection is its own database, so those read via the writer
+        let in_memory = path.as_os_str().is_empty() || path == Path::new(":memory:");
+
         let conn = Connection::open(path)?;
-        let store = Self {
+        if let Some(key) = key {
+            apply_key(&conn, key)?;
+        }
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        if !in_memory {
+            // WAL lets the read pool run alongside the writer
+            let journal_mode: String =
+                conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get(0))?;
+            if !journal_mode.eq_ignore_ascii_case("wal") {
+                warn!("SQLite journal mode is {}, reads may wait on writes", journal_mode);
+            }
+        }
+
+        let mut store = Self {
             conn: Mutex::new(conn),
+            readers: None,
+            path: Some(path.to_path_buf()),
+            key: key.map(str::to_string),
         };
         store.init_schema()?;
-        info!("SQLite store initialized");
+
+        if !in_memory {
+            // Open one reader now so a bad path or key fails here rather than on first read
+            let manager = ReadConnectionManager {
+                path: path.to_path_buf(),
+                key: store.key.clone(),
+            };
+            manager.connect()?;
+            store.readers = Some(
+                Pool::builder()
+                    .max_size(READ_POOL_SIZE)
+                    .min_idle(Some(1))
+                    .connection_timeout(READ_POOL_TIMEOUT)
+                    .build_unchecked(manager),
+            );
+        }
+
+        if store.key.is_some() {
+            info!("SQLite store initialized (encrypted)");
+        } else {
+            info!("SQLite store initialized");
+        }
         Ok(store)
     }
 
@@ -43,12 +197,102 @@ impl SqliteStore {
         let conn = Connection::open_in_memory()?;
         let store = Self {
             conn: Mutex::new(conn),
+            readers: None,
+            path: None,
+            key: None,
         };
         store.init_schema()?;
         debug!("In-memory SQLite store initialized");
         Ok(store)
     }
 
+    /// Check out a connection for a read-only query.
+    fn reader(&self) -> ReadConnection<'_> {
+        if let Some(readers) = &self.readers {
+            match readers.get() {
+                Ok(conn) => return ReadConnection::Pooled(conn),
+                Err(e) => warn!("SQLite read pool unavailable, reading via writer: {}", e),
+            }
+        }
+        ReadConnection::Writer(self.conn.lock().unwrap())
+    }
+
+    /// Snapshot the database to `path` using SQLite's online backup API.
+    ///
+    /// File-backed stores are copied from a separate read-only connection in small
+    /// steps, so the shared connection stays available to writers during the backup.
+    /// Backups of an encrypted store are encrypted with the same key.
+    pub fn backup_to<P: AsRef<Path>>(&self, path: P) -> Result<(), rusqlite::Error> {
+        let mut dst = Connection::open(path.as_ref())?;
+        if let Some(key) = &self.key {
+            apply_key(&dst, key)?;
+        }
+
+        match &self.path {
+            Some(src_path) => {
+                let src = Connection::open_with_flags(
+                    src_path,
+                    OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+                )?;
+                if let Some(key) = &self.key {
+                    apply_key(&src, key)?;
+                }
+                let backup = Backup::new(&src, &mut dst)?;
+                backup.run_to_completion(BACKUP_PAGES_PER_STEP, BACKUP_STEP_PAUSE, None)?;
+            }
+            None => {
+                // In-memory databases are only reachable through the shared connection
+                let conn = self.conn.lock().unwrap();
+                let backup = Backup::new(&conn, &mut dst)?;
+                backup.step(-1)?;
+            }
+        }
+
+        debug!("SQLite backup written to {}", path.as_ref().display());
+        Ok(())
+    }
+
+    /// Write a timestamped backup into `dir`, keeping only the newest `retain` backups.
+    /// Returns the path of the new backup.
+    pub fn backup_rotating<P: AsRef<Path>>(
+        &self,
+        dir: P,
+        retain: usize,
+    ) -> anyhow::Result<PathBuf> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+
+        let file_name = format!(
+            "{}{}.db",
+            BACKUP_FILE_PREFIX,
+            chrono::Utc::now().format("%Y%m%d%H%M%S%3f")
+        );
+        let backup_path = dir.join(file_name);
+        self.backup_to(&backup_path)?;
+
+        // Timestamped names sort chronologically, so the oldest come first
+        let mut backups: Vec<PathBuf> = std::fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| {
+                p.file_name()
+                    .and_then(|n| n.to_str())
+                    .map(|n| n.starts_with(BACKUP_FILE_PREFIX) && n.ends_with(".db"))
+                    .unwrap_or(false)
+            })
+            .collect();
+        backups.sort();
+
+        let excess = backups.len().saturating_sub(retain.max(1));
+        for old in backups.into_iter().take(excess) {
+            if let Err(e) = std::fs::remove_file(&old) {
+                warn!("Failed to remove old SQLite backup {}: {}", old.display(), e);
+            }
+        }
+
+        info!("SQLite backup created at {}", backup_path.display());
+        Ok(backup_path)
+    }
+
     /// Initialize database schema.
     fn init_schema(&self) -> Result<(), rusqlite::Error> {
         let conn = self.conn.lock().unwrap();
@@ -64,7 +308,9 @@ impl SqliteStore {
                 show_on_leaderboard INTEGER NOT NULL DEFAULT 0,
                 leaderboard_signature TEXT,
                 leaderboard_consent_at INTEGER,
-                settings_json TEXT DEFAULT '{}'
+                settings_json TEXT DEFAULT '{}',
+                bio TEXT,
+                avatar_url TEXT
             )",
             [],
         )?;
@@ -86,6 +332,8 @@ impl SqliteStore {
             "ALTER TABLE profiles ADD COLUMN leaderboard_consent_at INTEGER",
             [],
         );
+        let _ = conn.execute("ALTER TABLE profiles ADD COLUMN bio TEXT", []);
+        let _ = conn.execute("ALTER TABLE profiles ADD COLUMN avatar_url TEXT", []);
 
         // Index on public_key for faster lookups
         conn.execute(
@@ -168,6 +416,19 @@ impl SqliteStore {
             [],
         )?;
 
+        // Competitions table
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS competitions (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                starting_balance REAL NOT NULL,
+                start_time INTEGER NOT NULL,
+                end_time INTEGER NOT NULL,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
         // Orders table
         conn.execute(
             "CREATE TABLE IF NOT EXISTS orders (
@@ -198,11 +459,15 @@ impl SqliteStore {
                 trail_high_price REAL,
                 trail_low_price REAL,
                 bracket_role TEXT,
+                display_quantity REAL,
                 FOREIGN KEY (portfolio_id) REFERENCES portfolios(id)
             )",
             [],
         )?;
 
+        // Iceberg orders (migration for existing databases)
+        let _ = conn.execute("ALTER TABLE orders ADD COLUMN display_quantity REAL", []);
+
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_orders_portfolio_id ON orders(portfolio_id)",
             [],
@@ -215,6 +480,14 @@ impl SqliteStore {
             "CREATE INDEX IF NOT EXISTS idx_orders_symbol ON orders(symbol)",
             [],
         )?;
+        // Client order IDs are unique per portfolio; orders without one are exempt
+        if let Err(e) = conn.execute(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_orders_client_order_id
+             ON orders(portfolio_id, client_order_id) WHERE client_order_id IS NOT NULL",
+            [],
+        ) {
+            warn!("Could not create unique client_order_id index: {}", e);
+        }
 
         // Positions table
         conn.execute(
@@ -452,6 +725,7 @@ impl SqliteStore {
                 greeks_json TEXT DEFAULT '{}',
                 entry_iv REAL NOT NULL,
                 current_iv REAL NOT NULL,
+                margin_used REAL NOT NULL DEFAULT 0,
                 created_at INTEGER NOT NULL,
                 updated_at INTEGER NOT NULL,
                 closed_at INTEGER,
@@ -459,6 +733,7 @@ impl SqliteStore {
             )",
             [],
         )?;
+        let _ = conn.execute("ALTER TABLE options_positions ADD COLUMN margin_used REAL NOT NULL DEFAULT 0", []);
 
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_options_positions_portfolio ON options_positions(portfolio_id)",
@@ -510,6 +785,76 @@ impl SqliteStore {
             [],
         )?;
 
+        // Execution schedules (TWAP/VWAP parent orders)
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS execution_schedules (
+                id TEXT PRIMARY KEY,
+                portfolio_id TEXT NOT NULL,
+                symbol TEXT NOT NULL,
+                asset_class TEXT NOT NULL,
+                side TEXT NOT NULL,
+                algo TEXT NOT NULL,
+                quantity REAL NOT NULL,
+                leverage REAL NOT NULL DEFAULT 1.0,
+                duration_secs INTEGER NOT NULL,
+                slices_json TEXT NOT NULL DEFAULT '[]',
+                status TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL,
+                FOREIGN KEY (portfolio_id) REFERENCES portfolios(id)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_execution_schedules_portfolio
+             ON execution_schedules(portfolio_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_execution_schedules_status
+             ON execution_schedules(status)",
+            [],
+        )?;
+
+        // ========== Notification Tables ==========
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS notifications (
+                id TEXT PRIMARY KEY,
+                user_id TEXT NOT NULL,
+                notification_type TEXT NOT NULL,
+                title TEXT NOT NULL,
+                message TEXT NOT NULL DEFAULT '',
+                read INTEGER NOT NULL DEFAULT 0,
+                created_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_notifications_user_read ON notifications(user_id, read)",
+            [],
+        )?;
+
+        // ========== Social Tables ==========
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS follows (
+                follower_public_key TEXT NOT NULL,
+                followee_public_key TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                PRIMARY KEY (follower_public_key, followee_public_key),
+                CHECK (follower_public_key != followee_public_key)
+            )",
+            [],
+        )?;
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_follows_followee ON follows(followee_public_key)",
+            [],
+        )?;
+
         info!("SQLite schema initialized");
         Ok(())
     }
@@ -518,11 +863,12 @@ impl SqliteStore {
 
     /// Get a profile by public key.
     pub fn get_profile(&self, public_key: &str) -> Option<Profile> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let result = conn.query_row(
             "SELECT id, public_key, username, created_at, last_seen,
-                    show_on_leaderboard, leaderboard_signature, leaderboard_consent_at, settings_json
+                    show_on_leaderboard, leaderboard_signature, leaderboard_consent_at, settings_json,
+                    bio, avatar_url
              FROM profiles WHERE public_key = ?1",
             params![public_key],
             |row| {
@@ -539,6 +885,8 @@ impl SqliteStore {
                     show_on_leaderboard: row.get::<_, i64>(5)? != 0,
                     leaderboard_signature: row.get(6)?,
                     leaderboard_consent_at: row.get(7)?,
+                    bio: row.get(9)?,
+                    avatar_url: row.get(10)?,
                     settings,
                 })
             },
@@ -561,15 +909,18 @@ impl SqliteStore {
 
         conn.execute(
             "INSERT INTO profiles (id, public_key, username, created_at, last_seen,
-                                   show_on_leaderboard, leaderboard_signature, leaderboard_consent_at, settings_json)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                                   show_on_leaderboard, leaderboard_signature, leaderboard_consent_at, settings_json,
+                                   bio, avatar_url)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
              ON CONFLICT(public_key) DO UPDATE SET
                 username = excluded.username,
                 last_seen = excluded.last_seen,
                 show_on_leaderboard = excluded.show_on_leaderboard,
                 leaderboard_signature = excluded.leaderboard_signature,
                 leaderboard_consent_at = excluded.leaderboard_consent_at,
-                settings_json = excluded.settings_json",
+                settings_json = excluded.settings_json,
+                bio = excluded.bio,
+                avatar_url = excluded.avatar_url",
             params![
                 profile.id,
                 profile.public_key,
@@ -580,6 +931,8 @@ impl SqliteStore {
                 profile.leaderboard_signature,
                 profile.leaderboard_consent_at,
                 settings_json,
+                profile.bio,
+                profile.avatar_url,
             ],
         )?;
 
@@ -615,11 +968,185 @@ impl SqliteStore {
 
     /// Get total profile count.
     pub fn profile_count(&self) -> usize {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
         conn.query_row("SELECT COUNT(*) FROM profiles", [], |row| row.get(0))
             .unwrap_or(0)
     }
 
+    // ========== Notification Methods ==========
+
+    /// Store a new notification.
+    pub fn create_notification(&self, notification: &Notification) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT INTO notifications (id, user_id, notification_type, title, message, read, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                notification.id,
+                notification.user_id,
+                notification.notification_type.to_string(),
+                notification.title,
+                notification.message,
+                notification.read as i64,
+                notification.created_at,
+            ],
+        )?;
+
+        debug!(
+            "Created {} notification {} for {}",
+            notification.notification_type,
+            notification.id,
+            &notification.user_id[..16.min(notification.user_id.len())]
+        );
+        Ok(())
+    }
+
+    /// Get a user's notifications, newest first.
+    pub fn get_notifications(
+        &self,
+        user_id: &str,
+        unread_only: bool,
+        limit: usize,
+    ) -> Vec<Notification> {
+        let conn = self.reader();
+
+        let query = if unread_only {
+            "SELECT id, user_id, notification_type, title, message, read, created_at
+             FROM notifications WHERE user_id = ?1 AND read = 0
+             ORDER BY created_at DESC LIMIT ?2"
+        } else {
+            "SELECT id, user_id, notification_type, title, message, read, created_at
+             FROM notifications WHERE user_id = ?1
+             ORDER BY created_at DESC LIMIT ?2"
+        };
+
+        let mut stmt = match conn.prepare(query) {
+            Ok(stmt) => stmt,
+            Err(e) => {
+                error!("Error preparing notifications query: {}", e);
+                return Vec::new();
+            }
+        };
+
+        stmt.query_map(params![user_id, limit as i64], |row| {
+            Ok(Notification {
+                id: row.get(0)?,
+                user_id: row.get(1)?,
+                notification_type: parse_notification_type(&row.get::<_, String>(2)?),
+                title: row.get(3)?,
+                message: row.get(4)?,
+                read: row.get::<_, i64>(5)? != 0,
+                created_at: row.get(6)?,
+            })
+        })
+        .map(|rows| rows.filter_map(|r| r.ok()).collect())
+        .unwrap_or_default()
+    }
+
+    /// Get the number of unread notifications for a user.
+    pub fn unread_notification_count(&self, user_id: &str) -> usize {
+        let conn = self.reader();
+        conn.query_row(
+            "SELECT COUNT(*) FROM notifications WHERE user_id = ?1 AND read = 0",
+            params![user_id],
+            |row| row.get(0),
+        )
+        .unwrap_or(0)
+    }
+
+    /// Mark all of a user's notifications as read.
+    /// Returns the number of notifications that were unread.
+    pub fn mark_all_notifications_read(&self, user_id: &str) -> Result<usize, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE notifications SET read = 1 WHERE user_id = ?1 AND read = 0",
+            params![user_id],
+        )
+    }
+
+    // ========== Follow Methods ==========
+
+    /// Follow a user.
+    /// Returns false if already following or if `follower` and `followee` are the same.
+    pub fn follow(&self, follower: &str, followee: &str) -> Result<bool, rusqlite::Error> {
+        if follower == followee {
+            return Ok(false);
+        }
+        let conn = self.conn.lock().unwrap();
+        let inserted = conn.execute(
+            "INSERT OR IGNORE INTO follows (follower_public_key, followee_public_key, created_at)
+             VALUES (?1, ?2, ?3)",
+            params![follower, followee, chrono::Utc::now().timestamp_millis()],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    /// Unfollow a user. Returns false if `follower` wasn't following `followee`.
+    pub fn unfollow(&self, follower: &str, followee: &str) -> Result<bool, rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let deleted = conn.execute(
+            "DELETE FROM follows WHERE follower_public_key = ?1 AND followee_public_key = ?2",
+            params![follower, followee],
+        )?;
+        Ok(deleted > 0)
+    }
+
+    /// Public keys `public_key` follows, most recent first.
+    pub fn get_following(&self, public_key: &str) -> Vec<String> {
+        self.query_follows(
+            "SELECT followee_public_key FROM follows
+             WHERE follower_public_key = ?1 ORDER BY created_at DESC",
+            public_key,
+        )
+    }
+
+    /// Public keys following `public_key`, most recent first.
+    pub fn get_followers(&self, public_key: &str) -> Vec<String> {
+        self.query_follows(
+            "SELECT follower_public_key FROM follows
+             WHERE followee_public_key = ?1 ORDER BY created_at DESC",
+            public_key,
+        )
+    }
+
+    /// Number of users following `public_key`.
+    pub fn follower_count(&self, public_key: &str) -> usize {
+        let conn = self.reader();
+        conn.query_row(
+            "SELECT COUNT(*) FROM follows WHERE followee_public_key = ?1",
+            params![public_key],
+            |row| row.get(0),
+        )
+        .unwrap_or(0)
+    }
+
+    /// Number of users `public_key` follows.
+    pub fn following_count(&self, public_key: &str) -> usize {
+        let conn = self.reader();
+        conn.query_row(
+            "SELECT COUNT(*) FROM follows WHERE follower_public_key = ?1",
+            params![public_key],
+            |row| row.get(0),
+        )
+        .unwrap_or(0)
+    }
+
+    fn query_follows(&self, query: &str, public_key: &str) -> Vec<String> {
+        let conn = self.reader();
+        let mut stmt = match conn.prepare(query) {
+            Ok(stmt) => stmt,
+            Err(e) => {
+                error!("Error preparing follows query: {}", e);
+                return Vec::new();
+            }
+        };
+
+        stmt.query_map(params![public_key], |row| row.get(0))
+            .map(|rows| rows.filter_map(|r| r.ok()).collect())
+            .unwrap_or_default()
+    }
+
     // ========== Prediction History Methods ==========
 
     /// Archive a prediction to SQLite.
@@ -686,7 +1213,7 @@ impl SqliteStore {
         status: Option<&str>,
         limit: usize,
     ) -> Vec<SignalPrediction> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
         let symbol_lower = symbol.to_lowercase();
 
         let query = match status {
@@ -759,7 +1286,7 @@ impl SqliteStore {
 
     /// Get all predictions across all symbols (for loading on startup).
     pub fn get_all_predictions(&self, limit: usize) -> Vec<SignalPrediction> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let query =
             "SELECT id, symbol, indicator, direction, score, price_at_prediction, timestamp,
@@ -811,18 +1338,21 @@ impl SqliteStore {
         self.conn.lock().ok().map(|_| ())
     }
 
+    /// Check the database accepts writes.
+    ///
+    /// Takes and immediately releases the write lock, so a read-only or
+    /// locked database fails without changing any data.
+    pub fn check_writable(&self) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute_batch("BEGIN IMMEDIATE; ROLLBACK;")
+    }
+
     /// Get accuracy statistics for a symbol.
     pub fn get_accuracy_stats(&self, symbol: &str, timeframe: &str) -> AccuracyStats {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
         let symbol_lower = symbol.to_lowercase();
 
-        let outcome_col = match timeframe {
-            "5m" => "outcome_5m",
-            "1h" => "outcome_1h",
-            "4h" => "outcome_4h",
-            "24h" => "outcome_24h",
-            _ => "outcome_1h",
-        };
+        let outcome_col = outcome_column(timeframe);
 
         let query = format!(
             "SELECT
@@ -849,15 +1379,9 @@ impl SqliteStore {
 
     /// Get overall accuracy across all symbols.
     pub fn get_global_accuracy(&self, timeframe: &str) -> AccuracyStats {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
-        let outcome_col = match timeframe {
-            "5m" => "outcome_5m",
-            "1h" => "outcome_1h",
-            "4h" => "outcome_4h",
-            "24h" => "outcome_24h",
-            _ => "outcome_1h",
-        };
+        let outcome_col = outcome_column(timeframe);
 
         let query = format!(
             "SELECT
@@ -882,9 +1406,54 @@ impl SqliteStore {
         result.unwrap_or_default()
     }
 
+    /// Get accuracy across all symbols, broken down by indicator.
+    ///
+    /// Returns one entry per indicator with a recorded outcome for the
+    /// timeframe, ordered by indicator name.
+    pub fn get_indicator_accuracy(&self, timeframe: &str) -> Vec<(String, AccuracyStats)> {
+        let conn = self.reader();
+        let outcome_col = outcome_column(timeframe);
+
+        let query = format!(
+            "SELECT
+                indicator,
+                COUNT(*) as total,
+                SUM(CASE WHEN {} = 'correct' THEN 1 ELSE 0 END) as correct,
+                SUM(CASE WHEN {} = 'incorrect' THEN 1 ELSE 0 END) as incorrect,
+                SUM(CASE WHEN {} = 'neutral' THEN 1 ELSE 0 END) as neutral
+             FROM prediction_history
+             WHERE {} IS NOT NULL
+             GROUP BY indicator
+             ORDER BY indicator ASC",
+            outcome_col, outcome_col, outcome_col, outcome_col
+        );
+
+        let mut stmt = match conn.prepare(&query) {
+            Ok(stmt) => stmt,
+            Err(e) => {
+                error!("Error preparing indicator accuracy query: {}", e);
+                return Vec::new();
+            }
+        };
+
+        stmt.query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                AccuracyStats {
+                    total: row.get(1)?,
+                    correct: row.get(2)?,
+                    incorrect: row.get(3)?,
+                    neutral: row.get(4)?,
+                },
+            ))
+        })
+        .map(|rows| rows.filter_map(|r| r.ok()).collect())
+        .unwrap_or_default()
+    }
+
     /// Get prediction count for a symbol.
     pub fn prediction_count(&self, symbol: &str) -> usize {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
         conn.query_row(
             "SELECT COUNT(*) FROM prediction_history WHERE symbol = ?1",
             params![symbol.to_lowercase()],
@@ -954,7 +1523,7 @@ impl SqliteStore {
 
     /// Get a portfolio by ID.
     pub fn get_portfolio(&self, id: &str) -> Option<Portfolio> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let result = conn.query_row(
             "SELECT id, user_id, name, description, base_currency, starting_balance,
@@ -978,7 +1547,7 @@ impl SqliteStore {
 
     /// Get all portfolios for a user.
     pub fn get_user_portfolios(&self, user_id: &str) -> Vec<Portfolio> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let mut stmt = match conn.prepare(
             "SELECT id, user_id, name, description, base_currency, starting_balance,
@@ -999,6 +1568,52 @@ impl SqliteStore {
             .unwrap_or_default()
     }
 
+    /// Get every portfolio.
+    pub fn get_all_portfolios(&self) -> Vec<Portfolio> {
+        let conn = self.reader();
+
+        let mut stmt = match conn.prepare(
+            "SELECT id, user_id, name, description, base_currency, starting_balance,
+                    cash_balance, margin_used, margin_available, unrealized_pnl, realized_pnl,
+                    total_value, cost_basis_method, risk_settings_json, is_competition,
+                    competition_id, created_at, updated_at, total_trades, winning_trades
+             FROM portfolios",
+        ) {
+            Ok(stmt) => stmt,
+            Err(e) => {
+                error!("Error preparing portfolio query: {}", e);
+                return Vec::new();
+            }
+        };
+
+        stmt.query_map([], Self::row_to_portfolio)
+            .map(|rows| rows.filter_map(|r| r.ok()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Get all portfolios enrolled in a competition.
+    pub fn get_competition_portfolios(&self, competition_id: &str) -> Vec<Portfolio> {
+        let conn = self.reader();
+
+        let mut stmt = match conn.prepare(
+            "SELECT id, user_id, name, description, base_currency, starting_balance,
+                    cash_balance, margin_used, margin_available, unrealized_pnl, realized_pnl,
+                    total_value, cost_basis_method, risk_settings_json, is_competition,
+                    competition_id, created_at, updated_at, total_trades, winning_trades
+             FROM portfolios WHERE competition_id = ?1",
+        ) {
+            Ok(stmt) => stmt,
+            Err(e) => {
+                error!("Error preparing competition portfolio query: {}", e);
+                return Vec::new();
+            }
+        };
+
+        stmt.query_map(params![competition_id], Self::row_to_portfolio)
+            .map(|rows| rows.filter_map(|r| r.ok()).collect())
+            .unwrap_or_default()
+    }
+
     /// Update a portfolio.
     pub fn update_portfolio(&self, portfolio: &Portfolio) -> Result<(), rusqlite::Error> {
         let conn = self.conn.lock().unwrap();
@@ -1009,8 +1624,9 @@ impl SqliteStore {
                 name = ?1, description = ?2, cash_balance = ?3, margin_used = ?4,
                 margin_available = ?5, unrealized_pnl = ?6, realized_pnl = ?7,
                 total_value = ?8, cost_basis_method = ?9, risk_settings_json = ?10,
-                updated_at = ?11, total_trades = ?12, winning_trades = ?13
-             WHERE id = ?14",
+                updated_at = ?11, total_trades = ?12, winning_trades = ?13,
+                starting_balance = ?14, is_competition = ?15, competition_id = ?16
+             WHERE id = ?17",
             params![
                 portfolio.name,
                 portfolio.description,
@@ -1025,6 +1641,9 @@ impl SqliteStore {
                 portfolio.updated_at,
                 portfolio.total_trades,
                 portfolio.winning_trades,
+                portfolio.starting_balance,
+                portfolio.is_competition as i32,
+                portfolio.competition_id,
                 portfolio.id,
             ],
         )?;
@@ -1077,6 +1696,93 @@ impl SqliteStore {
         })
     }
 
+    // ========== Competition Methods ==========
+
+    /// Create a new competition.
+    pub fn create_competition(&self, competition: &Competition) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.execute(
+            "INSERT INTO competitions (id, name, starting_balance, start_time, end_time, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                competition.id,
+                competition.name,
+                competition.starting_balance,
+                competition.start_time,
+                competition.end_time,
+                competition.created_at,
+            ],
+        )?;
+
+        debug!("Created competition {}", competition.id);
+        Ok(())
+    }
+
+    /// Get a competition by ID.
+    pub fn get_competition(&self, id: &str) -> Option<Competition> {
+        let conn = self.reader();
+
+        let result = conn.query_row(
+            "SELECT id, name, starting_balance, start_time, end_time, created_at
+             FROM competitions WHERE id = ?1",
+            params![id],
+            Self::row_to_competition,
+        );
+
+        match result {
+            Ok(competition) => Some(competition),
+            Err(rusqlite::Error::QueryReturnedNoRows) => None,
+            Err(e) => {
+                error!("Error fetching competition: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Get all competitions, most recently started first.
+    pub fn get_competitions(&self) -> Vec<Competition> {
+        let conn = self.reader();
+
+        let mut stmt = match conn.prepare(
+            "SELECT id, name, starting_balance, start_time, end_time, created_at
+             FROM competitions ORDER BY start_time DESC",
+        ) {
+            Ok(stmt) => stmt,
+            Err(e) => {
+                error!("Error preparing competitions query: {}", e);
+                return Vec::new();
+            }
+        };
+
+        stmt.query_map([], Self::row_to_competition)
+            .map(|rows| rows.filter_map(|r| r.ok()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Move a competition's end time, e.g. to close its window in tests.
+    #[cfg(test)]
+    pub fn set_competition_end_time(&self, id: &str, end_time: i64) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "UPDATE competitions SET end_time = ?1 WHERE id = ?2",
+            params![end_time, id],
+        )?;
+        Ok(())
+    }
+
+    /// Helper to convert a row to a Competition.
+    fn row_to_competition(row: &rusqlite::Row) -> Result<Competition, rusqlite::Error> {
+        Ok(Competition {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            starting_balance: row.get(2)?,
+            start_time: row.get(3)?,
+            end_time: row.get(4)?,
+            created_at: row.get(5)?,
+        })
+    }
+
     // ========== Order Methods ==========
 
     /// Create a new order.
@@ -1090,8 +1796,9 @@ impl SqliteStore {
                 filled_quantity, price, stop_price, trail_amount, trail_percent,
                 time_in_force, status, linked_order_id, bracket_id, leverage,
                 fills_json, avg_fill_price, total_fees, client_order_id,
-                created_at, updated_at, expires_at, trail_high_price, trail_low_price, bracket_role
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27)",
+                created_at, updated_at, expires_at, trail_high_price, trail_low_price, bracket_role,
+                display_quantity
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28)",
             params![
                 order.id,
                 order.portfolio_id,
@@ -1120,6 +1827,7 @@ impl SqliteStore {
                 order.trail_high_price,
                 order.trail_low_price,
                 order.bracket_role.as_ref().map(|r| r.to_string()),
+                order.display_quantity,
             ],
         )?;
 
@@ -1129,14 +1837,15 @@ impl SqliteStore {
 
     /// Get an order by ID.
     pub fn get_order(&self, id: &str) -> Option<Order> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let result = conn.query_row(
             "SELECT id, portfolio_id, symbol, asset_class, side, order_type, quantity,
                     filled_quantity, price, stop_price, trail_amount, trail_percent,
                     time_in_force, status, linked_order_id, bracket_id, leverage,
                     fills_json, avg_fill_price, total_fees, client_order_id,
-                    created_at, updated_at, expires_at, trail_high_price, trail_low_price, bracket_role
+                    created_at, updated_at, expires_at, trail_high_price, trail_low_price, bracket_role,
+                    display_quantity
              FROM orders WHERE id = ?1",
             params![id],
             |row| Self::row_to_order(row),
@@ -1152,6 +1861,36 @@ impl SqliteStore {
         }
     }
 
+    /// Get an order by its client-provided ID within a portfolio.
+    pub fn get_order_by_client_id(
+        &self,
+        portfolio_id: &str,
+        client_order_id: &str,
+    ) -> Option<Order> {
+        let conn = self.reader();
+
+        let result = conn.query_row(
+            "SELECT id, portfolio_id, symbol, asset_class, side, order_type, quantity,
+                    filled_quantity, price, stop_price, trail_amount, trail_percent,
+                    time_in_force, status, linked_order_id, bracket_id, leverage,
+                    fills_json, avg_fill_price, total_fees, client_order_id,
+                    created_at, updated_at, expires_at, trail_high_price, trail_low_price, bracket_role,
+                    display_quantity
+             FROM orders WHERE portfolio_id = ?1 AND client_order_id = ?2",
+            params![portfolio_id, client_order_id],
+            Self::row_to_order,
+        );
+
+        match result {
+            Ok(order) => Some(order),
+            Err(rusqlite::Error::QueryReturnedNoRows) => None,
+            Err(e) => {
+                error!("Error fetching order by client id: {}", e);
+                None
+            }
+        }
+    }
+
     /// Get orders for a portfolio with optional status filter.
     pub fn get_portfolio_orders(
         &self,
@@ -1159,7 +1898,7 @@ impl SqliteStore {
         status: Option<OrderStatus>,
         limit: usize,
     ) -> Vec<Order> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         if let Some(s) = status {
             let mut stmt = match conn.prepare(
@@ -1167,7 +1906,8 @@ impl SqliteStore {
                         filled_quantity, price, stop_price, trail_amount, trail_percent,
                         time_in_force, status, linked_order_id, bracket_id, leverage,
                         fills_json, avg_fill_price, total_fees, client_order_id,
-                        created_at, updated_at, expires_at, trail_high_price, trail_low_price, bracket_role
+                        created_at, updated_at, expires_at, trail_high_price, trail_low_price, bracket_role,
+                    display_quantity
                  FROM orders WHERE portfolio_id = ?1 AND status = ?2
                  ORDER BY created_at DESC LIMIT ?3",
             ) {
@@ -1190,7 +1930,8 @@ impl SqliteStore {
                         filled_quantity, price, stop_price, trail_amount, trail_percent,
                         time_in_force, status, linked_order_id, bracket_id, leverage,
                         fills_json, avg_fill_price, total_fees, client_order_id,
-                        created_at, updated_at, expires_at, trail_high_price, trail_low_price, bracket_role
+                        created_at, updated_at, expires_at, trail_high_price, trail_low_price, bracket_role,
+                    display_quantity
                  FROM orders WHERE portfolio_id = ?1
                  ORDER BY created_at DESC LIMIT ?2",
             ) {
@@ -1211,14 +1952,15 @@ impl SqliteStore {
 
     /// Get open orders (pending, open, partially_filled).
     pub fn get_open_orders(&self, portfolio_id: &str) -> Vec<Order> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let mut stmt = match conn.prepare(
             "SELECT id, portfolio_id, symbol, asset_class, side, order_type, quantity,
                     filled_quantity, price, stop_price, trail_amount, trail_percent,
                     time_in_force, status, linked_order_id, bracket_id, leverage,
                     fills_json, avg_fill_price, total_fees, client_order_id,
-                    created_at, updated_at, expires_at, trail_high_price, trail_low_price, bracket_role
+                    created_at, updated_at, expires_at, trail_high_price, trail_low_price, bracket_role,
+                    display_quantity
              FROM orders WHERE portfolio_id = ?1
              AND status IN ('pending', 'open', 'partially_filled')
              ORDER BY created_at DESC",
@@ -1238,14 +1980,15 @@ impl SqliteStore {
     /// Get ALL open orders across all portfolios.
     /// Used by the market simulation engine to check for triggered orders.
     pub fn get_all_open_orders(&self) -> Vec<Order> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let mut stmt = match conn.prepare(
             "SELECT id, portfolio_id, symbol, asset_class, side, order_type, quantity,
                     filled_quantity, price, stop_price, trail_amount, trail_percent,
                     time_in_force, status, linked_order_id, bracket_id, leverage,
                     fills_json, avg_fill_price, total_fees, client_order_id,
-                    created_at, updated_at, expires_at, trail_high_price, trail_low_price, bracket_role
+                    created_at, updated_at, expires_at, trail_high_price, trail_low_price, bracket_role,
+                    display_quantity
              FROM orders
              WHERE status IN ('pending', 'open', 'partially_filled')
              ORDER BY created_at ASC",
@@ -1265,7 +2008,7 @@ impl SqliteStore {
     /// Get all unique symbols that have open positions.
     /// Used by the market simulation engine to update position prices.
     pub fn get_symbols_with_positions(&self) -> Vec<String> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let mut stmt = match conn.prepare(
             "SELECT DISTINCT symbol FROM positions WHERE closed_at IS NULL",
@@ -1284,7 +2027,7 @@ impl SqliteStore {
 
     /// Get all unique symbols that have open orders.
     pub fn get_symbols_with_orders(&self) -> Vec<String> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let mut stmt = match conn.prepare(
             "SELECT DISTINCT symbol FROM orders WHERE status IN ('pending', 'open', 'partially_filled')",
@@ -1364,6 +2107,7 @@ impl SqliteStore {
             trail_high_price: row.get(24).ok(),
             trail_low_price: row.get(25).ok(),
             bracket_role: row.get::<_, Option<String>>(26)?.map(|s| parse_bracket_role(&s)),
+            display_quantity: row.get(27)?,
         })
     }
 
@@ -1412,7 +2156,7 @@ impl SqliteStore {
 
     /// Get a position by ID.
     pub fn get_position(&self, id: &str) -> Option<Position> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let result = conn.query_row(
             "SELECT id, portfolio_id, symbol, asset_class, side, quantity, entry_price,
@@ -1436,7 +2180,7 @@ impl SqliteStore {
 
     /// Get open positions for a portfolio.
     pub fn get_portfolio_positions(&self, portfolio_id: &str) -> Vec<Position> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let mut stmt = match conn.prepare(
             "SELECT id, portfolio_id, symbol, asset_class, side, quantity, entry_price,
@@ -1465,7 +2209,7 @@ impl SqliteStore {
         symbol: &str,
         side: PositionSide,
     ) -> Option<Position> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let result = conn.query_row(
             "SELECT id, portfolio_id, symbol, asset_class, side, quantity, entry_price,
@@ -1498,8 +2242,8 @@ impl SqliteStore {
                 quantity = ?1, current_price = ?2, unrealized_pnl = ?3,
                 unrealized_pnl_pct = ?4, realized_pnl = ?5, margin_used = ?6,
                 stop_loss = ?7, take_profit = ?8, cost_basis_json = ?9,
-                funding_payments = ?10, updated_at = ?11
-             WHERE id = ?12",
+                funding_payments = ?10, updated_at = ?11, entry_price = ?12
+             WHERE id = ?13",
             params![
                 position.quantity,
                 position.current_price,
@@ -1512,6 +2256,7 @@ impl SqliteStore {
                 cost_basis_json,
                 position.funding_payments,
                 position.updated_at,
+                position.entry_price,
                 position.id,
             ],
         )?;
@@ -1591,29 +2336,63 @@ impl SqliteStore {
             ],
         )?;
 
-        debug!("Created trade {} for order {}", trade.id, trade.order_id);
-        Ok(())
+        debug!("Created trade {} for order {}", trade.id, trade.order_id);
+        Ok(())
+    }
+
+    /// Get trades for a portfolio.
+    pub fn get_portfolio_trades(&self, portfolio_id: &str, limit: usize) -> Vec<Trade> {
+        let conn = self.reader();
+
+        let mut stmt = match conn.prepare(
+            "SELECT id, order_id, portfolio_id, position_id, symbol, asset_class,
+                    side, quantity, price, fee, slippage, realized_pnl, executed_at
+             FROM trades WHERE portfolio_id = ?1
+             ORDER BY executed_at DESC LIMIT ?2",
+        ) {
+            Ok(stmt) => stmt,
+            Err(e) => {
+                error!("Error preparing trades query: {}", e);
+                return Vec::new();
+            }
+        };
+
+        stmt.query_map(params![portfolio_id, limit as i64], |row| {
+            Self::row_to_trade(row)
+        })
+        .map(|rows| rows.filter_map(|r| r.ok()).collect())
+        .unwrap_or_default()
+    }
+
+    /// Realized P&L and trade counts per portfolio for trades executed at or after `since` (ms).
+    pub fn trade_stats_since(&self, since: i64) -> Vec<TradeWindowStats> {
+        self.trade_stats_between(since, i64::MAX)
     }
 
-    /// Get trades for a portfolio.
-    pub fn get_portfolio_trades(&self, portfolio_id: &str, limit: usize) -> Vec<Trade> {
-        let conn = self.conn.lock().unwrap();
+    /// Realized P&L and trade counts per portfolio for trades executed in `[start, end)` (ms).
+    pub fn trade_stats_between(&self, start: i64, end: i64) -> Vec<TradeWindowStats> {
+        let conn = self.reader();
 
         let mut stmt = match conn.prepare(
-            "SELECT id, order_id, portfolio_id, position_id, symbol, asset_class,
-                    side, quantity, price, fee, slippage, realized_pnl, executed_at
-             FROM trades WHERE portfolio_id = ?1
-             ORDER BY executed_at DESC LIMIT ?2",
+            "SELECT portfolio_id, COALESCE(SUM(realized_pnl), 0), COUNT(*),
+                    SUM(CASE WHEN realized_pnl > 0 THEN 1 ELSE 0 END)
+             FROM trades WHERE executed_at >= ?1 AND executed_at < ?2
+             GROUP BY portfolio_id",
         ) {
             Ok(stmt) => stmt,
             Err(e) => {
-                error!("Error preparing trades query: {}", e);
+                error!("Error preparing trade stats query: {}", e);
                 return Vec::new();
             }
         };
 
-        stmt.query_map(params![portfolio_id, limit as i64], |row| {
-            Self::row_to_trade(row)
+        stmt.query_map(params![start, end], |row| {
+            Ok(TradeWindowStats {
+                portfolio_id: row.get(0)?,
+                realized_pnl: row.get(1)?,
+                total_trades: row.get::<_, i64>(2)? as u64,
+                winning_trades: row.get::<_, i64>(3)? as u64,
+            })
         })
         .map(|rows| rows.filter_map(|r| r.ok()).collect())
         .unwrap_or_default()
@@ -1621,7 +2400,7 @@ impl SqliteStore {
 
     /// Get trades for an order.
     pub fn get_order_trades(&self, order_id: &str) -> Vec<Trade> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let mut stmt = match conn.prepare(
             "SELECT id, order_id, portfolio_id, position_id, symbol, asset_class,
@@ -1661,7 +2440,7 @@ impl SqliteStore {
 
     /// Get total number of open positions for a portfolio.
     pub fn position_count(&self, portfolio_id: &str) -> usize {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
         conn.query_row(
             "SELECT COUNT(*) FROM positions WHERE portfolio_id = ?1 AND closed_at IS NULL",
             params![portfolio_id],
@@ -1672,7 +2451,7 @@ impl SqliteStore {
 
     /// Get total number of open orders for a portfolio.
     pub fn open_order_count(&self, portfolio_id: &str) -> usize {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
         conn.query_row(
             "SELECT COUNT(*) FROM orders WHERE portfolio_id = ?1
              AND status IN ('pending', 'open', 'partially_filled')",
@@ -1715,7 +2494,7 @@ impl SqliteStore {
 
     /// Get funding payments for a position.
     pub fn get_position_funding_payments(&self, position_id: &str) -> Vec<FundingPayment> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let mut stmt = match conn.prepare(
             "SELECT id, position_id, portfolio_id, symbol, position_size,
@@ -1741,7 +2520,7 @@ impl SqliteStore {
         portfolio_id: &str,
         limit: usize,
     ) -> Vec<FundingPayment> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let mut stmt = match conn.prepare(
             "SELECT id, position_id, portfolio_id, symbol, position_size,
@@ -1763,6 +2542,36 @@ impl SqliteStore {
         .unwrap_or_default()
     }
 
+    /// Get the funding rates applied to a symbol, newest first.
+    ///
+    /// Every position on the symbol is charged at the same settlement, but
+    /// each payment is stamped as it is written, so payments are grouped by
+    /// settlement window into one `(funding_rate, settlement start)` point.
+    /// `limit` is clamped to [`MAX_FUNDING_HISTORY_POINTS`].
+    pub fn get_symbol_funding_rates(&self, symbol: &str, limit: usize) -> Vec<(f64, i64)> {
+        let limit = limit.clamp(1, MAX_FUNDING_HISTORY_POINTS);
+        let conn = self.reader();
+
+        let mut stmt = match conn.prepare(
+            "SELECT AVG(funding_rate), (paid_at / ?3) * ?3 AS settled_at
+             FROM funding_payments WHERE symbol = ?1
+             GROUP BY settled_at
+             ORDER BY settled_at DESC LIMIT ?2",
+        ) {
+            Ok(stmt) => stmt,
+            Err(e) => {
+                error!("Error preparing symbol funding rates query: {}", e);
+                return Vec::new();
+            }
+        };
+
+        stmt.query_map(params![symbol, limit as i64, FUNDING_INTERVAL_MS], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .map(|rows| rows.filter_map(|r| r.ok()).collect())
+        .unwrap_or_default()
+    }
+
     /// Helper to convert a row to a FundingPayment.
     fn row_to_funding_payment(row: &rusqlite::Row) -> Result<FundingPayment, rusqlite::Error> {
         Ok(FundingPayment {
@@ -1815,7 +2624,7 @@ impl SqliteStore {
 
     /// Get liquidations for a portfolio.
     pub fn get_portfolio_liquidations(&self, portfolio_id: &str, limit: usize) -> Vec<Liquidation> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let mut stmt = match conn.prepare(
             "SELECT id, position_id, portfolio_id, symbol, quantity,
@@ -1840,7 +2649,7 @@ impl SqliteStore {
 
     /// Get a liquidation by ID.
     pub fn get_liquidation(&self, id: &str) -> Option<Liquidation> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let result = conn.query_row(
             "SELECT id, position_id, portfolio_id, symbol, quantity,
@@ -1917,7 +2726,7 @@ impl SqliteStore {
         portfolio_id: &str,
         limit: usize,
     ) -> Vec<MarginHistory> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let mut stmt = match conn.prepare(
             "SELECT id, portfolio_id, position_id, change_type,
@@ -1962,7 +2771,7 @@ impl SqliteStore {
 
     /// Get the insurance fund state.
     pub fn get_insurance_fund(&self) -> InsuranceFund {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let result = conn.query_row(
             "SELECT balance, total_contributions, total_payouts, liquidations_covered, updated_at
@@ -2042,8 +2851,8 @@ impl SqliteStore {
                 id, portfolio_id, contract_symbol, underlying_symbol, option_type,
                 strike, expiration, style, contracts, multiplier, entry_premium,
                 current_premium, underlying_price, unrealized_pnl, realized_pnl,
-                greeks_json, entry_iv, current_iv, created_at, updated_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20)",
+                greeks_json, entry_iv, current_iv, created_at, updated_at, margin_used
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21)",
             params![
                 position.id,
                 position.portfolio_id,
@@ -2065,6 +2874,7 @@ impl SqliteStore {
                 position.current_iv,
                 position.created_at,
                 position.updated_at,
+                position.margin_used,
             ],
         )?;
 
@@ -2077,13 +2887,13 @@ impl SqliteStore {
 
     /// Get an option position by ID.
     pub fn get_option_position(&self, id: &str) -> Option<OptionPosition> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let result = conn.query_row(
             "SELECT id, portfolio_id, contract_symbol, underlying_symbol, option_type,
                     strike, expiration, style, contracts, multiplier, entry_premium,
                     current_premium, underlying_price, unrealized_pnl, realized_pnl,
-                    greeks_json, entry_iv, current_iv, created_at, updated_at
+                    greeks_json, entry_iv, current_iv, created_at, updated_at, margin_used
              FROM options_positions WHERE id = ?1 AND closed_at IS NULL",
             params![id],
             |row| Self::row_to_option_position(row),
@@ -2101,13 +2911,13 @@ impl SqliteStore {
 
     /// Get open option positions for a portfolio.
     pub fn get_portfolio_option_positions(&self, portfolio_id: &str) -> Vec<OptionPosition> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let mut stmt = match conn.prepare(
             "SELECT id, portfolio_id, contract_symbol, underlying_symbol, option_type,
                     strike, expiration, style, contracts, multiplier, entry_premium,
                     current_premium, underlying_price, unrealized_pnl, realized_pnl,
-                    greeks_json, entry_iv, current_iv, created_at, updated_at
+                    greeks_json, entry_iv, current_iv, created_at, updated_at, margin_used
              FROM options_positions WHERE portfolio_id = ?1 AND closed_at IS NULL
              ORDER BY expiration ASC",
         ) {
@@ -2129,13 +2939,13 @@ impl SqliteStore {
         portfolio_id: &str,
         underlying_symbol: &str,
     ) -> Vec<OptionPosition> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let mut stmt = match conn.prepare(
             "SELECT id, portfolio_id, contract_symbol, underlying_symbol, option_type,
                     strike, expiration, style, contracts, multiplier, entry_premium,
                     current_premium, underlying_price, unrealized_pnl, realized_pnl,
-                    greeks_json, entry_iv, current_iv, created_at, updated_at
+                    greeks_json, entry_iv, current_iv, created_at, updated_at, margin_used
              FROM options_positions
              WHERE portfolio_id = ?1 AND underlying_symbol = ?2 AND closed_at IS NULL
              ORDER BY expiration ASC, strike ASC",
@@ -2160,13 +2970,13 @@ impl SqliteStore {
         portfolio_id: &str,
         before_timestamp: i64,
     ) -> Vec<OptionPosition> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let mut stmt = match conn.prepare(
             "SELECT id, portfolio_id, contract_symbol, underlying_symbol, option_type,
                     strike, expiration, style, contracts, multiplier, entry_premium,
                     current_premium, underlying_price, unrealized_pnl, realized_pnl,
-                    greeks_json, entry_iv, current_iv, created_at, updated_at
+                    greeks_json, entry_iv, current_iv, created_at, updated_at, margin_used
              FROM options_positions
              WHERE portfolio_id = ?1 AND expiration < ?2 AND closed_at IS NULL
              ORDER BY expiration ASC",
@@ -2194,8 +3004,8 @@ impl SqliteStore {
             "UPDATE options_positions SET
                 contracts = ?1, current_premium = ?2, underlying_price = ?3,
                 unrealized_pnl = ?4, realized_pnl = ?5, greeks_json = ?6,
-                current_iv = ?7, updated_at = ?8
-             WHERE id = ?9",
+                current_iv = ?7, updated_at = ?8, margin_used = ?9
+             WHERE id = ?10",
             params![
                 position.contracts,
                 position.current_premium,
@@ -2205,6 +3015,7 @@ impl SqliteStore {
                 greeks_json,
                 position.current_iv,
                 position.updated_at,
+                position.margin_used,
                 position.id,
             ],
         )?;
@@ -2228,7 +3039,7 @@ impl SqliteStore {
 
     /// Get count of open option positions for a portfolio.
     pub fn option_position_count(&self, portfolio_id: &str) -> usize {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
         conn.query_row(
             "SELECT COUNT(*) FROM options_positions WHERE portfolio_id = ?1 AND closed_at IS NULL",
             params![portfolio_id],
@@ -2261,6 +3072,7 @@ impl SqliteStore {
             greeks,
             entry_iv: row.get(16)?,
             current_iv: row.get(17)?,
+            margin_used: row.get(20)?,
             created_at: row.get(18)?,
             updated_at: row.get(19)?,
         })
@@ -2309,7 +3121,7 @@ impl SqliteStore {
 
     /// Get a strategy by ID.
     pub fn get_strategy(&self, id: &str) -> Option<TradingStrategy> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let result = conn.query_row(
             "SELECT id, portfolio_id, name, description, symbols_json, asset_class,
@@ -2333,7 +3145,7 @@ impl SqliteStore {
 
     /// Get all strategies for a portfolio.
     pub fn get_portfolio_strategies(&self, portfolio_id: &str) -> Vec<TradingStrategy> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let mut stmt = match conn.prepare(
             "SELECT id, portfolio_id, name, description, symbols_json, asset_class,
@@ -2357,7 +3169,7 @@ impl SqliteStore {
 
     /// Get all active strategies for a portfolio.
     pub fn get_active_strategies(&self, portfolio_id: &str) -> Vec<TradingStrategy> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let mut stmt = match conn.prepare(
             "SELECT id, portfolio_id, name, description, symbols_json, asset_class,
@@ -2431,7 +3243,7 @@ impl SqliteStore {
 
     /// Get count of strategies for a portfolio.
     pub fn strategy_count(&self, portfolio_id: &str) -> usize {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
         conn.query_row(
             "SELECT COUNT(*) FROM strategies WHERE portfolio_id = ?1 AND status != 'deleted'",
             params![portfolio_id],
@@ -2470,6 +3282,155 @@ impl SqliteStore {
         })
     }
 
+    // ========== Execution Schedule Methods ==========
+
+    /// Create an execution schedule.
+    pub fn create_execution_schedule(
+        &self,
+        schedule: &ExecutionSchedule,
+    ) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let slices_json = serde_json::to_string(&schedule.slices).unwrap_or_default();
+
+        conn.execute(
+            "INSERT INTO execution_schedules (
+                id, portfolio_id, symbol, asset_class, side, algo, quantity, leverage,
+                duration_secs, slices_json, status, created_at, updated_at
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+            params![
+                schedule.id,
+                schedule.portfolio_id,
+                schedule.symbol,
+                schedule.asset_class.to_string(),
+                schedule.side.to_string(),
+                schedule.algo.to_string(),
+                schedule.quantity,
+                schedule.leverage,
+                schedule.duration_secs as i64,
+                slices_json,
+                schedule.status.to_string(),
+                schedule.created_at,
+                schedule.updated_at,
+            ],
+        )?;
+
+        debug!(
+            "Created execution schedule {} for portfolio {}",
+            schedule.id, schedule.portfolio_id
+        );
+        Ok(())
+    }
+
+    /// Get an execution schedule by ID.
+    pub fn get_execution_schedule(&self, id: &str) -> Option<ExecutionSchedule> {
+        let conn = self.reader();
+
+        let result = conn.query_row(
+            "SELECT id, portfolio_id, symbol, asset_class, side, algo, quantity, leverage,
+                    duration_secs, slices_json, status, created_at, updated_at
+             FROM execution_schedules WHERE id = ?1",
+            params![id],
+            Self::row_to_execution_schedule,
+        );
+
+        match result {
+            Ok(schedule) => Some(schedule),
+            Err(rusqlite::Error::QueryReturnedNoRows) => None,
+            Err(e) => {
+                error!("Error fetching execution schedule: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Get all execution schedules for a portfolio, newest first.
+    pub fn get_portfolio_execution_schedules(&self, portfolio_id: &str) -> Vec<ExecutionSchedule> {
+        self.query_execution_schedules(
+            "SELECT id, portfolio_id, symbol, asset_class, side, algo, quantity, leverage,
+                    duration_secs, slices_json, status, created_at, updated_at
+             FROM execution_schedules WHERE portfolio_id = ?1
+             ORDER BY created_at DESC",
+            params![portfolio_id],
+        )
+    }
+
+    /// Get every execution schedule that still has slices to release.
+    pub fn get_active_execution_schedules(&self) -> Vec<ExecutionSchedule> {
+        self.query_execution_schedules(
+            "SELECT id, portfolio_id, symbol, asset_class, side, algo, quantity, leverage,
+                    duration_secs, slices_json, status, created_at, updated_at
+             FROM execution_schedules WHERE status = 'active'
+             ORDER BY created_at",
+            params![],
+        )
+    }
+
+    fn query_execution_schedules(
+        &self,
+        sql: &str,
+        params: &[&dyn rusqlite::ToSql],
+    ) -> Vec<ExecutionSchedule> {
+        let conn = self.reader();
+
+        let mut stmt = match conn.prepare(sql) {
+            Ok(stmt) => stmt,
+            Err(e) => {
+                error!("Error preparing execution schedules query: {}", e);
+                return Vec::new();
+            }
+        };
+
+        stmt.query_map(params, Self::row_to_execution_schedule)
+            .map(|rows| rows.filter_map(|r| r.ok()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Update an execution schedule's slices and status.
+    pub fn update_execution_schedule(
+        &self,
+        schedule: &ExecutionSchedule,
+    ) -> Result<(), rusqlite::Error> {
+        let conn = self.conn.lock().unwrap();
+        let slices_json = serde_json::to_string(&schedule.slices).unwrap_or_default();
+
+        conn.execute(
+            "UPDATE execution_schedules SET slices_json = ?1, status = ?2, updated_at = ?3
+             WHERE id = ?4",
+            params![
+                slices_json,
+                schedule.status.to_string(),
+                schedule.updated_at,
+                schedule.id,
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Helper to convert a row to an ExecutionSchedule.
+    fn row_to_execution_schedule(
+        row: &rusqlite::Row,
+    ) -> Result<ExecutionSchedule, rusqlite::Error> {
+        let slices_json: String = row.get(9)?;
+        let slices: Vec<ExecutionSlice> = serde_json::from_str(&slices_json).unwrap_or_default();
+
+        Ok(ExecutionSchedule {
+            id: row.get(0)?,
+            portfolio_id: row.get(1)?,
+            symbol: row.get(2)?,
+            asset_class: parse_asset_class(&row.get::<_, String>(3)?),
+            side: parse_order_side(&row.get::<_, String>(4)?),
+            algo: parse_execution_algo(&row.get::<_, String>(5)?),
+            quantity: row.get(6)?,
+            leverage: row.get(7)?,
+            duration_secs: row.get::<_, i64>(8)? as u64,
+            slices,
+            status: parse_execution_status(&row.get::<_, String>(10)?),
+            created_at: row.get(11)?,
+            updated_at: row.get(12)?,
+        })
+    }
+
     // ========== Portfolio Snapshot Methods ==========
 
     /// Create a portfolio snapshot for equity curve charting.
@@ -2515,11 +3476,18 @@ impl SqliteStore {
     }
 
     /// Create a portfolio snapshot from a Portfolio object.
+    ///
+    /// Drawdown is measured against the highest equity seen in any earlier
+    /// snapshot (or the starting balance, if higher).
     pub fn create_snapshot_from_portfolio(
         &self,
         portfolio: &Portfolio,
     ) -> Result<(), rusqlite::Error> {
-        let peak_equity = portfolio.starting_balance.max(portfolio.total_value);
+        let peak_equity = self
+            .get_peak_equity(&portfolio.id)
+            .unwrap_or(0.0)
+            .max(portfolio.starting_balance)
+            .max(portfolio.total_value);
         let drawdown_pct = if peak_equity > 0.0 {
             ((peak_equity - portfolio.total_value) / peak_equity) * 100.0
         } else {
@@ -2546,13 +3514,13 @@ impl SqliteStore {
         since_timestamp: Option<i64>,
         limit: Option<usize>,
     ) -> Vec<EquityPoint> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         // Always use the query with since parameter (use 0 if not specified)
         let query = "SELECT timestamp, equity, cash, positions_value, realized_pnl, unrealized_pnl, drawdown_pct
              FROM portfolio_snapshots
              WHERE portfolio_id = ?1 AND timestamp >= ?2
-             ORDER BY timestamp ASC
+             ORDER BY timestamp ASC, rowid ASC
              LIMIT ?3";
 
         let mut stmt = match conn.prepare(query) {
@@ -2581,15 +3549,28 @@ impl SqliteStore {
         .unwrap_or_default()
     }
 
+    /// Highest peak equity recorded in a portfolio's snapshots.
+    pub fn get_peak_equity(&self, portfolio_id: &str) -> Option<f64> {
+        let conn = self.reader();
+
+        conn.query_row(
+            "SELECT MAX(peak_equity) FROM portfolio_snapshots WHERE portfolio_id = ?1",
+            params![portfolio_id],
+            |row| row.get(0),
+        )
+        .ok()
+        .flatten()
+    }
+
     /// Get the latest snapshot for a portfolio.
     pub fn get_latest_portfolio_snapshot(&self, portfolio_id: &str) -> Option<EquityPoint> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         conn.query_row(
             "SELECT timestamp, equity, cash, positions_value, realized_pnl, unrealized_pnl, drawdown_pct
              FROM portfolio_snapshots
              WHERE portfolio_id = ?1
-             ORDER BY timestamp DESC
+             ORDER BY timestamp DESC, rowid DESC
              LIMIT 1",
             params![portfolio_id],
             |row| {
@@ -2629,7 +3610,7 @@ impl SqliteStore {
 
     /// Get snapshot count for a portfolio.
     pub fn snapshot_count(&self, portfolio_id: &str) -> usize {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
         conn.query_row(
             "SELECT COUNT(*) FROM portfolio_snapshots WHERE portfolio_id = ?1",
             params![portfolio_id],
@@ -2681,7 +3662,7 @@ impl SqliteStore {
 
     /// Get a backtest result by ID.
     pub fn get_backtest_result(&self, id: &str) -> Option<crate::types::BacktestResult> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let result = conn.query_row(
             "SELECT id, strategy_id, status, config_json, metrics_json, trades_json,
@@ -2704,7 +3685,7 @@ impl SqliteStore {
 
     /// Get backtest results for a strategy.
     pub fn get_strategy_backtests(&self, strategy_id: &str) -> Vec<crate::types::BacktestResult> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let mut stmt = match conn.prepare(
             "SELECT id, strategy_id, status, config_json, metrics_json, trades_json,
@@ -2766,7 +3747,7 @@ impl SqliteStore {
         start_time: i64,
         end_time: i64,
     ) -> Option<Vec<crate::types::ChartCandle>> {
-        let conn = self.conn.lock().unwrap();
+        let conn = self.reader();
 
         let mut stmt = match conn.prepare(
             "SELECT timestamp, open, high, low, close, volume
@@ -2920,6 +3901,30 @@ fn parse_strategy_status(s: &str) -> StrategyStatus {
     }
 }
 
+fn parse_execution_algo(s: &str) -> ExecutionAlgo {
+    match s {
+        "vwap" => ExecutionAlgo::Vwap,
+        _ => ExecutionAlgo::Twap,
+    }
+}
+
+fn parse_execution_status(s: &str) -> ExecutionStatus {
+    match s {
+        "completed" => ExecutionStatus::Completed,
+        "cancelled" => ExecutionStatus::Cancelled,
+        _ => ExecutionStatus::Active,
+    }
+}
+
+fn parse_notification_type(s: &str) -> NotificationType {
+    match s {
+        "trade" => NotificationType::Trade,
+        "gridline" => NotificationType::Gridline,
+        "warning" => NotificationType::Warning,
+        _ => NotificationType::System,
+    }
+}
+
 fn parse_backtest_status(s: &str) -> crate::types::BacktestStatus {
     match s {
         "pending" => crate::types::BacktestStatus::Pending,
@@ -2931,6 +3936,17 @@ fn parse_backtest_status(s: &str) -> crate::types::BacktestStatus {
     }
 }
 
+/// Outcome column holding results for a validation timeframe (default 1h).
+fn outcome_column(timeframe: &str) -> &'static str {
+    match timeframe {
+        "5m" => "outcome_5m",
+        "1h" => "outcome_1h",
+        "4h" => "outcome_4h",
+        "24h" => "outcome_24h",
+        _ => "outcome_1h",
+    }
+}
+
 /// Accuracy statistics.
 #[derive(Debug, Default, Clone)]
 pub struct AccuracyStats {
@@ -2999,6 +4015,148 @@ mod tests {
         assert!(store.get_profile(&profile.public_key).is_none());
     }
 
+    #[test]
+    fn test_profile_bio_and_avatar_persist() {
+        let store = SqliteStore::new_in_memory().unwrap();
+        let mut profile = Profile::new("def456".repeat(8), "TestTrader42".to_string());
+        store.save_profile(&profile).unwrap();
+
+        let loaded = store.get_profile(&profile.public_key).unwrap();
+        assert!(loaded.bio.is_none());
+        assert!(loaded.avatar_url.is_none());
+
+        profile.bio = Some("Swing trading majors".to_string());
+        profile.avatar_url = Some("https://example.com/me.png".to_string());
+        store.save_profile(&profile).unwrap();
+
+        let loaded = store.get_profile(&profile.public_key).unwrap();
+        assert_eq!(loaded.bio.as_deref(), Some("Swing trading majors"));
+        assert_eq!(loaded.avatar_url.as_deref(), Some("https://example.com/me.png"));
+
+        // Clearing persists too
+        profile.bio = None;
+        store.save_profile(&profile).unwrap();
+        assert!(store.get_profile(&profile.public_key).unwrap().bio.is_none());
+    }
+
+    #[test]
+    fn test_follow_and_unfollow_are_idempotent() {
+        let store = SqliteStore::new_in_memory().unwrap();
+
+        assert!(store.follow("alice", "bob").unwrap());
+        assert!(!store.follow("alice", "bob").unwrap());
+        assert_eq!(store.follower_count("bob"), 1);
+
+        assert!(store.unfollow("alice", "bob").unwrap());
+        assert!(!store.unfollow("alice", "bob").unwrap());
+        assert_eq!(store.follower_count("bob"), 0);
+        assert!(store.get_following("alice").is_empty());
+
+        // Self-follows are rejected
+        assert!(!store.follow("alice", "alice").unwrap());
+        assert_eq!(store.follower_count("alice"), 0);
+    }
+
+    #[test]
+    fn test_follow_counts_and_lists() {
+        let store = SqliteStore::new_in_memory().unwrap();
+        store.follow("alice", "carol").unwrap();
+        store.follow("bob", "carol").unwrap();
+        store.follow("alice", "bob").unwrap();
+
+        assert_eq!(store.follower_count("carol"), 2);
+        assert_eq!(store.follower_count("bob"), 1);
+        assert_eq!(store.follower_count("alice"), 0);
+        assert_eq!(store.following_count("alice"), 2);
+
+        let mut followers = store.get_followers("carol");
+        followers.sort();
+        assert_eq!(followers, vec!["alice", "bob"]);
+
+        let mut following = store.get_following("alice");
+        following.sort();
+        assert_eq!(following, vec!["bob", "carol"]);
+        assert!(store.get_following("carol").is_empty());
+    }
+
+    fn notify(store: &SqliteStore, user_id: &str, title: &str) -> Notification {
+        let notification = Notification::new(
+            user_id.to_string(),
+            NotificationType::Trade,
+            title.to_string(),
+            "Order filled".to_string(),
+        );
+        store.create_notification(&notification).unwrap();
+        notification
+    }
+
+    #[test]
+    fn test_notifications_unread_count_and_mark_all_read() {
+        let store = SqliteStore::new_in_memory().unwrap();
+        let user = "user_a".repeat(8);
+        let other = "user_b".repeat(8);
+
+        for i in 0..3 {
+            notify(&store, &user, &format!("Fill {}", i));
+        }
+        let mut already_read = Notification::new(
+            user.clone(),
+            NotificationType::Gridline,
+            "Crossed 50k".to_string(),
+            String::new(),
+        );
+        already_read.read = true;
+        store.create_notification(&already_read).unwrap();
+        notify(&store, &other, "Other user");
+
+        assert_eq!(store.unread_notification_count(&user), 3);
+        assert_eq!(store.get_notifications(&user, false, 50).len(), 4);
+        assert_eq!(store.get_notifications(&user, true, 50).len(), 3);
+        assert!(store
+            .get_notifications(&user, true, 50)
+            .iter()
+            .all(|n| !n.read && n.user_id == user));
+
+        // Only this user's unread notifications are touched
+        assert_eq!(store.mark_all_notifications_read(&user).unwrap(), 3);
+        assert_eq!(store.unread_notification_count(&user), 0);
+        assert!(store.get_notifications(&user, true, 50).is_empty());
+        assert!(store.get_notifications(&user, false, 50).iter().all(|n| n.read));
+        assert_eq!(store.unread_notification_count(&other), 1);
+
+        // Marking again is a no-op
+        assert_eq!(store.mark_all_notifications_read(&user).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_get_notifications_newest_first_with_limit() {
+        let store = SqliteStore::new_in_memory().unwrap();
+        let user = "user_a".repeat(8);
+
+        for (age_ms, title) in [(120_000, "Oldest"), (0, "Newest"), (60_000, "Middle")] {
+            let mut notification = Notification::new(
+                user.clone(),
+                NotificationType::System,
+                title.to_string(),
+                String::new(),
+            );
+            notification.created_at -= age_ms;
+            store.create_notification(&notification).unwrap();
+        }
+
+        let titles: Vec<String> = store
+            .get_notifications(&user, false, 10)
+            .into_iter()
+            .map(|n| n.title)
+            .collect();
+        assert_eq!(titles, ["Newest", "Middle", "Oldest"]);
+
+        let latest = store.get_notifications(&user, false, 1);
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].title, "Newest");
+        assert_eq!(latest[0].notification_type, NotificationType::System);
+    }
+
     #[test]
     fn test_prediction_archive() {
         let store = SqliteStore::new_in_memory().unwrap();
@@ -3052,6 +4210,194 @@ mod tests {
         assert!((stats.accuracy_pct() - 70.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_indicator_accuracy_breakdown() {
+        let store = SqliteStore::new_in_memory().unwrap();
+
+        // RSI: 8 of 10 correct on BTC and ETH; MACD: 2 correct, 6 incorrect, 2 neutral
+        for i in 0..10 {
+            for (symbol, indicator) in [("BTC", "RSI"), ("ETH", "MACD")] {
+                let mut prediction = SignalPrediction::new(
+                    symbol.to_string(),
+                    indicator.to_string(),
+                    SignalDirection::Buy,
+                    60,
+                    100.0,
+                );
+                prediction.outcome_1h = Some(match (indicator, i) {
+                    ("RSI", i) if i < 8 => PredictionOutcome::Correct,
+                    ("RSI", _) => PredictionOutcome::Incorrect,
+                    (_, i) if i < 2 => PredictionOutcome::Correct,
+                    (_, i) if i < 8 => PredictionOutcome::Incorrect,
+                    _ => PredictionOutcome::Neutral,
+                });
+                store.archive_prediction(&prediction).unwrap();
+            }
+        }
+        // No 1h outcome yet, so it is excluded
+        let pending = SignalPrediction::new(
+            "BTC".to_string(),
+            "EMA".to_string(),
+            SignalDirection::Sell,
+            -40,
+            100.0,
+        );
+        store.archive_prediction(&pending).unwrap();
+
+        let breakdown = store.get_indicator_accuracy("1h");
+        assert_eq!(breakdown.len(), 2);
+
+        let (name, macd) = &breakdown[0];
+        assert_eq!(name, "MACD");
+        assert_eq!((macd.total, macd.correct, macd.incorrect, macd.neutral), (10, 2, 6, 2));
+        assert!((macd.accuracy_pct() - 25.0).abs() < 0.01);
+
+        let (name, rsi) = &breakdown[1];
+        assert_eq!(name, "RSI");
+        assert_eq!((rsi.total, rsi.correct, rsi.incorrect, rsi.neutral), (10, 8, 2, 0));
+        assert!((rsi.accuracy_pct() - 80.0).abs() < 0.01);
+
+        // Nothing is validated at 24h
+        assert!(store.get_indicator_accuracy("24h").is_empty());
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("haunt-{}-{}", name, Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_backup_to_contains_rows() {
+        let dir = temp_dir("backup");
+        let store = SqliteStore::new(dir.join("haunt.db")).unwrap();
+
+        let profile = Profile::new("def456".repeat(8), "BackupTrader".to_string());
+        store.save_profile(&profile).unwrap();
+
+        let backup_path = dir.join("snapshot.db");
+        store.backup_to(&backup_path).unwrap();
+
+        let restored = SqliteStore::new(&backup_path).unwrap();
+        let loaded = restored.get_profile(&profile.public_key).unwrap();
+        assert_eq!(loaded.id, profile.id);
+        assert_eq!(restored.profile_count(), 1);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_parallel_reads_during_writes() {
+        let dir = temp_dir("read-pool");
+        let store = SqliteStore::new(dir.join("haunt.db")).unwrap();
+        let seed = Profile::new("5eed".repeat(16), "Seed".to_string());
+        store.save_profile(&seed).unwrap();
+        let portfolio = Portfolio::new(seed.public_key.clone(), "Trading".to_string());
+        store.create_portfolio(&portfolio).unwrap();
+        let mut order = Order::market(
+            portfolio.id.clone(),
+            "BTC".to_string(),
+            AssetClass::CryptoSpot,
+            OrderSide::Buy,
+            1.0,
+        );
+        order.client_order_id = Some("client-1".to_string());
+        store.create_order(&order).unwrap();
+
+        // Readers do not queue behind a held writer connection
+        {
+            let _writer = store.conn.lock().unwrap();
+            assert_eq!(store.profile_count(), 1);
+            assert!(store.get_profile(&seed.public_key).is_some());
+            let by_client_id = store.get_order_by_client_id(&portfolio.id, "client-1");
+            assert_eq!(by_client_id.map(|o| o.id), Some(order.id.clone()));
+        }
+
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                for i in 0..200 {
+                    let profile = Profile::new(format!("{:064x}", i), format!("Writer{}", i));
+                    store.save_profile(&profile).unwrap();
+                }
+            });
+            for _ in 0..4 {
+                s.spawn(|| {
+                    // A failed read would surface as a count of 0 or a missing profile
+                    let mut last = 1;
+                    for _ in 0..200 {
+                        let count = store.profile_count();
+                        assert!(count >= last, "count went from {} to {}", last, count);
+                        last = count;
+                        assert!(store.get_profile(&seed.public_key).is_some());
+                    }
+                });
+            }
+        });
+
+        assert_eq!(store.profile_count(), 201);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(not(feature = "sqlcipher"))]
+    #[test]
+    fn test_key_without_sqlcipher_is_rejected() {
+        let dir = temp_dir("no-cipher");
+        // Opening "encrypted" must not silently fall back to plaintext
+        assert!(SqliteStore::new_with_key(dir.join("haunt.db"), Some("secret")).is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(feature = "sqlcipher")]
+    #[test]
+    fn test_encrypted_store_requires_key() {
+        let dir = temp_dir("cipher");
+        let path = dir.join("haunt.db");
+        let profile = Profile::new("c1fe".repeat(16), "Cipher".to_string());
+        {
+            let store = SqliteStore::new_with_key(&path, Some("right key")).unwrap();
+            store.save_profile(&profile).unwrap();
+        }
+
+        assert!(SqliteStore::new_with_key(&path, Some("wrong key")).is_err());
+        assert!(SqliteStore::new(&path).is_err());
+
+        let store = SqliteStore::new_with_key(&path, Some("right key")).unwrap();
+        assert!(store.get_profile(&profile.public_key).is_some());
+
+        // Backups stay encrypted with the same key
+        let backup_path = dir.join("snapshot.db");
+        store.backup_to(&backup_path).unwrap();
+        assert!(SqliteStore::new(&backup_path).is_err());
+        let restored = SqliteStore::new_with_key(&backup_path, Some("right key")).unwrap();
+        assert!(restored.get_profile(&profile.public_key).is_some());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_backup_rotating_retains_newest() {
+        let dir = temp_dir("backup-rotate");
+        let store = SqliteStore::new_in_memory().unwrap();
+        let profile = Profile::new("aa11".repeat(16), "Rotator".to_string());
+        store.save_profile(&profile).unwrap();
+
+        let backups_dir = dir.join("backups");
+        let mut latest = PathBuf::new();
+        for _ in 0..4 {
+            latest = store.backup_rotating(&backups_dir, 2).unwrap();
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        let remaining = std::fs::read_dir(&backups_dir).unwrap().count();
+        assert_eq!(remaining, 2);
+        assert!(latest.exists());
+
+        let restored = SqliteStore::new(&latest).unwrap();
+        assert!(restored.get_profile(&profile.public_key).is_some());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
     // ========== Trading Tests ==========
 
     #[test]
@@ -9,14 +9,16 @@
 //! - Cooldown management
 //! - Position limit enforcement
 //! - Integration with trading service
+//! - Ready-made strategy templates
 
 use crate::services::SqliteStore;
 use crate::types::{
     ComparisonOperator, IndicatorType, LogicalOperator, PlaceOrderRequest, PositionSizeType,
     RuleAction, RuleActionType, RuleCondition, StrategySignal, StrategyStatus, TradingStrategy,
-    AssetClass, OrderSide, OrderType,
+    AssetClass, OrderSide, OrderType, TradingRule,
 };
 use dashmap::DashMap;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::sync::Arc;
 use thiserror::Error;
@@ -100,6 +102,8 @@ impl IndicatorSnapshot {
             IndicatorType::Ema => period.and_then(|p| self.ema.get(&p).copied()),
             IndicatorType::Sma => period.and_then(|p| self.sma.get(&p).copied()),
             IndicatorType::Bollinger => self.bollinger_middle,
+            IndicatorType::BollingerUpper => self.bollinger_upper,
+            IndicatorType::BollingerLower => self.bollinger_lower,
             IndicatorType::Atr => self.atr,
             IndicatorType::Adx => self.adx,
             IndicatorType::Stochastic => self.stochastic_k,
@@ -404,6 +408,7 @@ impl StrategyEngine {
             time_in_force: None,
             client_order_id: Some(format!("strategy-{}-{}", signal.strategy_id, signal.rule_id)),
             bypass_drawdown: false,
+            display_quantity: None,
         }
     }
 
@@ -438,10 +443,123 @@ impl StrategyEngine {
     }
 }
 
+/// A ready-made strategy that can be instantiated for any portfolio and symbols.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StrategyTemplate {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    #[serde(skip)]
+    rules: fn() -> Vec<TradingRule>,
+}
+
+impl StrategyTemplate {
+    /// Build a paused strategy from this template.
+    pub fn instantiate(&self, portfolio_id: &str, symbols: Vec<String>) -> TradingStrategy {
+        let mut strategy =
+            TradingStrategy::new(portfolio_id.to_string(), self.name.to_string(), symbols);
+        strategy.description = Some(self.description.to_string());
+        for (priority, mut rule) in (self.rules)().into_iter().enumerate() {
+            rule.priority = priority as u32;
+            strategy.add_rule(rule);
+        }
+        strategy
+    }
+}
+
+/// All strategy templates.
+pub fn templates() -> Vec<StrategyTemplate> {
+    vec![
+        StrategyTemplate {
+            id: "rsi_mean_reversion",
+            name: "RSI Mean Reversion",
+            description: "Buy when RSI-14 is oversold below 30, exit when it is overbought \
+                above 70",
+            rules: rsi_mean_reversion_rules,
+        },
+        StrategyTemplate {
+            id: "macd_trend",
+            name: "MACD Trend",
+            description: "Buy when MACD crosses above zero, exit when it crosses back below",
+            rules: macd_trend_rules,
+        },
+        StrategyTemplate {
+            id: "bollinger_breakout",
+            name: "Bollinger Breakout",
+            description: "Buy when price breaks above the upper Bollinger band, exit when it falls \
+                back through the middle band",
+            rules: bollinger_breakout_rules,
+        },
+    ]
+}
+
+/// Look up a strategy template by ID.
+pub fn template(id: &str) -> Option<StrategyTemplate> {
+    templates().into_iter().find(|t| t.id == id)
+}
+
+fn rsi_mean_reversion_rules() -> Vec<TradingRule> {
+    vec![
+        TradingRule::new(
+            "Oversold entry".to_string(),
+            vec![RuleCondition::new(IndicatorType::Rsi, ComparisonOperator::LessThan, 30.0)
+                .with_period(14)],
+            RuleAction::market_buy(PositionSizeType::PortfolioPercent, 5.0)
+                .with_stop_loss(5.0)
+                .with_take_profit(10.0),
+        ),
+        TradingRule::new(
+            "Overbought exit".to_string(),
+            vec![RuleCondition::new(IndicatorType::Rsi, ComparisonOperator::GreaterThan, 70.0)
+                .with_period(14)],
+            RuleAction::close_position(),
+        ),
+    ]
+}
+
+fn macd_trend_rules() -> Vec<TradingRule> {
+    vec![
+        TradingRule::new(
+            "MACD bullish cross".to_string(),
+            vec![RuleCondition::new(IndicatorType::Macd, ComparisonOperator::CrossesAbove, 0.0)],
+            RuleAction::market_buy(PositionSizeType::PortfolioPercent, 5.0).with_stop_loss(4.0),
+        ),
+        TradingRule::new(
+            "MACD bearish cross".to_string(),
+            vec![RuleCondition::new(IndicatorType::Macd, ComparisonOperator::CrossesBelow, 0.0)],
+            RuleAction::close_position(),
+        ),
+    ]
+}
+
+fn bollinger_breakout_rules() -> Vec<TradingRule> {
+    vec![
+        TradingRule::new(
+            "Upper band breakout".to_string(),
+            vec![
+                RuleCondition::new(IndicatorType::Price, ComparisonOperator::CrossesAbove, 0.0)
+                    .against(IndicatorType::BollingerUpper, Some(20)),
+            ],
+            RuleAction::market_buy(PositionSizeType::PortfolioPercent, 5.0)
+                .with_stop_loss(3.0)
+                .with_take_profit(8.0),
+        ),
+        TradingRule::new(
+            "Middle band exit".to_string(),
+            vec![
+                RuleCondition::new(IndicatorType::Price, ComparisonOperator::CrossesBelow, 0.0)
+                    .against(IndicatorType::Bollinger, Some(20)),
+            ],
+            RuleAction::close_position(),
+        ),
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{TradingRule, RuleCondition, RuleAction, PositionSizeType};
+    use crate::types::{Portfolio, TradingRule, RuleCondition, RuleAction, PositionSizeType};
 
     fn setup_test_engine() -> StrategyEngine {
         let store = Arc::new(SqliteStore::new_in_memory().unwrap());
@@ -659,4 +777,75 @@ mod tests {
         engine.update_position_count("port1", "BTC", 3);
         assert_eq!(engine.get_position_count("port1", "BTC"), 3);
     }
+
+    /// A snapshot for `symbol` whose move from its previous values should trigger an entry.
+    fn feed_entry_snapshots(engine: &StrategyEngine, template_id: &str, symbol: &str) {
+        let mut before = IndicatorSnapshot::new(100.0);
+        let mut after = IndicatorSnapshot::new(106.0);
+        match template_id {
+            "rsi_mean_reversion" => {
+                before.rsi = Some(45.0);
+                after.rsi = Some(25.0);
+            }
+            "macd_trend" => {
+                before.macd = Some(-0.5);
+                after.macd = Some(0.4);
+            }
+            "bollinger_breakout" => {
+                for snapshot in [&mut before, &mut after] {
+                    snapshot.bollinger_upper = Some(105.0);
+                    snapshot.bollinger_middle = Some(100.0);
+                    snapshot.bollinger_lower = Some(95.0);
+                }
+            }
+            other => panic!("no entry snapshots for template {}", other),
+        }
+        after.previous = Some(Box::new(before));
+        engine.update_snapshot(symbol, after);
+    }
+
+    #[test]
+    fn test_templates_instantiate_and_evaluate() {
+        let engine = setup_test_engine();
+        let portfolio = Portfolio::new("user".to_string(), "Templates".to_string());
+        engine.store.create_portfolio(&portfolio).unwrap();
+        let all = templates();
+        assert_eq!(all.len(), 3);
+
+        for template in all {
+            let symbol = format!("{}-coin", template.id);
+            let mut strategy = template.instantiate(&portfolio.id, vec![symbol.clone()]);
+            assert_eq!(strategy.portfolio_id, portfolio.id);
+            assert_eq!(strategy.status, StrategyStatus::Paused);
+            assert_eq!(strategy.rules.len(), 2);
+
+            // Rules survive the JSON round trip used for storage
+            let json = serde_json::to_string(&strategy.rules).unwrap();
+            let rules: Vec<TradingRule> = serde_json::from_str(&json).unwrap();
+            assert_eq!(rules.len(), strategy.rules.len());
+
+            strategy.activate();
+            engine.store.create_strategy(&strategy).unwrap();
+            let stored = engine.store.get_strategy(&strategy.id).unwrap();
+
+            // Nothing to evaluate yet, but the strategy runs cleanly
+            assert!(engine.evaluate_strategy(&stored).unwrap().is_empty());
+
+            feed_entry_snapshots(&engine, template.id, &symbol);
+            let signals = engine.evaluate_strategy(&stored).unwrap();
+            assert_eq!(signals.len(), 1, "template {} did not enter", template.id);
+            assert_eq!(signals[0].rule_id, stored.rules[0].id);
+            assert_eq!(signals[0].action.action_type, RuleActionType::MarketBuy);
+
+            let order = engine.signal_to_order_request(&signals[0], 10_000.0, 106.0);
+            assert_eq!(order.side, OrderSide::Buy);
+            assert!(order.quantity > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_template_lookup() {
+        assert_eq!(template("macd_trend").unwrap().name, "MACD Trend");
+        assert!(template("unknown").is_none());
+    }
 }
@@ -9,12 +9,19 @@
 //! Uses SQLite for persistence and DashMap for real-time caching.
 
 use crate::services::liquidity_sim::{LiquiditySimulator, LiquiditySimConfig};
-use crate::services::SqliteStore;
+use crate::services::strategy_engine;
+use crate::services::{Cache, ChartStore, NotificationService, SqliteStore};
 use crate::types::{
-    AggregatedOrderBook, AssetClass, BracketOrder, BracketRole, CostBasisEntry, CostBasisMethod,
-    EquityPoint, Fill, LeaderboardEntry, OcoOrder, Order, OrderSide, OrderStatus, OrderType,
-    PlaceOrderRequest, Portfolio, Position, PositionSide, PortfolioSummary, RiskSettings,
-    TimeInForce, Trade,
+    AggregatedOrderBook, AssetClass, BracketOrder, BracketRole, ChartRange, ChartResolution,
+    Competition, CompetitionStandings, CompetitionStatus, CostBasisEntry, CostBasisMethod,
+    CreateCompetitionRequest, EquityPoint, ExecutionAlgo, ExecutionAlgoRequest,
+    ExecutionSchedule, ExecutionStatus, Fill, FundingRateHistory, FundingRatePoint,
+    LeaderboardEntry, LeaderboardWindow, Notification, NotificationType, OcoOrder, OhlcPoint,
+    OptionContract, OptionPosition, Order, OrderSide, OrderStatus,
+    OrderType, PlaceOrderRequest, Portfolio, Position, PositionFunding, PositionSide,
+    PortfolioSummary, RiskSettings, StrategyFromTemplateRequest, TimeInForce, Trade,
+    TradeWindowStats, TradingStrategy, DEFAULT_STARTING_BALANCE, MAX_EXECUTION_DURATION_SECS,
+    MAX_EXECUTION_SLICES,
 };
 use crate::types::{
     LiquidationAlertData, MarginWarningData, OrderUpdateData, OrderUpdateType,
@@ -22,8 +29,10 @@ use crate::types::{
     ServerMessage, TradeExecutionData,
 };
 use crate::websocket::RoomManager;
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 use tracing::{debug, info, warn};
 
@@ -48,6 +57,17 @@ pub enum TradingError {
     #[error("Position limit exceeded: max {max} positions")]
     PositionLimitExceeded { max: u32 },
 
+    #[error(
+        "Concentration limit exceeded for {symbol}: {:.1}% of portfolio > {:.1}% max",
+        .concentration * 100.0,
+        .max * 100.0
+    )]
+    ConcentrationLimitExceeded {
+        symbol: String,
+        concentration: f64,
+        max: f64,
+    },
+
     #[error("Invalid order: {0}")]
     InvalidOrder(String),
 
@@ -68,6 +88,36 @@ pub enum TradingError {
 
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
+
+    #[error("Competition not found: {0}")]
+    CompetitionNotFound(String),
+
+    #[error("Invalid competition: {0}")]
+    InvalidCompetition(String),
+
+    #[error("Trading is closed outside the window of competition {0}")]
+    CompetitionClosed(String),
+
+    #[error("Invalid option chain request: {0}")]
+    InvalidOptionChain(String),
+
+    #[error("Execution schedule not found: {0}")]
+    ExecutionNotFound(String),
+
+    #[error("Strategy template not found: {0}")]
+    StrategyTemplateNotFound(String),
+
+    #[error("Invalid strategy: {0}")]
+    InvalidStrategy(String),
+
+    #[error("Internal error: {0}")]
+    Internal(String),
+}
+
+impl From<crate::services::options::OptionsError> for TradingError {
+    fn from(e: crate::services::options::OptionsError) -> Self {
+        TradingError::InvalidOptionChain(e.to_string())
+    }
 }
 
 impl From<rusqlite::Error> for TradingError {
@@ -103,6 +153,24 @@ impl Default for ExecutionConfig {
     }
 }
 
+/// Margin ratio (maintenance / equity) at which a margin call is issued,
+/// i.e. equity within ~10% of the maintenance requirement.
+const MARGIN_CALL_RATIO: f64 = 0.9;
+
+/// How long a computed leaderboard is served from cache.
+const LEADERBOARD_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Result of a scheduled snapshot pass over all portfolios.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SnapshotRunSummary {
+    /// Portfolios that got a new equity point
+    pub snapshotted: usize,
+    /// Portfolios skipped because nothing changed since their last snapshot
+    pub skipped: usize,
+    /// Old snapshots deleted
+    pub pruned: usize,
+}
+
 /// Paper trading service.
 #[derive(Clone)]
 pub struct TradingService {
@@ -120,6 +188,14 @@ pub struct TradingService {
     liquidity_sim: Arc<LiquiditySimulator>,
     /// Room manager for WebSocket broadcasts (optional for testing)
     room_manager: Option<Arc<RoomManager>>,
+    /// Computed leaderboards by window
+    leaderboard_cache: Arc<Cache<Vec<LeaderboardEntry>>>,
+    /// Positions currently in margin call (debounces margin call notifications)
+    margin_calls: Arc<DashSet<String>>,
+    /// Stores and delivers user notifications
+    notifications: Arc<NotificationService>,
+    /// Chart data for volume profiles (optional for testing)
+    chart_store: Option<Arc<ChartStore>>,
 }
 
 impl TradingService {
@@ -129,10 +205,14 @@ impl TradingService {
             portfolios: Arc::new(DashMap::new()),
             orders: Arc::new(DashMap::new()),
             positions: Arc::new(DashMap::new()),
+            margin_calls: Arc::new(DashSet::new()),
+            notifications: Arc::new(NotificationService::new(sqlite.clone())),
             sqlite,
             config: ExecutionConfig::default(),
             liquidity_sim: Arc::new(LiquiditySimulator::default()),
             room_manager: None,
+            leaderboard_cache: Arc::new(Cache::new(LEADERBOARD_CACHE_TTL)),
+            chart_store: None,
         }
     }
 
@@ -142,10 +222,14 @@ impl TradingService {
             portfolios: Arc::new(DashMap::new()),
             orders: Arc::new(DashMap::new()),
             positions: Arc::new(DashMap::new()),
+            margin_calls: Arc::new(DashSet::new()),
+            notifications: Arc::new(NotificationService::new(sqlite.clone())),
             sqlite,
             config,
             liquidity_sim: Arc::new(LiquiditySimulator::default()),
             room_manager: None,
+            leaderboard_cache: Arc::new(Cache::new(LEADERBOARD_CACHE_TTL)),
+            chart_store: None,
         }
     }
 
@@ -155,10 +239,14 @@ impl TradingService {
             portfolios: Arc::new(DashMap::new()),
             orders: Arc::new(DashMap::new()),
             positions: Arc::new(DashMap::new()),
+            margin_calls: Arc::new(DashSet::new()),
+            notifications: Arc::new(NotificationService::new(sqlite.clone())),
             sqlite,
             config: ExecutionConfig::default(),
             liquidity_sim: Arc::new(LiquiditySimulator::new(liquidity_config)),
             room_manager: None,
+            leaderboard_cache: Arc::new(Cache::new(LEADERBOARD_CACHE_TTL)),
+            chart_store: None,
         }
     }
 
@@ -168,10 +256,14 @@ impl TradingService {
             portfolios: Arc::new(DashMap::new()),
             orders: Arc::new(DashMap::new()),
             positions: Arc::new(DashMap::new()),
+            margin_calls: Arc::new(DashSet::new()),
+            notifications: Arc::new(NotificationService::new(sqlite.clone())),
             sqlite,
             config: ExecutionConfig::default(),
             liquidity_sim: Arc::new(LiquiditySimulator::default()),
             room_manager: Some(room_manager),
+            leaderboard_cache: Arc::new(Cache::new(LEADERBOARD_CACHE_TTL)),
+            chart_store: None,
         }
     }
 
@@ -180,6 +272,16 @@ impl TradingService {
         self.room_manager = Some(room_manager);
     }
 
+    /// Set the chart store used for VWAP volume profiles.
+    pub fn set_chart_store(&mut self, chart_store: Arc<ChartStore>) {
+        self.chart_store = Some(chart_store);
+    }
+
+    /// Set the notification service used for account alerts (margin calls).
+    pub fn set_notification_service(&mut self, notifications: Arc<NotificationService>) {
+        self.notifications = notifications;
+    }
+
     // ==========================================================================
     // WebSocket Broadcast Helpers
     // ==========================================================================
@@ -193,9 +295,7 @@ impl TradingService {
                 timestamp: chrono::Utc::now().timestamp_millis(),
             };
             let msg = ServerMessage::OrderUpdate { data };
-            if let Ok(json) = serde_json::to_string(&msg) {
-                room_manager.broadcast_trading(&order.portfolio_id, &json);
-            }
+            room_manager.broadcast_trading(&order.portfolio_id, &msg);
         }
     }
 
@@ -208,9 +308,7 @@ impl TradingService {
                 timestamp: chrono::Utc::now().timestamp_millis(),
             };
             let msg = ServerMessage::PositionUpdate { data };
-            if let Ok(json) = serde_json::to_string(&msg) {
-                room_manager.broadcast_trading(&position.portfolio_id, &json);
-            }
+            room_manager.broadcast_trading(&position.portfolio_id, &msg);
         }
     }
 
@@ -223,9 +321,7 @@ impl TradingService {
                 timestamp: chrono::Utc::now().timestamp_millis(),
             };
             let msg = ServerMessage::PortfolioUpdate { data };
-            if let Ok(json) = serde_json::to_string(&msg) {
-                room_manager.broadcast_trading(&portfolio.id, &json);
-            }
+            room_manager.broadcast_trading(&portfolio.id, &msg);
         }
     }
 
@@ -239,9 +335,7 @@ impl TradingService {
                 timestamp: chrono::Utc::now().timestamp_millis(),
             };
             let msg = ServerMessage::TradeExecution { data };
-            if let Ok(json) = serde_json::to_string(&msg) {
-                room_manager.broadcast_trading(&trade.portfolio_id, &json);
-            }
+            room_manager.broadcast_trading(&trade.portfolio_id, &msg);
         }
     }
 
@@ -262,9 +356,7 @@ impl TradingService {
                 timestamp: chrono::Utc::now().timestamp_millis(),
             };
             let msg = ServerMessage::MarginWarning { data };
-            if let Ok(json) = serde_json::to_string(&msg) {
-                room_manager.broadcast_trading(portfolio_id, &json);
-            }
+            room_manager.broadcast_trading(portfolio_id, &msg);
         }
     }
 
@@ -287,9 +379,7 @@ impl TradingService {
                 timestamp: chrono::Utc::now().timestamp_millis(),
             };
             let msg = ServerMessage::LiquidationAlert { data };
-            if let Ok(json) = serde_json::to_string(&msg) {
-                room_manager.broadcast_trading(portfolio_id, &json);
-            }
+            room_manager.broadcast_trading(portfolio_id, &msg);
         }
     }
 
@@ -367,60 +457,141 @@ impl TradingService {
         let mut entries: Vec<LeaderboardEntry> = self
             .portfolios
             .iter()
-            .filter(|r| {
-                let p = r.value();
-                // Always show bot portfolios
-                if p.user_id.starts_with("bot_") {
-                    return true;
-                }
-                // For user portfolios, check if they've opted in
-                self.sqlite
-                    .get_profile(&p.user_id)
-                    .map(|profile| profile.show_on_leaderboard)
-                    .unwrap_or(false)
-            })
+            .filter(|r| self.is_on_leaderboard(r.value()))
             .map(|r| {
                 let p = r.value();
-                let open_positions = self.sqlite.position_count(&p.id) as u32;
-                // Get display name from profile if available
-                let display_name = if p.user_id.starts_with("bot_") {
-                    p.name.clone()
+                self.leaderboard_entry(
+                    p,
+                    p.realized_pnl,
+                    p.total_return_pct(),
+                    p.total_trades,
+                    p.winning_trades,
+                )
+            })
+            .collect();
+
+        Self::rank_leaderboard(&mut entries);
+        entries.truncate(limit);
+        entries
+    }
+
+    /// Get the leaderboard for a time window.
+    ///
+    /// Daily and weekly boards rank portfolios by realized P&L from trades
+    /// executed within the window (return is relative to starting balance);
+    /// only portfolios that traded in the window appear. The all-time board is
+    /// the lifetime ranking from [`Self::get_leaderboard`]. Boards are cached
+    /// briefly since every request would otherwise rescan trades.
+    pub fn get_windowed_leaderboard(
+        &self,
+        window: LeaderboardWindow,
+        limit: usize,
+    ) -> Vec<LeaderboardEntry> {
+        let mut entries = match self.leaderboard_cache.get(window.as_str()) {
+            Some(entries) => entries,
+            None => {
+                let entries = self.compute_windowed_leaderboard(window);
+                self.leaderboard_cache
+                    .set(window.as_str().to_string(), entries.clone());
+                entries
+            }
+        };
+        entries.truncate(limit);
+        entries
+    }
+
+    fn compute_windowed_leaderboard(&self, window: LeaderboardWindow) -> Vec<LeaderboardEntry> {
+        let Some(duration) = window.duration_ms() else {
+            return self.get_leaderboard(usize::MAX);
+        };
+        let since = chrono::Utc::now().timestamp_millis() - duration;
+
+        let mut entries: Vec<LeaderboardEntry> = self
+            .sqlite
+            .trade_stats_since(since)
+            .into_iter()
+            .filter_map(|stats| {
+                let p = self.get_portfolio(&stats.portfolio_id)?;
+                if !self.is_on_leaderboard(&p) {
+                    return None;
+                }
+                let return_pct = if p.starting_balance > 0.0 {
+                    stats.realized_pnl / p.starting_balance * 100.0
                 } else {
-                    self.sqlite
-                        .get_profile(&p.user_id)
-                        .map(|profile| profile.username)
-                        .unwrap_or_else(|| p.name.clone())
+                    0.0
                 };
-                LeaderboardEntry {
-                    portfolio_id: p.id.clone(),
-                    name: display_name,
-                    user_id: p.user_id.clone(),
-                    total_value: p.total_value,
-                    starting_balance: p.starting_balance,
-                    realized_pnl: p.realized_pnl,
-                    unrealized_pnl: p.unrealized_pnl,
-                    total_return_pct: p.total_return_pct(),
-                    total_trades: p.total_trades,
-                    winning_trades: p.winning_trades,
-                    win_rate: if p.total_trades > 0 {
-                        p.winning_trades as f64 / p.total_trades as f64
-                    } else {
-                        0.0
-                    },
-                    open_positions,
-                }
+                Some(self.leaderboard_entry(
+                    &p,
+                    stats.realized_pnl,
+                    return_pct,
+                    stats.total_trades,
+                    stats.winning_trades,
+                ))
             })
             .collect();
 
-        // Sort by total return percentage (descending)
+        Self::rank_leaderboard(&mut entries);
+        entries
+    }
+
+    /// Whether a portfolio is shown on leaderboards (bots, or users who opted in).
+    fn is_on_leaderboard(&self, portfolio: &Portfolio) -> bool {
+        // Always show bot portfolios
+        if portfolio.user_id.starts_with("bot_") {
+            return true;
+        }
+        // For user portfolios, check if they've opted in
+        self.sqlite
+            .get_profile(&portfolio.user_id)
+            .map(|profile| profile.show_on_leaderboard)
+            .unwrap_or(false)
+    }
+
+    fn leaderboard_entry(
+        &self,
+        p: &Portfolio,
+        realized_pnl: f64,
+        total_return_pct: f64,
+        total_trades: u64,
+        winning_trades: u64,
+    ) -> LeaderboardEntry {
+        let open_positions = self.sqlite.position_count(&p.id) as u32;
+        // Get display name from profile if available
+        let display_name = if p.user_id.starts_with("bot_") {
+            p.name.clone()
+        } else {
+            self.sqlite
+                .get_profile(&p.user_id)
+                .map(|profile| profile.username)
+                .unwrap_or_else(|| p.name.clone())
+        };
+        LeaderboardEntry {
+            portfolio_id: p.id.clone(),
+            name: display_name,
+            user_id: p.user_id.clone(),
+            total_value: p.total_value,
+            starting_balance: p.starting_balance,
+            realized_pnl,
+            unrealized_pnl: p.unrealized_pnl,
+            total_return_pct,
+            total_trades,
+            winning_trades,
+            win_rate: if total_trades > 0 {
+                winning_trades as f64 / total_trades as f64
+            } else {
+                0.0
+            },
+            open_positions,
+        }
+    }
+
+    /// Sort by total return percentage (descending).
+    fn rank_leaderboard(entries: &mut [LeaderboardEntry]) {
         entries.sort_by(|a, b| {
             b.total_return_pct
                 .partial_cmp(&a.total_return_pct)
                 .unwrap_or(std::cmp::Ordering::Equal)
         });
-
-        entries.truncate(limit);
-        entries
     }
 
     /// Get portfolio summary with current metrics.
@@ -471,6 +642,29 @@ impl TradingService {
         Ok(portfolio)
     }
 
+    /// Change how lots are matched when positions are reduced.
+    ///
+    /// Applies to subsequent closes; lots already released are unaffected.
+    pub fn set_cost_basis_method(
+        &self,
+        portfolio_id: &str,
+        method: CostBasisMethod,
+    ) -> Result<Portfolio, TradingError> {
+        let mut portfolio = self
+            .get_portfolio(portfolio_id)
+            .ok_or_else(|| TradingError::PortfolioNotFound(portfolio_id.to_string()))?;
+
+        portfolio.cost_basis_method = method;
+        portfolio.updated_at = chrono::Utc::now().timestamp_millis();
+
+        self.sqlite.update_portfolio(&portfolio)?;
+        self.portfolios.insert(portfolio.id.clone(), portfolio.clone());
+
+        self.broadcast_portfolio_update(&portfolio, PortfolioUpdateType::SettingsChanged);
+
+        Ok(portfolio)
+    }
+
     /// Reset a portfolio to starting balance.
     pub fn reset_portfolio(&self, portfolio_id: &str) -> Result<Portfolio, TradingError> {
         let mut portfolio = self
@@ -481,7 +675,7 @@ impl TradingService {
         let positions = self.sqlite.get_portfolio_positions(portfolio_id);
         for position in positions {
             self.sqlite.close_position(&position.id)?;
-            self.positions.remove(&position.id);
+            self.forget_position(&position.id);
         }
 
         // Cancel all open orders
@@ -525,6 +719,200 @@ impl TradingService {
         Ok(())
     }
 
+    // ==========================================================================
+    // Competitions
+    // ==========================================================================
+
+    /// Create a competition.
+    pub fn create_competition(
+        &self,
+        request: CreateCompetitionRequest,
+    ) -> Result<Competition, TradingError> {
+        let name = request.name.trim();
+        if name.is_empty() {
+            return Err(TradingError::InvalidCompetition(
+                "Name is required".to_string(),
+            ));
+        }
+        if request.end_time <= request.start_time {
+            return Err(TradingError::InvalidCompetition(
+                "End time must be after start time".to_string(),
+            ));
+        }
+        let starting_balance = request.starting_balance.unwrap_or(DEFAULT_STARTING_BALANCE);
+        if !(starting_balance > 0.0 && starting_balance.is_finite()) {
+            return Err(TradingError::InvalidCompetition(format!(
+                "Starting balance must be positive, got {}",
+                starting_balance
+            )));
+        }
+
+        let competition = Competition::new(
+            name.to_string(),
+            starting_balance,
+            request.start_time,
+            request.end_time,
+        );
+        self.sqlite.create_competition(&competition)?;
+
+        info!("Created competition {} ({})", competition.id, competition.name);
+        Ok(competition)
+    }
+
+    /// Get a competition by ID.
+    pub fn get_competition(&self, id: &str) -> Option<Competition> {
+        self.sqlite.get_competition(id)
+    }
+
+    /// Get all competitions.
+    pub fn get_competitions(&self) -> Vec<Competition> {
+        self.sqlite.get_competitions()
+    }
+
+    /// Enroll a portfolio in a competition.
+    ///
+    /// The portfolio is reset to the competition's starting balance, closing
+    /// its positions and cancelling its orders, so every entrant starts level.
+    /// Enrollment closes when the competition ends.
+    pub fn enroll_portfolio(
+        &self,
+        competition_id: &str,
+        portfolio_id: &str,
+    ) -> Result<Portfolio, TradingError> {
+        let competition = self
+            .get_competition(competition_id)
+            .ok_or_else(|| TradingError::CompetitionNotFound(competition_id.to_string()))?;
+        let mut portfolio = self
+            .get_portfolio(portfolio_id)
+            .ok_or_else(|| TradingError::PortfolioNotFound(portfolio_id.to_string()))?;
+
+        if competition.status() == CompetitionStatus::Ended {
+            return Err(TradingError::CompetitionClosed(competition.id));
+        }
+        if let Some(existing) = &portfolio.competition_id {
+            return Err(TradingError::InvalidCompetition(format!(
+                "Portfolio {} is already enrolled in competition {}",
+                portfolio_id, existing
+            )));
+        }
+
+        portfolio.starting_balance = competition.starting_balance;
+        portfolio.is_competition = true;
+        portfolio.competition_id = Some(competition.id.clone());
+        self.sqlite.update_portfolio(&portfolio)?;
+        self.portfolios.insert(portfolio.id.clone(), portfolio);
+
+        let portfolio = self.reset_portfolio(portfolio_id)?;
+        info!("Enrolled portfolio {} in competition {}", portfolio_id, competition_id);
+        Ok(portfolio)
+    }
+
+    /// Rank a competition's portfolios by realized return within its window.
+    ///
+    /// Every enrolled portfolio is included, even without trades.
+    pub fn get_competition_standings(
+        &self,
+        competition_id: &str,
+    ) -> Result<CompetitionStandings, TradingError> {
+        let competition = self
+            .get_competition(competition_id)
+            .ok_or_else(|| TradingError::CompetitionNotFound(competition_id.to_string()))?;
+
+        let stats: HashMap<String, TradeWindowStats> = self
+            .sqlite
+            .trade_stats_between(competition.start_time, competition.end_time)
+            .into_iter()
+            .map(|stats| (stats.portfolio_id.clone(), stats))
+            .collect();
+
+        let mut standings: Vec<LeaderboardEntry> = self
+            .sqlite
+            .get_competition_portfolios(competition_id)
+            .into_iter()
+            .map(|stored| {
+                // Prefer the cached copy, which has live P&L
+                let p = self.get_portfolio(&stored.id).unwrap_or(stored);
+                let (realized_pnl, total_trades, winning_trades) = stats
+                    .get(&p.id)
+                    .map(|s| (s.realized_pnl, s.total_trades, s.winning_trades))
+                    .unwrap_or_default();
+                let return_pct = if p.starting_balance > 0.0 {
+                    realized_pnl / p.starting_balance * 100.0
+                } else {
+                    0.0
+                };
+                self.leaderboard_entry(&p, realized_pnl, return_pct, total_trades, winning_trades)
+            })
+            .collect();
+        Self::rank_leaderboard(&mut standings);
+
+        Ok(CompetitionStandings {
+            status: competition.status(),
+            competition,
+            standings,
+        })
+    }
+
+    /// Reject new exposure for a competition portfolio outside its competition window.
+    ///
+    /// Checked when an exposure-increasing order is placed and again when it
+    /// fills; closes, stop-loss/take-profit exits and liquidations always go through.
+    fn check_competition_window(&self, portfolio: &Portfolio) -> Result<(), TradingError> {
+        let Some(competition_id) = &portfolio.competition_id else {
+            return Ok(());
+        };
+        match self.get_competition(competition_id) {
+            Some(competition) if competition.status() != CompetitionStatus::Active => {
+                Err(TradingError::CompetitionClosed(competition.id))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Quantity of open positions in `symbol` that an order on `side` would reduce.
+    fn reducible_quantity(&self, portfolio_id: &str, symbol: &str, side: OrderSide) -> f64 {
+        self.get_positions(portfolio_id)
+            .iter()
+            .filter(|position| {
+                matches!(
+                    (position.side, side),
+                    (PositionSide::Long, OrderSide::Sell) | (PositionSide::Short, OrderSide::Buy)
+                ) && position.symbol.eq_ignore_ascii_case(symbol)
+            })
+            .map(|position| position.quantity)
+            .sum()
+    }
+
+    /// Whether a new order only reduces an open position in the same symbol,
+    /// after netting out other open orders on the same side.
+    ///
+    /// One-cancels-other legs (bracket exits or linked orders) can only fill
+    /// once, so each group counts by its largest remaining quantity.
+    fn reduces_position(
+        &self,
+        portfolio_id: &str,
+        symbol: &str,
+        side: OrderSide,
+        quantity: f64,
+    ) -> bool {
+        let mut pending: HashMap<String, f64> = HashMap::new();
+        for order in self.get_open_orders(portfolio_id) {
+            if order.side != side || !order.symbol.eq_ignore_ascii_case(symbol) {
+                continue;
+            }
+            let group = match (&order.bracket_id, &order.linked_order_id) {
+                (Some(bracket_id), _) => bracket_id.clone(),
+                (None, Some(linked_id)) => linked_id.clone().min(order.id.clone()),
+                (None, None) => order.id.clone(),
+            };
+            let largest = pending.entry(group).or_default();
+            *largest = largest.max(order.remaining_quantity());
+        }
+
+        let pending: f64 = pending.values().sum();
+        quantity <= self.reducible_quantity(portfolio_id, symbol, side) - pending
+    }
+
     // ==========================================================================
     // Portfolio Snapshot / Equity Curve
     // ==========================================================================
@@ -537,9 +925,75 @@ impl TradingService {
             .ok_or_else(|| TradingError::PortfolioNotFound(portfolio_id.to_string()))?;
 
         self.sqlite.create_snapshot_from_portfolio(&portfolio)?;
+        self.enforce_max_drawdown(&portfolio);
         Ok(())
     }
 
+    /// Pause every active strategy of a portfolio whose drawdown from peak
+    /// equity exceeds `max_drawdown_pct`, and notify the owner.
+    ///
+    /// The peak is the highest equity in the portfolio's snapshots (or its
+    /// starting balance). Strategies stay paused until the user reactivates
+    /// them; once none are active, later checks do nothing. Returns the IDs
+    /// of the strategies paused by this call.
+    pub fn enforce_max_drawdown(&self, portfolio: &Portfolio) -> Vec<String> {
+        let Some(max_drawdown) = portfolio.risk_settings.max_drawdown_pct else {
+            return Vec::new();
+        };
+        let peak = self
+            .sqlite
+            .get_peak_equity(&portfolio.id)
+            .unwrap_or(0.0)
+            .max(portfolio.starting_balance);
+        if peak <= 0.0 {
+            return Vec::new();
+        }
+        let drawdown = (peak - portfolio.total_value) / peak;
+        if drawdown <= max_drawdown {
+            return Vec::new();
+        }
+
+        let mut paused = Vec::new();
+        for mut strategy in self.sqlite.get_active_strategies(&portfolio.id) {
+            strategy.pause();
+            match self.sqlite.update_strategy(&strategy) {
+                Ok(()) => paused.push(strategy.id),
+                Err(e) => warn!("Failed to pause strategy {}: {}", strategy.id, e),
+            }
+        }
+        if paused.is_empty() {
+            return paused;
+        }
+
+        warn!(
+            "Portfolio {} drawdown {:.1}% exceeds {:.1}%; paused {} strategies",
+            portfolio.id,
+            drawdown * 100.0,
+            max_drawdown * 100.0,
+            paused.len()
+        );
+        let notification = Notification::new(
+            portfolio.user_id.clone(),
+            NotificationType::Warning,
+            format!("Strategies paused: {}", portfolio.name),
+            format!(
+                "Portfolio {} is down {:.1}% from its peak equity of {:.2}, past your \
+                 {:.1}% max drawdown. {} active strategies were paused and will stay \
+                 paused until you reactivate them.",
+                portfolio.name,
+                drawdown * 100.0,
+                peak,
+                max_drawdown * 100.0,
+                paused.len()
+            ),
+        );
+        if let Err(e) = self.notifications.notify(&notification) {
+            warn!("Failed to store drawdown notification: {}", e);
+        }
+
+        paused
+    }
+
     /// Get portfolio equity history for charting.
     /// Returns EquityPoint data points ordered by timestamp ascending.
     pub fn get_portfolio_history(
@@ -572,22 +1026,85 @@ impl TradingService {
         Ok(self.sqlite.cleanup_old_snapshots(portfolio_id, days_to_keep)?)
     }
 
+    /// Snapshot every portfolio whose equity changed since its last snapshot,
+    /// then prune snapshots older than `days_to_keep`.
+    ///
+    /// Idle portfolios are skipped so a quiet portfolio does not accumulate
+    /// identical points; its chart holds flat between the points it has.
+    pub fn snapshot_all_portfolios(&self, days_to_keep: i64) -> SnapshotRunSummary {
+        let mut summary = SnapshotRunSummary::default();
+
+        for stored in self.sqlite.get_all_portfolios() {
+            // Prefer the cached copy, which has live P&L
+            let portfolio = self.get_portfolio(&stored.id).unwrap_or(stored);
+
+            let unchanged = self
+                .sqlite
+                .get_latest_portfolio_snapshot(&portfolio.id)
+                .is_some_and(|last| {
+                    const EPSILON: f64 = 1e-6;
+                    (last.equity - portfolio.total_value).abs() < EPSILON
+                        && (last.cash - portfolio.cash_balance).abs() < EPSILON
+                        && (last.realized_pnl - portfolio.realized_pnl).abs() < EPSILON
+                        && (last.unrealized_pnl - portfolio.unrealized_pnl).abs() < EPSILON
+                });
+
+            if unchanged {
+                summary.skipped += 1;
+            } else {
+                match self.sqlite.create_snapshot_from_portfolio(&portfolio) {
+                    Ok(()) => {
+                        summary.snapshotted += 1;
+                        self.enforce_max_drawdown(&portfolio);
+                    }
+                    Err(e) => warn!("Failed to snapshot portfolio {}: {}", portfolio.id, e),
+                }
+            }
+
+            match self.sqlite.cleanup_old_snapshots(&portfolio.id, days_to_keep) {
+                Ok(deleted) => summary.pruned += deleted,
+                Err(e) => warn!("Failed to prune snapshots for {}: {}", portfolio.id, e),
+            }
+        }
+
+        summary
+    }
+
     // ==========================================================================
     // Order Management
     // ==========================================================================
 
     /// Place a new order.
+    ///
+    /// Placement is idempotent on `client_order_id`: if the portfolio already
+    /// has an order with the same client ID, that order is returned unchanged
+    /// instead of placing a duplicate.
     pub fn place_order(&self, request: PlaceOrderRequest) -> Result<Order, TradingError> {
         // Validate portfolio exists and is not stopped
         let portfolio = self
             .get_portfolio(&request.portfolio_id)
             .ok_or_else(|| TradingError::PortfolioNotFound(request.portfolio_id.clone()))?;
 
+        // A retried request returns the order it already placed
+        if let Some(existing) =
+            self.find_client_order(&request.portfolio_id, request.client_order_id.as_deref())
+        {
+            info!(
+                "Order with client id {:?} already placed as {}",
+                existing.client_order_id, existing.id
+            );
+            return Ok(existing);
+        }
+
         // Check if portfolio is stopped due to drawdown (unless bypass is requested)
         if portfolio.is_stopped() && !request.bypass_drawdown {
             return Err(TradingError::PortfolioStopped);
         }
 
+        if !self.reduces_position(&portfolio.id, &request.symbol, request.side, request.quantity) {
+            self.check_competition_window(&portfolio)?;
+        }
+
         // Validate leverage
         let leverage = request.leverage.unwrap_or(1.0);
         let max_leverage = request.asset_class.max_leverage();
@@ -607,7 +1124,7 @@ impl TradingService {
         }
 
         // Create order
-        let mut order = Order {
+        let order = Order {
             id: uuid::Uuid::new_v4().to_string(),
             portfolio_id: request.portfolio_id,
             symbol: request.symbol,
@@ -635,26 +1152,34 @@ impl TradingService {
             trail_high_price: None,
             trail_low_price: None,
             bracket_role: None,
+            display_quantity: request.display_quantity,
         };
 
-        // Initialize trailing stop tracking if applicable
-        if order.order_type == OrderType::TrailingStop {
-            // For trailing stops, we need an initial reference price
-            // This would typically come from the current market price
-            // For now, set initial tracking values to trigger price
-            if let Some(stop) = order.stop_price {
-                match order.side {
-                    OrderSide::Sell => order.trail_high_price = Some(stop),
-                    OrderSide::Buy => order.trail_low_price = Some(stop),
-                }
-            }
-        }
-
         // Validate order
         self.validate_order(&order, &portfolio)?;
 
-        // Persist and cache
-        self.sqlite.create_order(&order)?;
+        // Market orders are checked again at fill time, once their price is known
+        if let Some(price) = order.price.or(order.stop_price) {
+            self.check_concentration(&portfolio, &order, order.quantity, price)?;
+        }
+
+        // Persist and cache. A concurrent retry can win the race to insert,
+        // in which case the unique index rejects this one.
+        if let Err(e) = self.sqlite.create_order(&order) {
+            let duplicate = matches!(
+                &e,
+                rusqlite::Error::SqliteFailure(f, _)
+                    if f.code == rusqlite::ErrorCode::ConstraintViolation
+            );
+            if duplicate {
+                if let Some(existing) =
+                    self.find_client_order(&order.portfolio_id, order.client_order_id.as_deref())
+                {
+                    return Ok(existing);
+                }
+            }
+            return Err(e.into());
+        }
         self.orders.insert(order.id.clone(), order.clone());
 
         // Broadcast order creation
@@ -664,6 +1189,19 @@ impl TradingService {
         Ok(order)
     }
 
+    /// Find an existing order in a portfolio with the given client order ID.
+    fn find_client_order(
+        &self,
+        portfolio_id: &str,
+        client_order_id: Option<&str>,
+    ) -> Option<Order> {
+        let order = self
+            .sqlite
+            .get_order_by_client_id(portfolio_id, client_order_id?)?;
+        // Prefer the cached copy, which may be more recent
+        self.get_order(&order.id)
+    }
+
     /// Validate an order before placement.
     fn validate_order(&self, order: &Order, portfolio: &Portfolio) -> Result<(), TradingError> {
         // Validate quantity
@@ -697,6 +1235,20 @@ impl TradingService {
             _ => {}
         }
 
+        // Iceberg orders rest on the book, so only limit orders can hide size
+        if let Some(display) = order.display_quantity {
+            if order.order_type != OrderType::Limit {
+                return Err(TradingError::InvalidOrder(
+                    "Iceberg orders must be limit orders".to_string(),
+                ));
+            }
+            if display <= 0.0 || display > order.quantity {
+                return Err(TradingError::InvalidOrder(
+                    "display_quantity must be positive and at most quantity".to_string(),
+                ));
+            }
+        }
+
         // Check if buy order has sufficient funds (rough estimate)
         if order.side == OrderSide::Buy {
             // For market orders, we can't know exact cost without price
@@ -715,6 +1267,56 @@ impl TradingService {
         Ok(())
     }
 
+    /// Reject an order whose fill would push the symbol's share of the portfolio
+    /// above `max_symbol_concentration_pct`.
+    ///
+    /// Exposure is the net notional of the portfolio's positions in the symbol
+    /// plus `quantity` at `price`. Orders that shrink the exposure always pass, so
+    /// an over-concentrated portfolio can still be reduced.
+    fn check_concentration(
+        &self,
+        portfolio: &Portfolio,
+        order: &Order,
+        quantity: f64,
+        price: f64,
+    ) -> Result<(), TradingError> {
+        let Some(max) = portfolio.risk_settings.max_symbol_concentration_pct else {
+            return Ok(());
+        };
+        if portfolio.total_value <= 0.0 {
+            return Ok(());
+        }
+
+        let current: f64 = self
+            .sqlite
+            .get_portfolio_positions(&portfolio.id)
+            .iter()
+            .filter(|p| p.symbol.eq_ignore_ascii_case(&order.symbol))
+            .map(|p| match p.side {
+                PositionSide::Long => p.quantity * price,
+                PositionSide::Short => -p.quantity * price,
+            })
+            .sum();
+        let delta = match order.side {
+            OrderSide::Buy => quantity * price,
+            OrderSide::Sell => -quantity * price,
+        };
+        let after = (current + delta).abs();
+        if after <= current.abs() {
+            return Ok(());
+        }
+
+        let concentration = after / portfolio.total_value;
+        if concentration > max {
+            return Err(TradingError::ConcentrationLimitExceeded {
+                symbol: order.symbol.clone(),
+                concentration,
+                max,
+            });
+        }
+        Ok(())
+    }
+
     /// Get an order by ID.
     pub fn get_order(&self, order_id: &str) -> Option<Order> {
         if let Some(order) = self.orders.get(order_id) {
@@ -767,6 +1369,10 @@ impl TradingService {
     // ==========================================================================
 
     /// Execute a market order immediately.
+    ///
+    /// Fills the order's visible quantity: the whole remainder for regular
+    /// orders, or the current slice for iceberg orders, which stay
+    /// partially filled until later ticks fill the hidden remainder.
     pub fn execute_market_order(
         &self,
         order_id: &str,
@@ -784,16 +1390,41 @@ impl TradingService {
             )));
         }
 
+        let fill_quantity = order.visible_quantity();
+
+        if let Some(portfolio) = self.get_portfolio(&order.portfolio_id) {
+            // Orders placed inside a competition window may only reduce once it ends
+            let reducible = self.reducible_quantity(&order.portfolio_id, &order.symbol, order.side);
+            let check = if fill_quantity <= reducible {
+                Ok(())
+            } else {
+                self.check_competition_window(&portfolio)
+            };
+            let check = check.and_then(|_| {
+                self.check_concentration(&portfolio, &order, fill_quantity, current_price)
+            });
+            if let Err(e) = check {
+                // Retrying at the next tick would fail the same way
+                order.status = OrderStatus::Rejected;
+                order.updated_at = chrono::Utc::now().timestamp_millis();
+                self.sqlite.update_order(&order)?;
+                self.orders.insert(order.id.clone(), order.clone());
+                self.broadcast_order_update(&order, OrderUpdateType::Rejected);
+                warn!("Rejected order {}: {}", order.id, e);
+                return Err(e);
+            }
+        }
+
         // Calculate execution price with slippage
         let (execution_price, slippage) =
-            self.calculate_execution_price(&order, current_price, order_book);
+            self.calculate_execution_price(&order, fill_quantity, current_price, order_book);
 
         // Calculate fee
-        let notional = order.quantity * execution_price;
+        let notional = fill_quantity * execution_price;
         let fee = notional * self.config.fee_pct;
 
         // Create fill
-        let fill = Fill::new(order.quantity, execution_price, fee);
+        let fill = Fill::new(fill_quantity, execution_price, fee);
         order.add_fill(fill);
 
         // Update portfolio
@@ -802,7 +1433,8 @@ impl TradingService {
             .ok_or_else(|| TradingError::PortfolioNotFound(order.portfolio_id.clone()))?;
 
         // Create or update position
-        let position_id = self.update_position_for_trade(&mut portfolio, &order, execution_price)?;
+        let position_id =
+            self.update_position_for_trade(&mut portfolio, &order, fill_quantity, execution_price)?;
 
         // Recalculate unrealized PnL from all remaining open positions
         let open_positions = self.sqlite.get_portfolio_positions(&portfolio.id);
@@ -824,7 +1456,7 @@ impl TradingService {
             order.symbol.clone(),
             order.asset_class,
             order.side,
-            order.quantity,
+            fill_quantity,
             execution_price,
             fee,
             slippage,
@@ -834,12 +1466,15 @@ impl TradingService {
         self.sqlite.create_trade(&trade)?;
 
         // Take portfolio snapshot for equity curve charting
-        if let Err(e) = self.sqlite.create_snapshot_from_portfolio(&portfolio) {
-            debug!("Failed to create portfolio snapshot: {}", e);
+        match self.sqlite.create_snapshot_from_portfolio(&portfolio) {
+            Ok(()) => {
+                self.enforce_max_drawdown(&portfolio);
+            }
+            Err(e) => debug!("Failed to create portfolio snapshot: {}", e),
         }
 
         // Broadcast updates
-        self.broadcast_order_update(&order, OrderUpdateType::Filled);
+        self.broadcast_order_update(&order, OrderUpdateType::from(order.status));
         self.broadcast_trade_execution(&trade, Some(position_id));
         self.broadcast_portfolio_update(&portfolio, PortfolioUpdateType::BalanceChanged);
 
@@ -858,6 +1493,7 @@ impl TradingService {
     fn calculate_execution_price(
         &self,
         order: &Order,
+        quantity: f64,
         current_price: f64,
         order_book: Option<&AggregatedOrderBook>,
     ) -> (f64, f64) {
@@ -867,7 +1503,7 @@ impl TradingService {
                 let (exec_price, slippage, _filled) = self.liquidity_sim.calculate_execution_price(
                     book,
                     order.side,
-                    order.quantity,
+                    quantity,
                 );
                 return (exec_price, slippage);
             }
@@ -911,9 +1547,10 @@ impl TradingService {
         &self,
         portfolio: &mut Portfolio,
         order: &Order,
+        quantity: f64,
         execution_price: f64,
     ) -> Result<String, TradingError> {
-        let notional = order.quantity * execution_price;
+        let notional = quantity * execution_price;
         let margin_required = notional / order.leverage;
 
         // Determine position side from order
@@ -931,16 +1568,16 @@ impl TradingService {
 
         let position_id = if let Some(mut position) = existing {
             // Add to existing position (average in)
-            let total_qty = position.quantity + order.quantity;
+            let total_qty = position.quantity + quantity;
             let total_cost =
-                position.entry_price * position.quantity + execution_price * order.quantity;
+                position.entry_price * position.quantity + execution_price * quantity;
             position.entry_price = total_cost / total_qty;
             position.quantity = total_qty;
             position.margin_used += margin_required;
 
             // Add cost basis entry
             position.cost_basis.push(CostBasisEntry {
-                quantity: order.quantity,
+                quantity,
                 price: execution_price,
                 acquired_at: chrono::Utc::now().timestamp_millis(),
             });
@@ -966,10 +1603,22 @@ impl TradingService {
                 self.sqlite
                     .get_position_by_symbol(&order.portfolio_id, &order.symbol, opposite_side)
             {
-                // Closing position
-                let realized_pnl = self.calculate_realized_pnl(&opposite_position, order.quantity, execution_price);
+                // Closing position: lots are released per the portfolio's
+                // cost basis method, and any excess flips the position
+                let close_qty = quantity.min(opposite_position.quantity);
+                let closed_cost = Self::reduce_cost_basis(
+                    &mut opposite_position,
+                    close_qty,
+                    portfolio.cost_basis_method,
+                );
+                let realized_pnl = Self::calculate_realized_pnl(
+                    opposite_position.side,
+                    close_qty,
+                    closed_cost,
+                    execution_price,
+                );
 
-                if order.quantity >= opposite_position.quantity {
+                if quantity >= opposite_position.quantity {
                     // Fully close position
                     portfolio.realized_pnl += realized_pnl;
                     portfolio.cash_balance += opposite_position.margin_used + realized_pnl;
@@ -982,13 +1631,13 @@ impl TradingService {
                     }
 
                     self.sqlite.close_position(&opposite_position.id)?;
-                    self.positions.remove(&opposite_position.id);
+                    self.forget_position(&opposite_position.id);
 
                     // Broadcast position closed
                     self.broadcast_position_update(&opposite_position, PositionUpdateType::Closed);
 
                     // If there's remaining quantity, open new position in opposite direction
-                    let remaining = order.quantity - opposite_position.quantity;
+                    let remaining = quantity - opposite_position.quantity;
                     if remaining > 0.0 {
                         let new_position = self.create_new_position(
                             portfolio,
@@ -1005,17 +1654,18 @@ impl TradingService {
                     opposite_position.id
                 } else {
                     // Partially close position
-                    let close_ratio = order.quantity / opposite_position.quantity;
+                    let close_ratio = quantity / opposite_position.quantity;
                     let margin_released = opposite_position.margin_used * close_ratio;
 
-                    opposite_position.quantity -= order.quantity;
+                    opposite_position.quantity -= quantity;
                     opposite_position.margin_used -= margin_released;
                     opposite_position.realized_pnl += realized_pnl;
+                    // The remaining lots set the entry price
+                    if let Some(price) = Self::cost_basis_price(&opposite_position.cost_basis) {
+                        opposite_position.entry_price = price;
+                    }
                     opposite_position.update_price(execution_price);
 
-                    // Remove closed portion from cost basis (FIFO by default)
-                    self.reduce_cost_basis(&mut opposite_position, order.quantity, portfolio.cost_basis_method);
-
                     portfolio.realized_pnl += realized_pnl;
                     portfolio.cash_balance += margin_released + realized_pnl;
                     portfolio.margin_used -= margin_released;
@@ -1040,7 +1690,7 @@ impl TradingService {
                     portfolio,
                     order,
                     execution_price,
-                    order.quantity,
+                    quantity,
                     position_side,
                 )?;
                 // Broadcast new position opened
@@ -1098,62 +1748,82 @@ impl TradingService {
         Ok(position)
     }
 
-    /// Calculate realized P&L for closing a position.
-    fn calculate_realized_pnl(&self, position: &Position, close_qty: f64, close_price: f64) -> f64 {
-        let entry_value = close_qty * position.entry_price;
+    /// Calculate realized P&L for closing `close_qty` units whose lots cost `closed_cost`.
+    fn calculate_realized_pnl(
+        side: PositionSide,
+        close_qty: f64,
+        closed_cost: f64,
+        close_price: f64,
+    ) -> f64 {
         let exit_value = close_qty * close_price;
 
-        match position.side {
-            PositionSide::Long => exit_value - entry_value,
-            PositionSide::Short => entry_value - exit_value,
+        match side {
+            PositionSide::Long => exit_value - closed_cost,
+            PositionSide::Short => closed_cost - exit_value,
         }
     }
 
-    /// Reduce cost basis using the specified method.
-    fn reduce_cost_basis(
-        &self,
+    /// Weighted average price of a set of lots, or `None` if there are none.
+    fn cost_basis_price(lots: &[CostBasisEntry]) -> Option<f64> {
+        let quantity: f64 = lots.iter().map(|e| e.quantity).sum();
+        if quantity <= 0.0 {
+            return None;
+        }
+        let cost: f64 = lots.iter().map(|e| e.quantity * e.price).sum();
+        Some(cost / quantity)
+    }
+
+    /// Remove `qty_to_remove` units from the position's lots using the
+    /// specified method, returning the cost of the units removed.
+    ///
+    /// FIFO and LIFO consume whole lots from the oldest or newest end. Average
+    /// costs every unit at the weighted average and shrinks all lots
+    /// proportionally. Quantity not covered by lots (positions stored without
+    /// a lot history) is costed at the position's entry price.
+    fn reduce_cost_basis(
         position: &mut Position,
-        mut qty_to_remove: f64,
+        qty_to_remove: f64,
         method: CostBasisMethod,
-    ) {
+    ) -> f64 {
+        let mut remaining = qty_to_remove;
+        let mut cost = 0.0;
+
         match method {
-            CostBasisMethod::Fifo => {
-                // Remove from front
-                while qty_to_remove > 0.0 && !position.cost_basis.is_empty() {
-                    let entry = &mut position.cost_basis[0];
-                    if entry.quantity <= qty_to_remove {
-                        qty_to_remove -= entry.quantity;
-                        position.cost_basis.remove(0);
+            CostBasisMethod::Fifo | CostBasisMethod::Lifo => {
+                while remaining > 0.0 && !position.cost_basis.is_empty() {
+                    let idx = match method {
+                        CostBasisMethod::Fifo => 0,
+                        _ => position.cost_basis.len() - 1,
+                    };
+                    let entry = &mut position.cost_basis[idx];
+                    if entry.quantity <= remaining {
+                        remaining -= entry.quantity;
+                        cost += entry.quantity * entry.price;
+                        position.cost_basis.remove(idx);
                     } else {
-                        entry.quantity -= qty_to_remove;
-                        qty_to_remove = 0.0;
-                    }
-                }
-            }
-            CostBasisMethod::Lifo => {
-                // Remove from back
-                while qty_to_remove > 0.0 && !position.cost_basis.is_empty() {
-                    let last_idx = position.cost_basis.len() - 1;
-                    let entry = &mut position.cost_basis[last_idx];
-                    if entry.quantity <= qty_to_remove {
-                        qty_to_remove -= entry.quantity;
-                        position.cost_basis.pop();
-                    } else {
-                        entry.quantity -= qty_to_remove;
-                        qty_to_remove = 0.0;
+                        entry.quantity -= remaining;
+                        cost += remaining * entry.price;
+                        remaining = 0.0;
                     }
                 }
             }
             CostBasisMethod::Average => {
-                // Just reduce proportionally
                 let total_qty: f64 = position.cost_basis.iter().map(|e| e.quantity).sum();
-                let ratio = (total_qty - qty_to_remove) / total_qty;
-                for entry in &mut position.cost_basis {
-                    entry.quantity *= ratio;
+                if let Some(average) = Self::cost_basis_price(&position.cost_basis) {
+                    let removed = remaining.min(total_qty);
+                    cost += removed * average;
+                    remaining -= removed;
+
+                    let ratio = (total_qty - removed) / total_qty;
+                    for entry in &mut position.cost_basis {
+                        entry.quantity *= ratio;
+                    }
+                    position.cost_basis.retain(|e| e.quantity > 0.0001);
                 }
-                position.cost_basis.retain(|e| e.quantity > 0.0001);
             }
         }
+
+        cost + remaining * position.entry_price
     }
 
     // ==========================================================================
@@ -1179,6 +1849,39 @@ impl TradingService {
         self.sqlite.get_portfolio_positions(portfolio_id)
     }
 
+    /// Get the funding payments charged to a position and their total.
+    ///
+    /// Payments outlive the position, so a closed position still reports its
+    /// funding as long as any payments were recorded.
+    pub fn get_position_funding(&self, position_id: &str) -> Result<PositionFunding, TradingError> {
+        let payments = self.sqlite.get_position_funding_payments(position_id);
+        if payments.is_empty() && self.get_position(position_id).is_none() {
+            return Err(TradingError::PositionNotFound(position_id.to_string()));
+        }
+
+        let total_funding = payments.iter().map(|p| p.payment).sum();
+        Ok(PositionFunding {
+            position_id: position_id.to_string(),
+            payments,
+            total_funding,
+        })
+    }
+
+    /// Get the recent funding rates applied to a symbol, newest first.
+    pub fn get_funding_rate_history(&self, symbol: &str, limit: usize) -> FundingRateHistory {
+        let rates = self
+            .sqlite
+            .get_symbol_funding_rates(symbol, limit)
+            .into_iter()
+            .map(|(rate, timestamp)| FundingRatePoint { rate, timestamp })
+            .collect();
+
+        FundingRateHistory {
+            symbol: symbol.to_string(),
+            rates,
+        }
+    }
+
     /// Update position with current market price.
     pub fn update_position_price(
         &self,
@@ -1273,6 +1976,115 @@ impl TradingService {
         Ok(())
     }
 
+    // ==========================================================================
+    // Options Positions
+    // ==========================================================================
+
+    /// Write (sell to open) option contracts.
+    ///
+    /// The premium received is credited to cash and the naked option margin
+    /// requirement is reserved from the portfolio until the position is closed.
+    pub fn open_short_option(
+        &self,
+        portfolio_id: &str,
+        contract: &OptionContract,
+        contracts: u32,
+        premium: f64,
+        underlying_price: f64,
+    ) -> Result<OptionPosition, TradingError> {
+        if contracts == 0 {
+            return Err(TradingError::InvalidOrder(
+                "Contracts must be positive".to_string(),
+            ));
+        }
+        if premium <= 0.0 || underlying_price <= 0.0 {
+            return Err(TradingError::InvalidOrder(
+                "Premium and underlying price must be positive".to_string(),
+            ));
+        }
+
+        let mut portfolio = self
+            .get_portfolio(portfolio_id)
+            .ok_or_else(|| TradingError::PortfolioNotFound(portfolio_id.to_string()))?;
+
+        if portfolio.is_stopped() {
+            return Err(TradingError::PortfolioStopped);
+        }
+        self.check_competition_window(&portfolio)?;
+
+        let mut position = OptionPosition::new(
+            portfolio_id.to_string(),
+            contract,
+            -(contracts as i32),
+            premium,
+        );
+        position.underlying_price = underlying_price;
+
+        let margin_required = position.margin_requirement();
+        if margin_required > portfolio.margin_available {
+            return Err(TradingError::InsufficientMargin {
+                needed: margin_required,
+                available: portfolio.margin_available,
+            });
+        }
+        position.margin_used = margin_required;
+
+        // Credit the premium, then move the margin out of cash
+        portfolio.cash_balance += position.entry_cost().abs();
+        portfolio.cash_balance -= margin_required;
+        portfolio.margin_used += margin_required;
+        portfolio.recalculate();
+
+        self.sqlite.create_option_position(&position)?;
+        self.sqlite.update_portfolio(&portfolio)?;
+        self.portfolios.insert(portfolio.id.clone(), portfolio);
+
+        debug!(
+            "Wrote {} {} contracts, reserved {:.2} margin",
+            contracts, position.contract_symbol, margin_required
+        );
+        Ok(position)
+    }
+
+    /// Close an option position at the given premium, releasing its margin.
+    pub fn close_option_position(
+        &self,
+        position_id: &str,
+        premium: f64,
+    ) -> Result<OptionPosition, TradingError> {
+        let mut position = self
+            .sqlite
+            .get_option_position(position_id)
+            .ok_or_else(|| TradingError::PositionNotFound(position_id.to_string()))?;
+        let mut portfolio = self
+            .get_portfolio(&position.portfolio_id)
+            .ok_or_else(|| TradingError::PortfolioNotFound(position.portfolio_id.clone()))?;
+
+        let close_value = position.contracts as f64 * premium * position.multiplier as f64;
+        let realized_pnl = close_value - position.entry_cost();
+
+        // Release the margin, then buy back shorts or sell longs
+        portfolio.cash_balance += position.margin_used;
+        portfolio.margin_used = (portfolio.margin_used - position.margin_used).max(0.0);
+        portfolio.cash_balance += close_value;
+        portfolio.realized_pnl += realized_pnl;
+        portfolio.recalculate();
+
+        position.current_premium = premium;
+        position.realized_pnl += realized_pnl;
+        position.unrealized_pnl = 0.0;
+        position.margin_used = 0.0;
+        position.updated_at = chrono::Utc::now().timestamp_millis();
+
+        self.sqlite.update_option_position(&position)?;
+        self.sqlite.close_option_position(position_id)?;
+        self.sqlite.update_portfolio(&portfolio)?;
+        self.portfolios.insert(portfolio.id.clone(), portfolio);
+
+        debug!("Closed option position {} (P&L {:.2})", position_id, realized_pnl);
+        Ok(position)
+    }
+
     // ==========================================================================
     // Trade History
     // ==========================================================================
@@ -1681,7 +2493,7 @@ impl TradingService {
                     false
                 }
             }
-            OrderType::StopLoss => {
+            OrderType::StopLoss | OrderType::TrailingStop => {
                 if let Some(stop_price) = order.stop_price {
                     match order.side {
                         OrderSide::Sell => price <= stop_price, // Long position stop
@@ -1810,6 +2622,7 @@ impl TradingService {
             .map(|entry| entry.key().clone())
             .collect();
 
+        let mut updated_positions = Vec::new();
         for position_id in positions_to_update {
             if let Some(mut position) = self.positions.get_mut(&position_id) {
                 position.update_price(new_price);
@@ -1824,9 +2637,14 @@ impl TradingService {
 
                 // Broadcast position P&L change
                 self.broadcast_position_update(&position, PositionUpdateType::PnlChanged);
+                updated_positions.push(position.clone());
             }
         }
 
+        for position in &updated_positions {
+            self.check_margin_call(position);
+        }
+
         // Recalculate P&L for all affected portfolios
         for portfolio_id in affected_portfolios {
             if let Err(e) = self.recalculate_portfolio_pnl(&portfolio_id) {
@@ -1838,6 +2656,76 @@ impl TradingService {
         updated_count
     }
 
+    /// Notify the owner when a leveraged position enters margin call.
+    ///
+    /// Fires once when the margin ratio crosses [`MARGIN_CALL_RATIO`]; the
+    /// position must recover below it before another margin call is sent.
+    fn check_margin_call(&self, position: &Position) {
+        let Some(liquidation_price) = position.liquidation_price else {
+            return;
+        };
+
+        if position.margin_ratio() < MARGIN_CALL_RATIO {
+            if self.margin_calls.remove(&position.id).is_some() {
+                debug!("Position {} recovered from margin call", position.id);
+            }
+            return;
+        }
+        if !self.margin_calls.insert(position.id.clone()) {
+            return;
+        }
+
+        let Some(portfolio) = self.get_portfolio(&position.portfolio_id) else {
+            return;
+        };
+        let side = match position.side {
+            PositionSide::Long => "long",
+            PositionSide::Short => "short",
+        };
+        let notification = Notification::new(
+            portfolio.user_id,
+            NotificationType::Warning,
+            format!("Margin call: {} {}", position.symbol, side),
+            format!(
+                "Your {}x {} {} position ({}) is near liquidation. Margin level: {:.1}%, \
+                 current price: {:.2}, liquidation price: {:.2}.",
+                position.leverage,
+                position.symbol,
+                side,
+                position.id,
+                position.margin_level(),
+                position.current_price,
+                liquidation_price
+            ),
+        );
+
+        info!(
+            "Margin call for position {} ({} at {:.2}, liquidation {:.2})",
+            position.id, position.symbol, position.current_price, liquidation_price
+        );
+        match self.notifications.notify(&notification) {
+            Ok(_) => self.push_unread_count(&notification.user_id),
+            Err(e) => warn!("Failed to store margin call notification: {}", e),
+        }
+    }
+
+    /// Drop a closed or liquidated position from the caches, including its
+    /// margin call state.
+    fn forget_position(&self, position_id: &str) {
+        self.positions.remove(position_id);
+        self.margin_calls.remove(position_id);
+    }
+
+    /// Send a user's current unread notification count to their WebSocket
+    /// subscribers.
+    fn push_unread_count(&self, user_id: &str) {
+        if let Some(ref room_manager) = self.room_manager {
+            let unread_count = self.sqlite.unread_notification_count(user_id);
+            let message = crate::api::notifications::unread_count_message(user_id, unread_count);
+            room_manager.broadcast_notifications(user_id, &message);
+        }
+    }
+
     /// Execute a pending market order immediately at the given price.
     /// This is a convenience method for testing and simulation.
     pub fn auto_fill_market_order(
@@ -1890,6 +2778,7 @@ impl TradingService {
         for &price in price_points {
             self.update_positions_for_symbol(symbol, price);
             // Also check for triggered orders and position triggers
+            self.update_trailing_stops(symbol, price);
             let _ = self.check_triggered_orders(symbol, price, None);
             let _ = self.check_position_triggers(symbol, price);
         }
@@ -1898,6 +2787,259 @@ impl TradingService {
             .ok_or_else(|| TradingError::PortfolioNotFound(portfolio_id.to_string()))
     }
 
+    // ==========================================================================
+    // Execution Algorithms (TWAP / VWAP)
+    // ==========================================================================
+
+    /// Start working a large order as child market orders released over time.
+    ///
+    /// TWAP releases equal slices at a fixed interval. VWAP sizes each slice
+    /// by the volume traded in the same window a day earlier, falling back
+    /// to equal slices when there is no volume history.
+    pub fn start_execution(
+        &self,
+        request: ExecutionAlgoRequest,
+    ) -> Result<ExecutionSchedule, TradingError> {
+        let portfolio = self
+            .get_portfolio(&request.portfolio_id)
+            .ok_or_else(|| TradingError::PortfolioNotFound(request.portfolio_id.clone()))?;
+        if portfolio.is_stopped() {
+            return Err(TradingError::PortfolioStopped);
+        }
+
+        if request.quantity <= 0.0 {
+            return Err(TradingError::InvalidOrder("Quantity must be positive".to_string()));
+        }
+        if request.slices == 0 || request.slices > MAX_EXECUTION_SLICES {
+            return Err(TradingError::InvalidOrder(format!(
+                "Slices must be between 1 and {}",
+                MAX_EXECUTION_SLICES
+            )));
+        }
+        if request.duration_secs == 0 || request.duration_secs > MAX_EXECUTION_DURATION_SECS {
+            return Err(TradingError::InvalidOrder(format!(
+                "Duration must be between 1 and {} seconds",
+                MAX_EXECUTION_DURATION_SECS
+            )));
+        }
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let count = request.slices as usize;
+        let weights = match request.algo {
+            ExecutionAlgo::Twap => vec![1.0; count],
+            ExecutionAlgo::Vwap => self.vwap_weights(&request, now),
+        };
+        let schedule = ExecutionSchedule::new(&request, &weights, now);
+        self.sqlite.create_execution_schedule(&schedule)?;
+
+        info!(
+            "Started {} execution {} for {} {} {} over {} slices",
+            schedule.algo,
+            schedule.id,
+            schedule.side,
+            schedule.quantity,
+            schedule.symbol,
+            schedule.slices.len()
+        );
+        Ok(schedule)
+    }
+
+    /// Slice weights for a VWAP schedule: the volume traded in each slice's
+    /// window one day before it runs.
+    fn vwap_weights(&self, request: &ExecutionAlgoRequest, now: i64) -> Vec<f64> {
+        let count = request.slices as usize;
+        let Some(chart_store) = &self.chart_store else {
+            return vec![0.0; count];
+        };
+        let candles = chart_store.get_chart(&request.symbol, ChartRange::OneDay);
+        let slice_secs = (request.duration_secs / count as u64) as i64;
+        volume_profile(
+            &candles,
+            ChartResolution::FiveMinute.seconds(),
+            now / 1000 - 86_400,
+            slice_secs,
+            count,
+        )
+    }
+
+    /// Get an execution schedule by ID.
+    pub fn get_execution(&self, execution_id: &str) -> Option<ExecutionSchedule> {
+        self.sqlite.get_execution_schedule(execution_id)
+    }
+
+    /// Get a portfolio's execution schedules, newest first.
+    pub fn get_portfolio_executions(&self, portfolio_id: &str) -> Vec<ExecutionSchedule> {
+        self.sqlite.get_portfolio_execution_schedules(portfolio_id)
+    }
+
+    /// Cancel the unreleased remainder of an execution schedule.
+    ///
+    /// Child orders that have filled are kept; released children still
+    /// waiting for a price are cancelled along with the schedule.
+    pub fn cancel_execution(&self, execution_id: &str) -> Result<ExecutionSchedule, TradingError> {
+        let mut schedule = self
+            .get_execution(execution_id)
+            .ok_or_else(|| TradingError::ExecutionNotFound(execution_id.to_string()))?;
+
+        if schedule.status != ExecutionStatus::Active {
+            return Err(TradingError::CannotCancelOrder(schedule.status.to_string()));
+        }
+
+        for order_id in schedule.slices.iter().filter_map(|s| s.order_id.as_deref()) {
+            if self.get_order(order_id).is_some_and(|o| o.can_cancel()) {
+                self.cancel_order(order_id)?;
+            }
+        }
+
+        schedule.status = ExecutionStatus::Cancelled;
+        schedule.updated_at = chrono::Utc::now().timestamp_millis();
+        self.sqlite.update_execution_schedule(&schedule)?;
+
+        info!(
+            "Cancelled execution {} with {} of {} released",
+            schedule.id,
+            schedule.released_quantity(),
+            schedule.quantity
+        );
+        Ok(schedule)
+    }
+
+    /// Release every due slice of the active execution schedules as a market
+    /// order, filling it at the current price when one is available.
+    ///
+    /// Returns the number of child orders released.
+    pub fn process_execution_schedules<F>(&self, now: i64, get_price: F) -> usize
+    where
+        F: Fn(&str) -> Option<f64>,
+    {
+        self.sqlite
+            .get_active_execution_schedules()
+            .into_iter()
+            .map(|mut schedule| self.release_due_slices(&mut schedule, now, &get_price))
+            .sum()
+    }
+
+    /// Release a schedule's due slices and persist its progress.
+    fn release_due_slices<F>(
+        &self,
+        schedule: &mut ExecutionSchedule,
+        now: i64,
+        get_price: &F,
+    ) -> usize
+    where
+        F: Fn(&str) -> Option<f64>,
+    {
+        let mut released = 0;
+        for index in 0..schedule.slices.len() {
+            let slice = &schedule.slices[index];
+            if slice.order_id.is_some() || slice.release_at > now {
+                continue;
+            }
+
+            // Placement is idempotent on the client order ID, so a slice
+            // whose progress was not saved is not placed twice
+            let request = PlaceOrderRequest {
+                portfolio_id: schedule.portfolio_id.clone(),
+                symbol: schedule.symbol.clone(),
+                asset_class: schedule.asset_class,
+                side: schedule.side,
+                order_type: OrderType::Market,
+                quantity: slice.quantity,
+                price: None,
+                stop_price: None,
+                trail_amount: None,
+                trail_percent: None,
+                time_in_force: None,
+                leverage: Some(schedule.leverage),
+                stop_loss: None,
+                take_profit: None,
+                client_order_id: Some(schedule.child_client_order_id(index)),
+                bypass_drawdown: false,
+                display_quantity: None,
+            };
+
+            let order = match self.place_order(request) {
+                Ok(order) => order,
+                Err(e) => {
+                    warn!(
+                        "Stopping execution {}: slice {} could not be placed: {}",
+                        schedule.id, index, e
+                    );
+                    schedule.status = ExecutionStatus::Cancelled;
+                    break;
+                }
+            };
+
+            // Children left pending are filled by the next market tick
+            if order.status == OrderStatus::Pending {
+                if let Some(price) = get_price(&schedule.symbol) {
+                    if let Err(e) = self.execute_market_order(&order.id, price, None) {
+                        warn!("Failed to fill execution child order {}: {}", order.id, e);
+                    }
+                }
+            }
+
+            schedule.slices[index].order_id = Some(order.id);
+            released += 1;
+        }
+
+        if schedule.status == ExecutionStatus::Active
+            && schedule.slices.iter().all(|s| s.order_id.is_some())
+        {
+            schedule.status = ExecutionStatus::Completed;
+            info!("Execution {} completed", schedule.id);
+        }
+
+        if released > 0 || schedule.status != ExecutionStatus::Active {
+            schedule.updated_at = now;
+            if let Err(e) = self.sqlite.update_execution_schedule(schedule) {
+                warn!("Failed to save execution {}: {}", schedule.id, e);
+            }
+        }
+        released
+    }
+
+    // ==========================================================================
+    // Strategy Templates
+    // ==========================================================================
+
+    /// Create and save a strategy from one of the ready-made templates.
+    pub fn create_strategy_from_template(
+        &self,
+        request: StrategyFromTemplateRequest,
+    ) -> Result<TradingStrategy, TradingError> {
+        self.get_portfolio(&request.portfolio_id)
+            .ok_or_else(|| TradingError::PortfolioNotFound(request.portfolio_id.clone()))?;
+        let template = strategy_engine::template(&request.template_id)
+            .ok_or_else(|| TradingError::StrategyTemplateNotFound(request.template_id.clone()))?;
+
+        let symbols: Vec<String> = request
+            .symbols
+            .iter()
+            .map(|s| s.trim().to_uppercase())
+            .filter(|s| !s.is_empty())
+            .collect();
+        if symbols.is_empty() {
+            return Err(TradingError::InvalidStrategy(
+                "At least one symbol is required".to_string(),
+            ));
+        }
+
+        let mut strategy = template.instantiate(&request.portfolio_id, symbols);
+        if request.activate {
+            strategy.activate();
+        }
+        self.sqlite
+            .create_strategy(&strategy)
+            .map_err(|e| TradingError::DatabaseError(e.to_string()))?;
+
+        info!(
+            "Created strategy {} from template {} for portfolio {}",
+            strategy.id, template.id, strategy.portfolio_id
+        );
+        Ok(strategy)
+    }
+
     // ==========================================================================
     // Market Simulation Engine
     // ==========================================================================
@@ -1907,7 +3049,7 @@ impl TradingService {
     ///
     /// It will:
     /// 1. Update all positions for the symbol with the new price
-    /// 2. Check and execute any triggered limit/stop orders
+    /// 2. Ratchet trailing stops, then check and execute any triggered limit/stop orders
     /// 3. Check position triggers (stop loss, take profit, liquidation)
     ///
     /// Returns (positions_updated, orders_triggered, positions_closed)
@@ -1919,7 +3061,10 @@ impl TradingService {
         // 1. Update all positions with new price
         let positions_updated = self.update_positions_for_symbol(symbol, current_price);
 
-        // 2. Check and execute triggered orders (limit, stop loss, take profit, trailing stop)
+        // 2. Move trailing stops with the new price before checking triggers
+        self.update_trailing_stops(symbol, current_price);
+
+        // Check and execute triggered orders (limit, stop loss, take profit, trailing stop)
         let order_results = self.check_triggered_orders(symbol, current_price, None);
         let orders_triggered = order_results.iter().filter(|r| r.is_ok()).count();
 
@@ -2048,10 +3193,43 @@ impl TradingService {
     }
 }
 
+/// Volume traded in each of `count` consecutive `slice_secs` windows starting
+/// at `start_secs`. Each candle's volume is spread evenly over its
+/// `bucket_secs` span, so windows shorter than a candle still get a share.
+fn volume_profile(
+    candles: &[OhlcPoint],
+    bucket_secs: i64,
+    start_secs: i64,
+    slice_secs: i64,
+    count: usize,
+) -> Vec<f64> {
+    let mut weights = vec![0.0; count];
+    if bucket_secs <= 0 || slice_secs <= 0 {
+        return weights;
+    }
+    for candle in candles {
+        let Some(volume) = candle.volume else {
+            continue;
+        };
+        let (bucket_start, bucket_end) = (candle.time, candle.time + bucket_secs);
+        for (i, weight) in weights.iter_mut().enumerate() {
+            let window_start = start_secs + slice_secs * i as i64;
+            let window_end = window_start + slice_secs;
+            let overlap = bucket_end.min(window_end) - bucket_start.max(window_start);
+            if overlap > 0 {
+                *weight += volume * overlap as f64 / bucket_secs as f64;
+            }
+        }
+    }
+    weights
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::AssetClass;
+    use crate::types::{
+        AssetClass, FundingPayment, OptionStyle, OptionType, StrategyStatus, TradingStrategy,
+    };
 
     fn create_test_service() -> TradingService {
         let sqlite = Arc::new(SqliteStore::new_in_memory().unwrap());
@@ -2075,6 +3253,82 @@ mod tests {
         assert_eq!(loaded.id, portfolio.id);
     }
 
+    #[test]
+    fn test_position_funding_total() {
+        let service = create_test_service();
+
+        let portfolio = service
+            .create_portfolio("user123", "Perps", None, None)
+            .unwrap();
+        let position = Position::new(
+            portfolio.id.clone(),
+            "BTC-PERP".to_string(),
+            AssetClass::Perp,
+            PositionSide::Long,
+            2.0,
+            50000.0,
+            5.0,
+        );
+        service.sqlite.create_position(&position).unwrap();
+
+        // Longs pay positive rates and receive negative ones
+        let rates = [0.0001, 0.0003, -0.0002, 0.0001];
+        for (i, rate) in rates.iter().enumerate() {
+            let mut payment = FundingPayment::new(
+                position.id.clone(),
+                portfolio.id.clone(),
+                position.symbol.clone(),
+                100000.0,
+                PositionSide::Long,
+                *rate,
+            );
+            payment.paid_at = 1_000_000 + i as i64 * 28_800_000;
+            service.sqlite.create_funding_payment(&payment).unwrap();
+        }
+
+        let funding = service.get_position_funding(&position.id).unwrap();
+        assert_eq!(funding.payments.len(), rates.len());
+        assert!((funding.total_funding - 30.0).abs() < 1e-9);
+        assert_eq!(
+            funding.total_funding,
+            funding.payments.iter().map(|p| p.payment).sum::<f64>()
+        );
+
+        // A second position settled in the same window, stamped a moment later
+        let short = Position::new(
+            portfolio.id.clone(),
+            "BTC-PERP".to_string(),
+            AssetClass::Perp,
+            PositionSide::Short,
+            1.0,
+            50000.0,
+            5.0,
+        );
+        service.sqlite.create_position(&short).unwrap();
+        let mut other = FundingPayment::new(
+            short.id.clone(),
+            portfolio.id.clone(),
+            short.symbol.clone(),
+            50000.0,
+            PositionSide::Short,
+            0.0001,
+        );
+        other.paid_at = 1_000_000 + 3 * 28_800_000 + 250;
+        service.sqlite.create_funding_payment(&other).unwrap();
+
+        let history = service.get_funding_rate_history("BTC-PERP", 3);
+        let applied: Vec<f64> = history.rates.iter().map(|r| r.rate).collect();
+        assert_eq!(applied, vec![0.0001, -0.0002, 0.0003]);
+        let settlements: Vec<i64> = history.rates.iter().map(|r| r.timestamp).collect();
+        assert_eq!(settlements, vec![86_400_000, 57_600_000, 28_800_000]);
+        assert_eq!(service.get_funding_rate_history("BTC-PERP", 0).rates.len(), 1);
+
+        assert!(matches!(
+            service.get_position_funding("missing"),
+            Err(TradingError::PositionNotFound(_))
+        ));
+    }
+
     #[test]
     fn test_place_market_order() {
         let service = create_test_service();
@@ -2100,6 +3354,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
 
         let order = service.place_order(request).unwrap();
@@ -2109,6 +3364,55 @@ mod tests {
         assert_eq!(order.status, OrderStatus::Pending);
     }
 
+    #[test]
+    fn test_place_order_dedups_client_order_id() {
+        let service = create_test_service();
+
+        let portfolio = service
+            .create_portfolio("user123", "Trading", None, None)
+            .unwrap();
+
+        let request = |client_order_id: Option<&str>| PlaceOrderRequest {
+            portfolio_id: portfolio.id.clone(),
+            symbol: "BTC".to_string(),
+            asset_class: AssetClass::CryptoSpot,
+            side: OrderSide::Buy,
+            order_type: OrderType::Market,
+            quantity: 1.0,
+            price: None,
+            stop_price: None,
+            trail_amount: None,
+            trail_percent: None,
+            time_in_force: None,
+            leverage: None,
+            stop_loss: None,
+            take_profit: None,
+            client_order_id: client_order_id.map(String::from),
+            bypass_drawdown: false,
+            display_quantity: None,
+        };
+
+        // A retry with the same client id returns the original order
+        let first = service.place_order(request(Some("retry-1"))).unwrap();
+        let second = service.place_order(request(Some("retry-1"))).unwrap();
+        assert_eq!(second.id, first.id);
+        assert_eq!(service.get_order_history(&portfolio.id, 10).len(), 1);
+
+        // Orders without a client id are never deduplicated
+        service.place_order(request(None)).unwrap();
+        service.place_order(request(None)).unwrap();
+        assert_eq!(service.get_order_history(&portfolio.id, 10).len(), 3);
+
+        // The same client id is independent across portfolios
+        let other = service
+            .create_portfolio("user456", "Other", None, None)
+            .unwrap();
+        let mut other_request = request(Some("retry-1"));
+        other_request.portfolio_id = other.id.clone();
+        let other_order = service.place_order(other_request).unwrap();
+        assert_ne!(other_order.id, first.id);
+    }
+
     #[test]
     fn test_execute_market_order() {
         let service = create_test_service();
@@ -2134,6 +3438,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
 
         let order = service.place_order(request).unwrap();
@@ -2180,6 +3485,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
 
         let order = service.place_order(request).unwrap();
@@ -2229,6 +3535,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
 
         let order = service.place_order(request).unwrap();
@@ -2242,6 +3549,237 @@ mod tests {
         assert!((positions[0].margin_used - 5000.0).abs() < 100.0);
     }
 
+    /// Open a 10x BTC perp long and return the highest price at which it is in margin call.
+    fn open_leveraged_long(service: &TradingService, user_id: &str) -> (Position, f64) {
+        let portfolio = service.create_portfolio(user_id, "Perps", None, None).unwrap();
+        let request = PlaceOrderRequest {
+            portfolio_id: portfolio.id.clone(),
+            symbol: "BTC".to_string(),
+            asset_class: AssetClass::Perp,
+            side: OrderSide::Buy,
+            order_type: OrderType::Market,
+            quantity: 0.5,
+            price: None,
+            stop_price: None,
+            trail_amount: None,
+            trail_percent: None,
+            time_in_force: None,
+            leverage: Some(10.0),
+            stop_loss: None,
+            take_profit: None,
+            client_order_id: None,
+            bypass_drawdown: false,
+            display_quantity: None,
+        };
+        service.place_and_fill_market_order(request, 50000.0).unwrap();
+        let position = service.get_positions(&portfolio.id).remove(0);
+        let liquidation_price = position.liquidation_price.unwrap();
+
+        let mut probe = position.clone();
+        let mut price = position.entry_price;
+        loop {
+            probe.update_price(price);
+            if probe.margin_ratio() >= MARGIN_CALL_RATIO {
+                break;
+            }
+            price -= 1.0;
+        }
+        assert!(price > liquidation_price, "margin call zone must precede liquidation");
+        (position, price)
+    }
+
+    fn margin_calls(service: &TradingService, user_id: &str) -> Vec<Notification> {
+        service
+            .sqlite
+            .get_notifications(user_id, false, 100)
+            .into_iter()
+            .filter(|n| n.notification_type == NotificationType::Warning)
+            .collect()
+    }
+
+    #[test]
+    fn test_margin_call_notifies_once_per_crossing() {
+        let service = create_test_service();
+        let (position, call_price) = open_leveraged_long(&service, "trader1");
+
+        service.process_symbol_tick("BTC", position.entry_price);
+        assert!(margin_calls(&service, "trader1").is_empty());
+
+        // Crossing into margin call, then staying there
+        service.process_symbol_tick("BTC", call_price);
+        let deeper = (call_price + position.liquidation_price.unwrap()) / 2.0;
+        service.process_symbol_tick("BTC", deeper);
+        service.process_symbol_tick("BTC", call_price);
+        let calls = margin_calls(&service, "trader1");
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].title.contains("BTC"));
+        assert!(calls[0].message.contains(&position.id));
+        assert!(calls[0].message.contains(&format!(
+            "liquidation price: {:.2}",
+            position.liquidation_price.unwrap()
+        )));
+
+        // Recover, then cross again
+        service.process_symbol_tick("BTC", position.entry_price);
+        assert_eq!(margin_calls(&service, "trader1").len(), 1);
+        service.process_symbol_tick("BTC", call_price);
+        assert_eq!(margin_calls(&service, "trader1").len(), 2);
+    }
+
+    #[test]
+    fn test_margin_call_pushes_unread_count() {
+        let sqlite = Arc::new(SqliteStore::new_in_memory().unwrap());
+        let room_manager = RoomManager::new();
+        let service = TradingService::with_room_manager(sqlite, room_manager.clone());
+        let (_, call_price) = open_leveraged_long(&service, "trader1");
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let client = room_manager.register(tx);
+        room_manager.subscribe_notifications(client, "trader1");
+
+        service.process_symbol_tick("BTC", call_price);
+
+        let mut unread_counts = Vec::new();
+        while let Ok(msg) = rx.try_recv() {
+            let value: serde_json::Value = serde_json::from_str(msg.json()).unwrap();
+            if value["type"] == "unread_count" {
+                unread_counts.push(value["data"]["unreadCount"].as_u64().unwrap());
+            }
+        }
+        assert_eq!(unread_counts, vec![1]);
+    }
+
+    #[test]
+    fn test_margin_call_state_cleared_on_close_and_liquidation() {
+        let service = create_test_service();
+
+        let (position, call_price) = open_leveraged_long(&service, "trader1");
+        service.process_symbol_tick("BTC", call_price);
+        assert!(service.margin_calls.contains(&position.id));
+        service.close_position(&position.id, call_price).unwrap();
+        assert!(!service.margin_calls.contains(&position.id));
+
+        let (position, call_price) = open_leveraged_long(&service, "trader2");
+        service.process_symbol_tick("BTC", call_price);
+        assert!(service.margin_calls.contains(&position.id));
+        let (_, _, closed) =
+            service.process_symbol_tick("BTC", position.liquidation_price.unwrap() - 1.0);
+        assert_eq!(closed, 1);
+        assert!(!service.margin_calls.contains(&position.id));
+    }
+
+    #[test]
+    fn test_max_drawdown_pauses_active_strategies() {
+        let service = create_test_service();
+        let risk_settings = RiskSettings {
+            max_drawdown_pct: Some(0.10),
+            ..Default::default()
+        };
+        let portfolio = service
+            .create_portfolio("trader3", "Auto", None, Some(risk_settings))
+            .unwrap();
+
+        let mut strategy_ids = Vec::new();
+        for name in ["Momentum", "Mean Reversion"] {
+            let mut strategy =
+                TradingStrategy::new(portfolio.id.clone(), name.to_string(), vec!["BTC".into()]);
+            strategy.activate();
+            service.sqlite.create_strategy(&strategy).unwrap();
+            strategy_ids.push(strategy.id);
+        }
+
+        // $200k of BTC in a $250k portfolio
+        let request =
+            concentration_request(&portfolio.id, OrderSide::Buy, OrderType::Market, 4.0, None);
+        service.place_and_fill_market_order(request, 50000.0).unwrap();
+
+        // An 8% drawdown stays within the limit
+        service.process_symbol_tick("BTC", 45000.0);
+        let current = service.get_portfolio(&portfolio.id).unwrap();
+        assert!(service.enforce_max_drawdown(&current).is_empty());
+        assert_eq!(service.sqlite.get_active_strategies(&portfolio.id).len(), 2);
+
+        // A 16% drawdown trips the breaker on the next snapshot
+        service.process_symbol_tick("BTC", 40000.0);
+        service.take_portfolio_snapshot(&portfolio.id).unwrap();
+        for id in &strategy_ids {
+            let strategy = service.sqlite.get_strategy(id).unwrap();
+            assert_eq!(strategy.status, StrategyStatus::Paused);
+        }
+        let alerts = service.sqlite.get_notifications("trader3", false, 100);
+        assert_eq!(alerts.len(), 1);
+        assert!(alerts[0].title.contains("Strategies paused"));
+
+        // Recovery does not reactivate them, and no further alert is sent
+        service.process_symbol_tick("BTC", 50000.0);
+        service.take_portfolio_snapshot(&portfolio.id).unwrap();
+        assert!(service.sqlite.get_active_strategies(&portfolio.id).is_empty());
+        assert_eq!(service.sqlite.get_notifications("trader3", false, 100).len(), 1);
+    }
+
+    #[test]
+    fn test_create_strategy_from_template() {
+        let service = create_test_service();
+        let portfolio = service
+            .create_portfolio("trader-templates", "Templates", None, None)
+            .unwrap();
+        let request = |template_id: &str, symbols: Vec<&str>| StrategyFromTemplateRequest {
+            template_id: template_id.to_string(),
+            portfolio_id: portfolio.id.clone(),
+            symbols: symbols.into_iter().map(String::from).collect(),
+            activate: true,
+        };
+
+        assert!(matches!(
+            service.create_strategy_from_template(request("no_such_template", vec!["btc"])),
+            Err(TradingError::StrategyTemplateNotFound(_))
+        ));
+        assert!(matches!(
+            service.create_strategy_from_template(request("macd_trend", vec![" "])),
+            Err(TradingError::InvalidStrategy(_))
+        ));
+
+        let strategy = service
+            .create_strategy_from_template(request("macd_trend", vec!["btc", "eth"]))
+            .unwrap();
+        assert_eq!(strategy.symbols, vec!["BTC".to_string(), "ETH".to_string()]);
+        assert_eq!(strategy.status, StrategyStatus::Active);
+
+        let stored = service.sqlite.get_strategy(&strategy.id).unwrap();
+        assert_eq!(stored.name, "MACD Trend");
+        assert_eq!(stored.rules.len(), 2);
+        assert_eq!(service.sqlite.get_active_strategies(&portfolio.id).len(), 1);
+    }
+
+    #[test]
+    fn test_unleveraged_position_never_margin_called() {
+        let service = create_test_service();
+        let portfolio = service.create_portfolio("trader2", "Spot", None, None).unwrap();
+        let request = PlaceOrderRequest {
+            portfolio_id: portfolio.id.clone(),
+            symbol: "ETH".to_string(),
+            asset_class: AssetClass::CryptoSpot,
+            side: OrderSide::Buy,
+            order_type: OrderType::Market,
+            quantity: 1.0,
+            price: None,
+            stop_price: None,
+            trail_amount: None,
+            trail_percent: None,
+            time_in_force: None,
+            leverage: None,
+            stop_loss: None,
+            take_profit: None,
+            client_order_id: None,
+            bypass_drawdown: false,
+            display_quantity: None,
+        };
+        service.place_and_fill_market_order(request, 3000.0).unwrap();
+
+        service.process_symbol_tick("ETH", 100.0);
+        assert!(margin_calls(&service, "trader2").is_empty());
+    }
+
     #[test]
     fn test_cancel_order() {
         let service = create_test_service();
@@ -2267,6 +3805,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
 
         let order = service.place_order(request).unwrap();
@@ -2301,6 +3840,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
 
         let order = service.place_order(request).unwrap();
@@ -2342,6 +3882,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
 
         let result = service.place_order(request);
@@ -2373,6 +3914,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
 
         let result = service.place_order(request);
@@ -2454,6 +3996,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_order(request).unwrap();
 
@@ -2461,6 +4004,92 @@ mod tests {
         assert_eq!(leaderboard.len(), 2);
     }
 
+    fn record_trade(service: &TradingService, portfolio_id: &str, pnl: f64, age_ms: i64) {
+        let order = Order::market(
+            portfolio_id.to_string(),
+            "BTC".to_string(),
+            AssetClass::CryptoSpot,
+            OrderSide::Sell,
+            1.0,
+        );
+        service.sqlite.create_order(&order).unwrap();
+        let mut trade = Trade::new(
+            order.id.clone(),
+            portfolio_id.to_string(),
+            "BTC".to_string(),
+            AssetClass::CryptoSpot,
+            OrderSide::Sell,
+            1.0,
+            50000.0,
+            0.0,
+            0.0,
+        );
+        trade.realized_pnl = Some(pnl);
+        trade.executed_at = chrono::Utc::now().timestamp_millis() - age_ms;
+        service.sqlite.create_trade(&trade).unwrap();
+    }
+
+    #[test]
+    fn test_windowed_leaderboard_rankings() {
+        const HOUR: i64 = 3_600_000;
+        let service = create_test_service();
+        let a = service.create_portfolio("bot_a", "A", None, None).unwrap();
+        let b = service.create_portfolio("bot_b", "B", None, None).unwrap();
+        let c = service.create_portfolio("bot_c", "C", None, None).unwrap();
+
+        record_trade(&service, &a.id, 1000.0, HOUR);
+        record_trade(&service, &b.id, -500.0, 2 * HOUR);
+        record_trade(&service, &b.id, 3000.0, 72 * HOUR);
+        record_trade(&service, &c.id, 9000.0, 240 * HOUR);
+
+        let daily = service.get_windowed_leaderboard(LeaderboardWindow::Daily, 10);
+        let ids: Vec<&str> = daily.iter().map(|e| e.portfolio_id.as_str()).collect();
+        assert_eq!(ids, vec![a.id.as_str(), b.id.as_str()]);
+        assert_eq!(daily[0].realized_pnl, 1000.0);
+        assert_eq!(daily[1].realized_pnl, -500.0);
+        assert_eq!(daily[1].total_trades, 1);
+
+        let weekly = service.get_windowed_leaderboard(LeaderboardWindow::Weekly, 10);
+        let ids: Vec<&str> = weekly.iter().map(|e| e.portfolio_id.as_str()).collect();
+        assert_eq!(ids, vec![b.id.as_str(), a.id.as_str()]);
+        assert_eq!(weekly[0].realized_pnl, 2500.0);
+        assert_eq!(weekly[0].total_trades, 2);
+        assert_eq!(weekly[0].winning_trades, 1);
+        assert!(
+            (weekly[0].total_return_pct - 2500.0 / b.starting_balance * 100.0).abs() < 1e-9
+        );
+
+        // All-time uses lifetime portfolio stats and lists every portfolio
+        let all_time = service.get_windowed_leaderboard(LeaderboardWindow::AllTime, 10);
+        assert_eq!(all_time.len(), 3);
+
+        assert_eq!(
+            service
+                .get_windowed_leaderboard(LeaderboardWindow::Weekly, 1)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_windowed_leaderboard_is_cached() {
+        let service = create_test_service();
+        let a = service.create_portfolio("bot_a", "A", None, None).unwrap();
+        record_trade(&service, &a.id, 100.0, 0);
+
+        let first = service.get_windowed_leaderboard(LeaderboardWindow::Daily, 10);
+        assert_eq!(first[0].realized_pnl, 100.0);
+
+        // New trades don't show up until the cached board expires
+        record_trade(&service, &a.id, 100.0, 0);
+        let cached = service.get_windowed_leaderboard(LeaderboardWindow::Daily, 10);
+        assert_eq!(cached[0].realized_pnl, 100.0);
+
+        service.leaderboard_cache.clear();
+        let fresh = service.get_windowed_leaderboard(LeaderboardWindow::Daily, 10);
+        assert_eq!(fresh[0].realized_pnl, 200.0);
+    }
+
     #[test]
     fn test_get_all_portfolios() {
         let service = create_test_service();
@@ -2505,6 +4134,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         let (_, _) = service.place_and_fill_market_order(request, 50000.0).unwrap();
 
@@ -2555,6 +4185,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(request, 50000.0).unwrap();
 
@@ -2596,6 +4227,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(request, 50000.0).unwrap();
 
@@ -2650,6 +4282,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(request, 50000.0).unwrap();
 
@@ -2699,6 +4332,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(btc_request, 50000.0).unwrap();
 
@@ -2720,6 +4354,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(eth_request, 3000.0).unwrap();
 
@@ -2736,6 +4371,109 @@ mod tests {
             "Expected ~$10,000 total unrealized PnL, got {}", portfolio.unrealized_pnl);
     }
 
+    // ==========================================================================
+    // Concentration Limit Tests
+    // ==========================================================================
+
+    fn concentration_request(
+        portfolio_id: &str,
+        side: OrderSide,
+        order_type: OrderType,
+        quantity: f64,
+        price: Option<f64>,
+    ) -> PlaceOrderRequest {
+        PlaceOrderRequest {
+            portfolio_id: portfolio_id.to_string(),
+            symbol: "BTC".to_string(),
+            asset_class: AssetClass::CryptoSpot,
+            side,
+            order_type,
+            quantity,
+            price,
+            stop_price: None,
+            trail_amount: None,
+            trail_percent: None,
+            time_in_force: None,
+            leverage: None,
+            stop_loss: None,
+            take_profit: None,
+            client_order_id: None,
+            bypass_drawdown: false,
+            display_quantity: None,
+        }
+    }
+
+    /// A $250k portfolio capped at 40% per symbol, holding 1.5 BTC ($75k at $50k).
+    fn concentrated_portfolio(service: &TradingService) -> Portfolio {
+        let risk_settings = RiskSettings {
+            max_symbol_concentration_pct: Some(0.40),
+            ..Default::default()
+        };
+        let portfolio = service
+            .create_portfolio("user1", "Concentration Test", None, Some(risk_settings))
+            .unwrap();
+        let request =
+            concentration_request(&portfolio.id, OrderSide::Buy, OrderType::Market, 1.5, None);
+        service.place_and_fill_market_order(request, 50000.0).unwrap();
+        portfolio
+    }
+
+    #[test]
+    fn test_concentration_limit_rejects_order_over_cap() {
+        let service = create_test_service();
+        let portfolio = concentrated_portfolio(&service);
+
+        // Another 0.6 BTC would be $105k, 42% of the portfolio
+        let request =
+            concentration_request(&portfolio.id, OrderSide::Buy, OrderType::Market, 0.6, None);
+        let result = service.place_and_fill_market_order(request, 50000.0);
+        assert!(matches!(
+            result,
+            Err(TradingError::ConcentrationLimitExceeded { ref symbol, max, .. })
+                if symbol == "BTC" && max == 0.40
+        ));
+
+        // The market order is rejected rather than left pending, and nothing filled
+        let history = service.get_order_history(&portfolio.id, 10);
+        assert!(history.iter().any(|o| o.status == OrderStatus::Rejected));
+        let positions = service.sqlite.get_portfolio_positions(&portfolio.id);
+        assert_eq!(positions.len(), 1);
+        assert!((positions[0].quantity - 1.5).abs() < 1e-9);
+
+        // Limit orders are checked when placed
+        let limit = concentration_request(
+            &portfolio.id,
+            OrderSide::Buy,
+            OrderType::Limit,
+            0.6,
+            Some(50000.0),
+        );
+        assert!(matches!(
+            service.place_order(limit),
+            Err(TradingError::ConcentrationLimitExceeded { .. })
+        ));
+    }
+
+    #[test]
+    fn test_concentration_limit_accepts_order_below_cap() {
+        let service = create_test_service();
+        let portfolio = concentrated_portfolio(&service);
+
+        // 0.49 BTC more is $99.5k, just under 40% of the portfolio
+        let request =
+            concentration_request(&portfolio.id, OrderSide::Buy, OrderType::Market, 0.49, None);
+        service.place_and_fill_market_order(request, 50000.0).unwrap();
+
+        let positions = service.sqlite.get_portfolio_positions(&portfolio.id);
+        assert!((positions[0].quantity - 1.99).abs() < 1e-9);
+
+        // Reducing exposure is allowed even above the cap
+        service.update_positions_for_symbol("BTC", 60000.0);
+        let request =
+            concentration_request(&portfolio.id, OrderSide::Sell, OrderType::Market, 0.5, None);
+        service.place_and_fill_market_order(request, 60000.0).unwrap();
+    }
+
     // ==========================================================================
     // Drawdown Protection Tests
     // ==========================================================================
@@ -2771,6 +4509,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(request, 50000.0).unwrap();
 
@@ -2817,6 +4556,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(request, 50000.0).unwrap();
 
@@ -2863,6 +4603,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(request, 50000.0).unwrap();
 
@@ -2890,6 +4631,7 @@ mod tests {
                 take_profit: None,
                 client_order_id: None,
                 bypass_drawdown: false,
+                display_quantity: None,
             };
 
             let result = service.place_order(new_request);
@@ -2926,6 +4668,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
 
         let result = service.place_and_fill_market_order(request, 50000.0);
@@ -2965,6 +4708,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(request, 50000.0).unwrap();
 
@@ -3003,6 +4747,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(request, 50000.0).unwrap();
 
@@ -3049,6 +4794,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(request, 50000.0).unwrap();
 
@@ -3096,6 +4842,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(request, 50000.0).unwrap();
 
@@ -3143,6 +4890,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(request, 50000.0).unwrap();
 
@@ -3184,6 +4932,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(request, 50000.0).unwrap();
 
@@ -3226,6 +4975,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(request, 50000.0).unwrap();
 
@@ -3280,6 +5030,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(request1, 50000.0).unwrap();
 
@@ -3301,6 +5052,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(request2, 55000.0).unwrap();
 
@@ -3343,6 +5095,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(request, 50000.0).unwrap();
 
@@ -3395,6 +5148,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         let entry_price = 40000.0;
         let (order, trade) = service.place_and_fill_market_order(request, entry_price).unwrap();
@@ -3454,6 +5208,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         let order = service.place_order(request).unwrap();
         assert_eq!(order.order_type, OrderType::Limit);
@@ -3484,6 +5239,111 @@ mod tests {
         }
     }
 
+    fn iceberg_request(portfolio_id: &str, order_type: OrderType, display: f64) -> PlaceOrderRequest {
+        PlaceOrderRequest {
+            portfolio_id: portfolio_id.to_string(),
+            symbol: "ETH".to_string(),
+            asset_class: AssetClass::CryptoSpot,
+            side: OrderSide::Buy,
+            order_type,
+            quantity: 100.0,
+            price: Some(100.0),
+            stop_price: None,
+            trail_amount: None,
+            trail_percent: None,
+            time_in_force: None,
+            leverage: None,
+            stop_loss: None,
+            take_profit: None,
+            client_order_id: None,
+            bypass_drawdown: false,
+            display_quantity: Some(display),
+        }
+    }
+
+    /// Test an iceberg order fills one visible slice per tick
+    #[test]
+    fn test_iceberg_order_fills_in_tranches() {
+        let service = create_test_service();
+        let portfolio = service
+            .create_portfolio("user1", "Iceberg Test", None, None)
+            .unwrap();
+
+        let order = service
+            .place_order(iceberg_request(&portfolio.id, OrderType::Limit, 10.0))
+            .unwrap();
+        assert!(order.is_iceberg());
+        assert_eq!(order.visible_quantity(), 10.0);
+
+        for tranche in 1..=10 {
+            let results = service.check_triggered_orders("ETH", 99.0, None);
+            assert_eq!(results.len(), 1, "tranche {}", tranche);
+            let trade = results.into_iter().next().unwrap().unwrap();
+            assert_eq!(trade.quantity, 10.0);
+
+            let order = service.get_order(&order.id).unwrap();
+            assert_eq!(order.filled_quantity, tranche as f64 * 10.0);
+            assert!(order.visible_quantity() <= 10.0);
+            let expected_status = if tranche < 10 {
+                OrderStatus::PartiallyFilled
+            } else {
+                OrderStatus::Filled
+            };
+            assert_eq!(order.status, expected_status);
+        }
+
+        // Fully filled: nothing left to trigger
+        assert!(service.check_triggered_orders("ETH", 99.0, None).is_empty());
+        let trades = service.get_order_trades(&order.id);
+        assert_eq!(trades.len(), 10);
+        assert!(trades.iter().all(|t| t.quantity == 10.0));
+
+        let positions = service.get_positions(&portfolio.id);
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].quantity, 100.0);
+
+        // Display size survives persistence
+        let stored = service.sqlite.get_order(&order.id).unwrap();
+        assert_eq!(stored.display_quantity, Some(10.0));
+    }
+
+    /// Test a cancelled iceberg leaves the hidden remainder unfilled
+    #[test]
+    fn test_iceberg_order_cancel_keeps_hidden_quantity() {
+        let service = create_test_service();
+        let portfolio = service
+            .create_portfolio("user1", "Iceberg Cancel Test", None, None)
+            .unwrap();
+
+        let order = service
+            .place_order(iceberg_request(&portfolio.id, OrderType::Limit, 30.0))
+            .unwrap();
+        service.check_triggered_orders("ETH", 99.0, None);
+        service.check_triggered_orders("ETH", 99.0, None);
+        service.cancel_order(&order.id).unwrap();
+
+        assert!(service.check_triggered_orders("ETH", 99.0, None).is_empty());
+        let order = service.get_order(&order.id).unwrap();
+        assert_eq!(order.filled_quantity, 60.0);
+        assert_eq!(service.get_positions(&portfolio.id)[0].quantity, 60.0);
+    }
+
+    #[test]
+    fn test_iceberg_order_validation() {
+        let service = create_test_service();
+        let portfolio = service
+            .create_portfolio("user1", "Iceberg Validation", None, None)
+            .unwrap();
+
+        let market = service.place_order(iceberg_request(&portfolio.id, OrderType::Market, 10.0));
+        assert!(matches!(market, Err(TradingError::InvalidOrder(_))));
+
+        for display in [0.0, -5.0, 150.0] {
+            let result = service.place_order(iceberg_request(&portfolio.id, OrderType::Limit, display));
+            assert!(matches!(result, Err(TradingError::InvalidOrder(_))), "{}", display);
+        }
+    }
+
     /// Test stop loss order triggers correctly and tracks PnL
     #[test]
     fn test_stop_loss_order_trigger_and_pnl() {
@@ -3510,6 +5370,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(entry_request, 50000.0).unwrap();
 
@@ -3569,6 +5430,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(entry_request, 50000.0).unwrap();
 
@@ -3628,6 +5490,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         let (order, _) = service.place_and_fill_market_order(request, 50000.0).unwrap();
         assert_eq!(order.order_type, OrderType::Market);
@@ -3688,6 +5551,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         let (order, _) = service.place_and_fill_market_order(request, 50000.0).unwrap();
 
@@ -3747,6 +5611,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(btc_request, 50000.0).unwrap();
 
@@ -3768,6 +5633,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(eth_request, 3000.0).unwrap();
 
@@ -3789,6 +5655,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(sol_request, 100.0).unwrap();
 
@@ -3897,6 +5764,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(entry_request, 50000.0).unwrap();
 
@@ -3936,6 +5804,135 @@ mod tests {
         }
     }
 
+    /// Open a 1 BTC long at 100 and protect it with a trailing sell stop.
+    fn place_trailing_stop(
+        service: &TradingService,
+        trail_amount: Option<f64>,
+        trail_percent: Option<f64>,
+    ) -> Order {
+        let portfolio = service
+            .create_portfolio("user1", "Trailing Stop Test", None, None)
+            .unwrap();
+        let request = |side, order_type| PlaceOrderRequest {
+            portfolio_id: portfolio.id.clone(),
+            symbol: "BTC".to_string(),
+            asset_class: AssetClass::CryptoSpot,
+            side,
+            order_type,
+            quantity: 1.0,
+            price: None,
+            stop_price: None,
+            trail_amount,
+            trail_percent,
+            time_in_force: None,
+            leverage: None,
+            stop_loss: None,
+            take_profit: None,
+            client_order_id: None,
+            bypass_drawdown: false,
+            display_quantity: None,
+        };
+        service
+            .place_and_fill_market_order(request(OrderSide::Buy, OrderType::Market), 100.0)
+            .unwrap();
+        service
+            .place_order(request(OrderSide::Sell, OrderType::TrailingStop))
+            .unwrap()
+    }
+
+    /// Drive `path` through the tick engine, checking the stop after each tick.
+    /// Returns the index of the tick that filled the order, if any.
+    fn drive_trailing_stop(
+        service: &TradingService,
+        order_id: &str,
+        path: &[(f64, f64)],
+    ) -> Option<usize> {
+        for (i, &(price, expected_stop)) in path.iter().enumerate() {
+            service.process_symbol_tick("BTC", price);
+            let order = service.get_order(order_id).unwrap();
+            if order.status == OrderStatus::Filled {
+                return Some(i);
+            }
+            let stop = order.stop_price.unwrap();
+            assert!(
+                (stop - expected_stop).abs() < 1e-9,
+                "at price {} expected stop {}, got {}",
+                price,
+                expected_stop,
+                stop
+            );
+        }
+        None
+    }
+
+    #[test]
+    fn test_trailing_stop_amount_ratchets_and_fires() {
+        let service = create_test_service();
+        let order = place_trailing_stop(&service, Some(5.0), None);
+
+        // Stop follows new highs by $5 and holds through pullbacks
+        let path = [
+            (100.0, 95.0),
+            (105.0, 100.0),
+            (110.0, 105.0),
+            (108.0, 105.0),
+            (112.0, 107.0),
+            (109.0, 107.0),
+            (107.5, 107.0),
+            (107.0, 107.0),
+        ];
+        assert_eq!(drive_trailing_stop(&service, &order.id, &path), Some(7));
+
+        let filled = service.get_order(&order.id).unwrap();
+        assert_eq!(filled.trail_high_price, Some(112.0));
+        assert_eq!(filled.stop_price, Some(107.0));
+    }
+
+    #[test]
+    fn test_trailing_stop_percent_ratchets_and_fires() {
+        let service = create_test_service();
+        let order = place_trailing_stop(&service, None, Some(10.0));
+
+        // Stop sits 10% under the highest price seen
+        let path = [
+            (100.0, 90.0),
+            (120.0, 108.0),
+            (110.0, 108.0),
+            (150.0, 135.0),
+            (140.0, 135.0),
+            (136.0, 135.0),
+            (134.0, 135.0),
+        ];
+        assert_eq!(drive_trailing_stop(&service, &order.id, &path), Some(6));
+
+        // The long was closed by the stop
+        assert!(service.get_positions(&order.portfolio_id).is_empty());
+    }
+
+    #[test]
+    fn test_trailing_stop_never_loosens() {
+        let mut order = Order::trailing_stop(
+            "p".to_string(),
+            "BTC".to_string(),
+            AssetClass::CryptoSpot,
+            OrderSide::Sell,
+            1.0,
+            None,
+            Some(10.0),
+            100.0,
+        );
+        assert_eq!(order.stop_price, Some(90.0));
+
+        // A manually tightened stop is kept when a new high implies a lower one
+        order.stop_price = Some(99.0);
+        assert!(order.update_trailing_stop(105.0));
+        assert_eq!(order.stop_price, Some(99.0));
+
+        // And retraces never move it
+        assert!(!order.update_trailing_stop(95.0));
+        assert_eq!(order.stop_price, Some(99.0));
+    }
+
     /// Test Forex order PnL with leverage
     #[test]
     fn test_forex_order_pnl_over_time() {
@@ -3962,6 +5959,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         let entry_rate = 1.1000;
         service.place_and_fill_market_order(request, entry_rate).unwrap();
@@ -4015,6 +6013,7 @@ mod tests {
             take_profit: None,
             client_order_id: None,
             bypass_drawdown: false,
+            display_quantity: None,
         };
         service.place_and_fill_market_order(request, 50000.0).unwrap();
 
@@ -4038,4 +6037,561 @@ mod tests {
         let positions = service.get_positions(&portfolio.id);
         assert!(positions.is_empty(), "All positions should be closed");
     }
+
+    fn create_call(underlying: &str, strike: f64) -> OptionContract {
+        let expiration = chrono::Utc::now().timestamp_millis() + 30 * 24 * 60 * 60 * 1000;
+        OptionContract::new(
+            underlying.to_string(),
+            OptionType::Call,
+            strike,
+            expiration,
+            OptionStyle::American,
+        )
+    }
+
+    #[test]
+    fn test_naked_short_call_margin() {
+        let service = create_test_service();
+        let portfolio = service
+            .create_portfolio("user123", "Options", None, None)
+            .unwrap();
+
+        // OTM call: 20% of 100 less 10 OTM = 10, at the 10% floor, plus 2 premium
+        let otm = service
+            .open_short_option(&portfolio.id, &create_call("AAPL", 110.0), 2, 2.0, 100.0)
+            .unwrap();
+        assert_eq!(otm.contracts, -2);
+        assert!((otm.margin_used - 2400.0).abs() < 1e-9);
+
+        // ITM call: full 20% of the underlying plus premium
+        let itm = service
+            .open_short_option(&portfolio.id, &create_call("AAPL", 90.0), 1, 12.0, 100.0)
+            .unwrap();
+        assert!((itm.margin_used - 3200.0).abs() < 1e-9);
+
+        // Far OTM call falls back to the 10% floor
+        let far = service
+            .open_short_option(&portfolio.id, &create_call("AAPL", 200.0), 1, 0.5, 100.0)
+            .unwrap();
+        assert!((far.margin_used - 1050.0).abs() < 1e-9);
+
+        let loaded = service.get_portfolio(&portfolio.id).unwrap();
+        assert!((loaded.margin_used - 6650.0).abs() < 1e-9);
+        // Premium credited, margin moved out of cash
+        assert!((loaded.cash_balance - (250_000.0 + 400.0 + 1200.0 + 50.0 - 6650.0)).abs() < 1e-9);
+
+        // Buying back the OTM call at a lower premium releases its margin
+        let closed = service.close_option_position(&otm.id, 0.5).unwrap();
+        assert!((closed.realized_pnl - 300.0).abs() < 1e-9);
+        assert!(service.sqlite.get_option_position(&otm.id).is_none());
+
+        let loaded = service.get_portfolio(&portfolio.id).unwrap();
+        assert!((loaded.margin_used - 4250.0).abs() < 1e-9);
+        assert!((loaded.realized_pnl - 300.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_short_option_insufficient_margin() {
+        let service = create_test_service();
+        let portfolio = service
+            .create_portfolio("user123", "Options", None, None)
+            .unwrap();
+
+        // 20% of 1000 plus 20 premium, on 100 shares per contract = 22,000 each
+        let contract = create_call("SPY", 1000.0);
+        let result = service.open_short_option(&portfolio.id, &contract, 12, 20.0, 1000.0);
+        assert!(matches!(result, Err(TradingError::InsufficientMargin { .. })));
+
+        // Nothing was reserved or persisted
+        let loaded = service.get_portfolio(&portfolio.id).unwrap();
+        assert_eq!(loaded.margin_used, 0.0);
+        assert_eq!(loaded.cash_balance, 250_000.0);
+        assert_eq!(service.sqlite.option_position_count(&portfolio.id), 0);
+
+        assert!(service
+            .open_short_option(&portfolio.id, &contract, 11, 20.0, 1000.0)
+            .is_ok());
+    }
+
+    fn market_order(portfolio_id: &str, side: OrderSide, quantity: f64) -> PlaceOrderRequest {
+        PlaceOrderRequest {
+            portfolio_id: portfolio_id.to_string(),
+            symbol: "ETH".to_string(),
+            asset_class: AssetClass::CryptoSpot,
+            side,
+            order_type: OrderType::Market,
+            quantity,
+            price: None,
+            stop_price: None,
+            trail_amount: None,
+            trail_percent: None,
+            time_in_force: None,
+            leverage: None,
+            stop_loss: None,
+            take_profit: None,
+            client_order_id: None,
+            bypass_drawdown: false,
+            display_quantity: None,
+        }
+    }
+
+    /// Buy 1 ETH at `entry` and sell it at `exit`.
+    fn round_trip(service: &TradingService, portfolio_id: &str, entry: f64, exit: f64) {
+        let order = service
+            .place_order(market_order(portfolio_id, OrderSide::Buy, 1.0))
+            .unwrap();
+        service.execute_market_order(&order.id, entry, None).unwrap();
+        let position = service.get_positions(portfolio_id).remove(0);
+        service.close_position(&position.id, exit).unwrap();
+    }
+
+    fn create_competition(
+        service: &TradingService,
+        start_offset: i64,
+        end_offset: i64,
+    ) -> Competition {
+        let now = chrono::Utc::now().timestamp_millis();
+        service
+            .create_competition(CreateCompetitionRequest {
+                name: "Weekly Cup".to_string(),
+                starting_balance: Some(100_000.0),
+                start_time: now + start_offset,
+                end_time: now + end_offset,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_competition_enrollment() {
+        let service = create_test_service();
+        let competition = create_competition(&service, -60_000, 3_600_000);
+        assert_eq!(competition.status(), CompetitionStatus::Active);
+
+        let portfolio = service
+            .create_portfolio("user123", "Contender", None, None)
+            .unwrap();
+        round_trip(&service, &portfolio.id, 2500.0, 2600.0);
+
+        // Enrolling resets the portfolio to the competition balance
+        let enrolled = service
+            .enroll_portfolio(&competition.id, &portfolio.id)
+            .unwrap();
+        assert!(enrolled.is_competition);
+        assert_eq!(enrolled.competition_id.as_deref(), Some(competition.id.as_str()));
+        assert_eq!(enrolled.starting_balance, 100_000.0);
+        assert_eq!(enrolled.cash_balance, 100_000.0);
+        assert_eq!(enrolled.realized_pnl, 0.0);
+
+        // Enrollment is persisted
+        let stored = service.sqlite.get_competition_portfolios(&competition.id);
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].starting_balance, 100_000.0);
+
+        // A portfolio can only be in one competition
+        assert!(matches!(
+            service.enroll_portfolio(&competition.id, &portfolio.id),
+            Err(TradingError::InvalidCompetition(_))
+        ));
+        assert!(matches!(
+            service.enroll_portfolio("missing", &portfolio.id),
+            Err(TradingError::CompetitionNotFound(_))
+        ));
+
+        // Ended competitions are closed to new entrants
+        let ended = create_competition(&service, -7_200_000, -3_600_000);
+        let late = service
+            .create_portfolio("user456", "Latecomer", None, None)
+            .unwrap();
+        assert!(matches!(
+            service.enroll_portfolio(&ended.id, &late.id),
+            Err(TradingError::CompetitionClosed(_))
+        ));
+
+        assert!(matches!(
+            service.create_competition(CreateCompetitionRequest {
+                name: "Backwards".to_string(),
+                starting_balance: None,
+                start_time: 2000,
+                end_time: 1000,
+            }),
+            Err(TradingError::InvalidCompetition(_))
+        ));
+    }
+
+    #[test]
+    fn test_competition_window_enforced() {
+        let service = create_test_service();
+        let upcoming = create_competition(&service, 3_600_000, 7_200_000);
+        let portfolio = service
+            .create_portfolio("user123", "Early", None, None)
+            .unwrap();
+        service
+            .enroll_portfolio(&upcoming.id, &portfolio.id)
+            .unwrap();
+
+        // No trading before the window opens
+        let result = service.place_order(market_order(&portfolio.id, OrderSide::Buy, 1.0));
+        assert!(matches!(result, Err(TradingError::CompetitionClosed(_))));
+
+        // Portfolios outside a competition are unaffected
+        let casual = service
+            .create_portfolio("user456", "Casual", None, None)
+            .unwrap();
+        round_trip(&service, &casual.id, 2500.0, 2600.0);
+
+        // After the window closes, positions can be reduced and closed but not grown
+        let closing = create_competition(&service, -60_000, 3_600_000);
+        let trader = service
+            .create_portfolio("user789", "Trader", None, None)
+            .unwrap();
+        service.enroll_portfolio(&closing.id, &trader.id).unwrap();
+        let order = service
+            .place_order(market_order(&trader.id, OrderSide::Buy, 2.0))
+            .unwrap();
+        service.execute_market_order(&order.id, 2500.0, None).unwrap();
+        // Placed inside the window, filled after it
+        let mut late_entry = market_order(&trader.id, OrderSide::Buy, 1.0);
+        late_entry.order_type = OrderType::Limit;
+        late_entry.price = Some(2400.0);
+        let late_entry = service.place_order(late_entry).unwrap();
+        let past = chrono::Utc::now().timestamp_millis() - 1;
+        service.sqlite.set_competition_end_time(&closing.id, past).unwrap();
+
+        assert!(matches!(
+            service.execute_market_order(&late_entry.id, 2400.0, None),
+            Err(TradingError::CompetitionClosed(_))
+        ));
+        assert_eq!(service.get_order(&late_entry.id).unwrap().status, OrderStatus::Rejected);
+        assert!(matches!(
+            service.place_order(market_order(&trader.id, OrderSide::Buy, 1.0)),
+            Err(TradingError::CompetitionClosed(_))
+        ));
+        assert!(matches!(
+            service.place_order(market_order(&trader.id, OrderSide::Sell, 3.0)),
+            Err(TradingError::CompetitionClosed(_))
+        ));
+
+        // Reducing orders are netted against each other, so two sells of
+        // 1.5 cannot together flip the position short
+        let mut first_exit = market_order(&trader.id, OrderSide::Sell, 1.5);
+        first_exit.order_type = OrderType::Limit;
+        first_exit.price = Some(2700.0);
+        service.place_order(first_exit).unwrap();
+        assert!(matches!(
+            service.place_order(market_order(&trader.id, OrderSide::Sell, 1.5)),
+            Err(TradingError::CompetitionClosed(_))
+        ));
+        let reduce = service
+            .place_order(market_order(&trader.id, OrderSide::Sell, 0.5))
+            .unwrap();
+        service.execute_market_order(&reduce.id, 2550.0, None).unwrap();
+
+        let position = service.get_positions(&trader.id).remove(0);
+        assert!((position.quantity - 1.5).abs() < 1e-9);
+        service.close_position(&position.id, 2600.0).unwrap();
+        assert!(service.get_positions(&trader.id).is_empty());
+    }
+
+    #[test]
+    fn test_competition_standings() {
+        const HOUR: i64 = 3_600_000;
+        let service = create_test_service();
+        let competition = create_competition(&service, -2 * HOUR, HOUR);
+
+        let mut ids = Vec::new();
+        for user in ["alice", "bob", "carol", "dave"] {
+            let portfolio = service.create_portfolio(user, user, None, None).unwrap();
+            service
+                .enroll_portfolio(&competition.id, &portfolio.id)
+                .unwrap();
+            ids.push(portfolio.id);
+        }
+        let outsider = service.create_portfolio("erin", "erin", None, None).unwrap();
+
+        record_trade(&service, &ids[0], -500.0, HOUR);
+        record_trade(&service, &ids[1], 2000.0, HOUR);
+        record_trade(&service, &ids[1], -500.0, HOUR / 2);
+        record_trade(&service, &ids[2], 1000.0, HOUR);
+        // Trades outside the window and by non-entrants do not count
+        record_trade(&service, &ids[0], 50_000.0, 3 * HOUR);
+        record_trade(&service, &outsider.id, 10_000.0, HOUR);
+
+        let standings = service.get_competition_standings(&competition.id).unwrap();
+        assert_eq!(standings.status, CompetitionStatus::Active);
+
+        // Dave never traded but still appears
+        let ranked: Vec<&String> = standings.standings.iter().map(|e| &e.portfolio_id).collect();
+        assert_eq!(ranked, vec![&ids[1], &ids[2], &ids[3], &ids[0]]);
+
+        let bob = &standings.standings[0];
+        assert_eq!(bob.realized_pnl, 1500.0);
+        assert_eq!(bob.total_trades, 2);
+        assert_eq!(bob.winning_trades, 1);
+        assert!((bob.total_return_pct - 1.5).abs() < 1e-9);
+        assert_eq!(standings.standings[3].realized_pnl, -500.0);
+    }
+
+    /// Buy 10 ETH at each of 100, 110 and 120, then sell 15 at 130 using `method`.
+    /// Returns the realized P&L and the remaining position.
+    fn close_partial_lots(method: CostBasisMethod) -> (f64, Position) {
+        let config = ExecutionConfig {
+            base_slippage_pct: 0.0,
+            ..ExecutionConfig::default()
+        };
+        let service =
+            TradingService::with_config(Arc::new(SqliteStore::new_in_memory().unwrap()), config);
+        let portfolio = service
+            .create_portfolio("user123", "Lots", None, None)
+            .unwrap();
+        service
+            .set_cost_basis_method(&portfolio.id, method)
+            .unwrap();
+
+        for price in [100.0, 110.0, 120.0] {
+            let order = service
+                .place_order(market_order(&portfolio.id, OrderSide::Buy, 10.0))
+                .unwrap();
+            service.execute_market_order(&order.id, price, None).unwrap();
+        }
+        let order = service
+            .place_order(market_order(&portfolio.id, OrderSide::Sell, 15.0))
+            .unwrap();
+        service.execute_market_order(&order.id, 130.0, None).unwrap();
+
+        let realized = service.get_portfolio(&portfolio.id).unwrap().realized_pnl;
+        let position = service.get_positions(&portfolio.id).remove(0);
+        assert_eq!(position.quantity, 15.0);
+        assert!((position.realized_pnl - realized).abs() < 1e-9);
+        (realized, position)
+    }
+
+    fn lots(position: &Position) -> Vec<(f64, f64)> {
+        position
+            .cost_basis
+            .iter()
+            .map(|e| (e.quantity, e.price))
+            .collect()
+    }
+
+    #[test]
+    fn test_cost_basis_fifo() {
+        // Sells 10 @ 100 and 5 @ 110: 1950 - 1550
+        let (realized, position) = close_partial_lots(CostBasisMethod::Fifo);
+        assert!((realized - 400.0).abs() < 1e-9);
+        assert_eq!(lots(&position), vec![(5.0, 110.0), (10.0, 120.0)]);
+        assert!((position.entry_price - 1750.0 / 15.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cost_basis_lifo() {
+        // Sells 10 @ 120 and 5 @ 110: 1950 - 1750
+        let (realized, position) = close_partial_lots(CostBasisMethod::Lifo);
+        assert!((realized - 200.0).abs() < 1e-9);
+        assert_eq!(lots(&position), vec![(10.0, 100.0), (5.0, 110.0)]);
+        assert!((position.entry_price - 1550.0 / 15.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cost_basis_average() {
+        // Sells 15 at the 110 average: 1950 - 1650
+        let (realized, position) = close_partial_lots(CostBasisMethod::Average);
+        assert!((realized - 300.0).abs() < 1e-9);
+        let remaining = lots(&position);
+        assert_eq!(remaining.len(), 3);
+        for ((qty, _), price) in remaining.iter().zip([100.0, 110.0, 120.0]) {
+            assert!((qty - 5.0).abs() < 1e-9);
+            assert_eq!(remaining.iter().filter(|(_, p)| *p == price).count(), 1);
+        }
+        assert!((position.entry_price - 110.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cost_basis_defaults_to_fifo() {
+        let service = create_test_service();
+        let portfolio = service
+            .create_portfolio("user123", "Default", None, None)
+            .unwrap();
+        assert_eq!(portfolio.cost_basis_method, CostBasisMethod::Fifo);
+
+        // Positions stored without lots are costed at their entry price
+        let mut position = Position::new(
+            portfolio.id.clone(),
+            "ETH".to_string(),
+            AssetClass::CryptoSpot,
+            PositionSide::Long,
+            10.0,
+            100.0,
+            1.0,
+        );
+        position.cost_basis.clear();
+        let cost = TradingService::reduce_cost_basis(&mut position, 4.0, CostBasisMethod::Lifo);
+        assert!((cost - 400.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_snapshot_all_portfolios() {
+        let service = create_test_service();
+        let a = service.create_portfolio("user123", "A", None, None).unwrap();
+        let b = service.create_portfolio("user456", "B", None, None).unwrap();
+
+        // One equity point per portfolio
+        let summary = service.snapshot_all_portfolios(90);
+        assert_eq!(summary.snapshotted, 2);
+        assert_eq!(summary.skipped, 0);
+        for id in [&a.id, &b.id] {
+            let history = service.get_portfolio_history(id, None, None).unwrap();
+            assert_eq!(history.len(), 1);
+            assert_eq!(history[0].equity, 250_000.0);
+        }
+
+        // Idle portfolios are not snapshotted again
+        let summary = service.snapshot_all_portfolios(90);
+        assert_eq!((summary.snapshotted, summary.skipped), (0, 2));
+
+        // Only the portfolio whose equity moved gets a new point
+        let order = service
+            .place_order(market_order(&a.id, OrderSide::Buy, 10.0))
+            .unwrap();
+        service.execute_market_order(&order.id, 2500.0, None).unwrap();
+        service.update_positions_for_symbol("ETH", 3000.0);
+        let summary = service.snapshot_all_portfolios(90);
+        assert_eq!((summary.snapshotted, summary.skipped), (1, 1));
+        assert_eq!(service.get_portfolio_history(&b.id, None, None).unwrap().len(), 1);
+
+        // Drawdown is measured from the tracked peak, not just the starting balance
+        let peak = service.get_portfolio(&a.id).unwrap().total_value;
+        service.update_positions_for_symbol("ETH", 2800.0);
+        service.snapshot_all_portfolios(90);
+        let latest = service.get_latest_snapshot(&a.id).unwrap();
+        assert!(latest.equity > 250_000.0);
+        let expected = (peak - latest.equity) / peak * 100.0;
+        assert!(expected > 0.0);
+        assert!((latest.drawdown_pct - expected).abs() < 1e-9);
+    }
+
+    fn execution_request(portfolio_id: &str, algo: ExecutionAlgo) -> ExecutionAlgoRequest {
+        ExecutionAlgoRequest {
+            portfolio_id: portfolio_id.to_string(),
+            symbol: "BTC".to_string(),
+            asset_class: AssetClass::CryptoSpot,
+            side: OrderSide::Buy,
+            quantity: 1.0,
+            algo,
+            duration_secs: 60,
+            slices: 4,
+            leverage: None,
+        }
+    }
+
+    #[test]
+    fn test_twap_execution_releases_slices_on_schedule() {
+        let service = create_test_service();
+        let portfolio = service
+            .create_portfolio("user123", "Algo", None, None)
+            .unwrap();
+
+        let schedule = service
+            .start_execution(execution_request(&portfolio.id, ExecutionAlgo::Twap))
+            .unwrap();
+        assert_eq!(schedule.slices.len(), 4);
+        let total: f64 = schedule.slices.iter().map(|s| s.quantity).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        assert!(schedule.slices.iter().all(|s| (s.quantity - 0.25).abs() < 1e-9));
+        for (i, slice) in schedule.slices.iter().enumerate() {
+            assert_eq!(slice.release_at, schedule.created_at + 15_000 * i as i64);
+        }
+
+        // Only the first slice is due at the start, and re-processing is a no-op
+        let start = schedule.created_at;
+        let price = |_: &str| Some(50000.0);
+        assert_eq!(service.process_execution_schedules(start, price), 1);
+        assert_eq!(service.process_execution_schedules(start + 14_999, price), 0);
+
+        let loaded = service.get_execution(&schedule.id).unwrap();
+        let child_id = loaded.slices[0].order_id.clone().unwrap();
+        let child = service.get_order(&child_id).unwrap();
+        assert_eq!(child.status, OrderStatus::Filled);
+        assert!((child.filled_quantity - 0.25).abs() < 1e-9);
+        assert_eq!(child.client_order_id, Some(loaded.child_client_order_id(0)));
+
+        // Two more slices come due by the halfway point
+        assert_eq!(service.process_execution_schedules(start + 30_000, price), 2);
+        let loaded = service.get_execution(&schedule.id).unwrap();
+        assert!((loaded.released_quantity() - 0.75).abs() < 1e-9);
+
+        // Cancelling drops the unreleased remainder
+        let cancelled = service.cancel_execution(&schedule.id).unwrap();
+        assert_eq!(cancelled.status, ExecutionStatus::Cancelled);
+        assert!(cancelled.slices[3].order_id.is_none());
+        assert_eq!(service.process_execution_schedules(start + 60_000, price), 0);
+        assert!(matches!(
+            service.cancel_execution(&schedule.id),
+            Err(TradingError::CannotCancelOrder(_))
+        ));
+        assert_eq!(service.get_portfolio_executions(&portfolio.id).len(), 1);
+    }
+
+    #[test]
+    fn test_execution_completes_when_every_slice_is_released() {
+        let service = create_test_service();
+        let portfolio = service
+            .create_portfolio("user123", "Algo", None, None)
+            .unwrap();
+        let schedule = service
+            .start_execution(execution_request(&portfolio.id, ExecutionAlgo::Vwap))
+            .unwrap();
+
+        // Without volume history VWAP falls back to equal slices
+        assert_eq!(schedule.slices.len(), 4);
+        let released = service.process_execution_schedules(schedule.created_at + 60_000, |_| None);
+        assert_eq!(released, 4);
+
+        let loaded = service.get_execution(&schedule.id).unwrap();
+        assert_eq!(loaded.status, ExecutionStatus::Completed);
+        assert!((loaded.released_quantity() - 1.0).abs() < 1e-9);
+        // No price yet: the children wait for the market tick to fill them
+        let child = service.get_order(loaded.slices[0].order_id.as_ref().unwrap()).unwrap();
+        assert_eq!(child.status, OrderStatus::Pending);
+
+        let mut invalid = execution_request(&portfolio.id, ExecutionAlgo::Twap);
+        invalid.slices = 0;
+        assert!(matches!(
+            service.start_execution(invalid),
+            Err(TradingError::InvalidOrder(_))
+        ));
+    }
+
+    #[test]
+    fn test_vwap_slices_follow_volume_profile() {
+        let candle = |time: i64, volume: f64| OhlcPoint {
+            time,
+            open: 100.0,
+            high: 100.0,
+            low: 100.0,
+            close: 100.0,
+            volume: Some(volume),
+        };
+        // Three 5-minute candles; the third is split across two 150s windows
+        let candles = vec![candle(0, 10.0), candle(300, 30.0), candle(600, 60.0)];
+        let weights = volume_profile(&candles, 300, 0, 300, 4);
+        assert_eq!(weights, vec![10.0, 30.0, 60.0, 0.0]);
+        let split = volume_profile(&candles, 300, 600, 150, 2);
+        assert_eq!(split, vec![30.0, 30.0]);
+
+        let request = ExecutionAlgoRequest {
+            quantity: 3.0,
+            duration_secs: 1200,
+            ..execution_request("p1", ExecutionAlgo::Vwap)
+        };
+        let schedule = ExecutionSchedule::new(&request, &weights, 1_000);
+        // The empty last window is dropped; the rest are volume-weighted
+        assert_eq!(schedule.slices.len(), 3);
+        let quantities: Vec<f64> = schedule.slices.iter().map(|s| s.quantity).collect();
+        assert!((quantities[0] - 0.3).abs() < 1e-9);
+        assert!((quantities[1] - 0.9).abs() < 1e-9);
+        assert!((quantities[2] - 1.8).abs() < 1e-9);
+        assert!((quantities.iter().sum::<f64>() - 3.0).abs() < 1e-9);
+        let release: Vec<i64> = schedule.slices.iter().map(|s| s.release_at).collect();
+        assert_eq!(release, vec![1_000, 301_000, 601_000]);
+    }
 }
@@ -7,6 +7,7 @@
 // Some structs are used only for deserialization/tests
 #![allow(dead_code)]
 
+use super::symbols;
 use crate::services::{ChartStore, PriceCache};
 use crate::types::PriceSource;
 use futures_util::{SinkExt, StreamExt};
@@ -267,7 +268,10 @@ impl AlpacaWs {
                         msg.get("p").and_then(|p| p.as_f64()),
                         msg.get("s").and_then(|s| s.as_u64()),
                     ) {
-                        let symbol_lower = symbol.to_lowercase();
+                        let Some(symbol_lower) = symbols::to_canonical(PriceSource::Alpaca, symbol)
+                        else {
+                            continue;
+                        };
                         let timestamp = chrono::Utc::now().timestamp_millis();
 
                         debug!("Alpaca trade: {} = ${:.2} (size: {})", symbol, price, size);
@@ -297,7 +301,10 @@ impl AlpacaWs {
                         msg.get("ap").and_then(|p| p.as_f64()),
                     ) {
                         let mid_price = (bid + ask) / 2.0;
-                        let symbol_lower = symbol.to_lowercase();
+                        let Some(symbol_lower) = symbols::to_canonical(PriceSource::Alpaca, symbol)
+                        else {
+                            continue;
+                        };
                         let timestamp = chrono::Utc::now().timestamp_millis();
 
                         debug!(
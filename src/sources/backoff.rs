@@ -0,0 +1,71 @@
+//! Reconnect backoff for streaming sources.
+
+use std::time::Duration;
+
+/// Exponential reconnect delay with jitter. Call [`Backoff::reset`] once a
+/// connection is healthy so the next outage starts from the base delay.
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    base: Duration,
+    max: Duration,
+    attempt: u32,
+}
+
+impl Backoff {
+    /// Create a backoff starting at `base` and capped at `max`.
+    pub const fn new(base: Duration, max: Duration) -> Self {
+        Self {
+            base,
+            max,
+            attempt: 0,
+        }
+    }
+
+    /// Delay before the next attempt. Each call doubles the delay up to the
+    /// cap, minus up to 25% jitter so clients do not reconnect in lockstep.
+    pub fn next_delay(&mut self) -> Duration {
+        let exp = self
+            .base
+            .saturating_mul(2u32.saturating_pow(self.attempt))
+            .min(self.max);
+        self.attempt = self.attempt.saturating_add(1);
+        exp - exp.mul_f64(rand::random::<f64>() * 0.25)
+    }
+
+    /// Start over from the base delay.
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_grows_and_caps() {
+        let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(8));
+        let delays: Vec<Duration> = (0..6).map(|_| backoff.next_delay()).collect();
+
+        for (i, delay) in delays.iter().enumerate() {
+            let ceiling = Duration::from_secs(1u64 << i.min(3));
+            assert!(*delay <= ceiling, "attempt {} waited {:?}", i, delay);
+            assert!(
+                *delay >= ceiling.mul_f64(0.75),
+                "attempt {} waited {:?}",
+                i,
+                delay
+            );
+        }
+    }
+
+    #[test]
+    fn test_reset_returns_to_base() {
+        let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(60));
+        for _ in 0..5 {
+            backoff.next_delay();
+        }
+        backoff.reset();
+        assert!(backoff.next_delay() <= Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,447 @@
+//! Coinbase level2 order book feed.
+//!
+//! Maintains a local book per product from the feed's snapshot and
+//! incremental updates, and pushes the top of each book into the
+//! [`OrderBookService`] so aggregated depth uses live Coinbase data.
+
+use super::backoff::Backoff;
+use super::symbols;
+use crate::services::OrderBookService;
+use crate::types::{ExchangeOrderBook, OrderBookLevel, PriceSource};
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{debug, error, info, warn};
+
+const COINBASE_WS_URL: &str = "wss://ws-feed.exchange.coinbase.com";
+
+/// Unauthenticated level2 channel; same snapshot/l2update messages as
+/// `level2`, batched every 50ms.
+const L2_CHANNEL: &str = "level2_batch";
+
+/// Levels per side pushed to the order book service.
+const PUBLISHED_DEPTH: usize = 50;
+
+/// Reconnect delays start here and double up to the cap.
+const RECONNECT_BASE: Duration = Duration::from_secs(1);
+const RECONNECT_MAX: Duration = Duration::from_secs(60);
+
+/// Coinbase WebSocket subscription message.
+#[derive(Debug, Serialize)]
+struct SubscribeMessage {
+    #[serde(rename = "type")]
+    msg_type: &'static str,
+    product_ids: Vec<String>,
+    channels: Vec<&'static str>,
+}
+
+impl SubscribeMessage {
+    fn new(msg_type: &'static str, product_ids: Vec<String>) -> Self {
+        Self {
+            msg_type,
+            product_ids,
+            channels: vec![L2_CHANNEL],
+        }
+    }
+}
+
+/// A level2 feed message.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum L2Message {
+    /// Full book for a product, sent after subscribing.
+    Snapshot {
+        product_id: String,
+        bids: Vec<[String; 2]>,
+        asks: Vec<[String; 2]>,
+        #[serde(default)]
+        sequence: Option<u64>,
+    },
+    /// Changed levels as `[side, price, new size]`; a size of zero removes
+    /// the level.
+    L2update {
+        product_id: String,
+        changes: Vec<[String; 3]>,
+        #[serde(default)]
+        sequence: Option<u64>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// An update that cannot be applied without a fresh snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookGap {
+    /// No snapshot has been applied yet.
+    NoSnapshot,
+    /// Sequence numbers skipped from `expected` to `received`.
+    Sequence { expected: u64, received: u64 },
+}
+
+/// Price key with 8 decimal places, so sub-cent assets keep distinct levels.
+fn price_key(price: f64) -> u64 {
+    (price * 1e8).round() as u64
+}
+
+/// Local copy of one product's order book.
+#[derive(Debug, Default, Clone)]
+pub struct LocalBook {
+    bids: BTreeMap<u64, OrderBookLevel>,
+    asks: BTreeMap<u64, OrderBookLevel>,
+    sequence: Option<u64>,
+    ready: bool,
+}
+
+impl LocalBook {
+    /// Replace the book with a snapshot.
+    pub fn apply_snapshot(
+        &mut self,
+        bids: &[(f64, f64)],
+        asks: &[(f64, f64)],
+        sequence: Option<u64>,
+    ) {
+        fn side(levels: &[(f64, f64)]) -> BTreeMap<u64, OrderBookLevel> {
+            levels
+                .iter()
+                .filter(|(_, quantity)| *quantity > 0.0)
+                .map(|&(price, quantity)| (price_key(price), OrderBookLevel { price, quantity }))
+                .collect()
+        }
+
+        self.bids = side(bids);
+        self.asks = side(asks);
+        self.sequence = sequence;
+        self.ready = true;
+    }
+
+    /// Apply changed levels as `(is_bid, price, size)`.
+    ///
+    /// Fails without changing the book if no snapshot has been applied or the
+    /// sequence number does not follow the last one seen. Messages without a
+    /// sequence number are applied as they arrive.
+    pub fn apply_update(
+        &mut self,
+        changes: &[(bool, f64, f64)],
+        sequence: Option<u64>,
+    ) -> Result<(), BookGap> {
+        if !self.ready {
+            return Err(BookGap::NoSnapshot);
+        }
+        if let (Some(last), Some(received)) = (self.sequence, sequence) {
+            if received <= last {
+                // Already reflected in the book
+                return Ok(());
+            }
+            if received != last + 1 {
+                return Err(BookGap::Sequence {
+                    expected: last + 1,
+                    received,
+                });
+            }
+        }
+
+        for &(is_bid, price, quantity) in changes {
+            let side = if is_bid {
+                &mut self.bids
+            } else {
+                &mut self.asks
+            };
+            if quantity > 0.0 {
+                side.insert(price_key(price), OrderBookLevel { price, quantity });
+            } else {
+                side.remove(&price_key(price));
+            }
+        }
+        if sequence.is_some() {
+            self.sequence = sequence;
+        }
+        Ok(())
+    }
+
+    /// Drop the book until the next snapshot.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Best `depth` bids, highest price first.
+    pub fn bids(&self, depth: usize) -> Vec<OrderBookLevel> {
+        self.bids.values().rev().take(depth).cloned().collect()
+    }
+
+    /// Best `depth` asks, lowest price first.
+    pub fn asks(&self, depth: usize) -> Vec<OrderBookLevel> {
+        self.asks.values().take(depth).cloned().collect()
+    }
+}
+
+/// Parse `[price, size]` string pairs, skipping malformed levels.
+fn parse_levels(levels: &[[String; 2]]) -> Vec<(f64, f64)> {
+    levels
+        .iter()
+        .filter_map(|[price, size]| Some((price.parse().ok()?, size.parse().ok()?)))
+        .collect()
+}
+
+/// Parse `[side, price, size]` changes, skipping malformed entries.
+fn parse_changes(changes: &[[String; 3]]) -> Vec<(bool, f64, f64)> {
+    changes
+        .iter()
+        .filter_map(|[side, price, size]| {
+            let is_bid = match side.as_str() {
+                "buy" => true,
+                "sell" => false,
+                _ => return None,
+            };
+            Some((is_bid, price.parse().ok()?, size.parse().ok()?))
+        })
+        .collect()
+}
+
+/// Coinbase level2 WebSocket client for a fixed set of symbols.
+pub struct CoinbaseL2Ws {
+    orderbook: Arc<OrderBookService>,
+    product_ids: Vec<String>,
+}
+
+impl CoinbaseL2Ws {
+    /// Create a feed for the given canonical symbols. Symbols Coinbase does
+    /// not list are ignored.
+    pub fn new(symbols: &[String], orderbook: Arc<OrderBookService>) -> Self {
+        let product_ids = symbols
+            .iter()
+            .filter_map(|s| symbols::to_native(PriceSource::Coinbase, s))
+            .collect();
+        Self {
+            orderbook,
+            product_ids,
+        }
+    }
+
+    /// Connect and maintain the books, reconnecting with backoff.
+    pub async fn connect(&self) -> anyhow::Result<()> {
+        if self.product_ids.is_empty() {
+            return Ok(());
+        }
+        let mut backoff = Backoff::new(RECONNECT_BASE, RECONNECT_MAX);
+        loop {
+            match self.run_connection(&mut backoff).await {
+                Ok(_) => warn!("Coinbase level2 WebSocket disconnected, reconnecting..."),
+                Err(e) => error!("Coinbase level2 WebSocket error: {}, reconnecting...", e),
+            }
+            tokio::time::sleep(backoff.next_delay()).await;
+        }
+    }
+
+    async fn run_connection(&self, backoff: &mut Backoff) -> anyhow::Result<()> {
+        info!("Connecting to Coinbase level2 WebSocket");
+        let (ws_stream, _) = connect_async(COINBASE_WS_URL).await?;
+        let (mut write, mut read) = ws_stream.split();
+
+        let msg = SubscribeMessage::new("subscribe", self.product_ids.clone());
+        write
+            .send(Message::Text(serde_json::to_string(&msg)?))
+            .await?;
+        info!(
+            "Subscribed to Coinbase level2 for {}",
+            self.product_ids.join(", ")
+        );
+        backoff.reset();
+
+        // Books start over on every connection; the subscription sends snapshots
+        let mut books: HashMap<String, LocalBook> = HashMap::new();
+
+        while let Some(msg) = read.next().await {
+            match msg {
+                Ok(Message::Text(text)) => {
+                    if let Some(product_id) = self.handle_message(&text, &mut books) {
+                        // Resubscribe to get a fresh snapshot for this product
+                        for msg_type in ["unsubscribe", "subscribe"] {
+                            let msg = SubscribeMessage::new(msg_type, vec![product_id.clone()]);
+                            write
+                                .send(Message::Text(serde_json::to_string(&msg)?))
+                                .await?;
+                        }
+                    }
+                }
+                Ok(Message::Ping(data)) => {
+                    let _ = write.send(Message::Pong(data)).await;
+                }
+                Ok(Message::Close(_)) => {
+                    info!("Coinbase level2 WebSocket closed");
+                    break;
+                }
+                Err(e) => {
+                    error!("Coinbase level2 WebSocket read error: {}", e);
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply a feed message to the books and publish the changed book.
+    ///
+    /// Returns the product ID whose book hit a gap and needs a new snapshot.
+    fn handle_message(&self, text: &str, books: &mut HashMap<String, LocalBook>) -> Option<String> {
+        let msg: L2Message = match serde_json::from_str(text) {
+            Ok(msg) => msg,
+            Err(_) => {
+                if text.contains("\"type\":\"error\"") {
+                    warn!("Coinbase level2 error: {}", text);
+                }
+                return None;
+            }
+        };
+
+        let product_id = match msg {
+            L2Message::Snapshot {
+                product_id,
+                bids,
+                asks,
+                sequence,
+            } => {
+                books.entry(product_id.clone()).or_default().apply_snapshot(
+                    &parse_levels(&bids),
+                    &parse_levels(&asks),
+                    sequence,
+                );
+                product_id
+            }
+            L2Message::L2update {
+                product_id,
+                changes,
+                sequence,
+            } => {
+                let book = books.entry(product_id.clone()).or_default();
+                if let Err(gap) = book.apply_update(&parse_changes(&changes), sequence) {
+                    warn!("Coinbase level2 gap on {}: {:?}", product_id, gap);
+                    book.reset();
+                    return Some(product_id);
+                }
+                product_id
+            }
+            L2Message::Other => return None,
+        };
+
+        self.publish(&product_id, &books[&product_id]);
+        None
+    }
+
+    /// Push the top of a book to the order book service.
+    fn publish(&self, product_id: &str, book: &LocalBook) {
+        let Some(symbol) = symbols::to_canonical(PriceSource::Coinbase, product_id) else {
+            return;
+        };
+        debug!("Coinbase level2 book update for {}", symbol);
+        self.orderbook.update_live_book(ExchangeOrderBook {
+            exchange: PriceSource::Coinbase,
+            symbol,
+            bids: book.bids(PUBLISHED_DEPTH),
+            asks: book.asks(PUBLISHED_DEPTH),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn levels(side: &[OrderBookLevel]) -> Vec<(f64, f64)> {
+        side.iter().map(|l| (l.price, l.quantity)).collect()
+    }
+
+    #[test]
+    fn test_snapshot_then_deltas() {
+        let mut book = LocalBook::default();
+        book.apply_snapshot(
+            &[(100.0, 1.0), (99.5, 2.0), (99.0, 3.0)],
+            &[(100.5, 1.5), (101.0, 2.5)],
+            Some(10),
+        );
+
+        // Resize a bid, remove a bid, add a better ask, remove an ask
+        book.apply_update(&[(true, 99.5, 4.0), (true, 99.0, 0.0)], Some(11))
+            .unwrap();
+        book.apply_update(&[(false, 100.25, 0.5), (false, 101.0, 0.0)], Some(12))
+            .unwrap();
+        // A new best bid
+        book.apply_update(&[(true, 100.1, 0.2)], Some(13)).unwrap();
+
+        assert_eq!(
+            levels(&book.bids(10)),
+            vec![(100.1, 0.2), (100.0, 1.0), (99.5, 4.0)]
+        );
+        assert_eq!(levels(&book.asks(10)), vec![(100.25, 0.5), (100.5, 1.5)]);
+        assert_eq!(levels(&book.bids(1)), vec![(100.1, 0.2)]);
+    }
+
+    #[test]
+    fn test_gaps_require_a_new_snapshot() {
+        let mut book = LocalBook::default();
+        assert_eq!(
+            book.apply_update(&[(true, 100.0, 1.0)], None),
+            Err(BookGap::NoSnapshot)
+        );
+
+        book.apply_snapshot(&[(100.0, 1.0)], &[(101.0, 1.0)], Some(5));
+        assert_eq!(
+            book.apply_update(&[(true, 100.0, 9.0)], Some(7)),
+            Err(BookGap::Sequence {
+                expected: 6,
+                received: 7
+            })
+        );
+        // The gapped update was not applied; stale ones are ignored
+        assert_eq!(book.apply_update(&[(true, 100.0, 9.0)], Some(5)), Ok(()));
+        assert_eq!(levels(&book.bids(10)), vec![(100.0, 1.0)]);
+
+        book.reset();
+        assert_eq!(
+            book.apply_update(&[(true, 100.0, 1.0)], Some(6)),
+            Err(BookGap::NoSnapshot)
+        );
+    }
+
+    #[test]
+    fn test_handle_messages_publishes_book() {
+        let orderbook = Arc::new(OrderBookService::new());
+        let feed = CoinbaseL2Ws::new(&["btc".to_string()], orderbook.clone());
+        let mut books = HashMap::new();
+
+        let snapshot = r#"{"type":"snapshot","product_id":"BTC-USD",
+            "bids":[["50000.00","1.5"],["49990.00","2.0"]],
+            "asks":[["50010.00","0.5"]]}"#;
+        assert_eq!(feed.handle_message(snapshot, &mut books), None);
+
+        let update = r#"{"type":"l2update","product_id":"BTC-USD",
+            "changes":[["buy","50000.00","0"],["sell","50005.00","0.25"]],
+            "time":"2024-01-01T00:00:00.000000Z"}"#;
+        assert_eq!(feed.handle_message(update, &mut books), None);
+
+        let live = orderbook.live_book("btc", PriceSource::Coinbase).unwrap();
+        assert_eq!(levels(&live.bids), vec![(49990.0, 2.0)]);
+        assert_eq!(levels(&live.asks), vec![(50005.0, 0.25), (50010.0, 0.5)]);
+
+        // An update for a product without a snapshot asks for a resubscribe
+        let orphan = r#"{"type":"l2update","product_id":"ETH-USD",
+            "changes":[["buy","3000.00","1"]]}"#;
+        assert_eq!(
+            feed.handle_message(orphan, &mut books),
+            Some("ETH-USD".to_string())
+        );
+    }
+
+    #[test]
+    fn test_subscribe_message_uses_level2_channel() {
+        let msg = SubscribeMessage::new("subscribe", vec!["BTC-USD".to_string()]);
+        let json = serde_json::to_string(&msg).unwrap();
+        assert!(json.contains("\"type\":\"subscribe\""));
+        assert!(json.contains("\"channels\":[\"level2_batch\"]"));
+    }
+}
@@ -1,3 +1,4 @@
+use super::symbols;
 use crate::services::{ChartStore, PriceCache};
 use crate::types::PriceSource;
 use futures_util::{SinkExt, StreamExt};
@@ -55,7 +56,7 @@ impl CoinbaseWs {
     pub async fn subscribe(&self, symbols: &[String]) {
         let product_ids: Vec<String> = symbols
             .iter()
-            .map(|s| format!("{}-USD", s.to_uppercase()))
+            .filter_map(|s| symbols::to_native(PriceSource::Coinbase, s))
             .collect();
 
         let mut pending = self.pending_subscribe.write().await;
@@ -66,7 +67,7 @@ impl CoinbaseWs {
     pub async fn unsubscribe(&self, symbols: &[String]) {
         let product_ids: Vec<String> = symbols
             .iter()
-            .map(|s| format!("{}-USD", s.to_uppercase()))
+            .filter_map(|s| symbols::to_native(PriceSource::Coinbase, s))
             .collect();
 
         let mut pending = self.pending_unsubscribe.write().await;
@@ -225,11 +226,9 @@ impl CoinbaseWs {
         let volume_24h: Option<f64> = msg.volume_24h.and_then(|v| v.parse().ok());
 
         // Extract symbol from product_id (e.g., "BTC-USD" -> "btc")
-        let symbol = product_id
-            .split('-')
-            .next()
-            .unwrap_or(&product_id)
-            .to_lowercase();
+        let Some(symbol) = symbols::to_canonical(PriceSource::Coinbase, &product_id) else {
+            return;
+        };
 
         debug!("Coinbase price update: {} = ${}", symbol, price);
 
@@ -320,25 +319,16 @@ mod tests {
 
     #[test]
     fn test_symbol_extraction_from_product_id() {
-        let product_id = "BTC-USD";
-        let symbol = product_id
-            .split('-')
-            .next()
-            .unwrap_or(product_id)
-            .to_lowercase();
-        assert_eq!(symbol, "btc");
+        let symbol = symbols::to_canonical(PriceSource::Coinbase, "BTC-USD");
+        assert_eq!(symbol.as_deref(), Some("btc"));
     }
 
     #[test]
     fn test_symbol_extraction_various() {
         let test_cases = vec![("ETH-USD", "eth"), ("SOL-USD", "sol"), ("DOGE-USD", "doge")];
         for (product_id, expected) in test_cases {
-            let symbol = product_id
-                .split('-')
-                .next()
-                .unwrap_or(product_id)
-                .to_lowercase();
-            assert_eq!(symbol, expected);
+            let symbol = symbols::to_canonical(PriceSource::Coinbase, product_id);
+            assert_eq!(symbol.as_deref(), Some(expected));
         }
     }
 
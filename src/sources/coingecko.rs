@@ -1,6 +1,7 @@
 // Some fields are kept for API completeness
 #![allow(dead_code)]
 
+use super::symbols::{self, COINGECKO_IDS};
 use crate::services::{ChartStore, PriceCache};
 use crate::types::PriceSource;
 use reqwest::Client;
@@ -15,28 +16,8 @@ const COINGECKO_PRO_API_URL: &str = "https://pro-api.coingecko.com/api/v3";
 const POLL_INTERVAL_SECS: u64 = 120; // Rate limit friendly (free tier: 10-30 calls/min)
 
 /// Symbol to CoinGecko ID mapping.
-pub const SYMBOL_TO_ID: &[(&str, &str)] = &[
-    ("btc", "bitcoin"),
-    ("eth", "ethereum"),
-    ("bnb", "binancecoin"),
-    ("sol", "solana"),
-    ("xrp", "ripple"),
-    ("doge", "dogecoin"),
-    ("ada", "cardano"),
-    ("avax", "avalanche-2"),
-    ("dot", "polkadot"),
-    ("link", "chainlink"),
-    ("matic", "matic-network"),
-    ("shib", "shiba-inu"),
-    ("ltc", "litecoin"),
-    ("trx", "tron"),
-    ("atom", "cosmos"),
-    ("uni", "uniswap"),
-    ("xlm", "stellar"),
-    ("bch", "bitcoin-cash"),
-    ("near", "near"),
-    ("apt", "aptos"),
-];
+#[deprecated(note = "use `sources::symbols::COINGECKO_IDS`")]
+pub const SYMBOL_TO_ID: &[(&str, &str)] = COINGECKO_IDS;
 
 /// CoinGecko market data with sparkline.
 #[derive(Debug, Deserialize)]
@@ -122,7 +103,7 @@ impl CoinGeckoClient {
 
     /// Fetch markets with sparkline data to seed historical charts.
     async fn fetch_markets_with_sparkline(&self) -> anyhow::Result<()> {
-        let ids: Vec<&str> = SYMBOL_TO_ID.iter().map(|(_, id)| *id).collect();
+        let ids: Vec<&str> = COINGECKO_IDS.iter().map(|(_, id)| *id).collect();
         let ids_str = ids.join(",");
 
         let mut url = format!(
@@ -158,11 +139,9 @@ impl CoinGeckoClient {
         let markets: Vec<CoinGeckoMarket> = response.json().await?;
 
         let timestamp = chrono::Utc::now().timestamp_millis();
-        let id_to_symbol: HashMap<&str, &str> =
-            SYMBOL_TO_ID.iter().map(|(s, id)| (*id, *s)).collect();
 
         for market in markets {
-            if let Some(symbol) = id_to_symbol.get(market.id.as_str()) {
+            if let Some(symbol) = symbols::to_canonical(PriceSource::CoinGecko, &market.id) {
                 // Update current price - sparkline will build up from real-time updates
                 // Note: We don't seed from CoinGecko's 7-day hourly sparkline data because
                 // our mini charts show minute-level pulse data, not historical trends
@@ -170,17 +149,17 @@ impl CoinGeckoClient {
                     // Seed sparkline with current price to provide a starting baseline
                     // (10 points gives a flat line to start)
                     let baseline: Vec<f64> = vec![price; 10];
-                    self.chart_store.seed_sparkline(symbol, &baseline);
+                    self.chart_store.seed_sparkline(&symbol, &baseline);
                     info!("Seeded baseline sparkline for {} at ${:.2}", symbol, price);
 
                     self.price_cache.update_price(
-                        symbol,
+                        &symbol,
                         PriceSource::CoinGecko,
                         price,
                         market.total_volume,
                     );
                     self.chart_store
-                        .add_price(symbol, price, market.total_volume, timestamp);
+                        .add_price(&symbol, price, market.total_volume, timestamp);
                 }
             }
         }
@@ -192,7 +171,7 @@ impl CoinGeckoClient {
     }
 
     async fn fetch_prices(&self) -> anyhow::Result<()> {
-        let ids: Vec<&str> = SYMBOL_TO_ID.iter().map(|(_, id)| *id).collect();
+        let ids: Vec<&str> = COINGECKO_IDS.iter().map(|(_, id)| *id).collect();
         let ids_str = ids.join(",");
 
         let mut url = format!(
@@ -210,7 +189,7 @@ impl CoinGeckoClient {
 
         let timestamp = chrono::Utc::now().timestamp_millis();
 
-        for (symbol, id) in SYMBOL_TO_ID {
+        for (symbol, id) in COINGECKO_IDS {
             if let Some(price_data) = response.get(*id) {
                 if let Some(price) = price_data.usd {
                     debug!("CoinGecko price update: {} = ${}", symbol, price);
@@ -235,39 +214,16 @@ mod tests {
     use super::*;
 
     // =========================================================================
-    // SYMBOL_TO_ID Tests
+    // CoinGeckoMarket Tests
     // =========================================================================
 
     #[test]
-    fn test_symbol_to_id_contains_bitcoin() {
-        let btc = SYMBOL_TO_ID.iter().find(|(s, _)| *s == "btc");
-        assert!(btc.is_some());
-        assert_eq!(btc.unwrap().1, "bitcoin");
-    }
-
-    #[test]
-    fn test_symbol_to_id_contains_ethereum() {
-        let eth = SYMBOL_TO_ID.iter().find(|(s, _)| *s == "eth");
-        assert!(eth.is_some());
-        assert_eq!(eth.unwrap().1, "ethereum");
-    }
-
-    #[test]
-    fn test_symbol_to_id_count() {
-        assert!(SYMBOL_TO_ID.len() >= 20);
+    #[allow(deprecated)]
+    fn test_symbol_to_id_alias_matches_symbol_map() {
+        assert_eq!(SYMBOL_TO_ID, COINGECKO_IDS);
+        assert!(SYMBOL_TO_ID.contains(&("btc", "bitcoin")));
     }
 
-    #[test]
-    fn test_symbol_to_id_lowercase() {
-        for (symbol, _) in SYMBOL_TO_ID {
-            assert_eq!(*symbol, symbol.to_lowercase());
-        }
-    }
-
-    // =========================================================================
-    // CoinGeckoMarket Tests
-    // =========================================================================
-
     #[test]
     fn test_coingecko_market_deserialization() {
         let json = r#"{
@@ -1,6 +1,7 @@
 // Some structs/constants are kept for API completeness
 #![allow(dead_code)]
 
+use super::symbols;
 use crate::services::{Cache, ChartStore, FileCache, PriceCache};
 use crate::types::{
     Asset, AssetListing, FearGreedData, GlobalMetrics, PaginatedResponse, PriceSource, Quote,
@@ -33,6 +34,9 @@ pub struct CoinMarketCapClient {
 /// File cache TTL for listings (24 hours - used as ultimate fallback)
 const FILE_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
 
+/// Size cap for the listings file cache (50 MB)
+const FILE_CACHE_MAX_BYTES: u64 = 50 * 1024 * 1024;
+
 #[derive(Debug, Deserialize)]
 struct CmcResponse<T> {
     data: T,
@@ -106,7 +110,7 @@ impl CoinMarketCapClient {
             asset_cache: Arc::new(Cache::new(Duration::from_secs(60))),
             global_cache: Arc::new(Cache::new(Duration::from_secs(60))),
             fear_greed_cache: Arc::new(Cache::new(Duration::from_secs(3600))),
-            file_cache: Arc::new(FileCache::new()),
+            file_cache: Arc::new(FileCache::with_max_size(FILE_CACHE_MAX_BYTES)),
         }
     }
 
@@ -472,23 +476,32 @@ impl CoinMarketCapClient {
 
         let timestamp = chrono::Utc::now().timestamp_millis();
 
+        let mut quotes = Vec::with_capacity(response.data.len());
         for listing in response.data {
             if let Some(quote) = listing.quote.and_then(|m| m.get("USD").cloned()) {
-                if let Some(price) = quote.price {
-                    let symbol = listing.symbol.to_lowercase();
+                let symbol = symbols::to_canonical(PriceSource::CoinMarketCap, &listing.symbol);
+                if let (Some(price), Some(symbol)) = (quote.price, symbol) {
                     debug!("CMC price update: {} = ${}", symbol, price);
-                    self.price_cache.update_price(
-                        &symbol,
-                        PriceSource::CoinMarketCap,
-                        price,
-                        quote.volume_24h,
-                    );
-                    self.chart_store
-                        .add_price(&symbol, price, quote.volume_24h, timestamp);
+                    quotes.push((symbol, price, quote.volume_24h));
                 }
             }
         }
 
+        // One batch per poll instead of a lock round-trip per symbol
+        let prices: Vec<_> = quotes
+            .iter()
+            .map(|(symbol, price, volume)| {
+                (symbol.as_str(), PriceSource::CoinMarketCap, *price, *volume)
+            })
+            .collect();
+        self.price_cache.update_prices(&prices);
+
+        let points: Vec<_> = quotes
+            .iter()
+            .map(|(symbol, price, volume)| (symbol.as_str(), *price, *volume, timestamp))
+            .collect();
+        self.chart_store.add_prices(&points);
+
         Ok(())
     }
 
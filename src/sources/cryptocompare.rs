@@ -1,3 +1,4 @@
+use super::symbols;
 use crate::services::{ChartStore, PriceCache};
 use crate::types::PriceSource;
 use reqwest::Client;
@@ -89,7 +90,11 @@ impl CryptoCompareClient {
             for symbol in SYMBOLS {
                 if let Some(usd_data) = raw.get(*symbol).and_then(|m| m.get("USD")) {
                     if let Some(price) = usd_data.price {
-                        let symbol_lower = symbol.to_lowercase();
+                        let Some(symbol_lower) =
+                            symbols::to_canonical(PriceSource::CryptoCompare, symbol)
+                        else {
+                            continue;
+                        };
                         debug!("CryptoCompare price update: {} = ${}", symbol_lower, price);
                         self.price_cache.update_price(
                             &symbol_lower,
@@ -6,6 +6,7 @@
 // Some structs are used only for deserialization/tests
 #![allow(dead_code)]
 
+use super::symbols;
 use crate::services::{ChartStore, PriceCache};
 use crate::types::PriceSource;
 use futures_util::{SinkExt, StreamExt};
@@ -191,7 +192,9 @@ impl FinnhubWs {
         }
 
         for trade in msg.data {
-            let symbol = trade.s.to_lowercase();
+            let Some(symbol) = symbols::to_canonical(PriceSource::Finnhub, &trade.s) else {
+                continue;
+            };
             let price = trade.p;
             let timestamp = trade.t;
 
@@ -1,8 +1,8 @@
+use super::symbols;
 use crate::services::{ChartStore, PriceCache};
 use crate::types::PriceSource;
 use reqwest::Client;
 use serde::Deserialize;
-use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
@@ -123,12 +123,10 @@ impl HuobiClient {
 
         let timestamp = chrono::Utc::now().timestamp_millis();
 
-        // Build pair lookup
-        let pair_to_symbol: HashMap<&str, &str> =
-            SYMBOL_PAIRS.iter().map(|(s, p)| (*p, *s)).collect();
-
         for ticker in tickers {
-            if let Some(symbol) = pair_to_symbol.get(ticker.symbol.as_str()) {
+            let symbol = symbols::to_canonical(PriceSource::Huobi, &ticker.symbol)
+                .filter(|s| symbols::is_tracked(SYMBOL_PAIRS, s));
+            if let Some(symbol) = symbol {
                 let price = ticker.close;
                 // Huobi vol is in base currency, amount is in quote currency
                 let volume_24h = ticker.amount;
@@ -136,13 +134,13 @@ impl HuobiClient {
                 if price > 0.0 {
                     debug!("Huobi price update: {} = ${}", symbol, price);
                     self.price_cache.update_price(
-                        symbol,
+                        &symbol,
                         PriceSource::Huobi,
                         price,
                         Some(volume_24h),
                     );
                     self.chart_store
-                        .add_price(symbol, price, Some(volume_24h), timestamp);
+                        .add_price(&symbol, price, Some(volume_24h), timestamp);
                 }
             }
         }
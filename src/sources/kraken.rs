@@ -1,3 +1,4 @@
+use super::symbols;
 use crate::services::{ChartStore, PriceCache};
 use crate::types::PriceSource;
 use reqwest::Client;
@@ -121,22 +122,10 @@ impl KrakenClient {
 
         let timestamp = chrono::Utc::now().timestamp_millis();
 
-        // Build pair lookup
-        let pair_to_symbol: HashMap<&str, &str> =
-            SYMBOL_PAIRS.iter().map(|(s, p)| (*p, *s)).collect();
-
         for (pair, ticker) in result {
-            // Kraken sometimes returns slightly different pair names
-            let symbol = pair_to_symbol
-                .get(pair.as_str())
-                .or_else(|| {
-                    // Try without trailing zeros or with alternate format
-                    SYMBOL_PAIRS
-                        .iter()
-                        .find(|(_, p)| pair.starts_with(*p) || p.starts_with(&pair))
-                        .map(|(s, _)| s)
-                })
-                .copied();
+            // Kraken answers with legacy (XXBTZUSD) or short (XBTUSD) pair names
+            let symbol = symbols::to_canonical(PriceSource::Kraken, &pair)
+                .filter(|s| symbols::is_tracked(SYMBOL_PAIRS, s));
 
             if let Some(symbol) = symbol {
                 let price: f64 = ticker.c.first().and_then(|p| p.parse().ok()).unwrap_or(0.0);
@@ -146,13 +135,13 @@ impl KrakenClient {
                 if price > 0.0 {
                     debug!("Kraken price update: {} = ${}", symbol, price);
                     self.price_cache.update_price(
-                        symbol,
+                        &symbol,
                         PriceSource::Kraken,
                         price,
                         Some(volume_24h),
                     );
                     self.chart_store
-                        .add_price(symbol, price, Some(volume_24h), timestamp);
+                        .add_price(&symbol, price, Some(volume_24h), timestamp);
                 }
             }
         }
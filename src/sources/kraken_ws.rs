@@ -0,0 +1,346 @@
+use super::backoff::Backoff;
+use super::kraken::SYMBOL_PAIRS;
+use super::symbols;
+use crate::services::{ChartStore, PriceCache};
+use crate::types::PriceSource;
+use futures_util::{SinkExt, StreamExt};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{debug, error, info, warn};
+
+const KRAKEN_WS_URL: &str = "wss://ws.kraken.com";
+
+/// Reconnect delays start here and double up to the cap.
+const RECONNECT_BASE: Duration = Duration::from_secs(1);
+const RECONNECT_MAX: Duration = Duration::from_secs(60);
+
+/// Kraken WebSocket subscription message.
+#[derive(Debug, Serialize)]
+struct SubscribeMessage {
+    event: &'static str,
+    pair: Vec<String>,
+    subscription: Subscription,
+}
+
+#[derive(Debug, Serialize)]
+struct Subscription {
+    name: &'static str,
+}
+
+impl SubscribeMessage {
+    fn new(event: &'static str, symbols: &[String]) -> Self {
+        Self {
+            event,
+            pair: symbols.iter().map(|s| symbols::kraken_ws_pair(s)).collect(),
+            subscription: Subscription { name: "trade" },
+        }
+    }
+}
+
+/// A trade from Kraken's feed with the pair mapped to the canonical symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KrakenTrade {
+    pub symbol: String,
+    pub price: f64,
+    pub volume: f64,
+    /// Trade time in milliseconds since the epoch.
+    pub timestamp: i64,
+}
+
+/// Parse a trade message such as
+/// `[0, [["5541.2", "0.15", "1534614057.321597", "s", "l", ""]], "trade", "XBT/USD"]`.
+///
+/// Returns `None` for heartbeats, status events and trades on pairs that do
+/// not map to a canonical USD symbol.
+fn parse_trade_message(text: &str) -> Option<Vec<KrakenTrade>> {
+    let value: serde_json::Value = serde_json::from_str(text).ok()?;
+    let arr = value.as_array()?;
+    if arr.len() < 4 || arr[arr.len() - 2].as_str() != Some("trade") {
+        return None;
+    }
+
+    let pair = arr[arr.len() - 1].as_str()?;
+    let symbol = symbols::to_canonical(PriceSource::Kraken, pair)?;
+
+    let trades = arr[1]
+        .as_array()?
+        .iter()
+        .filter_map(|trade| {
+            let fields = trade.as_array()?;
+            let field = |i: usize| fields.get(i)?.as_str()?.parse::<f64>().ok();
+            Some(KrakenTrade {
+                symbol: symbol.clone(),
+                price: field(0)?,
+                volume: field(1)?,
+                timestamp: (field(2)? * 1000.0) as i64,
+            })
+        })
+        .filter(|trade| trade.price > 0.0)
+        .collect();
+
+    Some(trades)
+}
+
+/// Kraken WebSocket client streaming trades for a configured symbol set.
+///
+/// The configured symbols are always streamed; extra symbols can be added
+/// and removed at runtime through [`KrakenWs::subscribe`] and
+/// [`KrakenWs::unsubscribe`].
+#[derive(Clone)]
+pub struct KrakenWs {
+    price_cache: Arc<PriceCache>,
+    chart_store: Arc<ChartStore>,
+    configured: Arc<HashSet<String>>,
+    subscribed: Arc<RwLock<HashSet<String>>>,
+    pending_subscribe: Arc<RwLock<Vec<String>>>,
+    pending_unsubscribe: Arc<RwLock<Vec<String>>>,
+}
+
+impl KrakenWs {
+    /// Create a new Kraken WebSocket client for the given canonical symbols.
+    pub fn new(
+        symbols: &[String],
+        price_cache: Arc<PriceCache>,
+        chart_store: Arc<ChartStore>,
+    ) -> Self {
+        let configured: HashSet<String> = symbols.iter().map(|s| symbols::canonical(s)).collect();
+        Self {
+            price_cache,
+            chart_store,
+            subscribed: Arc::new(RwLock::new(configured.clone())),
+            configured: Arc::new(configured),
+            pending_subscribe: Arc::new(RwLock::new(Vec::new())),
+            pending_unsubscribe: Arc::new(RwLock::new(Vec::new())),
+        }
+    }
+
+    /// Subscribe to symbols. Symbols Kraken does not list are ignored.
+    pub async fn subscribe(&self, symbols: &[String]) {
+        let subscribed = self.subscribed.read().await;
+        let new: Vec<String> = symbols
+            .iter()
+            .map(|s| symbols::canonical(s))
+            .filter(|s| symbols::is_tracked(SYMBOL_PAIRS, s) && !subscribed.contains(s))
+            .collect();
+        drop(subscribed);
+
+        self.pending_subscribe.write().await.extend(new);
+    }
+
+    /// Unsubscribe from symbols. Configured symbols stay subscribed.
+    pub async fn unsubscribe(&self, symbols: &[String]) {
+        let removed: Vec<String> = symbols
+            .iter()
+            .map(|s| symbols::canonical(s))
+            .filter(|s| !self.configured.contains(s))
+            .collect();
+
+        self.pending_unsubscribe.write().await.extend(removed);
+    }
+
+    /// Connect and start receiving trades, reconnecting with backoff.
+    pub async fn connect(&self) -> anyhow::Result<()> {
+        let mut backoff = Backoff::new(RECONNECT_BASE, RECONNECT_MAX);
+        loop {
+            match self.run_connection(&mut backoff).await {
+                Ok(_) => warn!("Kraken WebSocket disconnected, reconnecting..."),
+                Err(e) => error!("Kraken WebSocket error: {}, reconnecting...", e),
+            }
+            // REST polling reports Kraken's own online status
+            self.price_cache
+                .set_stream_connected(PriceSource::Kraken, false);
+            tokio::time::sleep(backoff.next_delay()).await;
+        }
+    }
+
+    async fn run_connection(&self, backoff: &mut Backoff) -> anyhow::Result<()> {
+        info!("Connecting to Kraken WebSocket");
+        let (ws_stream, _) = connect_async(KRAKEN_WS_URL).await?;
+        let (mut write, mut read) = ws_stream.split();
+        info!("Connected to Kraken WebSocket");
+        self.price_cache
+            .set_stream_connected(PriceSource::Kraken, true);
+
+        // Resubscribe to everything we were streaming before the reconnect
+        let symbols: Vec<String> = self.subscribed.read().await.iter().cloned().collect();
+        if !symbols.is_empty() {
+            let msg = serde_json::to_string(&SubscribeMessage::new("subscribe", &symbols))?;
+            write.send(Message::Text(msg)).await?;
+        }
+        backoff.reset();
+
+        loop {
+            tokio::select! {
+                msg = read.next() => {
+                    match msg {
+                        Some(Ok(Message::Text(text))) => {
+                            self.handle_message(&text);
+                        }
+                        Some(Ok(Message::Ping(data))) => {
+                            let _ = write.send(Message::Pong(data)).await;
+                        }
+                        Some(Ok(Message::Close(_))) => {
+                            info!("Kraken WebSocket closed");
+                            break;
+                        }
+                        Some(Err(e)) => {
+                            error!("Kraken WebSocket read error: {}", e);
+                            break;
+                        }
+                        None => {
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+                _ = tokio::time::sleep(Duration::from_millis(100)) => {
+                    self.process_pending(&mut write).await;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Send any queued subscription changes.
+    async fn process_pending<S>(&self, write: &mut S)
+    where
+        S: SinkExt<Message> + Unpin,
+    {
+        let to_subscribe: Vec<String> = self.pending_subscribe.write().await.drain(..).collect();
+        if !to_subscribe.is_empty() {
+            if let Ok(json) =
+                serde_json::to_string(&SubscribeMessage::new("subscribe", &to_subscribe))
+            {
+                if write.send(Message::Text(json)).await.is_ok() {
+                    self.subscribed.write().await.extend(to_subscribe);
+                }
+            }
+        }
+
+        let to_unsubscribe: Vec<String> =
+            self.pending_unsubscribe.write().await.drain(..).collect();
+        if !to_unsubscribe.is_empty() {
+            if let Ok(json) =
+                serde_json::to_string(&SubscribeMessage::new("unsubscribe", &to_unsubscribe))
+            {
+                if write.send(Message::Text(json)).await.is_ok() {
+                    let mut subscribed = self.subscribed.write().await;
+                    for symbol in &to_unsubscribe {
+                        subscribed.remove(symbol);
+                    }
+                }
+            }
+        }
+    }
+
+    fn handle_message(&self, text: &str) {
+        let Some(trades) = parse_trade_message(text) else {
+            if text.contains("\"subscriptionStatus\"") && text.contains("\"error\"") {
+                warn!("Kraken subscription rejected: {}", text);
+            }
+            return;
+        };
+
+        for trade in &trades {
+            self.chart_store.add_price(
+                &trade.symbol,
+                trade.price,
+                Some(trade.volume),
+                trade.timestamp,
+            );
+        }
+
+        // Trades arrive oldest first, so the last one is the current price
+        if let Some(last) = trades.last() {
+            debug!("Kraken trade: {} = ${}", last.symbol, last.price);
+            self.price_cache
+                .update_price(&last.symbol, PriceSource::Kraken, last.price, None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::AggregationConfig;
+
+    const SAMPLE_TRADE: &str = r#"[
+        0,
+        [
+            ["43500.10000", "0.15850568", "1534614057.321597", "s", "l", ""],
+            ["43501.50000", "0.50000000", "1534614058.500000", "b", "m", ""]
+        ],
+        "trade",
+        "XBT/USD"
+    ]"#;
+
+    fn test_client(symbols: &[&str]) -> KrakenWs {
+        let (price_cache, _rx) = PriceCache::new(AggregationConfig::default());
+        let symbols: Vec<String> = symbols.iter().map(|s| s.to_string()).collect();
+        KrakenWs::new(&symbols, price_cache, ChartStore::new())
+    }
+
+    #[test]
+    fn test_parse_trade_message() {
+        let trades = parse_trade_message(SAMPLE_TRADE).unwrap();
+        assert_eq!(trades.len(), 2);
+        assert_eq!(
+            trades[0],
+            KrakenTrade {
+                symbol: "btc".to_string(),
+                price: 43500.1,
+                volume: 0.15850568,
+                timestamp: 1534614057321,
+            }
+        );
+        assert_eq!(trades[1].price, 43501.5);
+        assert_eq!(trades[1].timestamp, 1534614058500);
+    }
+
+    #[test]
+    fn test_parse_ignores_events() {
+        assert!(parse_trade_message(r#"{"event":"heartbeat"}"#).is_none());
+        assert!(parse_trade_message(
+            r#"{"event":"subscriptionStatus","status":"subscribed","pair":"XBT/USD"}"#
+        )
+        .is_none());
+        assert!(parse_trade_message(r#"[0, [], "trade", "XBT/EUR"]"#).is_none());
+        assert!(parse_trade_message("not json").is_none());
+    }
+
+    #[test]
+    fn test_handle_message_updates_chart_store() {
+        let ws = test_client(&["btc"]);
+        ws.handle_message(SAMPLE_TRADE);
+        assert_eq!(ws.chart_store.get_current_price("btc"), Some(43501.5));
+    }
+
+    #[test]
+    fn test_subscribe_message_uses_kraken_pairs() {
+        let msg = SubscribeMessage::new("subscribe", &["btc".to_string(), "doge".to_string()]);
+        let json: serde_json::Value = serde_json::to_value(&msg).unwrap();
+        assert_eq!(json["event"], "subscribe");
+        assert_eq!(json["pair"], serde_json::json!(["XBT/USD", "XDG/USD"]));
+        assert_eq!(json["subscription"]["name"], "trade");
+    }
+
+    #[tokio::test]
+    async fn test_configured_symbols_stay_subscribed() {
+        let ws = test_client(&["BTC", "eth"]);
+        ws.subscribe(&["sol".to_string(), "btc".to_string(), "aapl".to_string()])
+            .await;
+        assert_eq!(*ws.pending_subscribe.read().await, vec!["sol".to_string()]);
+
+        ws.unsubscribe(&["btc".to_string(), "sol".to_string()])
+            .await;
+        assert_eq!(
+            *ws.pending_unsubscribe.read().await,
+            vec!["sol".to_string()]
+        );
+    }
+}
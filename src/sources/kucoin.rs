@@ -1,8 +1,8 @@
+use super::symbols;
 use crate::services::{ChartStore, PriceCache};
 use crate::types::PriceSource;
 use reqwest::Client;
 use serde::Deserialize;
-use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
@@ -135,12 +135,10 @@ impl KuCoinClient {
 
         let timestamp = chrono::Utc::now().timestamp_millis();
 
-        // Build pair lookup
-        let pair_to_symbol: HashMap<&str, &str> =
-            SYMBOL_PAIRS.iter().map(|(s, p)| (*p, *s)).collect();
-
         for ticker in tickers {
-            if let Some(symbol) = pair_to_symbol.get(ticker.symbol.as_str()) {
+            let symbol = symbols::to_canonical(PriceSource::KuCoin, &ticker.symbol)
+                .filter(|s| symbols::is_tracked(SYMBOL_PAIRS, s));
+            if let Some(symbol) = symbol {
                 let price: f64 = ticker
                     .last
                     .as_ref()
@@ -156,13 +154,13 @@ impl KuCoinClient {
                 if price > 0.0 {
                     debug!("KuCoin price update: {} = ${}", symbol, price);
                     self.price_cache.update_price(
-                        symbol,
+                        &symbol,
                         PriceSource::KuCoin,
                         price,
                         Some(volume_24h),
                     );
                     self.chart_store
-                        .add_price(symbol, price, Some(volume_24h), timestamp);
+                        .add_price(&symbol, price, Some(volume_24h), timestamp);
                 }
             }
         }
@@ -1,6 +1,8 @@
 pub mod alpaca_ws;
 pub mod alphavantage;
+pub mod backoff;
 pub mod binance;
+pub mod coinbase_l2;
 pub mod coinbase_ws;
 pub mod coincap;
 pub mod coingecko;
@@ -10,14 +12,17 @@ pub mod finnhub;
 pub mod finnhub_ws;
 pub mod huobi;
 pub mod kraken;
+pub mod kraken_ws;
 pub mod kucoin;
 pub mod okx;
+pub mod symbols;
 pub mod tiingo_ws;
 pub mod yahoo;
 
 pub use alpaca_ws::AlpacaWs;
 pub use alphavantage::AlphaVantageClient;
 pub use binance::BinanceClient;
+pub use coinbase_l2::CoinbaseL2Ws;
 pub use coinbase_ws::CoinbaseWs;
 pub use coincap::CoinCapClient;
 pub use coingecko::CoinGeckoClient;
@@ -27,6 +32,7 @@ pub use finnhub::FinnhubClient;
 pub use finnhub_ws::FinnhubWs;
 pub use huobi::HuobiClient;
 pub use kraken::KrakenClient;
+pub use kraken_ws::KrakenWs;
 pub use kucoin::KuCoinClient;
 pub use okx::OkxClient;
 pub use tiingo_ws::TiingoWs;
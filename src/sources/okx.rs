@@ -1,8 +1,8 @@
+use super::symbols;
 use crate::services::{ChartStore, PriceCache};
 use crate::types::PriceSource;
 use reqwest::Client;
 use serde::Deserialize;
-use std::collections::HashMap;
 use std::sync::Arc;
 use tracing::{debug, error, info, warn};
 
@@ -122,25 +122,23 @@ impl OkxClient {
 
         let timestamp = chrono::Utc::now().timestamp_millis();
 
-        // Build pair lookup
-        let pair_to_symbol: HashMap<&str, &str> =
-            SYMBOL_PAIRS.iter().map(|(s, p)| (*p, *s)).collect();
-
         for ticker in data.data {
-            if let Some(symbol) = pair_to_symbol.get(ticker.inst_id.as_str()) {
+            let symbol = symbols::to_canonical(PriceSource::Okx, &ticker.inst_id)
+                .filter(|s| symbols::is_tracked(SYMBOL_PAIRS, s));
+            if let Some(symbol) = symbol {
                 let price: f64 = ticker.last.parse().unwrap_or(0.0);
                 let volume_24h: f64 = ticker.vol_ccy24h.parse().unwrap_or(0.0);
 
                 if price > 0.0 {
                     debug!("OKX price update: {} = ${}", symbol, price);
                     self.price_cache.update_price(
-                        symbol,
+                        &symbol,
                         PriceSource::Okx,
                         price,
                         Some(volume_24h),
                     );
                     self.chart_store
-                        .add_price(symbol, price, Some(volume_24h), timestamp);
+                        .add_price(&symbol, price, Some(volume_24h), timestamp);
                 }
             }
         }
@@ -0,0 +1,325 @@
+//! Symbol normalization across sources.
+//!
+//! Every source names assets its own way (`BTC-USD`, `XXBTZUSD`, `btcusdt`,
+//! `bitcoin`). Sources convert through here before writing to `PriceCache`
+//! or `ChartStore` so that all of them agree on the canonical lowercase
+//! symbol (`btc`).
+
+use crate::types::PriceSource;
+
+/// Symbol to CoinGecko ID mapping.
+pub const COINGECKO_IDS: &[(&str, &str)] = &[
+    ("btc", "bitcoin"),
+    ("eth", "ethereum"),
+    ("bnb", "binancecoin"),
+    ("sol", "solana"),
+    ("xrp", "ripple"),
+    ("doge", "dogecoin"),
+    ("ada", "cardano"),
+    ("avax", "avalanche-2"),
+    ("dot", "polkadot"),
+    ("link", "chainlink"),
+    ("matic", "matic-network"),
+    ("shib", "shiba-inu"),
+    ("ltc", "litecoin"),
+    ("trx", "tron"),
+    ("atom", "cosmos"),
+    ("uni", "uniswap"),
+    ("xlm", "stellar"),
+    ("bch", "bitcoin-cash"),
+    ("near", "near"),
+    ("apt", "aptos"),
+];
+
+/// Venue-specific tickers and the canonical symbol they stand for.
+const ALIASES: &[(&str, &str)] = &[("xbt", "btc"), ("xdg", "doge")];
+
+/// USD-like quote currencies accepted on trading pairs, longest first so
+/// `usdt` is not read as `usd` plus a stray `t`.
+const USD_QUOTES: &[&str] = &["usdt", "usdc", "usd"];
+
+/// How a source spells its symbols.
+enum Convention {
+    /// Bare ticker, e.g. `BTC` or `AAPL`.
+    Ticker,
+    /// Base and quote joined by a separator, e.g. `BTC-USD`.
+    Pair {
+        sep: &'static str,
+        quote: &'static str,
+        upper: bool,
+    },
+    /// Kraken pairs, with legacy `X`/`Z` prefixes and `XBT` for bitcoin.
+    Kraken,
+    /// CoinGecko coin IDs.
+    CoinGeckoId,
+}
+
+fn convention(source: PriceSource) -> Convention {
+    match source {
+        PriceSource::Coinbase => Convention::Pair {
+            sep: "-",
+            quote: "usd",
+            upper: true,
+        },
+        PriceSource::Binance => Convention::Pair {
+            sep: "",
+            quote: "usdt",
+            upper: true,
+        },
+        PriceSource::KuCoin | PriceSource::Okx => Convention::Pair {
+            sep: "-",
+            quote: "usdt",
+            upper: true,
+        },
+        PriceSource::Huobi => Convention::Pair {
+            sep: "",
+            quote: "usdt",
+            upper: false,
+        },
+        PriceSource::Kraken => Convention::Kraken,
+        PriceSource::CoinGecko => Convention::CoinGeckoId,
+        PriceSource::CryptoCompare
+        | PriceSource::CoinMarketCap
+        | PriceSource::Hyperliquid
+        | PriceSource::Finnhub
+        | PriceSource::AlphaVantage
+        | PriceSource::Alpaca
+        | PriceSource::Tiingo => Convention::Ticker,
+    }
+}
+
+/// Canonical form of a bare ticker: trimmed, lowercased, aliases resolved.
+pub fn canonical(ticker: &str) -> String {
+    let lower = ticker.trim().to_lowercase();
+    ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == lower)
+        .map(|(_, symbol)| symbol.to_string())
+        .unwrap_or(lower)
+}
+
+/// Map a source's native symbol to the canonical symbol. Returns `None` if
+/// the symbol is not in the source's convention, such as a pair quoted in
+/// something other than USD.
+pub fn to_canonical(source: PriceSource, native: &str) -> Option<String> {
+    let native = native.trim();
+    let symbol = match convention(source) {
+        Convention::Ticker => canonical(native),
+        Convention::Pair { sep, .. } => canonical(strip_usd_quote(native, sep)?),
+        Convention::Kraken => kraken_to_canonical(native)?,
+        Convention::CoinGeckoId => COINGECKO_IDS
+            .iter()
+            .find(|(_, id)| id.eq_ignore_ascii_case(native))
+            .map(|(symbol, _)| symbol.to_string())?,
+    };
+    (!symbol.is_empty()).then_some(symbol)
+}
+
+/// Map a canonical symbol to the source's native symbol. Returns `None` if
+/// the source has no name for it (an unmapped CoinGecko ID).
+pub fn to_native(source: PriceSource, symbol: &str) -> Option<String> {
+    let symbol = canonical(symbol);
+    if symbol.is_empty() {
+        return None;
+    }
+    match convention(source) {
+        Convention::Ticker => Some(symbol.to_uppercase()),
+        Convention::Pair { sep, quote, upper } => {
+            let pair = format!("{}{}{}", symbol, sep, quote);
+            Some(if upper { pair.to_uppercase() } else { pair })
+        }
+        Convention::Kraken => Some(kraken_to_native(&symbol)),
+        Convention::CoinGeckoId => COINGECKO_IDS
+            .iter()
+            .find(|(s, _)| *s == symbol)
+            .map(|(_, id)| id.to_string()),
+    }
+}
+
+/// Whether `symbol` is one of the canonical symbols in a source's pair table.
+pub fn is_tracked(pairs: &[(&str, &str)], symbol: &str) -> bool {
+    pairs.iter().any(|(s, _)| *s == symbol)
+}
+
+/// Strip a USD-like quote from a pair, returning the base.
+fn strip_usd_quote<'a>(pair: &'a str, sep: &str) -> Option<&'a str> {
+    let lower = pair.to_lowercase();
+    USD_QUOTES.iter().find_map(|quote| {
+        let suffix = format!("{}{}", sep, quote);
+        let base_len = lower.strip_suffix(&suffix)?.len();
+        // Lowercasing ASCII tickers keeps byte offsets, so slice the original
+        pair.get(..base_len).filter(|base| !base.is_empty())
+    })
+}
+
+/// Kraken WebSocket pair name for a canonical symbol, e.g. `btc` -> `XBT/USD`.
+pub fn kraken_ws_pair(symbol: &str) -> String {
+    format!("{}/USD", kraken_base(&canonical(symbol)))
+}
+
+fn kraken_to_canonical(pair: &str) -> Option<String> {
+    // The WebSocket feed separates base and quote (XBT/USD); REST does not
+    let upper = pair.to_uppercase().replace('/', "");
+    // Legacy pairs look like XXBTZUSD: X-prefixed asset, Z-prefixed fiat
+    if let Some(base) = upper.strip_suffix("ZUSD") {
+        if base.len() == 4 && base.starts_with('X') {
+            return Some(canonical(&base[1..]));
+        }
+    }
+    strip_usd_quote(&upper, "").map(canonical)
+}
+
+fn kraken_to_native(symbol: &str) -> String {
+    if let Some((_, pair)) = super::kraken::SYMBOL_PAIRS
+        .iter()
+        .find(|(s, _)| *s == symbol)
+    {
+        return pair.to_string();
+    }
+    format!("{}USD", kraken_base(symbol))
+}
+
+/// Kraken's name for an asset: its alias if it has one, uppercased.
+fn kraken_base(symbol: &str) -> String {
+    ALIASES
+        .iter()
+        .find(|(_, s)| *s == symbol)
+        .map(|(alias, _)| *alias)
+        .unwrap_or(symbol)
+        .to_uppercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::{binance, huobi, kraken, kucoin, okx};
+
+    // =========================================================================
+    // COINGECKO_IDS Tests
+    // =========================================================================
+
+    #[test]
+    fn test_symbol_to_id_contains_bitcoin() {
+        let btc = COINGECKO_IDS.iter().find(|(s, _)| *s == "btc");
+        assert!(btc.is_some());
+        assert_eq!(btc.unwrap().1, "bitcoin");
+    }
+
+    #[test]
+    fn test_symbol_to_id_contains_ethereum() {
+        let eth = COINGECKO_IDS.iter().find(|(s, _)| *s == "eth");
+        assert!(eth.is_some());
+        assert_eq!(eth.unwrap().1, "ethereum");
+    }
+
+    #[test]
+    fn test_symbol_to_id_count() {
+        assert!(COINGECKO_IDS.len() >= 20);
+    }
+
+    #[test]
+    fn test_symbol_to_id_lowercase() {
+        for (symbol, _) in COINGECKO_IDS {
+            assert_eq!(*symbol, symbol.to_lowercase());
+        }
+    }
+
+    // =========================================================================
+    // Round-trip Tests
+    // =========================================================================
+
+    #[test]
+    fn test_round_trip_across_conventions() {
+        let cases = [
+            (PriceSource::Coinbase, "btc", "BTC-USD"),
+            (PriceSource::Coinbase, "eth", "ETH-USD"),
+            (PriceSource::Binance, "btc", "BTCUSDT"),
+            (PriceSource::Binance, "doge", "DOGEUSDT"),
+            (PriceSource::Kraken, "btc", "XXBTZUSD"),
+            (PriceSource::Kraken, "doge", "XDGUSD"),
+            (PriceSource::Kraken, "sol", "SOLUSD"),
+            (PriceSource::CoinGecko, "avax", "avalanche-2"),
+            (PriceSource::Alpaca, "aapl", "AAPL"),
+        ];
+        for (source, symbol, native) in cases {
+            assert_eq!(to_native(source, symbol).as_deref(), Some(native));
+            assert_eq!(to_canonical(source, native).as_deref(), Some(symbol));
+        }
+    }
+
+    #[test]
+    fn test_source_pair_tables_round_trip() {
+        let tables: [(PriceSource, &[(&str, &str)]); 5] = [
+            (PriceSource::Binance, binance::SYMBOL_PAIRS),
+            (PriceSource::Huobi, huobi::SYMBOL_PAIRS),
+            (PriceSource::Kraken, kraken::SYMBOL_PAIRS),
+            (PriceSource::KuCoin, kucoin::SYMBOL_PAIRS),
+            (PriceSource::Okx, okx::SYMBOL_PAIRS),
+        ];
+        for (source, pairs) in tables {
+            for (symbol, pair) in pairs {
+                assert_eq!(to_canonical(source, pair).as_deref(), Some(*symbol));
+                assert_eq!(to_native(source, symbol).as_deref(), Some(*pair));
+            }
+        }
+    }
+
+    #[test]
+    fn test_kraken_ws_pairs() {
+        assert_eq!(kraken_ws_pair("btc"), "XBT/USD");
+        assert_eq!(kraken_ws_pair("doge"), "XDG/USD");
+        assert_eq!(kraken_ws_pair("eth"), "ETH/USD");
+        for pair in ["XBT/USD", "XDG/USD", "ETH/USD"] {
+            let symbol = to_canonical(PriceSource::Kraken, pair).unwrap();
+            assert_eq!(kraken_ws_pair(&symbol), pair);
+        }
+    }
+
+    #[test]
+    fn test_aliases_collapse_to_one_symbol() {
+        assert_eq!(
+            to_canonical(PriceSource::Kraken, "XBTUSD").as_deref(),
+            Some("btc")
+        );
+        assert_eq!(
+            to_canonical(PriceSource::CryptoCompare, "XBT").as_deref(),
+            Some("btc")
+        );
+        assert_eq!(
+            to_canonical(PriceSource::Coinbase, "btc-usd").as_deref(),
+            Some("btc")
+        );
+        assert_eq!(
+            to_native(PriceSource::Coinbase, "XBT").as_deref(),
+            Some("BTC-USD")
+        );
+    }
+
+    #[test]
+    fn test_non_usd_pairs_rejected() {
+        assert_eq!(to_canonical(PriceSource::Coinbase, "BTC-EUR"), None);
+        assert_eq!(to_canonical(PriceSource::Binance, "ETHBTC"), None);
+        assert_eq!(to_canonical(PriceSource::Kraken, "XXBTZEUR"), None);
+        assert_eq!(to_canonical(PriceSource::Binance, "USDT"), None);
+        assert_eq!(
+            to_canonical(PriceSource::Kraken, "XTZUSD").as_deref(),
+            Some("xtz")
+        );
+        assert_eq!(to_canonical(PriceSource::CoinGecko, "not-a-coin"), None);
+        assert_eq!(to_canonical(PriceSource::Alpaca, "  "), None);
+    }
+
+    #[test]
+    fn test_usdc_quote_accepted() {
+        assert_eq!(
+            to_canonical(PriceSource::Coinbase, "SOL-USDC").as_deref(),
+            Some("sol")
+        );
+    }
+
+    #[test]
+    fn test_is_tracked() {
+        assert!(is_tracked(binance::SYMBOL_PAIRS, "btc"));
+        assert!(!is_tracked(binance::SYMBOL_PAIRS, "notacoin"));
+    }
+}
@@ -6,6 +6,7 @@
 // Some structs/constants are kept for API completeness
 #![allow(dead_code)]
 
+use super::symbols;
 use crate::services::{ChartStore, PriceCache};
 use crate::types::PriceSource;
 use futures_util::{SinkExt, StreamExt};
@@ -231,7 +232,9 @@ impl TiingoWs {
             return;
         }
 
-        let symbol = ticker.to_lowercase();
+        let Some(symbol) = symbols::to_canonical(PriceSource::Tiingo, ticker) else {
+            return;
+        };
         let timestamp = chrono::Utc::now().timestamp_millis();
 
         match msg_type {
@@ -1,5 +1,6 @@
 use super::TradeDirection;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 /// A cryptocurrency asset with full metadata.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,7 +44,7 @@ pub struct AssetUrls {
 }
 
 /// Quote data for an asset.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Quote {
     pub price: f64,
@@ -77,7 +78,7 @@ pub struct Quote {
 
 /// A simplified asset listing for paginated results.
 /// This matches the frontend's expected Asset type.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AssetListing {
     pub id: i64,
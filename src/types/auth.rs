@@ -70,10 +70,58 @@ pub struct Profile {
     /// Timestamp when leaderboard consent was given
     #[serde(skip_serializing_if = "Option::is_none")]
     pub leaderboard_consent_at: Option<i64>,
+    /// Short user-written bio
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bio: Option<String>,
+    /// Avatar image URL (https only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
     /// User settings
     pub settings: ProfileSettings,
 }
 
+/// Profile fields visible to other users.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicProfile {
+    /// Public key (primary identifier)
+    pub public_key: String,
+    /// Display username
+    pub username: String,
+    /// Short user-written bio
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bio: Option<String>,
+    /// Avatar image URL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+    /// When account was created (ms)
+    pub created_at: i64,
+}
+
+impl From<&Profile> for PublicProfile {
+    fn from(profile: &Profile) -> Self {
+        Self {
+            public_key: profile.public_key.clone(),
+            username: profile.username.clone(),
+            bio: profile.bio.clone(),
+            avatar_url: profile.avatar_url.clone(),
+            created_at: profile.created_at,
+        }
+    }
+}
+
+/// Request to update the bio and avatar. Missing or empty values clear the field.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileDetailsRequest {
+    /// New bio
+    #[serde(default)]
+    pub bio: Option<String>,
+    /// New avatar URL
+    #[serde(default)]
+    pub avatar_url: Option<String>,
+}
+
 /// User settings stored in profile.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -87,6 +135,12 @@ pub struct ProfileSettings {
     /// Notification preferences
     #[serde(default)]
     pub notifications_enabled: bool,
+    /// Also deliver notifications by email/webhook
+    #[serde(default)]
+    pub external_notifications: bool,
+    /// Address for email notifications
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notification_email: Option<String>,
 }
 
 impl Default for ProfileSettings {
@@ -95,6 +149,8 @@ impl Default for ProfileSettings {
             default_timeframe: "day_trading".to_string(),
             preferred_indicators: Vec::new(),
             notifications_enabled: false,
+            external_notifications: false,
+            notification_email: None,
         }
     }
 }
@@ -141,6 +197,8 @@ impl Profile {
             show_on_leaderboard: false,
             leaderboard_signature: None,
             leaderboard_consent_at: None,
+            bio: None,
+            avatar_url: None,
             settings: ProfileSettings::default(),
         }
     }
@@ -351,6 +409,8 @@ mod tests {
             show_on_leaderboard: false,
             leaderboard_signature: None,
             leaderboard_consent_at: None,
+            bio: None,
+            avatar_url: None,
             settings: ProfileSettings::default(),
         };
 
@@ -377,6 +437,7 @@ mod tests {
             default_timeframe: "swing_trading".to_string(),
             preferred_indicators: vec!["RSI".to_string(), "MACD".to_string()],
             notifications_enabled: true,
+            ..Default::default()
         };
 
         assert_eq!(settings.default_timeframe, "swing_trading");
@@ -390,6 +451,7 @@ mod tests {
             default_timeframe: "scalping".to_string(),
             preferred_indicators: vec!["RSI".to_string()],
             notifications_enabled: true,
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&settings).unwrap();
@@ -432,6 +494,8 @@ mod tests {
                 show_on_leaderboard: false,
                 leaderboard_signature: None,
                 leaderboard_consent_at: None,
+                bio: None,
+                avatar_url: None,
                 settings: ProfileSettings::default(),
             },
         };
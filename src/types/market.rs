@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 /// Global cryptocurrency market metrics.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct GlobalMetrics {
     pub total_market_cap: f64,
@@ -24,7 +25,7 @@ pub struct GlobalMetrics {
 }
 
 /// Fear & Greed Index data.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct FearGreedData {
     #[serde(default = "default_fear_greed_value")]
@@ -125,7 +126,7 @@ impl std::str::FromStr for MoverTimeframe {
 }
 
 /// A single mover entry (gainer or loser).
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Mover {
     pub symbol: String,
@@ -136,7 +137,7 @@ pub struct Mover {
 }
 
 /// Response for top movers endpoint.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct MoversResponse {
     pub timeframe: String,
@@ -2,6 +2,7 @@ pub mod asset;
 pub mod auth;
 pub mod chart;
 pub mod market;
+pub mod notification;
 pub mod orderbook;
 pub mod peer;
 pub mod price;
@@ -13,6 +14,7 @@ pub use asset::*;
 pub use auth::*;
 pub use chart::*;
 pub use market::*;
+pub use notification::*;
 pub use orderbook::*;
 pub use peer::*;
 pub use price::*;
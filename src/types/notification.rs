@@ -0,0 +1,78 @@
+//! Notification Types
+//!
+//! In-app notifications for users (trade fills, gridline events, system notices).
+
+use serde::{Deserialize, Serialize};
+
+/// Kind of event a notification reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationType {
+    /// A trade was executed
+    Trade,
+    /// A gridline (price level) was crossed
+    Gridline,
+    /// System or account notice
+    System,
+    /// Account risk warning (e.g. margin call)
+    Warning,
+}
+
+impl std::fmt::Display for NotificationType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NotificationType::Trade => write!(f, "trade"),
+            NotificationType::Gridline => write!(f, "gridline"),
+            NotificationType::System => write!(f, "system"),
+            NotificationType::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A notification stored for a user.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Notification {
+    /// Unique notification ID
+    pub id: String,
+    /// Recipient's public key
+    pub user_id: String,
+    /// Kind of event
+    pub notification_type: NotificationType,
+    /// Short headline
+    pub title: String,
+    /// Full message body
+    pub message: String,
+    /// Whether the user has read it
+    pub read: bool,
+    /// When the notification was created (ms)
+    pub created_at: i64,
+}
+
+impl Notification {
+    /// Create a new unread notification for a user.
+    pub fn new(
+        user_id: String,
+        notification_type: NotificationType,
+        title: String,
+        message: String,
+    ) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            user_id,
+            notification_type,
+            title,
+            message,
+            read: false,
+            created_at: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+}
+
+/// Unread notification count for a user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnreadCount {
+    /// Number of unread notifications
+    pub unread_count: usize,
+}
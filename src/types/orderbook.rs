@@ -77,6 +77,34 @@ pub struct AggregatedOrderBook {
     pub timestamp: i64,
 }
 
+/// A point on a depth chart curve.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepthPoint {
+    /// Price at this level
+    pub price: f64,
+    /// Total quantity from the best price out to and including this level
+    pub cumulative_size: f64,
+}
+
+/// Cumulative bid and ask curves for rendering a depth chart.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepthChart {
+    /// Symbol this chart is for
+    pub symbol: String,
+    /// Bid curve, from the best bid outward (price descending)
+    pub bids: Vec<DepthPoint>,
+    /// Ask curve, from the best ask outward (price ascending)
+    pub asks: Vec<DepthPoint>,
+    /// Mid price; `None` unless both sides have levels
+    pub mid_price: Option<f64>,
+    /// Best ask minus best bid; `None` unless both sides have levels
+    pub spread: Option<f64>,
+    /// Timestamp of the underlying book (unix ms)
+    pub timestamp: i64,
+}
+
 impl AggregatedOrderBook {
     /// Create a new aggregated order book from multiple exchange books.
     pub fn from_exchange_books(
@@ -172,6 +200,36 @@ impl AggregatedOrderBook {
         }
     }
 
+    /// Build cumulative depth curves from this book.
+    ///
+    /// Empty or one-sided books produce empty curves for the missing side and
+    /// no mid price or spread.
+    pub fn depth_chart(&self) -> DepthChart {
+        fn cumulative(levels: &[AggregatedLevel]) -> Vec<DepthPoint> {
+            let mut total = 0.0;
+            levels
+                .iter()
+                .map(|level| {
+                    total += level.total_quantity;
+                    DepthPoint {
+                        price: level.price,
+                        cumulative_size: total,
+                    }
+                })
+                .collect()
+        }
+
+        let two_sided = !self.bids.is_empty() && !self.asks.is_empty();
+        DepthChart {
+            symbol: self.symbol.clone(),
+            bids: cumulative(&self.bids),
+            asks: cumulative(&self.asks),
+            mid_price: two_sided.then_some(self.mid_price),
+            spread: two_sided.then_some(self.spread),
+            timestamp: self.timestamp,
+        }
+    }
+
     /// Convert price to a u64 key for aggregation (preserves 4 decimal places).
     fn price_to_key(price: f64) -> u64 {
         (price * 10000.0).round() as u64
@@ -567,4 +625,63 @@ mod tests {
         assert_eq!(AggregatedOrderBook::price_to_key(1.2345), 12345);
         assert_eq!(AggregatedOrderBook::price_to_key(50000.0), 500000000);
     }
+
+    #[test]
+    fn test_depth_chart_cumulative_sizes() {
+        let level = |price: f64, quantity: f64| OrderBookLevel { price, quantity };
+        let book = ExchangeOrderBook {
+            exchange: PriceSource::Coinbase,
+            symbol: "BTC".to_string(),
+            bids: vec![level(99.0, 1.0), level(98.0, 2.0), level(97.0, 0.5)],
+            asks: vec![level(101.0, 0.5), level(102.0, 1.5), level(103.0, 3.0)],
+            timestamp: 1704067200000,
+        };
+        let second = ExchangeOrderBook {
+            exchange: PriceSource::Kraken,
+            symbol: "BTC".to_string(),
+            bids: vec![level(98.0, 1.0)],
+            asks: vec![level(102.0, 0.5)],
+            timestamp: 1704067200000,
+        };
+
+        let chart = AggregatedOrderBook::from_exchange_books("BTC", vec![book, second], 10)
+            .depth_chart();
+
+        let points = |curve: &[DepthPoint]| -> Vec<(f64, f64)> {
+            curve.iter().map(|p| (p.price, p.cumulative_size)).collect()
+        };
+        assert_eq!(points(&chart.bids), vec![(99.0, 1.0), (98.0, 4.0), (97.0, 4.5)]);
+        assert_eq!(points(&chart.asks), vec![(101.0, 0.5), (102.0, 2.5), (103.0, 5.5)]);
+
+        for curve in [&chart.bids, &chart.asks] {
+            assert!(curve.windows(2).all(|w| w[1].cumulative_size >= w[0].cumulative_size));
+        }
+        assert_eq!(chart.mid_price, Some(100.0));
+        assert_eq!(chart.spread, Some(2.0));
+    }
+
+    #[test]
+    fn test_depth_chart_empty_and_one_sided() {
+        let chart = AggregatedOrderBook::empty("ETH").depth_chart();
+        assert!(chart.bids.is_empty() && chart.asks.is_empty());
+        assert_eq!(chart.mid_price, None);
+        assert_eq!(chart.spread, None);
+
+        let book = ExchangeOrderBook {
+            exchange: PriceSource::Coinbase,
+            symbol: "ETH".to_string(),
+            bids: vec![OrderBookLevel {
+                price: 3000.0,
+                quantity: 2.0,
+            }],
+            asks: Vec::new(),
+            timestamp: 1704067200000,
+        };
+        let chart = AggregatedOrderBook::from_exchange_books("ETH", vec![book], 10).depth_chart();
+        assert_eq!(chart.bids.len(), 1);
+        assert_eq!(chart.bids[0].cumulative_size, 2.0);
+        assert!(chart.asks.is_empty());
+        assert_eq!(chart.mid_price, None);
+        assert_eq!(chart.spread, None);
+    }
 }
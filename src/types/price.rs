@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use utoipa::ToSchema;
 
 /// Trade direction indicator.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum TradeDirection {
     Up,
@@ -10,7 +11,7 @@ pub enum TradeDirection {
 }
 
 /// Price source identifier.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum PriceSource {
     // Crypto sources
@@ -93,6 +94,22 @@ pub struct SourcePrice {
     pub volume_24h: Option<f64>,
 }
 
+/// A source price rejected by a symbol's circuit breaker.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CircuitBreakerTrip {
+    pub symbol: String,
+    pub source: PriceSource,
+    /// The rejected price.
+    pub price: f64,
+    /// Aggregate of the other sources' recent prices.
+    pub consensus_price: f64,
+    /// Signed deviation of `price` from `consensus_price` (percent).
+    pub deviation_pct: f64,
+    /// Source timestamp of the rejected price (unix ms).
+    pub timestamp: i64,
+}
+
 /// Aggregated price from multiple sources.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -122,6 +139,11 @@ pub struct AggregationConfig {
     pub throttle_ms: u64,
     /// Time after which a source price is considered stale (ms).
     pub stale_threshold_ms: u64,
+    /// Deviation from the other sources' recent prices (percent) at which a
+    /// source update is quarantined; 0 disables the circuit breaker.
+    pub circuit_breaker_pct: f64,
+    /// Window (ms) for consensus prices and for corroborating a quarantined one.
+    pub circuit_breaker_window_ms: u64,
 }
 
 impl Default for AggregationConfig {
@@ -130,6 +152,8 @@ impl Default for AggregationConfig {
             change_threshold: 0.01, // 0.01%
             throttle_ms: 100,
             stale_threshold_ms: 120_000, // 2 minutes
+            circuit_breaker_pct: 20.0,
+            circuit_breaker_window_ms: 10_000,
         }
     }
 }
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
 /// Trading timeframe/style for signal calculations.
@@ -146,6 +147,9 @@ pub struct SignalOutput {
     pub category: SignalCategory,
     /// Raw indicator value.
     pub value: f64,
+    /// Named sub-values for indicators with more than one line.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub components: Option<BTreeMap<String, f64>>,
     /// Normalized score from -100 (strong sell) to +100 (strong buy).
     pub score: i8,
     /// Signal direction derived from score.
@@ -187,6 +191,181 @@ pub struct SymbolSignals {
     pub timestamp: i64,
 }
 
+/// Result of computing a single indicator for the raw indicators endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum RawIndicatorValue {
+    /// The indicator was computed.
+    #[serde(rename_all = "camelCase")]
+    Ok { output: SignalOutput },
+    /// Not enough candles to compute the indicator.
+    #[serde(rename_all = "camelCase")]
+    InsufficientData { required: usize, available: usize },
+    /// Enough candles, but the calculation produced no value.
+    Unavailable,
+}
+
+/// Raw output of one registered indicator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawIndicator {
+    /// Indicator ID (e.g., "rsi").
+    pub id: String,
+    /// Human-readable name.
+    pub name: String,
+    /// Category this indicator belongs to.
+    pub category: SignalCategory,
+    /// Computed output or the reason it is missing.
+    #[serde(flatten)]
+    pub result: RawIndicatorValue,
+}
+
+/// Raw indicator values for a symbol.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawIndicators {
+    /// Symbol this data is for.
+    pub symbol: String,
+    /// Trading timeframe used for calculations.
+    pub timeframe: TradingTimeframe,
+    /// Number of candles the indicators were computed from.
+    pub candle_count: usize,
+    /// Every registered indicator, in registration order.
+    pub indicators: Vec<RawIndicator>,
+    /// Unix timestamp (milliseconds) when calculated.
+    pub timestamp: i64,
+}
+
+/// Pivot point calculation method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PivotType {
+    /// Floor trader pivots: levels offset by the prior range from the pivot and extremes.
+    #[default]
+    Classic,
+    /// Levels at 38.2%, 61.8% and 100% of the prior range around the pivot.
+    Fibonacci,
+}
+
+impl PivotType {
+    /// Parse from string.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "classic" | "standard" | "floor" => Some(Self::Classic),
+            "fibonacci" | "fib" => Some(Self::Fibonacci),
+            _ => None,
+        }
+    }
+}
+
+/// Pivot point support and resistance levels.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PivotLevels {
+    pub pivot: f64,
+    pub r1: f64,
+    pub r2: f64,
+    pub r3: f64,
+    pub s1: f64,
+    pub s2: f64,
+    pub s3: f64,
+}
+
+/// Daily pivot points for a symbol, derived from the prior day's candle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PivotPoints {
+    /// Symbol this data is for.
+    pub symbol: String,
+    /// Calculation method used.
+    pub pivot_type: PivotType,
+    /// Start of the prior period (unix seconds).
+    pub period_start: i64,
+    /// Prior period high.
+    pub high: f64,
+    /// Prior period low.
+    pub low: f64,
+    /// Prior period close.
+    pub close: f64,
+    /// Computed pivot, resistance and support levels.
+    pub levels: PivotLevels,
+    /// Unix timestamp (milliseconds) when calculated.
+    pub timestamp: i64,
+}
+
+/// A single Fibonacci level.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FibLevel {
+    /// Fibonacci ratio (e.g., 0.618).
+    pub ratio: f64,
+    /// Price at this level.
+    pub price: f64,
+}
+
+/// Fibonacci retracement and extension levels for a price swing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FibonacciLevels {
+    /// Swing high price.
+    pub swing_high: f64,
+    /// Swing low price.
+    pub swing_low: f64,
+    /// True if the swing low came before the swing high.
+    pub uptrend: bool,
+    /// Retracement levels, measured back from the end of the swing.
+    pub retracements: Vec<FibLevel>,
+    /// Extension levels, projected beyond the end of the swing.
+    pub extensions: Vec<FibLevel>,
+}
+
+/// Fibonacci levels for a symbol from a swing detected over a chart range.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolFibonacci {
+    /// Symbol this data is for.
+    pub symbol: String,
+    /// Chart range the swing was detected over.
+    pub range: crate::types::ChartRange,
+    /// Time of the swing high candle (unix seconds).
+    pub high_time: i64,
+    /// Time of the swing low candle (unix seconds).
+    pub low_time: i64,
+    /// Computed levels.
+    #[serde(flatten)]
+    pub levels: FibonacciLevels,
+    /// Unix timestamp (milliseconds) when calculated.
+    pub timestamp: i64,
+}
+
+/// Volatility-based stop-loss suggestion for a position.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StopSuggestion {
+    /// Symbol this data is for.
+    pub symbol: String,
+    /// Side of the position being protected.
+    pub side: crate::types::PositionSide,
+    /// Trading timeframe the candles were taken from.
+    pub timeframe: TradingTimeframe,
+    /// Entry price the stop is measured from.
+    pub entry: f64,
+    /// ATR period used.
+    pub atr_period: usize,
+    /// Average True Range in price units.
+    pub atr: f64,
+    /// ATR multiplier applied.
+    pub multiplier: f64,
+    /// Suggested stop price: entry - mult * ATR for longs, entry + mult * ATR for shorts.
+    pub stop_price: f64,
+    /// Loss per unit if the stop is hit.
+    pub risk_per_unit: f64,
+    /// Risk per unit as a percentage of the entry price.
+    pub risk_pct: f64,
+    /// Unix timestamp (milliseconds) when calculated.
+    pub timestamp: i64,
+}
+
 /// Outcome of a validated prediction.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -411,6 +590,29 @@ pub struct AccuracyResponse {
     pub timestamp: i64,
 }
 
+/// Accuracy of one indicator across all symbols for a validation timeframe.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndicatorAccuracyBreakdown {
+    pub indicator: String,
+    pub total_predictions: i64,
+    pub correct_predictions: i64,
+    pub incorrect_predictions: i64,
+    pub neutral_predictions: i64,
+    /// Correct share of decided (non-neutral) predictions (0.0-1.0)
+    pub accuracy: f64,
+}
+
+/// Response for the per-indicator accuracy endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndicatorAccuracyResponse {
+    /// Validation timeframe the outcomes were measured over (5m, 1h, 4h, 24h)
+    pub timeframe: String,
+    pub indicators: Vec<IndicatorAccuracyBreakdown>,
+    pub timestamp: i64,
+}
+
 /// Response for predictions endpoint.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -3,13 +3,14 @@
 //! Types for paper trading system including portfolios, orders, positions, and trades.
 
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 // =============================================================================
 // Enums
 // =============================================================================
 
 /// Asset type for trading.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum AssetClass {
     /// Cryptocurrency spot trading
@@ -78,7 +79,7 @@ impl std::fmt::Display for AssetClass {
 }
 
 /// Order side (buy or sell).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum OrderSide {
     Buy,
@@ -95,7 +96,7 @@ impl std::fmt::Display for OrderSide {
 }
 
 /// Order type.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum OrderType {
     /// Execute immediately at best available price
@@ -126,7 +127,7 @@ impl std::fmt::Display for OrderType {
 }
 
 /// Order status.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum OrderStatus {
     /// Order is pending, waiting for conditions
@@ -160,7 +161,7 @@ impl std::fmt::Display for OrderStatus {
 }
 
 /// Time in force for orders.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum TimeInForce {
     /// Good till cancelled - remains active until filled or cancelled
@@ -191,7 +192,7 @@ impl std::fmt::Display for TimeInForce {
 }
 
 /// Position side (long or short).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum PositionSide {
     Long,
@@ -208,7 +209,7 @@ impl std::fmt::Display for PositionSide {
 }
 
 /// Margin mode for leveraged positions.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum MarginMode {
     /// Each position has independent margin
@@ -233,7 +234,7 @@ impl std::fmt::Display for MarginMode {
 }
 
 /// Cost basis calculation method.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum CostBasisMethod {
     /// First in, first out
@@ -265,7 +266,7 @@ impl std::fmt::Display for CostBasisMethod {
 // =============================================================================
 
 /// Risk settings for a portfolio.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RiskSettings {
     /// Maximum position size as percentage of portfolio (0.0-1.0)
@@ -283,6 +284,14 @@ pub struct RiskSettings {
     /// Portfolio stop - pause trading at this drawdown percentage
     #[serde(default = "default_portfolio_stop")]
     pub portfolio_stop_pct: f64,
+    /// Maximum exposure to any one symbol as percentage of portfolio value
+    /// (0.0-1.0); no cap when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_symbol_concentration_pct: Option<f64>,
+    /// Drawdown from peak equity (0.0-1.0) at which the portfolio's active
+    /// strategies are paused until manually reactivated; disabled when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_drawdown_pct: Option<f64>,
 }
 
 fn default_max_position_size() -> f64 { 0.25 }
@@ -299,12 +308,14 @@ impl Default for RiskSettings {
             max_open_positions: default_max_open_positions(),
             risk_per_trade_pct: default_risk_per_trade(),
             portfolio_stop_pct: default_portfolio_stop(),
+            max_symbol_concentration_pct: None,
+            max_drawdown_pct: None,
         }
     }
 }
 
 /// User's paper trading portfolio.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Portfolio {
     /// Unique portfolio ID
@@ -357,6 +368,9 @@ pub struct Portfolio {
     pub updated_at: i64,
 }
 
+/// Balance new portfolios start with.
+pub const DEFAULT_STARTING_BALANCE: f64 = 250_000.0;
+
 fn default_base_currency() -> String {
     "USD".to_string()
 }
@@ -365,7 +379,7 @@ impl Portfolio {
     /// Create a new portfolio with default starting balance of $250,000.
     pub fn new(user_id: String, name: String) -> Self {
         let now = chrono::Utc::now().timestamp_millis();
-        let starting_balance = 250_000.0;
+        let starting_balance = DEFAULT_STARTING_BALANCE;
 
         Self {
             id: uuid::Uuid::new_v4().to_string(),
@@ -437,7 +451,7 @@ impl Portfolio {
 // =============================================================================
 
 /// A single fill event for an order.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Fill {
     /// Unique fill ID
@@ -466,7 +480,7 @@ impl Fill {
 }
 
 /// A trading order.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Order {
     /// Unique order ID
@@ -539,10 +553,13 @@ pub struct Order {
     /// Whether this is part of a bracket order (entry, stop_loss, or take_profit)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bracket_role: Option<BracketRole>,
+    /// Visible slice size for iceberg orders; the rest of the quantity stays hidden
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_quantity: Option<f64>,
 }
 
 /// Role in a bracket order.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum BracketRole {
     /// Entry order for the bracket
@@ -603,6 +620,7 @@ impl Order {
             trail_high_price: None,
             trail_low_price: None,
             bracket_role: None,
+            display_quantity: None,
         }
     }
 
@@ -672,6 +690,23 @@ impl Order {
         self.quantity - self.filled_quantity
     }
 
+    /// Check if this is an iceberg order.
+    pub fn is_iceberg(&self) -> bool {
+        self.display_quantity.is_some()
+    }
+
+    /// Quantity currently eligible to fill.
+    ///
+    /// For iceberg orders this is the visible slice, replenished from the
+    /// hidden remainder after each fill; otherwise the full remaining quantity.
+    pub fn visible_quantity(&self) -> f64 {
+        let remaining = self.remaining_quantity();
+        match self.display_quantity {
+            Some(display) => display.min(remaining),
+            None => remaining,
+        }
+    }
+
     /// Add a fill to this order.
     pub fn add_fill(&mut self, fill: Fill) {
         self.filled_quantity += fill.quantity;
@@ -743,39 +778,45 @@ impl Order {
     }
 
     /// Update trailing stop based on current price.
-    /// Returns true if stop price was updated.
+    ///
+    /// The first price seen seeds the reference high (sell) or low (buy).
+    /// After that the stop only ratchets in the position's favour: it follows
+    /// new highs (or lows) and never loosens on a retrace.
+    /// Returns true if the tracked reference price was updated.
     pub fn update_trailing_stop(&mut self, current_price: f64) -> bool {
         if self.order_type != OrderType::TrailingStop {
             return false;
         }
 
-        let mut updated = false;
-
         match self.side {
             OrderSide::Sell => {
                 // For sell trailing stop, update if price makes new high
-                let current_high = self.trail_high_price.unwrap_or(current_price);
-                if current_price > current_high {
-                    self.trail_high_price = Some(current_price);
-                    self.stop_price = Some(self.calculate_trailing_stop_price(current_price));
-                    updated = true;
+                if self
+                    .trail_high_price
+                    .is_some_and(|high| current_price <= high)
+                {
+                    return false;
                 }
+                self.trail_high_price = Some(current_price);
             }
             OrderSide::Buy => {
                 // For buy trailing stop, update if price makes new low
-                let current_low = self.trail_low_price.unwrap_or(current_price);
-                if current_price < current_low {
-                    self.trail_low_price = Some(current_price);
-                    self.stop_price = Some(self.calculate_trailing_stop_price(current_price));
-                    updated = true;
+                if self.trail_low_price.is_some_and(|low| current_price >= low) {
+                    return false;
                 }
+                self.trail_low_price = Some(current_price);
             }
         }
 
-        if updated {
-            self.updated_at = chrono::Utc::now().timestamp_millis();
-        }
-        updated
+        // Never move the stop against the position
+        let candidate = self.calculate_trailing_stop_price(current_price);
+        self.stop_price = Some(match (self.side, self.stop_price) {
+            (OrderSide::Sell, Some(stop)) => stop.max(candidate),
+            (OrderSide::Buy, Some(stop)) => stop.min(candidate),
+            (_, None) => candidate,
+        });
+        self.updated_at = chrono::Utc::now().timestamp_millis();
+        true
     }
 
     /// Check if a GTD order has expired.
@@ -991,6 +1032,189 @@ impl OcoOrder {
     }
 }
 
+// =============================================================================
+// Execution Algorithm Types
+// =============================================================================
+
+/// Maximum number of child orders in one execution schedule.
+pub const MAX_EXECUTION_SLICES: u32 = 100;
+
+/// Longest an execution schedule may run (24 hours, in seconds).
+pub const MAX_EXECUTION_DURATION_SECS: u64 = 86_400;
+
+/// Algorithm for working a large order over time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionAlgo {
+    /// Time-weighted: equal slices released at a fixed interval
+    Twap,
+    /// Volume-weighted: slices sized by the symbol's volume profile
+    Vwap,
+}
+
+impl std::fmt::Display for ExecutionAlgo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExecutionAlgo::Twap => write!(f, "twap"),
+            ExecutionAlgo::Vwap => write!(f, "vwap"),
+        }
+    }
+}
+
+/// Execution schedule status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionStatus {
+    /// Slices are still being released
+    Active,
+    /// Every slice has been released
+    Completed,
+    /// The unreleased remainder was cancelled
+    Cancelled,
+}
+
+impl std::fmt::Display for ExecutionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExecutionStatus::Active => write!(f, "active"),
+            ExecutionStatus::Completed => write!(f, "completed"),
+            ExecutionStatus::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+/// One child order of an execution schedule.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionSlice {
+    /// Quantity of the child order
+    pub quantity: f64,
+    /// When the child order is released (ms)
+    pub release_at: i64,
+    /// Child order placed for this slice, once released
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<String>,
+}
+
+/// A parent order worked as child market orders released on a schedule.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionSchedule {
+    /// Unique schedule ID (the parent order)
+    pub id: String,
+    /// Portfolio the child orders are placed in
+    pub portfolio_id: String,
+    /// Symbol being traded
+    pub symbol: String,
+    /// Asset class
+    pub asset_class: AssetClass,
+    /// Buy or sell
+    pub side: OrderSide,
+    /// Slicing algorithm
+    pub algo: ExecutionAlgo,
+    /// Total quantity of the parent order
+    pub quantity: f64,
+    /// Leverage for each child order
+    pub leverage: f64,
+    /// How long execution is spread over, in seconds
+    pub duration_secs: u64,
+    /// Child orders in release order
+    pub slices: Vec<ExecutionSlice>,
+    /// Current status
+    pub status: ExecutionStatus,
+    /// When the schedule was created (ms)
+    pub created_at: i64,
+    /// When the schedule was last updated (ms)
+    pub updated_at: i64,
+}
+
+impl ExecutionSchedule {
+    /// Build a schedule from a request, splitting its quantity across one
+    /// slice per weight in proportion to the weights. Slice `i` is released
+    /// `i * duration / slices` after `now`. Zero-weight slices are dropped;
+    /// if every weight is zero the slices are equal.
+    pub fn new(request: &ExecutionAlgoRequest, weights: &[f64], now: i64) -> Self {
+        let count = weights.len().max(1);
+        let weights: Vec<f64> = if weights.iter().sum::<f64>() > 0.0 {
+            weights.to_vec()
+        } else {
+            vec![1.0; count]
+        };
+        let total: f64 = weights.iter().sum();
+        let interval_ms = (request.duration_secs * 1000 / count as u64) as i64;
+        let last = weights.iter().rposition(|w| *w > 0.0).unwrap_or(0);
+
+        // The last slice takes the remainder so the slices sum to the parent
+        let mut allocated = 0.0;
+        let mut slices = Vec::with_capacity(count);
+        for (i, weight) in weights.iter().enumerate() {
+            if *weight <= 0.0 {
+                continue;
+            }
+            let quantity = if i == last {
+                request.quantity - allocated
+            } else {
+                request.quantity * weight / total
+            };
+            allocated += quantity;
+            slices.push(ExecutionSlice {
+                quantity,
+                release_at: now + interval_ms * i as i64,
+                order_id: None,
+            });
+        }
+
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            portfolio_id: request.portfolio_id.clone(),
+            symbol: request.symbol.clone(),
+            asset_class: request.asset_class,
+            side: request.side,
+            algo: request.algo,
+            quantity: request.quantity,
+            leverage: request.leverage.unwrap_or(1.0),
+            duration_secs: request.duration_secs,
+            slices,
+            status: ExecutionStatus::Active,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Quantity released as child orders so far.
+    pub fn released_quantity(&self) -> f64 {
+        self.slices
+            .iter()
+            .filter(|s| s.order_id.is_some())
+            .map(|s| s.quantity)
+            .sum()
+    }
+
+    /// Client order ID of the child order for a slice. Placement is
+    /// idempotent on it, so a slice is never released twice.
+    pub fn child_client_order_id(&self, index: usize) -> String {
+        format!("{}:{}", self.id, index)
+    }
+}
+
+/// Request to work an order with an execution algorithm.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionAlgoRequest {
+    pub portfolio_id: String,
+    pub symbol: String,
+    pub asset_class: AssetClass,
+    pub side: OrderSide,
+    pub quantity: f64,
+    pub algo: ExecutionAlgo,
+    /// How long to spread execution over, in seconds
+    pub duration_secs: u64,
+    /// Number of child orders
+    pub slices: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub leverage: Option<f64>,
+}
+
 // =============================================================================
 // Perpetual Futures Types
 // =============================================================================
@@ -1056,6 +1280,12 @@ impl LeverageTier {
     }
 }
 
+/// Time between perpetual funding settlements (8 hours, in ms).
+pub const FUNDING_INTERVAL_MS: i64 = 8 * 60 * 60 * 1000;
+
+/// Most funding rate points returned by one history query.
+pub const MAX_FUNDING_HISTORY_POINTS: usize = 500;
+
 /// Funding rate for a perpetual futures symbol.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -1084,8 +1314,7 @@ impl FundingRate {
     pub fn new(symbol: String, rate: f64, index_price: f64, mark_price: f64) -> Self {
         let now = chrono::Utc::now().timestamp_millis();
         // Calculate next funding time (00:00, 08:00, 16:00 UTC)
-        let eight_hours_ms = 8 * 60 * 60 * 1000;
-        let next_funding_time = ((now / eight_hours_ms) + 1) * eight_hours_ms;
+        let next_funding_time = ((now / FUNDING_INTERVAL_MS) + 1) * FUNDING_INTERVAL_MS;
 
         Self {
             symbol,
@@ -1094,7 +1323,7 @@ impl FundingRate {
             index_price,
             mark_price,
             next_funding_time,
-            funding_interval_ms: eight_hours_ms,
+            funding_interval_ms: FUNDING_INTERVAL_MS,
             timestamp: now,
         }
     }
@@ -1119,7 +1348,7 @@ impl FundingRate {
 }
 
 /// A funding payment record.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct FundingPayment {
     /// Unique payment ID
@@ -1171,6 +1400,38 @@ impl FundingPayment {
     }
 }
 
+/// A funding rate applied at a single settlement.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FundingRatePoint {
+    /// Funding rate applied
+    pub rate: f64,
+    /// Settlement timestamp
+    pub timestamp: i64,
+}
+
+/// Recent funding rate history for a symbol.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FundingRateHistory {
+    /// Symbol
+    pub symbol: String,
+    /// Applied rates, newest first
+    pub rates: Vec<FundingRatePoint>,
+}
+
+/// Funding accrued by a single position.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionFunding {
+    /// Position ID
+    pub position_id: String,
+    /// Payments, newest first
+    pub payments: Vec<FundingPayment>,
+    /// Sum of all payments (positive = net paid, negative = net received)
+    pub total_funding: f64,
+}
+
 // =============================================================================
 // Liquidation Types
 // =============================================================================
@@ -1505,7 +1766,7 @@ impl AdlEntry {
 // =============================================================================
 
 /// Option type (Call or Put).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum OptionType {
     /// Right to buy at strike price
@@ -1524,7 +1785,7 @@ impl std::fmt::Display for OptionType {
 }
 
 /// Option exercise style.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum OptionStyle {
     /// Can be exercised any time before expiration
@@ -1549,7 +1810,7 @@ impl std::fmt::Display for OptionStyle {
 }
 
 /// Greeks for an option position.
-#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Greeks {
     /// Price sensitivity to underlying price change (dV/dS)
@@ -1572,7 +1833,7 @@ impl Greeks {
 }
 
 /// An option contract from an options chain.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OptionContract {
     /// Contract symbol (e.g., "AAPL230120C00150000")
@@ -1694,7 +1955,7 @@ impl OptionContract {
 }
 
 /// An options chain for a specific underlying and expiration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OptionsChain {
     /// Underlying symbol
@@ -1778,12 +2039,22 @@ pub struct OptionPosition {
     pub entry_iv: f64,
     /// Current implied volatility
     pub current_iv: f64,
+    /// Margin reserved for a short position
+    #[serde(default)]
+    pub margin_used: f64,
     /// When position was opened
     pub created_at: i64,
     /// When position was last updated
     pub updated_at: i64,
 }
 
+/// Share of the underlying value required as margin on a naked short option.
+pub const SHORT_OPTION_MARGIN_PCT: f64 = 0.20;
+
+/// Minimum margin on a naked short option, as a share of the underlying value
+/// for calls and of the strike for puts.
+pub const SHORT_OPTION_MIN_MARGIN_PCT: f64 = 0.10;
+
 impl OptionPosition {
     /// Create a new option position.
     pub fn new(
@@ -1812,6 +2083,7 @@ impl OptionPosition {
             greeks: contract.greeks,
             entry_iv: contract.implied_volatility,
             current_iv: contract.implied_volatility,
+            margin_used: 0.0,
             created_at: now,
             updated_at: now,
         }
@@ -1852,6 +2124,36 @@ impl OptionPosition {
         self.updated_at = chrono::Utc::now().timestamp_millis();
     }
 
+    /// Margin required to hold this position at the current premium and
+    /// underlying price. Long positions are paid for in full and need none.
+    ///
+    /// Uses the standard naked option formula: premium plus 20% of the
+    /// underlying less any out-of-the-money amount, with a floor of 10% of the
+    /// underlying (calls) or strike (puts).
+    pub fn margin_requirement(&self) -> f64 {
+        if !self.is_short() {
+            return 0.0;
+        }
+
+        let underlying = self.underlying_price;
+        let per_share = match self.option_type {
+            OptionType::Call => {
+                let otm = (self.strike - underlying).max(0.0);
+                (SHORT_OPTION_MARGIN_PCT * underlying - otm)
+                    .max(SHORT_OPTION_MIN_MARGIN_PCT * underlying)
+            }
+            OptionType::Put => {
+                let otm = (underlying - self.strike).max(0.0);
+                (SHORT_OPTION_MARGIN_PCT * underlying - otm)
+                    .max(SHORT_OPTION_MIN_MARGIN_PCT * self.strike)
+            }
+        };
+
+        (per_share + self.current_premium)
+            * self.contracts.unsigned_abs() as f64
+            * self.multiplier as f64
+    }
+
     /// Check if the option is in the money.
     pub fn is_itm(&self) -> bool {
         match self.option_type {
@@ -2096,7 +2398,7 @@ impl OptionStrategy {
 // =============================================================================
 
 /// A cost basis entry for tracking purchase lots.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CostBasisEntry {
     /// Quantity in this lot
@@ -2108,7 +2410,7 @@ pub struct CostBasisEntry {
 }
 
 /// An open trading position.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Position {
     /// Unique position ID
@@ -2351,6 +2653,19 @@ impl Position {
         Ok(())
     }
 
+    /// Maintenance margin required as a fraction of current equity.
+    ///
+    /// 1.0 means equity has fallen to the maintenance requirement; infinite
+    /// once equity is exhausted.
+    pub fn margin_ratio(&self) -> f64 {
+        let equity = self.margin_used + self.unrealized_pnl;
+        if equity > 0.0 {
+            self.maintenance_margin_required() / equity
+        } else {
+            f64::INFINITY
+        }
+    }
+
     /// Get the required maintenance margin for this position.
     pub fn maintenance_margin_required(&self) -> f64 {
         if self.asset_class == AssetClass::Perp {
@@ -2367,7 +2682,7 @@ impl Position {
 // =============================================================================
 
 /// A completed trade (execution record).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Trade {
     /// Unique trade ID
@@ -2456,7 +2771,7 @@ pub struct CreatePortfolioRequest {
 }
 
 /// Request to place an order.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PlaceOrderRequest {
     pub portfolio_id: String,
@@ -2486,10 +2801,13 @@ pub struct PlaceOrderRequest {
     /// Bypass drawdown protection for this order (one-time override)
     #[serde(default)]
     pub bypass_drawdown: bool,
+    /// Visible slice size; makes a limit order an iceberg order
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_quantity: Option<f64>,
 }
 
 /// Request to modify a position.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ModifyPositionRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -2499,7 +2817,7 @@ pub struct ModifyPositionRequest {
 }
 
 /// Summary of portfolio performance.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct PortfolioSummary {
     pub portfolio_id: String,
@@ -2516,7 +2834,7 @@ pub struct PortfolioSummary {
 }
 
 /// Leaderboard entry for portfolio rankings.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct LeaderboardEntry {
     pub portfolio_id: String,
@@ -2533,6 +2851,142 @@ pub struct LeaderboardEntry {
     pub open_positions: u32,
 }
 
+/// Time window a leaderboard is ranked over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardWindow {
+    /// Trades in the last 24 hours
+    Daily,
+    /// Trades in the last 7 days
+    Weekly,
+    /// Lifetime portfolio performance
+    #[default]
+    AllTime,
+}
+
+impl LeaderboardWindow {
+    /// Parse from a string.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "daily" | "day" | "1d" => Some(LeaderboardWindow::Daily),
+            "weekly" | "week" | "1w" => Some(LeaderboardWindow::Weekly),
+            "all_time" | "alltime" | "all" => Some(LeaderboardWindow::AllTime),
+            _ => None,
+        }
+    }
+
+    /// Window length in milliseconds (None for all-time).
+    pub fn duration_ms(&self) -> Option<i64> {
+        match self {
+            LeaderboardWindow::Daily => Some(86_400_000),
+            LeaderboardWindow::Weekly => Some(7 * 86_400_000),
+            LeaderboardWindow::AllTime => None,
+        }
+    }
+
+    /// Cache key for this window.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LeaderboardWindow::Daily => "daily",
+            LeaderboardWindow::Weekly => "weekly",
+            LeaderboardWindow::AllTime => "all_time",
+        }
+    }
+}
+
+/// Lifecycle stage of a trading competition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CompetitionStatus {
+    /// Enrollment open, trading not yet started
+    Upcoming,
+    /// Trading window is open
+    Active,
+    /// Trading window has closed
+    Ended,
+}
+
+/// A trading competition between portfolios over a fixed window.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Competition {
+    /// Unique competition ID
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Balance every enrolled portfolio starts with
+    pub starting_balance: f64,
+    /// When trading opens (ms)
+    pub start_time: i64,
+    /// When trading closes (ms)
+    pub end_time: i64,
+    /// When the competition was created (ms)
+    pub created_at: i64,
+}
+
+impl Competition {
+    /// Create a new competition.
+    pub fn new(name: String, starting_balance: f64, start_time: i64, end_time: i64) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            name,
+            starting_balance,
+            start_time,
+            end_time,
+            created_at: chrono::Utc::now().timestamp_millis(),
+        }
+    }
+
+    /// Lifecycle stage at time `now` (ms).
+    pub fn status_at(&self, now: i64) -> CompetitionStatus {
+        if now < self.start_time {
+            CompetitionStatus::Upcoming
+        } else if now < self.end_time {
+            CompetitionStatus::Active
+        } else {
+            CompetitionStatus::Ended
+        }
+    }
+
+    /// Current lifecycle stage.
+    pub fn status(&self) -> CompetitionStatus {
+        self.status_at(chrono::Utc::now().timestamp_millis())
+    }
+}
+
+/// Request to create a competition.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCompetitionRequest {
+    pub name: String,
+    /// Defaults to the standard $250,000 portfolio balance
+    pub starting_balance: Option<f64>,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+/// Ranked results of a competition.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CompetitionStandings {
+    pub competition: Competition,
+    pub status: CompetitionStatus,
+    /// Enrolled portfolios ranked by realized return within the window
+    pub standings: Vec<LeaderboardEntry>,
+}
+
+/// Realized trade results for one portfolio over a time window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeWindowStats {
+    pub portfolio_id: String,
+    /// Sum of realized P&L from trades in the window
+    pub realized_pnl: f64,
+    /// Number of trades in the window
+    pub total_trades: u64,
+    /// Trades in the window that closed at a profit
+    pub winning_trades: u64,
+}
+
 /// Order query parameters.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -2608,7 +3062,10 @@ pub enum IndicatorType {
     Macd,
     Ema,
     Sma,
+    /// Bollinger middle band
     Bollinger,
+    BollingerUpper,
+    BollingerLower,
     Atr,
     Adx,
     Stochastic,
@@ -2627,6 +3084,8 @@ impl std::fmt::Display for IndicatorType {
             IndicatorType::Ema => write!(f, "ema"),
             IndicatorType::Sma => write!(f, "sma"),
             IndicatorType::Bollinger => write!(f, "bollinger"),
+            IndicatorType::BollingerUpper => write!(f, "bollinger_upper"),
+            IndicatorType::BollingerLower => write!(f, "bollinger_lower"),
             IndicatorType::Atr => write!(f, "atr"),
             IndicatorType::Adx => write!(f, "adx"),
             IndicatorType::Stochastic => write!(f, "stochastic"),
@@ -2775,6 +3234,13 @@ impl RuleCondition {
         self.period = Some(period);
         self
     }
+
+    /// Compare against another indicator instead of the fixed value.
+    pub fn against(mut self, indicator: IndicatorType, period: Option<u32>) -> Self {
+        self.compare_indicator = Some(indicator);
+        self.compare_period = period;
+        self
+    }
 }
 
 /// Action to take when rule conditions are met.
@@ -2827,6 +3293,18 @@ impl RuleAction {
         }
     }
 
+    /// Create an action that closes the whole position.
+    pub fn close_position() -> Self {
+        Self {
+            action_type: RuleActionType::ClosePosition,
+            size_type: PositionSizeType::PortfolioPercent,
+            size_value: 100.0,
+            stop_loss_pct: None,
+            take_profit_pct: None,
+            leverage: 1.0,
+        }
+    }
+
     /// Set stop loss percentage.
     pub fn with_stop_loss(mut self, pct: f64) -> Self {
         self.stop_loss_pct = Some(pct);
@@ -3041,6 +3519,21 @@ impl TradingStrategy {
     }
 }
 
+/// Request to create a strategy from a ready-made template.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StrategyFromTemplateRequest {
+    /// Template ID, e.g. "rsi_mean_reversion"
+    pub template_id: String,
+    /// Portfolio the strategy trades in
+    pub portfolio_id: String,
+    /// Symbols the strategy applies to
+    pub symbols: Vec<String>,
+    /// Activate immediately instead of creating the strategy paused
+    #[serde(default)]
+    pub activate: bool,
+}
+
 /// Result of a strategy evaluation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -3125,6 +3618,12 @@ pub struct BacktestConfig {
     pub enable_margin: bool,
     /// Enable Monte Carlo simulation
     pub monte_carlo_runs: Option<u32>,
+    /// How Monte Carlo runs reorder the realized trades
+    #[serde(default)]
+    pub monte_carlo_method: MonteCarloMethod,
+    /// Seed for reproducible Monte Carlo runs (random if unset)
+    #[serde(default)]
+    pub monte_carlo_seed: Option<u64>,
 }
 
 impl BacktestConfig {
@@ -3142,6 +3641,8 @@ impl BacktestConfig {
             candle_interval: 300, // 5 minute candles
             enable_margin: false,
             monte_carlo_runs: None,
+            monte_carlo_method: MonteCarloMethod::default(),
+            monte_carlo_seed: None,
         }
     }
 
@@ -3275,7 +3776,7 @@ impl BacktestTrade {
 }
 
 /// A point on the equity curve.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct EquityPoint {
     /// Timestamp (ms)
@@ -3424,12 +3925,41 @@ pub struct BuyAndHoldComparison {
     pub bnh_sharpe: f64,
 }
 
+/// How a Monte Carlo run builds its trade sequence from the realized trades.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MonteCarloMethod {
+    /// Resample trades with replacement (bootstrap)
+    #[default]
+    Bootstrap,
+    /// Reorder the same trades (final equity is fixed, drawdowns vary)
+    Shuffle,
+}
+
 /// Monte Carlo simulation results.
+///
+/// Each run compounds the realized per-trade returns (P&L over equity at
+/// entry) in a resampled or shuffled order.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MonteCarloResults {
     /// Number of simulation runs
     pub num_runs: u32,
+    /// Sampling method used
+    #[serde(default)]
+    pub method: MonteCarloMethod,
+    /// Mean final equity across runs
+    #[serde(default)]
+    pub final_equity_mean: f64,
+    /// 5th percentile final equity
+    #[serde(default)]
+    pub final_equity_p5: f64,
+    /// 50th percentile final equity (median)
+    #[serde(default)]
+    pub final_equity_p50: f64,
+    /// 95th percentile final equity
+    #[serde(default)]
+    pub final_equity_p95: f64,
     /// 5th percentile return
     pub return_p5: f64,
     /// 25th percentile return
@@ -3442,8 +3972,14 @@ pub struct MonteCarloResults {
     pub return_p95: f64,
     /// 5th percentile max drawdown
     pub max_dd_p5: f64,
+    /// 25th percentile max drawdown
+    #[serde(default)]
+    pub max_dd_p25: f64,
     /// 50th percentile max drawdown
     pub max_dd_p50: f64,
+    /// 75th percentile max drawdown
+    #[serde(default)]
+    pub max_dd_p75: f64,
     /// 95th percentile max drawdown (worst case)
     pub max_dd_p95: f64,
     /// Probability of profit (% of runs with positive return)
@@ -3791,6 +4327,28 @@ mod tests {
         assert_eq!(order.remaining_quantity(), 3.0);
     }
 
+    #[test]
+    fn test_order_visible_quantity() {
+        let mut order = Order::limit(
+            "p1".to_string(),
+            "BTC".to_string(),
+            AssetClass::CryptoSpot,
+            OrderSide::Buy,
+            25.0,
+            50000.0,
+        );
+        assert!(!order.is_iceberg());
+        assert_eq!(order.visible_quantity(), 25.0);
+
+        order.display_quantity = Some(10.0);
+        assert!(order.is_iceberg());
+        assert_eq!(order.visible_quantity(), 10.0);
+
+        // Last slice is whatever remains hidden
+        order.filled_quantity = 20.0;
+        assert_eq!(order.visible_quantity(), 5.0);
+    }
+
     #[test]
     fn test_order_serialization() {
         let order = Order::limit(
@@ -4076,6 +4634,8 @@ mod tests {
         assert_eq!(settings.max_open_positions, 20);
         assert_eq!(settings.risk_per_trade_pct, 0.02);
         assert_eq!(settings.portfolio_stop_pct, 0.25);
+        assert_eq!(settings.max_symbol_concentration_pct, None);
+        assert_eq!(settings.max_drawdown_pct, None);
     }
 
     #[test]
@@ -4086,11 +4646,20 @@ mod tests {
             max_open_positions: 10,
             risk_per_trade_pct: 0.01,
             portfolio_stop_pct: 0.15,
+            max_symbol_concentration_pct: Some(0.4),
+            max_drawdown_pct: Some(0.2),
         };
 
         let json = serde_json::to_string(&settings).unwrap();
         assert!(json.contains("\"maxPositionSizePct\":0.5"));
         assert!(json.contains("\"dailyLossLimitPct\":0.05"));
+        assert!(json.contains("\"maxSymbolConcentrationPct\":0.4"));
+        assert!(json.contains("\"maxDrawdownPct\":0.2"));
+
+        // Settings saved before the cap existed load without one
+        let legacy: RiskSettings = serde_json::from_str("{\"maxPositionSizePct\":0.5}").unwrap();
+        assert_eq!(legacy.max_symbol_concentration_pct, None);
+        assert_eq!(legacy.max_drawdown_pct, None);
     }
 
     // =========================================================================
@@ -4791,6 +5360,7 @@ mod tests {
         assert_eq!(IndicatorType::Rsi.to_string(), "rsi");
         assert_eq!(IndicatorType::Macd.to_string(), "macd");
         assert_eq!(IndicatorType::Bollinger.to_string(), "bollinger");
+        assert_eq!(IndicatorType::BollingerUpper.to_string(), "bollinger_upper");
     }
 
     #[test]
@@ -1,6 +1,6 @@
 use super::{
-    AggregatedPrice, GlobalMetrics, Order, OrderStatus, PeerStatus, Portfolio, Position,
-    PriceSource, SignalDirection, Trade, TradeDirection,
+    AggregatedPrice, CircuitBreakerTrip, GlobalMetrics, Order, OrderStatus, PeerStatus, Portfolio,
+    Position, PriceSource, SignalDirection, Trade, TradeDirection,
 };
 use serde::{Deserialize, Serialize};
 
@@ -51,15 +51,32 @@ pub enum ClientMessage {
     /// Subscribe to trading updates for a portfolio
     SubscribeTrading {
         portfolio_id: String,
+        /// Last `seq` seen before a reconnect; newer messages are replayed
+        #[serde(default)]
+        last_seq: Option<u64>,
     },
     /// Unsubscribe from trading updates
     UnsubscribeTrading {
         portfolio_id: String,
     },
+    /// Subscribe to notification updates for a user
+    SubscribeNotifications {
+        user_id: String,
+        /// Session token of `user_id`; the subscription is refused without one
+        #[serde(default)]
+        token: Option<String>,
+        /// Last `seq` seen before a reconnect; newer messages are replayed
+        #[serde(default)]
+        last_seq: Option<u64>,
+    },
+    /// Unsubscribe from notification updates
+    UnsubscribeNotifications {
+        user_id: String,
+    },
 }
 
 /// Outgoing WebSocket message to client.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[allow(dead_code)]
 pub enum ServerMessage {
@@ -75,6 +92,10 @@ pub enum ServerMessage {
     SignalUpdate {
         data: SignalUpdateData,
     },
+    /// A source price for a subscribed asset was rejected by the circuit breaker
+    CircuitBreakerTrip {
+        data: CircuitBreakerTrip,
+    },
     /// Real-time peer status update with latency info
     PeerUpdate {
         data: PeerUpdateData,
@@ -138,10 +159,22 @@ pub enum ServerMessage {
     LiquidationAlert {
         data: LiquidationAlertData,
     },
+    /// Confirmation of notification subscription
+    NotificationsSubscribed {
+        user_id: String,
+    },
+    /// Confirmation of notification unsubscription
+    NotificationsUnsubscribed {
+        user_id: String,
+    },
+    /// Updated unread notification count
+    UnreadCount {
+        data: UnreadCountData,
+    },
 }
 
 /// Signal update payload.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SignalUpdateData {
     pub symbol: String,
@@ -155,7 +188,7 @@ pub struct SignalUpdateData {
 }
 
 /// Seeding progress payload for chart data updates.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SeedingProgressData {
     pub symbol: String,
@@ -172,7 +205,7 @@ pub struct SeedingProgressData {
 }
 
 /// Price update payload.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PriceUpdateData {
     pub id: String,
@@ -213,7 +246,7 @@ impl From<AggregatedPrice> for PriceUpdateData {
 }
 
 /// Market update payload.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MarketUpdateData {
     pub total_market_cap: f64,
@@ -234,7 +267,7 @@ impl From<GlobalMetrics> for MarketUpdateData {
 }
 
 /// Peer status update payload for real-time server connectivity info.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PeerUpdateData {
     /// This server's ID.
@@ -252,7 +285,7 @@ pub struct PeerUpdateData {
 // =============================================================================
 
 /// Order update payload.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderUpdateData {
     /// The updated order.
@@ -264,7 +297,7 @@ pub struct OrderUpdateData {
 }
 
 /// Type of order update.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum OrderUpdateType {
     Created,
@@ -291,7 +324,7 @@ impl From<OrderStatus> for OrderUpdateType {
 }
 
 /// Position update payload.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PositionUpdateData {
     /// The updated position.
@@ -303,7 +336,7 @@ pub struct PositionUpdateData {
 }
 
 /// Type of position update.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum PositionUpdateType {
     Opened,
@@ -318,7 +351,7 @@ pub enum PositionUpdateType {
 }
 
 /// Portfolio update payload.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PortfolioUpdateData {
     /// The updated portfolio.
@@ -330,7 +363,7 @@ pub struct PortfolioUpdateData {
 }
 
 /// Type of portfolio update.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum PortfolioUpdateType {
     BalanceChanged,
@@ -340,7 +373,7 @@ pub enum PortfolioUpdateType {
 }
 
 /// Trade execution payload.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TradeExecutionData {
     /// The executed trade.
@@ -353,8 +386,20 @@ pub struct TradeExecutionData {
     pub timestamp: i64,
 }
 
+/// Unread notification count payload.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnreadCountData {
+    /// User the count belongs to.
+    pub user_id: String,
+    /// Number of unread notifications.
+    pub unread_count: usize,
+    /// Timestamp of the update.
+    pub timestamp: i64,
+}
+
 /// Margin warning payload.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MarginWarningData {
     /// Portfolio ID.
@@ -370,7 +415,7 @@ pub struct MarginWarningData {
 }
 
 /// Liquidation alert payload.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LiquidationAlertData {
     /// Portfolio ID.
@@ -514,6 +559,25 @@ mod tests {
         assert!(json.contains("\"error\":\"Invalid asset\""));
     }
 
+    #[test]
+    fn test_server_message_circuit_breaker_trip_serialization() {
+        let msg = ServerMessage::CircuitBreakerTrip {
+            data: CircuitBreakerTrip {
+                symbol: "btc".to_string(),
+                source: PriceSource::Binance,
+                price: 52000.0,
+                consensus_price: 67200.0,
+                deviation_pct: -22.6,
+                timestamp: 1700000000000,
+            },
+        };
+
+        let json = serde_json::to_string(&msg).unwrap();
+        assert!(json.contains("\"type\":\"circuit_breaker_trip\""));
+        assert!(json.contains("\"source\":\"binance\""));
+        assert!(json.contains("\"consensusPrice\":67200.0"));
+    }
+
     #[test]
     fn test_server_message_auth_response_serialization() {
         let msg = ServerMessage::AuthResponse {
@@ -806,11 +870,48 @@ mod tests {
         let json = r#"{"type":"subscribe_trading","portfolio_id":"port-123"}"#;
         let msg: ClientMessage = serde_json::from_str(json).unwrap();
 
-        if let ClientMessage::SubscribeTrading { portfolio_id } = msg {
+        if let ClientMessage::SubscribeTrading {
+            portfolio_id,
+            last_seq,
+        } = msg
+        {
             assert_eq!(portfolio_id, "port-123");
+            assert_eq!(last_seq, None);
         } else {
             panic!("Expected SubscribeTrading message");
         }
+
+        let json = r#"{"type":"subscribe_trading","portfolio_id":"port-123","last_seq":42}"#;
+        let msg: ClientMessage = serde_json::from_str(json).unwrap();
+        assert!(matches!(
+            msg,
+            ClientMessage::SubscribeTrading {
+                last_seq: Some(42),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_client_subscribe_notifications_deserialization() {
+        let json = r#"{"type":"subscribe_notifications","user_id":"abc","token":"t1"}"#;
+        let msg: ClientMessage = serde_json::from_str(json).unwrap();
+        assert!(matches!(
+            msg,
+            ClientMessage::SubscribeNotifications {
+                ref user_id,
+                token: Some(ref token),
+                last_seq: None,
+            } if user_id == "abc" && token == "t1"
+        ));
+
+        // Parses without a token so the handler can reply with an error
+        let json = r#"{"type":"subscribe_notifications","user_id":"abc"}"#;
+        let msg: ClientMessage = serde_json::from_str(json).unwrap();
+        assert!(matches!(
+            msg,
+            ClientMessage::SubscribeNotifications { token: None, .. }
+        ));
     }
 
     #[test]
@@ -1,102 +1,105 @@
 use axum::{
     extract::{
-        ws::{Message, WebSocket, WebSocketUpgrade},
-        State,
+        ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        Query, State,
     },
+    http::{header::SEC_WEBSOCKET_PROTOCOL, HeaderMap},
     response::Response,
 };
 use futures_util::{SinkExt, StreamExt};
-use tokio::sync::mpsc;
+use serde::Deserialize;
+use std::sync::Arc;
+use tokio::sync::{broadcast, mpsc};
 use tracing::{debug, error, info};
 use uuid::Uuid;
 
+use super::protocol::{EncodedMessage, WireFormat, MSGPACK_SUBPROTOCOL};
+use super::room_manager::encode;
+use crate::api::notifications::unread_count_message;
 use crate::types::{ClientMessage, ServerMessage};
 use crate::AppState;
 
+/// Query parameters for the WebSocket upgrade.
+#[derive(Debug, Default, Deserialize)]
+pub struct WsParams {
+    /// Wire format: `json` (default) or `msgpack`
+    pub format: Option<String>,
+}
+
 /// WebSocket upgrade handler.
-pub async fn ws_handler(ws: WebSocketUpgrade, State(state): State<AppState>) -> Response {
-    ws.on_upgrade(move |socket| handle_socket(socket, state))
+///
+/// Clients opt into MessagePack frames with `?format=msgpack` or the
+/// `msgpack` subprotocol.
+pub async fn ws_handler(
+    ws: WebSocketUpgrade,
+    Query(params): Query<WsParams>,
+    headers: HeaderMap,
+    State(state): State<AppState>,
+) -> Response {
+    let protocols = headers
+        .get(SEC_WEBSOCKET_PROTOCOL)
+        .and_then(|v| v.to_str().ok());
+    let format = WireFormat::negotiate(params.format.as_deref(), protocols);
+
+    ws.protocols([MSGPACK_SUBPROTOCOL])
+        .on_upgrade(move |socket| handle_socket(socket, state, format))
 }
 
-async fn handle_socket(socket: WebSocket, state: AppState) {
+async fn handle_socket(socket: WebSocket, state: AppState, format: WireFormat) {
     let (mut sender, mut receiver) = socket.split();
 
     // Create a channel for sending messages to this client
-    let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+    let (tx, mut rx) = mpsc::unbounded_channel::<Arc<EncodedMessage>>();
 
     // Register the client
     let client_id = state.room_manager.register(tx);
-    info!("WebSocket client connected: {}", client_id);
-
-    // Spawn a task to forward messages from the channel to the WebSocket
-    let send_task = tokio::spawn(async move {
-        while let Some(msg) = rx.recv().await {
-            if sender.send(Message::Text(msg)).await.is_err() {
-                break;
-            }
-        }
-    });
-
-    // Clone state for the broadcast task
-    let state_clone = state.clone();
-    let room_manager = state.room_manager.clone();
-
-    // Spawn a task to broadcast price updates to subscribed clients
-    let mut price_rx = state.coordinator.subscribe();
-    let broadcast_client_id = client_id;
-    let broadcast_room_manager = room_manager.clone();
-
-    // Clone chart_store for broadcast task to calculate change_24h
-    let broadcast_chart_store = state.chart_store.clone();
-
-    let broadcast_task = tokio::spawn(async move {
-        while let Ok(mut price_update) = price_rx.recv().await {
-            let symbol = price_update.symbol.clone();
-
-            // Check if this client is subscribed to this asset
-            if let Some(client) = broadcast_room_manager.clients.get(&broadcast_client_id) {
-                if !client.assets.contains(&symbol) {
-                    continue;
-                }
-            } else {
-                break;
-            }
+    info!("WebSocket client connected: {} ({:?})", client_id, format);
 
-            // Check throttling - skip if we shouldn't send yet
-            if !broadcast_room_manager
-                .should_send_update(broadcast_client_id, &symbol)
-                .await
-            {
-                continue;
-            }
-
-            // Enrich with change_24h from chart store if not already set
-            if price_update.change_24h.is_none() {
-                if let Some(change) = broadcast_chart_store.get_price_change(&symbol, 86400) {
-                    price_update.change_24h = Some(change);
+    // Spawn a task to forward messages from the channel to the WebSocket,
+    // encoding them in the client's wire format. On server shutdown the
+    // client gets a "going away" close frame instead of a dropped socket.
+    let send_shutdown = state.shutdown.clone();
+    let mut send_task = tokio::spawn(async move {
+        loop {
+            let encoded = tokio::select! {
+                encoded = rx.recv() => match encoded {
+                    Some(encoded) => encoded,
+                    None => break,
+                },
+                _ = send_shutdown.wait() => {
+                    let _ = sender
+                        .send(Message::Close(Some(CloseFrame {
+                            code: close_code::AWAY,
+                            reason: "server shutting down".into(),
+                        })))
+                        .await;
+                    break;
                 }
-            }
-
-            let msg = ServerMessage::PriceUpdate {
-                data: price_update.into(),
             };
-
-            if let Ok(json) = serde_json::to_string(&msg) {
-                // Send directly to this client instead of broadcasting
-                if let Some(client) = broadcast_room_manager.clients.get(&broadcast_client_id) {
-                    let _ = client.tx.send(json);
-                }
+            if sender.send(format.frame(&encoded)).await.is_err() {
+                break;
             }
         }
     });
 
     // Handle incoming messages
-    while let Some(result) = receiver.next().await {
+    loop {
+        let result = tokio::select! {
+            next = receiver.next() => match next {
+                Some(result) => result,
+                None => break,
+            },
+            _ = state.shutdown.wait() => break,
+        };
         match result {
             Ok(Message::Text(text)) => {
                 debug!("Received message from {}: {}", client_id, text);
-                handle_message(&state_clone, client_id, &text).await;
+                handle_message(&state, client_id, &text).await;
             }
+            Ok(Message::Binary(bytes)) => match format.decode_binary(&bytes) {
+                Ok(msg) => handle_client_message(&state, client_id, msg).await,
+                Err(e) => send_error(&state, client_id, &format!("Invalid message: {}", e)),
+            },
             Ok(Message::Close(_)) => {
                 info!("WebSocket client disconnecting: {}", client_id);
                 break;
@@ -115,20 +118,77 @@ async fn handle_socket(socket: WebSocket, state: AppState) {
 
     // Clean up
     state.room_manager.unregister(client_id);
+    if state.shutdown.is_triggered() {
+        // Give the send task a moment to deliver its close frame
+        let _ = tokio::time::timeout(std::time::Duration::from_secs(1), &mut send_task).await;
+    }
     send_task.abort();
-    broadcast_task.abort();
     info!("WebSocket client disconnected: {}", client_id);
 }
 
-async fn handle_message(state: &AppState, client_id: Uuid, text: &str) {
-    let msg: ClientMessage = match serde_json::from_str(text) {
-        Ok(m) => m,
-        Err(e) => {
-            send_error(state, client_id, &format!("Invalid message: {}", e));
-            return;
+/// Forward aggregated price updates to the clients subscribed to each asset.
+///
+/// Each update is serialized once and the buffer shared by every recipient,
+/// rather than once per connected client.
+pub async fn broadcast_prices(state: AppState) {
+    let mut price_rx = state.coordinator.subscribe();
+    loop {
+        let mut price_update = match price_rx.recv().await {
+            Ok(update) => update,
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                debug!("Price broadcast lagged, skipped {} updates", skipped);
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        let symbol = price_update.symbol.clone();
+
+        // Enrich with change_24h from chart store if not already set
+        if price_update.change_24h.is_none() {
+            if let Some(change) = state.chart_store.get_price_change(&symbol, 86400) {
+                price_update.change_24h = Some(change);
+            }
         }
-    };
 
+        let msg = ServerMessage::PriceUpdate {
+            data: price_update.into(),
+        };
+        state.room_manager.broadcast_price(&symbol, &msg).await;
+    }
+}
+
+/// Tell the clients subscribed to an asset when one of its source prices is
+/// rejected by the circuit breaker. Trips are rare, so they skip throttling.
+pub async fn broadcast_breaker_trips(state: AppState) {
+    let mut trip_rx = state.price_cache.subscribe_breaker_trips();
+    loop {
+        let trip = match trip_rx.recv().await {
+            Ok(trip) => trip,
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                debug!("Circuit breaker broadcast lagged, skipped {} trips", skipped);
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        let symbol = trip.symbol.clone();
+
+        let msg = ServerMessage::CircuitBreakerTrip { data: trip };
+        if let Some(encoded) = encode(&msg) {
+            for tx in state.room_manager.get_subscribers(&symbol) {
+                let _ = tx.send(encoded.clone());
+            }
+        }
+    }
+}
+
+async fn handle_message(state: &AppState, client_id: Uuid, text: &str) {
+    match serde_json::from_str(text) {
+        Ok(msg) => handle_client_message(state, client_id, msg).await,
+        Err(e) => send_error(state, client_id, &format!("Invalid message: {}", e)),
+    }
+}
+
+async fn handle_client_message(state: &AppState, client_id: Uuid, msg: ClientMessage) {
     match msg {
         ClientMessage::Subscribe { assets } => {
             let subscribed = state.room_manager.subscribe(client_id, &assets);
@@ -177,9 +237,10 @@ async fn handle_message(state: &AppState, client_id: Uuid, text: &str) {
             timestamp,
         } => {
             debug!("Received peer ping from {} ({})", from_id, from_region);
+            let config = state.config.load();
             let response = ServerMessage::Pong {
-                from_id: state.config.server_id.clone(),
-                from_region: state.config.server_region.clone(),
+                from_id: config.server_id.clone(),
+                from_region: config.server_region.clone(),
                 original_timestamp: timestamp,
             };
             send_message(state, client_id, &response);
@@ -213,7 +274,10 @@ async fn handle_message(state: &AppState, client_id: Uuid, text: &str) {
             debug!("Peer identified: {} ({}) v{}", id, region, version);
         }
         // Trading subscriptions
-        ClientMessage::SubscribeTrading { portfolio_id } => {
+        ClientMessage::SubscribeTrading {
+            portfolio_id,
+            last_seq,
+        } => {
             let subscribed = state.room_manager.subscribe_trading(client_id, &portfolio_id).await;
             if subscribed {
                 debug!("Client {} subscribed to trading for portfolio {}", client_id, portfolio_id);
@@ -221,6 +285,13 @@ async fn handle_message(state: &AppState, client_id: Uuid, text: &str) {
                     portfolio_id: portfolio_id.clone(),
                 };
                 send_message(state, client_id, &response);
+
+                // Catch up on anything missed while disconnected
+                if let Some(last_seq) = last_seq {
+                    state
+                        .room_manager
+                        .replay_trading(client_id, &portfolio_id, last_seq);
+                }
             } else {
                 send_error(state, client_id, &format!("Failed to subscribe to portfolio {}", portfolio_id));
             }
@@ -237,13 +308,62 @@ async fn handle_message(state: &AppState, client_id: Uuid, text: &str) {
                 send_error(state, client_id, &format!("Not subscribed to portfolio {}", portfolio_id));
             }
         }
+        // Notification subscriptions
+        ClientMessage::SubscribeNotifications {
+            user_id,
+            token,
+            last_seq,
+        } => {
+            // Notifications are private, so the session must belong to the user
+            let authorized = match token {
+                Some(token) => state
+                    .auth_service
+                    .validate_session(&token)
+                    .await
+                    .is_some_and(|(_, profile)| profile.public_key == user_id),
+                None => false,
+            };
+            if !authorized {
+                send_error(state, client_id, "A valid session token for this user is required");
+                return;
+            }
+
+            if state.room_manager.subscribe_notifications(client_id, &user_id) {
+                debug!("Client {} subscribed to notifications", client_id);
+                let response = ServerMessage::NotificationsSubscribed {
+                    user_id: user_id.clone(),
+                };
+                send_message(state, client_id, &response);
+
+                // Send the current count so the client starts in sync
+                let unread_count = state.sqlite_store.unread_notification_count(&user_id);
+                send_message(state, client_id, &unread_count_message(&user_id, unread_count));
+
+                if let Some(last_seq) = last_seq {
+                    state
+                        .room_manager
+                        .replay_notifications(client_id, &user_id, last_seq);
+                }
+            } else {
+                send_error(state, client_id, "Failed to subscribe to notifications");
+            }
+        }
+        ClientMessage::UnsubscribeNotifications { user_id } => {
+            if state.room_manager.unsubscribe_notifications(client_id, &user_id) {
+                debug!("Client {} unsubscribed from notifications", client_id);
+                let response = ServerMessage::NotificationsUnsubscribed { user_id };
+                send_message(state, client_id, &response);
+            } else {
+                send_error(state, client_id, "Not subscribed to notifications");
+            }
+        }
     }
 }
 
 fn send_message(state: &AppState, client_id: Uuid, msg: &ServerMessage) {
-    if let Ok(json) = serde_json::to_string(msg) {
+    if let Some(encoded) = encode(msg) {
         if let Some(client) = state.room_manager.clients.get(&client_id) {
-            let _ = client.tx.send(json);
+            let _ = client.tx.send(encoded);
         }
     }
 }
@@ -1,4 +1,5 @@
 pub mod handler;
+pub mod protocol;
 pub mod room_manager;
 
 pub use handler::ws_handler;
@@ -0,0 +1,196 @@
+//! WebSocket wire formats.
+//!
+//! Clients get JSON text frames by default. Clients that ask for MessagePack
+//! (`?format=msgpack` or the `msgpack` subprotocol) get binary frames with the
+//! same message schema: maps with the same field names and `type` tag.
+//!
+//! Broadcasts are encoded once into an [`EncodedMessage`] holding both forms,
+//! and each connection picks the one for its format just before hitting the
+//! socket, so every broadcast path works for both formats.
+
+use std::sync::Arc;
+
+use axum::extract::ws::Message;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::types::ClientMessage;
+
+/// WebSocket subprotocol name for MessagePack.
+pub const MSGPACK_SUBPROTOCOL: &str = "msgpack";
+
+/// A server message encoded in every wire format, shared by all the
+/// connections it is sent to.
+#[derive(Debug)]
+pub struct EncodedMessage {
+    json: String,
+    msgpack: Vec<u8>,
+}
+
+impl EncodedMessage {
+    /// Encode a message in both formats. Failures are logged.
+    pub fn new<T: Serialize>(msg: &T) -> Option<Arc<Self>> {
+        let json = serde_json::to_string(msg)
+            .map_err(|e| warn!("Failed to encode WebSocket message as JSON: {}", e))
+            .ok()?;
+        let msgpack = rmp_serde::to_vec_named(msg)
+            .map_err(|e| warn!("Failed to encode WebSocket message as MessagePack: {}", e))
+            .ok()?;
+        Some(Arc::new(Self { json, msgpack }))
+    }
+
+    /// Encode a message that is already JSON.
+    pub fn from_json(json: &str) -> Option<Arc<Self>> {
+        let value: serde_json::Value = serde_json::from_str(json)
+            .map_err(|e| warn!("Dropping invalid JSON WebSocket message: {}", e))
+            .ok()?;
+        let msgpack = rmp_serde::to_vec_named(&value)
+            .map_err(|e| warn!("Failed to encode WebSocket message as MessagePack: {}", e))
+            .ok()?;
+        Some(Arc::new(Self {
+            json: json.to_string(),
+            msgpack,
+        }))
+    }
+
+    /// The JSON encoding.
+    pub fn json(&self) -> &str {
+        &self.json
+    }
+
+    /// The MessagePack encoding.
+    pub fn msgpack(&self) -> &[u8] {
+        &self.msgpack
+    }
+}
+
+/// Encoding used for a connection's messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WireFormat {
+    /// JSON text frames
+    #[default]
+    Json,
+    /// MessagePack binary frames
+    MessagePack,
+}
+
+impl WireFormat {
+    /// Pick the format from the `format` query parameter or the
+    /// `Sec-WebSocket-Protocol` header, falling back to JSON.
+    pub fn negotiate(format: Option<&str>, protocols: Option<&str>) -> Self {
+        let wants_msgpack = format.is_some_and(|f| {
+            f.eq_ignore_ascii_case("msgpack") || f.eq_ignore_ascii_case("messagepack")
+        }) || protocols.is_some_and(|header| {
+            header
+                .split(',')
+                .any(|p| p.trim().eq_ignore_ascii_case(MSGPACK_SUBPROTOCOL))
+        });
+
+        if wants_msgpack {
+            WireFormat::MessagePack
+        } else {
+            WireFormat::Json
+        }
+    }
+
+    /// Frame an already encoded message for the socket.
+    pub fn frame(&self, msg: &EncodedMessage) -> Message {
+        match self {
+            WireFormat::Json => Message::Text(msg.json.clone()),
+            WireFormat::MessagePack => Message::Binary(msg.msgpack.clone()),
+        }
+    }
+
+    /// Decode a client message from a binary frame.
+    pub fn decode_binary(&self, bytes: &[u8]) -> Result<ClientMessage, String> {
+        match self {
+            WireFormat::Json => serde_json::from_slice(bytes).map_err(|e| e.to_string()),
+            WireFormat::MessagePack => rmp_serde::from_slice(bytes).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ServerMessage, UnreadCountData};
+
+    fn sample_message() -> ServerMessage {
+        ServerMessage::UnreadCount {
+            data: UnreadCountData {
+                user_id: "abc".to_string(),
+                unread_count: 3,
+                timestamp: 1_700_000_000_000,
+            },
+        }
+    }
+
+    fn binary(message: Option<Message>) -> Vec<u8> {
+        match message {
+            Some(Message::Binary(bytes)) => bytes,
+            other => panic!("expected binary frame, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(WireFormat::negotiate(None, None), WireFormat::Json);
+        assert_eq!(
+            WireFormat::negotiate(Some("msgpack"), None),
+            WireFormat::MessagePack
+        );
+        assert_eq!(
+            WireFormat::negotiate(None, Some("json, msgpack")),
+            WireFormat::MessagePack
+        );
+        assert_eq!(
+            WireFormat::negotiate(Some("json"), Some("graphql-ws")),
+            WireFormat::Json
+        );
+    }
+
+    fn assert_sample(msg: ServerMessage) {
+        match msg {
+            ServerMessage::UnreadCount { data } => {
+                assert_eq!(data.user_id, "abc");
+                assert_eq!(data.unread_count, 3);
+                assert_eq!(data.timestamp, 1_700_000_000_000);
+            }
+            other => panic!("unexpected message {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_msgpack_round_trip_matches_json_schema() {
+        let msg = sample_message();
+
+        let encoded = EncodedMessage::new(&msg).unwrap();
+        let bytes = binary(Some(WireFormat::MessagePack.frame(&encoded)));
+        assert_sample(rmp_serde::from_slice(&bytes).unwrap());
+        // Same field names and tag as the JSON schema
+        let fields: serde_json::Value = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(fields, serde_json::to_value(&msg).unwrap());
+        assert_eq!(bytes, rmp_serde::to_vec_named(&msg).unwrap());
+
+        match WireFormat::Json.frame(&encoded) {
+            Message::Text(text) => assert_sample(serde_json::from_str(&text).unwrap()),
+            other => panic!("expected text frame, got {:?}", other),
+        }
+
+        // Pre-encoded JSON broadcasts decode the same way
+        let from_json = EncodedMessage::from_json(encoded.json()).unwrap();
+        assert_sample(rmp_serde::from_slice(from_json.msgpack()).unwrap());
+        assert!(EncodedMessage::from_json("not json").is_none());
+    }
+
+    #[test]
+    fn test_decode_binary_client_message() {
+        let msg = serde_json::json!({ "type": "subscribe", "assets": ["btc"] });
+        let bytes = rmp_serde::to_vec_named(&msg).unwrap();
+        match WireFormat::MessagePack.decode_binary(&bytes).unwrap() {
+            ClientMessage::Subscribe { assets } => assert_eq!(assets, vec!["btc"]),
+            other => panic!("unexpected message {:?}", other),
+        }
+        assert!(WireFormat::MessagePack.decode_binary(b"\xc1").is_err());
+    }
+}
@@ -1,19 +1,38 @@
 use dashmap::DashMap;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
+use super::protocol::EncodedMessage;
+
+/// Number of recent messages kept per room for replay after a reconnect.
+const REPLAY_BUFFER_SIZE: usize = 256;
+
+/// How long a room's replay buffer is kept after its last message.
+const REPLAY_BUFFER_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Channel carrying encoded messages to one client. Messages are shared
+/// buffers so a broadcast is serialized once, however many clients receive it.
+pub type ClientSender = mpsc::UnboundedSender<Arc<EncodedMessage>>;
+
+/// Serialize a message once for sending to any number of clients.
+pub fn encode<T: Serialize>(msg: &T) -> Option<Arc<EncodedMessage>> {
+    EncodedMessage::new(msg)
+}
+
 /// A client's subscription information.
 pub struct ClientSubscription {
     /// Subscribed asset symbols.
     pub assets: HashSet<String>,
     /// Channel to send messages to the client.
-    pub tx: mpsc::UnboundedSender<String>,
+    pub tx: ClientSender,
     /// Throttle interval in milliseconds (0 = no throttling).
     pub throttle_ms: AtomicU64,
     /// Last update time per symbol for throttling.
@@ -22,6 +41,34 @@ pub struct ClientSubscription {
     pub subscribed_to_peers: std::sync::atomic::AtomicBool,
     /// Subscribed trading portfolio IDs.
     pub trading_portfolios: RwLock<HashSet<String>>,
+    /// Newest sequence number in each replay room when the client joined it.
+    /// Later messages reach the client live, so a replay stops there.
+    pub replay_cutoffs: DashMap<String, u64>,
+}
+
+/// Recent sequenced messages for one trading/notification room.
+struct ReplayBuffer {
+    /// Messages oldest first.
+    messages: VecDeque<(u64, Arc<EncodedMessage>)>,
+    /// When the newest message was added.
+    last_recorded: Instant,
+}
+
+impl Default for ReplayBuffer {
+    fn default() -> Self {
+        Self {
+            messages: VecDeque::new(),
+            last_recorded: Instant::now(),
+        }
+    }
+}
+
+/// A replayable message with its sequence number added beside its own fields.
+#[derive(Serialize)]
+struct Sequenced<'a, T> {
+    #[serde(flatten)]
+    message: &'a T,
+    seq: u64,
 }
 
 /// Manages WebSocket client subscriptions.
@@ -32,6 +79,13 @@ pub struct RoomManager {
     rooms: DashMap<String, HashSet<Uuid>>,
     /// Trading rooms: portfolio_id -> set of client IDs.
     trading_rooms: DashMap<String, HashSet<Uuid>>,
+    /// Notification rooms: user_id -> set of client IDs.
+    notification_rooms: DashMap<String, HashSet<Uuid>>,
+    /// Last sequence number assigned to a replayable message.
+    sequence: AtomicU64,
+    /// Recent sequenced messages per trading/notification room, oldest first.
+    /// Price ticks are not buffered; clients only need the latest price.
+    replay_buffers: DashMap<String, ReplayBuffer>,
 }
 
 impl RoomManager {
@@ -41,11 +95,14 @@ impl RoomManager {
             clients: DashMap::new(),
             rooms: DashMap::new(),
             trading_rooms: DashMap::new(),
+            notification_rooms: DashMap::new(),
+            sequence: AtomicU64::new(0),
+            replay_buffers: DashMap::new(),
         })
     }
 
     /// Register a new client.
-    pub fn register(&self, tx: mpsc::UnboundedSender<String>) -> Uuid {
+    pub fn register(&self, tx: ClientSender) -> Uuid {
         let client_id = Uuid::new_v4();
         self.clients.insert(
             client_id,
@@ -56,6 +113,7 @@ impl RoomManager {
                 last_updates: RwLock::new(HashMap::new()),
                 subscribed_to_peers: std::sync::atomic::AtomicBool::new(false),
                 trading_portfolios: RwLock::new(HashSet::new()),
+                replay_cutoffs: DashMap::new(),
             },
         );
         client_id
@@ -97,7 +155,7 @@ impl RoomManager {
     }
 
     /// Get all clients subscribed to peer updates.
-    pub fn get_peer_subscribers(&self) -> Vec<mpsc::UnboundedSender<String>> {
+    pub fn get_peer_subscribers(&self) -> Vec<ClientSender> {
         self.clients
             .iter()
             .filter(|c| {
@@ -108,20 +166,32 @@ impl RoomManager {
             .collect()
     }
 
+    /// Send a message to every client subscribed to peer updates.
+    /// Returns the number of clients it was sent to.
+    pub fn broadcast_peers<T: Serialize>(&self, msg: &T) -> usize {
+        fan_out(msg, self.get_peer_subscribers())
+    }
+
     /// Subscribe a client to trading updates for a portfolio.
     pub async fn subscribe_trading(&self, client_id: Uuid, portfolio_id: &str) -> bool {
-        if let Some(client) = self.clients.get(&client_id) {
-            let mut portfolios = client.trading_portfolios.write().await;
-            if portfolios.insert(portfolio_id.to_string()) {
-                // Add to trading room
+        let inserted = match self.clients.get(&client_id) {
+            Some(client) => client
+                .trading_portfolios
+                .write()
+                .await
+                .insert(portfolio_id.to_string()),
+            None => false,
+        };
+        if inserted {
+            // Add to trading room
+            self.join_replay_room(client_id, &trading_room(portfolio_id), || {
                 self.trading_rooms
                     .entry(portfolio_id.to_string())
                     .or_default()
                     .insert(client_id);
-                return true;
-            }
+            });
         }
-        false
+        inserted
     }
 
     /// Unsubscribe a client from trading updates for a portfolio.
@@ -140,7 +210,7 @@ impl RoomManager {
     }
 
     /// Get all clients subscribed to a portfolio's trading updates.
-    pub fn get_trading_subscribers(&self, portfolio_id: &str) -> Vec<mpsc::UnboundedSender<String>> {
+    pub fn get_trading_subscribers(&self, portfolio_id: &str) -> Vec<ClientSender> {
         let client_ids: Vec<Uuid> = self
             .trading_rooms
             .get(portfolio_id)
@@ -154,11 +224,133 @@ impl RoomManager {
     }
 
     /// Broadcast a trading update to all clients subscribed to a portfolio.
-    pub fn broadcast_trading(&self, portfolio_id: &str, message: &str) {
-        let senders = self.get_trading_subscribers(portfolio_id);
-        for tx in senders {
-            let _ = tx.send(message.to_string());
+    ///
+    /// The message is serialized with a `seq` field and kept for replay.
+    pub fn broadcast_trading<T: Serialize>(&self, portfolio_id: &str, msg: &T) {
+        self.broadcast_sequenced(&trading_room(portfolio_id), msg, || {
+            self.trading_rooms
+                .get(portfolio_id)
+                .map(|room| room.iter().copied().collect())
+                .unwrap_or_default()
+        });
+    }
+
+    /// Resend trading updates for a portfolio newer than `last_seq` to a client.
+    /// Returns the number of messages replayed.
+    pub fn replay_trading(&self, client_id: Uuid, portfolio_id: &str, last_seq: u64) -> usize {
+        self.replay(client_id, &trading_room(portfolio_id), last_seq)
+    }
+
+    /// Subscribe a client to notification updates for a user.
+    pub fn subscribe_notifications(&self, client_id: Uuid, user_id: &str) -> bool {
+        if !self.clients.contains_key(&client_id) {
+            return false;
         }
+        self.join_replay_room(client_id, &notification_room(user_id), || {
+            self.notification_rooms
+                .entry(user_id.to_string())
+                .or_default()
+                .insert(client_id);
+        });
+        true
+    }
+
+    /// Unsubscribe a client from notification updates for a user.
+    pub fn unsubscribe_notifications(&self, client_id: Uuid, user_id: &str) -> bool {
+        self.notification_rooms
+            .get_mut(user_id)
+            .map(|mut room| room.remove(&client_id))
+            .unwrap_or(false)
+    }
+
+    /// Broadcast a notification update to all clients subscribed to a user.
+    ///
+    /// The message is serialized with a `seq` field and kept for replay.
+    pub fn broadcast_notifications<T: Serialize>(&self, user_id: &str, msg: &T) {
+        self.broadcast_sequenced(&notification_room(user_id), msg, || {
+            self.notification_rooms
+                .get(user_id)
+                .map(|room| room.iter().copied().collect())
+                .unwrap_or_default()
+        });
+    }
+
+    /// Resend notification updates for a user newer than `last_seq` to a client.
+    /// Returns the number of messages replayed.
+    pub fn replay_notifications(&self, client_id: Uuid, user_id: &str, last_seq: u64) -> usize {
+        self.replay(client_id, &notification_room(user_id), last_seq)
+    }
+
+    /// Add a client to a replayable room and record where its replay stops.
+    ///
+    /// Runs under the room's buffer lock, so every message is either sent to
+    /// the client live or falls at or before its cutoff, never both.
+    fn join_replay_room(&self, client_id: Uuid, room: &str, join: impl FnOnce()) {
+        let buffer = self.replay_buffers.entry(room.to_string()).or_default();
+        join();
+        let cutoff = buffer.messages.back().map(|(seq, _)| *seq).unwrap_or(0);
+        if let Some(client) = self.clients.get(&client_id) {
+            client.replay_cutoffs.insert(room.to_string(), cutoff);
+        }
+    }
+
+    /// Assign the next sequence number to a message, add it to a room's
+    /// replay buffer and send it to the room's current members.
+    ///
+    /// Membership is read under the room's buffer lock so it cannot race a
+    /// client joining; see [`RoomManager::join_replay_room`].
+    fn broadcast_sequenced<T: Serialize>(
+        &self,
+        room: &str,
+        msg: &T,
+        members: impl FnOnce() -> Vec<Uuid>,
+    ) {
+        let mut buffer = self.replay_buffers.entry(room.to_string()).or_default();
+
+        let seq = self.sequence.fetch_add(1, Ordering::Relaxed) + 1;
+        let Some(message) = encode(&Sequenced { message: msg, seq }) else {
+            return;
+        };
+
+        if buffer.messages.len() == REPLAY_BUFFER_SIZE {
+            buffer.messages.pop_front();
+        }
+        buffer.messages.push_back((seq, message.clone()));
+        buffer.last_recorded = Instant::now();
+
+        for id in members() {
+            if let Some(client) = self.clients.get(&id) {
+                let _ = client.tx.send(message.clone());
+            }
+        }
+    }
+
+    /// Send a client every buffered message in a room newer than `last_seq`
+    /// that it did not already receive live. Returns the number of messages sent.
+    fn replay(&self, client_id: Uuid, room: &str, last_seq: u64) -> usize {
+        let Some(buffer) = self.replay_buffers.get(room) else {
+            return 0;
+        };
+        let Some(client) = self.clients.get(&client_id) else {
+            return 0;
+        };
+        // A client that never joined the room has received nothing live
+        let cutoff = client.replay_cutoffs.get(room).map_or(u64::MAX, |c| *c);
+        buffer
+            .messages
+            .iter()
+            .filter(|(seq, _)| *seq > last_seq && *seq <= cutoff)
+            .filter(|(_, message)| client.tx.send(message.clone()).is_ok())
+            .count()
+    }
+
+    /// Drop replay buffers that have had no messages for [`REPLAY_BUFFER_TTL`].
+    /// Returns the number of buffers removed.
+    pub fn prune_replay_buffers(&self) -> usize {
+        let before = self.replay_buffers.len();
+        self.replay_buffers
+            .retain(|_, buffer| buffer.last_recorded.elapsed() < REPLAY_BUFFER_TTL);
+        before.saturating_sub(self.replay_buffers.len())
     }
 
     /// Set throttle interval for a client.
@@ -225,6 +417,9 @@ impl RoomManager {
             for mut room in self.trading_rooms.iter_mut() {
                 room.remove(&client_id);
             }
+            for mut room in self.notification_rooms.iter_mut() {
+                room.remove(&client_id);
+            }
         }
     }
 
@@ -269,33 +464,54 @@ impl RoomManager {
     }
 
     /// Get all clients subscribed to an asset.
-    pub fn get_subscribers(&self, asset: &str) -> Vec<mpsc::UnboundedSender<String>> {
-        let asset_lower = asset.to_lowercase();
-
-        let client_ids: Vec<Uuid> = self
-            .rooms
-            .get(&asset_lower)
-            .map(|room| room.iter().copied().collect())
-            .unwrap_or_default();
-
-        client_ids
+    pub fn get_subscribers(&self, asset: &str) -> Vec<ClientSender> {
+        self.room_members(asset)
             .iter()
             .filter_map(|id| self.clients.get(id).map(|c| c.tx.clone()))
             .collect()
     }
 
+    /// IDs of the clients in an asset's room.
+    fn room_members(&self, asset: &str) -> Vec<Uuid> {
+        self.rooms
+            .get(&asset.to_lowercase())
+            .map(|room| room.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
     /// Broadcast a message to all clients subscribed to an asset.
     pub fn broadcast(&self, asset: &str, message: &str) {
-        let senders = self.get_subscribers(asset);
-        for tx in senders {
-            let _ = tx.send(message.to_string());
+        let Some(message) = EncodedMessage::from_json(message) else {
+            return;
+        };
+        for tx in self.get_subscribers(asset) {
+            let _ = tx.send(message.clone());
+        }
+    }
+
+    /// Send a price update to every client subscribed to its asset whose
+    /// throttle interval has elapsed. Returns the number of clients it was
+    /// sent to.
+    pub async fn broadcast_price<T: Serialize>(&self, asset: &str, msg: &T) -> usize {
+        let mut senders = Vec::new();
+        for client_id in self.room_members(asset) {
+            if !self.should_send_update(client_id, asset).await {
+                continue;
+            }
+            if let Some(client) = self.clients.get(&client_id) {
+                senders.push(client.tx.clone());
+            }
         }
+        fan_out(msg, senders)
     }
 
     /// Broadcast a message to all connected clients.
     pub fn broadcast_all(&self, message: &str) {
+        let Some(message) = EncodedMessage::from_json(message) else {
+            return;
+        };
         for client in self.clients.iter() {
-            let _ = client.tx.send(message.to_string());
+            let _ = client.tx.send(message.clone());
         }
     }
 
@@ -309,6 +525,32 @@ impl RoomManager {
         self.rooms.iter().filter(|r| !r.is_empty()).count()
     }
 
+    /// Get the number of subscribed clients per asset room.
+    /// Rooms with no subscribers are omitted.
+    pub fn subscriber_counts(&self) -> HashMap<String, usize> {
+        self.rooms
+            .iter()
+            .filter(|r| !r.is_empty())
+            .map(|r| (r.key().clone(), r.len()))
+            .collect()
+    }
+
+    /// Unregister clients whose connection has gone away without a clean
+    /// disconnect. Returns the number of clients removed.
+    pub fn reap_dead_connections(&self) -> usize {
+        let dead: Vec<Uuid> = self
+            .clients
+            .iter()
+            .filter(|c| c.tx.is_closed())
+            .map(|c| *c.key())
+            .collect();
+
+        for client_id in &dead {
+            self.unregister(*client_id);
+        }
+        dead.len()
+    }
+
     /// Get all assets that have at least one subscriber.
     pub fn active_assets(&self) -> Vec<String> {
         self.rooms
@@ -319,12 +561,241 @@ impl RoomManager {
     }
 }
 
+/// Serialize `msg` once and send the shared buffer to each sender.
+/// Returns the number of clients it was sent to.
+fn fan_out<T: Serialize>(msg: &T, senders: Vec<ClientSender>) -> usize {
+    if senders.is_empty() {
+        return 0;
+    }
+    let Some(message) = encode(msg) else {
+        return 0;
+    };
+    senders
+        .into_iter()
+        .filter(|tx| tx.send(message.clone()).is_ok())
+        .count()
+}
+
+/// Replay buffer key for a portfolio's trading room.
+fn trading_room(portfolio_id: &str) -> String {
+    format!("trading:{}", portfolio_id)
+}
+
+/// Replay buffer key for a user's notification room.
+fn notification_room(user_id: &str) -> String {
+    format!("notifications:{}", user_id)
+}
+
 impl Default for RoomManager {
     fn default() -> Self {
         Self {
             clients: DashMap::new(),
             rooms: DashMap::new(),
             trading_rooms: DashMap::new(),
+            notification_rooms: DashMap::new(),
+            sequence: AtomicU64::new(0),
+            replay_buffers: DashMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connect(manager: &RoomManager) -> (Uuid, mpsc::UnboundedReceiver<Arc<EncodedMessage>>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (manager.register(tx), rx)
+    }
+
+    fn drain(rx: &mut mpsc::UnboundedReceiver<Arc<EncodedMessage>>) -> Vec<serde_json::Value> {
+        let mut messages = Vec::new();
+        while let Ok(message) = rx.try_recv() {
+            messages.push(serde_json::from_str(message.json()).unwrap());
+        }
+        messages
+    }
+
+    fn event(name: &str) -> serde_json::Value {
+        serde_json::json!({ "type": "order_update", "data": { "event": name } })
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_replays_missed_messages() {
+        let manager = RoomManager::new();
+        let (client, mut rx) = connect(&manager);
+        manager.subscribe_trading(client, "p1").await;
+        manager.subscribe_notifications(client, "u1");
+        manager.subscribe(client, &["btc".to_string()]);
+
+        manager.broadcast_trading("p1", &event("filled"));
+        let seen = drain(&mut rx);
+        assert_eq!(seen.len(), 1);
+        let last_seq = seen[0]["seq"].as_u64().unwrap();
+
+        // Client drops; trading, notification and price traffic continues
+        manager.unregister(client);
+        manager.broadcast_trading("p1", &event("closed"));
+        manager.broadcast("btc", r#"{"type":"price_update"}"#);
+        manager.broadcast_notifications("u1", &event("alert"));
+        manager.broadcast_trading("p2", &event("other_portfolio"));
+        manager.broadcast_trading("p1", &event("liquidated"));
+
+        let (client, mut rx) = connect(&manager);
+        manager.subscribe_trading(client, "p1").await;
+        manager.subscribe_notifications(client, "u1");
+        assert_eq!(manager.replay_trading(client, "p1", last_seq), 2);
+        assert_eq!(manager.replay_notifications(client, "u1", last_seq), 1);
+
+        let replayed = drain(&mut rx);
+        let events: Vec<&str> = replayed
+            .iter()
+            .map(|m| m["data"]["event"].as_str().unwrap())
+            .collect();
+        assert_eq!(events, vec!["closed", "liquidated", "alert"]);
+        assert!(replayed.iter().all(|m| m["seq"].as_u64().unwrap() > last_seq));
+
+        // Fully caught up: nothing more to replay
+        let newest = replayed[1]["seq"].as_u64().unwrap();
+        assert_eq!(manager.replay_trading(client, "p1", newest), 0);
+    }
+
+    #[test]
+    fn test_subscriber_counts_track_disconnects() {
+        let manager = RoomManager::new();
+        let (a, _rx_a) = connect(&manager);
+        let (b, rx_b) = connect(&manager);
+        let (c, _rx_c) = connect(&manager);
+        manager.subscribe(a, &["BTC".to_string(), "eth".to_string()]);
+        manager.subscribe(b, &["btc".to_string()]);
+        manager.subscribe(c, &["btc".to_string(), "sol".to_string()]);
+
+        let counts = manager.subscriber_counts();
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts["btc"], 3);
+        assert_eq!(counts["eth"], 1);
+        assert_eq!(counts["sol"], 1);
+
+        // Clean disconnect
+        manager.unregister(a);
+        let counts = manager.subscriber_counts();
+        assert_eq!(counts["btc"], 2);
+        assert!(!counts.contains_key("eth"));
+
+        // Connection dropped without unregistering is reaped
+        drop(rx_b);
+        assert_eq!(manager.reap_dead_connections(), 1);
+        assert_eq!(manager.reap_dead_connections(), 0);
+        let counts = manager.subscriber_counts();
+        assert_eq!(counts["btc"], 1);
+        assert_eq!(counts["sol"], 1);
+        assert_eq!(manager.client_count(), 1);
+
+        manager.unsubscribe(c, &["sol".to_string()]);
+        let counts = manager.subscriber_counts();
+        assert_eq!(counts.len(), 1);
+        assert_eq!(manager.room_count(), 1);
+    }
+
+    #[test]
+    fn test_replay_buffer_is_bounded() {
+        let manager = RoomManager::new();
+        for i in 0..REPLAY_BUFFER_SIZE + 10 {
+            manager.broadcast_trading("p1", &event(&i.to_string()));
+        }
+
+        let (client, mut rx) = connect(&manager);
+        assert_eq!(manager.replay_trading(client, "p1", 0), REPLAY_BUFFER_SIZE);
+        let replayed = drain(&mut rx);
+        assert_eq!(replayed[0]["seq"], 11);
+        assert_eq!(replayed[0]["data"]["event"], "10");
+    }
+
+    #[tokio::test]
+    async fn test_replay_skips_messages_already_sent_live() {
+        let manager = RoomManager::new();
+        let (client, mut rx) = connect(&manager);
+        manager.broadcast_trading("p1", &event("missed"));
+
+        // A message lands between subscribing and replaying
+        manager.subscribe_trading(client, "p1").await;
+        manager.broadcast_trading("p1", &event("live"));
+        assert_eq!(manager.replay_trading(client, "p1", 0), 1);
+
+        let events: Vec<String> = drain(&mut rx)
+            .iter()
+            .map(|m| m["data"]["event"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(events, vec!["live", "missed"]);
+
+        // Messages are serialized with their seq beside the original fields
+        manager.broadcast_trading("p1", &event("next"));
+        let next = rx.try_recv().unwrap();
+        for fields in [
+            serde_json::from_str::<serde_json::Value>(next.json()).unwrap(),
+            rmp_serde::from_slice(next.msgpack()).unwrap(),
+        ] {
+            assert_eq!(fields["type"], "order_update");
+            assert_eq!(fields["seq"], 3);
+        }
+    }
+
+    #[test]
+    fn test_idle_replay_buffers_are_pruned() {
+        let manager = RoomManager::new();
+        manager.broadcast_trading("p1", &event("old"));
+        manager.broadcast_notifications("u1", &event("recent"));
+        assert_eq!(manager.prune_replay_buffers(), 0);
+
+        manager
+            .replay_buffers
+            .get_mut(&trading_room("p1"))
+            .unwrap()
+            .last_recorded -= REPLAY_BUFFER_TTL;
+        assert_eq!(manager.prune_replay_buffers(), 1);
+        assert!(!manager.replay_buffers.contains_key(&trading_room("p1")));
+        assert!(manager.replay_buffers.contains_key(&notification_room("u1")));
+    }
+
+    /// Serializes as a fixed message, counting how often it is serialized.
+    struct CountingMessage<'a>(&'a AtomicU64);
+
+    impl Serialize for CountingMessage<'_> {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            serde_json::json!({ "type": "peer_update" }).serialize(serializer)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_serializes_once_for_all_subscribers() {
+        let manager = RoomManager::new();
+        let mut receivers = Vec::new();
+        for _ in 0..5 {
+            let (client, rx) = connect(&manager);
+            manager.subscribe_peers(client);
+            manager.subscribe(client, &["btc".to_string()]);
+            receivers.push(rx);
         }
+        let (_idle, mut idle_rx) = connect(&manager);
+
+        // Once per wire format, however many clients receive it
+        let serializations = AtomicU64::new(0);
+        assert_eq!(manager.broadcast_peers(&CountingMessage(&serializations)), 5);
+        assert_eq!(serializations.load(Ordering::Relaxed), 2);
+
+        let sent: Vec<Arc<EncodedMessage>> =
+            receivers.iter_mut().map(|rx| rx.try_recv().unwrap()).collect();
+        assert!(sent.iter().all(|m| Arc::ptr_eq(m, &sent[0])));
+        assert!(idle_rx.try_recv().is_err());
+
+        let price_serializations = AtomicU64::new(0);
+        let message = CountingMessage(&price_serializations);
+        assert_eq!(manager.broadcast_price("btc", &message).await, 5);
+        assert_eq!(price_serializations.load(Ordering::Relaxed), 2);
+
+        // No subscribers: nothing is serialized
+        assert_eq!(manager.broadcast_price("eth", &message).await, 0);
+        assert_eq!(price_serializations.load(Ordering::Relaxed), 2);
     }
 }
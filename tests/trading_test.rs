@@ -1008,13 +1008,20 @@ mod backtest_tests {
     fn test_monte_carlo_results() {
         let mc = MonteCarloResults {
             num_runs: 1000,
+            method: MonteCarloMethod::Bootstrap,
+            final_equity_mean: 10_800.0,
+            final_equity_p5: 9_500.0,
+            final_equity_p50: 10_800.0,
+            final_equity_p95: 12_500.0,
             return_p5: -5.0,
             return_p25: 2.0,
             return_p50: 8.0,
             return_p75: 15.0,
             return_p95: 25.0,
             max_dd_p5: 3.0,
+            max_dd_p25: 6.0,
             max_dd_p50: 10.0,
+            max_dd_p75: 15.0,
             max_dd_p95: 25.0,
             probability_of_profit: 75.0,
             probability_of_ruin: 2.0,
@@ -1023,6 +1030,7 @@ mod backtest_tests {
         assert_eq!(mc.num_runs, 1000);
         assert!(mc.return_p50 > mc.return_p25);
         assert!(mc.probability_of_profit > mc.probability_of_ruin);
+        assert!(mc.final_equity_p5 < mc.final_equity_p95);
     }
 }
 